@@ -34,7 +34,7 @@ use mz_controller::clusters::{
     ManagedReplicaLocation, ProcessId, ReplicaAllocation, ReplicaConfig, ReplicaId,
     ReplicaLocation, ReplicaLogging, UnmanagedReplicaLocation,
 };
-use mz_expr::{MirScalarExpr, OptimizedMirRelationExpr};
+use mz_expr::{MirScalarExpr, OptimizedMirRelationExpr, OptimizerHints};
 use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
 use mz_ore::metrics::MetricsRegistry;
@@ -47,6 +47,7 @@ use mz_repr::explain::ExprHumanizer;
 use mz_repr::namespaces::{
     INFORMATION_SCHEMA, MZ_CATALOG_SCHEMA, MZ_INTERNAL_SCHEMA, MZ_TEMP_SCHEMA, PG_CATALOG_SCHEMA,
 };
+use mz_repr::refresh_schedule::RefreshSchedule;
 use mz_repr::role_id::RoleId;
 use mz_repr::{Diff, GlobalId, RelationDesc, ScalarType};
 use mz_secrets::InMemorySecretsController;
@@ -68,11 +69,11 @@ use mz_sql::names::{
     PUBLIC_ROLE_NAME,
 };
 use mz_sql::plan::{
-    CreateConnectionPlan, CreateIndexPlan, CreateMaterializedViewPlan, CreateSecretPlan,
-    CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan, CreateViewPlan,
-    Ingestion as PlanIngestion, Params, Plan, PlanContext, PlanNotice,
-    SourceSinkClusterConfig as PlanStorageClusterConfig, StatementDesc, WebhookHeaders,
-    WebhookValidation,
+    CreateConnectionPlan, CreateFunctionPlan, CreateIndexPlan, CreateMaterializedViewPlan,
+    CreateSecretPlan, CreateSequencePlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan,
+    CreateTypePlan, CreateViewPlan, Ingestion as PlanIngestion, Params, Plan, PlanContext,
+    PlanNotice, SequenceOptions, SourceSinkClusterConfig as PlanStorageClusterConfig,
+    StatementDesc, TableConstraints, WebhookHeaders, WebhookValidation,
 };
 use mz_sql::session::user::{MZ_SYSTEM_ROLE_ID, SUPPORT_USER, SYSTEM_USER};
 use mz_sql::session::vars::{
@@ -109,10 +110,11 @@ use crate::catalog::builtin::{
     Builtin, BuiltinCluster, BuiltinLog, BuiltinSource, BuiltinTable, BuiltinType, Fingerprint,
     BUILTINS, BUILTIN_PREFIXES, MZ_INTROSPECTION_CLUSTER, MZ_SYSTEM_CLUSTER,
 };
-use crate::catalog::storage::{BootstrapArgs, SystemObjectMapping, Transaction};
+use crate::catalog::storage::{BootstrapArgs, PlanFingerprint, SystemObjectMapping, Transaction};
 use crate::client::ConnectionId;
 use crate::command::CatalogDump;
 use crate::config::{SynchronizedParameters, SystemParameterFrontend, SystemParameterSyncConfig};
+use crate::coord::dataflows::eval_refresh_schedule;
 use crate::coord::{timeline, ConnMeta, TargetCluster, DEFAULT_LOGICAL_COMPACTION_WINDOW};
 use crate::session::{PreparedStatement, Session, DEFAULT_DATABASE_NAME};
 use crate::util::{index_sql, ResultExt};
@@ -316,7 +318,8 @@ impl CatalogState {
             CatalogItem::Log(_) => out.push(id),
             item @ (CatalogItem::View(_)
             | CatalogItem::MaterializedView(_)
-            | CatalogItem::Connection(_)) => {
+            | CatalogItem::Connection(_)
+            | CatalogItem::Function(_)) => {
                 for id in &item.uses().0 {
                     self.introspection_dependencies_inner(*id, out);
                 }
@@ -327,7 +330,9 @@ impl CatalogState {
             | CatalogItem::Source(_)
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
-            | CatalogItem::Secret(_) => (),
+            | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
+            | CatalogItem::Function(_) => (),
         }
     }
 
@@ -476,7 +481,11 @@ impl CatalogState {
         if !seen.contains(&object_id) {
             seen.insert(object_id.clone());
             let schema = self.get_schema(&database_spec, &schema_spec, conn_id);
-            for item_id in schema.item_ids().values() {
+            for item_id in schema
+                .item_ids()
+                .values()
+                .chain(schema.function_ids().values())
+            {
                 dependents.extend_from_slice(&self.item_dependents(*item_id, seen));
             }
             dependents.push(object_id)
@@ -512,9 +521,9 @@ impl CatalogState {
     pub fn uses_tables(&self, id: GlobalId) -> bool {
         match self.get_entry(&id).item() {
             CatalogItem::Table(_) => true,
-            item @ (CatalogItem::View(_) | CatalogItem::MaterializedView(_)) => {
-                item.uses().0.iter().any(|id| self.uses_tables(*id))
-            }
+            item @ (CatalogItem::View(_)
+            | CatalogItem::MaterializedView(_)
+            | CatalogItem::Function(_)) => item.uses().0.iter().any(|id| self.uses_tables(*id)),
             CatalogItem::Index(idx) => self.uses_tables(idx.on),
             CatalogItem::Source(_)
             | CatalogItem::Log(_)
@@ -522,6 +531,8 @@ impl CatalogState {
             | CatalogItem::Sink(_)
             | CatalogItem::Type(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
+            | CatalogItem::Function(_)
             | CatalogItem::Connection(_) => false,
         }
     }
@@ -893,10 +904,11 @@ impl CatalogState {
             conn_id,
         );
 
-        let prev_id = if let CatalogItem::Func(_) = entry.item() {
-            schema.functions.insert(entry.name.item.clone(), entry.id)
-        } else {
-            schema.items.insert(entry.name.item.clone(), entry.id)
+        let prev_id = match entry.item() {
+            CatalogItem::Func(_) | CatalogItem::Function(_) => {
+                schema.functions.insert(entry.name.item.clone(), entry.id)
+            }
+            _ => schema.items.insert(entry.name.item.clone(), entry.id),
         };
 
         assert!(
@@ -931,8 +943,11 @@ impl CatalogState {
             &metadata.name().qualifiers.schema_spec,
             conn_id,
         );
-        schema
-            .items
+        let items = match metadata.item() {
+            CatalogItem::Func(_) | CatalogItem::Function(_) => &mut schema.functions,
+            _ => &mut schema.items,
+        };
+        items
             .remove(&metadata.name().item)
             .expect("catalog out of sync");
 
@@ -1622,6 +1637,7 @@ impl CatalogState {
                     CatalogItemType::Connection => CommentObjectId::Connection(global_id),
                     CatalogItemType::Type => CommentObjectId::Type(global_id),
                     CatalogItemType::Secret => CommentObjectId::Secret(global_id),
+                    CatalogItemType::Sequence => CommentObjectId::Sequence(global_id),
                 }
             }
             ObjectId::Role(role_id) => CommentObjectId::Role(role_id),
@@ -2177,6 +2193,8 @@ pub enum CatalogItem {
     Func(Func),
     Secret(Secret),
     Connection(Connection),
+    Sequence(Sequence),
+    Function(Function),
 }
 
 impl From<CatalogEntry> for storage::Item {
@@ -2197,6 +2215,11 @@ pub struct Table {
     pub desc: RelationDesc,
     #[serde(skip)]
     pub defaults: Vec<Expr<Aug>>,
+    /// The columns whose values default to the next value of the sequence
+    /// they own.
+    pub identity_columns: Vec<usize>,
+    #[serde(skip)]
+    pub constraints: TableConstraints,
     #[serde(skip)]
     pub conn_id: Option<ConnectionId>,
     pub resolved_ids: ResolvedIds,
@@ -2353,7 +2376,10 @@ impl Source {
             desc: plan.source.desc,
             timeline: plan.timeline,
             resolved_ids,
-            custom_logical_compaction_window,
+            custom_logical_compaction_window: plan
+                .source
+                .compaction_window
+                .or(custom_logical_compaction_window),
             is_retained_metrics_object,
         }
     }
@@ -2525,6 +2551,11 @@ pub struct MaterializedView {
     pub desc: RelationDesc,
     pub resolved_ids: ResolvedIds,
     pub cluster_id: ClusterId,
+    pub custom_logical_compaction_window: Option<Duration>,
+    /// The refresh schedule configured by `REFRESH` options, if any.
+    pub refresh_schedule: Option<RefreshSchedule>,
+    /// The optimizer hints given in the `OPTIONS` of the defining query.
+    pub hints: OptimizerHints,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -2558,6 +2589,23 @@ pub struct Secret {
     pub create_sql: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct Function {
+    pub create_sql: String,
+    #[serde(skip)]
+    pub func: Arc<mz_sql::func::Func>,
+    pub resolved_ids: ResolvedIds,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Sequence {
+    pub create_sql: String,
+    pub options: SequenceOptions,
+    /// The table and column that own the sequence, if any.
+    pub owned_by: Option<(GlobalId, usize)>,
+    pub resolved_ids: ResolvedIds,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct Connection {
     pub create_sql: String,
@@ -2585,6 +2633,8 @@ impl CatalogItem {
             CatalogItem::Type(_) => mz_sql::catalog::CatalogItemType::Type,
             CatalogItem::Func(_) => mz_sql::catalog::CatalogItemType::Func,
             CatalogItem::Secret(_) => mz_sql::catalog::CatalogItemType::Secret,
+            CatalogItem::Sequence(_) => mz_sql::catalog::CatalogItemType::Sequence,
+            CatalogItem::Function(_) => mz_sql::catalog::CatalogItemType::Func,
             CatalogItem::Connection(_) => mz_sql::catalog::CatalogItemType::Connection,
         }
     }
@@ -2601,6 +2651,8 @@ impl CatalogItem {
             | CatalogItem::Sink(_)
             | CatalogItem::Type(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
+            | CatalogItem::Function(_)
             | CatalogItem::Connection(_) => Err(SqlCatalogError::InvalidDependency {
                 name: name.to_string(),
                 typ: self.typ(),
@@ -2608,12 +2660,10 @@ impl CatalogItem {
         }
     }
 
-    pub fn func(
-        &self,
-        entry: &CatalogEntry,
-    ) -> Result<&'static mz_sql::func::Func, SqlCatalogError> {
+    pub fn func(&self, entry: &CatalogEntry) -> Result<&mz_sql::func::Func, SqlCatalogError> {
         match &self {
             CatalogItem::Func(func) => Ok(func.inner),
+            CatalogItem::Function(function) => Ok(&function.func),
             _ => Err(SqlCatalogError::UnexpectedType {
                 name: entry.name().item.to_string(),
                 actual_type: entry.item_type(),
@@ -2657,6 +2707,8 @@ impl CatalogItem {
             CatalogItem::View(view) => &view.resolved_ids,
             CatalogItem::MaterializedView(mview) => &mview.resolved_ids,
             CatalogItem::Secret(_) => &*EMPTY,
+            CatalogItem::Sequence(sequence) => &sequence.resolved_ids,
+            CatalogItem::Function(function) => &function.resolved_ids,
             CatalogItem::Connection(connection) => &connection.resolved_ids,
         }
     }
@@ -2674,6 +2726,8 @@ impl CatalogItem {
             | CatalogItem::View(_)
             | CatalogItem::MaterializedView(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
+            | CatalogItem::Function(_)
             | CatalogItem::Connection(_) => false,
             CatalogItem::Sink(s) => match s.connection {
                 StorageSinkConnectionState::Pending(_) => true,
@@ -2694,6 +2748,8 @@ impl CatalogItem {
             | CatalogItem::Sink(_)
             | CatalogItem::MaterializedView(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
+            | CatalogItem::Function(_)
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Connection(_) => None,
@@ -2764,6 +2820,16 @@ impl CatalogItem {
                 i.create_sql = do_rewrite(i.create_sql)?;
                 Ok(CatalogItem::Secret(i))
             }
+            CatalogItem::Sequence(i) => {
+                let mut i = i.clone();
+                i.create_sql = do_rewrite(i.create_sql)?;
+                Ok(CatalogItem::Sequence(i))
+            }
+            CatalogItem::Function(i) => {
+                let mut i = i.clone();
+                i.create_sql = do_rewrite(i.create_sql)?;
+                Ok(CatalogItem::Function(i))
+            }
             CatalogItem::Func(_) | CatalogItem::Type(_) => {
                 unreachable!("{}s cannot be renamed", self.typ())
             }
@@ -2793,6 +2859,8 @@ impl CatalogItem {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
+            | CatalogItem::Function(_)
             | CatalogItem::Connection(_) => None,
         }
     }
@@ -2815,27 +2883,30 @@ impl CatalogItem {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
+            | CatalogItem::Function(_)
             | CatalogItem::Connection(_) => None,
         }
     }
 
     /// The custom compaction window, if any has been set.
-    // Note[btv]: As of 2023-04-10, this is only set
-    // for objects with `is_retained_metrics_object`. That
-    // may not always be true in the future, if we enable user-settable
-    // compaction windows.
+    ///
+    /// This is set either for objects with `is_retained_metrics_object`, or
+    /// for user objects created or altered with `RETAIN HISTORY`.
     pub fn custom_logical_compaction_window(&self) -> Option<Duration> {
         match self {
             CatalogItem::Table(table) => table.custom_logical_compaction_window,
             CatalogItem::Source(source) => source.custom_logical_compaction_window,
             CatalogItem::Index(index) => index.custom_logical_compaction_window,
-            CatalogItem::MaterializedView(_)
-            | CatalogItem::Log(_)
+            CatalogItem::MaterializedView(mv) => mv.custom_logical_compaction_window,
+            CatalogItem::Log(_)
             | CatalogItem::View(_)
             | CatalogItem::Sink(_)
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
+            | CatalogItem::Function(_)
             | CatalogItem::Connection(_) => None,
         }
     }
@@ -2860,6 +2931,8 @@ impl CatalogItem {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
+            | CatalogItem::Function(_)
             | CatalogItem::Connection(_) => return None,
         };
         Some(custom_logical_compaction_window.unwrap_or(DEFAULT_LOGICAL_COMPACTION_WINDOW))
@@ -2880,6 +2953,8 @@ impl CatalogItem {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
+            | CatalogItem::Function(_)
             | CatalogItem::Connection(_) => false,
         }
     }
@@ -2901,6 +2976,8 @@ impl CatalogItem {
             CatalogItem::Sink(sink) => sink.create_sql.clone(),
             CatalogItem::Type(typ) => typ.create_sql.clone(),
             CatalogItem::Secret(secret) => secret.create_sql.clone(),
+            CatalogItem::Sequence(sequence) => sequence.create_sql.clone(),
+            CatalogItem::Function(function) => function.create_sql.clone(),
             CatalogItem::Connection(connection) => connection.create_sql.clone(),
             CatalogItem::Func(_) => unreachable!("cannot serialize functions yet"),
         }
@@ -2923,6 +3000,8 @@ impl CatalogItem {
             CatalogItem::Sink(sink) => sink.create_sql,
             CatalogItem::Type(typ) => typ.create_sql,
             CatalogItem::Secret(secret) => secret.create_sql,
+            CatalogItem::Sequence(sequence) => sequence.create_sql,
+            CatalogItem::Function(function) => function.create_sql,
             CatalogItem::Connection(connection) => connection.create_sql,
             CatalogItem::Func(_) => unreachable!("cannot serialize functions yet"),
         }
@@ -2936,7 +3015,7 @@ impl CatalogEntry {
     }
 
     /// Returns the [`mz_sql::func::Func`] associated with this `CatalogEntry`.
-    pub fn func(&self) -> Result<&'static mz_sql::func::Func, SqlCatalogError> {
+    pub fn func(&self) -> Result<&mz_sql::func::Func, SqlCatalogError> {
         self.item.func(self)
     }
 
@@ -2972,6 +3051,14 @@ impl CatalogEntry {
         }
     }
 
+    /// Returns the inner [`Sequence`] if this entry is a sequence, else `None`.
+    pub fn sequence(&self) -> Option<&Sequence> {
+        match self.item() {
+            CatalogItem::Sequence(sequence) => Some(sequence),
+            _ => None,
+        }
+    }
+
     pub fn connection(&self) -> Result<&Connection, SqlCatalogError> {
         match self.item() {
             CatalogItem::Connection(connection) => Ok(connection),
@@ -3050,6 +3137,8 @@ impl CatalogEntry {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
+            | CatalogItem::Function(_)
             | CatalogItem::Connection(_) => BTreeSet::new(),
         }
     }
@@ -3073,6 +3162,8 @@ impl CatalogEntry {
             | CatalogItem::Type(_)
             | CatalogItem::Func(_)
             | CatalogItem::Secret(_)
+            | CatalogItem::Sequence(_)
+            | CatalogItem::Function(_)
             | CatalogItem::Connection(_) => None,
         }
     }
@@ -3829,6 +3920,8 @@ impl Catalog {
                                 create_sql: CREATE_SQL_TODO.to_string(),
                                 desc: table.desc.clone(),
                                 defaults: vec![Expr::null(); table.desc.arity()],
+                                identity_columns: vec![],
+                                constraints: TableConstraints::default(),
                                 conn_id: None,
                                 resolved_ids: ResolvedIds(BTreeSet::new()),
                                 custom_logical_compaction_window: table
@@ -4574,6 +4667,9 @@ impl Catalog {
             CatalogType::MzTimestamp => CatalogType::MzTimestamp,
             CatalogType::Interval => CatalogType::Interval,
             CatalogType::Jsonb => CatalogType::Jsonb,
+            CatalogType::JsonPath => CatalogType::JsonPath,
+            CatalogType::TsVector => CatalogType::TsVector,
+            CatalogType::TsQuery => CatalogType::TsQuery,
             CatalogType::Numeric => CatalogType::Numeric,
             CatalogType::Oid => CatalogType::Oid,
             CatalogType::PgLegacyChar => CatalogType::PgLegacyChar,
@@ -4587,6 +4683,9 @@ impl Catalog {
             CatalogType::Timestamp => CatalogType::Timestamp,
             CatalogType::TimestampTz => CatalogType::TimestampTz,
             CatalogType::Uuid => CatalogType::Uuid,
+            CatalogType::Inet => CatalogType::Inet,
+            CatalogType::Cidr => CatalogType::Cidr,
+            CatalogType::MacAddr => CatalogType::MacAddr,
             CatalogType::VarChar => CatalogType::VarChar,
             CatalogType::Int2Vector => CatalogType::Int2Vector,
             CatalogType::MzAclItem => CatalogType::MzAclItem,
@@ -4718,6 +4817,8 @@ impl Catalog {
                 CatalogItem::Type(_)
                 | CatalogItem::Func(_)
                 | CatalogItem::Secret(_)
+                | CatalogItem::Sequence(_)
+                | CatalogItem::Function(_)
                 | CatalogItem::Connection(_) => unreachable!(
                     "impossible to migrate schema for builtin {}",
                     entry.item().typ()
@@ -5208,6 +5309,49 @@ impl Catalog {
         self.storage().await.allocate_user_id().await
     }
 
+    /// Allocates up to `amount` values of the sequence `id`.
+    pub async fn allocate_sequence_values(
+        &self,
+        id: GlobalId,
+        options: SequenceOptions,
+        amount: u64,
+    ) -> Result<Vec<i64>, Error> {
+        self.storage()
+            .await
+            .allocate_sequence_values(id, options, amount)
+            .await
+    }
+
+    /// Sets the next value of the sequence `id` to `next`.
+    pub async fn set_sequence_value(&self, id: GlobalId, next: i64) -> Result<(), Error> {
+        self.storage().await.set_sequence_value(id, next).await
+    }
+
+    /// Loads the persisted plan fingerprints of indexes and materialized views.
+    pub async fn load_plan_fingerprints(
+        &self,
+    ) -> Result<BTreeMap<GlobalId, PlanFingerprint>, Error> {
+        self.storage().await.load_plan_fingerprints().await
+    }
+
+    /// Persists the plan fingerprints in `fingerprints` and removes those of the objects in
+    /// `removed`.
+    ///
+    /// Does nothing if the catalog is read-only.
+    pub async fn update_plan_fingerprints(
+        &self,
+        fingerprints: BTreeMap<GlobalId, PlanFingerprint>,
+        removed: BTreeSet<GlobalId>,
+    ) -> Result<(), Error> {
+        let mut storage = self.storage().await;
+        if storage.is_read_only() {
+            return Ok(());
+        }
+        storage
+            .update_plan_fingerprints(fingerprints, removed)
+            .await
+    }
+
     #[cfg(test)]
     pub async fn allocate_system_id(&self) -> Result<GlobalId, Error> {
         self.storage()
@@ -6565,6 +6709,9 @@ impl Catalog {
                             owner_id,
                             privileges.clone(),
                         )?;
+                        if let CatalogItem::Sequence(sequence) = &item {
+                            tx.insert_sequence(id, sequence.options.start)?;
+                        }
                     }
 
                     if Self::should_audit_log_item(&item) {
@@ -6905,6 +7052,9 @@ impl Catalog {
                             if !entry.item().is_temporary() {
                                 tx.remove_item(id)?;
                             }
+                            if let CatalogItem::Sequence(_) = entry.item() {
+                                tx.remove_sequence(id)?;
+                            }
 
                             builtin_table_updates.extend(state.pack_item_update(id, -1));
                             if Self::should_audit_log_item(&entry.item) {
@@ -7717,7 +7867,11 @@ impl Catalog {
             &old_entry.name().qualifiers.schema_spec,
             conn_id,
         );
-        schema.items.remove(&old_entry.name().item);
+        let items = match old_entry.item() {
+            CatalogItem::Func(_) | CatalogItem::Function(_) => &mut schema.functions,
+            _ => &mut schema.items,
+        };
+        items.remove(&old_entry.name().item);
 
         // We only need to install this item on items in the `used_by` of new
         // dependencies.
@@ -7732,7 +7886,7 @@ impl Catalog {
         new_entry.name = to_name;
         new_entry.item = to_item;
 
-        schema.items.insert(new_entry.name().item.clone(), id);
+        items.insert(new_entry.name().item.clone(), id);
 
         for u in new_deps {
             match state.entry_by_id.get_mut(&u) {
@@ -7895,9 +8049,13 @@ impl Catalog {
                 create_sql: table.create_sql,
                 desc: table.desc,
                 defaults: table.defaults,
+                identity_columns: table.identity_columns,
+                constraints: table.constraints,
                 conn_id: None,
                 resolved_ids,
-                custom_logical_compaction_window,
+                custom_logical_compaction_window: table
+                    .compaction_window
+                    .or(custom_logical_compaction_window),
                 is_retained_metrics_object,
             }),
             Plan::CreateSource(CreateSourcePlan {
@@ -7940,7 +8098,9 @@ impl Catalog {
                 desc: source.desc,
                 timeline,
                 resolved_ids,
-                custom_logical_compaction_window,
+                custom_logical_compaction_window: source
+                    .compaction_window
+                    .or(custom_logical_compaction_window),
                 is_retained_metrics_object,
             }),
             Plan::CreateView(CreateViewPlan { view, .. }) => {
@@ -7963,12 +8123,23 @@ impl Catalog {
                     Optimizer::logical_optimizer(&mz_transform::typecheck::empty_context());
                 let optimized_expr = optimizer.optimize(materialized_view.expr)?;
                 let desc = RelationDesc::new(optimized_expr.typ(), materialized_view.column_names);
+                // The refresh times have been evaluated when the materialized
+                // view was created, and recorded in its definition.
+                let refresh_schedule = materialized_view
+                    .refresh_schedule
+                    .map(|plan| eval_refresh_schedule(self.state(), plan, None))
+                    .transpose()?;
                 CatalogItem::MaterializedView(MaterializedView {
                     create_sql: materialized_view.create_sql,
                     optimized_expr,
                     desc,
                     resolved_ids,
                     cluster_id: materialized_view.cluster_id,
+                    custom_logical_compaction_window: materialized_view
+                        .compaction_window
+                        .or(custom_logical_compaction_window),
+                    refresh_schedule,
+                    hints: materialized_view.hints,
                 })
             }
             Plan::CreateIndex(CreateIndexPlan { index, .. }) => CatalogItem::Index(Index {
@@ -8013,6 +8184,21 @@ impl Catalog {
             Plan::CreateSecret(CreateSecretPlan { secret, .. }) => CatalogItem::Secret(Secret {
                 create_sql: secret.create_sql,
             }),
+            Plan::CreateSequence(CreateSequencePlan {
+                sequence, owned_by, ..
+            }) => CatalogItem::Sequence(Sequence {
+                create_sql: sequence.create_sql,
+                options: sequence.options,
+                owned_by,
+                resolved_ids,
+            }),
+            Plan::CreateFunction(CreateFunctionPlan { function, .. }) => {
+                CatalogItem::Function(Function {
+                    create_sql: function.create_sql,
+                    func: function.func,
+                    resolved_ids,
+                })
+            }
             Plan::CreateConnection(CreateConnectionPlan {
                 connection:
                     mz_sql::plan::Connection {
@@ -8278,6 +8464,7 @@ pub(crate) fn system_object_type_to_audit_object_type(
             mz_sql::catalog::ObjectType::Cluster => ObjectType::Cluster,
             mz_sql::catalog::ObjectType::ClusterReplica => ObjectType::ClusterReplica,
             mz_sql::catalog::ObjectType::Secret => ObjectType::Secret,
+            mz_sql::catalog::ObjectType::Sequence => ObjectType::Sequence,
             mz_sql::catalog::ObjectType::Connection => ObjectType::Connection,
             mz_sql::catalog::ObjectType::Database => ObjectType::Database,
             mz_sql::catalog::ObjectType::Schema => ObjectType::Schema,
@@ -8807,8 +8994,13 @@ impl SessionCatalog for ConnCatalog<'_> {
     fn get_items(&self) -> Vec<&dyn mz_sql::catalog::CatalogItem> {
         self.get_schemas()
             .into_iter()
-            .flat_map(|schema| schema.item_ids().into_iter())
-            .map(|(_, global_id)| self.get_item(global_id))
+            .flat_map(|schema| {
+                schema
+                    .item_ids()
+                    .values()
+                    .chain(schema.function_ids().values())
+            })
+            .map(|global_id| self.get_item(global_id))
             .collect()
     }
 
@@ -9039,13 +9231,17 @@ impl mz_sql::catalog::CatalogSchema for Schema {
     }
 
     fn has_items(&self) -> bool {
-        !self.items.is_empty()
+        !self.items.is_empty() || !self.functions.is_empty()
     }
 
     fn item_ids(&self) -> &BTreeMap<String, GlobalId> {
         &self.items
     }
 
+    fn function_ids(&self) -> &BTreeMap<String, GlobalId> {
+        &self.functions
+    }
+
     fn owner_id(&self) -> RoleId {
         self.owner_id
     }
@@ -9155,7 +9351,7 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
         self.desc(name)
     }
 
-    fn func(&self) -> Result<&'static mz_sql::func::Func, SqlCatalogError> {
+    fn func(&self) -> Result<&mz_sql::func::Func, SqlCatalogError> {
         self.func()
     }
 
@@ -9183,6 +9379,8 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
             CatalogItem::Type(Type { create_sql, .. }) => create_sql,
             CatalogItem::Secret(Secret { create_sql, .. }) => create_sql,
             CatalogItem::Connection(Connection { create_sql, .. }) => create_sql,
+            CatalogItem::Sequence(Sequence { create_sql, .. }) => create_sql,
+            CatalogItem::Function(Function { create_sql, .. }) => create_sql,
             CatalogItem::Func(_) => "<builtin>",
             CatalogItem::Log(_) => "<builtin>",
         }
@@ -9208,6 +9406,28 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
         }
     }
 
+    fn table_identity_columns(&self) -> Option<&[usize]> {
+        if let CatalogItem::Table(Table {
+            identity_columns, ..
+        }) = self.item()
+        {
+            Some(identity_columns)
+        } else {
+            None
+        }
+    }
+
+    fn sequence_details(&self) -> Option<(&SequenceOptions, Option<(GlobalId, usize)>)> {
+        if let CatalogItem::Sequence(Sequence {
+            options, owned_by, ..
+        }) = self.item()
+        {
+            Some((options, *owned_by))
+        } else {
+            None
+        }
+    }
+
     fn type_details(&self) -> Option<&CatalogTypeDetails<IdReference>> {
         if let CatalogItem::Type(Type { details, .. }) = self.item() {
             Some(details)
@@ -9267,7 +9487,7 @@ mod tests {
         self, DatabaseId, ItemQualifiers, ObjectId, PartialItemName, QualifiedItemName,
         ResolvedDatabaseSpecifier, ResolvedIds, SchemaId, SchemaSpecifier, SystemObjectId,
     };
-    use mz_sql::plan::StatementContext;
+    use mz_sql::plan::{StatementContext, TableConstraints};
     use mz_sql::session::user::MZ_SYSTEM_ROLE_ID;
     use mz_sql::session::vars::VarInput;
     use mz_sql::DEFAULT_SCHEMA;
@@ -9574,6 +9794,8 @@ mod tests {
                             .with_column("a", ScalarType::Int32.nullable(true))
                             .with_key(vec![0]),
                         defaults: vec![Expr::null(); 1],
+                        identity_columns: vec![],
+                        constraints: TableConstraints::default(),
                         conn_id: None,
                         resolved_ids: ResolvedIds(BTreeSet::new()),
                         custom_logical_compaction_window: None,
@@ -9598,6 +9820,9 @@ mod tests {
                                 .with_key(vec![0]),
                             resolved_ids: ResolvedIds(BTreeSet::from_iter(resolved_ids)),
                             cluster_id: ClusterId::User(1),
+                            custom_logical_compaction_window: None,
+                            refresh_schedule: None,
+                            hints: OptimizerHints::default(),
                         })
                     }
                     SimplifiedItem::Index { on } => {
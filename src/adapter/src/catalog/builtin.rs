@@ -709,6 +709,150 @@ pub const TYPE_JSONB_ARRAY: BuiltinType<NameReference> = BuiltinType {
     },
 };
 
+pub const TYPE_JSONPATH: BuiltinType<NameReference> = BuiltinType {
+    name: "jsonpath",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_JSONPATH_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::JsonPath,
+        array_id: None,
+        typreceive_oid: None,
+    },
+};
+
+pub const TYPE_JSONPATH_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_jsonpath",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_JSONPATH_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_JSONPATH.name,
+        },
+        array_id: None,
+        typreceive_oid: Some(2400),
+    },
+};
+
+pub const TYPE_TSVECTOR: BuiltinType<NameReference> = BuiltinType {
+    name: "tsvector",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_TSVECTOR_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::TsVector,
+        array_id: None,
+        typreceive_oid: None,
+    },
+};
+
+pub const TYPE_TSVECTOR_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_tsvector",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_TSVECTOR_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_TSVECTOR.name,
+        },
+        array_id: None,
+        typreceive_oid: Some(2400),
+    },
+};
+
+pub const TYPE_TSQUERY: BuiltinType<NameReference> = BuiltinType {
+    name: "tsquery",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_TSQUERY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::TsQuery,
+        array_id: None,
+        typreceive_oid: None,
+    },
+};
+
+pub const TYPE_TSQUERY_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_tsquery",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_TSQUERY_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_TSQUERY.name,
+        },
+        array_id: None,
+        typreceive_oid: Some(2400),
+    },
+};
+
+pub const TYPE_INET: BuiltinType<NameReference> = BuiltinType {
+    name: "inet",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_INET_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Inet,
+        array_id: None,
+        typreceive_oid: None,
+    },
+};
+
+pub const TYPE_INET_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_inet",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_INET_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_INET.name,
+        },
+        array_id: None,
+        typreceive_oid: Some(2400),
+    },
+};
+
+pub const TYPE_CIDR: BuiltinType<NameReference> = BuiltinType {
+    name: "cidr",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_CIDR_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Cidr,
+        array_id: None,
+        typreceive_oid: None,
+    },
+};
+
+pub const TYPE_CIDR_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_cidr",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_CIDR_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_CIDR.name,
+        },
+        array_id: None,
+        typreceive_oid: Some(2400),
+    },
+};
+
+pub const TYPE_MACADDR: BuiltinType<NameReference> = BuiltinType {
+    name: "macaddr",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_MACADDR_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::MacAddr,
+        array_id: None,
+        typreceive_oid: None,
+    },
+};
+
+pub const TYPE_MACADDR_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_macaddr",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_MACADDR_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_MACADDR.name,
+        },
+        array_id: None,
+        typreceive_oid: Some(2400),
+    },
+};
+
 pub const TYPE_ANY: BuiltinType<NameReference> = BuiltinType {
     name: "any",
     schema: PG_CATALOG_SCHEMA,
@@ -1427,6 +1571,12 @@ pub const MZ_COMPUTE_DELAYS_HISTOGRAM_RAW: BuiltinLog = BuiltinLog {
     variant: LogVariant::Compute(ComputeLog::FrontierDelay),
 };
 
+pub const MZ_PERSIST_SOURCE_PARTS_RAW: BuiltinLog = BuiltinLog {
+    name: "mz_persist_source_parts_raw",
+    schema: MZ_INTERNAL_SCHEMA,
+    variant: LogVariant::Compute(ComputeLog::PersistSourceParts),
+};
+
 pub const MZ_ACTIVE_PEEKS_PER_WORKER: BuiltinLog = BuiltinLog {
     name: "mz_active_peeks_per_worker",
     schema: MZ_INTERNAL_SCHEMA,
@@ -1463,6 +1613,12 @@ pub const MZ_ARRANGEMENT_HEAP_ALLOCATIONS_RAW: BuiltinLog = BuiltinLog {
     variant: LogVariant::Compute(ComputeLog::ArrangementHeapAllocations),
 };
 
+pub const MZ_ARRANGEMENT_SPILLED_SIZE_RAW: BuiltinLog = BuiltinLog {
+    name: "mz_arrangement_spilled_size_raw",
+    schema: MZ_INTERNAL_SCHEMA,
+    variant: LogVariant::Compute(ComputeLog::ArrangementSpilledSize),
+};
+
 pub const MZ_MESSAGE_BATCH_COUNTS_RECEIVED_RAW: BuiltinLog = BuiltinLog {
     name: "mz_message_batch_counts_received_raw",
     schema: MZ_INTERNAL_SCHEMA,
@@ -2242,6 +2398,35 @@ pub static MZ_STATEMENT_EXECUTION_HISTORY: Lazy<BuiltinTable> = Lazy::new(|| Bui
     is_retained_metrics_object: false,
 });
 
+pub static MZ_INDEX_RECOMMENDATION_HISTORY: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_index_recommendation_history",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("statement_execution_id", ScalarType::Uuid.nullable(false))
+        .with_column("cluster_id", ScalarType::String.nullable(false))
+        .with_column("on_id", ScalarType::String.nullable(false))
+        .with_column(
+            "key",
+            ScalarType::Array(Box::new(ScalarType::String)).nullable(false),
+        )
+        .with_column("lookup", ScalarType::Bool.nullable(false)),
+    is_retained_metrics_object: false,
+});
+
+pub static MZ_PLAN_CHANGES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_plan_changes",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("stage", ScalarType::String.nullable(false))
+        .with_column("previous_version", ScalarType::String.nullable(false))
+        .with_column("previous_fingerprint", ScalarType::String.nullable(false))
+        .with_column("current_fingerprint", ScalarType::String.nullable(false))
+        .with_column("regression", ScalarType::Bool.nullable(false))
+        .with_column("diff", ScalarType::String.nullable(false)),
+    is_retained_metrics_object: false,
+});
+
 pub static MZ_SESSION_HISTORY: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_session_history",
     schema: MZ_INTERNAL_SCHEMA,
@@ -2263,6 +2448,16 @@ pub static MZ_WEBHOOKS_SOURCES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     is_retained_metrics_object: false,
 });
 
+pub static MZ_HISTORY_RETENTION_STRATEGIES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_history_retention_strategies",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("id", ScalarType::String.nullable(false))
+        .with_column("strategy", ScalarType::String.nullable(false))
+        .with_column("value", ScalarType::Jsonb.nullable(false)),
+    is_retained_metrics_object: false,
+});
+
 // These will be replaced with per-replica tables once source/sink multiplexing on
 // a single cluster is supported.
 pub static MZ_SOURCE_STATISTICS: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
@@ -3234,6 +3429,34 @@ FROM mz_internal.mz_compute_delays_histogram_per_worker
 GROUP BY export_id, import_id, delay_ns",
 };
 
+pub const MZ_PERSIST_SOURCE_PARTS_PER_WORKER: BuiltinView = BuiltinView {
+    name: "mz_persist_source_parts_per_worker",
+    schema: MZ_INTERNAL_SCHEMA,
+    sql: "CREATE VIEW mz_internal.mz_persist_source_parts_per_worker AS SELECT
+    export_id,
+    import_id,
+    worker_id,
+    pg_catalog.count(*) FILTER (WHERE fetched) AS parts_fetched,
+    pg_catalog.count(*) FILTER (WHERE NOT fetched) AS parts_skipped
+FROM
+    mz_internal.mz_persist_source_parts_raw
+GROUP BY
+    export_id, import_id, worker_id",
+};
+
+pub const MZ_PERSIST_SOURCE_PARTS: BuiltinView = BuiltinView {
+    name: "mz_persist_source_parts",
+    schema: MZ_INTERNAL_SCHEMA,
+    sql: "CREATE VIEW mz_internal.mz_persist_source_parts AS
+SELECT
+    export_id,
+    import_id,
+    pg_catalog.sum(parts_fetched) AS parts_fetched,
+    pg_catalog.sum(parts_skipped) AS parts_skipped
+FROM mz_internal.mz_persist_source_parts_per_worker
+GROUP BY export_id, import_id",
+};
+
 pub const MZ_MESSAGE_COUNTS_PER_WORKER: BuiltinView = BuiltinView {
     name: "mz_message_counts_per_worker",
     schema: MZ_INTERNAL_SCHEMA,
@@ -3401,6 +3624,16 @@ heap_allocations_cte AS (
         mz_internal.mz_arrangement_heap_allocations_raw
     GROUP BY
         operator_id, worker_id
+),
+spilled_size_cte AS (
+    SELECT
+        operator_id,
+        worker_id,
+        pg_catalog.count(*) AS spilled_size
+    FROM
+        mz_internal.mz_arrangement_spilled_size_raw
+    GROUP BY
+        operator_id, worker_id
 )
 SELECT
     batches_cte.operator_id,
@@ -3409,12 +3642,14 @@ SELECT
     batches_cte.batches,
     COALESCE(heap_size_cte.size, 0) AS size,
     COALESCE(heap_capacity_cte.capacity, 0) AS capacity,
-    COALESCE(heap_allocations_cte.allocations, 0) AS allocations
+    COALESCE(heap_allocations_cte.allocations, 0) AS allocations,
+    COALESCE(spilled_size_cte.spilled_size, 0) AS spilled_size
 FROM batches_cte
 LEFT OUTER JOIN records_cte USING (operator_id, worker_id)
 LEFT OUTER JOIN heap_size_cte USING (operator_id, worker_id)
 LEFT OUTER JOIN heap_capacity_cte USING (operator_id, worker_id)
-LEFT OUTER JOIN heap_allocations_cte USING (operator_id, worker_id)",
+LEFT OUTER JOIN heap_allocations_cte USING (operator_id, worker_id)
+LEFT OUTER JOIN spilled_size_cte USING (operator_id, worker_id)",
 };
 
 pub const MZ_ARRANGEMENT_SIZES: BuiltinView = BuiltinView {
@@ -3427,7 +3662,8 @@ SELECT
     pg_catalog.sum(batches) AS batches,
     pg_catalog.sum(size) AS size,
     pg_catalog.sum(capacity) AS capacity,
-    pg_catalog.sum(allocations) AS allocations
+    pg_catalog.sum(allocations) AS allocations,
+    pg_catalog.sum(spilled_size) AS spilled_size
 FROM mz_internal.mz_arrangement_sizes_per_worker
 GROUP BY operator_id",
 };
@@ -3527,6 +3763,103 @@ pub const MZ_DATAFLOW_ARRANGEMENT_SIZES: BuiltinView = BuiltinView {
         GROUP BY mo.name, mdod.dataflow_id",
 };
 
+pub const MZ_INDEX_RECOMMENDATIONS: BuiltinView = BuiltinView {
+    name: "mz_index_recommendations",
+    schema: MZ_INTERNAL_SCHEMA,
+    sql: "CREATE VIEW
+    mz_internal.mz_index_recommendations
+    AS
+        -- The mz_index_recommendations view lists indexes that would have allowed SELECT
+        -- statements to be answered from an existing arrangement, rather than by building a
+        -- dataflow. The candidates are the logged statement executions that succeeded by
+        -- building a dataflow, together with the index that was found for their optimized plan
+        -- when they executed.
+        WITH candidates AS (
+            SELECT
+                irh.cluster_id,
+                irh.on_id,
+                irh.key,
+                pg_catalog.bool_or(irh.lookup) AS lookup,
+                pg_catalog.count(*) AS slow_path_executions,
+                pg_catalog.max(seh.began_at) AS last_executed_at
+            FROM
+                mz_internal.mz_statement_execution_history AS seh
+                JOIN mz_internal.mz_index_recommendation_history AS irh
+                    ON irh.statement_execution_id = seh.id
+            WHERE
+                seh.finished_status = 'success' AND
+                seh.execution_strategy = 'standard'
+            GROUP BY irh.cluster_id, irh.on_id, irh.key
+        ),
+        -- The keys of the existing indexes, as the names of their columns in the same order
+        -- as recommended keys. Keys with expressions have no names, and never match.
+        index_keys AS (
+            SELECT
+                i.on_id,
+                i.cluster_id,
+                pg_catalog.array_agg(col.name ORDER BY ic.on_position) AS key,
+                pg_catalog.bool_and(ic.on_position IS NOT NULL) AS on_columns
+            FROM
+                mz_catalog.mz_indexes AS i
+                JOIN mz_catalog.mz_index_columns AS ic ON ic.index_id = i.id
+                LEFT JOIN mz_catalog.mz_columns AS col
+                    ON col.id = i.on_id AND col.position = ic.on_position
+            GROUP BY i.id, i.on_id, i.cluster_id
+        )
+        SELECT
+            c.cluster_id,
+            c.on_id,
+            o.name AS on_name,
+            c.key,
+            c.slow_path_executions,
+            c.last_executed_at
+        FROM
+            candidates AS c
+            JOIN mz_catalog.mz_objects AS o ON o.id = c.on_id
+        -- Recommendations that have since been followed are retired. Lookups need an index
+        -- with the recommended key, while scans can be served by any index on the collection.
+        WHERE NOT EXISTS (
+            SELECT 1
+            FROM index_keys AS i
+            WHERE
+                i.on_id = c.on_id AND
+                i.cluster_id = c.cluster_id AND
+                (NOT c.lookup OR (i.on_columns AND i.key = c.key))
+        )",
+};
+
+pub const MZ_INDEX_RECOMMENDATION_SIZES: BuiltinView = BuiltinView {
+    name: "mz_index_recommendation_sizes",
+    schema: MZ_INTERNAL_SCHEMA,
+    sql: "CREATE VIEW
+    mz_internal.mz_index_recommendation_sizes
+    AS
+        -- The mz_index_recommendation_sizes view estimates the memory cost of the indexes in
+        -- mz_index_recommendations by the arrangement sizes of the existing indexes on the same
+        -- collections on the active replica, which hold the same records under another key.
+        -- The dataflow of an index on a view also arranges the inputs of the view, so the
+        -- smallest one is used.
+        WITH index_sizes AS (
+            SELECT
+                i.on_id,
+                pg_catalog.min(das.size) AS size
+            FROM
+                mz_catalog.mz_indexes AS i
+                JOIN mz_internal.mz_compute_exports AS ce ON ce.export_id = i.id
+                JOIN mz_internal.mz_dataflow_arrangement_sizes AS das
+                    ON das.id = ce.dataflow_id
+            GROUP BY i.on_id
+        )
+        SELECT
+            r.cluster_id,
+            r.on_id,
+            r.key,
+            s.size AS estimated_size
+        FROM
+            mz_internal.mz_index_recommendations AS r
+            LEFT JOIN index_sizes AS s ON s.on_id = r.on_id",
+};
+
 pub const MZ_EXPECTED_GROUP_SIZE_ADVICE: BuiltinView = BuiltinView {
     name: "mz_expected_group_size_advice",
     schema: MZ_INTERNAL_SCHEMA,
@@ -5028,6 +5361,18 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Type(&TYPE_MZ_ACL_ITEM_ARRAY),
         Builtin::Type(&TYPE_ACL_ITEM),
         Builtin::Type(&TYPE_ACL_ITEM_ARRAY),
+        Builtin::Type(&TYPE_JSONPATH),
+        Builtin::Type(&TYPE_JSONPATH_ARRAY),
+        Builtin::Type(&TYPE_TSVECTOR),
+        Builtin::Type(&TYPE_TSVECTOR_ARRAY),
+        Builtin::Type(&TYPE_TSQUERY),
+        Builtin::Type(&TYPE_TSQUERY_ARRAY),
+        Builtin::Type(&TYPE_INET),
+        Builtin::Type(&TYPE_INET_ARRAY),
+        Builtin::Type(&TYPE_CIDR),
+        Builtin::Type(&TYPE_CIDR_ARRAY),
+        Builtin::Type(&TYPE_MACADDR),
+        Builtin::Type(&TYPE_MACADDR_ARRAY),
         Builtin::Type(&TYPE_INTERNAL),
     ];
     for (schema, funcs) in &[
@@ -5065,6 +5410,7 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Log(&MZ_DATAFLOW_SHUTDOWN_DURATIONS_HISTOGRAM_RAW),
         Builtin::Log(&MZ_ARRANGEMENT_HEAP_CAPACITY_RAW),
         Builtin::Log(&MZ_ARRANGEMENT_HEAP_ALLOCATIONS_RAW),
+        Builtin::Log(&MZ_ARRANGEMENT_SPILLED_SIZE_RAW),
         Builtin::Log(&MZ_ARRANGEMENT_HEAP_SIZE_RAW),
         Builtin::Log(&MZ_SCHEDULING_ELAPSED_RAW),
         Builtin::Log(&MZ_COMPUTE_OPERATOR_DURATIONS_HISTOGRAM_RAW),
@@ -5072,6 +5418,7 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Log(&MZ_COMPUTE_FRONTIERS_PER_WORKER),
         Builtin::Log(&MZ_COMPUTE_IMPORT_FRONTIERS_PER_WORKER),
         Builtin::Log(&MZ_COMPUTE_DELAYS_HISTOGRAM_RAW),
+        Builtin::Log(&MZ_PERSIST_SOURCE_PARTS_RAW),
         Builtin::Table(&MZ_VIEW_KEYS),
         Builtin::Table(&MZ_VIEW_FOREIGN_KEYS),
         Builtin::Table(&MZ_KAFKA_SINKS),
@@ -5123,8 +5470,11 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Table(&MZ_SYSTEM_PRIVILEGES),
         Builtin::Table(&MZ_PREPARED_STATEMENT_HISTORY),
         Builtin::Table(&MZ_STATEMENT_EXECUTION_HISTORY),
+        Builtin::Table(&MZ_INDEX_RECOMMENDATION_HISTORY),
+        Builtin::Table(&MZ_PLAN_CHANGES),
         Builtin::Table(&MZ_COMMENTS),
         Builtin::Table(&MZ_WEBHOOKS_SOURCES),
+        Builtin::Table(&MZ_HISTORY_RETENTION_STRATEGIES),
         Builtin::View(&MZ_RELATIONS),
         Builtin::View(&MZ_OBJECTS),
         Builtin::View(&MZ_OBJECT_FULLY_QUALIFIED_NAMES),
@@ -5149,6 +5499,8 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::View(&MZ_COMPUTE_EXPORTS),
         Builtin::View(&MZ_DATAFLOW_ARRANGEMENT_SIZES),
         Builtin::View(&MZ_EXPECTED_GROUP_SIZE_ADVICE),
+        Builtin::View(&MZ_INDEX_RECOMMENDATIONS),
+        Builtin::View(&MZ_INDEX_RECOMMENDATION_SIZES),
         Builtin::View(&MZ_COMPUTE_FRONTIERS),
         Builtin::View(&MZ_DATAFLOW_CHANNEL_OPERATORS_PER_WORKER),
         Builtin::View(&MZ_DATAFLOW_CHANNEL_OPERATORS),
@@ -5172,6 +5524,8 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::View(&MZ_SCHEDULING_PARKS_HISTOGRAM),
         Builtin::View(&MZ_COMPUTE_DELAYS_HISTOGRAM_PER_WORKER),
         Builtin::View(&MZ_COMPUTE_DELAYS_HISTOGRAM),
+        Builtin::View(&MZ_PERSIST_SOURCE_PARTS_PER_WORKER),
+        Builtin::View(&MZ_PERSIST_SOURCE_PARTS),
         Builtin::View(&MZ_SHOW_MATERIALIZED_VIEWS),
         Builtin::View(&MZ_SHOW_INDEXES),
         Builtin::View(&MZ_SHOW_CLUSTER_REPLICAS),
@@ -6037,7 +6391,13 @@ mod tests {
                         | ScalarType::Char { .. }
                         | ScalarType::VarChar { .. }
                         | ScalarType::Jsonb
+                        | ScalarType::JsonPath
+                        | ScalarType::TsVector
+                        | ScalarType::TsQuery
                         | ScalarType::Uuid
+                        | ScalarType::Inet
+                        | ScalarType::Cidr
+                        | ScalarType::MacAddr
                         | ScalarType::Array(_)
                         | ScalarType::Record { .. }
                         | ScalarType::Oid
@@ -8,6 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use std::net::Ipv4Addr;
+use std::time::Duration;
 
 use bytesize::ByteSize;
 use chrono::{DateTime, Utc};
@@ -37,27 +38,30 @@ use mz_storage_client::types::sinks::{KafkaSinkConnection, StorageSinkConnection
 use mz_storage_client::types::sources::{
     GenericSourceConnection, KafkaSourceConnection, PostgresSourceConnection,
 };
+use uuid::Uuid;
 
 use crate::catalog::builtin::{
     MZ_AGGREGATES, MZ_ARRAY_TYPES, MZ_AUDIT_EVENTS, MZ_AWS_PRIVATELINK_CONNECTIONS, MZ_BASE_TYPES,
     MZ_CLUSTERS, MZ_CLUSTER_LINKS, MZ_CLUSTER_REPLICAS, MZ_CLUSTER_REPLICA_HEARTBEATS,
     MZ_CLUSTER_REPLICA_METRICS, MZ_CLUSTER_REPLICA_SIZES, MZ_CLUSTER_REPLICA_STATUSES, MZ_COLUMNS,
     MZ_COMMENTS, MZ_COMPUTE_DEPENDENCIES, MZ_CONNECTIONS, MZ_DATABASES, MZ_DEFAULT_PRIVILEGES,
-    MZ_EGRESS_IPS, MZ_FUNCTIONS, MZ_INDEXES, MZ_INDEX_COLUMNS, MZ_KAFKA_CONNECTIONS,
-    MZ_KAFKA_SINKS, MZ_KAFKA_SOURCES, MZ_LIST_TYPES, MZ_MAP_TYPES, MZ_MATERIALIZED_VIEWS,
-    MZ_OBJECT_DEPENDENCIES, MZ_OPERATORS, MZ_POSTGRES_SOURCES, MZ_PSEUDO_TYPES, MZ_ROLES,
-    MZ_ROLE_MEMBERS, MZ_SCHEMAS, MZ_SECRETS, MZ_SESSIONS, MZ_SINKS, MZ_SOURCES,
-    MZ_SSH_TUNNEL_CONNECTIONS, MZ_STORAGE_USAGE_BY_SHARD, MZ_SUBSCRIPTIONS, MZ_SYSTEM_PRIVILEGES,
-    MZ_TABLES, MZ_TYPES, MZ_TYPE_PG_METADATA, MZ_VIEWS, MZ_WEBHOOKS_SOURCES,
+    MZ_EGRESS_IPS, MZ_FUNCTIONS, MZ_HISTORY_RETENTION_STRATEGIES, MZ_INDEXES, MZ_INDEX_COLUMNS,
+    MZ_KAFKA_CONNECTIONS, MZ_KAFKA_SINKS, MZ_KAFKA_SOURCES, MZ_LIST_TYPES, MZ_MAP_TYPES,
+    MZ_MATERIALIZED_VIEWS, MZ_OBJECT_DEPENDENCIES, MZ_OPERATORS, MZ_POSTGRES_SOURCES,
+    MZ_PSEUDO_TYPES, MZ_ROLES, MZ_ROLE_MEMBERS, MZ_SCHEMAS, MZ_SECRETS, MZ_SESSIONS, MZ_SINKS,
+    MZ_SOURCES, MZ_SSH_TUNNEL_CONNECTIONS, MZ_STORAGE_USAGE_BY_SHARD, MZ_SUBSCRIPTIONS,
+    MZ_SYSTEM_PRIVILEGES, MZ_TABLES, MZ_TYPES, MZ_TYPE_PG_METADATA, MZ_VIEWS, MZ_WEBHOOKS_SOURCES,
 };
 use crate::catalog::builtin::{
-    MZ_PREPARED_STATEMENT_HISTORY, MZ_SESSION_HISTORY, MZ_STATEMENT_EXECUTION_HISTORY,
+    MZ_INDEX_RECOMMENDATION_HISTORY, MZ_PLAN_CHANGES, MZ_PREPARED_STATEMENT_HISTORY,
+    MZ_SESSION_HISTORY, MZ_STATEMENT_EXECUTION_HISTORY,
 };
 use crate::catalog::{
     AwsPrincipalContext, CatalogItem, CatalogState, ClusterVariant, Connection, DataSourceDesc,
     Database, DefaultPrivilegeObject, Error, ErrorKind, Func, Index, MaterializedView, Sink,
     StorageSinkConnectionState, Type, View, SYSTEM_CONN_ID,
 };
+use crate::coord::peek::IndexRecommendation;
 use crate::coord::ConnMeta;
 use crate::statement_logging::{
     SessionHistoryEvent, StatementBeganExecutionRecord, StatementEndedExecutionReason,
@@ -400,6 +404,10 @@ impl CatalogState {
             CatalogItem::Secret(_) => {
                 self.pack_secret_update(id, oid, schema_id, name, owner_id, privileges, diff)
             }
+            // Sequences are not yet exposed in the system catalog.
+            CatalogItem::Sequence(_) => vec![],
+            // User-defined functions are not yet exposed in the system catalog.
+            CatalogItem::Function(_) => vec![],
             CatalogItem::Connection(connection) => self.pack_connection_update(
                 id, oid, schema_id, name, owner_id, privileges, connection, diff,
             ),
@@ -412,6 +420,14 @@ impl CatalogState {
             }
         }
 
+        // Only report windows set via `RETAIN HISTORY`; the windows of retained
+        // metrics objects are controlled by the `metrics_retention` system var.
+        if !entry.item().is_retained_metrics_object() {
+            if let Some(window) = entry.item().custom_logical_compaction_window() {
+                updates.push(self.pack_history_retention_strategy_update(id, window, diff));
+            }
+        }
+
         if let Ok(desc) = entry.desc(&self.resolve_full_name(entry.name(), entry.conn_id())) {
             let defaults = match entry.item() {
                 CatalogItem::Table(table) => Some(&table.defaults),
@@ -444,6 +460,27 @@ impl CatalogState {
         updates
     }
 
+    fn pack_history_retention_strategy_update(
+        &self,
+        id: GlobalId,
+        window: Duration,
+        diff: Diff,
+    ) -> BuiltinTableUpdate {
+        let millis = u64::try_from(window.as_millis()).unwrap_or(u64::MAX);
+        let value = Jsonb::from_serde_json(serde_json::Value::from(millis))
+            .expect("integers are valid JSON")
+            .into_row();
+        BuiltinTableUpdate {
+            id: self.resolve_builtin_table(&MZ_HISTORY_RETENTION_STRATEGIES),
+            row: Row::pack_slice(&[
+                Datum::String(&id.to_string()),
+                Datum::String("FOR"),
+                value.unpack_first(),
+            ]),
+            diff,
+        }
+    }
+
     fn pack_table_update(
         &self,
         id: GlobalId,
@@ -1317,6 +1354,75 @@ impl CatalogState {
         }
     }
 
+    /// Packs an index that would have allowed the statement execution `execution_id` to use the
+    /// fast path, or returns `None` if the recommendation cannot be acted on.
+    pub fn pack_index_recommendation_update(
+        &self,
+        execution_id: Uuid,
+        cluster_id: ClusterId,
+        recommendation: &IndexRecommendation,
+    ) -> Option<BuiltinTableUpdate> {
+        let IndexRecommendation { on_id, key, lookup } = recommendation;
+        let entry = self.try_get_entry(on_id)?;
+        if matches!(entry.item(), CatalogItem::Log(_)) {
+            return None;
+        }
+        let desc = entry
+            .desc(&self.resolve_full_name(entry.name(), entry.conn_id()))
+            .ok()?;
+
+        let mut row = Row::default();
+        let mut packer = row.packer();
+        packer.extend([
+            Datum::Uuid(execution_id),
+            Datum::String(&cluster_id.to_string()),
+            Datum::String(&on_id.to_string()),
+        ]);
+        packer
+            .push_array(
+                &[ArrayDimension {
+                    lower_bound: 1,
+                    length: key.len(),
+                }],
+                key.iter()
+                    .map(|i| Datum::String(desc.get_name(*i).as_str())),
+            )
+            .expect("correct array dimensions");
+        packer.push(Datum::from(*lookup));
+        Some(BuiltinTableUpdate {
+            id: self.resolve_builtin_table(&MZ_INDEX_RECOMMENDATION_HISTORY),
+            row,
+            diff: 1,
+        })
+    }
+
+    /// Packs a change of the `stage` plan of the index or materialized view `id` since the
+    /// previous run of Materialize, which ran `previous_version`.
+    pub fn pack_plan_change_update(
+        &self,
+        id: GlobalId,
+        stage: &str,
+        previous_version: &str,
+        previous_fingerprint: &str,
+        current_fingerprint: &str,
+        regression: bool,
+        plan_diff: &str,
+    ) -> BuiltinTableUpdate {
+        BuiltinTableUpdate {
+            id: self.resolve_builtin_table(&MZ_PLAN_CHANGES),
+            row: Row::pack_slice(&[
+                Datum::String(&id.to_string()),
+                Datum::String(stage),
+                Datum::String(previous_version),
+                Datum::String(previous_fingerprint),
+                Datum::String(current_fingerprint),
+                Datum::from(regression),
+                Datum::String(plan_diff),
+            ]),
+            diff: 1,
+        }
+    }
+
     pub fn pack_default_privileges_update(
         &self,
         default_privilege_object: &DefaultPrivilegeObject,
@@ -1557,6 +1663,7 @@ impl CatalogState {
             | CommentObjectId::Func(global_id)
             | CommentObjectId::Connection(global_id)
             | CommentObjectId::Secret(global_id)
+            | CommentObjectId::Sequence(global_id)
             | CommentObjectId::Type(global_id) => global_id.to_string(),
             CommentObjectId::Role(role_id) => role_id.to_string(),
             CommentObjectId::Database(database_id) => database_id.to_string(),
@@ -18,6 +18,7 @@ use futures::StreamExt;
 use itertools::Itertools;
 use mz_audit_log::{VersionedEvent, VersionedStorageUsage};
 use mz_controller::clusters::{ClusterId, ReplicaId, ReplicaLogging};
+use mz_ore::cast::ReinterpretCast;
 use mz_ore::collections::CollectionExt;
 use mz_ore::now::NowFn;
 use mz_ore::retry::Retry;
@@ -33,6 +34,7 @@ use mz_sql::names::{
     CommentObjectId, DatabaseId, ItemQualifiers, QualifiedItemName, ResolvedDatabaseSpecifier,
     SchemaId, SchemaSpecifier,
 };
+use mz_sql::plan::SequenceOptions;
 use mz_sql::session::user::MZ_SYSTEM_ROLE_ID;
 use mz_sql_parser::ast::QualifiedReplica;
 use mz_stash::objects::proto;
@@ -53,10 +55,10 @@ pub mod stash;
 pub use stash::{
     AUDIT_LOG_COLLECTION, CLUSTER_COLLECTION, CLUSTER_INTROSPECTION_SOURCE_INDEX_COLLECTION,
     CLUSTER_REPLICA_COLLECTION, COMMENTS_COLLECTION, CONFIG_COLLECTION, DATABASES_COLLECTION,
-    DEFAULT_PRIVILEGES_COLLECTION, ID_ALLOCATOR_COLLECTION, ITEM_COLLECTION, ROLES_COLLECTION,
-    SCHEMAS_COLLECTION, SETTING_COLLECTION, STORAGE_USAGE_COLLECTION,
-    SYSTEM_CONFIGURATION_COLLECTION, SYSTEM_GID_MAPPING_COLLECTION, SYSTEM_PRIVILEGES_COLLECTION,
-    TIMESTAMP_COLLECTION,
+    DEFAULT_PRIVILEGES_COLLECTION, ID_ALLOCATOR_COLLECTION, ITEM_COLLECTION,
+    PLAN_FINGERPRINT_COLLECTION, ROLES_COLLECTION, SCHEMAS_COLLECTION, SETTING_COLLECTION,
+    STORAGE_USAGE_COLLECTION, SYSTEM_CONFIGURATION_COLLECTION, SYSTEM_GID_MAPPING_COLLECTION,
+    SYSTEM_PRIVILEGES_COLLECTION, TIMESTAMP_COLLECTION,
 };
 
 const DATABASE_ID_ALLOC_KEY: &str = "database";
@@ -69,6 +71,13 @@ const SYSTEM_REPLICA_ID_ALLOC_KEY: &str = "system_replica";
 pub(crate) const AUDIT_LOG_ID_ALLOC_KEY: &str = "auditlog";
 pub(crate) const STORAGE_USAGE_ID_ALLOC_KEY: &str = "storage_usage";
 
+/// Returns the ID allocator key that stores the next value of sequence `id`.
+///
+/// Sequence values are `i64`s, which are stored reinterpreted as `u64`s.
+fn sequence_alloc_key(id: GlobalId) -> String {
+    format!("sequence:{id}")
+}
+
 fn add_new_builtin_clusters_migration(txn: &mut Transaction<'_>) -> Result<(), Error> {
     let cluster_names: BTreeSet<_> = txn
         .clusters
@@ -617,6 +626,54 @@ impl Connection {
         Ok(comments)
     }
 
+    /// Load the plan fingerprints of indexes and materialized views, as recorded when they were
+    /// last planned.
+    #[tracing::instrument(level = "info", skip_all)]
+    pub async fn load_plan_fingerprints(
+        &mut self,
+    ) -> Result<BTreeMap<GlobalId, PlanFingerprint>, Error> {
+        let fingerprints = PLAN_FINGERPRINT_COLLECTION
+            .peek_one(&mut self.stash)
+            .await?
+            .into_iter()
+            .map(RustType::from_proto)
+            .map_ok(|(k, v): (PlanFingerprintKey, PlanFingerprint)| (k.id, v))
+            .collect::<Result<_, _>>()?;
+
+        Ok(fingerprints)
+    }
+
+    /// Persist the plan fingerprints in `fingerprints`, replacing any previous ones of the same
+    /// objects, and remove the plan fingerprints of the objects in `removed`.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn update_plan_fingerprints(
+        &mut self,
+        fingerprints: BTreeMap<GlobalId, PlanFingerprint>,
+        removed: BTreeSet<GlobalId>,
+    ) -> Result<(), Error> {
+        if !removed.is_empty() {
+            let keys = removed
+                .into_iter()
+                .map(|id| PlanFingerprintKey { id }.into_proto())
+                .collect();
+            PLAN_FINGERPRINT_COLLECTION
+                .delete_keys(&mut self.stash, keys)
+                .await?;
+        }
+        if !fingerprints.is_empty() {
+            let entries = fingerprints.into_iter().map(|(id, fingerprint)| {
+                (
+                    PlanFingerprintKey { id }.into_proto(),
+                    fingerprint.into_proto(),
+                )
+            });
+            PLAN_FINGERPRINT_COLLECTION
+                .upsert(&mut self.stash, entries)
+                .await?;
+        }
+        Ok(())
+    }
+
     /// Persist mapping from system objects to global IDs and fingerprints.
     ///
     /// Panics if provided id is not a system id.
@@ -787,6 +844,54 @@ impl Connection {
         Ok((id..next.next_id).collect())
     }
 
+    /// Allocates up to `amount` values of the sequence `id`, whose options are
+    /// `options`.
+    ///
+    /// Fewer values are returned if the sequence is exhausted.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn allocate_sequence_values(
+        &mut self,
+        id: GlobalId,
+        options: SequenceOptions,
+        amount: u64,
+    ) -> Result<Vec<i64>, Error> {
+        let key = IdAllocKey {
+            name: sequence_alloc_key(id),
+        }
+        .into_proto();
+        let (prev, _next) = ID_ALLOCATOR_COLLECTION
+            .upsert_key(&mut self.stash, key, move |prev| {
+                let next = i64::reinterpret_cast(prev.expect("must exist").next_id);
+                let (_, next) = options.advance(next, amount);
+                Ok::<_, Error>(
+                    IdAllocValue {
+                        next_id: u64::reinterpret_cast(next),
+                    }
+                    .into_proto(),
+                )
+            })
+            .await??;
+        let next = i64::reinterpret_cast(prev.expect("must exist").next_id);
+        let (values, _) = options.advance(next, amount);
+        Ok(values)
+    }
+
+    /// Sets the next value of the sequence `id` to `next`.
+    pub async fn set_sequence_value(&mut self, id: GlobalId, next: i64) -> Result<(), Error> {
+        let key = IdAllocKey {
+            name: sequence_alloc_key(id),
+        }
+        .into_proto();
+        let value = IdAllocValue {
+            next_id: u64::reinterpret_cast(next),
+        }
+        .into_proto();
+        ID_ALLOCATOR_COLLECTION
+            .upsert_key(&mut self.stash, key, |_| Ok::<_, Error>(value))
+            .await??;
+        Ok(())
+    }
+
     /// Gets a global timestamp for a timeline that has been persisted to disk.
     ///
     /// Returns `None` if no persisted timestamp for the specified timeline exists.
@@ -1366,6 +1471,30 @@ impl<'a> Transaction<'a> {
         Ok(id)
     }
 
+    /// Initializes the next value of the sequence `id` to `start`.
+    pub(crate) fn insert_sequence(&mut self, id: GlobalId, start: i64) -> Result<(), Error> {
+        self.id_allocator.set(
+            IdAllocKey {
+                name: sequence_alloc_key(id),
+            },
+            Some(IdAllocValue {
+                next_id: u64::reinterpret_cast(start),
+            }),
+        )?;
+        Ok(())
+    }
+
+    /// Removes the next value of the sequence `id`.
+    pub(crate) fn remove_sequence(&mut self, id: GlobalId) -> Result<(), Error> {
+        self.id_allocator.set(
+            IdAllocKey {
+                name: sequence_alloc_key(id),
+            },
+            None,
+        )?;
+        Ok(())
+    }
+
     pub(crate) fn remove_database(&mut self, id: &DatabaseId) -> Result<(), Error> {
         let prev = self.databases.set(DatabaseKey { id: *id }, None)?;
         if prev.is_some() {
@@ -2112,6 +2241,24 @@ pub struct SystemObjectMapping {
     pub fingerprint: String,
 }
 
+/// The plans of an index or materialized view, as produced by a particular version of
+/// Materialize.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PlanFingerprint {
+    /// The version of Materialize that produced the plans.
+    pub build_version: String,
+    /// A hash of the structure of the optimized plan.
+    pub optimized_fingerprint: String,
+    /// A hash of the structure of the physical plan.
+    pub physical_fingerprint: String,
+    /// The `EXPLAIN OPTIMIZED PLAN` output of the object.
+    pub optimized_plan: String,
+    /// The `EXPLAIN PHYSICAL PLAN` output of the object.
+    pub physical_plan: String,
+    /// The number of arrangements that `physical_plan` builds.
+    pub physical_arrangements: u64,
+}
+
 // Structs used internally to represent on disk-state.
 
 #[derive(Clone, PartialOrd, PartialEq, Eq, Ord, Hash)]
@@ -2277,6 +2424,11 @@ pub struct StorageUsageKey {
     metric: VersionedStorageUsage,
 }
 
+#[derive(Clone, PartialOrd, PartialEq, Eq, Ord, Hash)]
+pub struct PlanFingerprintKey {
+    id: GlobalId,
+}
+
 #[derive(Clone, PartialOrd, PartialEq, Eq, Ord, Hash)]
 pub struct TimestampKey {
     id: String,
@@ -2332,6 +2484,7 @@ pub const ALL_COLLECTIONS: &[&str] = &[
     DEFAULT_PRIVILEGES_COLLECTION.name(),
     ID_ALLOCATOR_COLLECTION.name(),
     ITEM_COLLECTION.name(),
+    PLAN_FINGERPRINT_COLLECTION.name(),
     ROLES_COLLECTION.name(),
     SCHEMAS_COLLECTION.name(),
     SETTING_COLLECTION.name(),
@@ -12,8 +12,8 @@ use mz_proto::{IntoRustIfSome, ProtoType};
 use mz_stash::objects::{proto, RustType, TryFromProtoError};
 
 use crate::catalog::storage::{
-    CommentValue, DefaultPrivilegesKey, DefaultPrivilegesValue, ReplicaConfig, ReplicaLocation,
-    SystemPrivilegesKey, SystemPrivilegesValue,
+    CommentValue, DefaultPrivilegesKey, DefaultPrivilegesValue, PlanFingerprint,
+    PlanFingerprintKey, ReplicaConfig, ReplicaLocation, SystemPrivilegesKey, SystemPrivilegesValue,
 };
 use crate::catalog::{ClusterConfig, ClusterVariant, ClusterVariantManaged};
 
@@ -732,3 +732,41 @@ impl RustType<proto::SystemPrivilegesValue> for SystemPrivilegesValue {
         })
     }
 }
+
+impl RustType<proto::PlanFingerprintKey> for PlanFingerprintKey {
+    fn into_proto(&self) -> proto::PlanFingerprintKey {
+        proto::PlanFingerprintKey {
+            id: Some(self.id.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: proto::PlanFingerprintKey) -> Result<Self, TryFromProtoError> {
+        Ok(PlanFingerprintKey {
+            id: proto.id.into_rust_if_some("PlanFingerprintKey::id")?,
+        })
+    }
+}
+
+impl RustType<proto::PlanFingerprintValue> for PlanFingerprint {
+    fn into_proto(&self) -> proto::PlanFingerprintValue {
+        proto::PlanFingerprintValue {
+            build_version: self.build_version.clone(),
+            optimized_fingerprint: self.optimized_fingerprint.clone(),
+            physical_fingerprint: self.physical_fingerprint.clone(),
+            optimized_plan: self.optimized_plan.clone(),
+            physical_plan: self.physical_plan.clone(),
+            physical_arrangements: self.physical_arrangements,
+        }
+    }
+
+    fn from_proto(proto: proto::PlanFingerprintValue) -> Result<Self, TryFromProtoError> {
+        Ok(PlanFingerprint {
+            build_version: proto.build_version,
+            optimized_fingerprint: proto.optimized_fingerprint,
+            physical_fingerprint: proto.physical_fingerprint,
+            optimized_plan: proto.optimized_plan,
+            physical_plan: proto.physical_plan,
+            physical_arrangements: proto.physical_arrangements,
+        })
+    }
+}
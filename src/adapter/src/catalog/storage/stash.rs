@@ -57,6 +57,10 @@ pub const ITEM_COLLECTION: TypedCollection<proto::ItemKey, proto::ItemValue> =
     TypedCollection::new("item");
 pub const COMMENTS_COLLECTION: TypedCollection<proto::CommentKey, proto::CommentValue> =
     TypedCollection::new("comments");
+pub const PLAN_FINGERPRINT_COLLECTION: TypedCollection<
+    proto::PlanFingerprintKey,
+    proto::PlanFingerprintValue,
+> = TypedCollection::new("plan_fingerprint");
 pub const TIMESTAMP_COLLECTION: TypedCollection<proto::TimestampKey, proto::TimestampValue> =
     TypedCollection::new("timestamp");
 pub const SYSTEM_CONFIGURATION_COLLECTION: TypedCollection<
@@ -817,6 +821,7 @@ pub async fn initialize(
         .await?;
     STORAGE_USAGE_COLLECTION.initialize(tx, vec![]).await?;
     COMMENTS_COLLECTION.initialize(tx, vec![]).await?;
+    PLAN_FINGERPRINT_COLLECTION.initialize(tx, vec![]).await?;
 
     // Set our initial version.
     CONFIG_COLLECTION
@@ -19,6 +19,7 @@ use chrono::{DateTime, Utc};
 use derivative::Derivative;
 use futures::{Stream, StreamExt};
 use mz_build_info::BuildInfo;
+use mz_expr::MirRelationExpr;
 use mz_ore::collections::CollectionExt;
 use mz_ore::id_gen::{IdAllocator, IdHandle};
 use mz_ore::now::{to_datetime, EpochMillis, NowFn};
@@ -604,23 +605,57 @@ impl SessionClient {
         // self.session returns a mut ref, so we can't call it twice.
         let pcx = self.session().pcx().clone();
         let conn_catalog = catalog.for_session(self.session());
-        let result: Result<_, AdapterError> =
+        let planned: Result<_, AdapterError> =
             mz_sql::plan::plan_copy_from(&pcx, &conn_catalog, id, columns, rows)
                 .err_into()
-                .and_then(|values| values.lower().err_into())
-                .and_then(|values| {
-                    Optimizer::logical_optimizer(&mz_transform::typecheck::empty_context())
-                        .optimize(values)
-                        .err_into()
-                })
-                .and_then(|values| {
-                    // Copied rows must always be constants.
-                    Coordinator::insert_constant(&catalog, self.session(), id, values.into_inner())
+                .and_then(|(values, sequence_defaults)| {
+                    let values = values.lower()?;
+                    let values =
+                        Optimizer::logical_optimizer(&mz_transform::typecheck::empty_context())
+                            .optimize(values)?;
+                    Ok((values.into_inner(), sequence_defaults))
                 });
+        let result = match planned {
+            Ok((values, sequence_defaults)) => {
+                match self.fill_sequence_defaults(values, sequence_defaults).await {
+                    // Copied rows must always be constants.
+                    Ok(values) => {
+                        Coordinator::insert_constant(&catalog, self.session(), id, values)
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            Err(e) => Err(e),
+        };
         self.retire_execute(ctx_extra, (&result).into());
         result
     }
 
+    /// Fills in the columns of the constant `values` that default to a sequence with values
+    /// drawn from that sequence, one per row.
+    async fn fill_sequence_defaults(
+        &mut self,
+        values: MirRelationExpr,
+        sequence_defaults: BTreeMap<usize, GlobalId>,
+    ) -> Result<MirRelationExpr, AdapterError> {
+        if sequence_defaults.is_empty() {
+            return Ok(values);
+        }
+        let count = Coordinator::constant_row_count(&values)?;
+        let mut sequence_values = BTreeMap::new();
+        for (column, sequence) in sequence_defaults {
+            let allocated = self
+                .send_without_session(|tx| Command::AllocateSequenceValues {
+                    id: sequence,
+                    count,
+                    tx,
+                })
+                .await?;
+            sequence_values.insert(column, allocated);
+        }
+        Coordinator::fill_sequence_values(values, sequence_values)
+    }
+
     /// Gets the current value of all system variables.
     pub async fn get_system_vars(&mut self) -> Result<GetVariablesResponse, AdapterError> {
         let conn_id = self.session().conn_id().clone();
@@ -710,6 +745,7 @@ impl SessionClient {
                 | Command::PrivilegedCancelRequest { .. }
                 | Command::GetSystemVars { .. }
                 | Command::SetSystemVars { .. }
+                | Command::AllocateSequenceValues { .. }
                 | Command::Terminate { .. }
                 | Command::RetireExecute { .. } => {}
             };
@@ -35,7 +35,10 @@ use mz_sql::plan::{
 };
 use mz_sql::session::user::User;
 use mz_sql::session::vars::Var;
-use mz_sql_parser::ast::{AlterObjectRenameStatement, AlterOwnerStatement, DropObjectsStatement};
+use mz_sql_parser::ast::{
+    AlterObjectRenameStatement, AlterOwnerStatement, AlterRetainHistoryStatement,
+    DropObjectsStatement,
+};
 use mz_storage_client::controller::MonotonicAppender;
 use tokio::sync::{mpsc, oneshot, watch};
 use uuid::Uuid;
@@ -121,6 +124,14 @@ pub enum Command {
         tx: oneshot::Sender<Result<(), AdapterError>>,
     },
 
+    /// Allocates the next `count` values of the sequence `id`, for a `COPY FROM` that omits
+    /// columns that default to it.
+    AllocateSequenceValues {
+        id: GlobalId,
+        count: usize,
+        tx: oneshot::Sender<Result<Vec<i64>, AdapterError>>,
+    },
+
     Terminate {
         conn_id: ConnectionId,
         tx: Option<oneshot::Sender<Result<(), AdapterError>>>,
@@ -150,6 +161,7 @@ impl Command {
             | Command::Terminate { .. }
             | Command::GetSystemVars { .. }
             | Command::SetSystemVars { .. }
+            | Command::AllocateSequenceValues { .. }
             | Command::RetireExecute { .. } => None,
         }
     }
@@ -165,6 +177,7 @@ impl Command {
             | Command::Terminate { .. }
             | Command::GetSystemVars { .. }
             | Command::SetSystemVars { .. }
+            | Command::AllocateSequenceValues { .. }
             | Command::RetireExecute { .. } => None,
         }
     }
@@ -483,10 +496,14 @@ pub enum ExecuteResponse {
     CreatedCluster,
     /// The requested cluster replica was created.
     CreatedClusterReplica,
+    /// The requested function was created.
+    CreatedFunction,
     /// The requested index was created.
     CreatedIndex,
     /// The requested secret was created.
     CreatedSecret,
+    /// The requested sequence was created.
+    CreatedSequence,
     /// The requested sink was created.
     CreatedSink,
     /// The requested source was created.
@@ -509,6 +526,8 @@ pub enum ExecuteResponse {
     Deleted(usize),
     /// The temporary objects associated with the session have been discarded.
     DiscardedTemp,
+    /// The sequence state associated with the session has been discarded.
+    DiscardedSequences,
     /// All state associated with the session has been discarded.
     DiscardedAll,
     /// The requested object was dropped.
@@ -533,6 +552,9 @@ pub enum ExecuteResponse {
     GrantedRole,
     /// The specified number of rows were inserted into the requested table.
     Inserted(usize),
+    /// The specified number of rows were inserted, updated or deleted by a
+    /// `MERGE` into the requested table.
+    Merged(usize),
     /// The specified prepared statement was created.
     Prepare,
     /// A user-requested warning was raised.
@@ -612,7 +634,8 @@ impl TryFrom<&Statement<Raw>> for ExecuteResponse {
                 ExecuteResponse::DroppedObject((*object_type).into())
             }
             Statement::AlterObjectRename(AlterObjectRenameStatement { object_type, .. })
-            | Statement::AlterOwner(AlterOwnerStatement { object_type, .. }) => {
+            | Statement::AlterOwner(AlterOwnerStatement { object_type, .. })
+            | Statement::AlterRetainHistory(AlterRetainHistoryStatement { object_type, .. }) => {
                 ExecuteResponse::AlteredObject((*object_type).into())
             }
             _ => return Err(()),
@@ -658,8 +681,10 @@ impl TryInto<ExecuteResponse> for ExecuteResponseKind {
             ExecuteResponseKind::CreatedClusterReplica => {
                 Ok(ExecuteResponse::CreatedClusterReplica)
             }
+            ExecuteResponseKind::CreatedFunction => Ok(ExecuteResponse::CreatedFunction),
             ExecuteResponseKind::CreatedIndex => Ok(ExecuteResponse::CreatedIndex),
             ExecuteResponseKind::CreatedSecret => Ok(ExecuteResponse::CreatedSecret),
+            ExecuteResponseKind::CreatedSequence => Ok(ExecuteResponse::CreatedSequence),
             ExecuteResponseKind::CreatedSink => Ok(ExecuteResponse::CreatedSink),
             ExecuteResponseKind::CreatedSource => Ok(ExecuteResponse::CreatedSource),
             ExecuteResponseKind::CreatedTable => Ok(ExecuteResponse::CreatedTable),
@@ -673,6 +698,7 @@ impl TryInto<ExecuteResponse> for ExecuteResponseKind {
             ExecuteResponseKind::DeclaredCursor => Ok(ExecuteResponse::DeclaredCursor),
             ExecuteResponseKind::Deleted => Err(()),
             ExecuteResponseKind::DiscardedTemp => Ok(ExecuteResponse::DiscardedTemp),
+            ExecuteResponseKind::DiscardedSequences => Ok(ExecuteResponse::DiscardedSequences),
             ExecuteResponseKind::DiscardedAll => Ok(ExecuteResponse::DiscardedAll),
             ExecuteResponseKind::DroppedObject => Err(()),
             ExecuteResponseKind::DroppedOwned => Ok(ExecuteResponse::DroppedOwned),
@@ -681,6 +707,7 @@ impl TryInto<ExecuteResponse> for ExecuteResponseKind {
             ExecuteResponseKind::GrantedPrivilege => Ok(ExecuteResponse::GrantedPrivilege),
             ExecuteResponseKind::GrantedRole => Ok(ExecuteResponse::GrantedRole),
             ExecuteResponseKind::Inserted => Err(()),
+            ExecuteResponseKind::Merged => Err(()),
             ExecuteResponseKind::Prepare => Ok(ExecuteResponse::Prepare),
             ExecuteResponseKind::Raised => Ok(ExecuteResponse::Raised),
             ExecuteResponseKind::ReassignOwned => Ok(ExecuteResponse::ReassignOwned),
@@ -719,8 +746,10 @@ impl ExecuteResponse {
             CreatedRole => Some("CREATE ROLE".into()),
             CreatedCluster { .. } => Some("CREATE CLUSTER".into()),
             CreatedClusterReplica { .. } => Some("CREATE CLUSTER REPLICA".into()),
+            CreatedFunction { .. } => Some("CREATE FUNCTION".into()),
             CreatedIndex { .. } => Some("CREATE INDEX".into()),
             CreatedSecret { .. } => Some("CREATE SECRET".into()),
+            CreatedSequence { .. } => Some("CREATE SEQUENCE".into()),
             CreatedSink { .. } => Some("CREATE SINK".into()),
             CreatedSource { .. } => Some("CREATE SOURCE".into()),
             CreatedTable { .. } => Some("CREATE TABLE".into()),
@@ -732,6 +761,7 @@ impl ExecuteResponse {
             DeclaredCursor => Some("DECLARE CURSOR".into()),
             Deleted(n) => Some(format!("DELETE {}", n)),
             DiscardedTemp => Some("DISCARD TEMP".into()),
+            DiscardedSequences => Some("DISCARD SEQUENCES".into()),
             DiscardedAll => Some("DISCARD ALL".into()),
             DroppedObject(o) => Some(format!("DROP {o}")),
             DroppedOwned => Some("DROP OWNED".into()),
@@ -749,6 +779,7 @@ impl ExecuteResponse {
                 // have OIDs.
                 Some(format!("INSERT 0 {}", n))
             }
+            Merged(n) => Some(format!("MERGE {}", n)),
             Prepare => Some("PREPARE".into()),
             Raised => Some("RAISE".into()),
             ReassignOwned => Some("REASSIGN OWNED".into()),
@@ -781,6 +812,7 @@ impl ExecuteResponse {
             | AlterOwner
             | AlterItemRename
             | AlterNoop
+            | AlterRetainHistory
             | AlterSecret
             | AlterSink
             | AlterSource
@@ -803,12 +835,14 @@ impl ExecuteResponse {
             CommitTransaction => vec![TransactionCommitted, TransactionRolledBack],
             CreateConnection => vec![CreatedConnection],
             CreateDatabase => vec![CreatedDatabase],
+            CreateFunction => vec![CreatedFunction],
             CreateSchema => vec![CreatedSchema],
             CreateRole => vec![CreatedRole],
             CreateCluster => vec![CreatedCluster],
             CreateClusterReplica => vec![CreatedClusterReplica],
             CreateSource | CreateSources => vec![CreatedSource],
             CreateSecret => vec![CreatedSecret],
+            CreateSequence => vec![CreatedSequence],
             CreateSink => vec![CreatedSink],
             CreateTable => vec![CreatedTable],
             CreateView => vec![CreatedView],
@@ -818,6 +852,7 @@ impl ExecuteResponse {
             PlanKind::Deallocate => vec![ExecuteResponseKind::Deallocate],
             Declare => vec![DeclaredCursor],
             DiscardTemp => vec![DiscardedTemp],
+            DiscardSequences => vec![DiscardedSequences],
             DiscardAll => vec![DiscardedAll],
             DropObjects => vec![DroppedObject],
             DropOwned => vec![DroppedOwned],
@@ -829,6 +864,7 @@ impl ExecuteResponse {
             Execute | ReadThenWrite => vec![
                 Deleted,
                 Inserted,
+                Merged,
                 SendingRows,
                 SendingRowsImmediate,
                 Updated,
@@ -86,7 +86,9 @@ use mz_compute_client::types::dataflows::DataflowDescription;
 use mz_controller::clusters::{
     ClusterConfig, ClusterEvent, ClusterId, CreateReplicaConfig, ReplicaId,
 };
-use mz_expr::{MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr, RowSetFinishing};
+use mz_expr::{
+    MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr, OptimizerHints, RowSetFinishing,
+};
 use mz_orchestrator::ServiceProcessMetrics;
 use mz_ore::cast::CastFrom;
 use mz_ore::metrics::{MetricsFutureExt, MetricsRegistry};
@@ -97,6 +99,7 @@ use mz_ore::thread::JoinHandleExt;
 use mz_ore::tracing::{OpenTelemetryContext, TracingHandle};
 use mz_ore::{soft_assert_or_log, stack, task};
 use mz_persist_client::usage::{ShardsUsageReferenced, StorageUsageClient};
+use mz_postgres_util::desc::PostgresTableDesc;
 use mz_repr::explain::ExplainFormat;
 use mz_repr::role_id::RoleId;
 use mz_repr::{Datum, GlobalId, RelationType, Row, Timestamp};
@@ -132,7 +135,9 @@ use crate::catalog::{
 use crate::client::{Client, ConnectionId, Handle};
 use crate::command::{Canceled, Command, ExecuteResponse};
 use crate::config::SystemParameterSyncConfig;
-use crate::coord::appends::{Deferred, PendingWriteTxn};
+use crate::coord::appends::{
+    Deferred, PendingWriteTxn, UniqueConstraintValidation, UniqueKeys, UniqueKeysLoaded,
+};
 use crate::coord::dataflows::dataflow_import_id_bundle;
 use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::peek::PendingPeek;
@@ -162,8 +167,10 @@ mod ddl;
 mod indexes;
 mod introspection;
 mod message_handler;
+mod plan_fingerprints;
 mod read_policy;
 mod sequencer;
+mod source_schema_changes;
 mod sql;
 
 // TODO: We can have only two consts here, instead of three, once there exists a `const` way to
@@ -186,6 +193,19 @@ pub const DEFAULT_LOGICAL_COMPACTION_WINDOW: Duration =
 pub const DEFAULT_LOGICAL_COMPACTION_WINDOW_TS: mz_repr::Timestamp =
     Timestamp::new(DEFAULT_LOGICAL_COMPACTION_WINDOW_MILLIS);
 
+/// Converts an optional custom logical compaction window, e.g. one set via
+/// `RETAIN HISTORY`, into the lag used by read policies, falling back to
+/// `DEFAULT_LOGICAL_COMPACTION_WINDOW_TS`.
+pub(crate) fn compaction_window_ts(window: Option<Duration>) -> mz_repr::Timestamp {
+    match window {
+        Some(window) => Timestamp::new(u64::try_from(window.as_millis()).unwrap_or_else(|_| {
+            tracing::error!("absurd logical compaction window: {window:?}");
+            u64::MAX
+        })),
+        None => DEFAULT_LOGICAL_COMPACTION_WINDOW_TS,
+    }
+}
+
 #[derive(Debug)]
 pub enum Message<T = mz_repr::Timestamp> {
     Command(Command),
@@ -194,6 +214,14 @@ pub enum Message<T = mz_repr::Timestamp> {
     CreateConnectionValidationReady(CreateConnectionValidationReady),
     SinkConnectionReady(SinkConnectionReady),
     WriteLockGrant(tokio::sync::OwnedMutexGuard<()>),
+    /// Validates a user transaction against the unique constraints of the tables it writes to,
+    /// now that the write lock was granted.
+    UniqueConstraintValidationReady {
+        validation: UniqueConstraintValidation,
+        write_lock_guard: OwnedMutexGuard<()>,
+    },
+    /// Validates a user transaction now that the keys of the tables it writes to were read.
+    UniqueKeysLoaded(UniqueKeysLoaded),
     /// Initiates a group commit.
     GroupCommitInitiate(Span),
     /// Makes a group commit visible to all clients.
@@ -213,6 +241,16 @@ pub enum Message<T = mz_repr::Timestamp> {
     LinearizeReads(Vec<PendingReadTxn>),
     StorageUsageFetch,
     StorageUsageUpdate(ShardsUsageReferenced),
+    /// Allocates the next `count` values of the sequence `id` for an insert whose rows were read.
+    AllocateSequenceValues {
+        id: GlobalId,
+        count: usize,
+        tx: oneshot::Sender<Result<Vec<i64>, AdapterError>>,
+    },
+    PgSourceSchemaChangeCheck,
+    /// The tables of PostgreSQL sources that gained columns upstream, with their upstream
+    /// definitions.
+    PgSourceSchemaChanges(Vec<(GlobalId, Vec<PostgresTableDesc>)>),
     RealTimeRecencyTimestamp {
         conn_id: ConnectionId,
         real_time_recency_ts: Timestamp,
@@ -248,6 +286,8 @@ impl Message {
             CreateConnectionValidationReady(_) => "create_connection_validation_ready",
             SinkConnectionReady(_) => "sink_connection_ready",
             WriteLockGrant(_) => "write_lock_grant",
+            UniqueConstraintValidationReady { .. } => "unique_constraint_validation_ready",
+            UniqueKeysLoaded(_) => "unique_keys_loaded",
             GroupCommitInitiate(..) => "group_commit_initiate",
             GroupCommitApply(..) => "group_commit_apply",
             AdvanceTimelines => "advance_timelines",
@@ -256,6 +296,9 @@ impl Message {
             LinearizeReads(_) => "linearize_reads",
             StorageUsageFetch => "storage_usage_fetch",
             StorageUsageUpdate(_) => "storage_usage_update",
+            AllocateSequenceValues { .. } => "allocate_sequence_values",
+            PgSourceSchemaChangeCheck => "pg_source_schema_change_check",
+            PgSourceSchemaChanges(_) => "pg_source_schema_changes",
             RealTimeRecencyTimestamp { .. } => "real_time_recency_timestamp",
             RetireExecute { .. } => "retire_execute",
             ExecuteSingleStatementTransaction { .. } => "execute_single_statement_transaction",
@@ -324,6 +367,7 @@ pub enum RealTimeRecencyContext {
         index_id: GlobalId,
         timeline_context: TimelineContext,
         source_ids: BTreeSet<GlobalId>,
+        id_bundle: CollectionIdBundle,
         in_immediate_multi_stmt_txn: bool,
         key: Vec<MirScalarExpr>,
         typ: RelationType,
@@ -379,6 +423,7 @@ pub struct PeekStageOptimize {
     target_replica: Option<ReplicaId>,
     timeline_context: TimelineContext,
     in_immediate_multi_stmt_txn: bool,
+    hints: OptimizerHints,
 }
 
 #[derive(Debug)]
@@ -408,7 +453,7 @@ pub struct PeekStageFinish {
     copy_to: Option<CopyFormat>,
     dataflow: DataflowDescription<OptimizedMirRelationExpr>,
     cluster_id: ClusterId,
-    id_bundle: Option<CollectionIdBundle>,
+    id_bundle: CollectionIdBundle,
     when: QueryWhen,
     target_replica: Option<ReplicaId>,
     view_id: GlobalId,
@@ -944,6 +989,12 @@ pub struct Coordinator {
     write_lock_wait_group: VecDeque<Deferred>,
     /// Pending writes waiting for a group commit.
     pending_writes: Vec<PendingWriteTxn>,
+    /// The keys of the tables whose unique constraints are enforced, for the tables that writes
+    /// have been validated against. Maintained as writes are applied.
+    unique_keys: BTreeMap<GlobalId, UniqueKeys>,
+    /// For each sequence, values that have been durably allocated but not yet
+    /// handed out, in the order in which they are to be handed out.
+    sequence_caches: BTreeMap<GlobalId, VecDeque<i64>>,
     /// For the realtime timeline, an explicit SELECT or INSERT on a table will bump the
     /// table's timestamps, but there are cases where timestamps are not bumped but
     /// we expect the closed timestamps to advance (`AS OF X`, SUBSCRIBing views over
@@ -1413,13 +1464,16 @@ impl Coordinator {
                         .resolve_full_name(entry.name(), entry.conn_id())
                         .to_string();
 
-                    let mut builder = self.dataflow_builder(mview.cluster_id);
+                    let mut builder = self
+                        .dataflow_builder(mview.cluster_id)
+                        .with_hints(&mview.hints);
                     let (mut df, df_metainfo) = builder.build_materialized_view(
                         entry.id(),
                         internal_view_id,
                         debug_name,
                         &mview.optimized_expr,
                         &mview.desc,
+                        mview.refresh_schedule.clone(),
                     )?;
 
                     // Note: ideally, the optimized_plan should be computed and
@@ -1518,7 +1572,9 @@ impl Coordinator {
                 CatalogItem::Log(_)
                 | CatalogItem::Type(_)
                 | CatalogItem::Func(_)
-                | CatalogItem::Secret(_) => {}
+                | CatalogItem::Secret(_)
+                | CatalogItem::Sequence(_)
+                | CatalogItem::Function(_) => {}
             }
         }
 
@@ -1551,6 +1607,9 @@ impl Coordinator {
             self.initialize_read_policies(&policies, Some(ts)).await;
         }
 
+        info!("coordinator init: comparing plans with the previous run");
+        builtin_table_updates.extend(self.bootstrap_plan_fingerprints().await?);
+
         info!("coordinator init: announcing completion of initialization to controller");
         // Announce the completion of initialization.
         self.controller.initialization_complete();
@@ -1836,6 +1895,7 @@ impl Coordinator {
         });
 
         self.schedule_storage_usage_collection();
+        self.schedule_pg_source_schema_change_check();
         flags::tracing_config(self.catalog.system_config()).apply(&self.tracing_handle);
 
         // Report if the handling of a single message takes longer than this threshold.
@@ -2114,6 +2174,8 @@ pub async fn serve(
                 write_lock: Arc::new(tokio::sync::Mutex::new(())),
                 write_lock_wait_group: VecDeque::new(),
                 pending_writes: Vec::new(),
+                unique_keys: BTreeMap::new(),
+                sequence_caches: BTreeMap::new(),
                 advance_timelines_interval,
                 secrets_controller,
                 caching_secrets_reader,
@@ -9,23 +9,31 @@
 
 //! Logic and types for all appends executed by the [`Coordinator`].
 
+use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::time::Duration;
 
+use bytes::BytesMut;
 use derivative::Derivative;
+use futures::stream::BoxStream;
+use futures::StreamExt;
 use mz_ore::task;
 use mz_ore::vec::VecExt;
-use mz_repr::{Diff, GlobalId, Row, Timestamp};
-use mz_sql::plan::Plan;
+use mz_repr::{Datum, Diff, GlobalId, RelationDesc, Row, Timestamp};
+use mz_sql::plan::{Plan, UniqueConstraint};
 use mz_storage_client::client::Update;
+use mz_storage_client::controller::StorageError;
 use tokio::sync::OwnedMutexGuard;
 use tracing::{warn, Instrument, Span};
 
-use crate::catalog::BuiltinTableUpdate;
+use crate::catalog::{BuiltinTableUpdate, CatalogItem};
+use crate::coord::id_bundle::CollectionIdBundle;
+use crate::coord::read_policy::ReadHolds;
 use crate::coord::timeline::WriteTimestamp;
 use crate::coord::{Coordinator, Message, PendingTxn, PlanValidity};
-use crate::session::{Session, WriteOp};
+use crate::error::AdapterError;
+use crate::session::{EndTransactionAction, Session, WriteOp};
 use crate::util::{CompletedClientTransmitter, ResultExt};
 use crate::ExecuteContext;
 
@@ -56,6 +64,30 @@ pub(crate) enum BuiltinTableUpdateSource {
     Background,
 }
 
+/// The writes of a user transaction that must be validated against the unique constraints of
+/// the tables they target before they are submitted to group commit.
+#[derive(Debug)]
+pub struct UniqueConstraintValidation {
+    writes: Vec<WriteOp>,
+    pending_txn: PendingTxn,
+}
+
+/// The keys of the tables targeted by a [`UniqueConstraintValidation`], read from their
+/// contents.
+#[derive(Debug)]
+pub struct UniqueKeysLoaded {
+    validation: UniqueConstraintValidation,
+    write_lock_guard: OwnedMutexGuard<()>,
+    /// The read holds on the tables that were read.
+    read_holds: ReadHolds<Timestamp>,
+    result: Result<BTreeMap<GlobalId, UniqueKeys>, AdapterError>,
+}
+
+/// The number of rows with each key of a table, per unique constraint of the table.
+///
+/// Keys whose rows contain `NULL`s that are distinct from each other are not included.
+pub(crate) type UniqueKeys = Vec<BTreeMap<Row, Diff>>;
+
 /// A pending write transaction that will be committing during the next group commit.
 #[derive(Debug)]
 pub(crate) enum PendingWriteTxn {
@@ -296,6 +328,7 @@ impl Coordinator {
         for (_, updates) in &mut appends {
             differential_dataflow::consolidation::consolidate(updates);
         }
+        self.apply_unique_keys(&appends);
         // Add table advancements for all tables.
         for table in self.catalog().entries().filter(|entry| entry.is_table()) {
             appends.entry(table.id()).or_default();
@@ -388,6 +421,313 @@ impl Coordinator {
             .expect("sending to self.internal_cmd_tx cannot fail");
     }
 
+    /// Submits the writes of a user transaction to be executed during the next group commit.
+    ///
+    /// If the writes target tables whose unique constraints are enforced, they are first
+    /// validated against the contents of those tables, which requires the write lock. If the lock
+    /// isn't available, validation is deferred until it is.
+    pub(crate) fn submit_user_write(
+        &mut self,
+        writes: Vec<WriteOp>,
+        write_lock_guard: Option<OwnedMutexGuard<()>>,
+        pending_txn: PendingTxn,
+    ) {
+        if pending_txn.response.is_err() || !self.enforces_unique_constraints(&writes) {
+            self.submit_write(PendingWriteTxn::User {
+                writes,
+                write_lock_guard,
+                pending_txn,
+            });
+            return;
+        }
+
+        let validation = UniqueConstraintValidation {
+            writes,
+            pending_txn,
+        };
+        let write_lock_guard =
+            write_lock_guard.or_else(|| Arc::clone(&self.write_lock).try_lock_owned().ok());
+        match write_lock_guard {
+            Some(write_lock_guard) => {
+                self.validate_unique_constraints(validation, write_lock_guard)
+            }
+            None => {
+                let internal_cmd_tx = self.internal_cmd_tx.clone();
+                let write_lock = Arc::clone(&self.write_lock);
+                task::spawn(|| "unique_constraint_validation_write_lock", async move {
+                    let write_lock_guard = write_lock.lock_owned().await;
+                    // It is not an error for this lock to be released after `internal_cmd_rx` to
+                    // be dropped.
+                    let result = internal_cmd_tx.send(Message::UniqueConstraintValidationReady {
+                        validation,
+                        write_lock_guard,
+                    });
+                    if let Err(e) = result {
+                        warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+                    }
+                });
+            }
+        }
+    }
+
+    /// Reports whether any of `writes` targets a table whose unique constraints are enforced.
+    fn enforces_unique_constraints(&self, writes: &[WriteOp]) -> bool {
+        writes
+            .iter()
+            .any(|WriteOp { id, .. }| self.enforced_unique_constraints(id).is_some())
+    }
+
+    /// Returns the description and the unique constraints of the table `id` if writes to it must
+    /// satisfy them, which is the case if `enable_table_constraint_enforcement` is set.
+    fn enforced_unique_constraints(
+        &self,
+        id: &GlobalId,
+    ) -> Option<(&RelationDesc, &[UniqueConstraint])> {
+        if !self
+            .catalog()
+            .system_config()
+            .enable_table_constraint_enforcement()
+        {
+            return None;
+        }
+        match self.catalog().try_get_entry(id).map(|entry| entry.item()) {
+            Some(CatalogItem::Table(table)) => {
+                if table.constraints.unique.is_empty() {
+                    None
+                } else {
+                    Some((&table.desc, &table.constraints.unique))
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Validates the writes of `validation` against the unique constraints of the tables they
+    /// target, and submits them if they don't violate any.
+    ///
+    /// Writes are validated against the keys of the tables, which are maintained in
+    /// `unique_keys` as writes are applied, so validating a write doesn't read the tables it
+    /// targets. The keys of a table are read from its contents, off the main loop, the first time
+    /// a write to it is validated.
+    ///
+    /// Holding the write lock guarantees that all previous writes to user tables are applied
+    /// and that no other writes to them are in flight, because all writes to tables with enforced
+    /// unique constraints are validated and submitted while holding it.
+    pub(crate) fn validate_unique_constraints(
+        &mut self,
+        validation: UniqueConstraintValidation,
+        write_lock_guard: OwnedMutexGuard<()>,
+    ) {
+        // The change in the number of rows with each key, per table and constraint. Only keys
+        // that gain rows can become duplicates.
+        let mut deltas: BTreeMap<(GlobalId, usize), BTreeMap<Row, Diff>> = BTreeMap::new();
+        let mut unknown = BTreeMap::new();
+        for WriteOp { id, rows } in &validation.writes {
+            let Some((_, unique_constraints)) = self.enforced_unique_constraints(id) else {
+                continue;
+            };
+            for (i, constraint) in unique_constraints.iter().enumerate() {
+                let delta = deltas.entry((*id, i)).or_default();
+                for (row, diff) in rows {
+                    if let Some(key) = unique_key(constraint, row) {
+                        *delta.entry(key).or_default() += diff;
+                    }
+                }
+                delta.retain(|_, diff| *diff > 0);
+                if !delta.is_empty() && !self.unique_keys.contains_key(id) {
+                    unknown.insert(*id, unique_constraints.to_vec());
+                }
+            }
+        }
+
+        if !unknown.is_empty() {
+            self.load_unique_keys(validation, write_lock_guard, unknown);
+            return;
+        }
+
+        let UniqueConstraintValidation {
+            writes,
+            pending_txn,
+        } = validation;
+        match self.check_unique_constraints(&deltas) {
+            Ok(()) => self.submit_write(PendingWriteTxn::User {
+                writes,
+                write_lock_guard: Some(write_lock_guard),
+                pending_txn,
+            }),
+            Err(e) => {
+                // The transaction is rolled back, so none of its writes are applied.
+                let (ctx, result) = CompletedClientTransmitter::new(
+                    pending_txn.ctx,
+                    Err(e),
+                    EndTransactionAction::Rollback,
+                )
+                .finalize();
+                ctx.retire(result);
+            }
+        }
+    }
+
+    /// Verifies that applying `deltas`, the change in the number of rows with each key per table
+    /// and unique constraint, to the keys of the tables doesn't leave more than one row with any
+    /// key.
+    fn check_unique_constraints(
+        &self,
+        deltas: &BTreeMap<(GlobalId, usize), BTreeMap<Row, Diff>>,
+    ) -> Result<(), AdapterError> {
+        for ((id, i), delta) in deltas {
+            let Some(keys) = self.unique_keys.get(id) else {
+                continue;
+            };
+            for (key, diff) in delta {
+                let count = keys[*i].get(key).copied().unwrap_or(0);
+                if count + diff > 1 {
+                    let (desc, unique_constraints) = self
+                        .enforced_unique_constraints(id)
+                        .expect("validated table");
+                    let constraint = &unique_constraints[*i];
+                    return Err(AdapterError::UniqueViolation {
+                        constraint: constraint.name.clone(),
+                        key: format_key(desc, constraint, key),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the keys of the tables in `constraints` from their contents, off the main loop, and
+    /// validates `validation` once they are read.
+    fn load_unique_keys(
+        &mut self,
+        validation: UniqueConstraintValidation,
+        write_lock_guard: OwnedMutexGuard<()>,
+        constraints: BTreeMap<GlobalId, Vec<UniqueConstraint>>,
+    ) {
+        // Read the tables at the latest timestamp, which includes all previous writes, and hold
+        // back their compaction until they are read.
+        let read_ts = self.get_local_read_ts();
+        let read_holds = self.acquire_read_holds(
+            read_ts,
+            &CollectionIdBundle {
+                storage_ids: constraints.keys().copied().collect(),
+                compute_ids: BTreeMap::new(),
+            },
+        );
+        let snapshots: Result<Vec<_>, _> = constraints
+            .keys()
+            .map(|id| {
+                let snapshot = self.controller.storage.snapshot_and_stream(*id, read_ts)?;
+                Ok((*id, snapshot))
+            })
+            .collect();
+        let snapshots = match snapshots {
+            Ok(snapshots) => snapshots,
+            Err(e) => {
+                self.finish_loading_unique_keys(UniqueKeysLoaded {
+                    validation,
+                    write_lock_guard,
+                    read_holds,
+                    result: Err(AdapterError::Storage(e)),
+                });
+                return;
+            }
+        };
+
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "unique_constraint_keys", async move {
+            let result = read_unique_keys(snapshots, &constraints).await;
+            // It is not an error for the keys to be read after `internal_cmd_rx` is dropped.
+            let result = internal_cmd_tx.send(Message::UniqueKeysLoaded(UniqueKeysLoaded {
+                validation,
+                write_lock_guard,
+                read_holds,
+                result,
+            }));
+            if let Err(e) = result {
+                warn!("internal_cmd_rx dropped before we could send: {:?}", e);
+            }
+        });
+    }
+
+    /// Records the keys read for a [`UniqueConstraintValidation`] and validates it, or rolls back
+    /// its transaction if they couldn't be read.
+    pub(crate) fn finish_loading_unique_keys(&mut self, loaded: UniqueKeysLoaded) {
+        let UniqueKeysLoaded {
+            validation,
+            write_lock_guard,
+            read_holds,
+            result,
+        } = loaded;
+        self.release_read_hold(&read_holds);
+        match result {
+            Ok(keys) => {
+                for (id, keys) in keys {
+                    // Tables dropped while their keys were read no longer need them.
+                    if self.catalog().try_get_entry(&id).is_some() {
+                        self.unique_keys.insert(id, keys);
+                    }
+                }
+                self.validate_unique_constraints(validation, write_lock_guard);
+            }
+            Err(e) => {
+                // The transaction is rolled back, so none of its writes are applied.
+                let (ctx, result) = CompletedClientTransmitter::new(
+                    validation.pending_txn.ctx,
+                    Err(e),
+                    EndTransactionAction::Rollback,
+                )
+                .finalize();
+                ctx.retire(result);
+            }
+        }
+    }
+
+    /// Applies the consolidated `appends` of a group commit to the keys of the tables they write
+    /// to, so that the keys keep reflecting the contents of the tables.
+    ///
+    /// Writes are only applied here, rather than when they are submitted, because submitted
+    /// writes can still be cancelled before they are committed.
+    fn apply_unique_keys(&mut self, appends: &BTreeMap<GlobalId, Vec<(Row, Diff)>>) {
+        if !self
+            .catalog()
+            .system_config()
+            .enable_table_constraint_enforcement()
+        {
+            // Without enforcement, the keys aren't needed and would otherwise only take up memory.
+            self.unique_keys.clear();
+            return;
+        }
+        for (id, updates) in appends {
+            let Some(keys) = self.unique_keys.get_mut(id) else {
+                continue;
+            };
+            let Some(CatalogItem::Table(table)) =
+                self.catalog.try_get_entry(id).map(|entry| entry.item())
+            else {
+                continue;
+            };
+            for (constraint, keys) in table.constraints.unique.iter().zip(keys.iter_mut()) {
+                for (row, diff) in updates {
+                    let Some(key) = unique_key(constraint, row) else {
+                        continue;
+                    };
+                    match keys.entry(key) {
+                        Entry::Occupied(mut entry) => {
+                            *entry.get_mut() += diff;
+                            if *entry.get() == 0 {
+                                entry.remove();
+                            }
+                        }
+                        Entry::Vacant(entry) => {
+                            entry.insert(*diff);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     /// Submit a write to be executed during the next group commit and trigger a group commit.
     pub(crate) fn submit_write(&mut self, pending_write_txn: PendingWriteTxn) {
         self.pending_writes.push(pending_write_txn);
@@ -462,3 +802,71 @@ impl Coordinator {
         })
     }
 }
+
+/// Reads the keys of the tables read by `snapshots`, for each of the unique constraints of the
+/// tables in `constraints`.
+async fn read_unique_keys(
+    snapshots: Vec<(
+        GlobalId,
+        BoxStream<'static, Result<(Row, Diff), StorageError>>,
+    )>,
+    constraints: &BTreeMap<GlobalId, Vec<UniqueConstraint>>,
+) -> Result<BTreeMap<GlobalId, UniqueKeys>, AdapterError> {
+    let mut keys = BTreeMap::new();
+    for (id, mut snapshot) in snapshots {
+        let table_constraints = &constraints[&id];
+        let mut table_keys: UniqueKeys = vec![BTreeMap::new(); table_constraints.len()];
+        while let Some(update) = snapshot.next().await {
+            let (row, diff) = update?;
+            for (constraint, keys) in table_constraints.iter().zip(table_keys.iter_mut()) {
+                if let Some(key) = unique_key(constraint, &row) {
+                    *keys.entry(key).or_default() += diff;
+                }
+            }
+        }
+        for keys in &mut table_keys {
+            keys.retain(|_, count| *count != 0);
+        }
+        keys.insert(id, table_keys);
+    }
+    Ok(keys)
+}
+
+/// Returns the key of `row` for the unique constraint `constraint`, or `None` if the row can't
+/// conflict with any other row because its key contains `NULL`s.
+fn unique_key(constraint: &UniqueConstraint, row: &Row) -> Option<Row> {
+    let datums = row.unpack();
+    let key = constraint.columns.iter().map(|i| datums[*i]);
+    if !constraint.nulls_not_distinct && key.clone().any(|datum| datum.is_null()) {
+        return None;
+    }
+    Some(Row::pack(key))
+}
+
+/// Formats `key` like PostgreSQL does in unique violation errors, e.g. `(a, b)=(1, 2)`.
+fn format_key(desc: &RelationDesc, constraint: &UniqueConstraint, key: &Row) -> String {
+    let columns = constraint
+        .columns
+        .iter()
+        .map(|i| desc.get_name(*i).as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let values = constraint
+        .columns
+        .iter()
+        .zip(key.iter())
+        .map(|(i, datum)| match datum {
+            Datum::Null => "null".to_string(),
+            datum => {
+                let typ = &desc.typ().column_types[*i].scalar_type;
+                let mut buf = BytesMut::new();
+                mz_pgrepr::Value::from_datum(datum, typ)
+                    .expect("non-null datum")
+                    .encode_text(&mut buf);
+                String::from_utf8_lossy(&buf).into_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("({columns})=({values})")
+}
@@ -20,7 +20,7 @@ use mz_repr::role_id::RoleId;
 use mz_sql::ast::{
     CopyRelation, CopyStatement, InsertSource, Query, Raw, SetExpr, Statement, SubscribeStatement,
 };
-use mz_sql::catalog::RoleAttributes;
+use mz_sql::catalog::{CatalogError, RoleAttributes};
 use mz_sql::names::{PartialItemName, ResolvedIds};
 use mz_sql::plan::{
     AbortTransactionPlan, CommitTransactionPlan, CreateRolePlan, Params, Plan, TransactionType,
@@ -134,6 +134,16 @@ impl Coordinator {
                 let _ = tx.send(Ok(vars));
             }
 
+            Command::AllocateSequenceValues { id, count, tx } => {
+                let result = match self.catalog().try_get_entry(&id) {
+                    Some(_) => self.allocate_sequence_values(id, count).await,
+                    None => Err(AdapterError::SqlCatalog(CatalogError::UnknownItem(
+                        id.to_string(),
+                    ))),
+                };
+                let _ = tx.send(result);
+            }
+
             Command::SetSystemVars { vars, conn_id, tx } => {
                 let mut ops = Vec::with_capacity(vars.len());
                 let conn = &self.active_conns[&conn_id];
@@ -512,10 +522,11 @@ impl Coordinator {
                                 body: SetExpr::Values(..),
                                 ..
                             }) | InsertSource::DefaultValues
-                        ) =>
+                        ) && insert_statement.on_conflict.is_none() =>
                     {
                         // Inserting from default? values statements
-                        // is always safe.
+                        // is always safe, unless conflicts require
+                        // reading the table.
                     }
 
                     // Statements below must by run singly (in Started).
@@ -526,6 +537,7 @@ impl Coordinator {
                     | Statement::AlterSetCluster(_)
                     | Statement::AlterObjectRename(_)
                     | Statement::AlterOwner(_)
+                    | Statement::AlterRetainHistory(_)
                     | Statement::AlterRole(_)
                     | Statement::AlterSecret(_)
                     | Statement::AlterSink(_)
@@ -537,11 +549,13 @@ impl Coordinator {
                     | Statement::CreateClusterReplica(_)
                     | Statement::CreateConnection(_)
                     | Statement::CreateDatabase(_)
+                    | Statement::CreateFunction(_)
                     | Statement::CreateIndex(_)
                     | Statement::CreateMaterializedView(_)
                     | Statement::CreateRole(_)
                     | Statement::CreateSchema(_)
                     | Statement::CreateSecret(_)
+                    | Statement::CreateSequence(_)
                     | Statement::CreateSink(_)
                     | Statement::CreateSource(_)
                     | Statement::CreateSubsource(_)
@@ -555,6 +569,7 @@ impl Coordinator {
                     | Statement::GrantPrivileges(_)
                     | Statement::GrantRole(_)
                     | Statement::Insert(_)
+                    | Statement::Merge(_)
                     | Statement::ReassignOwned(_)
                     | Statement::RevokePrivileges(_)
                     | Statement::RevokeRole(_)
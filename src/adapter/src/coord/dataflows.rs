@@ -31,14 +31,16 @@ use mz_controller::Controller;
 use mz_expr::visit::Visit;
 use mz_expr::{
     CollectionPlan, Id, MapFilterProject, MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr,
-    UnmaterializableFunc, RECURSION_LIMIT,
+    OptimizerHints, UnmaterializableFunc, RECURSION_LIMIT,
 };
 use mz_ore::cast::ReinterpretCast;
 use mz_ore::stack::{maybe_grow, CheckedRecursion, RecursionGuard, RecursionLimitError};
 use mz_repr::adt::array::ArrayDimension;
+use mz_repr::refresh_schedule::{RefreshEvery, RefreshSchedule};
 use mz_repr::role_id::RoleId;
-use mz_repr::{Datum, GlobalId, RelationDesc, Row, Timestamp};
+use mz_repr::{Datum, GlobalId, RelationDesc, Row, RowArena, Timestamp};
 use mz_sql::catalog::{CatalogRole, SessionCatalog};
+use mz_sql::plan::{RefreshEveryPlan, RefreshSchedulePlan};
 use mz_sql::rbac;
 use mz_transform::dataflow::DataflowMetainfo;
 use timely::progress::Antichain;
@@ -46,7 +48,7 @@ use timely::PartialOrder;
 use tracing::warn;
 
 use crate::catalog::{
-    Catalog, CatalogItem, CatalogState, DataSourceDesc, MaterializedView, Source, View,
+    Catalog, CatalogItem, CatalogState, DataSourceDesc, Log, MaterializedView, Source, View,
 };
 use crate::coord::ddl::CatalogTxn;
 use crate::coord::id_bundle::CollectionIdBundle;
@@ -95,6 +97,11 @@ pub struct DataflowBuilder<'a> {
     /// This can also be used to grab a handle to the storage abstraction, through
     /// its `storage_mut()` method.
     pub compute: ComputeInstanceSnapshot,
+    /// The optimizer hints applied to the dataflows built by this builder.
+    pub hints: OptimizerHints,
+    /// The indexes that must not be imported into the dataflows built by this
+    /// builder, because of `NO INDEX` hints.
+    ignored_indexes: BTreeSet<GlobalId>,
     recursion_guard: RecursionGuard,
 }
 
@@ -276,6 +283,8 @@ impl Coordinator {
             self.catalog()
                 .system_config()
                 .enable_monotonic_oneshot_selects(),
+            self.catalog().system_config().enable_windowed_reductions(),
+            self.catalog().system_config().enable_bounded_topk(),
         )
         .map_err(AdapterError::Internal)
     }
@@ -308,10 +317,44 @@ impl<'a> DataflowBuilder<'a> {
         Self {
             catalog,
             compute,
+            hints: OptimizerHints::default(),
+            ignored_indexes: BTreeSet::new(),
             recursion_guard: RecursionGuard::with_limit(RECURSION_LIMIT),
         }
     }
 
+    /// Applies the optimizer `hints` to the dataflows built by this builder.
+    ///
+    /// The indexes named in `NO INDEX` hints are hidden from the builder, so
+    /// that their collections are read from storage instead. Indexes on logs
+    /// that have no storage collection are the only way to read those logs,
+    /// so they are never hidden.
+    pub fn with_hints(mut self, hints: &OptimizerHints) -> Self {
+        self.ignored_indexes = hints
+            .no_index
+            .iter()
+            .filter(|id| match self.catalog.get_entry(id).item() {
+                CatalogItem::Index(index) => !matches!(
+                    self.catalog.get_entry(&index.on).item(),
+                    CatalogItem::Log(Log {
+                        has_storage_collection: false,
+                        ..
+                    })
+                ),
+                _ => false,
+            })
+            .copied()
+            .collect();
+        self.hints = hints.clone();
+        self
+    }
+
+    /// Reports whether the index with the given `id` is hidden from this
+    /// builder by a `NO INDEX` hint.
+    pub fn is_ignored_index(&self, id: &GlobalId) -> bool {
+        self.ignored_indexes.contains(id)
+    }
+
     /// Imports the view, source, or table with `id` into the provided
     /// dataflow description.
     fn import_into_dataflow(
@@ -446,6 +489,7 @@ impl<'a> DataflowBuilder<'a> {
             &mut dataflow,
             self,
             &mz_transform::EmptyStatisticsOracle,
+            &self.hints,
         )?;
 
         Ok((dataflow, dataflow_metainfo))
@@ -483,8 +527,12 @@ impl<'a> DataflowBuilder<'a> {
         dataflow.export_sink(id, sink_description);
 
         // Optimize the dataflow across views, and any other ways that appeal.
-        let dataflow_metainfo =
-            mz_transform::optimize_dataflow(dataflow, self, &mz_transform::EmptyStatisticsOracle)?;
+        let dataflow_metainfo = mz_transform::optimize_dataflow(
+            dataflow,
+            self,
+            &mz_transform::EmptyStatisticsOracle,
+            &self.hints,
+        )?;
 
         Ok(dataflow_metainfo)
     }
@@ -494,7 +542,8 @@ impl<'a> DataflowBuilder<'a> {
     /// For this, we first build a dataflow for the view expression, then we add
     /// a sink that writes that dataflow's output to storage. `internal_view_id`
     /// is the ID we assign to the view dataflow internally, so we can connect
-    /// the sink to it.
+    /// the sink to it. If the materialized view has a `refresh_schedule`, the
+    /// sink only advances its output at the scheduled refresh times.
     pub fn build_materialized_view(
         &mut self,
         exported_sink_id: GlobalId,
@@ -502,6 +551,7 @@ impl<'a> DataflowBuilder<'a> {
         debug_name: String,
         optimized_expr: &OptimizedMirRelationExpr,
         desc: &RelationDesc,
+        refresh_schedule: Option<RefreshSchedule>,
     ) -> Result<(DataflowDesc, DataflowMetainfo), AdapterError> {
         let mut dataflow = DataflowDesc::new(debug_name);
 
@@ -520,6 +570,7 @@ impl<'a> DataflowBuilder<'a> {
             }),
             with_snapshot: true,
             up_to: Antichain::default(),
+            refresh_schedule,
         };
 
         let dataflow_metainfo =
@@ -609,6 +660,8 @@ impl<'a> DataflowBuilder<'a> {
                     )
                 }
                 CatalogItem::Secret(_)
+                | CatalogItem::Sequence(_)
+                | CatalogItem::Function(_)
                 | CatalogItem::Type(_)
                 | CatalogItem::Connection(_)
                 | CatalogItem::Table(_)
@@ -711,6 +764,60 @@ pub fn prep_scalar_expr(
     }
 }
 
+/// Evaluates the times of a planned refresh schedule.
+///
+/// `REFRESH AT CREATION` and `REFRESH EVERY` options without `ALIGNED TO`
+/// refer to the creation time of the materialized view, at which calls to
+/// `mz_now()` are evaluated, too. When `creation` is `None`, e.g., when
+/// re-planning a materialized view whose times have already been evaluated,
+/// such references are rejected.
+pub fn eval_refresh_schedule(
+    state: &CatalogState,
+    plan: RefreshSchedulePlan,
+    creation: Option<(Timestamp, &Session)>,
+) -> Result<RefreshSchedule, AdapterError> {
+    let eval_time = |time: Option<MirScalarExpr>| {
+        let Some(mut time) = time else {
+            return match creation {
+                Some((creation_time, _)) => Ok(creation_time),
+                None => Err(AdapterError::Internal(
+                    "refresh time of an existing materialized view not evaluated".into(),
+                )),
+            };
+        };
+        let style = match creation {
+            Some((creation_time, session)) => ExprPrepStyle::OneShot {
+                logical_time: EvalTime::Time(creation_time),
+                session,
+            },
+            None => ExprPrepStyle::Index,
+        };
+        prep_scalar_expr(state, &mut time, style)?;
+        let temp_storage = RowArena::new();
+        let evaled = time.eval(&[], &temp_storage)?;
+        if evaled.is_null() {
+            coord_bail!("REFRESH time must not be null");
+        }
+        Ok(evaled.unwrap_mz_timestamp())
+    };
+
+    let mut schedule = RefreshSchedule::default();
+    for RefreshEveryPlan {
+        interval,
+        aligned_to,
+    } in plan.everies
+    {
+        schedule.everies.push(RefreshEvery {
+            interval,
+            aligned_to: eval_time(aligned_to)?,
+        });
+    }
+    for at in plan.ats {
+        schedule.ats.push(eval_time(at)?);
+    }
+    Ok(schedule)
+}
+
 fn eval_unmaterializable_func(
     state: &CatalogState,
     f: &UnmaterializableFunc,
@@ -113,6 +113,7 @@ impl Coordinator {
         let mut materialized_views_to_drop = vec![];
         let mut replication_slots_to_drop: Vec<(mz_postgres_util::Config, String)> = vec![];
         let mut secrets_to_drop = vec![];
+        let mut sequences_to_drop = vec![];
         let mut timelines_to_drop = vec![];
         let mut vpc_endpoints_to_drop = vec![];
         let mut clusters_to_drop = vec![];
@@ -179,6 +180,9 @@ impl Coordinator {
                         CatalogItem::Secret(_) => {
                             secrets_to_drop.push(*id);
                         }
+                        CatalogItem::Sequence(_) => {
+                            sequences_to_drop.push(*id);
+                        }
                         CatalogItem::Connection(catalog::Connection { connection, .. }) => {
                             match connection {
                                 // SSH connections have an associated secret that should be dropped
@@ -384,6 +388,9 @@ impl Coordinator {
                 self.drop_sources(sources_to_drop);
             }
             if !tables_to_drop.is_empty() {
+                for id in &tables_to_drop {
+                    self.unique_keys.remove(id);
+                }
                 self.drop_sources(tables_to_drop);
             }
             if !storage_sinks_to_drop.is_empty() {
@@ -432,6 +439,9 @@ impl Coordinator {
             if !secrets_to_drop.is_empty() {
                 self.drop_secrets(secrets_to_drop).await;
             }
+            for id in sequences_to_drop {
+                self.sequence_caches.remove(&id);
+            }
             if !vpc_endpoints_to_drop.is_empty() {
                 self.drop_vpc_endpoints(vpc_endpoints_to_drop).await;
             }
@@ -978,7 +988,9 @@ impl Coordinator {
                         | CatalogItem::View(_)
                         | CatalogItem::Index(_)
                         | CatalogItem::Type(_)
-                        | CatalogItem::Func(_) => {}
+                        | CatalogItem::Func(_)
+                        | CatalogItem::Sequence(_)
+                        | CatalogItem::Function(_) => {}
                     }
                 }
                 Op::DropObject(id) => match id {
@@ -1045,7 +1057,9 @@ impl Coordinator {
                                 | CatalogItem::View(_)
                                 | CatalogItem::Index(_)
                                 | CatalogItem::Type(_)
-                                | CatalogItem::Func(_) => {}
+                                | CatalogItem::Func(_)
+                                | CatalogItem::Sequence(_)
+                                | CatalogItem::Function(_) => {}
                             }
                     }
                 },
@@ -85,7 +85,9 @@ impl DataflowBuilder<'_> {
     pub fn indexes_on(&self, id: GlobalId) -> impl Iterator<Item = (GlobalId, &Index)> {
         self.catalog
             .get_indexes_on(id, self.compute.instance_id())
-            .filter(|(idx_id, _idx)| self.compute.contains_collection(idx_id))
+            .filter(|(idx_id, _idx)| {
+                self.compute.contains_collection(idx_id) && !self.is_ignored_index(idx_id)
+            })
     }
 }
 
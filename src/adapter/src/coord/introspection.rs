@@ -72,6 +72,8 @@ pub fn auto_run_on_introspection<'a, 's, 'p>(
         | Plan::CreateSource(_)
         | Plan::CreateSources(_)
         | Plan::CreateSecret(_)
+        | Plan::CreateSequence(_)
+        | Plan::CreateFunction(_)
         | Plan::CreateSink(_)
         | Plan::CreateTable(_)
         | Plan::CreateView(_)
@@ -80,6 +82,7 @@ pub fn auto_run_on_introspection<'a, 's, 'p>(
         | Plan::CreateType(_)
         | Plan::Comment(_)
         | Plan::DiscardTemp
+        | Plan::DiscardSequences
         | Plan::DiscardAll
         | Plan::DropObjects(_)
         | Plan::DropOwned(_)
@@ -109,6 +112,7 @@ pub fn auto_run_on_introspection<'a, 's, 'p>(
         | Plan::PurifiedAlterSource { .. }
         | Plan::AlterSetCluster(_)
         | Plan::AlterItemRename(_)
+        | Plan::AlterRetainHistory(_)
         | Plan::AlterSecret(_)
         | Plan::AlterSystemSet(_)
         | Plan::AlterSystemReset(_)
@@ -290,6 +294,8 @@ pub fn user_privilege_hack(
         | Plan::CreateSource(_)
         | Plan::CreateSources(_)
         | Plan::CreateSecret(_)
+        | Plan::CreateSequence(_)
+        | Plan::CreateFunction(_)
         | Plan::CreateSink(_)
         | Plan::CreateTable(_)
         | Plan::CreateView(_)
@@ -298,6 +304,7 @@ pub fn user_privilege_hack(
         | Plan::CreateType(_)
         | Plan::Comment(_)
         | Plan::DiscardTemp
+        | Plan::DiscardSequences
         | Plan::DiscardAll
         | Plan::DropObjects(_)
         | Plan::DropOwned(_)
@@ -314,6 +321,7 @@ pub fn user_privilege_hack(
         | Plan::AlterSource(_)
         | Plan::PurifiedAlterSource { .. }
         | Plan::AlterItemRename(_)
+        | Plan::AlterRetainHistory(_)
         | Plan::AlterSecret(_)
         | Plan::AlterSystemSet(_)
         | Plan::AlterSystemReset(_)
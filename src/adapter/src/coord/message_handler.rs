@@ -20,6 +20,7 @@ use mz_ore::now::EpochMillis;
 use mz_ore::task;
 use mz_persist_client::usage::ShardsUsageReferenced;
 use mz_sql::ast::Statement;
+use mz_sql::catalog::CatalogError;
 use mz_sql::names::ResolvedIds;
 use mz_sql::plan::{CreateSourcePlans, Plan};
 use mz_storage_client::controller::CollectionMetadata;
@@ -34,6 +35,7 @@ use crate::coord::{
     PendingReadTxn, PlanValidity, PurifiedStatementReady, RealTimeRecencyContext,
     SinkConnectionReady,
 };
+use crate::error::AdapterError;
 use crate::session::Session;
 use crate::util::{ComputeSinkId, ResultExt};
 use crate::{catalog, AdapterNotice, TimestampContext};
@@ -62,6 +64,15 @@ impl Coordinator {
             Message::WriteLockGrant(write_lock_guard) => {
                 self.message_write_lock_grant(write_lock_guard).await;
             }
+            Message::UniqueConstraintValidationReady {
+                validation,
+                write_lock_guard,
+            } => {
+                self.validate_unique_constraints(validation, write_lock_guard);
+            }
+            Message::UniqueKeysLoaded(loaded) => {
+                self.finish_loading_unique_keys(loaded);
+            }
             Message::GroupCommitInitiate(span) => self.try_group_commit().instrument(span).await,
             Message::GroupCommitApply(timestamp, responses, write_lock_guard) => {
                 self.group_commit_apply(timestamp, responses, write_lock_guard)
@@ -86,6 +97,22 @@ impl Coordinator {
             Message::StorageUsageUpdate(sizes) => {
                 self.storage_usage_update(sizes).await;
             }
+            Message::AllocateSequenceValues { id, count, tx } => {
+                let result = match self.catalog().try_get_entry(&id) {
+                    Some(_) => self.allocate_sequence_values(id, count).await,
+                    None => Err(AdapterError::SqlCatalog(CatalogError::UnknownItem(
+                        id.to_string(),
+                    ))),
+                };
+                // It is not an error for the insert to have gone away.
+                let _ = tx.send(result);
+            }
+            Message::PgSourceSchemaChangeCheck => {
+                self.check_pg_source_schema_changes();
+            }
+            Message::PgSourceSchemaChanges(changes) => {
+                self.apply_pg_source_schema_changes(changes).await;
+            }
             Message::RealTimeRecencyTimestamp {
                 conn_id,
                 real_time_recency_ts,
@@ -765,6 +792,7 @@ impl Coordinator {
                 index_id,
                 timeline_context,
                 source_ids,
+                id_bundle,
                 in_immediate_multi_stmt_txn: _,
                 key,
                 typ,
@@ -778,7 +806,7 @@ impl Coordinator {
                         copy_to,
                         dataflow,
                         cluster_id,
-                        id_bundle: None,
+                        id_bundle,
                         when,
                         target_replica,
                         view_id,
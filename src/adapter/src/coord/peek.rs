@@ -22,7 +22,8 @@ use mz_compute_client::protocol::response::PeekResponse;
 use mz_compute_client::types::dataflows::{DataflowDescription, IndexImport};
 use mz_controller::clusters::ClusterId;
 use mz_expr::{
-    EvalError, Id, MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr, RowSetFinishing,
+    BinaryFunc, EvalError, Id, MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr,
+    RowSetFinishing,
 };
 use mz_ore::cast::CastFrom;
 use mz_ore::str::{separated, Indent, StrExt};
@@ -73,6 +74,21 @@ pub struct PeekDataflowPlan<T = mz_repr::Timestamp> {
     key: Vec<MirScalarExpr>,
     permutation: BTreeMap<usize, usize>,
     thinned_arity: usize,
+    /// An index that would have allowed us to use the fast path instead.
+    index_recommendation: Option<IndexRecommendation>,
+}
+
+/// An index that would allow a peek to be answered from an existing arrangement, rather than by
+/// building a dataflow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexRecommendation {
+    /// The collection to index.
+    pub on_id: GlobalId,
+    /// The columns of the collection by which to key the index.
+    pub key: Vec<usize>,
+    /// Whether the peek looks up values of `key`, rather than scanning the collection. Scans
+    /// can be served by any index on the collection.
+    pub lookup: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -281,6 +297,70 @@ pub fn create_fast_path_plan<T: Timestamp>(
     Ok(None)
 }
 
+/// Determine an index that would make [`create_fast_path_plan`] succeed for the dataflow plan.
+///
+/// This mirrors the `PeekExisting` case of [`create_fast_path_plan`]: if the optimized plan is a
+/// linear operator around a `Get` of a collection without an index, an index on that collection
+/// lets us skip building a dataflow. The index is keyed by the columns that the operator
+/// constrains to literals, which lets the fast path look up these literals rather than scan the
+/// whole index. Without such constraints, the index is keyed like a default index.
+pub fn recommend_index<T>(
+    dataflow_plan: &DataflowDescription<OptimizedMirRelationExpr, (), T>,
+    view_id: GlobalId,
+) -> Option<IndexRecommendation> {
+    let object = dataflow_plan.objects_to_build.first()?;
+    if object.id != view_id {
+        return None;
+    }
+    let (mfp, mir) = mz_expr::MapFilterProject::extract_from_expression(object.plan.as_inner());
+    let MirRelationExpr::Get {
+        id: Id::Global(on_id),
+        typ,
+        ..
+    } = mir
+    else {
+        return None;
+    };
+    if on_id.is_transient()
+        || dataflow_plan
+            .index_imports
+            .values()
+            .any(|import| import.desc.on_id == *on_id)
+    {
+        return None;
+    }
+
+    let mut key = Vec::new();
+    for (_, predicate) in &mfp.predicates {
+        if let MirScalarExpr::CallBinary {
+            func: BinaryFunc::Eq,
+            expr1,
+            expr2,
+        } = predicate
+        {
+            let column = match (&**expr1, &**expr2) {
+                (MirScalarExpr::Column(c), MirScalarExpr::Literal(..))
+                | (MirScalarExpr::Literal(..), MirScalarExpr::Column(c)) => *c,
+                _ => continue,
+            };
+            if column < mfp.input_arity && !key.contains(&column) {
+                key.push(column);
+            }
+        }
+    }
+    let lookup = !key.is_empty();
+    if !lookup {
+        key = typ.default_key();
+    }
+    key.sort();
+
+    Some(IndexRecommendation {
+        on_id: *on_id,
+        key,
+        lookup,
+    })
+}
+
 impl FastPathPlan {
     pub fn used_indexes(&self, finishing: &Option<RowSetFinishing>) -> UsedIndexes {
         match self {
@@ -327,6 +407,7 @@ impl crate::coord::Coordinator {
         let peek_plan = fast_path_plan.map_or_else(
             // finalize the dataflow and produce a PeekPlan::SlowPath as a default
             || {
+                let index_recommendation = recommend_index(&dataflow, view_id);
                 // We have the opportunity to name an `until` frontier that will prevent work we needn't perform.
                 // By default, `until` will be `Antichain::new()`, which prevents no updates and is safe.
                 if let Some(as_of) = dataflow.as_of.as_ref() {
@@ -345,6 +426,7 @@ impl crate::coord::Coordinator {
                     key,
                     permutation,
                     thinned_arity,
+                    index_recommendation,
                 }))
             },
             // produce a PeekPlan::FastPath if possible
@@ -448,7 +530,18 @@ impl crate::coord::Coordinator {
                 key: index_key,
                 permutation: index_permutation,
                 thinned_arity: index_thinned_arity,
+                index_recommendation,
             }) => {
+                if let (Some(logging_id), Some(recommendation)) =
+                    (&ctx_extra.statement_uuid, index_recommendation)
+                {
+                    self.set_statement_execution_index_recommendation(
+                        logging_id,
+                        compute_instance,
+                        recommendation,
+                    );
+                }
+
                 let output_ids = dataflow.export_ids().collect();
 
                 // Very important: actually create the dataflow (here, so we can destructure).
@@ -688,6 +781,7 @@ fn consolidate_constant_updates(rows: Vec<(Row, Diff)>) -> Vec<(Row, Diff)> {
 
 #[cfg(test)]
 mod tests {
+    use mz_compute_client::types::dataflows::IndexDesc;
     use mz_expr::func::IsNull;
     use mz_expr::{MapFilterProject, UnaryFunc};
     use mz_ore::str::Indent;
@@ -770,4 +864,61 @@ mod tests {
             constant_exp2
         );
     }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `rust_psm_stack_pointer` on OS `linux`
+    fn test_recommend_index() {
+        let view_id = GlobalId::Transient(1);
+        let on_id = GlobalId::User(1);
+        let typ = RelationType::new(vec![
+            ColumnType {
+                scalar_type: ScalarType::Int32,
+                nullable: false,
+            };
+            3
+        ]);
+        let dataflow_for = |plan: MirRelationExpr, indexed: bool| {
+            let mut dataflow = DataflowDescription::<_, (), mz_repr::Timestamp>::new("test".into());
+            if indexed {
+                let desc = IndexDesc {
+                    on_id,
+                    key: vec![MirScalarExpr::column(0)],
+                };
+                dataflow.import_index(GlobalId::User(2), desc, typ.clone(), false);
+            }
+            dataflow.insert_plan(view_id, OptimizedMirRelationExpr::declare_optimized(plan));
+            dataflow
+        };
+        let literal = MirScalarExpr::literal_ok(Datum::Int32(5), ScalarType::Int32);
+        let lookup = MirRelationExpr::global_get(on_id, typ.clone()).filter(vec![
+            MirScalarExpr::column(2).call_binary(literal.clone(), BinaryFunc::Eq),
+            literal.call_binary(MirScalarExpr::column(1), BinaryFunc::Eq),
+        ]);
+        let scan = MirRelationExpr::global_get(on_id, typ.clone()).project(vec![1]);
+
+        assert_eq!(
+            recommend_index(&dataflow_for(lookup.clone(), false), view_id),
+            Some(IndexRecommendation {
+                on_id,
+                key: vec![1, 2],
+                lookup: true,
+            })
+        );
+        assert_eq!(
+            recommend_index(&dataflow_for(scan, false), view_id),
+            Some(IndexRecommendation {
+                on_id,
+                key: vec![0, 1, 2],
+                lookup: false,
+            })
+        );
+        // An index already enables the fast path.
+        assert_eq!(
+            recommend_index(&dataflow_for(lookup.clone(), true), view_id),
+            None
+        );
+        // No index would enable the fast path for non-linear plans.
+        let reduce = lookup.reduce(vec![0], vec![], None);
+        assert_eq!(recommend_index(&dataflow_for(reduce, false), view_id), None);
+    }
 }
@@ -0,0 +1,529 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Detection of plan changes of indexes and materialized views.
+//!
+//! The dataflows of indexes and materialized views are planned anew whenever `environmentd`
+//! starts, so a new version of the optimizer can silently change their plans. To surface such
+//! changes, we persist a hash of the structure of the plans of each user index and materialized
+//! view in the catalog, and compare it with the hash of the freshly computed plans during
+//! bootstrap. The `EXPLAIN` output of the plans is persisted alongside, to describe changes.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use itertools::Itertools;
+use mz_compute_client::plan::join::JoinPlan;
+use mz_compute_client::plan::Plan;
+use mz_compute_client::types::dataflows::DataflowDescription;
+use mz_expr::MirScalarExpr;
+use mz_ore::cast::CastFrom;
+use mz_repr::explain::{ExplainConfig, ExplainFormat};
+use mz_repr::GlobalId;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use similar::TextDiff;
+use tracing::warn;
+
+use crate::catalog::storage::PlanFingerprint;
+use crate::catalog::{BuiltinTableUpdate, CatalogItem};
+use crate::coord::Coordinator;
+use crate::explain::explain_dataflow;
+use crate::AdapterError;
+
+impl Coordinator {
+    /// Computes the plan fingerprint of the index or materialized view `id`, from the plans the
+    /// catalog holds for it.
+    ///
+    /// Returns `None` if the catalog holds no plans for `id`, or they cannot be explained.
+    fn plan_fingerprint(&self, id: GlobalId) -> Option<PlanFingerprint> {
+        let catalog = self.catalog();
+        let dataflow_metainfo = catalog.try_get_dataflow_metainfo(&id)?;
+        let optimized_plan = catalog.try_get_optimized_plan(&id)?;
+        let physical_plan = catalog.try_get_physical_plan(&id)?;
+        let optimized_fingerprint = fingerprint(optimized_plan);
+        let physical_fingerprint = fingerprint(physical_plan);
+        let physical_arrangements = arrangement_count(physical_plan);
+
+        let humanizer = catalog.for_system_session();
+        let config = ExplainConfig::default();
+        let optimized_plan = explain_dataflow(
+            optimized_plan.clone(),
+            ExplainFormat::Text,
+            &config,
+            &humanizer,
+            dataflow_metainfo,
+        );
+        let physical_plan = explain_dataflow(
+            physical_plan.clone(),
+            ExplainFormat::Text,
+            &config,
+            &humanizer,
+            dataflow_metainfo,
+        );
+        let (optimized_plan, physical_plan) = match (optimized_plan, physical_plan) {
+            (Ok(optimized_plan), Ok(physical_plan)) => (optimized_plan, physical_plan),
+            (Err(e), _) | (_, Err(e)) => {
+                warn!(%id, "cannot fingerprint plans: {e}");
+                return None;
+            }
+        };
+
+        Some(PlanFingerprint {
+            build_version: catalog.config().build_info.version.to_string(),
+            optimized_fingerprint,
+            physical_fingerprint,
+            optimized_plan,
+            physical_plan,
+            physical_arrangements,
+        })
+    }
+
+    /// Persists the plan fingerprint of the newly created index or materialized view `id`, so
+    /// that the next run can detect changes to its plans.
+    pub(crate) async fn record_plan_fingerprint(&self, id: GlobalId) {
+        if !id.is_user() {
+            return;
+        }
+        let Some(fingerprint) = self.plan_fingerprint(id) else {
+            return;
+        };
+        let fingerprints = BTreeMap::from([(id, fingerprint)]);
+        if let Err(e) = self
+            .catalog()
+            .update_plan_fingerprints(fingerprints, BTreeSet::new())
+            .await
+        {
+            warn!(%id, "cannot record plan fingerprint: {e}");
+        }
+    }
+
+    /// Compares the plans of all user indexes and materialized views with the ones recorded by
+    /// the previous run, and records the new plans.
+    ///
+    /// Returns the updates to `mz_internal.mz_plan_changes` that describe the plans that changed.
+    ///
+    /// Fails if this run upgraded Materialize and regressed the physical plan of any object listed
+    /// in `plan_regression_critical_objects`. The recorded plans are left untouched in that case,
+    /// so that the check fails again until the objects are removed from the parameter. Note that
+    /// the catalog has already been migrated to this version at this point.
+    pub(crate) async fn bootstrap_plan_fingerprints(
+        &self,
+    ) -> Result<Vec<BuiltinTableUpdate>, AdapterError> {
+        let previous = self.catalog().load_plan_fingerprints().await?;
+        let current: BTreeMap<_, _> = self
+            .catalog()
+            .entries()
+            .filter(|entry| {
+                entry.id().is_user()
+                    && matches!(
+                        entry.item(),
+                        CatalogItem::Index(_) | CatalogItem::MaterializedView(_)
+                    )
+            })
+            .filter_map(|entry| Some((entry.id(), self.plan_fingerprint(entry.id())?)))
+            .collect();
+
+        let build_version = self.catalog().config().build_info.version;
+        let critical_objects: BTreeSet<_> = self
+            .catalog()
+            .system_config()
+            .plan_regression_critical_objects()
+            .into_iter()
+            .collect();
+
+        let changes = compare_plan_fingerprints(&previous, &current);
+        for change in &changes {
+            warn!(
+                id = %change.id,
+                stage = change.stage,
+                previous_version = %change.previous_version,
+                regression = change.regression,
+                "plan changed since the previous run:\n{}",
+                change.diff,
+            );
+        }
+
+        let regressions = critical_regressions(&changes, build_version, &critical_objects);
+        if !regressions.is_empty() {
+            return Err(AdapterError::Unstructured(anyhow::anyhow!(
+                "refusing to start: the physical plans of critical objects {} regressed in version {}",
+                regressions.iter().join(", "),
+                build_version,
+            )));
+        }
+
+        let updates = changes
+            .iter()
+            .map(|change| {
+                self.catalog().state().pack_plan_change_update(
+                    change.id,
+                    change.stage,
+                    &change.previous_version,
+                    &change.previous_fingerprint,
+                    &change.current_fingerprint,
+                    change.regression,
+                    &change.diff,
+                )
+            })
+            .collect();
+
+        let removed = previous
+            .keys()
+            .filter(|id| !current.contains_key(*id))
+            .copied()
+            .collect();
+        let changed = current
+            .into_iter()
+            .filter(|(id, fingerprint)| previous.get(id) != Some(fingerprint))
+            .collect();
+        self.catalog()
+            .update_plan_fingerprints(changed, removed)
+            .await?;
+
+        Ok(updates)
+    }
+}
+
+/// A change to a plan of an index or materialized view since the previous run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PlanChange {
+    id: GlobalId,
+    /// The plan that changed: `optimized` or `physical`.
+    stage: &'static str,
+    previous_version: String,
+    previous_fingerprint: String,
+    current_fingerprint: String,
+    /// Whether the current physical plan builds more arrangements than the previous one.
+    regression: bool,
+    /// A unified diff from the `EXPLAIN` output of the previous plan to the current one.
+    diff: String,
+}
+
+/// Returns the changes from the `previous` to the `current` plans of each object.
+///
+/// Objects that have no previous plans are new, and are not reported.
+fn compare_plan_fingerprints(
+    previous: &BTreeMap<GlobalId, PlanFingerprint>,
+    current: &BTreeMap<GlobalId, PlanFingerprint>,
+) -> Vec<PlanChange> {
+    let mut changes = Vec::new();
+    for (id, current) in current {
+        let Some(previous) = previous.get(id) else {
+            continue;
+        };
+        let stages = [
+            (
+                "optimized",
+                (&previous.optimized_fingerprint, &previous.optimized_plan),
+                (&current.optimized_fingerprint, &current.optimized_plan),
+                false,
+            ),
+            (
+                "physical",
+                (&previous.physical_fingerprint, &previous.physical_plan),
+                (&current.physical_fingerprint, &current.physical_plan),
+                current.physical_arrangements > previous.physical_arrangements,
+            ),
+        ];
+        for (
+            stage,
+            (previous_fingerprint, previous_plan),
+            (current_fingerprint, current_plan),
+            regression,
+        ) in stages
+        {
+            if previous_fingerprint == current_fingerprint {
+                continue;
+            }
+            changes.push(PlanChange {
+                id: *id,
+                stage,
+                previous_version: previous.build_version.clone(),
+                previous_fingerprint: previous_fingerprint.clone(),
+                current_fingerprint: current_fingerprint.clone(),
+                regression,
+                diff: plan_diff(previous_plan, current_plan),
+            });
+        }
+    }
+    changes
+}
+
+/// Returns the objects in `critical_objects` whose physical plans regressed in `changes`, when
+/// upgrading to `build_version`.
+fn critical_regressions(
+    changes: &[PlanChange],
+    build_version: &str,
+    critical_objects: &BTreeSet<String>,
+) -> Vec<GlobalId> {
+    changes
+        .iter()
+        .filter(|change| {
+            change.regression
+                && change.previous_version != build_version
+                && critical_objects.contains(&change.id.to_string())
+        })
+        .map(|change| change.id)
+        .collect()
+}
+
+/// Returns a hash of the structure of the plans of `dataflow`.
+///
+/// The hash covers the imports, the plans of the objects to build and the exports, which all
+/// refer to other objects by their `GlobalId`. It does not cover the frontiers and the name of
+/// the dataflow, which change between runs without the plans changing.
+fn fingerprint<P: Serialize>(dataflow: &DataflowDescription<P>) -> String {
+    let sink_exports: BTreeMap<_, _> = dataflow
+        .sink_exports
+        .iter()
+        .map(|(id, sink)| (id, sink.from))
+        .collect();
+    let structure = (
+        &dataflow.source_imports,
+        &dataflow.index_imports,
+        &dataflow.objects_to_build,
+        &dataflow.index_exports,
+        sink_exports,
+    );
+    let bytes = bincode::serialize(&structure).expect("plans are serializable");
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Returns the number of arrangements that the physical plans of `dataflow` build.
+///
+/// Each reduction, top-k and threshold stage counts as one arrangement, and each stage of a linear
+/// join arranges its intermediate result.
+fn arrangement_count(dataflow: &DataflowDescription<Plan>) -> u64 {
+    fn arranged_keys(plan: &Plan) -> Vec<&Vec<MirScalarExpr>> {
+        match plan {
+            Plan::Get { keys: forms, .. } | Plan::ArrangeBy { forms, .. } => {
+                forms.arranged.iter().map(|(key, _, _)| key).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn count(plan: &Plan) -> usize {
+        let own = match plan {
+            // The forms of an `ArrangeBy` include the arrangements of its input.
+            Plan::ArrangeBy { input, forms, .. } => {
+                let input_keys = arranged_keys(input);
+                forms
+                    .arranged
+                    .iter()
+                    .filter(|(key, _, _)| !input_keys.contains(&key))
+                    .count()
+            }
+            Plan::Join {
+                plan: JoinPlan::Linear(plan),
+                ..
+            } => plan.stage_plans.len(),
+            Plan::Reduce { .. } | Plan::TopK { .. } | Plan::Threshold { .. } => 1,
+            _ => 0,
+        };
+        own + plan.children().map(count).sum::<usize>()
+    }
+
+    let count = dataflow
+        .objects_to_build
+        .iter()
+        .map(|object| count(&object.plan))
+        .sum();
+    u64::cast_from(count)
+}
+
+/// Returns a unified diff from the `EXPLAIN` output `previous` to `current`.
+fn plan_diff(previous: &str, current: &str) -> String {
+    TextDiff::from_lines(previous, current)
+        .unified_diff()
+        .header("previous", "current")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_compute_client::plan::{AvailableCollections, GetPlan};
+    use mz_compute_client::types::dataflows::BuildDesc;
+    use mz_expr::{Id, MapFilterProject};
+    use timely::progress::Antichain;
+
+    use super::*;
+
+    fn get(id: GlobalId, keys: AvailableCollections) -> Plan {
+        Plan::Get {
+            id: Id::Global(id),
+            keys,
+            plan: GetPlan::PassArrangements,
+        }
+    }
+
+    fn arrange_by(input: Plan, key: Vec<MirScalarExpr>) -> Plan {
+        Plan::ArrangeBy {
+            input: Box::new(input),
+            forms: AvailableCollections::new_arranged(vec![(key, BTreeMap::new(), vec![])]),
+            input_key: None,
+            input_mfp: MapFilterProject::new(1),
+        }
+    }
+
+    fn dataflow(plan: Plan) -> DataflowDescription<Plan> {
+        DataflowDescription {
+            source_imports: BTreeMap::new(),
+            index_imports: BTreeMap::new(),
+            objects_to_build: vec![BuildDesc {
+                id: GlobalId::User(10),
+                plan,
+            }],
+            index_exports: BTreeMap::new(),
+            sink_exports: BTreeMap::new(),
+            as_of: None,
+            until: Antichain::new(),
+            debug_name: "mv".into(),
+        }
+    }
+
+    fn plan_fingerprint(
+        build_version: &str,
+        physical_fingerprint: &str,
+        physical_arrangements: u64,
+    ) -> PlanFingerprint {
+        PlanFingerprint {
+            build_version: build_version.into(),
+            optimized_fingerprint: "optimized".into(),
+            physical_fingerprint: physical_fingerprint.into(),
+            optimized_plan: String::new(),
+            physical_plan: format!("{physical_fingerprint}\n"),
+            physical_arrangements,
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_fingerprint() {
+        let key = vec![MirScalarExpr::Column(0)];
+        let plan = dataflow(arrange_by(
+            get(GlobalId::User(1), AvailableCollections::new_raw()),
+            key.clone(),
+        ));
+        assert_eq!(fingerprint(&plan), fingerprint(&plan.clone()));
+
+        // The frontiers and the name of the dataflow are not part of the structure.
+        let mut renamed = plan.clone();
+        renamed.as_of = Some(Antichain::from_elem(mz_repr::Timestamp::from(5u64)));
+        renamed.until = Antichain::from_elem(mz_repr::Timestamp::from(10u64));
+        renamed.debug_name = "renamed".into();
+        assert_eq!(fingerprint(&plan), fingerprint(&renamed));
+
+        // Reading from another object changes the structure.
+        let other_input = dataflow(arrange_by(
+            get(GlobalId::User(2), AvailableCollections::new_raw()),
+            key,
+        ));
+        assert_ne!(fingerprint(&plan), fingerprint(&other_input));
+    }
+
+    #[mz_ore::test]
+    fn test_arrangement_count() {
+        let key = vec![MirScalarExpr::Column(0)];
+        let raw = get(GlobalId::User(1), AvailableCollections::new_raw());
+        let arranged = get(
+            GlobalId::User(1),
+            AvailableCollections::new_arranged(vec![(key.clone(), BTreeMap::new(), vec![])]),
+        );
+        assert_eq!(arrangement_count(&dataflow(raw.clone())), 0);
+        assert_eq!(
+            arrangement_count(&dataflow(arrange_by(raw.clone(), key.clone()))),
+            1
+        );
+        // Arranging an already arranged input by the same key builds no arrangement.
+        assert_eq!(
+            arrangement_count(&dataflow(arrange_by(arranged.clone(), key.clone()))),
+            0
+        );
+        assert_eq!(
+            arrangement_count(&dataflow(Plan::Union {
+                inputs: vec![
+                    arrange_by(raw, key.clone()),
+                    arrange_by(arranged, vec![MirScalarExpr::Column(1)]),
+                ],
+                consolidate_output: false,
+            })),
+            2
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_compare_plan_fingerprints() {
+        let previous = BTreeMap::from([
+            (GlobalId::User(1), plan_fingerprint("v0.1.0", "a", 1)),
+            (GlobalId::User(2), plan_fingerprint("v0.1.0", "b", 1)),
+            (GlobalId::User(3), plan_fingerprint("v0.1.0", "c", 2)),
+        ]);
+        let current = BTreeMap::from([
+            (GlobalId::User(1), plan_fingerprint("v0.2.0", "a", 1)),
+            (GlobalId::User(2), plan_fingerprint("v0.2.0", "b2", 2)),
+            (GlobalId::User(3), plan_fingerprint("v0.2.0", "c2", 1)),
+            (GlobalId::User(4), plan_fingerprint("v0.2.0", "d", 1)),
+        ]);
+        let changes = compare_plan_fingerprints(&previous, &current);
+        assert_eq!(
+            changes,
+            vec![
+                PlanChange {
+                    id: GlobalId::User(2),
+                    stage: "physical",
+                    previous_version: "v0.1.0".into(),
+                    previous_fingerprint: "b".into(),
+                    current_fingerprint: "b2".into(),
+                    regression: true,
+                    diff: "--- previous\n+++ current\n@@ -1 +1 @@\n-b\n+b2\n".into(),
+                },
+                PlanChange {
+                    id: GlobalId::User(3),
+                    stage: "physical",
+                    previous_version: "v0.1.0".into(),
+                    previous_fingerprint: "c".into(),
+                    current_fingerprint: "c2".into(),
+                    regression: false,
+                    diff: "--- previous\n+++ current\n@@ -1 +1 @@\n-c\n+c2\n".into(),
+                },
+            ]
+        );
+
+        // Only regressions of critical objects refuse an upgrade.
+        let critical = BTreeSet::from(["u2".to_string(), "u3".to_string()]);
+        assert_eq!(
+            critical_regressions(&changes, "v0.2.0", &critical),
+            vec![GlobalId::User(2)]
+        );
+        assert_eq!(
+            critical_regressions(&changes, "v0.2.0", &BTreeSet::from(["u3".to_string()])),
+            Vec::<GlobalId>::new()
+        );
+        assert_eq!(
+            critical_regressions(&changes, "v0.2.0", &BTreeSet::new()),
+            Vec::<GlobalId>::new()
+        );
+        // Restarting the same version never refuses to start.
+        assert_eq!(
+            critical_regressions(&changes, "v0.1.0", &critical),
+            Vec::<GlobalId>::new()
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_plan_diff() {
+        let previous =
+            "materialize.public.mv:\n  Filter (#1 = 100)\n    Get materialize.public.t\n";
+        let current = "materialize.public.mv:\n  Filter (#1 = 100)\n    ReadIndex on=t\n";
+        assert_eq!(
+            plan_diff(previous, current),
+            "--- previous\n+++ current\n@@ -1,3 +1,3 @@\n materialize.public.mv:\n   Filter (#1 = 100)\n-    Get materialize.public.t\n+    ReadIndex on=t\n"
+        );
+    }
+}
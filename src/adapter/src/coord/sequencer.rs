@@ -17,7 +17,7 @@ use mz_controller::clusters::ClusterId;
 use mz_expr::{MirRelationExpr, OptimizedMirRelationExpr, RowSetFinishing};
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_repr::explain::ExplainFormat;
-use mz_repr::{Diff, GlobalId, Timestamp};
+use mz_repr::{Datum, Diff, GlobalId, Row, RowArena, Timestamp};
 use mz_sql::catalog::{CatalogCluster, CatalogError};
 use mz_sql::names::ResolvedIds;
 use mz_sql::plan::{
@@ -30,7 +30,7 @@ use mz_storage_client::types::connections::inline::IntoInlineConnection;
 use tokio::sync::oneshot;
 use tracing::{event, Level};
 
-use crate::catalog::Catalog;
+use crate::catalog::{Catalog, CatalogItem};
 use crate::command::{Command, ExecuteResponse, Response};
 use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::{introspection, Coordinator, Message};
@@ -177,6 +177,18 @@ impl Coordinator {
                 let result = self.sequence_create_secret(ctx.session_mut(), plan).await;
                 ctx.retire(result);
             }
+            Plan::CreateSequence(plan) => {
+                let result = self
+                    .sequence_create_sequence(ctx.session_mut(), plan, resolved_ids)
+                    .await;
+                ctx.retire(result);
+            }
+            Plan::CreateFunction(plan) => {
+                let result = self
+                    .sequence_create_function(ctx.session_mut(), plan, resolved_ids)
+                    .await;
+                ctx.retire(result);
+            }
             Plan::CreateSink(plan) => {
                 self.sequence_create_sink(ctx, plan, resolved_ids).await;
             }
@@ -290,7 +302,10 @@ impl Coordinator {
                 ctx.retire(result);
             }
             Plan::SideEffectingFunc(plan) => {
-                ctx.retire(self.sequence_side_effecting_func(plan));
+                let result = self
+                    .sequence_side_effecting_func(ctx.session_mut(), plan)
+                    .await;
+                ctx.retire(result);
             }
             Plan::ShowCreate(plan) => {
                 ctx.retire(Ok(Self::send_immediate_rows(vec![plan.row])));
@@ -350,6 +365,12 @@ impl Coordinator {
                 let result = self.sequence_alter_item_rename(ctx.session(), plan).await;
                 ctx.retire(result);
             }
+            Plan::AlterRetainHistory(plan) => {
+                let result = self
+                    .sequence_alter_retain_history(ctx.session(), plan)
+                    .await;
+                ctx.retire(result);
+            }
             Plan::AlterIndexSetOptions(plan) => {
                 let result = self.sequence_alter_index_set_options(plan);
                 ctx.retire(result);
@@ -403,6 +424,10 @@ impl Coordinator {
                 self.drop_temp_items(ctx.session().conn_id()).await;
                 ctx.retire(Ok(ExecuteResponse::DiscardedTemp));
             }
+            Plan::DiscardSequences => {
+                ctx.session_mut().clear_sequence_values();
+                ctx.retire(Ok(ExecuteResponse::DiscardedSequences));
+            }
             Plan::DiscardAll => {
                 let ret = if let TransactionStatus::Started(_) = ctx.session().transaction() {
                     self.clear_transaction(ctx.session_mut());
@@ -683,10 +708,16 @@ impl Coordinator {
         match constants.as_const() {
             Some((rows, ..)) => {
                 let rows = rows.clone()?;
-                for (row, _) in &rows {
+                let checks = Self::enforced_check_constraints(catalog, id);
+                for (row, diff) in &rows {
                     for (i, datum) in row.iter().enumerate() {
                         desc.constraints_met(i, &datum)?;
                     }
+                    if let Some((relation, checks)) = &checks {
+                        if *diff > 0 {
+                            Self::check_constraints_met(relation, checks, row)?;
+                        }
+                    }
                 }
                 let diffs_plan = plan::SendDiffsPlan {
                     id,
@@ -694,6 +725,7 @@ impl Coordinator {
                     kind: MutationKind::Insert,
                     returning: Vec::new(),
                     max_result_size: catalog.system_config().max_result_size(),
+                    affected_rows: None,
                 };
                 Self::send_diffs(session, diffs_plan)
             }
@@ -704,6 +736,48 @@ impl Coordinator {
         }
     }
 
+    /// Returns the name and the check constraints of the table `id` if
+    /// constraint enforcement is enabled and the table has any.
+    pub(crate) fn enforced_check_constraints(
+        catalog: &Catalog,
+        id: GlobalId,
+    ) -> Option<(String, Vec<plan::CheckConstraint>)> {
+        if !catalog
+            .system_config()
+            .enable_table_constraint_enforcement()
+        {
+            return None;
+        }
+        let entry = catalog.try_get_entry(&id)?;
+        match entry.item() {
+            CatalogItem::Table(table) if !table.constraints.checks.is_empty() => {
+                Some((entry.name().item.clone(), table.constraints.checks.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Verifies that `row` satisfies `checks`, the check constraints of the
+    /// table named `relation`. Like in PostgreSQL, a check that evaluates to
+    /// `NULL` is satisfied.
+    pub(crate) fn check_constraints_met(
+        relation: &str,
+        checks: &[plan::CheckConstraint],
+        row: &Row,
+    ) -> Result<(), AdapterError> {
+        let arena = RowArena::new();
+        let datums = row.unpack();
+        for check in checks {
+            if check.expr.eval(&datums, &arena)? == Datum::False {
+                return Err(AdapterError::CheckViolation {
+                    relation: relation.to_string(),
+                    constraint: check.name.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn send_diffs(
         session: &mut Session,
@@ -741,6 +815,7 @@ impl Coordinator {
 
             usize::try_from(affected_rows).expect("positive isize must fit")
         };
+        let affected_rows = plan.affected_rows.unwrap_or(affected_rows);
         event!(
             Level::TRACE,
             affected_rows,
@@ -770,6 +845,8 @@ impl Coordinator {
             MutationKind::Delete => ExecuteResponse::Deleted(affected_rows),
             MutationKind::Insert => ExecuteResponse::Inserted(affected_rows),
             MutationKind::Update => ExecuteResponse::Updated(affected_rows / 2),
+            MutationKind::Upsert { .. } => ExecuteResponse::Inserted(affected_rows),
+            MutationKind::Merge { .. } => ExecuteResponse::Merged(affected_rows),
         })
     }
 }
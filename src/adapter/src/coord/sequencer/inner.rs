@@ -26,9 +26,10 @@ use mz_compute_client::types::sinks::{
 };
 use mz_controller::clusters::{ClusterId, ReplicaId};
 use mz_expr::{
-    permutation_for_arrangement, CollectionPlan, MirScalarExpr, OptimizedMirRelationExpr,
-    RowSetFinishing,
+    permutation_for_arrangement, CollectionPlan, EvalError, MirRelationExpr, MirScalarExpr,
+    OptimizedMirRelationExpr, OptimizerHints, RowSetFinishing,
 };
+use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
 use mz_ore::task;
 use mz_ore::tracing::OpenTelemetryContext;
@@ -36,8 +37,11 @@ use mz_ore::vec::VecExt;
 use mz_repr::adt::jsonb::Jsonb;
 use mz_repr::adt::mz_acl_item::{MzAclItem, PrivilegeMap};
 use mz_repr::explain::{ExplainFormat, UsedIndexes};
+use mz_repr::refresh_schedule::RefreshSchedule;
 use mz_repr::role_id::RoleId;
-use mz_repr::{Datum, Diff, GlobalId, RelationDesc, RelationType, Row, RowArena, Timestamp};
+use mz_repr::{
+    Datum, Diff, GlobalId, RelationDesc, RelationType, Row, RowArena, ScalarType, Timestamp,
+};
 use mz_sql::ast::{ExplainStage, IndexOptionName};
 use mz_sql::catalog::{
     CatalogCluster, CatalogClusterReplica, CatalogDatabase, CatalogError,
@@ -61,9 +65,12 @@ use mz_sql::session::vars::{
 use mz_sql::{plan, rbac};
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::{
-    AlterSourceAddSubsourceOptionName, CreateSourceConnection, CreateSourceSubsource,
-    DeferredItemName, PgConfigOption, PgConfigOptionName, ReferencedSubsources, Statement,
-    TransactionMode, WithOptionValue,
+    AlterSourceAddSubsourceOptionName, CreateSourceConnection, CreateSourceOption,
+    CreateSourceOptionName, CreateSourceSubsource, CreateSubsourceOption,
+    CreateSubsourceOptionName, DeferredItemName, Expr, MaterializedViewOption,
+    MaterializedViewOptionName, PgConfigOption, PgConfigOptionName, ReferencedSubsources,
+    RefreshAtOptionValue, RefreshOptionValue, Statement, TableOption, TableOptionName,
+    TransactionMode, Value, WithOptionValue,
 };
 use mz_ssh_util::keys::SshKeyPairSet;
 use mz_storage_client::controller::{
@@ -85,10 +92,10 @@ use crate::catalog::{
 };
 use crate::client::ConnectionId;
 use crate::command::{ExecuteResponse, Response};
-use crate::coord::appends::{Deferred, DeferredPlan, PendingWriteTxn};
+use crate::coord::appends::{Deferred, DeferredPlan};
 use crate::coord::dataflows::{
-    prep_relation_expr, prep_scalar_expr, ComputeInstanceSnapshot, DataflowBuilder, EvalTime,
-    ExprPrepStyle,
+    eval_refresh_schedule, prep_relation_expr, prep_scalar_expr, ComputeInstanceSnapshot,
+    DataflowBuilder, EvalTime, ExprPrepStyle,
 };
 use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::peek::{FastPathPlan, PlannedPeek};
@@ -98,10 +105,11 @@ use crate::coord::timestamp_selection::{
     TimestampContext, TimestampDetermination, TimestampProvider, TimestampSource,
 };
 use crate::coord::{
-    peek, Coordinator, CreateConnectionValidationReady, ExecuteContext, Message, PeekStage,
-    PeekStageFinish, PeekStageOptimize, PeekStageTimestamp, PeekStageValidate, PendingRead,
-    PendingReadTxn, PendingTxn, PendingTxnResponse, PlanValidity, RealTimeRecencyContext,
-    SinkConnectionReady, TargetCluster, DEFAULT_LOGICAL_COMPACTION_WINDOW_TS,
+    compaction_window_ts, peek, Coordinator, CreateConnectionValidationReady, ExecuteContext,
+    Message, PeekStage, PeekStageFinish, PeekStageOptimize, PeekStageTimestamp, PeekStageValidate,
+    PendingRead, PendingReadTxn, PendingTxn, PendingTxnResponse, PlanValidity,
+    RealTimeRecencyContext, SinkConnectionReady, TargetCluster,
+    DEFAULT_LOGICAL_COMPACTION_WINDOW_TS,
 };
 use crate::error::AdapterError;
 use crate::explain::explain_dataflow;
@@ -221,7 +229,7 @@ impl Coordinator {
 
         match self.catalog_transact(Some(session), ops).await {
             Ok(()) => {
-                let mut source_ids = Vec::with_capacity(sources.len());
+                let mut source_ids: BTreeMap<_, Vec<_>> = BTreeMap::new();
                 for (source_id, source) in sources {
                     let source_status_collection_id =
                         Some(self.catalog().resolve_builtin_storage_collection(
@@ -274,14 +282,18 @@ impl Coordinator {
                         .await
                         .unwrap_or_terminate("cannot fail to create collections");
 
-                    source_ids.push(source_id);
+                    source_ids
+                        .entry(compaction_window_ts(
+                            source.custom_logical_compaction_window,
+                        ))
+                        .or_default()
+                        .push(source_id);
                 }
 
-                self.initialize_storage_read_policies(
-                    source_ids,
-                    Some(DEFAULT_LOGICAL_COMPACTION_WINDOW_TS),
-                )
-                .await;
+                for (compaction_window, source_ids) in source_ids {
+                    self.initialize_storage_read_policies(source_ids, Some(compaction_window))
+                        .await;
+                }
 
                 Ok(ExecuteResponse::CreatedSource)
             }
@@ -541,6 +553,7 @@ impl Coordinator {
         let plan::CreateTablePlan {
             name,
             table,
+            sequences,
             if_not_exists,
         } = plan;
 
@@ -554,19 +567,39 @@ impl Coordinator {
             create_sql: table.create_sql,
             desc: table.desc,
             defaults: table.defaults,
+            identity_columns: table.identity_columns,
+            constraints: table.constraints,
             conn_id: conn_id.cloned(),
             resolved_ids,
-            custom_logical_compaction_window: None,
+            custom_logical_compaction_window: table.compaction_window,
             is_retained_metrics_object: false,
         };
         let table_oid = self.catalog_mut().allocate_oid()?;
-        let ops = vec![catalog::Op::CreateItem {
+        let mut ops = vec![catalog::Op::CreateItem {
             id: table_id,
             oid: table_oid,
             name: name.clone(),
             item: CatalogItem::Table(table.clone()),
             owner_id: *session.current_role_id(),
         }];
+        // Sequences backing identity and serial columns are created alongside,
+        // and owned by, the table.
+        for (sequence_name, column, sequence) in sequences {
+            let id = self.catalog_mut().allocate_user_id().await?;
+            let oid = self.catalog_mut().allocate_oid()?;
+            ops.push(catalog::Op::CreateItem {
+                id,
+                oid,
+                name: sequence_name,
+                item: CatalogItem::Sequence(catalog::Sequence {
+                    create_sql: sequence.create_sql,
+                    options: sequence.options,
+                    owned_by: Some((table_id, column)),
+                    resolved_ids: ResolvedIds(BTreeSet::from([table_id])),
+                }),
+                owner_id: *session.current_role_id(),
+            });
+        }
         match self.catalog_transact(Some(session), ops).await {
             Ok(()) => {
                 // Determine the initial validity for the table.
@@ -589,7 +622,7 @@ impl Coordinator {
 
                 self.initialize_storage_read_policies(
                     vec![table_id],
-                    Some(DEFAULT_LOGICAL_COMPACTION_WINDOW_TS),
+                    Some(compaction_window_ts(table.custom_logical_compaction_window)),
                 )
                 .await;
 
@@ -620,6 +653,80 @@ impl Coordinator {
         }
     }
 
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(super) async fn sequence_create_sequence(
+        &mut self,
+        session: &mut Session,
+        plan: plan::CreateSequencePlan,
+        resolved_ids: ResolvedIds,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let plan::CreateSequencePlan {
+            name,
+            sequence,
+            owned_by,
+            if_not_exists,
+        } = plan;
+
+        let id = self.catalog_mut().allocate_user_id().await?;
+        let oid = self.catalog_mut().allocate_oid()?;
+        let sequence = catalog::Sequence {
+            create_sql: sequence.create_sql,
+            options: sequence.options,
+            owned_by,
+            resolved_ids,
+        };
+        let ops = vec![catalog::Op::CreateItem {
+            id,
+            oid,
+            name: name.clone(),
+            item: CatalogItem::Sequence(sequence),
+            owner_id: *session.current_role_id(),
+        }];
+
+        match self.catalog_transact(Some(session), ops).await {
+            Ok(()) => Ok(ExecuteResponse::CreatedSequence),
+            Err(AdapterError::Catalog(catalog::Error {
+                kind: catalog::ErrorKind::ItemAlreadyExists(_, _),
+                ..
+            })) if if_not_exists => {
+                session.add_notice(AdapterNotice::ObjectAlreadyExists {
+                    name: name.item,
+                    ty: "sequence",
+                });
+                Ok(ExecuteResponse::CreatedSequence)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(super) async fn sequence_create_function(
+        &mut self,
+        session: &mut Session,
+        plan: plan::CreateFunctionPlan,
+        resolved_ids: ResolvedIds,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let plan::CreateFunctionPlan { name, function } = plan;
+
+        let id = self.catalog_mut().allocate_user_id().await?;
+        let oid = self.catalog_mut().allocate_oid()?;
+        let function = catalog::Function {
+            create_sql: function.create_sql,
+            func: function.func,
+            resolved_ids,
+        };
+        let ops = vec![catalog::Op::CreateItem {
+            id,
+            oid,
+            name,
+            item: CatalogItem::Function(function),
+            owner_id: *session.current_role_id(),
+        }];
+
+        self.catalog_transact(Some(session), ops).await?;
+        Ok(ExecuteResponse::CreatedFunction)
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     pub(super) async fn sequence_create_secret(
         &mut self,
@@ -951,6 +1058,9 @@ impl Coordinator {
                     expr: view_expr,
                     column_names,
                     cluster_id,
+                    compaction_window,
+                    refresh_schedule,
+                    hints,
                 },
             replace: _,
             drop_ids,
@@ -1006,9 +1116,32 @@ impl Coordinator {
         // amount of historical detail.
         let id_bundle = self
             .index_oracle(cluster_id)
+            .with_hints(&hints)
             .sufficient_collections(&expr_depends_on);
         let as_of = self.least_valid_read(&id_bundle);
 
+        // Evaluate the refresh times now, and record them in the definition of
+        // the materialized view, so that the same schedule is picked up again on
+        // restart. The creation time is the as-of of the dataflow, but no earlier
+        // than the current read timestamp.
+        let (create_sql, refresh_schedule) = match refresh_schedule {
+            Some(plan) => {
+                let creation_time = as_of
+                    .as_option()
+                    .copied()
+                    .unwrap_or(Timestamp::MIN)
+                    .max(self.get_local_read_ts());
+                let schedule = eval_refresh_schedule(
+                    self.catalog().state(),
+                    plan,
+                    Some((creation_time, session)),
+                )?;
+                let create_sql = rewrite_refresh_options(&create_sql, &schedule);
+                (create_sql, Some(schedule))
+            }
+            None => (create_sql, None),
+        };
+
         let mut ops = Vec::new();
         ops.extend(
             drop_ids
@@ -1025,6 +1158,9 @@ impl Coordinator {
                 desc: desc.clone(),
                 resolved_ids,
                 cluster_id,
+                custom_logical_compaction_window: compaction_window,
+                refresh_schedule,
+                hints,
             }),
             owner_id: *session.current_role_id(),
         });
@@ -1037,13 +1173,14 @@ impl Coordinator {
                     unreachable!()
                 };
 
-                let mut builder = txn.dataflow_builder(cluster_id);
+                let mut builder = txn.dataflow_builder(cluster_id).with_hints(&mv.hints);
                 let (df, df_metainfo) = builder.build_materialized_view(
                     id,
                     internal_view_id,
                     debug_name,
                     &mv.optimized_expr,
                     &mv.desc,
+                    mv.refresh_schedule.clone(),
                 )?;
 
                 Ok((df, df_metainfo))
@@ -1069,7 +1206,7 @@ impl Coordinator {
 
                 self.initialize_storage_read_policies(
                     vec![id],
-                    Some(DEFAULT_LOGICAL_COMPACTION_WINDOW_TS),
+                    Some(compaction_window_ts(compaction_window)),
                 )
                 .await;
 
@@ -1079,6 +1216,7 @@ impl Coordinator {
                 df.set_as_of(as_of);
                 let df = self.must_ship_dataflow(df, cluster_id).await;
                 self.catalog_mut().set_physical_plan(id, df);
+                self.record_plan_fingerprint(id).await;
 
                 Ok(ExecuteResponse::CreatedMaterializedView)
             }
@@ -1167,6 +1305,7 @@ impl Coordinator {
 
                 let df = self.must_ship_dataflow(df, cluster_id).await;
                 self.catalog_mut().set_physical_plan(id, df);
+                self.record_plan_fingerprint(id).await;
 
                 self.set_index_options(id, options).expect("index enabled");
                 Ok(ExecuteResponse::CreatedIndex)
@@ -1863,15 +2002,15 @@ impl Coordinator {
                 (response, action)
             }
             Ok((Some(TransactionOps::Writes(writes)), write_lock_guard)) => {
-                self.submit_write(PendingWriteTxn::User {
+                self.submit_user_write(
                     writes,
                     write_lock_guard,
-                    pending_txn: PendingTxn {
+                    PendingTxn {
                         ctx,
                         response,
                         action,
                     },
-                });
+                );
                 return;
             }
             Ok((Some(TransactionOps::Peeks(determination)), _))
@@ -1946,11 +2085,50 @@ impl Coordinator {
         Ok((None, None))
     }
 
-    pub(super) fn sequence_side_effecting_func(
+    pub(super) async fn sequence_side_effecting_func(
         &mut self,
+        session: &mut Session,
         plan: SideEffectingFunc,
     ) -> Result<ExecuteResponse, AdapterError> {
         match plan {
+            SideEffectingFunc::Nextval { sequence } => {
+                let value = self
+                    .allocate_sequence_values(sequence, 1)
+                    .await?
+                    .into_element();
+                session.set_sequence_value(sequence, value);
+                Ok(Self::send_immediate_rows(vec![Row::pack_slice(&[
+                    Datum::Int64(value),
+                ])]))
+            }
+            SideEffectingFunc::Currval { sequence } => match session.sequence_value(&sequence) {
+                Some(value) => Ok(Self::send_immediate_rows(vec![Row::pack_slice(&[
+                    Datum::Int64(value),
+                ])])),
+                None => Err(AdapterError::SequenceValueUndefined(
+                    self.catalog().get_entry(&sequence).name().item.clone(),
+                )),
+            },
+            SideEffectingFunc::Setval { sequence, value } => {
+                let entry = self.catalog().get_entry(&sequence);
+                let options = entry.sequence().expect("must be a sequence").options;
+                if !options.contains(value) {
+                    return Err(AdapterError::SequenceValueOutOfBounds {
+                        sequence: entry.name().item.clone(),
+                        value,
+                        min_value: options.min_value,
+                        max_value: options.max_value,
+                    });
+                }
+                let (_, next) = options.advance(value, 1);
+                self.catalog().set_sequence_value(sequence, next).await?;
+                // Values cached before the reset must not be handed out.
+                self.sequence_caches.remove(&sequence);
+                session.set_sequence_value(sequence, value);
+                Ok(Self::send_immediate_rows(vec![Row::pack_slice(&[
+                    Datum::Int64(value),
+                ])]))
+            }
             SideEffectingFunc::PgCancelBackend { connection_id } => {
                 let res = if let Some((id_handle, _conn_meta)) =
                     self.active_conns.get_key_value(&connection_id)
@@ -1966,6 +2144,47 @@ impl Coordinator {
         }
     }
 
+    /// Hands out the next `count` values of the sequence `id`.
+    ///
+    /// Values are drawn from the coordinator's cache of the sequence first. Any
+    /// shortfall is durably allocated from the catalog in batches of at least
+    /// the sequence's `CACHE` size, and the surplus is cached for later calls.
+    pub(crate) async fn allocate_sequence_values(
+        &mut self,
+        id: GlobalId,
+        count: usize,
+    ) -> Result<Vec<i64>, AdapterError> {
+        let mut values: Vec<i64> = match self.sequence_caches.get_mut(&id) {
+            Some(cache) => cache.drain(..std::cmp::min(count, cache.len())).collect(),
+            None => Vec::new(),
+        };
+        if values.len() < count {
+            let entry = self.catalog().get_entry(&id);
+            let options = entry.sequence().expect("must be a sequence").options;
+            let remaining = u64::cast_from(count - values.len());
+            let amount = std::cmp::max(options.cache, remaining);
+            let mut allocated = self
+                .catalog()
+                .allocate_sequence_values(id, options, amount)
+                .await?;
+            if u64::cast_from(allocated.len()) < remaining {
+                return Err(AdapterError::SequenceExhausted {
+                    sequence: entry.name().item.clone(),
+                    ascending: options.increment > 0,
+                    limit: if options.increment > 0 {
+                        options.max_value
+                    } else {
+                        options.min_value
+                    },
+                });
+            }
+            let surplus = allocated.split_off(usize::cast_from(remaining));
+            values.extend(allocated);
+            self.sequence_caches.entry(id).or_default().extend(surplus);
+        }
+        Ok(values)
+    }
+
     /// Sequence a peek, determining a timestamp and the most efficient dataflow interaction.
     ///
     /// Peeks are sequenced by assigning a timestamp for evaluation, and then determining and
@@ -2047,6 +2266,7 @@ impl Coordinator {
             when,
             finishing,
             copy_to,
+            hints,
         } = plan;
 
         // Two transient allocations. We could reclaim these if we don't use them, potentially.
@@ -2110,6 +2330,7 @@ impl Coordinator {
             target_replica,
             timeline_context,
             in_immediate_multi_stmt_txn,
+            hints,
         })
     }
 
@@ -2125,6 +2346,7 @@ impl Coordinator {
         // optimize_peek)?
         let id_bundle = self
             .dataflow_builder(stage.cluster_id)
+            .with_hints(&stage.hints)
             .sufficient_collections(&stage.source_ids);
         // Although we have added `sources.depends_on()` to the validity already, also add the
         // sufficient collections for safety.
@@ -2191,11 +2413,12 @@ impl Coordinator {
             target_replica,
             timeline_context,
             in_immediate_multi_stmt_txn,
+            hints,
         }: PeekStageOptimize,
     ) -> Result<PeekStageTimestamp, AdapterError> {
         let optimizer = Optimizer::logical_optimizer(&mz_transform::typecheck::empty_context());
         let source = optimizer.optimize(source)?;
-        let mut builder = DataflowBuilder::new(catalog, compute);
+        let mut builder = DataflowBuilder::new(catalog, compute).with_hints(&hints);
 
         // We create a dataflow and optimize it, to determine if we can avoid building it.
         // This can happen if the result optimizes to a constant, or to a `Get` expression
@@ -2231,7 +2454,8 @@ impl Coordinator {
         );
 
         // Optimize the dataflow across views, and any other ways that appeal.
-        let dataflow_metainfo = mz_transform::optimize_dataflow(&mut dataflow, &builder, &*stats)?;
+        let dataflow_metainfo =
+            mz_transform::optimize_dataflow(&mut dataflow, &builder, &*stats, &hints)?;
 
         Ok(PeekStageTimestamp {
             validity,
@@ -2294,6 +2518,7 @@ impl Coordinator {
                         index_id,
                         timeline_context,
                         source_ids,
+                        id_bundle,
                         in_immediate_multi_stmt_txn,
                         key,
                         typ,
@@ -2322,7 +2547,7 @@ impl Coordinator {
                     copy_to,
                     dataflow,
                     cluster_id,
-                    id_bundle: Some(id_bundle),
+                    id_bundle,
                     when,
                     target_replica,
                     view_id,
@@ -2361,10 +2586,6 @@ impl Coordinator {
             dataflow_metainfo,
         }: PeekStageFinish,
     ) -> Result<ExecuteResponse, AdapterError> {
-        let id_bundle = id_bundle.unwrap_or_else(|| {
-            self.index_oracle(cluster_id)
-                .sufficient_collections(&source_ids)
-        });
         let peek_plan = self.plan_peek(
             dataflow,
             ctx.session_mut(),
@@ -2699,6 +2920,7 @@ impl Coordinator {
                 connection: ComputeSinkConnection::Subscribe(SubscribeSinkConnection::default()),
                 with_snapshot,
                 up_to,
+                refresh_schedule: None,
             })
         };
 
@@ -2971,6 +3193,7 @@ impl Coordinator {
             raw_plan,
             row_set_finishing,
             broken,
+            hints,
         } = explainee
         else {
             // This is currently asserted in the `sequence_explain_plan` code that
@@ -2990,6 +3213,7 @@ impl Coordinator {
                 target_cluster,
                 ctx.session_mut(),
                 &row_set_finishing,
+                &hints,
             )
             .with_subscriber(&optimizer_trace)
             .await
@@ -3071,6 +3295,7 @@ impl Coordinator {
         target_cluster: TargetCluster,
         session: &mut Session,
         finishing: &Option<RowSetFinishing>,
+        hints: &OptimizerHints,
     ) -> Result<(UsedIndexes, Option<FastPathPlan>, DataflowMetainfo), AdapterError> {
         use mz_repr::explain::trace_plan;
 
@@ -3128,6 +3353,7 @@ impl Coordinator {
         let source_ids = decorrelated_plan.depends_on();
         let id_bundle = self
             .index_oracle(cluster_id)
+            .with_hints(hints)
             .sufficient_collections(&source_ids);
 
         // Execute the `optimize/local` stage.
@@ -3142,7 +3368,7 @@ impl Coordinator {
         })?;
 
         let mut dataflow = DataflowDesc::new("explanation".to_string());
-        let mut builder = self.dataflow_builder(cluster_id);
+        let mut builder = self.dataflow_builder(cluster_id).with_hints(hints);
         builder.import_view_into_dataflow(&explainee_id, &optimized_plan, &mut dataflow)?;
 
         // Resolve all unmaterializable function calls except mz_now(), because we don't yet have a
@@ -3180,8 +3406,9 @@ impl Coordinator {
         let dataflow_metainfo = catch_unwind(no_errors, "global", || {
             mz_transform::optimize_dataflow(
                 &mut dataflow,
-                &self.index_oracle(cluster_id),
+                &self.index_oracle(cluster_id).with_hints(hints),
                 stats.as_ref(),
+                hints,
             )
         })?;
 
@@ -3443,7 +3670,20 @@ impl Coordinator {
             return_if_err!(self.view_optimizer.optimize(plan.values), ctx)
         };
 
-        match optimized_mir.into_inner() {
+        let mut selection = optimized_mir.into_inner();
+        // The sequence defaults of constant inserts are filled in right away, and those of other
+        // inserts once their rows are read.
+        let mut sequence_defaults = plan.sequence_defaults;
+        if !sequence_defaults.is_empty() && selection.as_const().is_some() {
+            selection = return_if_err!(
+                self.fill_sequence_defaults(selection, &sequence_defaults)
+                    .await,
+                ctx
+            );
+            sequence_defaults.clear();
+        }
+
+        match selection {
             selection if selection.as_const().is_some() && plan.returning.is_empty() => {
                 let catalog = self.owned_catalog();
                 mz_ore::task::spawn(|| "coord::sequence_inner", async move {
@@ -3492,6 +3732,7 @@ impl Coordinator {
                     assignments: BTreeMap::new(),
                     kind: MutationKind::Insert,
                     returning: plan.returning,
+                    sequence_defaults,
                 };
 
                 self.sequence_read_then_write(ctx, read_then_write_plan)
@@ -3500,6 +3741,67 @@ impl Coordinator {
         }
     }
 
+    /// Fills in the columns of the constant `selection` that default to a
+    /// sequence with values drawn from that sequence, one per inserted row.
+    async fn fill_sequence_defaults(
+        &mut self,
+        selection: MirRelationExpr,
+        sequence_defaults: &BTreeMap<usize, GlobalId>,
+    ) -> Result<MirRelationExpr, AdapterError> {
+        let count = Self::constant_row_count(&selection)?;
+        let mut values = BTreeMap::new();
+        for (column, sequence) in sequence_defaults {
+            values.insert(
+                *column,
+                self.allocate_sequence_values(*sequence, count).await?,
+            );
+        }
+        Self::fill_sequence_values(selection, values)
+    }
+
+    /// Returns the number of rows of the constant `selection`.
+    pub(crate) fn constant_row_count(selection: &MirRelationExpr) -> Result<usize, AdapterError> {
+        match selection.as_const() {
+            Some((Ok(rows), _)) => rows.iter().try_fold(0, |count, (_, diff)| {
+                let diff = usize::try_from(*diff).map_err(|_| {
+                    AdapterError::Internal(format!("invalid insert multiplicity {diff}"))
+                })?;
+                Ok(count + diff)
+            }),
+            Some((Err(e), _)) => Err(e.clone().into()),
+            None => unreachable!("only called with constant selections"),
+        }
+    }
+
+    /// Replaces the columns of the constant `selection` that default to a
+    /// sequence with the given sequence `values`, one per inserted row.
+    pub(crate) fn fill_sequence_values(
+        selection: MirRelationExpr,
+        values: BTreeMap<usize, Vec<i64>>,
+    ) -> Result<MirRelationExpr, AdapterError> {
+        let (rows, mut typ) = match selection.as_const() {
+            Some((Ok(rows), typ)) => (rows.clone(), typ.clone()),
+            Some((Err(e), _)) => return Err(e.clone().into()),
+            None => unreachable!("only called with constant selections"),
+        };
+        let mut expanded = Vec::new();
+        for (row, diff) in rows {
+            let count = usize::try_from(diff).map_err(|_| {
+                AdapterError::Internal(format!("invalid insert multiplicity {diff}"))
+            })?;
+            expanded.extend(iter::repeat(row).take(count));
+        }
+        let mut datums: Vec<Vec<Datum>> = expanded.iter().map(|row| row.unpack()).collect();
+        for (column, values) in values {
+            let column_type = &mut typ.column_types[column];
+            for (datums, value) in datums.iter_mut().zip(values) {
+                datums[column] = sequence_datum(&column_type.scalar_type, value)?;
+            }
+            column_type.nullable = false;
+        }
+        Ok(MirRelationExpr::constant(datums, typ))
+    }
+
     /// ReadThenWrite is a plan whose writes depend on the results of a
     /// read. This works by doing a Peek then queuing a SendDiffs. No writes
     /// or read-then-writes can occur between the Peek and SendDiff otherwise a
@@ -3520,6 +3822,7 @@ impl Coordinator {
             assignments,
             finishing,
             returning,
+            sequence_defaults,
         } = plan;
 
         // Read then writes can be queued, so re-verify the id exists.
@@ -3610,14 +3913,24 @@ impl Coordinator {
                 when: QueryWhen::Freshest,
                 finishing,
                 copy_to: None,
+                hints: Default::default(),
             },
             TargetCluster::Active,
         )
         .await;
 
+        // The type of each column that defaults to the next value of a sequence.
+        let sequence_defaults: Vec<_> = sequence_defaults
+            .into_iter()
+            .map(|(column, sequence)| {
+                let scalar_type = desc.typ().column_types[column].scalar_type.clone();
+                (column, sequence, scalar_type)
+            })
+            .collect();
         let internal_cmd_tx = self.internal_cmd_tx.clone();
         let strict_serializable_reads_tx = self.strict_serializable_reads_tx.clone();
         let max_result_size = self.catalog().system_config().max_result_size();
+        let checks = Self::enforced_check_constraints(self.catalog(), id);
         task::spawn(|| format!("sequence_read_then_write:{id}"), async move {
             let (peek_response, session) = match peek_rx.await {
                 Ok(Response {
@@ -3638,12 +3951,58 @@ impl Coordinator {
             };
             let mut ctx = ExecuteContext::from_parts(tx, internal_cmd_tx.clone(), session, extra);
             let timeout_dur = *ctx.session().vars().statement_timeout();
-            let make_diffs = move |rows: Vec<Row>| -> Result<Vec<(Row, Diff)>, AdapterError> {
+            let make_diffs = move |rows: Vec<Row>| -> Result<_, AdapterError> {
                 let arena = RowArena::new();
                 // Use 2x row len incase there's some assignments.
-                let mut diffs = Vec::with_capacity(rows.len() * 2);
+                let mut diffs: Vec<(Row, Diff)> = Vec::with_capacity(rows.len() * 2);
                 let mut datum_vec = mz_repr::DatumVec::new();
+                let mut affected_rows: Option<usize> = None;
+                let command = match kind {
+                    MutationKind::Upsert { update: true } => Some("ON CONFLICT DO UPDATE"),
+                    MutationKind::Upsert { update: false } => Some("ON CONFLICT DO NOTHING"),
+                    MutationKind::Merge { .. } => Some("MERGE"),
+                    MutationKind::Insert | MutationKind::Update | MutationKind::Delete => None,
+                };
+                // The keys of the rows inserted so far, per key of the table.
+                let mut inserted_keys = BTreeSet::new();
                 for row in rows {
+                    if let MutationKind::Upsert { .. } | MutationKind::Merge { .. } = kind {
+                        // Each row describes one affected row of the table, as
+                        // `[old..., new..., retract, insert]`.
+                        let datums = datum_vec.borrow_with(&row);
+                        let arity = (datums.len() - 2) / 2;
+                        let retract = datums[2 * arity] == Datum::True;
+                        let insert = datums[2 * arity + 1] == Datum::True;
+                        if insert {
+                            // Rows proposed by the same statement don't
+                            // conflict with each other in the read, so a row
+                            // whose key was already inserted is detected here.
+                            // The rows arrive in no particular order, so even
+                            // `DO NOTHING` can't tell which of them came first
+                            // and should be inserted.
+                            let new = &datums[arity..2 * arity];
+                            let keys: Vec<_> = desc
+                                .typ()
+                                .keys
+                                .iter()
+                                .enumerate()
+                                .map(|(i, key)| (i, Row::pack(key.iter().map(|c| new[*c]))))
+                                .collect();
+                            if keys.iter().any(|key| inserted_keys.contains(key)) {
+                                let command = command.expect("upsert or merge");
+                                return Err(AdapterError::RowAffectedTwice { command });
+                            }
+                            inserted_keys.extend(keys);
+                        }
+                        if retract {
+                            diffs.push((Row::pack_slice(&datums[..arity]), -1));
+                        }
+                        if insert {
+                            diffs.push((Row::pack_slice(&datums[arity..2 * arity]), 1));
+                        }
+                        *affected_rows.get_or_insert(0) += 1;
+                        continue;
+                    }
                     if !assignments.is_empty() {
                         assert!(
                             matches!(kind, MutationKind::Update),
@@ -3670,6 +4029,19 @@ impl Coordinator {
                         // updated value.
                         MutationKind::Update | MutationKind::Delete => diffs.push((row, -1)),
                         MutationKind::Insert => diffs.push((row, 1)),
+                        MutationKind::Upsert { .. } | MutationKind::Merge { .. } => {
+                            unreachable!("handled above")
+                        }
+                    }
+                }
+                if let Some(command) = command {
+                    // The rows of the table are distinct, so a row that is
+                    // retracted more than once was affected more than once.
+                    let mut retracted = BTreeSet::new();
+                    for (row, diff) in &diffs {
+                        if *diff < 0 && !retracted.insert(row) {
+                            return Err(AdapterError::RowAffectedTwice { command });
+                        }
                     }
                 }
                 for (row, diff) in &diffs {
@@ -3677,11 +4049,14 @@ impl Coordinator {
                         for (idx, datum) in row.iter().enumerate() {
                             desc.constraints_met(idx, &datum)?;
                         }
+                        if let Some((relation, checks)) = &checks {
+                            Self::check_constraints_met(relation, checks, row)?;
+                        }
                     }
                 }
-                Ok(diffs)
+                Ok((diffs, affected_rows))
             };
-            let diffs = match peek_response {
+            let rows = match peek_response {
                 ExecuteResponse::SendingRows {
                     future: batch,
                     span: _,
@@ -3692,7 +4067,7 @@ impl Coordinator {
                     // clusters.
                     match tokio::time::timeout(timeout_dur, batch).await {
                         Ok(res) => match res {
-                            PeekResponseUnary::Rows(rows) => make_diffs(rows),
+                            PeekResponseUnary::Rows(rows) => Ok(rows),
                             PeekResponseUnary::Canceled => Err(AdapterError::Canceled),
                             PeekResponseUnary::Error(e) => {
                                 Err(AdapterError::Unstructured(anyhow!(e)))
@@ -3713,7 +4088,7 @@ impl Coordinator {
                         }
                     }
                 }
-                ExecuteResponse::SendingRowsImmediate { rows, span: _ } => make_diffs(rows),
+                ExecuteResponse::SendingRowsImmediate { rows, span: _ } => Ok(rows),
                 resp @ ExecuteResponse::Canceled => {
                     ctx.retire(Ok(resp));
                     return;
@@ -3722,6 +4097,17 @@ impl Coordinator {
                     "unexpected peek response: {resp:?}"
                 ))),
             };
+            let rows = match rows {
+                Ok(rows) if !sequence_defaults.is_empty() => {
+                    fill_read_sequence_defaults(&internal_cmd_tx, rows, &sequence_defaults).await
+                }
+                rows => rows,
+            };
+            let diffs = rows.and_then(make_diffs);
+            let (diffs, affected_rows) = match diffs {
+                Ok((diffs, affected_rows)) => (Ok(diffs), affected_rows),
+                Err(e) => (Err(e), None),
+            };
             let mut returning_rows = Vec::new();
             let mut diff_err: Option<AdapterError> = None;
             if !returning.is_empty() && diffs.is_ok() {
@@ -3818,6 +4204,7 @@ impl Coordinator {
                             kind,
                             returning: returning_rows,
                             max_result_size,
+                            affected_rows,
                         },
                     );
                     ctx.retire(result);
@@ -3845,6 +4232,127 @@ impl Coordinator {
         }
     }
 
+    pub(super) async fn sequence_alter_retain_history(
+        &mut self,
+        session: &Session,
+        plan: plan::AlterRetainHistoryPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let plan::AlterRetainHistoryPlan {
+            id,
+            object_type,
+            value,
+            window,
+        } = plan;
+        let entry = self.catalog().get_entry(&id);
+
+        let value = match value {
+            Some(WithOptionValue::RetainHistoryFor(value)) => {
+                Some(WithOptionValue::RetainHistoryFor(value))
+            }
+            Some(value) => {
+                coord_bail!("unexpected RETAIN HISTORY value: {}", value.to_ast_string())
+            }
+            None => None,
+        };
+
+        // The subsources that ingest a source's data retain as much history as
+        // the source itself.
+        let progress_id = entry.progress_id();
+        let mut ids = vec![id];
+        ids.extend(
+            entry
+                .subsources()
+                .into_iter()
+                .filter(|id| Some(*id) != progress_id),
+        );
+
+        let mut ops = vec![];
+        for id in &ids {
+            let entry = self.catalog().get_entry(id);
+
+            // Rewrite the item's definition so that the new retention period is
+            // picked up again on restart.
+            let mut stmt = mz_sql::parse::parse(entry.create_sql())
+                .expect("invalid create sql persisted to catalog")
+                .into_element()
+                .ast;
+            match &mut stmt {
+                Statement::CreateMaterializedView(stmt) => {
+                    stmt.with_options
+                        .retain(|o| o.name != MaterializedViewOptionName::RetainHistory);
+                    if value.is_some() {
+                        stmt.with_options.push(MaterializedViewOption {
+                            name: MaterializedViewOptionName::RetainHistory,
+                            value: value.clone(),
+                        });
+                    }
+                }
+                Statement::CreateSource(stmt) => {
+                    stmt.with_options
+                        .retain(|o| o.name != CreateSourceOptionName::RetainHistory);
+                    if value.is_some() {
+                        stmt.with_options.push(CreateSourceOption {
+                            name: CreateSourceOptionName::RetainHistory,
+                            value: value.clone(),
+                        });
+                    }
+                }
+                Statement::CreateSubsource(stmt) => {
+                    stmt.with_options
+                        .retain(|o| o.name != CreateSubsourceOptionName::RetainHistory);
+                    if value.is_some() {
+                        stmt.with_options.push(CreateSubsourceOption {
+                            name: CreateSubsourceOptionName::RetainHistory,
+                            value: value.clone(),
+                        });
+                    }
+                }
+                Statement::CreateTable(stmt) => {
+                    stmt.with_options
+                        .retain(|o| o.name != TableOptionName::RetainHistory);
+                    if value.is_some() {
+                        stmt.with_options.push(TableOption {
+                            name: TableOptionName::RetainHistory,
+                            value: value.clone(),
+                        });
+                    }
+                }
+                _ => coord_bail!("cannot alter the retained history of {}", entry.item_type()),
+            }
+            let create_sql = stmt.to_ast_string_stable();
+
+            let mut item = entry.item().clone();
+            match &mut item {
+                CatalogItem::MaterializedView(mv) => {
+                    mv.create_sql = create_sql;
+                    mv.custom_logical_compaction_window = window;
+                }
+                CatalogItem::Source(source) => {
+                    source.create_sql = create_sql;
+                    source.custom_logical_compaction_window = window;
+                }
+                CatalogItem::Table(table) => {
+                    table.create_sql = create_sql;
+                    table.custom_logical_compaction_window = window;
+                }
+                _ => unreachable!("checked above"),
+            }
+            ops.push(catalog::Op::UpdateItem {
+                id: *id,
+                name: entry.name().clone(),
+                to_item: item,
+            });
+        }
+        self.catalog_transact(Some(session), ops).await?;
+
+        let policy = ReadPolicy::lag_writes_by(compaction_window_ts(window), SINCE_GRANULARITY);
+        self.update_storage_base_read_policies(
+            ids.into_iter().map(|id| (id, policy.clone())).collect(),
+        );
+
+        Ok(ExecuteResponse::AlteredObject(object_type))
+    }
+
     pub(super) fn sequence_alter_index_set_options(
         &mut self,
         plan: plan::AlterIndexSetOptionsPlan,
@@ -5012,6 +5520,7 @@ impl Coordinator {
 #[derive(Debug)]
 struct CachedStatisticsOracle {
     cache: BTreeMap<GlobalId, usize>,
+    cost_based_join_ordering: bool,
 }
 
 const OPTIMIZER_MAX_STATS_WAIT: Duration = Duration::from_millis(250);
@@ -5022,6 +5531,7 @@ impl CachedStatisticsOracle {
         ids: &BTreeSet<GlobalId>,
         as_of: &Antichain<T>,
         storage: &dyn mz_storage_client::controller::StorageController<Timestamp = T>,
+        cost_based_join_ordering: bool,
     ) -> Result<Self, StorageError> {
         let mut cache = BTreeMap::new();
 
@@ -5046,7 +5556,10 @@ impl CachedStatisticsOracle {
             }
         }
 
-        Ok(Self { cache })
+        Ok(Self {
+            cache,
+            cost_based_join_ordering,
+        })
     }
 }
 
@@ -5054,6 +5567,10 @@ impl mz_transform::StatisticsOracle for CachedStatisticsOracle {
     fn cardinality_estimate(&self, id: GlobalId) -> Option<usize> {
         self.cache.get(&id).map(|estimate| *estimate)
     }
+
+    fn cost_based_join_ordering(&self) -> bool {
+        self.cost_based_join_ordering
+    }
 }
 
 impl Coordinator {
@@ -5077,7 +5594,12 @@ impl Coordinator {
 
         let cached_stats = mz_ore::future::timeout(
             timeout,
-            CachedStatisticsOracle::new(source_ids, &query_as_of, self.controller.storage.as_ref()),
+            CachedStatisticsOracle::new(
+                source_ids,
+                &query_as_of,
+                self.controller.storage.as_ref(),
+                session.vars().enable_session_cost_based_join_ordering(),
+            ),
         )
         .await;
 
@@ -5142,6 +5664,43 @@ fn alter_storage_cluster_config(size: AlterOptionParameter) -> Option<SourceSink
     }
 }
 
+/// Replaces the times in the `REFRESH` options of a materialized view's
+/// definition with the evaluated times of its refresh `schedule`.
+///
+/// `REFRESH AT CREATION` becomes `REFRESH AT <time>`, and `REFRESH EVERY`
+/// options always get an explicit `ALIGNED TO`.
+fn rewrite_refresh_options(create_sql: &str, schedule: &RefreshSchedule) -> String {
+    let mut stmt = mz_sql::parse::parse(create_sql)
+        .expect("invalid create sql")
+        .into_element()
+        .ast;
+    let Statement::CreateMaterializedView(create_stmt) = &mut stmt else {
+        unreachable!("refresh schedule on a non-materialized view");
+    };
+    let literal = |ts: Timestamp| Expr::Value(Value::Number(ts.to_string()));
+    // The planner retains the order of the `REFRESH AT` and `REFRESH EVERY`
+    // options, respectively.
+    let mut everies = schedule.everies.iter();
+    let mut ats = schedule.ats.iter();
+    for option in &mut create_stmt.with_options {
+        let Some(WithOptionValue::Refresh(refresh)) = &mut option.value else {
+            continue;
+        };
+        match refresh {
+            RefreshOptionValue::AtCreation | RefreshOptionValue::At(_) => {
+                let at = ats.next().expect("one time per REFRESH AT");
+                *refresh = RefreshOptionValue::At(RefreshAtOptionValue { time: literal(*at) });
+            }
+            RefreshOptionValue::Every(every) => {
+                let aligned_to = everies.next().expect("one time per REFRESH EVERY");
+                every.aligned_to = Some(literal(aligned_to.aligned_to));
+            }
+            RefreshOptionValue::OnCommit => {}
+        }
+    }
+    stmt.to_ast_string_stable()
+}
+
 impl Coordinator {
     /// Forward notices that we got from the optimizer.
     fn emit_optimizer_notices(
@@ -5157,6 +5716,9 @@ impl Coordinator {
                     system_vars.enable_notices_for_index_too_wide_for_literal_constraints()
                 }
                 OptimizerNotice::IndexKeyEmpty => system_vars.enable_notices_for_index_empty_key(),
+                OptimizerNotice::UnsatisfiedHint(..) => {
+                    system_vars.enable_notices_for_unsatisfied_optimizer_hints()
+                }
             };
             if notice_enabled {
                 let (notice, hint) = optimizer_notice.to_string(&humanizer);
@@ -5165,3 +5727,47 @@ impl Coordinator {
         }
     }
 }
+
+/// Converts the sequence value `value` to a datum of the integer type `scalar_type`.
+fn sequence_datum(scalar_type: &ScalarType, value: i64) -> Result<Datum<'static>, EvalError> {
+    Ok(match scalar_type {
+        ScalarType::Int16 => Datum::Int16(
+            i16::try_from(value).map_err(|_| EvalError::Int16OutOfRange(value.to_string()))?,
+        ),
+        ScalarType::Int32 => Datum::Int32(
+            i32::try_from(value).map_err(|_| EvalError::Int32OutOfRange(value.to_string()))?,
+        ),
+        _ => Datum::Int64(value),
+    })
+}
+
+/// Fills in the columns of the read `rows` that default to a sequence with values drawn from
+/// that sequence, one per row. The values are allocated by the coordinator, because
+/// allocating them may require a durable catalog write.
+async fn fill_read_sequence_defaults(
+    internal_cmd_tx: &mpsc::UnboundedSender<Message>,
+    rows: Vec<Row>,
+    sequence_defaults: &[(usize, GlobalId, ScalarType)],
+) -> Result<Vec<Row>, AdapterError> {
+    let mut datums: Vec<Vec<Datum>> = rows.iter().map(|row| row.unpack()).collect();
+    for (column, sequence, scalar_type) in sequence_defaults {
+        let (tx, rx) = oneshot::channel();
+        internal_cmd_tx
+            .send(Message::AllocateSequenceValues {
+                id: *sequence,
+                count: rows.len(),
+                tx,
+            })
+            .map_err(|_| AdapterError::Internal("coordinator shut down".into()))?;
+        let values = rx
+            .await
+            .map_err(|_| AdapterError::Internal("coordinator shut down".into()))??;
+        for (datums, value) in datums.iter_mut().zip(values) {
+            datums[*column] = sequence_datum(scalar_type, value)?;
+        }
+    }
+    Ok(datums
+        .iter()
+        .map(|datums| Row::pack_slice(datums))
+        .collect())
+}
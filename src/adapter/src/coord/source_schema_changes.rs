@@ -0,0 +1,374 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Absorption of new upstream columns into the subsources of PostgreSQL sources.
+//!
+//! PostgreSQL sources created with `SCHEMA CHANGES = 'ADDITIVE'` stall with a transient error when
+//! a table they ingest gains columns. The coordinator periodically compares the tables of such
+//! sources with their upstream definitions and, if a table only gained nullable columns of
+//! supported types, appends those columns to the subsource's description in the catalog and in
+//! the storage controller, and restarts the ingestion with the new table definition. Rows written
+//! before the change read `NULL` in the new columns.
+
+use mz_ore::collections::CollectionExt;
+use mz_ore::task;
+use mz_postgres_util::desc::PostgresTableDesc;
+use mz_proto::RustType;
+use mz_repr::{GlobalId, RelationDesc};
+use mz_sql::ast::{
+    CreateSourceConnection, PgConfigOption, PgConfigOptionName, Statement, Value, WithOptionValue,
+};
+use mz_sql::plan::{Params, Plan};
+use mz_storage_client::types::connections::inline::IntoInlineConnection;
+use mz_storage_client::types::connections::ConnectionContext;
+use mz_storage_client::types::sources::{
+    GenericSourceConnection, IngestionDescription, PostgresSourceConnection,
+    PostgresSourcePublicationDetails,
+};
+use prost::Message as _;
+use tracing::warn;
+
+use crate::catalog::{self, CatalogItem, DataSourceDesc};
+use crate::coord::{Coordinator, Message};
+use crate::session::Session;
+use crate::AdapterError;
+
+const SCHEMA_CHANGE: &str = "PostgreSQL source schema change";
+
+/// The changes to apply to the catalog and the storage controller to absorb the new columns of
+/// the tables of a PostgreSQL source.
+struct SchemaChange {
+    ops: Vec<catalog::Op>,
+    subsource_descs: Vec<(GlobalId, RelationDesc)>,
+    ingestion: IngestionDescription,
+}
+
+impl Coordinator {
+    /// Schedules the next check of PostgreSQL sources for new upstream columns.
+    pub(crate) fn schedule_pg_source_schema_change_check(&self) {
+        let interval = self
+            .catalog()
+            .system_config()
+            .pg_source_schema_change_check_interval();
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| "pg_source_schema_change_check", async move {
+            tokio::time::sleep(interval).await;
+            if internal_cmd_tx
+                .send(Message::PgSourceSchemaChangeCheck)
+                .is_err()
+            {
+                // If sending fails, the main thread has shutdown.
+            }
+        });
+    }
+
+    /// Fetches the upstream definitions of the tables of all PostgreSQL sources that allow
+    /// additive schema changes, off the main loop, and reports the tables that gained columns.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn check_pg_source_schema_changes(&mut self) {
+        let sources: Vec<_> = self
+            .catalog()
+            .entries()
+            .filter_map(|entry| {
+                let DataSourceDesc::Ingestion(ingestion) = &entry.source()?.data_source else {
+                    return None;
+                };
+                match &ingestion.desc.connection {
+                    GenericSourceConnection::Postgres(connection)
+                        if connection.additive_schema_changes =>
+                    {
+                        let connection = connection
+                            .clone()
+                            .into_inline_connection(self.catalog().state());
+                        Some((entry.id(), connection))
+                    }
+                    _ => None,
+                }
+            })
+            .collect();
+
+        if sources.is_empty() {
+            self.schedule_pg_source_schema_change_check();
+            return;
+        }
+
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let connection_context = self.connection_context.clone();
+        task::spawn(|| "pg_source_schema_change_fetch", async move {
+            let mut changes = vec![];
+            for (id, connection) in sources {
+                match evolved_tables(&connection, &connection_context).await {
+                    Ok(tables) if tables.is_empty() => {}
+                    Ok(tables) => changes.push((id, tables)),
+                    Err(e) => {
+                        warn!("failed to check source {id} for upstream schema changes: {e:#}")
+                    }
+                }
+            }
+            if internal_cmd_tx
+                .send(Message::PgSourceSchemaChanges(changes))
+                .is_err()
+            {
+                // If sending fails, the main thread has shutdown.
+            }
+        });
+    }
+
+    /// Appends the new columns of the given tables to their subsources.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) async fn apply_pg_source_schema_changes(
+        &mut self,
+        changes: Vec<(GlobalId, Vec<PostgresTableDesc>)>,
+    ) {
+        for (id, tables) in changes {
+            // The source may have been dropped or altered while the check was in flight.
+            let change = match self.pg_source_schema_change(id, &tables) {
+                Ok(Some(change)) => change,
+                Ok(None) => continue,
+                Err(e) => {
+                    warn!("failed to absorb upstream schema changes of source {id}: {e}");
+                    continue;
+                }
+            };
+            let SchemaChange {
+                ops,
+                subsource_descs,
+                ingestion,
+            } = change;
+
+            if let Err(e) = self.catalog_transact(None::<&Session>, ops).await {
+                warn!("failed to absorb upstream schema changes of source {id}: {e}");
+                continue;
+            }
+
+            for (subsource_id, desc) in subsource_descs {
+                self.controller
+                    .storage
+                    .alter_collection_desc(subsource_id, desc)
+                    .expect("altering collection description after txn must succeed");
+            }
+            self.controller
+                .storage
+                .alter_collection(id, ingestion)
+                .await
+                .expect("altering collection after txn must succeed");
+        }
+
+        self.schedule_pg_source_schema_change_check();
+    }
+
+    /// Determines the changes to apply to absorb the new columns of `tables` into the
+    /// subsources of source `id`.
+    ///
+    /// Returns `None` if the source no longer exists or has already absorbed the columns.
+    fn pg_source_schema_change(
+        &self,
+        id: GlobalId,
+        tables: &[PostgresTableDesc],
+    ) -> Result<Option<SchemaChange>, AdapterError> {
+        let Some(entry) = self.catalog().try_get_entry(&id) else {
+            return Ok(None);
+        };
+        let Some(source) = entry.source() else {
+            return Ok(None);
+        };
+        let DataSourceDesc::Ingestion(cur_ingestion) = &source.data_source else {
+            return Ok(None);
+        };
+        let GenericSourceConnection::Postgres(cur_connection) = &cur_ingestion.desc.connection
+        else {
+            return Ok(None);
+        };
+
+        let mut details = cur_connection.publication_details.clone();
+        let mut ops = vec![];
+        let mut subsource_descs = vec![];
+        for table in tables {
+            let Some(position) = details.tables.iter().position(|t| t.oid == table.oid) else {
+                continue;
+            };
+            let expected = &details.tables[position];
+            if expected.columns.len() >= table.columns.len()
+                || expected.determine_additive_compatibility(table).is_err()
+            {
+                continue;
+            }
+            // Outputs are numbered from one, because the zeroth output is the source itself.
+            let output_index = position + 1;
+            let subsource_id = cur_ingestion
+                .source_exports
+                .iter()
+                .find(|(_, export)| export.output_index == output_index)
+                .map(|(subsource_id, _)| *subsource_id);
+            let Some(subsource_id) = subsource_id else {
+                continue;
+            };
+
+            let subsource_entry = self.catalog().get_entry(&subsource_id);
+            let subsource = subsource_entry.source().expect("known to be source");
+            let stmt = match mz_sql::parse::parse(subsource_entry.create_sql())
+                .expect("invalid create sql persisted to catalog")
+                .into_element()
+                .ast
+            {
+                Statement::CreateSubsource(stmt) => stmt,
+                _ => unreachable!("proved type is subsource"),
+            };
+            let catalog = self.catalog().for_system_session();
+            let (mut stmt, resolved_ids) = mz_sql::names::resolve(&catalog, stmt)
+                .map_err(|e| AdapterError::internal(SCHEMA_CHANGE, e))?;
+            mz_sql::pure::add_postgres_subsource_columns(&catalog, &mut stmt, expected, table)
+                .map_err(|e| AdapterError::internal(SCHEMA_CHANGE, e))?;
+            let plan = match mz_sql::plan::plan(
+                None,
+                &catalog,
+                Statement::CreateSubsource(stmt),
+                &Params::empty(),
+                &resolved_ids,
+            )
+            .map_err(|e| AdapterError::internal(SCHEMA_CHANGE, e))?
+            {
+                Plan::CreateSource(plan) => plan,
+                _ => unreachable!("create subsource plan is only valid response"),
+            };
+            let new_subsource = catalog::Source::new(
+                subsource_id,
+                plan,
+                None,
+                resolved_ids,
+                subsource.custom_logical_compaction_window,
+                subsource.is_retained_metrics_object,
+            );
+            self.controller
+                .storage
+                .check_alter_collection_desc(subsource_id, &new_subsource.desc)
+                .map_err(|e| AdapterError::internal(SCHEMA_CHANGE, e))?;
+
+            subsource_descs.push((subsource_id, new_subsource.desc.clone()));
+            ops.push(catalog::Op::UpdateItem {
+                id: subsource_id,
+                name: subsource_entry.name().clone(),
+                to_item: CatalogItem::Source(new_subsource),
+            });
+
+            // Only ever append columns, so that the existing columns of the subsource keep the
+            // types and constraints they were ingested with.
+            let mut evolved = expected.clone();
+            evolved
+                .columns
+                .extend(table.columns[expected.columns.len()..].iter().cloned());
+            details.tables[position] = evolved;
+        }
+
+        if ops.is_empty() {
+            return Ok(None);
+        }
+
+        // Re-plan the source with the evolved table definitions, which extends the casts of the
+        // affected tables to the new columns.
+        let stmt = match mz_sql::parse::parse(entry.create_sql())
+            .expect("invalid create sql persisted to catalog")
+            .into_element()
+            .ast
+        {
+            Statement::CreateSource(stmt) => stmt,
+            _ => unreachable!("proved type is source"),
+        };
+        let mut catalog = self.catalog().for_system_session();
+        let (mut stmt, resolved_ids) = mz_sql::names::resolve(&catalog, stmt)
+            .map_err(|e| AdapterError::internal(SCHEMA_CHANGE, e))?;
+        let CreateSourceConnection::Postgres { options, .. } = &mut stmt.connection else {
+            unreachable!("proved source is a PostgreSQL source");
+        };
+        options.retain(|PgConfigOption { name, .. }| name != &PgConfigOptionName::Details);
+        options.push(PgConfigOption {
+            name: PgConfigOptionName::Details,
+            value: Some(WithOptionValue::Value(Value::String(hex::encode(
+                details.into_proto().encode_to_vec(),
+            )))),
+        });
+
+        catalog.mark_id_unresolvable_for_replanning(id);
+        let plan = match mz_sql::plan::plan(
+            None,
+            &catalog,
+            Statement::CreateSource(stmt),
+            &Params::empty(),
+            &resolved_ids,
+        )
+        .map_err(|e| AdapterError::internal(SCHEMA_CHANGE, e))?
+        {
+            Plan::CreateSource(plan) => plan,
+            _ => unreachable!("create source plan is only valid response"),
+        };
+        let new_source = catalog::Source::new(
+            id,
+            plan,
+            Some(cur_ingestion.instance_id),
+            resolved_ids,
+            source.custom_logical_compaction_window,
+            source.is_retained_metrics_object,
+        );
+        let ingestion = match &new_source.data_source {
+            DataSourceDesc::Ingestion(ingestion) => ingestion
+                .clone()
+                .into_inline_connection(self.catalog().state()),
+            _ => unreachable!("already verified of type ingestion"),
+        };
+        self.controller
+            .storage
+            .check_alter_collection(id, ingestion.clone())
+            .map_err(|e| AdapterError::internal(SCHEMA_CHANGE, e))?;
+
+        ops.push(catalog::Op::UpdateItem {
+            id,
+            name: entry.name().clone(),
+            to_item: CatalogItem::Source(new_source),
+        });
+
+        Ok(Some(SchemaChange {
+            ops,
+            subsource_descs,
+            ingestion,
+        }))
+    }
+}
+
+/// Returns the upstream definitions of the tables of `connection` that only gained nullable
+/// columns of supported types since the source last absorbed their schema.
+async fn evolved_tables(
+    connection: &PostgresSourceConnection,
+    connection_context: &ConnectionContext,
+) -> Result<Vec<PostgresTableDesc>, anyhow::Error> {
+    let config = connection
+        .connection
+        .config(&*connection_context.secrets_reader)
+        .await?;
+    let upstream =
+        mz_postgres_util::publication_info(&config, &connection.publication, None).await?;
+
+    let PostgresSourcePublicationDetails { tables, .. } = &connection.publication_details;
+    let evolved = tables
+        .iter()
+        .enumerate()
+        // Tables without casts are not ingested into any subsource.
+        .filter(|(position, _)| connection.table_casts.contains_key(&(position + 1)))
+        .filter_map(|(_, expected)| {
+            let table = upstream.iter().find(|t| t.oid == expected.oid)?;
+            let added = table.columns.get(expected.columns.len()..)?;
+            let absorbable = !added.is_empty()
+                && expected.determine_additive_compatibility(table).is_ok()
+                && added
+                    .iter()
+                    .all(|c| mz_pgrepr::Type::from_oid_and_typmod(c.type_oid, -1).is_ok());
+            absorbable.then(|| table.clone())
+        })
+        .collect();
+    Ok(evolved)
+}
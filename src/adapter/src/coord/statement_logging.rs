@@ -11,6 +11,7 @@ use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use bytes::BytesMut;
+use mz_controller::clusters::ClusterId;
 use mz_ore::{cast::CastFrom, now::EpochMillis};
 use mz_sql::plan::Params;
 use qcell::QCell;
@@ -18,11 +19,12 @@ use rand::SeedableRng;
 use rand::{distributions::Bernoulli, prelude::Distribution, thread_rng};
 use uuid::Uuid;
 
+use crate::coord::peek::IndexRecommendation;
 use crate::coord::{ConnMeta, Coordinator};
 use crate::session::Session;
 use crate::statement_logging::{
     SessionHistoryEvent, StatementBeganExecutionRecord, StatementEndedExecutionReason,
-    StatementEndedExecutionRecord, StatementPreparedRecord,
+    StatementEndedExecutionRecord, StatementExecutionStrategy, StatementPreparedRecord,
 };
 
 /// Metadata required for logging a prepared statement.
@@ -61,6 +63,13 @@ pub(crate) struct StatementLogging {
     /// execution finished.)
     executions_begun: BTreeMap<Uuid, StatementBeganExecutionRecord>,
 
+    /// Indexes that would have allowed statement executions that have been logged but not
+    /// finished to use the fast path, and the clusters these executions run on.
+    ///
+    /// They are only recorded once the statement log shows that the execution succeeded by
+    /// building a dataflow.
+    index_recommendations: BTreeMap<Uuid, (ClusterId, IndexRecommendation)>,
+
     /// Information about sessions that have been started, but which
     /// have not yet been logged in `mz_session_history`.
     /// They may be logged as part of a statement being executed (and chosen for logging).
@@ -76,6 +85,7 @@ impl StatementLogging {
     pub(crate) fn new() -> Self {
         Self {
             executions_begun: BTreeMap::new(),
+            index_recommendations: BTreeMap::new(),
             unlogged_sessions: BTreeMap::new(),
             reproducible_rng: rand_chacha::ChaCha8Rng::seed_from_u64(42),
         }
@@ -162,10 +172,26 @@ impl Coordinator {
         let began_record = self.statement_logging.executions_begun.remove(&id).expect(
             "matched `begin_statement_execution` and `end_statement_execution` invocations",
         );
-        let updates = self
+        let mut updates = self
             .catalog
             .state()
             .pack_statement_ended_execution_updates(&began_record, &ended_record);
+        if let (
+            Some((cluster_id, recommendation)),
+            StatementEndedExecutionReason::Success {
+                execution_strategy: Some(StatementExecutionStrategy::Standard),
+                ..
+            },
+        ) = (
+            self.statement_logging.index_recommendations.remove(&id),
+            &ended_record.reason,
+        ) {
+            updates.extend(self.catalog.state().pack_index_recommendation_update(
+                id,
+                cluster_id,
+                &recommendation,
+            ));
+        }
         self.buffer_builtin_table_updates(updates);
     }
     /// Possibly record the beginning of statement execution, depending on a randomly-chosen value.
@@ -262,6 +288,22 @@ impl Coordinator {
         Some(StatementLoggingId(ev_id))
     }
 
+    /// Remember an index that would have allowed a logged statement execution on the given
+    /// cluster to use the fast path, for the benefit of `mz_internal.mz_index_recommendations`.
+    ///
+    /// The recommendation is recorded when the execution ends, if the statement log shows that
+    /// it succeeded by building a dataflow.
+    pub(crate) fn set_statement_execution_index_recommendation(
+        &mut self,
+        StatementLoggingId(id): &StatementLoggingId,
+        cluster_id: ClusterId,
+        recommendation: IndexRecommendation,
+    ) {
+        self.statement_logging
+            .index_recommendations
+            .insert(*id, (cluster_id, recommendation));
+    }
+
     /// Record a new connection event
     pub fn begin_session_for_statement_logging(&mut self, session: &ConnMeta) {
         let id = session.uuid();
@@ -592,6 +592,8 @@ impl Coordinator {
                         | CatalogItem::Type(_)
                         | CatalogItem::Func(_)
                         | CatalogItem::Secret(_)
+                        | CatalogItem::Sequence(_)
+                        | CatalogItem::Function(_)
                         | CatalogItem::Connection(_)
                         | CatalogItem::Log(_) => {}
                     }
@@ -741,6 +743,8 @@ impl Coordinator {
                     | CatalogItem::Type(_)
                     | CatalogItem::Func(_)
                     | CatalogItem::Secret(_)
+                    | CatalogItem::Sequence(_)
+                    | CatalogItem::Function(_)
                     | CatalogItem::Connection(_) => {}
                 }
             }
@@ -26,7 +26,7 @@ use timely::progress::frontier::AntichainRef;
 use timely::progress::{Antichain, Timestamp as TimelyTimestamp};
 use tracing::{event, Level};
 
-use crate::catalog::{Catalog, CatalogState};
+use crate::catalog::{Catalog, CatalogItem, CatalogState};
 use crate::coord::dataflows::{prep_scalar_expr, ExprPrepStyle};
 use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::timeline::TimelineContext;
@@ -291,7 +291,23 @@ pub trait TimestampProvider {
             || (when.can_advance_to_upper()
                 && (isolation_level == &IsolationLevel::Serializable || timeline.is_none()))
         {
-            candidate.join_assign(&largest_not_in_advance_of_upper);
+            // Materialized views with a refresh schedule have a step-wise
+            // frontier: their upper jumps ahead to the next refresh time, which
+            // can be arbitrarily far in the future, and stays there until the
+            // refresh happens. Their contents do not change in between, so we
+            // don't advance past the current read timestamp of the timeline
+            // when reading from them.
+            let refresh_read_ts = match &timeline {
+                Some(timeline) if contains_refresh_schedule(catalog, id_bundle) => {
+                    self.oracle_read_ts(timeline)
+                }
+                _ => None,
+            };
+            let upper_ts = match refresh_read_ts {
+                Some(read_ts) => std::cmp::min(largest_not_in_advance_of_upper, read_ts),
+                None => largest_not_in_advance_of_upper,
+            };
+            candidate.join_assign(&upper_ts);
         }
 
         if let Some(real_time_recency_ts) = real_time_recency_ts {
@@ -537,6 +553,22 @@ impl Coordinator {
     }
 }
 
+/// Reports whether any collection in `id_bundle` is a materialized view with a
+/// refresh schedule, or an index on one.
+fn contains_refresh_schedule(catalog: &CatalogState, id_bundle: &CollectionIdBundle) -> bool {
+    let has_refresh_schedule = |item: &CatalogItem| match item {
+        CatalogItem::MaterializedView(mv) => mv.refresh_schedule.is_some(),
+        _ => false,
+    };
+    id_bundle.iter().any(|id| match catalog.try_get_entry(&id) {
+        Some(entry) => match entry.item() {
+            CatalogItem::Index(index) => has_refresh_schedule(catalog.get_entry(&index.on).item()),
+            item => has_refresh_schedule(item),
+        },
+        None => false,
+    })
+}
+
 /// Information used when determining the timestamp for a query.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TimestampDetermination<T> {
@@ -99,6 +99,38 @@ pub enum AdapterError {
     },
     /// Expression violated a column's constraint
     ConstraintViolation(NotNullViolation),
+    /// A row written to a table violated a check constraint.
+    CheckViolation {
+        relation: String,
+        constraint: String,
+    },
+    /// A row written to a table violated a unique constraint.
+    UniqueViolation {
+        constraint: String,
+        /// The violating key, formatted like `(a, b)=(1, 2)`.
+        key: String,
+    },
+    /// A sequence reached its maximum or minimum value.
+    SequenceExhausted {
+        sequence: String,
+        /// Whether the sequence was ascending when it ran out of values.
+        ascending: bool,
+        limit: i64,
+    },
+    /// `currval` was called before `nextval` in the session.
+    SequenceValueUndefined(String),
+    /// `setval` was called with a value outside the sequence's bounds.
+    SequenceValueOutOfBounds {
+        sequence: String,
+        value: i64,
+        min_value: i64,
+        max_value: i64,
+    },
+    /// A single `INSERT ... ON CONFLICT` or `MERGE` affected the same row, or
+    /// proposed rows with the same key, more than once.
+    RowAffectedTwice {
+        command: &'static str,
+    },
     /// Target cluster has no replicas to service query.
     NoClusterReplicasAvailable(String),
     /// The named operation cannot be run in a transaction.
@@ -225,6 +257,9 @@ impl AdapterError {
                 See https://github.com/MaterializeInc/materialize/issues/16650 for details.".to_string())
             },
             AdapterError::Catalog(c) => c.detail(),
+            AdapterError::UniqueViolation { key, .. } => {
+                Some(format!("Key {key} already exists."))
+            }
             AdapterError::Eval(e) => e.detail(),
             AdapterError::RelationOutsideTimeDomain { relations, names } => Some(format!(
                 "The following relations in the query are outside the transaction's time domain:\n{}\n{}",
@@ -302,6 +337,13 @@ impl AdapterError {
             AdapterError::Catalog(c) => c.hint(),
             AdapterError::SqlCatalog(e) => e.hint(),
             AdapterError::Eval(e) => e.hint(),
+            AdapterError::RowAffectedTwice { command } => Some(if *command == "MERGE" {
+                "Ensure that not more than one source row matches any one target row.".into()
+            } else {
+                "Ensure that no rows proposed for insertion within the same command have \
+                duplicate constrained values."
+                    .into()
+            }),
             AdapterError::InvalidClusterReplicaAz { expected, az: _ } => {
                 Some(if expected.is_empty() {
                     "No availability zones configured; do not specify AVAILABILITY ZONE".into()
@@ -389,6 +431,12 @@ impl AdapterError {
             AdapterError::SourceOrSinkSizeRequired { .. } => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::InvalidTableMutationSelection => SqlState::INVALID_TRANSACTION_STATE,
             AdapterError::ConstraintViolation(NotNullViolation(_)) => SqlState::NOT_NULL_VIOLATION,
+            AdapterError::CheckViolation { .. } => SqlState::CHECK_VIOLATION,
+            AdapterError::UniqueViolation { .. } => SqlState::UNIQUE_VIOLATION,
+            AdapterError::RowAffectedTwice { .. } => SqlState::CARDINALITY_VIOLATION,
+            AdapterError::SequenceExhausted { .. } => SqlState::SEQUENCE_GENERATOR_LIMIT_EXCEEDED,
+            AdapterError::SequenceValueUndefined(_) => SqlState::OBJECT_NOT_IN_PREREQUISITE_STATE,
+            AdapterError::SequenceValueOutOfBounds { .. } => SqlState::NUMERIC_VALUE_OUT_OF_RANGE,
             AdapterError::NoClusterReplicasAvailable(_) => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::OperationProhibitsTransaction(_) => SqlState::ACTIVE_SQL_TRANSACTION,
             AdapterError::OperationRequiresTransaction(_) => SqlState::NO_ACTIVE_SQL_TRANSACTION,
@@ -515,6 +563,48 @@ impl fmt::Display for AdapterError {
             AdapterError::ConstraintViolation(not_null_violation) => {
                 write!(f, "{}", not_null_violation)
             }
+            AdapterError::CheckViolation {
+                relation,
+                constraint,
+            } => write!(
+                f,
+                "new row for relation {} violates check constraint {}",
+                relation.quoted(),
+                constraint.quoted()
+            ),
+            AdapterError::UniqueViolation { constraint, .. } => write!(
+                f,
+                "duplicate key value violates unique constraint {}",
+                constraint.quoted()
+            ),
+            AdapterError::RowAffectedTwice { command } => {
+                write!(f, "{command} command cannot affect row a second time")
+            }
+            AdapterError::SequenceExhausted {
+                sequence,
+                ascending,
+                limit,
+            } => write!(
+                f,
+                "nextval: reached {} value of sequence {} ({limit})",
+                if *ascending { "maximum" } else { "minimum" },
+                sequence.quoted()
+            ),
+            AdapterError::SequenceValueUndefined(sequence) => write!(
+                f,
+                "currval of sequence {} is not yet defined in this session",
+                sequence.quoted()
+            ),
+            AdapterError::SequenceValueOutOfBounds {
+                sequence,
+                value,
+                min_value,
+                max_value,
+            } => write!(
+                f,
+                "setval: value {value} is out of bounds for sequence {} ({min_value}..{max_value})",
+                sequence.quoted()
+            ),
             AdapterError::NoClusterReplicasAvailable(cluster) => {
                 write!(
                     f,
@@ -83,6 +83,9 @@ pub struct Session<T = mz_repr::Timestamp> {
     secret_key: u32,
     external_metadata_tx: mpsc::UnboundedSender<ExternalUserMetadata>,
     external_metadata_rx: mpsc::UnboundedReceiver<ExternalUserMetadata>,
+    /// The value most recently obtained by `nextval` for each sequence in
+    /// this session, as reported by `currval`.
+    sequence_values: BTreeMap<GlobalId, i64>,
     // Token allowing us to access `Arc<QCell<StatementLogging>>`
     // metadata. We want these to be reference-counted, because the same
     // statement might be referenced from multiple portals simultaneously.
@@ -182,6 +185,7 @@ impl<T: TimestampManipulation> Session<T> {
             secret_key: rand::thread_rng().gen(),
             external_metadata_tx,
             external_metadata_rx,
+            sequence_values: BTreeMap::new(),
             qcell_owner: QCellOwner::new(),
         }
     }
@@ -634,9 +638,27 @@ impl<T: TimestampManipulation> Session<T> {
     pub fn reset(&mut self) {
         let _ = self.clear_transaction();
         self.prepared_statements.clear();
+        self.sequence_values.clear();
         self.vars = SessionVars::new(self.vars.build_info(), self.vars.user().clone());
     }
 
+    /// Returns the value most recently obtained by `nextval` for the sequence
+    /// `id` in this session, if any.
+    pub fn sequence_value(&self, id: &GlobalId) -> Option<i64> {
+        self.sequence_values.get(id).copied()
+    }
+
+    /// Records that `value` was obtained by `nextval` for the sequence `id`.
+    pub fn set_sequence_value(&mut self, id: GlobalId, value: i64) {
+        self.sequence_values.insert(id, value);
+    }
+
+    /// Forgets the values obtained by `nextval` in this session, as
+    /// `DISCARD SEQUENCES` does.
+    pub fn clear_sequence_values(&mut self) {
+        self.sequence_values.clear();
+    }
+
     /// Returns the user who owns this session.
     pub fn user(&self) -> &User {
         self.vars.user()
@@ -160,6 +160,8 @@ impl From<&ExecuteResponse> for StatementEndedExecutionReason {
             | ExecuteResponse::CreatedClusterReplica
             | ExecuteResponse::CreatedIndex
             | ExecuteResponse::CreatedSecret
+            | ExecuteResponse::CreatedSequence
+            | ExecuteResponse::CreatedFunction
             | ExecuteResponse::CreatedSink
             | ExecuteResponse::CreatedSource
             | ExecuteResponse::CreatedTable
@@ -171,6 +173,7 @@ impl From<&ExecuteResponse> for StatementEndedExecutionReason {
             | ExecuteResponse::DeclaredCursor
             | ExecuteResponse::Deleted(_)
             | ExecuteResponse::DiscardedTemp
+            | ExecuteResponse::DiscardedSequences
             | ExecuteResponse::DiscardedAll
             | ExecuteResponse::DroppedObject(_)
             | ExecuteResponse::DroppedOwned
@@ -178,6 +181,7 @@ impl From<&ExecuteResponse> for StatementEndedExecutionReason {
             | ExecuteResponse::GrantedPrivilege
             | ExecuteResponse::GrantedRole
             | ExecuteResponse::Inserted(_)
+            | ExecuteResponse::Merged(_)
             | ExecuteResponse::Prepare
             | ExecuteResponse::Raised
             | ExecuteResponse::ReassignOwned
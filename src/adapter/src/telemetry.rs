@@ -136,6 +136,7 @@ pub fn analyze_audited_statement(
         }
         StatementKind::CreateConnection => Some((StatementAction::Create, ObjectType::Connection)),
         StatementKind::CreateDatabase => Some((StatementAction::Create, ObjectType::Database)),
+        StatementKind::CreateFunction => Some((StatementAction::Create, ObjectType::Func)),
         StatementKind::CreateIndex => Some((StatementAction::Create, ObjectType::Index)),
         StatementKind::CreateMaterializedView => {
             Some((StatementAction::Create, ObjectType::MaterializedView))
@@ -143,6 +144,7 @@ pub fn analyze_audited_statement(
         StatementKind::CreateRole => Some((StatementAction::Create, ObjectType::Role)),
         StatementKind::CreateSchema => Some((StatementAction::Create, ObjectType::Schema)),
         StatementKind::CreateSecret => Some((StatementAction::Create, ObjectType::Secret)),
+        StatementKind::CreateSequence => Some((StatementAction::Create, ObjectType::Sequence)),
         StatementKind::CreateSink => Some((StatementAction::Create, ObjectType::Sink)),
         StatementKind::CreateSource => Some((StatementAction::Create, ObjectType::Source)),
         StatementKind::CreateTable => Some((StatementAction::Create, ObjectType::Table)),
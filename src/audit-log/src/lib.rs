@@ -222,6 +222,7 @@ pub enum ObjectType {
     Role,
     Secret,
     Schema,
+    Sequence,
     Sink,
     Source,
     System,
@@ -243,6 +244,7 @@ impl ObjectType {
             ObjectType::Role => "Role",
             ObjectType::Schema => "Schema",
             ObjectType::Secret => "Secret",
+            ObjectType::Sequence => "Sequence",
             ObjectType::Sink => "Sink",
             ObjectType::Source => "Source",
             ObjectType::System => "System",
@@ -266,6 +268,7 @@ impl RustType<proto::audit_log_event_v1::ObjectType> for ObjectType {
             ObjectType::Role => proto::audit_log_event_v1::ObjectType::Role,
             ObjectType::Secret => proto::audit_log_event_v1::ObjectType::Secret,
             ObjectType::Schema => proto::audit_log_event_v1::ObjectType::Schema,
+            ObjectType::Sequence => proto::audit_log_event_v1::ObjectType::Sequence,
             ObjectType::Sink => proto::audit_log_event_v1::ObjectType::Sink,
             ObjectType::Source => proto::audit_log_event_v1::ObjectType::Source,
             ObjectType::System => proto::audit_log_event_v1::ObjectType::System,
@@ -289,6 +292,7 @@ impl RustType<proto::audit_log_event_v1::ObjectType> for ObjectType {
             proto::audit_log_event_v1::ObjectType::Role => Ok(ObjectType::Role),
             proto::audit_log_event_v1::ObjectType::Secret => Ok(ObjectType::Secret),
             proto::audit_log_event_v1::ObjectType::Schema => Ok(ObjectType::Schema),
+            proto::audit_log_event_v1::ObjectType::Sequence => Ok(ObjectType::Sequence),
             proto::audit_log_event_v1::ObjectType::Sink => Ok(ObjectType::Sink),
             proto::audit_log_event_v1::ObjectType::Source => Ok(ObjectType::Source),
             proto::audit_log_event_v1::ObjectType::System => Ok(ObjectType::System),
@@ -710,6 +710,7 @@ where
                 connection,
                 with_snapshot: se.with_snapshot,
                 up_to: se.up_to,
+                refresh_schedule: se.refresh_schedule,
             };
             sink_exports.insert(id, desc);
         }
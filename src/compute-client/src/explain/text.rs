@@ -33,7 +33,9 @@ use mz_repr::explain::{CompactScalarSeq, Indices, PlanRenderingContext};
 use crate::plan::join::delta_join::{DeltaPathPlan, DeltaStagePlan};
 use crate::plan::join::linear_join::LinearStagePlan;
 use crate::plan::join::{DeltaJoinPlan, JoinClosure, LinearJoinPlan};
-use crate::plan::reduce::{AccumulablePlan, BasicPlan, CollationPlan, HierarchicalPlan};
+use crate::plan::reduce::{
+    AccumulablePlan, BasicPlan, CollationPlan, HierarchicalPlan, WindowedPlan,
+};
 use crate::plan::{AvailableCollections, Plan};
 
 impl DisplayText<PlanRenderingContext<'_, Plan>> for Plan {
@@ -239,6 +241,10 @@ impl DisplayText<PlanRenderingContext<'_, Plan>> for Plan {
                         writeln!(f, "{}Reduce::Collation", ctx.indent)?;
                         ctx.indented(|ctx| plan.fmt_text(f, ctx))?;
                     }
+                    ReducePlan::Windowed(plan) => {
+                        writeln!(f, "{}Reduce::Windowed", ctx.indent)?;
+                        ctx.indented(|ctx| plan.fmt_text(f, ctx))?;
+                    }
                 }
                 ctx.indented(|ctx| {
                     if key_val_plan.val_plan.deref().is_identity() {
@@ -311,6 +317,21 @@ impl DisplayText<PlanRenderingContext<'_, Plan>> for Plan {
                             write!(f, " offset={}", plan.offset)?;
                         }
                     }
+                    TopKPlan::Bounded(plan) => {
+                        write!(f, "{}TopK::Bounded", ctx.indent)?;
+                        if plan.group_key.len() > 0 {
+                            let group_by = Indices(&plan.group_key);
+                            write!(f, " group_by=[{}]", group_by)?;
+                        }
+                        if plan.order_key.len() > 0 {
+                            let order_by = separated(", ", &plan.order_key);
+                            write!(f, " order_by=[{}]", order_by)?;
+                        }
+                        write!(f, " limit={}", plan.limit)?;
+                        if &plan.offset > &0 {
+                            write!(f, " offset={}", plan.offset)?;
+                        }
+                    }
                 }
                 writeln!(f)?;
                 ctx.indented(|ctx| input.fmt_text(f, ctx))?;
@@ -601,6 +622,18 @@ impl DisplayText<PlanRenderingContext<'_, Plan>> for AccumulablePlan {
     }
 }
 
+impl DisplayText<PlanRenderingContext<'_, Plan>> for WindowedPlan {
+    fn fmt_text(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        ctx: &mut PlanRenderingContext<'_, Plan>,
+    ) -> fmt::Result {
+        let upper_bounds = separated(", ", &self.upper_bounds);
+        writeln!(f, "{}upper_bounds=[{}]", ctx.indent, upper_bounds)?;
+        self.accumulable.fmt_text(f, ctx)
+    }
+}
+
 impl DisplayText<PlanRenderingContext<'_, Plan>> for HierarchicalPlan {
     fn fmt_text(
         &self,
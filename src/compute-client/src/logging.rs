@@ -229,6 +229,8 @@ pub enum ComputeLog {
     ArrangementHeapCapacity,
     ArrangementHeapAllocations,
     ShutdownDuration,
+    ArrangementSpilledSize,
+    PersistSourceParts,
 }
 
 impl RustType<ProtoComputeLog> for ComputeLog {
@@ -246,6 +248,8 @@ impl RustType<ProtoComputeLog> for ComputeLog {
                 ComputeLog::ArrangementHeapCapacity => ArrangementHeapCapacity(()),
                 ComputeLog::ArrangementHeapAllocations => ArrangementHeapAllocations(()),
                 ComputeLog::ShutdownDuration => ShutdownDuration(()),
+                ComputeLog::ArrangementSpilledSize => ArrangementSpilledSize(()),
+                ComputeLog::PersistSourceParts => PersistSourceParts(()),
             }),
         }
     }
@@ -263,6 +267,8 @@ impl RustType<ProtoComputeLog> for ComputeLog {
             Some(ArrangementHeapCapacity(())) => Ok(ComputeLog::ArrangementHeapCapacity),
             Some(ArrangementHeapAllocations(())) => Ok(ComputeLog::ArrangementHeapAllocations),
             Some(ShutdownDuration(())) => Ok(ComputeLog::ShutdownDuration),
+            Some(ArrangementSpilledSize(())) => Ok(ComputeLog::ArrangementSpilledSize),
+            Some(PersistSourceParts(())) => Ok(ComputeLog::PersistSourceParts),
             None => Err(TryFromProtoError::missing_field("ProtoComputeLog::kind")),
         }
     }
@@ -392,7 +398,8 @@ impl LogVariant {
             | LogVariant::Differential(DifferentialLog::Sharing)
             | LogVariant::Compute(ComputeLog::ArrangementHeapSize)
             | LogVariant::Compute(ComputeLog::ArrangementHeapCapacity)
-            | LogVariant::Compute(ComputeLog::ArrangementHeapAllocations) => RelationDesc::empty()
+            | LogVariant::Compute(ComputeLog::ArrangementHeapAllocations)
+            | LogVariant::Compute(ComputeLog::ArrangementSpilledSize) => RelationDesc::empty()
                 .with_column("operator_id", ScalarType::UInt64.nullable(false))
                 .with_column("worker_id", ScalarType::UInt64.nullable(false)),
 
@@ -433,6 +440,12 @@ impl LogVariant {
             LogVariant::Compute(ComputeLog::ShutdownDuration) => RelationDesc::empty()
                 .with_column("worker_id", ScalarType::UInt64.nullable(false))
                 .with_column("duration_ns", ScalarType::UInt64.nullable(false)),
+
+            LogVariant::Compute(ComputeLog::PersistSourceParts) => RelationDesc::empty()
+                .with_column("export_id", ScalarType::String.nullable(false))
+                .with_column("import_id", ScalarType::String.nullable(false))
+                .with_column("worker_id", ScalarType::UInt64.nullable(false))
+                .with_column("fetched", ScalarType::Bool.nullable(false)),
         }
     }
 
@@ -466,7 +479,8 @@ impl LogVariant {
             | Differential(DifferentialLog::Sharing)
             | Compute(ComputeLog::ArrangementHeapSize)
             | Compute(ComputeLog::ArrangementHeapCapacity)
-            | Compute(ComputeLog::ArrangementHeapAllocations) => {
+            | Compute(ComputeLog::ArrangementHeapAllocations)
+            | Compute(ComputeLog::ArrangementSpilledSize) => {
                 vec![(Timely(TimelyLog::Operates), vec![(0, 0), (1, 1)])]
             }
             Compute(ComputeLog::DataflowCurrent) => vec![],
@@ -476,6 +490,7 @@ impl LogVariant {
             Compute(ComputeLog::PeekCurrent) => vec![],
             Compute(ComputeLog::PeekDuration) => vec![],
             Compute(ComputeLog::ShutdownDuration) => vec![],
+            Compute(ComputeLog::PersistSourceParts) => vec![],
         }
     }
 }
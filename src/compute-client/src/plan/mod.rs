@@ -11,14 +11,16 @@
 
 #![warn(missing_debug_implementations)]
 
+use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet};
 use std::num::NonZeroU64;
 
 use itertools::Itertools;
 use mz_expr::JoinImplementation::{DeltaQuery, Differential, IndexedFilter, Unimplemented};
 use mz_expr::{
-    permutation_for_arrangement, CollectionPlan, EvalError, Id, JoinInputMapper, LetRecLimit,
-    LocalId, MapFilterProject, MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr, TableFunc,
+    func, permutation_for_arrangement, BinaryFunc, CollectionPlan, EvalError, Id, JoinInputMapper,
+    LetRecLimit, LocalId, MapFilterProject, MirRelationExpr, MirScalarExpr,
+    OptimizedMirRelationExpr, TableFunc, UnaryFunc, UnmaterializableFunc,
 };
 use mz_ore::soft_panic_or_log;
 use mz_ore::str::Indent;
@@ -355,6 +357,45 @@ pub enum Plan<T = mz_repr::Timestamp> {
 }
 
 impl<T> Plan<T> {
+    /// Iterates through references to child expressions.
+    pub fn children(&self) -> impl Iterator<Item = &Self> {
+        let mut first = None;
+        let mut second = None;
+        let mut rest = None;
+        let mut last = None;
+
+        use Plan::*;
+        match self {
+            Constant { .. } | Get { .. } => (),
+            Let { value, body, .. } => {
+                first = Some(&**value);
+                second = Some(&**body);
+            }
+            LetRec { values, body, .. } => {
+                rest = Some(values);
+                last = Some(&**body);
+            }
+            Mfp { input, .. }
+            | FlatMap { input, .. }
+            | Reduce { input, .. }
+            | TopK { input, .. }
+            | Negate { input }
+            | Threshold { input, .. }
+            | ArrangeBy { input, .. } => {
+                first = Some(&**input);
+            }
+            Join { inputs, .. } | Union { inputs, .. } => {
+                rest = Some(inputs);
+            }
+        }
+
+        first
+            .into_iter()
+            .chain(second)
+            .chain(rest.into_iter().flatten())
+            .chain(last)
+    }
+
     /// Iterates through mutable references to child expressions.
     pub fn children_mut(&mut self) -> impl Iterator<Item = &mut Self> {
         let mut first = None;
@@ -1031,10 +1072,14 @@ impl<T: timely::progress::Timestamp> Plan<T> {
     ///
     /// An empty list of arrangement keys indicates that only a `Collection` stream can
     /// be assumed to exist.
+    ///
+    /// If `enable_windowed_reductions` is set, reductions whose input is only retained
+    /// until a time determined by the group key are planned as [`ReducePlan::Windowed`].
     fn from_mir(
         expr: &MirRelationExpr,
         arrangements: &mut BTreeMap<Id, AvailableCollections>,
         debug_info: LirDebugInfo<'_>,
+        enable_windowed_reductions: bool,
     ) -> Result<(Self, AvailableCollections), String> {
         // This function is recursive and can overflow its stack, so grow it if
         // needed. The growth here is unbounded. Our general solution for this problem
@@ -1045,13 +1090,16 @@ impl<T: timely::progress::Timestamp> Plan<T> {
         // to allow the unbounded growth here. We are though somewhat protected by
         // higher levels enforcing their own limits on stack depth (in the parser,
         // transformer/desugarer, and planner).
-        mz_ore::stack::maybe_grow(|| Plan::from_mir_stack_safe(expr, arrangements, debug_info))
+        mz_ore::stack::maybe_grow(|| {
+            Plan::from_mir_stack_safe(expr, arrangements, debug_info, enable_windowed_reductions)
+        })
     }
 
     fn from_mir_stack_safe(
         expr: &MirRelationExpr,
         arrangements: &mut BTreeMap<Id, AvailableCollections>,
         debug_info: LirDebugInfo<'_>,
+        enable_windowed_reductions: bool,
     ) -> Result<(Self, AvailableCollections), String> {
         // Extract a maximally large MapFilterProject from `expr`.
         // We will then try and push this in to the resulting expression.
@@ -1165,12 +1213,14 @@ impl<T: timely::progress::Timestamp> Plan<T> {
 
                 // Plan the value using only the initial arrangements, but
                 // introduce any resulting arrangements bound to `id`.
-                let (value, v_keys) = Plan::from_mir(value, arrangements, debug_info)?;
+                let (value, v_keys) =
+                    Plan::from_mir(value, arrangements, debug_info, enable_windowed_reductions)?;
                 let pre_existing = arrangements.insert(Id::Local(*id), v_keys);
                 assert!(pre_existing.is_none());
                 // Plan the body using initial and `value` arrangements,
                 // and then remove reference to the value arrangements.
-                let (body, b_keys) = Plan::from_mir(body, arrangements, debug_info)?;
+                let (body, b_keys) =
+                    Plan::from_mir(body, arrangements, debug_info, enable_windowed_reductions)?;
                 arrangements.remove(&Id::Local(*id));
                 // Return the plan, and any `body` arrangements.
                 (
@@ -1196,8 +1246,12 @@ impl<T: timely::progress::Timestamp> Plan<T> {
                 // as we cannot circulate an arrangement through a `Variable` yet.
                 let mut lir_values = Vec::with_capacity(values.len());
                 for (id, value) in ids.iter().zip(values) {
-                    let (mut lir_value, mut v_keys) =
-                        Plan::from_mir(value, arrangements, debug_info)?;
+                    let (mut lir_value, mut v_keys) = Plan::from_mir(
+                        value,
+                        arrangements,
+                        debug_info,
+                        enable_windowed_reductions,
+                    )?;
                     // If `v_keys` does not contain an unarranged collection, we must form it.
                     if !v_keys.raw {
                         // Choose an "arbitrary" arrangement; TODO: prefer a specific one.
@@ -1252,7 +1306,8 @@ impl<T: timely::progress::Timestamp> Plan<T> {
                 }
                 // Plan the body using initial and `value` arrangements,
                 // and then remove reference to the value arrangements.
-                let (body, b_keys) = Plan::from_mir(body, arrangements, debug_info)?;
+                let (body, b_keys) =
+                    Plan::from_mir(body, arrangements, debug_info, enable_windowed_reductions)?;
                 for id in ids.iter() {
                     arrangements.remove(&Id::Local(*id));
                 }
@@ -1268,7 +1323,8 @@ impl<T: timely::progress::Timestamp> Plan<T> {
                 )
             }
             MirRelationExpr::FlatMap { input, func, exprs } => {
-                let (input, keys) = Plan::from_mir(input, arrangements, debug_info)?;
+                let (input, keys) =
+                    Plan::from_mir(input, arrangements, debug_info, enable_windowed_reductions)?;
                 // This stage can absorb arbitrary MFP instances.
                 let mfp = mfp.take();
                 let mut exprs = exprs.clone();
@@ -1313,7 +1369,12 @@ impl<T: timely::progress::Timestamp> Plan<T> {
                 let mut input_keys = Vec::new();
                 let mut input_arities = Vec::new();
                 for input in inputs.iter() {
-                    let (plan, keys) = Plan::from_mir(input, arrangements, debug_info)?;
+                    let (plan, keys) = Plan::from_mir(
+                        input,
+                        arrangements,
+                        debug_info,
+                        enable_windowed_reductions,
+                    )?;
                     input_arities.push(input.arity());
                     plans.push(plan);
                     input_keys.push(keys);
@@ -1439,7 +1500,31 @@ This is not expected to cause incorrect results, but could indicate a performanc
             } => {
                 let input_arity = input.arity();
                 let output_arity = group_key.len() + aggregates.len();
-                let (input, keys) = Self::from_mir(input, arrangements, debug_info)?;
+                // Temporal predicates that only upper-bound `mz_now()` by expressions of the
+                // group key can be applied to whole groups rather than to each input update.
+                let windowed = if enable_windowed_reductions {
+                    Self::extract_window_upper_bounds(input, group_key).and_then(
+                        |(input, upper_bounds)| {
+                            ReducePlan::create_windowed(aggregates.clone(), upper_bounds)
+                                .map(|plan| (input, plan))
+                        },
+                    )
+                } else {
+                    None
+                };
+                let (input, reduce_plan) = match windowed {
+                    Some((input, plan)) => (Cow::Owned(input), plan),
+                    None => (
+                        Cow::Borrowed(&**input),
+                        ReducePlan::create_from(
+                            aggregates.clone(),
+                            *monotonic,
+                            *expected_group_size,
+                        ),
+                    ),
+                };
+                let (input, keys) =
+                    Self::from_mir(&input, arrangements, debug_info, enable_windowed_reductions)?;
                 let (input_key, permutation_and_new_arity) = if let Some((
                     input_key,
                     permutation,
@@ -1459,8 +1544,6 @@ This is not expected to cause incorrect results, but could indicate a performanc
                     aggregates,
                     permutation_and_new_arity,
                 );
-                let reduce_plan =
-                    ReducePlan::create_from(aggregates.clone(), *monotonic, *expected_group_size);
                 let output_keys = reduce_plan.keys(group_key.len(), output_arity);
                 // Return the plan, and the keys it produces.
                 (
@@ -1483,7 +1566,8 @@ This is not expected to cause incorrect results, but could indicate a performanc
                 expected_group_size,
             } => {
                 let arity = input.arity();
-                let (input, keys) = Self::from_mir(input, arrangements, debug_info)?;
+                let (input, keys) =
+                    Self::from_mir(input, arrangements, debug_info, enable_windowed_reductions)?;
 
                 let top_k_plan = TopKPlan::create_from(
                     group_key.clone(),
@@ -1513,7 +1597,8 @@ This is not expected to cause incorrect results, but could indicate a performanc
             }
             MirRelationExpr::Negate { input } => {
                 let arity = input.arity();
-                let (input, keys) = Self::from_mir(input, arrangements, debug_info)?;
+                let (input, keys) =
+                    Self::from_mir(input, arrangements, debug_info, enable_windowed_reductions)?;
 
                 // We don't have an MFP here -- install an operator to permute the
                 // input, if necessary.
@@ -1532,7 +1617,8 @@ This is not expected to cause incorrect results, but could indicate a performanc
             }
             MirRelationExpr::Threshold { input } => {
                 let arity = input.arity();
-                let (input, keys) = Self::from_mir(input, arrangements, debug_info)?;
+                let (input, keys) =
+                    Self::from_mir(input, arrangements, debug_info, enable_windowed_reductions)?;
                 // We don't have an MFP here -- install an operator to permute the
                 // input, if necessary.
                 let input = if !keys.raw {
@@ -1568,10 +1654,16 @@ This is not expected to cause incorrect results, but could indicate a performanc
             MirRelationExpr::Union { base, inputs } => {
                 let arity = base.arity();
                 let mut plans_keys = Vec::with_capacity(1 + inputs.len());
-                let (plan, keys) = Self::from_mir(base, arrangements, debug_info)?;
+                let (plan, keys) =
+                    Self::from_mir(base, arrangements, debug_info, enable_windowed_reductions)?;
                 plans_keys.push((plan, keys));
                 for input in inputs.iter() {
-                    let (plan, keys) = Self::from_mir(input, arrangements, debug_info)?;
+                    let (plan, keys) = Self::from_mir(
+                        input,
+                        arrangements,
+                        debug_info,
+                        enable_windowed_reductions,
+                    )?;
                     plans_keys.push((plan, keys));
                 }
                 let plans = plans_keys
@@ -1595,7 +1687,8 @@ This is not expected to cause incorrect results, but could indicate a performanc
             }
             MirRelationExpr::ArrangeBy { input, keys } => {
                 let arity = input.arity();
-                let (input, mut input_keys) = Self::from_mir(input, arrangements, debug_info)?;
+                let (input, mut input_keys) =
+                    Self::from_mir(input, arrangements, debug_info, enable_windowed_reductions)?;
                 // Determine keys that are not present in `input_keys`.
                 let new_keys = keys
                     .iter()
@@ -1720,6 +1813,103 @@ This is not expected to cause incorrect results, but could indicate a performanc
         Ok((plan, keys))
     }
 
+    /// Splits off the predicates of a `Filter` below a reduction that upper-bound `mz_now()` by
+    /// expressions of the `group_key`. The `Filter` may be wrapped in projections.
+    ///
+    /// Returns the input without those predicates, and the bounds expressed in terms of the
+    /// columns of the group key, or `None` if there are no such predicates.
+    ///
+    /// This recognizes windows that close at the same time for all records of a group, which is
+    /// the case for tumbling windows grouped by `date_bin(..)`, and for hopping windows grouped
+    /// by the window starts of `date_bin_hopping(..)`. A sliding window expressed directly on the
+    /// records, such as `mz_now() < ts + INTERVAL '7 days'`, is left to the temporal filter of the
+    /// input, which retracts every record on its own. Such windows can be retired as a whole by
+    /// rewriting them as hopping windows, at the granularity of the hop.
+    fn extract_window_upper_bounds(
+        input: &MirRelationExpr,
+        group_key: &[MirScalarExpr],
+    ) -> Option<(MirRelationExpr, Vec<MirScalarExpr>)> {
+        let (input, predicates) = match input {
+            MirRelationExpr::Filter { input, predicates } => (input, predicates),
+            MirRelationExpr::Project { input, outputs } => {
+                let group_key = group_key
+                    .iter()
+                    .map(|key| {
+                        let mut key = key.clone();
+                        key.permute(outputs);
+                        key
+                    })
+                    .collect::<Vec<_>>();
+                let (input, upper_bounds) = Self::extract_window_upper_bounds(input, &group_key)?;
+                return Some((input.project(outputs.clone()), upper_bounds));
+            }
+            _ => return None,
+        };
+        let mut upper_bounds = Vec::new();
+        let mut remaining = Vec::new();
+        for predicate in predicates {
+            match Self::window_upper_bound(predicate, group_key) {
+                Some(bound) => upper_bounds.push(bound),
+                None => remaining.push(predicate.clone()),
+            }
+        }
+        if upper_bounds.is_empty() {
+            return None;
+        }
+        let input = if remaining.is_empty() {
+            (**input).clone()
+        } else {
+            (**input).clone().filter(remaining)
+        };
+        Some((input, upper_bounds))
+    }
+
+    /// Recognizes `mz_now() < expr` and `mz_now() <= expr`, in either argument order, and
+    /// returns the exclusive upper bound on `mz_now()` in terms of the columns of `group_key`.
+    fn window_upper_bound(
+        predicate: &MirScalarExpr,
+        group_key: &[MirScalarExpr],
+    ) -> Option<MirScalarExpr> {
+        let MirScalarExpr::CallBinary { func, expr1, expr2 } = predicate else {
+            return None;
+        };
+        let mz_now = MirScalarExpr::CallUnmaterializable(UnmaterializableFunc::MzNow);
+        let (strict, bound) = match func {
+            BinaryFunc::Lt if **expr1 == mz_now => (true, expr2),
+            BinaryFunc::Lte if **expr1 == mz_now => (false, expr2),
+            BinaryFunc::Gt if **expr2 == mz_now => (true, expr1),
+            BinaryFunc::Gte if **expr2 == mz_now => (false, expr1),
+            _ => return None,
+        };
+        let bound = Self::in_terms_of_group_key(bound, group_key)?;
+        if strict {
+            Some(bound)
+        } else {
+            Some(bound.call_unary(UnaryFunc::StepMzTimestamp(func::StepMzTimestamp)))
+        }
+    }
+
+    /// Rewrites `expr` to reference the columns of `group_key` instead of its sub-expressions
+    /// that equal group key expressions, if no other column references remain.
+    fn in_terms_of_group_key(
+        expr: &MirScalarExpr,
+        group_key: &[MirScalarExpr],
+    ) -> Option<MirScalarExpr> {
+        if let Some(position) = group_key.iter().position(|key| key == expr) {
+            return Some(MirScalarExpr::Column(position));
+        }
+        match expr {
+            MirScalarExpr::Column(_) | MirScalarExpr::CallUnmaterializable(_) => None,
+            _ => {
+                let mut expr = expr.clone();
+                for child in expr.children_mut() {
+                    *child = Self::in_terms_of_group_key(child, group_key)?;
+                }
+                Some(expr)
+            }
+        }
+    }
+
     /// Convert the dataflow description into one that uses render plans.
     #[tracing::instrument(
         target = "optimizer",
@@ -1731,9 +1921,11 @@ This is not expected to cause incorrect results, but could indicate a performanc
         desc: DataflowDescription<OptimizedMirRelationExpr>,
         enable_consolidate_after_union_negate: bool,
         enable_monotonic_oneshot_selects: bool,
+        enable_windowed_reductions: bool,
+        enable_bounded_topk: bool,
     ) -> Result<DataflowDescription<Self>, String> {
         // First, we lower the dataflow description from MIR to LIR.
-        let mut dataflow = Self::lower_dataflow(desc)?;
+        let mut dataflow = Self::lower_dataflow(desc, enable_windowed_reductions)?;
 
         // Subsequently, we perform plan refinements for the dataflow.
         Self::refine_source_mfps(&mut dataflow);
@@ -1742,6 +1934,10 @@ This is not expected to cause incorrect results, but could indicate a performanc
             Self::refine_union_negate_consolidation(&mut dataflow);
         }
 
+        if enable_bounded_topk {
+            Self::refine_bounded_topk(&mut dataflow);
+        }
+
         if enable_monotonic_oneshot_selects {
             Self::refine_single_time_operator_selection(&mut dataflow);
 
@@ -1790,6 +1986,7 @@ This is not expected to cause incorrect results, but could indicate a performanc
     )]
     fn lower_dataflow(
         desc: DataflowDescription<OptimizedMirRelationExpr>,
+        enable_windowed_reductions: bool,
     ) -> Result<DataflowDescription<Self>, String> {
         // Collect available arrangements by identifier.
         let mut arrangements = BTreeMap::new();
@@ -1832,6 +2029,7 @@ This is not expected to cause incorrect results, but could indicate a performanc
                     debug_name: &desc.debug_name,
                     id: build.id,
                 },
+                enable_windowed_reductions,
             )?;
             arrangements.insert(Id::Global(build.id), keys);
             objects_to_build.push(BuildDesc { id: build.id, plan });
@@ -1942,6 +2140,38 @@ This is not expected to cause incorrect results, but could indicate a performanc
         mz_repr::explain::trace_plan(dataflow);
     }
 
+    /// Refines the plans of TopK operators with small limits to bounded plans, which
+    /// retain only a buffer of rows per group instead of the entire group.
+    ///
+    /// Bounded plans require totally ordered timestamps, and so are not selected for
+    /// dataflows containing recursive objects, which are rendered in iterative scopes.
+    #[tracing::instrument(
+        target = "optimizer",
+        level = "debug",
+        skip_all,
+        fields(path.segment = "refine_bounded_topk")
+    )]
+    fn refine_bounded_topk(dataflow: &mut DataflowDescription<Self>) {
+        if dataflow
+            .objects_to_build
+            .iter()
+            .any(|build_desc| matches!(build_desc.plan, Plan::LetRec { .. }))
+        {
+            return;
+        }
+
+        for build_desc in dataflow.objects_to_build.iter_mut() {
+            let mut todo = vec![&mut build_desc.plan];
+            while let Some(expression) = todo.pop() {
+                if let Plan::TopK { top_k_plan, .. } = expression {
+                    top_k_plan.as_bounded();
+                }
+                todo.extend(expression.children_mut());
+            }
+        }
+        mz_repr::explain::trace_plan(dataflow);
+    }
+
     /// Refines the plans of objects to be built as part of `dataflow` to take advantage
     /// of monotonic operators if the dataflow refers to a single-time, i.e., is for a
     /// one-shot SELECT query.
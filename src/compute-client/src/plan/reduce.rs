@@ -66,7 +66,7 @@ use mz_expr::{permutation_for_arrangement, AggregateExpr, AggregateFunc, MirScal
 use mz_ore::soft_assert_or_log;
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use proptest::prelude::{any, Arbitrary, BoxedStrategy};
-use proptest::strategy::Strategy;
+use proptest::strategy::{Just, Strategy};
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
 
@@ -163,6 +163,9 @@ pub enum ReducePlan {
     /// We need to do extra work here to reassemble results back in the
     /// requested order.
     Collation(CollationPlan),
+    /// Plan for computing accumulable aggregations over windows that
+    /// close at a time determined by the group key.
+    Windowed(WindowedPlan),
 }
 
 proptest::prop_compose! {
@@ -206,6 +209,24 @@ impl Arbitrary for ReducePlan {
                     ReducePlan::create_from(exprs, monotonic, expected_group_size)
                 },
             )
+            .prop_flat_map(|plan| {
+                // Plans without distinct accumulable aggregations can also be windowed.
+                let upper_bounds = proptest::collection::vec(any::<MirScalarExpr>(), 0..2);
+                (Just(plan), any::<bool>(), upper_bounds)
+            })
+            .prop_map(|(plan, windowed, upper_bounds)| match plan {
+                ReducePlan::Accumulable(accumulable)
+                    if windowed
+                        && accumulable.distinct_aggrs.is_empty()
+                        && !upper_bounds.is_empty() =>
+                {
+                    ReducePlan::Windowed(WindowedPlan {
+                        accumulable,
+                        upper_bounds,
+                    })
+                }
+                plan => plan,
+            })
             .boxed()
     }
 }
@@ -220,6 +241,7 @@ impl RustType<ProtoReducePlan> for ReducePlan {
                 ReducePlan::Hierarchical(plan) => Hierarchical(plan.into_proto()),
                 ReducePlan::Basic(plan) => Basic(plan.into_proto()),
                 ReducePlan::Collation(plan) => Collation(plan.into_proto()),
+                ReducePlan::Windowed(plan) => Windowed(plan.into_proto()),
             }),
         }
     }
@@ -235,6 +257,7 @@ impl RustType<ProtoReducePlan> for ReducePlan {
             Hierarchical(plan) => ReducePlan::Hierarchical(plan.into_rust()?),
             Basic(plan) => ReducePlan::Basic(plan.into_rust()?),
             Collation(plan) => ReducePlan::Collation(plan.into_rust()?),
+            Windowed(plan) => ReducePlan::Windowed(plan.into_rust()?),
         })
     }
 }
@@ -298,6 +321,50 @@ impl RustType<ProtoAccumulablePlan> for AccumulablePlan {
     }
 }
 
+/// Plan for computing a set of accumulable aggregations over windows.
+///
+/// Reductions are planned as windowed if their input is subject to temporal
+/// predicates that only upper-bound `mz_now()` by expressions of the group key,
+/// as is the case when grouping by `date_bin(..)` and retaining each bin only
+/// for as long as it is open. The predicates then hold for all updates of a
+/// group alike, and we can apply them to the output of the reduction instead of
+/// to its input: rather than retracting every input update once its window
+/// closes, we maintain the partial aggregates of each group, and retract them
+/// all at once when the window of the group closes.
+///
+/// This covers tumbling windows grouped by `date_bin(..)`, and hopping windows
+/// grouped by the window starts of `date_bin_hopping(..)`, which assigns each
+/// record to every window that contains it. Sliding windows expressed directly
+/// on the records, in which each record leaves the window at a time determined
+/// by its own columns, still retract every record individually.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct WindowedPlan {
+    /// The accumulable aggregations to compute in each window. These do not
+    /// contain any `DISTINCT` aggregations.
+    pub accumulable: AccumulablePlan,
+    /// Expressions over the group key that upper-bound `mz_now()`. The window
+    /// of a group closes at the least time they evaluate to.
+    pub upper_bounds: Vec<MirScalarExpr>,
+}
+
+impl RustType<ProtoWindowedPlan> for WindowedPlan {
+    fn into_proto(&self) -> ProtoWindowedPlan {
+        ProtoWindowedPlan {
+            accumulable: Some(self.accumulable.into_proto()),
+            upper_bounds: self.upper_bounds.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoWindowedPlan) -> Result<Self, TryFromProtoError> {
+        Ok(Self {
+            accumulable: proto
+                .accumulable
+                .into_rust_if_some("ProtoWindowedPlan::accumulable")?,
+            upper_bounds: proto.upper_bounds.into_rust()?,
+        })
+    }
+}
+
 /// Plan for computing a set of hierarchical aggregations.
 ///
 /// In the append-only setting we can render them in-place
@@ -650,7 +717,7 @@ impl ReducePlan {
                     assert!(collation.basic.is_none());
                     collation.basic = Some(e);
                 }
-                ReducePlan::Distinct | ReducePlan::Collation(_) => {
+                ReducePlan::Distinct | ReducePlan::Collation(_) | ReducePlan::Windowed(_) => {
                     panic!("Inner reduce plan was unsupported type!")
                 }
             }
@@ -659,6 +726,32 @@ impl ReducePlan {
         ReducePlan::Collation(collation)
     }
 
+    /// Generate a plan for computing the supplied aggregations in windows that
+    /// close once `mz_now()` reaches the least value of `upper_bounds`, which
+    /// are evaluated on the group key.
+    ///
+    /// Returns `None` if not all aggregations are accumulable and without
+    /// `DISTINCT`, as only those can be retired window by window.
+    pub fn create_windowed(
+        aggregates: Vec<AggregateExpr>,
+        upper_bounds: Vec<MirScalarExpr>,
+    ) -> Option<Self> {
+        if upper_bounds.is_empty()
+            || aggregates.iter().any(|aggr| {
+                aggr.distinct || reduction_type(&aggr.func) != ReductionType::Accumulable
+            })
+        {
+            return None;
+        }
+        match ReducePlan::create_from(aggregates, false, None) {
+            ReducePlan::Accumulable(accumulable) => Some(ReducePlan::Windowed(WindowedPlan {
+                accumulable,
+                upper_bounds,
+            })),
+            _ => None,
+        }
+    }
+
     /// Generate a plan for computing the specified type of aggregations.
     ///
     /// This function assumes that all of the supplied aggregates are
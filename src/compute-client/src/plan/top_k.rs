@@ -16,6 +16,8 @@
 //! * A [MonotonicTop1Plan] maintains a single row per key and is suitable for monotonic inputs.
 //! * A [MonotonicTopKPlan] maintains up to K rows per key and is suitable for monotonic inputs.
 //! * A [BasicTopKPlan] maintains up to K rows per key and can handle retractions.
+//! * A [BoundedTopKPlan] maintains a buffer of slightly more than K rows per key and can
+//!   handle retractions, but requires a small, constant limit.
 
 use mz_expr::ColumnOrder;
 use mz_proto::{ProtoType, RustType, TryFromProtoError};
@@ -35,8 +37,16 @@ pub enum TopKPlan {
     MonotonicTopK(MonotonicTopKPlan),
     /// A plan for generic TopK operations.
     Basic(BasicTopKPlan),
+    /// A plan for TopK operations with a small limit.
+    Bounded(BoundedTopKPlan),
 }
 
+/// The largest `offset + limit` for which we select a [BoundedTopKPlan].
+///
+/// The per-group buffers of a bounded plan hold a multiple of this many rows, which
+/// for larger limits approaches the size of the groups themselves.
+pub const BOUNDED_TOP_K_MAX_ROWS: usize = 64;
+
 impl TopKPlan {
     /// Create a plan from the information provided. Here we decide on which of the TopK plan
     /// variants to select.
@@ -92,7 +102,7 @@ impl TopKPlan {
         }
     }
 
-    /// Upgrades from a basic topk plan to a monotonic plan, if necessary, and
+    /// Upgrades from a basic or bounded topk plan to a monotonic plan, if necessary, and
     /// sets consolidation requirements.
     pub fn as_monotonic(&mut self, must_consolidate: bool) {
         match self {
@@ -121,6 +131,50 @@ impl TopKPlan {
             TopKPlan::MonotonicTopK(plan) => {
                 plan.must_consolidate = must_consolidate;
             }
+            TopKPlan::Bounded(plan) => {
+                if plan.offset == 0 {
+                    *self = if plan.limit == 1 {
+                        TopKPlan::MonotonicTop1(MonotonicTop1Plan {
+                            group_key: plan.group_key.clone(),
+                            order_key: plan.order_key.clone(),
+                            must_consolidate,
+                        })
+                    } else {
+                        TopKPlan::MonotonicTopK(MonotonicTopKPlan {
+                            group_key: plan.group_key.clone(),
+                            order_key: plan.order_key.clone(),
+                            limit: Some(plan.limit),
+                            arity: plan.arity,
+                            must_consolidate,
+                        })
+                    }
+                }
+            }
+        }
+    }
+
+    /// Switches from a basic topk plan to a bounded plan, if the plan has a limit
+    /// and `offset + limit` is at most [BOUNDED_TOP_K_MAX_ROWS].
+    ///
+    /// The bounded plan processes the updates of each group in time order, and must
+    /// only be rendered in dataflows with totally ordered timestamps.
+    pub fn as_bounded(&mut self) {
+        if let TopKPlan::Basic(plan) = self {
+            if let Some(limit) = plan.limit {
+                if limit > 0 && plan.offset + limit <= BOUNDED_TOP_K_MAX_ROWS {
+                    *self = TopKPlan::Bounded(BoundedTopKPlan {
+                        group_key: plan.group_key.clone(),
+                        order_key: plan.order_key.clone(),
+                        limit,
+                        offset: plan.offset,
+                        arity: plan.arity,
+                        // Buffer as many additional rows as are produced, so that a group
+                        // can absorb as many retractions as it has output rows before it
+                        // needs to consult the input again.
+                        slack: plan.offset + limit,
+                    });
+                }
+            }
         }
     }
 }
@@ -134,6 +188,7 @@ impl RustType<ProtoTopKPlan> for TopKPlan {
                 TopKPlan::Basic(plan) => Some(Basic(plan.into_proto())),
                 TopKPlan::MonotonicTop1(plan) => Some(MonotonicTop1(plan.into_proto())),
                 TopKPlan::MonotonicTopK(plan) => Some(MonotonicTopK(plan.into_proto())),
+                TopKPlan::Bounded(plan) => Some(Bounded(plan.into_proto())),
             },
         }
     }
@@ -145,6 +200,7 @@ impl RustType<ProtoTopKPlan> for TopKPlan {
             Some(Basic(plan)) => Ok(TopKPlan::Basic(plan.into_rust()?)),
             Some(MonotonicTop1(plan)) => Ok(TopKPlan::MonotonicTop1(plan.into_rust()?)),
             Some(MonotonicTopK(plan)) => Ok(TopKPlan::MonotonicTopK(plan.into_rust()?)),
+            Some(Bounded(plan)) => Ok(TopKPlan::Bounded(plan.into_rust()?)),
             None => Err(TryFromProtoError::missing_field("ProtoTopKPlan::kind")),
         }
     }
@@ -288,6 +344,57 @@ impl RustType<ProtoBasicTopKPlan> for BasicTopKPlan {
     }
 }
 
+/// A plan for TopKs with a small limit over inputs that can contain retractions.
+///
+/// Rather than retaining all rows of each group in a hierarchy of arrangements, the
+/// plan arranges its input once and maintains for each group a buffer of the first
+/// `offset + limit + slack` rows. Retractions are absorbed by the buffer, and only
+/// when it holds fewer than `offset + limit` rows is it refilled from the input
+/// arrangement.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct BoundedTopKPlan {
+    /// The columns that form the key for each group.
+    pub group_key: Vec<usize>,
+    /// Ordering that is used within each group.
+    pub order_key: Vec<mz_expr::ColumnOrder>,
+    /// An upper bound on the per-group ordinal position of the records to
+    /// produce from each group.
+    pub limit: usize,
+    /// A lower bound on the per-group ordinal position of the records to
+    /// produce from each group.
+    ///
+    /// This can be set to zero to have no effect.
+    pub offset: usize,
+    /// The number of columns in the input and output.
+    pub arity: usize,
+    /// The number of rows to buffer per group in addition to `offset + limit`.
+    pub slack: usize,
+}
+
+impl RustType<ProtoBoundedTopKPlan> for BoundedTopKPlan {
+    fn into_proto(&self) -> ProtoBoundedTopKPlan {
+        ProtoBoundedTopKPlan {
+            group_key: self.group_key.into_proto(),
+            order_key: self.order_key.into_proto(),
+            limit: self.limit.into_proto(),
+            offset: self.offset.into_proto(),
+            arity: self.arity.into_proto(),
+            slack: self.slack.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoBoundedTopKPlan) -> Result<Self, TryFromProtoError> {
+        Ok(BoundedTopKPlan {
+            group_key: proto.group_key.into_rust()?,
+            order_key: proto.order_key.into_rust()?,
+            limit: proto.limit.into_rust()?,
+            offset: proto.offset.into_rust()?,
+            arity: proto.arity.into_rust()?,
+            slack: proto.slack.into_rust()?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use mz_proto::protobuf_roundtrip;
@@ -303,4 +410,32 @@ mod tests {
             assert_eq!(actual.unwrap(), expect);
         }
     }
+
+    #[mz_ore::test]
+    fn top_k_plan_as_bounded() {
+        let basic = |offset, limit| {
+            TopKPlan::Basic(BasicTopKPlan {
+                group_key: vec![0],
+                order_key: vec![],
+                limit,
+                offset,
+                arity: 2,
+                buckets: vec![],
+            })
+        };
+
+        let mut plan = basic(2, Some(10));
+        plan.as_bounded();
+        let TopKPlan::Bounded(bounded) = &plan else {
+            panic!("expected a bounded plan, got {plan:?}");
+        };
+        assert_eq!((bounded.offset, bounded.limit, bounded.slack), (2, 10, 12));
+
+        // Plans without a limit or with a large limit remain basic.
+        for (offset, limit) in [(0, None), (0, Some(0)), (1, Some(BOUNDED_TOP_K_MAX_ROWS))] {
+            let mut plan = basic(offset, limit);
+            plan.as_bounded();
+            assert_eq!(plan, basic(offset, limit));
+        }
+    }
 }
@@ -357,6 +357,11 @@ pub struct ComputeParameters {
     pub enable_mz_join_core: Option<bool>,
     /// Enable arrangement size logging
     pub enable_arrangement_size_logging: Option<bool>,
+    /// Whether replicas with a scratch directory should back large merged arrangement batches
+    /// by a file on disk.
+    pub enable_arrangement_spilling: Option<bool>,
+    /// The size in bytes from which on merged arrangement batches are spilled to disk.
+    pub arrangement_spill_threshold_bytes: Option<usize>,
     /// Persist client configuration.
     pub persist: PersistParameters,
     /// Tracing configuration.
@@ -373,6 +378,8 @@ impl ComputeParameters {
             dataflow_max_inflight_bytes,
             enable_mz_join_core,
             enable_arrangement_size_logging,
+            enable_arrangement_spilling,
+            arrangement_spill_threshold_bytes,
             persist,
             tracing,
             grpc_client,
@@ -391,6 +398,12 @@ impl ComputeParameters {
         if enable_arrangement_size_logging.is_some() {
             self.enable_arrangement_size_logging = enable_arrangement_size_logging;
         }
+        if enable_arrangement_spilling.is_some() {
+            self.enable_arrangement_spilling = enable_arrangement_spilling;
+        }
+        if arrangement_spill_threshold_bytes.is_some() {
+            self.arrangement_spill_threshold_bytes = arrangement_spill_threshold_bytes;
+        }
 
         self.persist.update(persist);
         self.tracing.update(tracing);
@@ -410,6 +423,8 @@ impl RustType<ProtoComputeParameters> for ComputeParameters {
             dataflow_max_inflight_bytes: self.dataflow_max_inflight_bytes.into_proto(),
             enable_arrangement_size_logging: self.enable_arrangement_size_logging.into_proto(),
             enable_mz_join_core: self.enable_mz_join_core.into_proto(),
+            enable_arrangement_spilling: self.enable_arrangement_spilling.into_proto(),
+            arrangement_spill_threshold_bytes: self.arrangement_spill_threshold_bytes.into_proto(),
             persist: Some(self.persist.into_proto()),
             tracing: Some(self.tracing.into_proto()),
             grpc_client: Some(self.grpc_client.into_proto()),
@@ -422,6 +437,10 @@ impl RustType<ProtoComputeParameters> for ComputeParameters {
             dataflow_max_inflight_bytes: proto.dataflow_max_inflight_bytes.into_rust()?,
             enable_arrangement_size_logging: proto.enable_arrangement_size_logging.into_rust()?,
             enable_mz_join_core: proto.enable_mz_join_core.into_rust()?,
+            enable_arrangement_spilling: proto.enable_arrangement_spilling.into_rust()?,
+            arrangement_spill_threshold_bytes: proto
+                .arrangement_spill_threshold_bytes
+                .into_rust()?,
             persist: proto
                 .persist
                 .into_rust_if_some("ProtoComputeParameters::persist")?,
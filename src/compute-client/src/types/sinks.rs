@@ -10,6 +10,7 @@
 //! Types for describing dataflow sinks.
 
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
+use mz_repr::refresh_schedule::RefreshSchedule;
 use mz_repr::{GlobalId, RelationDesc};
 use mz_storage_client::controller::CollectionMetadata;
 use proptest::prelude::{any, Arbitrary, BoxedStrategy, Strategy};
@@ -30,6 +31,9 @@ pub struct ComputeSinkDesc<S: 'static = (), T = mz_repr::Timestamp> {
     pub connection: ComputeSinkConnection<S>,
     pub with_snapshot: bool,
     pub up_to: Antichain<T>,
+    /// The refresh schedule of a materialized view, if any. The sink only
+    /// advances its output at the scheduled refresh times.
+    pub refresh_schedule: Option<RefreshSchedule>,
 }
 
 impl Arbitrary for ComputeSinkDesc<CollectionMetadata, mz_repr::Timestamp> {
@@ -43,14 +47,18 @@ impl Arbitrary for ComputeSinkDesc<CollectionMetadata, mz_repr::Timestamp> {
             any::<ComputeSinkConnection<CollectionMetadata>>(),
             any::<bool>(),
             proptest::collection::vec(any::<mz_repr::Timestamp>(), 1..4),
+            any::<Option<RefreshSchedule>>(),
         )
             .prop_map(
-                |(from, from_desc, connection, with_snapshot, up_to_frontier)| ComputeSinkDesc {
-                    from,
-                    from_desc,
-                    connection,
-                    with_snapshot,
-                    up_to: Antichain::from(up_to_frontier),
+                |(from, from_desc, connection, with_snapshot, up_to_frontier, refresh_schedule)| {
+                    ComputeSinkDesc {
+                        from,
+                        from_desc,
+                        connection,
+                        with_snapshot,
+                        up_to: Antichain::from(up_to_frontier),
+                        refresh_schedule,
+                    }
                 },
             )
             .boxed()
@@ -65,6 +73,7 @@ impl RustType<ProtoComputeSinkDesc> for ComputeSinkDesc<CollectionMetadata, mz_r
             from_desc: Some(self.from_desc.into_proto()),
             with_snapshot: self.with_snapshot,
             up_to: Some(self.up_to.into_proto()),
+            refresh_schedule: self.refresh_schedule.into_proto(),
         }
     }
 
@@ -81,6 +90,7 @@ impl RustType<ProtoComputeSinkDesc> for ComputeSinkDesc<CollectionMetadata, mz_r
             up_to: proto
                 .up_to
                 .into_rust_if_some("ProtoComputeSinkDesc::up_to")?,
+            refresh_schedule: proto.refresh_schedule.into_rust()?,
         })
     }
 }
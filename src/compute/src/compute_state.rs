@@ -25,6 +25,7 @@ use mz_compute_client::protocol::response::{ComputeResponse, PeekResponse, Subsc
 use mz_compute_client::types::dataflows::DataflowDescription;
 use mz_ore::cast::CastFrom;
 use mz_ore::metrics::UIntGauge;
+use mz_ore::region::SpillConfig;
 use mz_ore::tracing::{OpenTelemetryContext, TracingHandle};
 use mz_persist_client::cache::PersistClientCache;
 use mz_repr::{GlobalId, Row, Timestamp};
@@ -42,7 +43,7 @@ use crate::logging;
 use crate::logging::compute::ComputeEvent;
 use crate::metrics::ComputeMetrics;
 use crate::render::LinearJoinImpl;
-use crate::server::ResponseSender;
+use crate::server::{ComputeInstanceContext, ResponseSender};
 
 /// Worker-local state that is maintained across dataflows.
 ///
@@ -88,6 +89,12 @@ pub struct ComputeState {
     tracing_handle: Arc<TracingHandle>,
     /// Enable arrangement size logging
     pub enable_arrangement_size_logging: bool,
+    /// Whether to back large merged arrangement batches by a file in the scratch directory.
+    pub enable_arrangement_spilling: bool,
+    /// The size in bytes from which on merged arrangement batches are spilled.
+    pub arrangement_spill_threshold_bytes: usize,
+    /// Extra context for the compute instance.
+    pub context: ComputeInstanceContext,
 }
 
 impl ComputeState {
@@ -97,6 +104,7 @@ impl ComputeState {
         persist_clients: Arc<PersistClientCache>,
         metrics: ComputeMetrics,
         tracing_handle: Arc<TracingHandle>,
+        context: ComputeInstanceContext,
     ) -> Self {
         let traces = TraceManager::new(metrics.for_traces(worker_id));
         let command_history = ComputeCommandHistory::new(metrics.for_history(worker_id));
@@ -116,9 +124,28 @@ impl ComputeState {
             metrics,
             tracing_handle,
             enable_arrangement_size_logging: Default::default(),
+            enable_arrangement_spilling: Default::default(),
+            arrangement_spill_threshold_bytes: usize::MAX,
+            context,
         }
     }
 
+    /// Install the arrangement spill configuration of this worker.
+    ///
+    /// Spilling requires a scratch directory, so it stays disabled on replicas without disk,
+    /// regardless of the configuration. The configuration is thread-local, so only the
+    /// arrangement batches merged by this worker are affected.
+    pub fn apply_spill_config(&self) {
+        let config = match &self.context.scratch_directory {
+            Some(path) if self.enable_arrangement_spilling => Some(SpillConfig {
+                path: path.clone(),
+                threshold_bytes: self.arrangement_spill_threshold_bytes,
+            }),
+            _ => None,
+        };
+        mz_ore::region::set_spill_config(config);
+    }
+
     /// Return whether a collection with the given ID exists.
     pub fn collection_exists(&self, id: GlobalId) -> bool {
         self.collections.contains_key(&id)
@@ -198,6 +225,8 @@ impl<'a, A: Allocate + 'static> ActiveComputeState<'a, A> {
             dataflow_max_inflight_bytes,
             enable_arrangement_size_logging,
             enable_mz_join_core,
+            enable_arrangement_spilling,
+            arrangement_spill_threshold_bytes,
             persist,
             tracing,
             grpc_client: _grpc_client,
@@ -218,6 +247,13 @@ impl<'a, A: Allocate + 'static> ActiveComputeState<'a, A> {
                 true => LinearJoinImpl::Materialize,
             };
         }
+        if let Some(v) = enable_arrangement_spilling {
+            self.compute_state.enable_arrangement_spilling = v;
+        }
+        if let Some(v) = arrangement_spill_threshold_bytes {
+            self.compute_state.arrangement_spill_threshold_bytes = v;
+        }
+        self.compute_state.apply_spill_config();
 
         persist.apply(self.compute_state.persist_clients.cfg());
         tracing.apply(self.compute_state.tracing_handle.as_ref());
@@ -257,6 +257,7 @@ where
     let trace = Rc::downgrade(&arranged.trace.trace_box_unstable());
 
     let (mut old_size, mut old_capacity, mut old_allocations) = (0isize, 0isize, 0isize);
+    let mut old_spilled_size = 0isize;
 
     let stream = arranged
         .stream
@@ -273,7 +274,8 @@ where
                     return;
                 };
 
-                let (size, capacity, allocations) = logic(&trace.borrow().trace);
+                let ((size, capacity, allocations), spilled_size, _spilled_capacity) =
+                    mz_ore::region::spilled_size(|| logic(&trace.borrow().trace));
 
                 let size = size.try_into().expect("must fit");
                 if size != old_size {
@@ -299,9 +301,18 @@ where
                     });
                 }
 
+                let spilled_size = spilled_size.try_into().expect("must fit");
+                if spilled_size != old_spilled_size {
+                    logger.log(ComputeEvent::ArrangementSpilledSize {
+                        operator,
+                        delta_size: spilled_size - old_spilled_size,
+                    });
+                }
+
                 old_size = size;
                 old_capacity = capacity;
                 old_allocations = allocations;
+                old_spilled_size = spilled_size;
             }
         });
     Arranged {
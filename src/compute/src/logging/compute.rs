@@ -21,6 +21,7 @@ use differential_dataflow::trace::TraceReader;
 use mz_expr::{permutation_for_arrangement, MirScalarExpr};
 use mz_ore::cast::CastFrom;
 use mz_repr::{Datum, DatumVec, Diff, GlobalId, Row, Timestamp};
+use mz_storage_client::source::persist_source::PartFetchLogger;
 use mz_timely_util::replay::MzReplay;
 use timely::communication::Allocate;
 use timely::dataflow::channels::pact::Pipeline;
@@ -95,6 +96,14 @@ pub enum ComputeEvent {
         /// Delta of distinct heap allocations backing the arrangement.
         delta_allocations: isize,
     },
+    /// Arrangement spilled size update
+    ArrangementSpilledSize {
+        /// Operator index
+        operator: usize,
+        /// Delta of the size in bytes of the arrangement that is spilled to disk and not
+        /// resident in memory.
+        delta_size: isize,
+    },
     /// Arrangement size operator address
     ArrangementHeapSizeOperator {
         /// Operator index
@@ -107,6 +116,15 @@ pub enum ComputeEvent {
         /// Operator index
         operator: usize,
     },
+    /// A persist source fetched a part of its shard or, based on the part's statistics, skipped it.
+    PersistSourcePart {
+        /// Identifier of the collection the persist source reads.
+        import_id: GlobalId,
+        /// Identifier of an export of the dataflow containing the persist source.
+        export_id: GlobalId,
+        /// Whether the part was fetched.
+        fetched: bool,
+    },
     /// All operators of a dataflow have shut down.
     DataflowShutdown {
         /// Timely worker index of the dataflow.
@@ -180,6 +198,8 @@ pub(super) fn construct<A: Allocate + 'static>(
         let (mut arrangement_heap_capacity_out, arrangement_heap_capacity) = demux.new_output();
         let (mut arrangement_heap_allocations_out, arrangement_heap_allocations) =
             demux.new_output();
+        let (mut arrangement_spilled_size_out, arrangement_spilled_size) = demux.new_output();
+        let (mut persist_source_parts_out, persist_source_parts) = demux.new_output();
 
         let mut demux_state = DemuxState::new(worker2);
         let mut demux_buffer = Vec::new();
@@ -195,6 +215,8 @@ pub(super) fn construct<A: Allocate + 'static>(
                 let mut arrangement_heap_size = arrangement_heap_size_out.activate();
                 let mut arrangement_heap_capacity = arrangement_heap_capacity_out.activate();
                 let mut arrangement_heap_allocations = arrangement_heap_allocations_out.activate();
+                let mut arrangement_spilled_size = arrangement_spilled_size_out.activate();
+                let mut persist_source_parts = persist_source_parts_out.activate();
 
                 input.for_each(|cap, data| {
                     data.swap(&mut demux_buffer);
@@ -210,6 +232,8 @@ pub(super) fn construct<A: Allocate + 'static>(
                         arrangement_heap_size: arrangement_heap_size.session(&cap),
                         arrangement_heap_capacity: arrangement_heap_capacity.session(&cap),
                         arrangement_heap_allocations: arrangement_heap_allocations.session(&cap),
+                        arrangement_spilled_size: arrangement_spilled_size.session(&cap),
+                        persist_source_parts: persist_source_parts.session(&cap),
                     };
 
                     for (time, logger_id, event) in demux_buffer.drain(..) {
@@ -299,9 +323,22 @@ pub(super) fn construct<A: Allocate + 'static>(
             .map(arrangement_heap_datum_to_row.clone());
 
         let arrangement_heap_allocations = arrangement_heap_allocations
+            .as_collection()
+            .map(arrangement_heap_datum_to_row.clone());
+
+        let arrangement_spilled_size = arrangement_spilled_size
             .as_collection()
             .map(arrangement_heap_datum_to_row);
 
+        let persist_source_parts = persist_source_parts.as_collection().map(move |datum| {
+            Row::pack_slice(&[
+                Datum::String(&datum.export_id.to_string()),
+                Datum::String(&datum.import_id.to_string()),
+                Datum::UInt64(u64::cast_from(worker_id)),
+                Datum::from(datum.fetched),
+            ])
+        });
+
         use ComputeLog::*;
         let logs = [
             (DataflowCurrent, dataflow_current),
@@ -314,6 +351,8 @@ pub(super) fn construct<A: Allocate + 'static>(
             (ArrangementHeapSize, arrangement_heap_size),
             (ArrangementHeapCapacity, arrangement_heap_capacity),
             (ArrangementHeapAllocations, arrangement_heap_allocations),
+            (ArrangementSpilledSize, arrangement_spilled_size),
+            (PersistSourceParts, persist_source_parts),
         ];
 
         // Build the output arrangements.
@@ -363,6 +402,8 @@ struct DemuxState<A: Allocate> {
     export_dataflows: BTreeMap<GlobalId, usize>,
     /// Maps dataflow exports to their imports and frontier delay tracking state.
     export_imports: BTreeMap<GlobalId, BTreeMap<GlobalId, FrontierDelayState>>,
+    /// Maps dataflow exports to counts of the persist parts their imports fetched and skipped.
+    export_persist_parts: BTreeMap<GlobalId, BTreeMap<PersistSourcePartDatum, Diff>>,
     /// Maps live dataflows to counts of their exports.
     dataflow_export_counts: BTreeMap<usize, u32>,
     /// Maps dropped dataflows to their drop time.
@@ -381,6 +422,7 @@ impl<A: Allocate> DemuxState<A> {
             worker,
             export_dataflows: Default::default(),
             export_imports: Default::default(),
+            export_persist_parts: Default::default(),
             dataflow_export_counts: Default::default(),
             dataflow_drop_times: Default::default(),
             shutdown_dataflows: Default::default(),
@@ -417,6 +459,8 @@ struct DemuxOutput<'a> {
     arrangement_heap_size: OutputSession<'a, ArrangementHeapDatum>,
     arrangement_heap_capacity: OutputSession<'a, ArrangementHeapDatum>,
     arrangement_heap_allocations: OutputSession<'a, ArrangementHeapDatum>,
+    arrangement_spilled_size: OutputSession<'a, ArrangementHeapDatum>,
+    persist_source_parts: OutputSession<'a, PersistSourcePartDatum>,
 }
 
 #[derive(Clone)]
@@ -445,6 +489,13 @@ struct FrontierDelayDatum {
     delay_pow: u128,
 }
 
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct PersistSourcePartDatum {
+    export_id: GlobalId,
+    import_id: GlobalId,
+    fetched: bool,
+}
+
 #[derive(Clone)]
 struct ArrangementHeapDatum {
     operator_id: usize,
@@ -455,6 +506,7 @@ struct ArrangementSizeState {
     size: isize,
     capacity: isize,
     count: isize,
+    spilled_size: isize,
 }
 
 /// Event handler of the demux operator.
@@ -509,12 +561,21 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
                 operator,
                 delta_allocations: allocations,
             } => self.handle_arrangement_heap_allocations(operator, allocations),
+            ArrangementSpilledSize {
+                operator,
+                delta_size: size,
+            } => self.handle_arrangement_spilled_size(operator, size),
             ArrangementHeapSizeOperator { operator, address } => {
                 self.handle_arrangement_heap_size_operator(operator, address)
             }
             ArrangementHeapSizeOperatorDrop { operator } => {
                 self.handle_arrangement_heap_size_operator_dropped(operator)
             }
+            PersistSourcePart {
+                import_id,
+                export_id,
+                fetched,
+            } => self.handle_persist_source_part(import_id, export_id, fetched),
             DataflowShutdown { dataflow_index } => self.handle_dataflow_shutdown(dataflow_index),
         }
     }
@@ -526,6 +587,7 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
 
         self.state.export_dataflows.insert(id, dataflow_id);
         self.state.export_imports.insert(id, BTreeMap::new());
+        self.state.export_persist_parts.insert(id, BTreeMap::new());
         *self
             .state
             .dataflow_export_counts
@@ -575,6 +637,13 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
                 "missing export_imports entry at time of export drop"
             );
         }
+
+        // Remove persist part logging for this export.
+        if let Some(parts) = self.state.export_persist_parts.remove(&id) {
+            for (datum, count) in parts {
+                self.output.persist_source_parts.give((datum, ts, -count));
+            }
+        }
     }
 
     fn handle_dataflow_dropped(&mut self, id: usize) {
@@ -714,6 +783,31 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
         }
     }
 
+    fn handle_persist_source_part(
+        &mut self,
+        import_id: GlobalId,
+        export_id: GlobalId,
+        fetched: bool,
+    ) {
+        // Like `ImportFrontier` events, these events can arrive after the export was dropped,
+        // since the dataflow's persist sources may outlive it for a while. Ignore them, so that
+        // they don't linger in the log.
+        let Some(parts) = self.state.export_persist_parts.get_mut(&export_id) else {
+            return;
+        };
+
+        let ts = self.ts();
+        let datum = PersistSourcePartDatum {
+            export_id,
+            import_id,
+            fetched,
+        };
+        self.output
+            .persist_source_parts
+            .give((datum.clone(), ts, 1));
+        *parts.entry(datum).or_default() += 1;
+    }
+
     /// Update the allocation size for an arrangement.
     fn handle_arrangement_heap_size(&mut self, operator_id: usize, size: isize) {
         let ts = self.ts();
@@ -759,6 +853,21 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
         state.count += count;
     }
 
+    /// Update the spilled size for an arrangement.
+    fn handle_arrangement_spilled_size(&mut self, operator_id: usize, size: isize) {
+        let ts = self.ts();
+        let Some(state) = self.state.arrangement_size.get_mut(&operator_id) else {
+            return;
+        };
+
+        let datum = ArrangementHeapDatum { operator_id };
+        self.output
+            .arrangement_spilled_size
+            .give((datum, ts, Diff::cast_from(size)));
+
+        state.spilled_size += size;
+    }
+
     /// Indicate that a new arrangement exists, start maintaining the heap size state.
     fn handle_arrangement_heap_size_operator(&mut self, operator_id: usize, address: Vec<usize>) {
         let activator = self.state.worker.activator_for(&address);
@@ -786,10 +895,15 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
                 -Diff::cast_from(state.capacity),
             ));
             self.output.arrangement_heap_allocations.give((
-                datum,
+                datum.clone(),
                 ts,
                 -Diff::cast_from(state.count),
             ));
+            self.output.arrangement_spilled_size.give((
+                datum,
+                ts,
+                -Diff::cast_from(state.spilled_size),
+            ));
         }
         self.shared_state
             .arrangement_size_activators
@@ -797,6 +911,24 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
     }
 }
 
+/// Returns a [`PartFetchLogger`] that logs the parts a persist source reading `import_id` fetches
+/// and skips, on behalf of each of the `export_ids` of its dataflow.
+pub(crate) fn persist_part_fetch_logger(
+    logger: Logger,
+    import_id: GlobalId,
+    export_ids: Vec<GlobalId>,
+) -> PartFetchLogger {
+    Rc::new(move |fetched| {
+        for &export_id in export_ids.iter() {
+            logger.log(ComputeEvent::PersistSourcePart {
+                import_id,
+                export_id,
+                fetched,
+            });
+        }
+    })
+}
+
 pub(crate) trait LogImportFrontiers {
     fn log_import_frontiers(
         self,
@@ -8,8 +8,9 @@
 // by the Apache License, Version 2.0.
 
 use mz_compute_client::metrics::{CommandMetrics, HistoryMetrics};
+use mz_ore::cast::CastFrom;
 use mz_ore::metric;
-use mz_ore::metrics::{raw, MetricsRegistry, UIntGauge};
+use mz_ore::metrics::{raw, ComputedUIntGauge, MetricsRegistry, UIntGauge};
 use prometheus::core::{AtomicF64, GenericCounter};
 
 /// Metrics exposed by compute replicas.
@@ -34,6 +35,10 @@ pub struct ComputeMetrics {
     // arrangements
     arrangement_maintenance_seconds_total: raw::CounterVec,
     arrangement_maintenance_active_info: raw::UIntGaugeVec,
+
+    // arrangement spilling
+    _arrangement_spilled_bytes: ComputedUIntGauge,
+    _arrangement_spill_fallbacks: ComputedUIntGauge,
 }
 
 impl ComputeMetrics {
@@ -69,6 +74,21 @@ impl ComputeMetrics {
                 help: "Whether maintenance is currently occuring.",
                 var_labels: ["worker_id"],
             )),
+            _arrangement_spilled_bytes: registry.register_computed_gauge(
+                metric!(
+                    name: "mz_arrangement_spilled_bytes",
+                    help: "The number of bytes of merged arrangement batches backed by spill files.",
+                ),
+                || u64::cast_from(mz_ore::region::spilled_bytes()),
+            ),
+            _arrangement_spill_fallbacks: registry.register_computed_gauge(
+                metric!(
+                    name: "mz_arrangement_spill_fallbacks",
+                    help: "The number of merged arrangement batches that were kept on the heap \
+                        because spilling them failed.",
+                ),
+                mz_ore::region::spill_fallbacks,
+            ),
         }
     }
 
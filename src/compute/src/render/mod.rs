@@ -134,7 +134,7 @@ use crate::arrangement::manager::TraceBundle;
 use crate::compute_state::ComputeState;
 use crate::extensions::arrange::{KeyCollection, MzArrange};
 use crate::extensions::reduce::MzReduce;
-use crate::logging::compute::LogImportFrontiers;
+use crate::logging::compute::{persist_part_fetch_logger, LogImportFrontiers};
 use crate::render::context::{ArrangementFlavor, Context, ShutdownToken};
 use crate::typedefs::{ErrSpine, RowKeySpine};
 
@@ -223,6 +223,12 @@ pub fn build_compute_dataflow<A: Allocate>(
                         metrics: None,
                     };
 
+                    // If logging is enabled, log the parts the source fetches and skips.
+                    let part_fetch_logger = compute_state.compute_logger.clone().map(|logger| {
+                        let export_ids = dataflow.export_ids().collect();
+                        persist_part_fetch_logger(logger, *source_id, export_ids)
+                    });
+
                     // Note: For correctness, we require that sources only emit times advanced by
                     // `dataflow.as_of`. `persist_source` is documented to provide this guarantee.
                     let (mut ok_stream, err_stream, token) = persist_source::persist_source(
@@ -234,6 +240,7 @@ pub fn build_compute_dataflow<A: Allocate>(
                         dataflow.until.clone(),
                         mfp.as_mut(),
                         Some(flow_control),
+                        part_fetch_logger,
                         // Copy the logic in DeltaJoin/Get/Join to start.
                         |_timer, count| count > 1_000_000,
                     );
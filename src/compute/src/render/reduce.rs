@@ -27,7 +27,7 @@ use mz_expr::{AggregateExpr, AggregateFunc, EvalError, MirScalarExpr};
 use mz_repr::adt::numeric::{self, Numeric, NumericAgg};
 use mz_repr::{Datum, DatumList, DatumVec, Diff, Row, RowArena};
 use mz_storage_client::types::errors::DataflowError;
-use mz_timely_util::operator::CollectionExt;
+use mz_timely_util::operator::{CollectionExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use timely::dataflow::Scope;
 use timely::progress::timestamp::Refines;
@@ -39,13 +39,13 @@ use crate::extensions::reduce::{MzReduce, ReduceExt};
 use crate::render::context::{Arrangement, CollectionBundle, Context, KeyArrangement};
 use crate::render::errors::MaybeValidatingRow;
 use crate::render::reduce::monoids::ReductionMonoid;
-use crate::render::ArrangementFlavor;
+use crate::render::{ArrangementFlavor, RenderTimestamp};
 use crate::typedefs::{ErrValSpine, RowKeySpine, RowSpine};
 
 impl<G, T> Context<G, Row, T>
 where
     G: Scope,
-    G::Timestamp: Lattice + Refines<T>,
+    G::Timestamp: Lattice + Refines<T> + RenderTimestamp,
     T: Timestamp + Lattice,
 {
     /// Renders a `MirRelationExpr::Reduce` using various non-obvious techniques to
@@ -194,7 +194,13 @@ where
                 arranged_output
             }
             ReducePlan::Accumulable(expr) => {
-                let (arranged_output, errs) = self.build_accumulable(collection, expr);
+                let (arranged_output, errs) = self.build_accumulable(collection, expr, Vec::new());
+                errors.push(errs);
+                arranged_output
+            }
+            ReducePlan::Windowed(expr) => {
+                let (arranged_output, errs) =
+                    self.build_accumulable(collection, expr.accumulable, expr.upper_bounds);
                 errors.push(errs);
                 arranged_output
             }
@@ -974,6 +980,10 @@ where
     /// they can be accumulated in place. The `count` operator promotes the accumulated
     /// values to data, at which point a final map applies operator-specific logic to
     /// yield the final aggregate.
+    ///
+    /// If `upper_bounds` is non-empty, the accumulations of each group are retracted once
+    /// `mz_now()` reaches the least of the bounds evaluated on the group key, which retires
+    /// the whole window of the group at once.
     fn build_accumulable<S>(
         &self,
         collection: Collection<S, (Row, Row), Diff>,
@@ -982,6 +992,7 @@ where
             simple_aggrs,
             distinct_aggrs,
         }: AccumulablePlan,
+        upper_bounds: Vec<MirScalarExpr>,
     ) -> (Arrangement<S, Row>, Collection<S, DataflowError, Diff>)
     where
         S: Scope<Timestamp = G::Timestamp>,
@@ -1251,6 +1262,14 @@ where
             differential_dataflow::collection::concatenate(&mut collection.scope(), to_aggregate)
         };
 
+        // Retire the accumulations of each group once its window closes.
+        let (collection, window_errs) = if upper_bounds.is_empty() {
+            (collection, None)
+        } else {
+            let (collection, errs) = build_window_retractions(collection, upper_bounds);
+            (collection, Some(errs))
+        };
+
         let error_logger = self.error_logger();
         let err_full_aggrs = full_aggrs.clone();
         let (arranged_output, arranged_errs) = collection
@@ -1496,13 +1515,74 @@ where
                     }
                 },
             );
-        (
-            arranged_output,
-            arranged_errs.as_collection(|_key, error| error.clone()),
-        )
+        let mut errs = arranged_errs.as_collection(|_key, error| error.clone());
+        if let Some(window_errs) = window_errs {
+            errs = errs.concat(&window_errs);
+        }
+        (arranged_output, errs)
     }
 }
 
+/// Supplements the accumulations of each group with their retraction at the time the window
+/// of the group closes, which is the least value of `upper_bounds` evaluated on the group key.
+///
+/// Updates are consolidated first, so that a single retraction is produced for all updates
+/// to a group at the same time. Groups for which a bound evaluates to `NULL` are dropped, as
+/// their input records would not have passed the temporal filter.
+fn build_window_retractions<S>(
+    collection: Collection<S, (Row, ()), (Vec<Accum>, Diff)>,
+    upper_bounds: Vec<MirScalarExpr>,
+) -> (
+    Collection<S, (Row, ()), (Vec<Accum>, Diff)>,
+    Collection<S, DataflowError, Diff>,
+)
+where
+    S: Scope,
+    S::Timestamp: RenderTimestamp,
+{
+    let mut datums = DatumVec::new();
+    let (oks, errs) = collection.consolidate_stream().inner.flat_map_fallible(
+        "WindowRetractions",
+        move |((key, ()), time, (accums, total))| {
+            let upper_bound = {
+                let temp_storage = RowArena::new();
+                let datums_local = datums.borrow_with(&key);
+                let mut upper_bound = None;
+                for bound in upper_bounds.iter() {
+                    match bound.eval(&datums_local, &temp_storage) {
+                        Ok(Datum::MzTimestamp(bound)) => {
+                            upper_bound = Some(
+                                upper_bound.map_or(bound, |upper| std::cmp::min(upper, bound)),
+                            );
+                        }
+                        Ok(Datum::Null) => return vec![],
+                        Ok(x) => {
+                            let e = EvalError::Internal(format!(
+                                "non-mz_timestamp value in temporal predicate: {x:?}"
+                            ));
+                            return vec![Err((DataflowError::from(e), time, total))];
+                        }
+                        Err(e) => return vec![Err((DataflowError::from(e), time, total))],
+                    }
+                }
+                upper_bound.expect("windowed reductions have at least one upper bound")
+            };
+            let mut retire_time = time.clone();
+            let event_time = retire_time.event_time();
+            *event_time = std::cmp::max(*event_time, upper_bound);
+            let retraction = accums
+                .iter()
+                .map(|accum| accum.clone().multiply(&-1))
+                .collect();
+            vec![
+                Ok(((key.clone(), ()), retire_time, (retraction, -total))),
+                Ok(((key, ()), time, (accums, total))),
+            ]
+        },
+    );
+    (oks.as_collection(), errs.as_collection())
+}
+
 /// Accumulates values for the various types of accumulable aggregations.
 ///
 /// We assume that there are not more than 2^32 elements for the aggregation.
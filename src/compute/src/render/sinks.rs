@@ -27,6 +27,7 @@ use timely::progress::Antichain;
 use crate::compute_state::SinkToken;
 use crate::render::context::Context;
 use crate::render::RenderTimestamp;
+use crate::sink::refresh::apply_refresh;
 
 impl<'g, G, T> Context<Child<'g, G, T>, Row>
 where
@@ -73,8 +74,15 @@ where
             bundle.as_collection_core(mfp, Some((key.clone(), None)), self.until.clone())
         };
 
-        let ok_collection = ok_collection.leave();
-        let err_collection = err_collection.leave();
+        let mut ok_collection = ok_collection.leave();
+        let mut err_collection = err_collection.leave();
+
+        // Materialized views with a refresh schedule only change at the
+        // scheduled refresh times.
+        if let Some(refresh_schedule) = &sink.refresh_schedule {
+            ok_collection = apply_refresh(ok_collection, refresh_schedule.clone());
+            err_collection = apply_refresh(err_collection, refresh_schedule.clone());
+        }
 
         let region_name = match sink.connection {
             ComputeSinkConnection::Subscribe(_) => format!("SubscribeSink({:?})", sink_id),
@@ -12,14 +12,18 @@
 //! Consult [TopKPlan] documentation for details.
 
 use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::rc::Rc;
 
+use differential_dataflow::consolidation;
 use differential_dataflow::hashable::Hashable;
 use differential_dataflow::lattice::Lattice;
+use differential_dataflow::operators::arrange::arrangement::Arranged;
+use differential_dataflow::trace::{BatchReader, Cursor, TraceReader};
 use differential_dataflow::{AsCollection, Collection};
 use mz_compute_client::plan::top_k::{
-    BasicTopKPlan, MonotonicTop1Plan, MonotonicTopKPlan, TopKPlan,
+    BasicTopKPlan, BoundedTopKPlan, MonotonicTop1Plan, MonotonicTopKPlan, TopKPlan,
 };
 use mz_expr::EvalError;
 use mz_ore::soft_assert_or_log;
@@ -29,6 +33,7 @@ use mz_timely_util::operator::CollectionExt;
 use timely::dataflow::channels::pact::Pipeline;
 use timely::dataflow::operators::Operator;
 use timely::dataflow::Scope;
+use timely::PartialOrder;
 
 use crate::extensions::arrange::MzArrange;
 use crate::extensions::reduce::MzReduce;
@@ -168,6 +173,19 @@ where
                     err_collection = err_collection.concat(&errs);
                     oks
                 }
+                TopKPlan::Bounded(BoundedTopKPlan {
+                    group_key,
+                    order_key,
+                    limit,
+                    offset,
+                    arity: _,
+                    slack,
+                }) => {
+                    let (oks, errs) = self
+                        .build_bounded_topk(ok_input, group_key, order_key, offset, limit, slack);
+                    err_collection = err_collection.concat(&errs);
+                    oks
+                }
             };
             // Extract the results from the region.
             (ok_result.leave_region(), err_collection.leave_region())
@@ -250,6 +268,54 @@ where
         )
     }
 
+    /// Constructs a TopK dataflow subgraph that retains a bounded buffer of rows per group.
+    ///
+    /// The input is arranged by group key, and each group's buffer is refilled from the
+    /// arrangement when retractions drain it below `offset + limit` rows.
+    fn build_bounded_topk<S>(
+        &self,
+        collection: Collection<S, Row, Diff>,
+        group_key: Vec<usize>,
+        order_key: Vec<mz_expr::ColumnOrder>,
+        offset: usize,
+        limit: usize,
+        slack: usize,
+    ) -> (Collection<S, Row, Diff>, Collection<S, DataflowError, Diff>)
+    where
+        S: Scope<Timestamp = G::Timestamp>,
+    {
+        let mut datum_vec = mz_repr::DatumVec::new();
+        let arranged = collection
+            .map(move |row| {
+                let group_row = {
+                    let datums = datum_vec.borrow_with(&row);
+                    let iterator = group_key.iter().map(|i| datums[*i]);
+                    let total_size = mz_repr::datums_size(iterator.clone());
+                    let mut group_row = Row::with_capacity(total_size);
+                    group_row.packer().extend(iterator);
+                    group_row
+                };
+                (group_row, row)
+            })
+            .mz_arrange::<RowSpine<Row, Row, _, _>>(
+                "Arranged BoundedTopK input",
+                self.enable_arrangement_size_logging,
+            );
+
+        let error_logger = self.error_logger();
+        build_bounded_topk_stage(&arranged, order_key, offset, limit, slack).map_fallible(
+            "Demuxing Errors",
+            move |result| match result {
+                Err(k) => {
+                    let message = "Negative multiplicities in TopK";
+                    error_logger.log(message, &format!("k={k:?}"));
+                    Err(EvalError::Internal(message.to_string()).into())
+                }
+                Ok(row) => Ok(row),
+            },
+        )
+    }
+
     // To provide a robust incremental orderby-limit experience, we want to avoid grouping *all*
     // records (or even large groups) and then applying the ordering and limit. Instead, a more
     // robust approach forms groups of bounded size and applies the offset and limit to each,
@@ -500,6 +566,328 @@ where
         .as_collection(|k, v| (k.clone(), v.clone()))
 }
 
+/// Maintains the TopK of each group in `arranged`, using a bounded buffer per group.
+///
+/// Each group's buffer holds the group's first rows in the TopK order, and updates to
+/// rows past the buffer are discarded, as the rows remain available in the arrangement.
+/// Once retractions leave fewer than `offset + limit` rows in the buffer, it is refilled
+/// with `offset + limit + slack` rows from the arrangement.
+///
+/// The operator produces the group key as an error while the group has rows with negative
+/// multiplicities. Retractions of rows past the buffer are checked against the arrangement.
+///
+/// The updates of each group are applied in the order of their timestamps, which is only
+/// correct for totally ordered timestamps.
+fn build_bounded_topk_stage<G, Tr>(
+    arranged: &Arranged<G, Tr>,
+    order_key: Vec<mz_expr::ColumnOrder>,
+    offset: usize,
+    limit: usize,
+    slack: usize,
+) -> Collection<G, Result<Row, Row>, Diff>
+where
+    G: Scope,
+    G::Timestamp: Lattice,
+    Tr: TraceReader<Key = Row, Val = Row, Time = G::Timestamp, R = Diff> + Clone + 'static,
+{
+    let offset = Diff::try_from(offset).expect("must fit");
+    let limit = Diff::try_from(limit).expect("must fit");
+    let capacity = offset + limit + Diff::try_from(slack).expect("must fit");
+
+    let mut trace = Some(arranged.trace.clone());
+    let mut order = RowOrder::new(order_key);
+    let mut groups = BTreeMap::<Row, BoundedTopKBuffer>::new();
+    let mut input_buffer = Vec::new();
+    let mut pending = Vec::new();
+    let mut changes = Vec::new();
+    let mut output_buffer = Vec::new();
+
+    arranged
+        .stream
+        .unary_frontier(Pipeline, "BoundedTopK", move |_capability, _info| {
+            move |input, output| {
+                input.for_each(|capability, data| {
+                    let trace = trace
+                        .as_mut()
+                        .expect("we only drop the trace once the input is complete");
+                    data.swap(&mut input_buffer);
+                    for batch in input_buffer.drain(..) {
+                        // The trace contains the batch, and all times of the batch are in
+                        // advance of the trace's logical compaction frontier.
+                        let (mut trace_cursor, trace_storage) = trace.cursor();
+                        let mut cursor = batch.cursor();
+                        while let Some(key) = cursor.get_key(&batch) {
+                            while let Some(val) = cursor.get_val(&batch) {
+                                cursor.map_times(&batch, |time, diff| {
+                                    pending.push((time.clone(), val.clone(), *diff));
+                                });
+                                cursor.step_val(&batch);
+                            }
+                            pending.sort_by(|(x, _, _), (y, _, _)| x.cmp(y));
+                            trace_cursor.seek_key(&trace_storage, key);
+
+                            let group = groups
+                                .entry(key.clone())
+                                .or_insert_with(BoundedTopKBuffer::default);
+                            let mut before = group.output(offset, limit);
+                            let mut was_valid = group.is_valid();
+                            let mut refill = false;
+
+                            let mut updates = pending.drain(..).peekable();
+                            while let Some((time, row, diff)) = updates.next() {
+                                if group.covers(&row, &mut order) {
+                                    group.update(row, diff, &mut order);
+                                } else if diff < 0 {
+                                    // A retraction of a row past the buffer must not leave
+                                    // the row with a negative multiplicity.
+                                    let rows = accumulate_group(
+                                        &mut trace_cursor,
+                                        &trace_storage,
+                                        key,
+                                        Some(&row),
+                                        &time,
+                                    );
+                                    refill |= rows.iter().any(|(_, diff)| *diff < 0);
+                                }
+
+                                // Complete the group at `time` once we have seen all its updates.
+                                if updates.peek().map_or(false, |(next, _, _)| next == &time) {
+                                    continue;
+                                }
+                                if refill || group.is_drained(offset + limit) {
+                                    let rows = accumulate_group(
+                                        &mut trace_cursor,
+                                        &trace_storage,
+                                        key,
+                                        None,
+                                        &time,
+                                    );
+                                    group.refill(rows, capacity, &mut order);
+                                    refill = false;
+                                }
+                                group.trim(capacity);
+
+                                let after = group.output(offset, limit);
+                                changes.extend(before.drain(..).map(|(row, diff)| (row, -diff)));
+                                changes.extend(after.iter().cloned());
+                                consolidation::consolidate(&mut changes);
+                                output_buffer.extend(
+                                    changes
+                                        .drain(..)
+                                        .map(|(row, diff)| (Ok(row), time.clone(), diff)),
+                                );
+                                before = after;
+
+                                let is_valid = group.is_valid();
+                                if is_valid != was_valid {
+                                    let diff = if is_valid { -1 } else { 1 };
+                                    output_buffer.push((Err(key.clone()), time.clone(), diff));
+                                    was_valid = is_valid;
+                                }
+                            }
+                            if group.is_empty() {
+                                groups.remove(key);
+                            }
+                            cursor.step_key(&batch);
+                        }
+                    }
+                    output
+                        .session(&capability)
+                        .give_iterator(output_buffer.drain(..));
+                });
+
+                // Allow the trace to compact up to the frontier of batches we may yet
+                // receive, as we only read it at times of those batches.
+                if input.frontier().is_empty() {
+                    trace = None;
+                } else if let Some(trace) = trace.as_mut() {
+                    trace.set_logical_compaction(input.frontier().frontier());
+                    trace.set_physical_compaction(input.frontier().frontier());
+                }
+            }
+        })
+        .as_collection()
+}
+
+/// Accumulates the rows of group `key` at `time`, restricted to `row` if provided.
+///
+/// The cursor must be positioned at or before `key`.
+fn accumulate_group<C>(
+    cursor: &mut C,
+    storage: &C::Storage,
+    key: &Row,
+    row: Option<&Row>,
+    time: &C::Time,
+) -> Vec<(Row, Diff)>
+where
+    C: Cursor<Key = Row, Val = Row, R = Diff>,
+    C::Time: Lattice,
+{
+    let mut rows = Vec::new();
+    if cursor.get_key(storage) != Some(key) {
+        return rows;
+    }
+    cursor.rewind_vals(storage);
+    if let Some(row) = row {
+        cursor.seek_val(storage, row);
+    }
+    while let Some(val) = cursor.get_val(storage) {
+        if row.map_or(false, |row| row != val) {
+            break;
+        }
+        let mut count = 0;
+        cursor.map_times(storage, |t, diff| {
+            if t.less_equal(time) {
+                count += *diff;
+            }
+        });
+        if count != 0 {
+            rows.push((val.clone(), count));
+        }
+        cursor.step_val(storage);
+    }
+    rows
+}
+
+/// Compares rows by a TopK ordering, breaking ties by the rows themselves.
+struct RowOrder {
+    order_key: Vec<mz_expr::ColumnOrder>,
+    left: DatumVec,
+    right: DatumVec,
+}
+
+impl RowOrder {
+    fn new(order_key: Vec<mz_expr::ColumnOrder>) -> Self {
+        Self {
+            order_key,
+            left: DatumVec::new(),
+            right: DatumVec::new(),
+        }
+    }
+
+    fn compare(&mut self, left: &Row, right: &Row) -> Ordering {
+        let left = self.left.borrow_with(left);
+        let right = self.right.borrow_with(right);
+        mz_expr::compare_columns(&self.order_key, &left, &right, || left.cmp(&right))
+    }
+}
+
+/// The buffered rows of a group of a bounded TopK.
+///
+/// The buffer holds, in order, all rows of the group up to and including `bound`, with
+/// their multiplicities. Rows past `bound` are only present in the input arrangement. A
+/// `bound` of `None` indicates that the buffer holds the entire group.
+#[derive(Default)]
+struct BoundedTopKBuffer {
+    rows: Vec<(Row, Diff)>,
+    bound: Option<Row>,
+}
+
+impl BoundedTopKBuffer {
+    /// Returns `true` if the buffer holds the entire group, and the group is empty.
+    fn is_empty(&self) -> bool {
+        self.rows.is_empty() && self.bound.is_none()
+    }
+
+    /// Returns `true` if no row of the group has a negative multiplicity.
+    fn is_valid(&self) -> bool {
+        self.rows.iter().all(|(_, diff)| diff.is_positive())
+    }
+
+    /// Returns `true` if the buffer must be refilled to produce `required` rows.
+    fn is_drained(&self, required: Diff) -> bool {
+        self.bound.is_some() && self.is_valid() && self.count() < required
+    }
+
+    /// The number of rows in the buffer.
+    fn count(&self) -> Diff {
+        self.rows.iter().map(|(_, diff)| *diff).sum()
+    }
+
+    /// Returns `true` if updates to `row` must be applied to the buffer.
+    fn covers(&self, row: &Row, order: &mut RowOrder) -> bool {
+        self.bound
+            .as_ref()
+            .map_or(true, |bound| order.compare(row, bound) != Ordering::Greater)
+    }
+
+    /// Applies an update to a row covered by the buffer.
+    fn update(&mut self, row: Row, diff: Diff, order: &mut RowOrder) {
+        match self
+            .rows
+            .binary_search_by(|(probe, _)| order.compare(probe, &row))
+        {
+            Ok(index) => {
+                self.rows[index].1 += diff;
+                if self.rows[index].1 == 0 {
+                    self.rows.remove(index);
+                }
+            }
+            Err(index) => self.rows.insert(index, (row, diff)),
+        }
+    }
+
+    /// Replaces the buffer with the first rows of the group, covering at least `capacity`
+    /// rows if the group has as many.
+    ///
+    /// Groups with negative multiplicities are retained in full, so that we can observe
+    /// their correction.
+    fn refill(&mut self, mut rows: Vec<(Row, Diff)>, capacity: Diff, order: &mut RowOrder) {
+        rows.sort_by(|(left, _), (right, _)| order.compare(left, right));
+        self.bound = None;
+        if rows.iter().all(|(_, diff)| diff.is_positive()) {
+            let mut count = 0;
+            if let Some(index) = rows.iter().position(|(_, diff)| {
+                count += *diff;
+                count >= capacity
+            }) {
+                if index + 1 < rows.len() {
+                    rows.truncate(index + 1);
+                    self.bound = Some(rows[index].0.clone());
+                }
+            }
+        }
+        self.rows = rows;
+    }
+
+    /// Releases the last rows of the buffer as long as it still covers `capacity` rows.
+    fn trim(&mut self, capacity: Diff) {
+        if !self.is_valid() {
+            return;
+        }
+        let mut count = self.count();
+        while let Some((_, diff)) = self.rows.last() {
+            if count - *diff < capacity {
+                break;
+            }
+            count -= *diff;
+            self.rows.pop();
+            self.bound = self.rows.last().map(|(row, _)| row.clone());
+        }
+    }
+
+    /// The rows at positions `offset` up to `offset + limit` of the group.
+    fn output(&self, mut offset: Diff, mut limit: Diff) -> Vec<(Row, Diff)> {
+        let mut output = Vec::new();
+        if !self.is_valid() {
+            return output;
+        }
+        for (row, diff) in self.rows.iter() {
+            if limit == 0 {
+                break;
+            }
+            let skip = std::cmp::min(offset, *diff);
+            offset -= skip;
+            let take = std::cmp::min(limit, *diff - skip);
+            limit -= take;
+            if take > 0 {
+                output.push((row.clone(), take));
+            }
+        }
+        output
+    }
+}
+
 fn render_intra_ts_thinning<S>(
     collection: Collection<S, (Row, Row), Diff>,
     order_key: Vec<mz_expr::ColumnOrder>,
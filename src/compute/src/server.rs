@@ -12,6 +12,7 @@
 use std::cell::RefCell;
 use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::fmt::Debug;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -49,11 +50,24 @@ pub struct Config {
     // TODO(guswynn): cluster-unification: ensure these stats
     // also work for storage when merging.
     pub metrics: ComputeMetrics,
+    /// Extra context for the compute instance.
+    pub context: ComputeInstanceContext,
+}
+
+/// Extra context for a compute instance.
+///
+/// This is information about the replica process that is not communicated by the compute
+/// controller.
+#[derive(Debug, Clone, Default)]
+pub struct ComputeInstanceContext {
+    /// A directory that can be used for scratch work.
+    pub scratch_directory: Option<PathBuf>,
 }
 
 /// Initiates a timely dataflow computation, processing compute commands.
 pub fn serve(
     config: mz_cluster::server::ClusterConfig,
+    context: ComputeInstanceContext,
 ) -> Result<
     (
         TimelyContainerRef<ComputeCommand, ComputeResponse, SyncActivator>,
@@ -62,7 +76,7 @@ pub fn serve(
     Error,
 > {
     let metrics = ComputeMetrics::register_with(&config.metrics_registry);
-    let compute_config = Config { metrics };
+    let compute_config = Config { metrics, context };
 
     let (timely_container, client_builder) = mz_cluster::server::serve::<
         Config,
@@ -171,6 +185,8 @@ struct Worker<'w, A: Allocate> {
     persist_clients: Arc<PersistClientCache>,
     /// A process-global handle to tracing configuration.
     tracing_handle: Arc<TracingHandle>,
+    /// Extra context for the compute instance.
+    context: ComputeInstanceContext,
 }
 
 impl mz_cluster::types::AsRunnableWorker<ComputeCommand, ComputeResponse> for Config {
@@ -193,6 +209,7 @@ impl mz_cluster::types::AsRunnableWorker<ComputeCommand, ComputeResponse> for Co
             persist_clients,
             compute_state: None,
             tracing_handle,
+            context: config.context,
         }
         .run()
     }
@@ -407,6 +424,7 @@ impl<'w, A: Allocate + 'static> Worker<'w, A> {
                     Arc::clone(&self.persist_clients),
                     self.metrics.clone(),
                     Arc::clone(&self.tracing_handle),
+                    self.context.clone(),
                 ));
             }
             _ => (),
@@ -8,4 +8,5 @@
 // by the Apache License, Version 2.0.
 
 mod persist_sink;
+pub(crate) mod refresh;
 mod subscribe;
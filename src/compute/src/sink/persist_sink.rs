@@ -102,6 +102,7 @@ where
         Antichain::new(), // we want all updates
         None,             // no MFP
         None,             // no flow control
+        None,             // no part fetch logging
         // Copy the logic in DeltaJoin/Get/Join to start.
         |_timer, count| count > 1_000_000,
     );
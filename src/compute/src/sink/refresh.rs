@@ -0,0 +1,82 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Operator that makes the output of a materialized view change only at the
+//! times of its refresh schedule.
+
+use differential_dataflow::{AsCollection, Collection, Data};
+use mz_repr::refresh_schedule::RefreshSchedule;
+use mz_repr::{Diff, Timestamp};
+use timely::dataflow::channels::pact::Pipeline;
+use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+use timely::dataflow::Scope;
+use timely::progress::Antichain;
+
+/// Rounds the times of the updates in `coll` up to the next refresh time of
+/// `refresh_schedule`.
+///
+/// The output frontier is held back at the next refresh time at or after the
+/// input frontier, so it advances step-wise, from one refresh to the next.
+/// Updates after the last refresh are dropped, and the output frontier becomes
+/// empty once the input frontier advances past the last refresh.
+pub(crate) fn apply_refresh<G, D>(
+    coll: Collection<G, D, Diff>,
+    refresh_schedule: RefreshSchedule,
+) -> Collection<G, D, Diff>
+where
+    G: Scope<Timestamp = Timestamp>,
+    D: Data,
+{
+    let mut builder = OperatorBuilder::new("ApplyRefresh".to_string(), coll.scope());
+    let (mut output, output_stream) = builder.new_output();
+    // The input is disconnected from the output. We manage the output frontier
+    // ourselves, by holding a capability at the next refresh time.
+    let mut input = builder.new_input_connection(&coll.inner, Pipeline, vec![Antichain::new()]);
+
+    builder.build(move |capabilities| {
+        let mut capability = capabilities.into_iter().next().and_then(|mut cap| {
+            let first_refresh = refresh_schedule.round_up_timestamp(*cap.time())?;
+            cap.downgrade(&first_refresh);
+            Some(cap)
+        });
+        let mut buffer = Vec::new();
+
+        move |frontiers| {
+            let mut output = output.activate();
+            input.for_each(|_cap, data| {
+                data.swap(&mut buffer);
+                // Without a capability, there is no refresh at or after the
+                // input frontier, so the updates are dropped.
+                let Some(capability) = &capability else {
+                    buffer.clear();
+                    return;
+                };
+                // The input frontier is not beyond the times of the updates,
+                // so their refresh times are not before our capability.
+                let mut session = output.session(capability);
+                for (data, time, diff) in buffer.drain(..) {
+                    if let Some(refresh_time) = refresh_schedule.round_up_timestamp(time) {
+                        session.give((data, refresh_time, diff));
+                    }
+                }
+            });
+
+            let next_refresh = frontiers[0]
+                .frontier()
+                .first()
+                .and_then(|time| refresh_schedule.round_up_timestamp(*time));
+            match (&mut capability, next_refresh) {
+                (Some(capability), Some(next_refresh)) => capability.downgrade(&next_refresh),
+                (capability, _) => *capability = None,
+            }
+        }
+    });
+
+    output_stream.as_collection()
+}
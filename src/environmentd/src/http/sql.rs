@@ -992,6 +992,8 @@ async fn execute_stmt<S: ResultSender>(
         | ExecuteResponse::CreatedTable { .. }
         | ExecuteResponse::CreatedIndex { .. }
         | ExecuteResponse::CreatedSecret { .. }
+        | ExecuteResponse::CreatedSequence
+        | ExecuteResponse::CreatedFunction
         | ExecuteResponse::CreatedSource { .. }
         | ExecuteResponse::CreatedSink { .. }
         | ExecuteResponse::CreatedView { .. }
@@ -1001,6 +1003,7 @@ async fn execute_stmt<S: ResultSender>(
         | ExecuteResponse::Comment
         | ExecuteResponse::Deleted(_)
         | ExecuteResponse::DiscardedTemp
+        | ExecuteResponse::DiscardedSequences
         | ExecuteResponse::DiscardedAll
         | ExecuteResponse::DroppedObject(_)
         | ExecuteResponse::DroppedOwned
@@ -1008,6 +1011,7 @@ async fn execute_stmt<S: ResultSender>(
         | ExecuteResponse::GrantedPrivilege
         | ExecuteResponse::GrantedRole
         | ExecuteResponse::Inserted(_)
+        | ExecuteResponse::Merged(_)
         | ExecuteResponse::Raised
         | ExecuteResponse::ReassignOwned
         | ExecuteResponse::RevokedPrivilege
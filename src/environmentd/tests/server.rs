@@ -1410,6 +1410,71 @@ fn test_old_storage_usage_records_are_reaped_on_restart() {
     };
 }
 
+// Test that `mz_plan_changes` reports the plans of a materialized view that changed since the
+// previous run, because the indexes it used were dropped.
+#[mz_ore::test]
+fn test_plan_changes_between_restarts() {
+    let data_dir = tempfile::tempdir().unwrap();
+    let config = util::Config::default().data_directory(data_dir.path());
+
+    let mv_id: String = {
+        let server = util::start_server(config.clone()).unwrap();
+        let mut client = server.connect(postgres::NoTls).unwrap();
+        client
+            .batch_execute(
+                "CREATE TABLE t1 (a int);
+                 CREATE TABLE t2 (a int);
+                 CREATE INDEX t1_a ON t1 (a);
+                 CREATE INDEX t2_a ON t2 (a);
+                 CREATE MATERIALIZED VIEW mv AS SELECT t1.a FROM t1 JOIN t2 ON t1.a = t2.a;
+                 DROP INDEX t1_a;
+                 DROP INDEX t2_a;",
+            )
+            .unwrap();
+        client
+            .query_one(
+                "SELECT id FROM mz_materialized_views WHERE name = 'mv'",
+                &[],
+            )
+            .unwrap()
+            .get(0)
+    };
+
+    let server = util::start_server(config).unwrap();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    let changes: Vec<(String, String, bool, String)> = client
+        .query(
+            "SELECT object_id, stage, regression, diff
+             FROM mz_internal.mz_plan_changes
+             ORDER BY object_id, stage",
+            &[],
+        )
+        .unwrap()
+        .into_iter()
+        .map(|row| (row.get(0), row.get(1), row.get(2), row.get(3)))
+        .collect();
+    assert_eq!(changes.len(), 2, "unexpected plan changes: {changes:?}");
+
+    // The optimized plan no longer imports the indexes.
+    let (id, stage, regression, _) = &changes[0];
+    assert_eq!(
+        (id, stage.as_str(), *regression),
+        (&mv_id, "optimized", false)
+    );
+
+    // The physical plan now arranges the inputs of the join itself.
+    let (id, stage, regression, diff) = &changes[1];
+    assert_eq!(
+        (id, stage.as_str(), *regression),
+        (&mv_id, "physical", true)
+    );
+    assert!(
+        diff.lines()
+            .any(|line| line.starts_with('+') && line.contains("ArrangeBy")),
+        "unexpected diff: {diff}"
+    );
+}
+
 #[mz_ore::test]
 fn test_default_cluster_sizes() {
     let config = util::Config::default()
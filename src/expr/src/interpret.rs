@@ -487,11 +487,12 @@ impl<'a, E: Interpreter + ?Sized> Interpreter for MfpEval<'a, E> {
 }
 
 /// A unary function we've added special-case handling for; including:
-/// - A three-argument function, taking and returning [ResultSpec]s. This
-///   overrides the default function-handling logic entirely.
+/// - A three-argument function, taking the interpreter, the function itself (for functions
+///   that carry parameters), and the argument's [ResultSpec]. This overrides the default
+///   function-handling logic entirely.
 /// - Metadata on whether / not this function is pushdownable. See [Trace].
 struct SpecialUnary {
-    map_fn: for<'a, 'b> fn(&'b ColumnSpecs<'a>, ResultSpec<'a>) -> ResultSpec<'a>,
+    map_fn: for<'a, 'b> fn(&'b ColumnSpecs<'a>, &UnaryFunc, ResultSpec<'a>) -> ResultSpec<'a>,
     pushdownable: bool,
 }
 
@@ -500,7 +501,7 @@ impl SpecialUnary {
     fn for_func(func: &UnaryFunc) -> Option<SpecialUnary> {
         match func {
             UnaryFunc::TryParseMonotonicIso8601Timestamp(_) => Some(SpecialUnary {
-                map_fn: |specs, range| {
+                map_fn: |specs, _func, range| {
                     // Using `true` for `is_monotone` is correct for this except
                     // that it also might return NULL for anything in the range,
                     // so union them.
@@ -517,6 +518,46 @@ impl SpecialUnary {
                 },
                 pushdownable: true,
             }),
+            UnaryFunc::IsLikeMatch(_) => Some(SpecialUnary {
+                map_fn: |_specs, func, range| {
+                    let UnaryFunc::IsLikeMatch(crate::func::IsLikeMatch(matcher)) = func else {
+                        unreachable!("special-cased for IsLikeMatch only")
+                    };
+                    let values_spec = match range.values {
+                        Values::Empty => ResultSpec::nothing(),
+                        Values::Within(Datum::String(min), Datum::String(max)) if min == max => {
+                            ResultSpec::value(matcher.is_match(min).into())
+                        }
+                        // All strings that start with a given prefix form a contiguous range,
+                        // so a range of strings can only contain a match if it overlaps
+                        // the range of strings that start with the pattern's literal prefix.
+                        Values::Within(Datum::String(min), Datum::String(max)) => {
+                            let may_match = match matcher.literal_prefix() {
+                                Some(prefix) => {
+                                    min.starts_with(&prefix)
+                                        || (min < prefix.as_str() && max >= prefix.as_str())
+                                }
+                                None => true,
+                            };
+                            if may_match {
+                                ResultSpec::value_between(Datum::False, Datum::True)
+                            } else {
+                                ResultSpec::value(Datum::False)
+                            }
+                        }
+                        Values::Within(_, _) | Values::Nested(_) | Values::All => {
+                            ResultSpec::value_between(Datum::False, Datum::True)
+                        }
+                    };
+                    // Like all eager functions, nulls and errors in the input propagate.
+                    ResultSpec {
+                        nullable: range.nullable,
+                        fallible: range.fallible,
+                        ..values_spec
+                    }
+                },
+                pushdownable: true,
+            }),
             _ => None,
         }
     }
@@ -567,23 +608,7 @@ impl SpecialBinary {
                 };
 
                 if let Some(field_spec) = nested_spec {
-                    if stringify {
-                        // We only preserve value-range information when stringification
-                        // is a noop. (Common in real queries.)
-                        let values = match field_spec.values {
-                            Values::Empty => Values::Empty,
-                            Values::Within(min @ Datum::String(_), max @ Datum::String(_)) => {
-                                Values::Within(min, max)
-                            }
-                            Values::Within(_, _) | Values::Nested(_) | Values::All => Values::All,
-                        };
-                        ResultSpec {
-                            values,
-                            ..field_spec
-                        }
-                    } else {
-                        field_spec
-                    }
+                    jsonb_stringified(field_spec, stringify)
                 } else {
                     // TODO: it should be possible to narrow this further...
                     ResultSpec::anything()
@@ -591,6 +616,60 @@ impl SpecialBinary {
             })
         }
 
+        fn jsonb_get_path<'b>(
+            left: ResultSpec<'b>,
+            right: ResultSpec<'b>,
+            stringify: bool,
+        ) -> ResultSpec<'b> {
+            eagerly(left, right, |left, right| {
+                let path = match right {
+                    Values::Within(path @ Datum::Array(_), path2) if path == path2 => {
+                        path.unwrap_array().elements()
+                    }
+                    _ => return ResultSpec::anything(),
+                };
+
+                // Follow the path through the nested field specs, one key at a time. A key that
+                // isn't described by the specs could lead anywhere, as could indexing into an array.
+                let mut spec = ResultSpec {
+                    values: left,
+                    ..ResultSpec::nothing()
+                };
+                for key in path.iter() {
+                    let field_spec = match (spec.values, key) {
+                        (Values::Nested(mut map_spec), Datum::String(_)) => map_spec.remove(&key),
+                        _ => None,
+                    };
+                    let Some(field_spec) = field_spec else {
+                        return ResultSpec::anything();
+                    };
+                    spec = ResultSpec {
+                        nullable: spec.nullable || field_spec.nullable,
+                        fallible: spec.fallible || field_spec.fallible,
+                        values: field_spec.values,
+                    };
+                }
+                jsonb_stringified(spec, stringify)
+            })
+        }
+
+        fn jsonb_stringified<'b>(spec: ResultSpec<'b>, stringify: bool) -> ResultSpec<'b> {
+            if stringify {
+                // We only preserve value-range information when stringification
+                // is a noop. (Common in real queries.)
+                let values = match spec.values {
+                    Values::Empty => Values::Empty,
+                    Values::Within(min @ Datum::String(_), max @ Datum::String(_)) => {
+                        Values::Within(min, max)
+                    }
+                    Values::Within(_, _) | Values::Nested(_) | Values::All => Values::All,
+                };
+                ResultSpec { values, ..spec }
+            } else {
+                spec
+            }
+        }
+
         fn eq<'b>(left: ResultSpec<'b>, right: ResultSpec<'b>) -> ResultSpec<'b> {
             eagerly(left, right, |left, right| {
                 // `eq` might return true if there's any overlap between the range of its two arguments...
@@ -619,6 +698,14 @@ impl SpecialBinary {
                 },
                 pushdownable: (true, false),
             }),
+            BinaryFunc::JsonbGetPath { stringify } => Some(SpecialBinary {
+                map_fn: if *stringify {
+                    |l, r| jsonb_get_path(l, r, true)
+                } else {
+                    |l, r| jsonb_get_path(l, r, false)
+                },
+                pushdownable: (true, false),
+            }),
             BinaryFunc::Eq => Some(SpecialBinary {
                 map_fn: eq,
                 pushdownable: (true, true),
@@ -776,7 +863,7 @@ impl<'a> Interpreter for ColumnSpecs<'a> {
 
     fn unary(&self, func: &UnaryFunc, summary: Self::Summary) -> Self::Summary {
         let mapped_spec = if let Some(special) = SpecialUnary::for_func(func) {
-            (special.map_fn)(self, summary.range)
+            (special.map_fn)(self, func, summary.range)
         } else {
             let is_monotone = func.is_monotone();
             let mut expr = MirScalarExpr::CallUnary {
@@ -971,6 +1058,7 @@ impl Interpreter for Trace {
 #[cfg(test)]
 mod tests {
     use itertools::Itertools;
+    use mz_repr::adt::array::ArrayDimension;
     use mz_repr::adt::datetime::DateTimeUnits;
     use mz_repr::{Datum, PropDatum, RowArena, ScalarType};
     use proptest::prelude::*;
@@ -1472,6 +1560,124 @@ mod tests {
         assert!(!range_out.may_contain(Datum::Numeric(400.into())));
     }
 
+    #[mz_ore::test]
+    fn test_jsonb_path() {
+        let arena = RowArena::new();
+
+        let path = arena.make_datum(|packer| {
+            packer
+                .push_array(
+                    &[ArrayDimension {
+                        lower_bound: 1,
+                        length: 2,
+                    }],
+                    [Datum::String("user"), Datum::String("id")],
+                )
+                .unwrap()
+        });
+        let expr = MirScalarExpr::CallBinary {
+            func: BinaryFunc::JsonbGetPath { stringify: true },
+            expr1: Box::new(MirScalarExpr::Column(0)),
+            expr2: Box::new(MirScalarExpr::literal_ok(
+                path,
+                ScalarType::Array(Box::new(ScalarType::String)),
+            )),
+        };
+
+        let relation = RelationType::new(vec![ScalarType::Jsonb.nullable(true)]);
+        let mut interpreter = ColumnSpecs::new(&relation, &arena);
+        interpreter.push_column(
+            0,
+            ResultSpec::map_spec(
+                [(
+                    "user".into(),
+                    ResultSpec::map_spec(
+                        [(
+                            "id".into(),
+                            ResultSpec::value_between(Datum::String("b"), Datum::String("d")),
+                        )]
+                        .into_iter()
+                        .collect(),
+                    ),
+                )]
+                .into_iter()
+                .collect(),
+            ),
+        );
+
+        let range_out = interpreter.expr(&expr).range;
+        assert!(!range_out.may_contain(Datum::String("a")));
+        assert!(range_out.may_contain(Datum::String("c")));
+        assert!(!range_out.may_contain(Datum::String("e")));
+        assert!(Trace.expr(&expr));
+    }
+
+    #[mz_ore::test]
+    fn test_like_prefix() {
+        let like = |pattern| MirScalarExpr::CallUnary {
+            func: UnaryFunc::IsLikeMatch(IsLikeMatch(
+                crate::like_pattern::compile(pattern, false).unwrap(),
+            )),
+            expr: Box::new(MirScalarExpr::Column(0)),
+        };
+
+        let relation = RelationType::new(vec![ScalarType::String.nullable(false)]);
+        let arena = RowArena::new();
+        let mut interpreter = ColumnSpecs::new(&relation, &arena);
+        interpreter.push_column(
+            0,
+            ResultSpec::value_between(Datum::String("orders/2023"), Datum::String("orders/2024")),
+        );
+
+        for (pattern, may_match) in [
+            ("orders/2023-11%", true),
+            ("orders/%", true),
+            ("orders/2022%", false),
+            ("orders/2025%", false),
+            ("refunds/%", false),
+            ("%/2022", true),
+        ] {
+            let range_out = interpreter.expr(&like(pattern)).range;
+            assert_eq!(range_out.may_contain(Datum::True), may_match, "{pattern}");
+            assert!(range_out.may_contain(Datum::False), "{pattern}");
+            assert!(!range_out.may_contain(Datum::Null), "{pattern}");
+        }
+        assert!(Trace.expr(&like("orders/%")));
+    }
+
+    #[mz_ore::test]
+    fn test_in_list() {
+        // `#0 IN (...)` is planned as a disjunction of equalities.
+        let in_list = |values: &[i64]| MirScalarExpr::CallVariadic {
+            func: VariadicFunc::Or,
+            exprs: values
+                .iter()
+                .map(|v| MirScalarExpr::CallBinary {
+                    func: BinaryFunc::Eq,
+                    expr1: Box::new(MirScalarExpr::Column(0)),
+                    expr2: Box::new(MirScalarExpr::literal_ok(
+                        Datum::Int64(*v),
+                        ScalarType::Int64,
+                    )),
+                })
+                .collect(),
+        };
+
+        let relation = RelationType::new(vec![ScalarType::Int64.nullable(false)]);
+        let arena = RowArena::new();
+        let mut interpreter = ColumnSpecs::new(&relation, &arena);
+        interpreter.push_column(0, ResultSpec::value_between(10i64.into(), 20i64.into()));
+
+        let outside = in_list(&[1, 2, 3, 4, 5, 6, 7, 8, 21, 30]);
+        let range_out = interpreter.expr(&outside).range;
+        assert!(!range_out.may_contain(Datum::True));
+        assert!(Trace.expr(&outside));
+
+        let inside = in_list(&[1, 2, 3, 4, 5, 6, 7, 8, 15, 30]);
+        let range_out = interpreter.expr(&inside).range;
+        assert!(range_out.may_contain(Datum::True));
+    }
+
     #[mz_ore::test]
     fn test_trace() {
         use super::Trace;
@@ -107,7 +107,7 @@ pub use relation::join_input_mapper::JoinInputMapper;
 pub use relation::{
     canonicalize, compare_columns, non_nullable_columns, AccessStrategy, AggregateExpr,
     CollectionPlan, ColumnOrder, JoinImplementation, JoinInputCharacteristics, LetRecLimit,
-    MirRelationExpr, ProtoAggregateExpr, ProtoAggregateFunc, ProtoColumnOrder,
+    MirRelationExpr, OptimizerHints, ProtoAggregateExpr, ProtoAggregateFunc, ProtoColumnOrder,
     ProtoRowSetFinishing, ProtoTableFunc, RowSetFinishing, WindowFrame, WindowFrameBound,
     WindowFrameUnits, RECURSION_LIMIT,
 };
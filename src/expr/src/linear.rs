@@ -1426,7 +1426,7 @@ pub mod util {
 }
 
 pub mod plan {
-    use std::collections::BTreeMap;
+    use std::collections::{BTreeMap, BTreeSet};
     use std::iter;
 
     use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
@@ -1732,6 +1732,28 @@ pub mod plan {
             identity
         }
 
+        /// Lists input columns whose values are used in outputs, predicates, or temporal bounds.
+        ///
+        /// The values of all other input columns are never read while evaluating the plan.
+        pub fn demand(&self) -> BTreeSet<usize> {
+            let mfp = &self.mfp.mfp;
+            let mut demanded = BTreeSet::new();
+            for expr in self.lower_bounds.iter().chain(self.upper_bounds.iter()) {
+                demanded.extend(expr.support());
+            }
+            for (_index, pred) in mfp.predicates.iter() {
+                demanded.extend(pred.support());
+            }
+            demanded.extend(mfp.projection.iter().cloned());
+            for index in (0..mfp.expressions.len()).rev() {
+                if demanded.contains(&(mfp.input_arity + index)) {
+                    demanded.extend(mfp.expressions[index].support());
+                }
+            }
+            demanded.retain(|col| col < &mfp.input_arity);
+            demanded
+        }
+
         /// Attempt to convert self into a non-temporal MapFilterProject plan.
         ///
         /// If that is not possible, the original instance is returned as an error.
@@ -1902,4 +1924,30 @@ mod tests {
             assert_eq!(actual.unwrap(), expect);
         }
     }
+
+    #[mz_ore::test]
+    fn mfp_plan_demand() {
+        use crate::{func, BinaryFunc, UnaryFunc, UnmaterializableFunc};
+
+        // Map `#0 + #1`, keep rows while `mz_now() <= #3`, and project only the mapped column.
+        let mfp = MapFilterProject::new(5)
+            .map(vec![MirScalarExpr::CallBinary {
+                func: BinaryFunc::AddInt64,
+                expr1: Box::new(MirScalarExpr::Column(0)),
+                expr2: Box::new(MirScalarExpr::Column(1)),
+            }])
+            .filter(vec![MirScalarExpr::CallBinary {
+                func: BinaryFunc::Lte,
+                expr1: Box::new(MirScalarExpr::CallUnmaterializable(
+                    UnmaterializableFunc::MzNow,
+                )),
+                expr2: Box::new(MirScalarExpr::CallUnary {
+                    func: UnaryFunc::CastInt64ToMzTimestamp(func::CastInt64ToMzTimestamp),
+                    expr: Box::new(MirScalarExpr::Column(3)),
+                }),
+            }])
+            .project(vec![5]);
+        let plan = MfpPlan::create_from(mfp).unwrap();
+        assert_eq!(plan.demand(), BTreeSet::from([0, 1, 3]));
+    }
 }
@@ -42,7 +42,7 @@ use crate::relation::{
     compare_columns, proto_table_func, ColumnOrder, ProtoAggregateFunc, ProtoTableFunc,
     WindowFrame, WindowFrameBound, WindowFrameUnits,
 };
-use crate::scalar::func::{add_timestamp_months, jsonb_stringify};
+use crate::scalar::func::{add_timestamp_months, jsonb_path_query, jsonb_stringify};
 use crate::EvalError;
 
 include!(concat!(env!("OUT_DIR"), "/mz_expr.relation.func.rs"));
@@ -1581,6 +1581,17 @@ fn jsonb_array_elements<'a>(
     })
 }
 
+fn jsonb_path_query_items<'a>(
+    datums: &'a [Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<impl Iterator<Item = (Row, Diff)> + 'a, EvalError> {
+    let items = jsonb_path_query(datums[0], datums[1], datums[2], datums[3], temp_storage)?;
+    Ok(items
+        .unwrap_or_default()
+        .into_iter()
+        .map(|item| (Row::pack_slice(&[item]), 1)))
+}
+
 fn regexp_extract(a: Datum, r: &AnalyzedRegex) -> Option<(Row, Diff)> {
     let r = r.inner();
     let a = a.unwrap_str();
@@ -2025,6 +2036,7 @@ pub enum TableFunc {
     JsonbArrayElements {
         stringify: bool,
     },
+    JsonbPathQuery,
     RegexpExtract(AnalyzedRegex),
     CsvExtract(usize),
     GenerateSeriesInt32,
@@ -2066,6 +2078,7 @@ impl RustType<ProtoTableFunc> for TableFunc {
                 TableFunc::JsonbEach { stringify } => Kind::JsonbEach(*stringify),
                 TableFunc::JsonbObjectKeys => Kind::JsonbObjectKeys(()),
                 TableFunc::JsonbArrayElements { stringify } => Kind::JsonbArrayElements(*stringify),
+                TableFunc::JsonbPathQuery => Kind::JsonbPathQuery(()),
                 TableFunc::RegexpExtract(x) => Kind::RegexpExtract(x.into_proto()),
                 TableFunc::CsvExtract(x) => Kind::CsvExtract(x.into_proto()),
                 TableFunc::GenerateSeriesInt32 => Kind::GenerateSeriesInt32(()),
@@ -2103,6 +2116,7 @@ impl RustType<ProtoTableFunc> for TableFunc {
             Kind::JsonbEach(stringify) => TableFunc::JsonbEach { stringify },
             Kind::JsonbObjectKeys(()) => TableFunc::JsonbObjectKeys,
             Kind::JsonbArrayElements(stringify) => TableFunc::JsonbArrayElements { stringify },
+            Kind::JsonbPathQuery(()) => TableFunc::JsonbPathQuery,
             Kind::RegexpExtract(x) => TableFunc::RegexpExtract(x.into_rust()?),
             Kind::CsvExtract(x) => TableFunc::CsvExtract(x.into_rust()?),
             Kind::GenerateSeriesInt32(()) => TableFunc::GenerateSeriesInt32,
@@ -2152,6 +2166,9 @@ impl TableFunc {
                 temp_storage,
                 *stringify,
             ))),
+            TableFunc::JsonbPathQuery => {
+                Ok(Box::new(jsonb_path_query_items(datums, temp_storage)?))
+            }
             TableFunc::RegexpExtract(a) => Ok(Box::new(regexp_extract(datums[0], a).into_iter())),
             TableFunc::CsvExtract(n_cols) => Ok(Box::new(csv_extract(datums[0], *n_cols))),
             TableFunc::GenerateSeriesInt32 => {
@@ -2261,6 +2278,11 @@ impl TableFunc {
                 let keys = vec![];
                 (column_types, keys)
             }
+            TableFunc::JsonbPathQuery => {
+                let column_types = vec![ScalarType::Jsonb.nullable(false)];
+                let keys = vec![];
+                (column_types, keys)
+            }
             TableFunc::RegexpExtract(a) => {
                 let column_types = a
                     .capture_groups_iter()
@@ -2340,6 +2362,7 @@ impl TableFunc {
             TableFunc::JsonbEach { .. } => 2,
             TableFunc::JsonbObjectKeys => 1,
             TableFunc::JsonbArrayElements { .. } => 1,
+            TableFunc::JsonbPathQuery => 1,
             TableFunc::RegexpExtract(a) => a.capture_groups_len(),
             TableFunc::CsvExtract(n_cols) => *n_cols,
             TableFunc::GenerateSeriesInt32 => 1,
@@ -2362,6 +2385,7 @@ impl TableFunc {
             | TableFunc::JsonbEach { .. }
             | TableFunc::JsonbObjectKeys
             | TableFunc::JsonbArrayElements { .. }
+            | TableFunc::JsonbPathQuery
             | TableFunc::GenerateSeriesInt32
             | TableFunc::GenerateSeriesInt64
             | TableFunc::GenerateSeriesTimestamp
@@ -2387,6 +2411,7 @@ impl TableFunc {
             TableFunc::JsonbEach { .. } => true,
             TableFunc::JsonbObjectKeys => true,
             TableFunc::JsonbArrayElements { .. } => true,
+            TableFunc::JsonbPathQuery => true,
             TableFunc::RegexpExtract(_) => true,
             TableFunc::CsvExtract(_) => true,
             TableFunc::GenerateSeriesInt32 => true,
@@ -2411,6 +2436,7 @@ impl fmt::Display for TableFunc {
             TableFunc::JsonbEach { .. } => f.write_str("jsonb_each"),
             TableFunc::JsonbObjectKeys => f.write_str("jsonb_object_keys"),
             TableFunc::JsonbArrayElements { .. } => f.write_str("jsonb_array_elements"),
+            TableFunc::JsonbPathQuery => f.write_str("jsonb_path_query"),
             TableFunc::RegexpExtract(a) => write!(f, "regexp_extract({:?}, _)", a.0),
             TableFunc::CsvExtract(n_cols) => write!(f, "csv_extract({}, _)", n_cols),
             TableFunc::GenerateSeriesInt32 => f.write_str("generate_series"),
@@ -2794,6 +2794,29 @@ impl JoinImplementation {
     }
 }
 
+/// Hints given by the user to steer the implementation of the joins in a
+/// statement, e.g. through `OPTIONS (JOIN ORDER (a, b), DELTA JOIN, NO INDEX i)`.
+///
+/// Hints are advisory: a hint that cannot be honored does not fail the
+/// statement, but is reported through an optimizer notice.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct OptimizerHints {
+    /// The collections that should be joined first, in the given order.
+    pub join_order: Vec<GlobalId>,
+    /// Whether joins should be implemented as delta joins, even if that
+    /// requires creating new arrangements.
+    pub delta_join: bool,
+    /// Indexes that must not be used by the statement.
+    pub no_index: BTreeSet<GlobalId>,
+}
+
+impl OptimizerHints {
+    /// Returns `true` iff no hints are present.
+    pub fn is_empty(&self) -> bool {
+        self.join_order.is_empty() && !self.delta_join && self.no_index.is_empty()
+    }
+}
+
 /// Characteristics of a join order candidate collection.
 ///
 /// A candidate is described by a collection and a key, and may have various liabilities.
@@ -12,9 +12,11 @@
 // which can be found in the LICENSE file at the root of this repository.
 
 use std::borrow::Cow;
+use std::cell::RefCell;
 use std::cmp::{self, Ordering};
 use std::convert::{TryFrom, TryInto};
 use std::ops::Deref;
+use std::rc::Rc;
 use std::str::FromStr;
 use std::{fmt, iter, str};
 
@@ -40,10 +42,14 @@ use mz_repr::adt::date::Date;
 use mz_repr::adt::datetime::Timezone;
 use mz_repr::adt::interval::Interval;
 use mz_repr::adt::jsonb::JsonbRef;
+use mz_repr::adt::jsonpath::JsonPath;
 use mz_repr::adt::mz_acl_item::{AclItem, AclMode, MzAclItem};
 use mz_repr::adt::numeric::{self, DecimalLike, Numeric, NumericMaxScale};
 use mz_repr::adt::range::{self, Range, RangeBound, RangeOps};
 use mz_repr::adt::regex::{any_regex, Regex};
+use mz_repr::adt::text_search::{
+    TextSearchConfig, TsQuery, TsVector, Weight, DEFAULT_RANK_WEIGHTS,
+};
 use mz_repr::adt::timestamp::{CheckedTimestamp, TimestampLike};
 use mz_repr::chrono::any_naive_datetime;
 use mz_repr::role_id::RoleId;
@@ -2038,6 +2044,90 @@ fn parse_ident<'a>(
     })?)
 }
 
+fn text_search_config(config: Datum<'_>) -> Result<TextSearchConfig, EvalError> {
+    config
+        .unwrap_str()
+        .parse()
+        .map_err(EvalError::InvalidParameterValue)
+}
+
+fn to_tsvector<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let vector = TsVector::from_document(text_search_config(a)?, b.unwrap_str());
+    Ok(Datum::String(temp_storage.push_string(vector.to_string())))
+}
+
+fn to_tsquery<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let query = TsQuery::parse_with_config(text_search_config(a)?, b.unwrap_str())
+        .map_err(EvalError::InvalidParameterValue)?;
+    Ok(Datum::String(temp_storage.push_string(query.to_string())))
+}
+
+fn plainto_tsquery<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let query = TsQuery::from_plain_text(text_search_config(a)?, b.unwrap_str());
+    Ok(Datum::String(temp_storage.push_string(query.to_string())))
+}
+
+fn ts_match<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let vector = strconv::parse_tsvector(a.unwrap_str())?;
+    let query = strconv::parse_tsquery(b.unwrap_str())?;
+    Ok(Datum::from(vector.matches(&query)))
+}
+
+fn tsvector_concat<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let left = strconv::parse_tsvector(a.unwrap_str())?;
+    let right = strconv::parse_tsvector(b.unwrap_str())?;
+    let vector = left.concat(&right);
+    Ok(Datum::String(temp_storage.push_string(vector.to_string())))
+}
+
+fn setweight<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let mut vector = strconv::parse_tsvector(a.unwrap_str())?;
+    let code = b.unwrap_uint8();
+    let weight = Weight::from_char(char::from(code)).ok_or_else(|| {
+        EvalError::InvalidParameterValue(format!("unrecognized weight: {}", code))
+    })?;
+    vector.set_weight(weight);
+    Ok(Datum::String(temp_storage.push_string(vector.to_string())))
+}
+
+fn inet_contained_by<'a>(a: Datum<'a>, b: Datum<'a>, strict: bool) -> Datum<'a> {
+    Datum::from(a.unwrap_inet().is_contained_by(&b.unwrap_inet(), strict))
+}
+
+fn inet_overlaps<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_inet().overlaps(&b.unwrap_inet()))
+}
+
+fn set_masklen<'a>(a: Datum<'a>, b: Datum<'a>, is_cidr: bool) -> Result<Datum<'a>, EvalError> {
+    let inet = a
+        .unwrap_inet()
+        .with_bits(b.unwrap_int32())
+        .map_err(|e| EvalError::InvalidParameterValue(e.to_string()))?;
+    // Unlike `inet`, `cidr` values never have bits set to the right of the
+    // netmask.
+    Ok(Datum::Inet(if is_cidr { inet.network() } else { inet }))
+}
+
 fn regexp_split_to_array<'a>(
     text: Datum<'a>,
     regexp: Datum<'a>,
@@ -2280,6 +2370,17 @@ pub enum BinaryFunc {
     UuidGenerateV5,
     MzAclItemContainsPrivilege,
     ParseIdent,
+    ToTsVector,
+    ToTsQuery,
+    PlainToTsQuery,
+    TsMatch,
+    TsVectorConcat,
+    SetWeight,
+    InetContainedBy,
+    InetContainedByOrEq,
+    InetOverlaps,
+    InetSetMasklen,
+    CidrSetMasklen,
 }
 
 impl BinaryFunc {
@@ -2540,6 +2641,17 @@ impl BinaryFunc {
             BinaryFunc::UuidGenerateV5 => Ok(uuid_generate_v5(a, b)),
             BinaryFunc::MzAclItemContainsPrivilege => mz_acl_item_contains_privilege(a, b),
             BinaryFunc::ParseIdent => parse_ident(a, b, temp_storage),
+            BinaryFunc::ToTsVector => to_tsvector(a, b, temp_storage),
+            BinaryFunc::ToTsQuery => to_tsquery(a, b, temp_storage),
+            BinaryFunc::PlainToTsQuery => plainto_tsquery(a, b, temp_storage),
+            BinaryFunc::TsMatch => ts_match(a, b),
+            BinaryFunc::TsVectorConcat => tsvector_concat(a, b, temp_storage),
+            BinaryFunc::SetWeight => setweight(a, b, temp_storage),
+            BinaryFunc::InetContainedBy => Ok(inet_contained_by(a, b, true)),
+            BinaryFunc::InetContainedByOrEq => Ok(inet_contained_by(a, b, false)),
+            BinaryFunc::InetOverlaps => Ok(inet_overlaps(a, b)),
+            BinaryFunc::InetSetMasklen => set_masklen(a, b, false),
+            BinaryFunc::CidrSetMasklen => set_masklen(a, b, true),
         }
     }
 
@@ -2714,6 +2826,16 @@ impl BinaryFunc {
             MzAclItemContainsPrivilege => ScalarType::Bool.nullable(in_nullable),
 
             ParseIdent => ScalarType::Array(Box::new(ScalarType::String)).nullable(in_nullable),
+
+            ToTsVector | TsVectorConcat | SetWeight => ScalarType::TsVector.nullable(in_nullable),
+            ToTsQuery | PlainToTsQuery => ScalarType::TsQuery.nullable(in_nullable),
+            TsMatch => ScalarType::Bool.nullable(in_nullable),
+
+            InetContainedBy | InetContainedByOrEq | InetOverlaps => {
+                ScalarType::Bool.nullable(in_nullable)
+            }
+            InetSetMasklen => ScalarType::Inet.nullable(in_nullable),
+            CidrSetMasklen => ScalarType::Cidr.nullable(in_nullable),
         }
     }
 
@@ -2910,7 +3032,18 @@ impl BinaryFunc {
             | RangeDifference
             | UuidGenerateV5
             | MzAclItemContainsPrivilege
-            | ParseIdent => false,
+            | ParseIdent
+            | ToTsVector
+            | ToTsQuery
+            | PlainToTsQuery
+            | TsMatch
+            | TsVectorConcat
+            | SetWeight
+            | InetContainedBy
+            | InetContainedByOrEq
+            | InetOverlaps
+            | InetSetMasklen
+            | CidrSetMasklen => false,
 
             JsonbGetInt64 { .. }
             | JsonbGetString { .. }
@@ -3061,7 +3194,12 @@ impl BinaryFunc {
             | RangeAdjacent
             | RangeUnion
             | RangeIntersection
-            | RangeDifference => true,
+            | RangeDifference
+            | TsMatch
+            | TsVectorConcat
+            | InetContainedBy
+            | InetContainedByOrEq
+            | InetOverlaps => true,
             ToCharTimestamp
             | ToCharTimestampTz
             | AgeTimestamp
@@ -3110,7 +3248,13 @@ impl BinaryFunc {
             | UuidGenerateV5
             | GetByte
             | MzAclItemContainsPrivilege
-            | ParseIdent => false,
+            | ParseIdent
+            | ToTsVector
+            | ToTsQuery
+            | PlainToTsQuery
+            | SetWeight
+            | InetSetMasklen
+            | CidrSetMasklen => false,
         }
     }
 
@@ -3331,6 +3475,17 @@ impl BinaryFunc {
             BinaryFunc::UuidGenerateV5 => (false, false),
             BinaryFunc::MzAclItemContainsPrivilege => (false, false),
             BinaryFunc::ParseIdent => (false, false),
+            BinaryFunc::ToTsVector
+            | BinaryFunc::ToTsQuery
+            | BinaryFunc::PlainToTsQuery
+            | BinaryFunc::TsMatch
+            | BinaryFunc::TsVectorConcat
+            | BinaryFunc::SetWeight => (false, false),
+            BinaryFunc::InetContainedBy
+            | BinaryFunc::InetContainedByOrEq
+            | BinaryFunc::InetOverlaps
+            | BinaryFunc::InetSetMasklen
+            | BinaryFunc::CidrSetMasklen => (false, false),
         }
     }
 }
@@ -3534,6 +3689,16 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::UuidGenerateV5 => f.write_str("uuid_generate_v5"),
             BinaryFunc::MzAclItemContainsPrivilege => f.write_str("mz_aclitem_contains_privilege"),
             BinaryFunc::ParseIdent => f.write_str("parse_ident"),
+            BinaryFunc::ToTsVector => f.write_str("to_tsvector"),
+            BinaryFunc::ToTsQuery => f.write_str("to_tsquery"),
+            BinaryFunc::PlainToTsQuery => f.write_str("plainto_tsquery"),
+            BinaryFunc::TsMatch => f.write_str("@@"),
+            BinaryFunc::TsVectorConcat => f.write_str("||"),
+            BinaryFunc::SetWeight => f.write_str("setweight"),
+            BinaryFunc::InetContainedBy => f.write_str("<<"),
+            BinaryFunc::InetContainedByOrEq => f.write_str("<<="),
+            BinaryFunc::InetOverlaps => f.write_str("&&"),
+            BinaryFunc::InetSetMasklen | BinaryFunc::CidrSetMasklen => f.write_str("set_masklen"),
         }
     }
 }
@@ -3746,6 +3911,17 @@ impl Arbitrary for BinaryFunc {
             Just(BinaryFunc::RangeIntersection).boxed(),
             Just(BinaryFunc::RangeDifference).boxed(),
             Just(BinaryFunc::ParseIdent).boxed(),
+            Just(BinaryFunc::ToTsVector).boxed(),
+            Just(BinaryFunc::ToTsQuery).boxed(),
+            Just(BinaryFunc::PlainToTsQuery).boxed(),
+            Just(BinaryFunc::TsMatch).boxed(),
+            Just(BinaryFunc::TsVectorConcat).boxed(),
+            Just(BinaryFunc::SetWeight).boxed(),
+            Just(BinaryFunc::InetContainedBy).boxed(),
+            Just(BinaryFunc::InetContainedByOrEq).boxed(),
+            Just(BinaryFunc::InetOverlaps).boxed(),
+            Just(BinaryFunc::InetSetMasklen).boxed(),
+            Just(BinaryFunc::CidrSetMasklen).boxed(),
         ])
     }
 }
@@ -3941,6 +4117,17 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
             BinaryFunc::UuidGenerateV5 => UuidGenerateV5(()),
             BinaryFunc::MzAclItemContainsPrivilege => MzAclItemContainsPrivilege(()),
             BinaryFunc::ParseIdent => ParseIdent(()),
+            BinaryFunc::ToTsVector => ToTsVector(()),
+            BinaryFunc::ToTsQuery => ToTsQuery(()),
+            BinaryFunc::PlainToTsQuery => PlainToTsQuery(()),
+            BinaryFunc::TsMatch => TsMatch(()),
+            BinaryFunc::TsVectorConcat => TsVectorConcat(()),
+            BinaryFunc::SetWeight => SetWeight(()),
+            BinaryFunc::InetContainedBy => InetContainedBy(()),
+            BinaryFunc::InetContainedByOrEq => InetContainedByOrEq(()),
+            BinaryFunc::InetOverlaps => InetOverlaps(()),
+            BinaryFunc::InetSetMasklen => InetSetMasklen(()),
+            BinaryFunc::CidrSetMasklen => CidrSetMasklen(()),
         };
         ProtoBinaryFunc { kind: Some(kind) }
     }
@@ -4140,6 +4327,17 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
                 UuidGenerateV5(()) => Ok(BinaryFunc::UuidGenerateV5),
                 MzAclItemContainsPrivilege(()) => Ok(BinaryFunc::MzAclItemContainsPrivilege),
                 ParseIdent(()) => Ok(BinaryFunc::ParseIdent),
+                ToTsVector(()) => Ok(BinaryFunc::ToTsVector),
+                ToTsQuery(()) => Ok(BinaryFunc::ToTsQuery),
+                PlainToTsQuery(()) => Ok(BinaryFunc::PlainToTsQuery),
+                TsMatch(()) => Ok(BinaryFunc::TsMatch),
+                TsVectorConcat(()) => Ok(BinaryFunc::TsVectorConcat),
+                SetWeight(()) => Ok(BinaryFunc::SetWeight),
+                InetContainedBy(()) => Ok(BinaryFunc::InetContainedBy),
+                InetContainedByOrEq(()) => Ok(BinaryFunc::InetContainedByOrEq),
+                InetOverlaps(()) => Ok(BinaryFunc::InetOverlaps),
+                InetSetMasklen(()) => Ok(BinaryFunc::InetSetMasklen),
+                CidrSetMasklen(()) => Ok(BinaryFunc::CidrSetMasklen),
             }
         } else {
             Err(TryFromProtoError::missing_field("ProtoBinaryFunc::kind"))
@@ -4485,6 +4683,19 @@ derive_unary!(
     CastPgLegacyCharToInt32,
     CastBytesToString,
     CastStringToJsonb,
+    CastStringToJsonPath,
+    CastStringToTsVector,
+    CastStringToTsQuery,
+    CastStringToInet,
+    CastStringToCidr,
+    CastStringToMacAddr,
+    CastInetToString,
+    CastCidrToString,
+    CastMacAddrToString,
+    CastCidrToInet,
+    CastInetToCidr,
+    InetHost,
+    InetMasklen,
     CastJsonbToString,
     CastJsonbOrNullToJsonb,
     CastJsonbToInt16,
@@ -4874,6 +5085,19 @@ impl Arbitrary for UnaryFunc {
             CastPgLegacyCharToInt32::arbitrary().prop_map_into().boxed(),
             CastBytesToString::arbitrary().prop_map_into().boxed(),
             CastStringToJsonb::arbitrary().prop_map_into().boxed(),
+            CastStringToJsonPath::arbitrary().prop_map_into().boxed(),
+            CastStringToTsVector::arbitrary().prop_map_into().boxed(),
+            CastStringToTsQuery::arbitrary().prop_map_into().boxed(),
+            CastStringToInet::arbitrary().prop_map_into().boxed(),
+            CastStringToCidr::arbitrary().prop_map_into().boxed(),
+            CastStringToMacAddr::arbitrary().prop_map_into().boxed(),
+            CastInetToString::arbitrary().prop_map_into().boxed(),
+            CastCidrToString::arbitrary().prop_map_into().boxed(),
+            CastMacAddrToString::arbitrary().prop_map_into().boxed(),
+            CastCidrToInet::arbitrary().prop_map_into().boxed(),
+            CastInetToCidr::arbitrary().prop_map_into().boxed(),
+            InetHost::arbitrary().prop_map_into().boxed(),
+            InetMasklen::arbitrary().prop_map_into().boxed(),
             CastJsonbToString::arbitrary().prop_map_into().boxed(),
             CastJsonbOrNullToJsonb::arbitrary().prop_map_into().boxed(),
             CastJsonbToInt16::arbitrary().prop_map_into().boxed(),
@@ -5229,6 +5453,19 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
             UnaryFunc::CastPgLegacyCharToInt32(_) => CastPgLegacyCharToInt32(()),
             UnaryFunc::CastBytesToString(_) => CastBytesToString(()),
             UnaryFunc::CastStringToJsonb(_) => CastStringToJsonb(()),
+            UnaryFunc::CastStringToJsonPath(_) => CastStringToJsonPath(()),
+            UnaryFunc::CastStringToTsVector(_) => CastStringToTsVector(()),
+            UnaryFunc::CastStringToTsQuery(_) => CastStringToTsQuery(()),
+            UnaryFunc::CastStringToInet(_) => CastStringToInet(()),
+            UnaryFunc::CastStringToCidr(_) => CastStringToCidr(()),
+            UnaryFunc::CastStringToMacAddr(_) => CastStringToMacAddr(()),
+            UnaryFunc::CastInetToString(_) => CastInetToString(()),
+            UnaryFunc::CastCidrToString(_) => CastCidrToString(()),
+            UnaryFunc::CastMacAddrToString(_) => CastMacAddrToString(()),
+            UnaryFunc::CastCidrToInet(_) => CastCidrToInet(()),
+            UnaryFunc::CastInetToCidr(_) => CastInetToCidr(()),
+            UnaryFunc::InetHost(_) => InetHost(()),
+            UnaryFunc::InetMasklen(_) => InetMasklen(()),
             UnaryFunc::CastJsonbToString(_) => CastJsonbToString(()),
             UnaryFunc::CastJsonbOrNullToJsonb(_) => CastJsonbOrNullToJsonb(()),
             UnaryFunc::CastJsonbToInt16(_) => CastJsonbToInt16(()),
@@ -5623,6 +5860,19 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
                 CastPgLegacyCharToInt32(()) => Ok(impls::CastPgLegacyCharToInt32.into()),
                 CastBytesToString(()) => Ok(impls::CastBytesToString.into()),
                 CastStringToJsonb(()) => Ok(impls::CastStringToJsonb.into()),
+                CastStringToJsonPath(()) => Ok(impls::CastStringToJsonPath.into()),
+                CastStringToTsVector(()) => Ok(impls::CastStringToTsVector.into()),
+                CastStringToTsQuery(()) => Ok(impls::CastStringToTsQuery.into()),
+                CastStringToInet(()) => Ok(impls::CastStringToInet.into()),
+                CastStringToCidr(()) => Ok(impls::CastStringToCidr.into()),
+                CastStringToMacAddr(()) => Ok(impls::CastStringToMacAddr.into()),
+                CastInetToString(()) => Ok(impls::CastInetToString.into()),
+                CastCidrToString(()) => Ok(impls::CastCidrToString.into()),
+                CastMacAddrToString(()) => Ok(impls::CastMacAddrToString.into()),
+                CastCidrToInet(()) => Ok(impls::CastCidrToInet.into()),
+                CastInetToCidr(()) => Ok(impls::CastInetToCidr.into()),
+                InetHost(()) => Ok(impls::InetHost.into()),
+                InetMasklen(()) => Ok(impls::InetMasklen.into()),
                 CastJsonbToString(()) => Ok(impls::CastJsonbToString.into()),
                 CastJsonbOrNullToJsonb(()) => Ok(impls::CastJsonbOrNullToJsonb.into()),
                 CastJsonbToInt16(()) => Ok(impls::CastJsonbToInt16.into()),
@@ -6272,6 +6522,347 @@ fn jsonb_build_object<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> D
     }
 }
 
+thread_local! {
+    /// The most recently parsed `jsonpath` and its text.
+    ///
+    /// The path is usually a literal, so that all rows that a worker evaluates
+    /// share one parsed path instead of parsing it again for every row.
+    static LAST_JSONPATH: RefCell<Option<(String, Rc<JsonPath>)>> = RefCell::new(None);
+}
+
+/// Parses `path`, reusing the last parsed path if it has the same text.
+fn parse_jsonpath_cached(path: &str) -> Result<Rc<JsonPath>, EvalError> {
+    LAST_JSONPATH.with(|last| {
+        let mut last = last.borrow_mut();
+        if let Some((text, parsed)) = &*last {
+            if text == path {
+                return Ok(Rc::clone(parsed));
+            }
+        }
+        let parsed = Rc::new(strconv::parse_jsonpath(path)?);
+        *last = Some((path.to_owned(), Rc::clone(&parsed)));
+        Ok(parsed)
+    })
+}
+
+/// Evaluates the `jsonpath` in `path` against the JSON document `target`,
+/// with the variables in `vars`.
+///
+/// If `silent` is true, errors that occur while evaluating the path are
+/// suppressed, and `None` is returned instead.
+pub(crate) fn jsonb_path_query<'a>(
+    target: Datum<'a>,
+    path: Datum<'a>,
+    vars: Datum<'a>,
+    silent: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Option<Vec<Datum<'a>>>, EvalError> {
+    let path = parse_jsonpath_cached(path.unwrap_str())?;
+    // Unlike errors during evaluation, an invalid `vars` argument is reported
+    // even in silent mode, as in PostgreSQL.
+    if !matches!(vars, Datum::Map(_)) {
+        return Err(EvalError::JsonPathError(
+            "\"vars\" argument is not an object".into(),
+        ));
+    }
+    match path.query(target, vars, temp_storage) {
+        Ok(items) => Ok(Some(items)),
+        Err(_) if silent.unwrap_bool() => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn jsonb_path_exists<'a>(
+    ds: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    match jsonb_path_query(ds[0], ds[1], ds[2], ds[3], temp_storage)? {
+        Some(items) => Ok(Datum::from(!items.is_empty())),
+        None => Ok(Datum::Null),
+    }
+}
+
+fn jsonb_path_match<'a>(
+    ds: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let Some(items) = jsonb_path_query(ds[0], ds[1], ds[2], ds[3], temp_storage)? else {
+        return Ok(Datum::Null);
+    };
+    match items.as_slice() {
+        [d @ (Datum::True | Datum::False)] => Ok(*d),
+        [Datum::JsonNull] => Ok(Datum::Null),
+        _ if ds[3].unwrap_bool() => Ok(Datum::Null),
+        _ => Err(EvalError::JsonPathError(
+            "single boolean result is expected".into(),
+        )),
+    }
+}
+
+fn jsonb_path_query_array<'a>(
+    ds: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let items = jsonb_path_query(ds[0], ds[1], ds[2], ds[3], temp_storage)?.unwrap_or_default();
+    Ok(temp_storage.make_datum(|packer| packer.push_list(items)))
+}
+
+fn jsonb_path_query_first<'a>(
+    ds: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let items = jsonb_path_query(ds[0], ds[1], ds[2], ds[3], temp_storage)?;
+    Ok(items
+        .and_then(|items| items.into_iter().next())
+        .unwrap_or(Datum::Null))
+}
+
+/// How [`jsonb_set_path`] changes the value at the end of the path.
+#[derive(Clone, Copy)]
+enum JsonbSetPathMode {
+    /// Replaces the value, adding it if it is missing and `create` is true.
+    Set { create: bool },
+    /// Inserts the value before or after the array element, or adds the object
+    /// key, at the end of the path.
+    Insert { after: bool },
+    /// Removes the value.
+    Delete,
+}
+
+/// Returns a copy of the JSON document `target` in which the value at `path`
+/// is changed according to `mode`.
+///
+/// As in PostgreSQL, a path that leads through a missing key or a scalar
+/// leaves `target` unchanged.
+fn jsonb_set_path<'a>(
+    target: Datum<'a>,
+    path: Datum<'a>,
+    value: Datum<'a>,
+    mode: JsonbSetPathMode,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    if !matches!(target, Datum::Map(_) | Datum::List(_)) {
+        let verb = match mode {
+            JsonbSetPathMode::Delete => "delete",
+            _ => "set",
+        };
+        return Err(EvalError::InvalidParameterValue(format!(
+            "cannot {verb} path in scalar"
+        )));
+    }
+    let path = path.unwrap_array().elements().iter().collect::<Vec<_>>();
+    jsonb_set_path_inner(target, &path, 0, value, mode, temp_storage)
+}
+
+fn jsonb_set_path_inner<'a>(
+    json: Datum<'a>,
+    path: &[Datum<'a>],
+    level: usize,
+    value: Datum<'a>,
+    mode: JsonbSetPathMode,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let Some(key) = path.get(level) else {
+        return Ok(json);
+    };
+    let key = match key {
+        Datum::String(key) => *key,
+        _ => {
+            return Err(EvalError::InvalidParameterValue(format!(
+                "path element at position {} is null",
+                level + 1
+            )))
+        }
+    };
+    let last = level + 1 == path.len();
+    let adds = matches!(
+        mode,
+        JsonbSetPathMode::Set { create: true } | JsonbSetPathMode::Insert { .. }
+    );
+    match json {
+        Datum::Map(map) => {
+            let mut pairs = map.iter().collect::<Vec<_>>();
+            match pairs.iter().position(|(k, _)| *k == key) {
+                Some(i) if last => match mode {
+                    JsonbSetPathMode::Set { .. } => pairs[i].1 = value,
+                    JsonbSetPathMode::Insert { .. } => {
+                        return Err(EvalError::InvalidParameterValue(
+                            "cannot replace existing key".into(),
+                        ))
+                    }
+                    JsonbSetPathMode::Delete => {
+                        pairs.remove(i);
+                    }
+                },
+                Some(i) => {
+                    pairs[i].1 = jsonb_set_path_inner(
+                        pairs[i].1,
+                        path,
+                        level + 1,
+                        value,
+                        mode,
+                        temp_storage,
+                    )?;
+                }
+                None if last && adds => {
+                    pairs.push((key, value));
+                    pairs.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
+                }
+                None => return Ok(json),
+            }
+            Ok(temp_storage.make_datum(|packer| packer.push_dict(pairs)))
+        }
+        Datum::List(list) => {
+            let i = strconv::parse_int64(key).map_err(|_| {
+                EvalError::InvalidParameterValue(format!(
+                    "path element at position {} is not an integer: \"{}\"",
+                    level + 1,
+                    key
+                ))
+            })?;
+            let mut elems = list.iter().collect::<Vec<_>>();
+            let len = i64::try_from(elems.len()).expect("list length fits in i64");
+            // Negative indices count backwards from the end of the array.
+            let i = if i < 0 { len + i } else { i };
+            if i < 0 || i >= len {
+                if !(last && adds) {
+                    return Ok(json);
+                }
+                // Indices before the start of the array prepend to it, and
+                // indices after its end append to it.
+                let i = if i < 0 { 0 } else { elems.len() };
+                elems.insert(i, value);
+            } else {
+                let i = usize::try_from(i).expect("known to be non-negative");
+                match mode {
+                    JsonbSetPathMode::Set { .. } if last => elems[i] = value,
+                    JsonbSetPathMode::Insert { after } if last => {
+                        elems.insert(if after { i + 1 } else { i }, value)
+                    }
+                    JsonbSetPathMode::Delete if last => {
+                        elems.remove(i);
+                    }
+                    _ => {
+                        elems[i] = jsonb_set_path_inner(
+                            elems[i],
+                            path,
+                            level + 1,
+                            value,
+                            mode,
+                            temp_storage,
+                        )?;
+                    }
+                }
+            }
+            Ok(temp_storage.make_datum(|packer| packer.push_list(elems)))
+        }
+        _ => Ok(json),
+    }
+}
+
+fn jsonb_set<'a>(ds: &[Datum<'a>], temp_storage: &'a RowArena) -> Result<Datum<'a>, EvalError> {
+    let mode = JsonbSetPathMode::Set {
+        create: ds[3].unwrap_bool(),
+    };
+    jsonb_set_path(ds[0], ds[1], ds[2], mode, temp_storage)
+}
+
+fn jsonb_insert<'a>(ds: &[Datum<'a>], temp_storage: &'a RowArena) -> Result<Datum<'a>, EvalError> {
+    let mode = JsonbSetPathMode::Insert {
+        after: ds[3].unwrap_bool(),
+    };
+    jsonb_set_path(ds[0], ds[1], ds[2], mode, temp_storage)
+}
+
+fn jsonb_set_lax<'a>(ds: &[Datum<'a>], temp_storage: &'a RowArena) -> Result<Datum<'a>, EvalError> {
+    // Only a NULL `new_value` is treated specially; the function is otherwise
+    // strict.
+    let [target, path, value, create, treatment] = ds else {
+        unreachable!("jsonb_set_lax takes five arguments")
+    };
+    if [target, path, create, treatment]
+        .iter()
+        .any(|d| d.is_null())
+    {
+        return Ok(Datum::Null);
+    }
+    let create = create.unwrap_bool();
+    if !value.is_null() {
+        return jsonb_set_path(
+            *target,
+            *path,
+            *value,
+            JsonbSetPathMode::Set { create },
+            temp_storage,
+        );
+    }
+    match treatment.unwrap_str() {
+        "raise_exception" => Err(EvalError::MustNotBeNull("JSON value".into())),
+        "use_json_null" => jsonb_set_path(
+            *target,
+            *path,
+            Datum::JsonNull,
+            JsonbSetPathMode::Set { create },
+            temp_storage,
+        ),
+        "delete_key" => jsonb_set_path(
+            *target,
+            *path,
+            Datum::JsonNull,
+            JsonbSetPathMode::Delete,
+            temp_storage,
+        ),
+        "return_target" => Ok(*target),
+        _ => Err(EvalError::InvalidParameterValue(
+            "null_value_treatment must be \"delete_key\", \"return_target\", \"use_json_null\", or \"raise_exception\"".into(),
+        )),
+    }
+}
+
+/// Reads the `weights` argument of `ts_rank`, which gives the weight of each
+/// lexeme label in `{D, C, B, A}` order. Negative weights select the default
+/// weight for their label.
+fn ts_rank_weights(weights: Datum<'_>) -> Result<[f32; 4], EvalError> {
+    let weights = weights.unwrap_array();
+    if weights.dims().len() != 1 {
+        return Err(EvalError::InvalidParameterValue(
+            "array of weight must be one-dimensional".into(),
+        ));
+    }
+    let elems = weights.elements().iter().collect::<Vec<_>>();
+    if elems.len() < DEFAULT_RANK_WEIGHTS.len() {
+        return Err(EvalError::InvalidParameterValue(
+            "array of weight is too short".into(),
+        ));
+    }
+    if elems.iter().any(|d| d.is_null()) {
+        return Err(EvalError::InvalidParameterValue(
+            "array of weight must not contain nulls".into(),
+        ));
+    }
+    let mut out = DEFAULT_RANK_WEIGHTS;
+    for (weight, elem) in out.iter_mut().zip(elems) {
+        let elem = elem.unwrap_float32();
+        if elem > 1.0 {
+            return Err(EvalError::InvalidParameterValue(
+                "weight out of range".into(),
+            ));
+        }
+        if elem >= 0.0 {
+            *weight = elem;
+        }
+    }
+    Ok(out)
+}
+
+fn ts_rank<'a>(ds: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
+    let weights = ts_rank_weights(ds[0])?;
+    let vector = strconv::parse_tsvector(ds[1].unwrap_str())?;
+    let query = strconv::parse_tsquery(ds[2].unwrap_str())?;
+    let normalization = ds[3].unwrap_int32();
+    Ok(Datum::from(vector.rank(&query, &weights, normalization)))
+}
+
 /// Constructs a new multidimensional array out of an arbitrary number of
 /// lower-dimensional arrays.
 ///
@@ -6403,7 +6994,9 @@ where
         TimestampTz => Ok(strconv::format_timestamptz(buf, &d.unwrap_timestamptz())),
         Interval => Ok(strconv::format_interval(buf, d.unwrap_interval())),
         Bytes => Ok(strconv::format_bytes(buf, d.unwrap_bytes())),
-        String | VarChar { .. } | PgLegacyName => Ok(strconv::format_string(buf, d.unwrap_str())),
+        String | VarChar { .. } | PgLegacyName | JsonPath | TsVector | TsQuery => {
+            Ok(strconv::format_string(buf, d.unwrap_str()))
+        }
         Char { length } => Ok(strconv::format_string(
             buf,
             &mz_repr::adt::char::format_str_pad(d.unwrap_str(), *length),
@@ -6414,6 +7007,9 @@ where
         }
         Jsonb => Ok(strconv::format_jsonb(buf, JsonbRef::from_datum(d))),
         Uuid => Ok(strconv::format_uuid(buf, d.unwrap_uuid())),
+        Inet => Ok(strconv::format_inet(buf, d.unwrap_inet())),
+        Cidr => Ok(strconv::format_cidr(buf, d.unwrap_inet())),
+        MacAddr => Ok(strconv::format_macaddr(buf, d.unwrap_mac_addr())),
         Record { fields, .. } => {
             let mut fields = fields.iter();
             strconv::format_record(buf, &d.unwrap_list(), |buf, d| {
@@ -7259,6 +7855,14 @@ pub enum VariadicFunc {
     Replace,
     JsonbBuildArray,
     JsonbBuildObject,
+    JsonbPathExists,
+    JsonbPathMatch,
+    JsonbPathQueryArray,
+    JsonbPathQueryFirst,
+    JsonbSet,
+    JsonbInsert,
+    JsonbSetLax,
+    TsRank,
     ArrayCreate {
         // We need to know the element type to type empty arrays.
         elem_type: ScalarType,
@@ -7353,6 +7957,14 @@ impl VariadicFunc {
             VariadicFunc::Translate => Ok(translate(&ds, temp_storage)),
             VariadicFunc::JsonbBuildArray => Ok(jsonb_build_array(&ds, temp_storage)),
             VariadicFunc::JsonbBuildObject => Ok(jsonb_build_object(&ds, temp_storage)),
+            VariadicFunc::JsonbPathExists => jsonb_path_exists(&ds, temp_storage),
+            VariadicFunc::JsonbPathMatch => jsonb_path_match(&ds, temp_storage),
+            VariadicFunc::JsonbPathQueryArray => jsonb_path_query_array(&ds, temp_storage),
+            VariadicFunc::JsonbPathQueryFirst => jsonb_path_query_first(&ds, temp_storage),
+            VariadicFunc::JsonbSet => jsonb_set(&ds, temp_storage),
+            VariadicFunc::JsonbInsert => jsonb_insert(&ds, temp_storage),
+            VariadicFunc::JsonbSetLax => jsonb_set_lax(&ds, temp_storage),
+            VariadicFunc::TsRank => ts_rank(&ds),
             VariadicFunc::ArrayCreate {
                 elem_type: ScalarType::Array(_),
             } => array_create_multidim(&ds, temp_storage),
@@ -7434,6 +8046,14 @@ impl VariadicFunc {
             | VariadicFunc::Translate
             | VariadicFunc::JsonbBuildArray
             | VariadicFunc::JsonbBuildObject
+            | VariadicFunc::JsonbPathExists
+            | VariadicFunc::JsonbPathMatch
+            | VariadicFunc::JsonbPathQueryArray
+            | VariadicFunc::JsonbPathQueryFirst
+            | VariadicFunc::JsonbSet
+            | VariadicFunc::JsonbInsert
+            | VariadicFunc::JsonbSetLax
+            | VariadicFunc::TsRank
             | VariadicFunc::ArrayCreate { elem_type: _ }
             | VariadicFunc::ArrayToString { elem_type: _ }
             | VariadicFunc::ArrayIndex { offset: _ }
@@ -7478,6 +8098,11 @@ impl VariadicFunc {
             Replace => ScalarType::String.nullable(in_nullable),
             Translate => ScalarType::String.nullable(in_nullable),
             JsonbBuildArray | JsonbBuildObject => ScalarType::Jsonb.nullable(true),
+            JsonbPathExists | JsonbPathMatch => ScalarType::Bool.nullable(true),
+            JsonbPathQueryArray => ScalarType::Jsonb.nullable(in_nullable),
+            JsonbPathQueryFirst => ScalarType::Jsonb.nullable(true),
+            JsonbSet | JsonbInsert | JsonbSetLax => ScalarType::Jsonb.nullable(in_nullable),
+            TsRank => ScalarType::Float32.nullable(in_nullable),
             ArrayCreate { elem_type } => {
                 debug_assert!(
                     input_types.iter().all(|t| t.scalar_type.base_eq(elem_type)),
@@ -7562,6 +8187,7 @@ impl VariadicFunc {
                 | VariadicFunc::ConcatWs
                 | VariadicFunc::JsonbBuildArray
                 | VariadicFunc::JsonbBuildObject
+                | VariadicFunc::JsonbSetLax
                 | VariadicFunc::ListCreate { .. }
                 | VariadicFunc::RecordCreate { .. }
                 | VariadicFunc::ArrayCreate { .. }
@@ -7589,6 +8215,11 @@ impl VariadicFunc {
             | Translate
             | JsonbBuildArray
             | JsonbBuildObject
+            | JsonbPathQueryArray
+            | JsonbSet
+            | JsonbInsert
+            | JsonbSetLax
+            | TsRank
             | ArrayCreate { .. }
             | ArrayToString { .. }
             | ListCreate { .. }
@@ -7620,7 +8251,10 @@ impl VariadicFunc {
             | MakeTimestamp
             | ArrayIndex { .. }
             | ListIndex
-            | RegexpMatch => true,
+            | RegexpMatch
+            | JsonbPathExists
+            | JsonbPathMatch
+            | JsonbPathQueryFirst => true,
         }
     }
 
@@ -7692,6 +8326,14 @@ impl VariadicFunc {
             | VariadicFunc::Replace
             | VariadicFunc::JsonbBuildArray
             | VariadicFunc::JsonbBuildObject
+            | VariadicFunc::JsonbPathExists
+            | VariadicFunc::JsonbPathMatch
+            | VariadicFunc::JsonbPathQueryArray
+            | VariadicFunc::JsonbPathQueryFirst
+            | VariadicFunc::JsonbSet
+            | VariadicFunc::JsonbInsert
+            | VariadicFunc::JsonbSetLax
+            | VariadicFunc::TsRank
             | VariadicFunc::ArrayCreate { .. }
             | VariadicFunc::ArrayToString { .. }
             | VariadicFunc::ArrayIndex { .. }
@@ -7738,6 +8380,14 @@ impl fmt::Display for VariadicFunc {
             VariadicFunc::Translate => f.write_str("translate"),
             VariadicFunc::JsonbBuildArray => f.write_str("jsonb_build_array"),
             VariadicFunc::JsonbBuildObject => f.write_str("jsonb_build_object"),
+            VariadicFunc::JsonbPathExists => f.write_str("jsonb_path_exists"),
+            VariadicFunc::JsonbPathMatch => f.write_str("jsonb_path_match"),
+            VariadicFunc::JsonbPathQueryArray => f.write_str("jsonb_path_query_array"),
+            VariadicFunc::JsonbPathQueryFirst => f.write_str("jsonb_path_query_first"),
+            VariadicFunc::JsonbSet => f.write_str("jsonb_set"),
+            VariadicFunc::JsonbInsert => f.write_str("jsonb_insert"),
+            VariadicFunc::JsonbSetLax => f.write_str("jsonb_set_lax"),
+            VariadicFunc::TsRank => f.write_str("ts_rank"),
             VariadicFunc::ArrayCreate { .. } => f.write_str("array_create"),
             VariadicFunc::ArrayToString { .. } => f.write_str("array_to_string"),
             VariadicFunc::ArrayIndex { .. } => f.write_str("array_index"),
@@ -7803,6 +8453,14 @@ impl Arbitrary for VariadicFunc {
             Just(VariadicFunc::Replace).boxed(),
             Just(VariadicFunc::JsonbBuildArray).boxed(),
             Just(VariadicFunc::JsonbBuildObject).boxed(),
+            Just(VariadicFunc::JsonbPathExists).boxed(),
+            Just(VariadicFunc::JsonbPathMatch).boxed(),
+            Just(VariadicFunc::JsonbPathQueryArray).boxed(),
+            Just(VariadicFunc::JsonbPathQueryFirst).boxed(),
+            Just(VariadicFunc::JsonbSet).boxed(),
+            Just(VariadicFunc::JsonbInsert).boxed(),
+            Just(VariadicFunc::JsonbSetLax).boxed(),
+            Just(VariadicFunc::TsRank).boxed(),
             Just(VariadicFunc::MakeAclItem).boxed(),
             Just(VariadicFunc::MakeMzAclItem).boxed(),
             ScalarType::arbitrary()
@@ -7863,6 +8521,14 @@ impl RustType<ProtoVariadicFunc> for VariadicFunc {
             VariadicFunc::Translate => Translate(()),
             VariadicFunc::JsonbBuildArray => JsonbBuildArray(()),
             VariadicFunc::JsonbBuildObject => JsonbBuildObject(()),
+            VariadicFunc::JsonbPathExists => JsonbPathExists(()),
+            VariadicFunc::JsonbPathMatch => JsonbPathMatch(()),
+            VariadicFunc::JsonbPathQueryArray => JsonbPathQueryArray(()),
+            VariadicFunc::JsonbPathQueryFirst => JsonbPathQueryFirst(()),
+            VariadicFunc::JsonbSet => JsonbSet(()),
+            VariadicFunc::JsonbInsert => JsonbInsert(()),
+            VariadicFunc::JsonbSetLax => JsonbSetLax(()),
+            VariadicFunc::TsRank => TsRank(()),
             VariadicFunc::ArrayCreate { elem_type } => ArrayCreate(elem_type.into_proto()),
             VariadicFunc::ArrayToString { elem_type } => ArrayToString(elem_type.into_proto()),
             VariadicFunc::ArrayIndex { offset } => ArrayIndex(offset.into_proto()),
@@ -7914,6 +8580,14 @@ impl RustType<ProtoVariadicFunc> for VariadicFunc {
                 Translate(()) => Ok(VariadicFunc::Translate),
                 JsonbBuildArray(()) => Ok(VariadicFunc::JsonbBuildArray),
                 JsonbBuildObject(()) => Ok(VariadicFunc::JsonbBuildObject),
+                JsonbPathExists(()) => Ok(VariadicFunc::JsonbPathExists),
+                JsonbPathMatch(()) => Ok(VariadicFunc::JsonbPathMatch),
+                JsonbPathQueryArray(()) => Ok(VariadicFunc::JsonbPathQueryArray),
+                JsonbPathQueryFirst(()) => Ok(VariadicFunc::JsonbPathQueryFirst),
+                JsonbSet(()) => Ok(VariadicFunc::JsonbSet),
+                JsonbInsert(()) => Ok(VariadicFunc::JsonbInsert),
+                JsonbSetLax(()) => Ok(VariadicFunc::JsonbSetLax),
+                TsRank(()) => Ok(VariadicFunc::TsRank),
                 ArrayCreate(elem_type) => Ok(VariadicFunc::ArrayCreate {
                     elem_type: elem_type.into_rust()?,
                 }),
@@ -25,6 +25,7 @@ mod list;
 mod map;
 mod mz_acl_item;
 mod mz_timestamp;
+mod network;
 mod numeric;
 mod oid;
 mod pg_legacy_char;
@@ -58,6 +59,7 @@ pub use crate::scalar::func::impls::list::*;
 pub use crate::scalar::func::impls::map::*;
 pub use crate::scalar::func::impls::mz_acl_item::*;
 pub use crate::scalar::func::impls::mz_timestamp::*;
+pub use crate::scalar::func::impls::network::*;
 pub use crate::scalar::func::impls::numeric::*;
 pub use crate::scalar::func::impls::oid::*;
 pub use crate::scalar::func::impls::pg_legacy_char::*;
@@ -0,0 +1,109 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::fmt;
+
+use mz_lowertest::MzReflect;
+use mz_repr::adt::network::{Inet, MacAddr};
+use mz_repr::{strconv, ColumnType, ScalarType};
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use crate::scalar::func::EagerUnaryFunc;
+
+sqlfunc!(
+    #[sqlname = "inet_to_text"]
+    #[preserves_uniqueness = true]
+    #[inverse = to_unary!(super::CastStringToInet)]
+    fn cast_inet_to_string(a: Inet) -> String {
+        // Unlike the output function, the cast to text always includes the
+        // netmask length.
+        a.to_cidr_string()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "cidr_to_text"]
+    #[preserves_uniqueness = true]
+    #[inverse = to_unary!(super::CastStringToCidr)]
+    fn cast_cidr_to_string(a: Inet) -> String {
+        let mut buf = String::new();
+        strconv::format_cidr(&mut buf, a);
+        buf
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "macaddr_to_text"]
+    #[preserves_uniqueness = true]
+    #[inverse = to_unary!(super::CastStringToMacAddr)]
+    fn cast_mac_addr_to_string(a: MacAddr) -> String {
+        let mut buf = String::with_capacity(17);
+        strconv::format_macaddr(&mut buf, a);
+        buf
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "cidr_to_inet"]
+    #[preserves_uniqueness = true]
+    #[inverse = to_unary!(super::CastInetToCidr)]
+    #[is_monotone = true]
+    fn cast_cidr_to_inet(a: Inet) -> Inet {
+        a
+    }
+);
+
+/// Converts an `inet` to a `cidr` by clearing the bits to the right of the
+/// netmask, like PostgreSQL's `network` function.
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastInetToCidr;
+
+impl<'a> EagerUnaryFunc<'a> for CastInetToCidr {
+    type Input = Inet;
+    type Output = Inet;
+
+    fn call(&self, a: Inet) -> Inet {
+        a.network()
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::Cidr.nullable(input.nullable)
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        to_unary!(super::CastCidrToInet)
+    }
+
+    fn is_monotone(&self) -> bool {
+        true
+    }
+}
+
+impl fmt::Display for CastInetToCidr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("inet_to_cidr")
+    }
+}
+
+sqlfunc!(
+    #[sqlname = "host"]
+    fn inet_host(a: Inet) -> String {
+        a.addr().to_string()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "masklen"]
+    fn inet_masklen(a: Inet) -> i32 {
+        a.bits().into()
+    }
+);
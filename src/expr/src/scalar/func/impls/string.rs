@@ -20,10 +20,13 @@ use mz_repr::adt::char::{format_str_trim, Char};
 use mz_repr::adt::date::Date;
 use mz_repr::adt::interval::Interval;
 use mz_repr::adt::jsonb::Jsonb;
+use mz_repr::adt::jsonpath::JsonPath;
+use mz_repr::adt::network::{Inet, MacAddr};
 use mz_repr::adt::numeric::{self, Numeric, NumericMaxScale};
 use mz_repr::adt::pg_legacy_name::PgLegacyName;
 use mz_repr::adt::regex::Regex;
 use mz_repr::adt::system::{Oid, PgLegacyChar};
+use mz_repr::adt::text_search::{TsQuery, TsVector};
 use mz_repr::adt::timestamp::CheckedTimestamp;
 use mz_repr::adt::varchar::{VarChar, VarCharMaxLength};
 use mz_repr::{strconv, ColumnType, Datum, Row, RowArena, ScalarType};
@@ -262,6 +265,52 @@ sqlfunc!(
     }
 );
 
+sqlfunc!(
+    #[sqlname = "text_to_inet"]
+    #[preserves_uniqueness = false]
+    #[inverse = to_unary!(super::CastInetToString)]
+    fn cast_string_to_inet<'a>(a: &'a str) -> Result<Inet, EvalError> {
+        strconv::parse_inet(a).err_into()
+    }
+);
+
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastStringToCidr;
+
+impl<'a> EagerUnaryFunc<'a> for CastStringToCidr {
+    type Input = &'a str;
+    type Output = Result<Inet, EvalError>;
+
+    fn call(&self, a: &'a str) -> Result<Inet, EvalError> {
+        strconv::parse_cidr(a).err_into()
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::Cidr.nullable(input.nullable)
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        to_unary!(super::CastCidrToString)
+    }
+}
+
+impl fmt::Display for CastStringToCidr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("text_to_cidr")
+    }
+}
+
+sqlfunc!(
+    #[sqlname = "text_to_macaddr"]
+    #[preserves_uniqueness = false]
+    #[inverse = to_unary!(super::CastMacAddrToString)]
+    fn cast_string_to_mac_addr<'a>(a: &'a str) -> Result<MacAddr, EvalError> {
+        strconv::parse_macaddr(a).err_into()
+    }
+);
+
 #[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect)]
 pub struct CastStringToArray {
     // Target array's type.
@@ -740,6 +789,33 @@ sqlfunc!(
     }
 );
 
+sqlfunc!(
+    #[sqlname = "text_to_jsonpath"]
+    #[preserves_uniqueness = false]
+    #[inverse = to_unary!(super::CastVarCharToString)]
+    fn cast_string_to_json_path<'a>(a: &'a str) -> Result<JsonPath, EvalError> {
+        Ok(strconv::parse_jsonpath(a)?)
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "text_to_tsvector"]
+    #[preserves_uniqueness = false]
+    #[inverse = to_unary!(super::CastVarCharToString)]
+    fn cast_string_to_ts_vector<'a>(a: &'a str) -> Result<TsVector, EvalError> {
+        Ok(strconv::parse_tsvector(a)?)
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "text_to_tsquery"]
+    #[preserves_uniqueness = false]
+    #[inverse = to_unary!(super::CastVarCharToString)]
+    fn cast_string_to_ts_query<'a>(a: &'a str) -> Result<TsQuery, EvalError> {
+        Ok(strconv::parse_tsquery(a)?)
+    }
+);
+
 sqlfunc!(
     #[sqlname = "btrim"]
     fn trim_whitespace<'a>(a: &'a str) -> &'a str {
@@ -121,6 +121,22 @@ impl Matcher {
             MatcherImpl::Regex(r) => r.is_match(text),
         }
     }
+
+    /// Returns the literal prefix that every string matched by this pattern starts with, if
+    /// there is a non-empty one.
+    ///
+    /// Case-insensitive patterns never report a prefix, since their matches need not share one.
+    pub fn literal_prefix(&self) -> Option<String> {
+        if self.case_insensitive {
+            return None;
+        }
+        let first = build_subpatterns(&self.pattern).ok()?.into_iter().next()?;
+        if first.consume == 0 && !first.many && !first.suffix.is_empty() {
+            Some(first.suffix)
+        } else {
+            None
+        }
+    }
 }
 
 impl RustType<ProtoMatcher> for Matcher {
@@ -548,4 +564,17 @@ mod test {
             }
         }
     }
+
+    #[mz_ore::test]
+    fn test_literal_prefix() {
+        let prefix =
+            |needle, case_insensitive| compile(needle, case_insensitive).unwrap().literal_prefix();
+        assert_eq!(prefix("ban%", false), Some("ban".to_string()));
+        assert_eq!(prefix("ban_na%", false), Some("ban".to_string()));
+        assert_eq!(prefix("banana", false), Some("banana".to_string()));
+        assert_eq!(prefix("b\\%n%", false), Some("b%n".to_string()));
+        assert_eq!(prefix("%ban", false), None);
+        assert_eq!(prefix("_an%", false), None);
+        assert_eq!(prefix("ban%", true), None);
+    }
 }
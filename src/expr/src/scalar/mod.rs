@@ -2409,6 +2409,7 @@ pub enum EvalError {
     // printer.
     IfNullError(String),
     LengthTooLarge,
+    JsonPathError(String),
 }
 
 impl fmt::Display for EvalError {
@@ -2597,6 +2598,7 @@ impl fmt::Display for EvalError {
             }
             EvalError::IfNullError(s) => f.write_str(s),
             EvalError::LengthTooLarge => write!(f, "requested length too large"),
+            EvalError::JsonPathError(e) => f.write_str(e),
         }
     }
 }
@@ -2661,6 +2663,12 @@ impl From<InvalidArrayError> for EvalError {
     }
 }
 
+impl From<mz_repr::adt::jsonpath::JsonPathError> for EvalError {
+    fn from(e: mz_repr::adt::jsonpath::JsonPathError) -> EvalError {
+        EvalError::JsonPathError(e.to_string())
+    }
+}
+
 impl From<regex::Error> for EvalError {
     fn from(e: regex::Error) -> EvalError {
         EvalError::InvalidRegex(e.to_string())
@@ -2847,6 +2855,7 @@ impl RustType<ProtoEvalError> for EvalError {
             }),
             EvalError::IfNullError(s) => IfNullError(s.clone()),
             EvalError::LengthTooLarge => LengthTooLarge(()),
+            EvalError::JsonPathError(v) => JsonPathError(v.clone()),
         };
         ProtoEvalError { kind: Some(kind) }
     }
@@ -2964,6 +2973,7 @@ impl RustType<ProtoEvalError> for EvalError {
                 }),
                 IfNullError(v) => Ok(EvalError::IfNullError(v)),
                 LengthTooLarge(()) => Ok(EvalError::LengthTooLarge),
+                JsonPathError(v) => Ok(EvalError::JsonPathError(v)),
             },
             None => Err(TryFromProtoError::missing_field("ProtoEvalError::kind")),
         }
@@ -336,15 +336,21 @@ impl<'a> mz_avro::types::ToAvro for TypedDatum<'a> {
                     buf
                 }),
                 ScalarType::Bytes => Value::Bytes(Vec::from(datum.unwrap_bytes())),
-                ScalarType::String | ScalarType::VarChar { .. } | ScalarType::PgLegacyName => {
-                    Value::String(datum.unwrap_str().to_owned())
-                }
+                ScalarType::String
+                | ScalarType::VarChar { .. }
+                | ScalarType::PgLegacyName
+                | ScalarType::JsonPath
+                | ScalarType::TsVector
+                | ScalarType::TsQuery => Value::String(datum.unwrap_str().to_owned()),
                 ScalarType::Char { length } => {
                     let s = mz_repr::adt::char::format_str_pad(datum.unwrap_str(), *length);
                     Value::String(s)
                 }
                 ScalarType::Jsonb => Value::Json(JsonbRef::from_datum(datum).to_serde_json()),
                 ScalarType::Uuid => Value::Uuid(datum.unwrap_uuid()),
+                ScalarType::Inet => Value::String(datum.unwrap_inet().to_string()),
+                ScalarType::Cidr => Value::String(datum.unwrap_inet().to_cidr_string()),
+                ScalarType::MacAddr => Value::String(datum.unwrap_mac_addr().to_string()),
                 ty @ (ScalarType::Array(..) | ScalarType::Int2Vector | ScalarType::List { .. }) => {
                     let list = match ty {
                         ScalarType::Array(_) | ScalarType::Int2Vector => {
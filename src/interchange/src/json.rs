@@ -147,15 +147,21 @@ impl ToJson for TypedDatum<'_> {
                     serde_json::Value::String(format!("{}", datum.unwrap_interval()))
                 }
                 ScalarType::Bytes => json!(datum.unwrap_bytes()),
-                ScalarType::String | ScalarType::VarChar { .. } | ScalarType::PgLegacyName => {
-                    json!(datum.unwrap_str())
-                }
+                ScalarType::String
+                | ScalarType::VarChar { .. }
+                | ScalarType::PgLegacyName
+                | ScalarType::JsonPath
+                | ScalarType::TsVector
+                | ScalarType::TsQuery => json!(datum.unwrap_str()),
                 ScalarType::Char { length } => {
                     let s = char::format_str_pad(datum.unwrap_str(), *length);
                     serde_json::Value::String(s)
                 }
                 ScalarType::Jsonb => JsonbRef::from_datum(datum).to_serde_json(),
                 ScalarType::Uuid => json!(datum.unwrap_uuid()),
+                ScalarType::Inet => json!(datum.unwrap_inet().to_string()),
+                ScalarType::Cidr => json!(datum.unwrap_inet().to_cidr_string()),
+                ScalarType::MacAddr => json!(datum.unwrap_mac_addr().to_string()),
                 ty @ (ScalarType::Array(..) | ScalarType::Int2Vector | ScalarType::List { .. }) => {
                     let list = match typ.scalar_type {
                         ScalarType::Array(_) | ScalarType::Int2Vector => {
@@ -266,7 +272,10 @@ fn build_row_schema_field(
         ScalarType::String
         | ScalarType::Char { .. }
         | ScalarType::VarChar { .. }
-        | ScalarType::PgLegacyName => {
+        | ScalarType::PgLegacyName
+        | ScalarType::JsonPath
+        | ScalarType::TsVector
+        | ScalarType::TsQuery => {
             json!("string")
         }
         ScalarType::Jsonb => json!({
@@ -277,6 +286,7 @@ fn build_row_schema_field(
             "type": "string",
             "logicalType": "uuid",
         }),
+        ScalarType::Inet | ScalarType::Cidr | ScalarType::MacAddr => json!("string"),
         ty @ (ScalarType::Array(..) | ScalarType::Int2Vector | ScalarType::List { .. }) => {
             let inner = build_row_schema_field(
                 type_namer,
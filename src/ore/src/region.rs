@@ -0,0 +1,473 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE file at the
+// root of this repository, or online at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! File-backed byte regions, for spilling cold data to disk.
+//!
+//! A [`Region`] is a fixed-capacity byte buffer that lives in a memory-mapped
+//! file rather than on the heap, so the kernel is free to page it out to the
+//! file instead of keeping it resident in memory. Regions can only be created
+//! on threads that installed a [`SpillConfig`] through [`set_spill_config`].
+//!
+//! All regions of a thread are carved out of a single spill file, which is
+//! created in [`SpillConfig::path`] on first use and unlinked right away. Freed
+//! extents of the file are reused by later regions, and the file shrinks when
+//! its tail is freed.
+
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::mem::ManuallyDrop;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use memmap2::{MmapMut, MmapOptions};
+use tracing::warn;
+
+/// Configures the regions of a thread.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpillConfig {
+    /// The directory in which to create the spill file.
+    pub path: PathBuf,
+    /// The capacity in bytes from which on a region is backed by the spill
+    /// file.
+    pub threshold_bytes: usize,
+}
+
+thread_local! {
+    /// The spill file of this thread, if spilling is enabled.
+    static SPILL_FILE: RefCell<Option<Arc<SpillFile>>> = RefCell::new(None);
+
+    /// The non-resident size and capacity in bytes of the regions visited by
+    /// [`Region::heap_size`] within the current [`spilled_size`] call.
+    static SPILLED_SIZE: Cell<Option<(usize, usize)>> = Cell::new(None);
+}
+
+/// The capacity in bytes of all regions of this process.
+static SPILLED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// The number of regions that could not be created, despite spilling being
+/// enabled.
+static SPILL_FALLBACKS: AtomicU64 = AtomicU64::new(0);
+
+/// Distinguishes the spill files of the threads of this process.
+static NEXT_FILE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Installs the spill configuration of the current thread.
+///
+/// Passing `None` disables spilling for regions created afterwards. Existing
+/// regions keep their backing, and the spill file is only removed once they
+/// are all dropped. Reinstalling an unchanged configuration keeps the current
+/// spill file.
+pub fn set_spill_config(config: Option<SpillConfig>) {
+    SPILL_FILE.with(|file| {
+        let mut file = file.borrow_mut();
+        if file.as_ref().map(|f| &f.config) != config.as_ref() {
+            *file = config.map(|config| Arc::new(SpillFile::new(config)));
+        }
+    });
+}
+
+/// Returns the capacity in bytes of all regions of this process.
+pub fn spilled_bytes() -> usize {
+    SPILLED_BYTES.load(Ordering::Relaxed)
+}
+
+/// Returns the number of times spilling was enabled, but a region could not
+/// be created and the caller fell back to the heap.
+pub fn spill_fallbacks() -> u64 {
+    SPILL_FALLBACKS.load(Ordering::Relaxed)
+}
+
+/// Calls `f` and returns its result, together with the size and the capacity
+/// in bytes of the regions that `f` visited through [`Region::heap_size`] and
+/// that are not resident in memory.
+///
+/// [`Region::heap_size`] only reports the resident part of a region to its
+/// callback. This function allows callers to learn about the rest.
+pub fn spilled_size<R>(f: impl FnOnce() -> R) -> (R, usize, usize) {
+    let outer = SPILLED_SIZE.with(|s| s.replace(Some((0, 0))));
+    let result = f();
+    let (size, capacity) = SPILLED_SIZE.with(|s| s.replace(outer)).expect("set above");
+    (result, size, capacity)
+}
+
+/// A fixed-capacity byte buffer backed by the spill file of a thread.
+pub struct Region {
+    /// The mapping of the region's extent of the spill file.
+    mmap: ManuallyDrop<MmapMut>,
+    /// The number of bytes in use.
+    len: usize,
+    /// The spill file the region is carved out of.
+    file: Arc<SpillFile>,
+    /// The offset of the region in the spill file.
+    offset: u64,
+}
+
+impl Region {
+    /// Creates an empty region of at least the given capacity, if the current
+    /// thread installed a [`SpillConfig`] and `capacity` reaches its
+    /// threshold.
+    ///
+    /// Returns `None` if the region should live on the heap instead. Failures
+    /// to extend or map the spill file are logged and counted by
+    /// [`spill_fallbacks`], and also return `None`.
+    pub fn new_spilled(capacity: usize) -> Option<Region> {
+        let file = SPILL_FILE.with(|file| file.borrow().clone())?;
+        if capacity == 0 || capacity < file.config.threshold_bytes {
+            return None;
+        }
+        match file.allocate(capacity) {
+            Ok(region) => Some(region),
+            Err(err) => {
+                SPILL_FALLBACKS.fetch_add(1, Ordering::Relaxed);
+                warn!(
+                    "failed to spill {capacity} bytes to {}, using the heap instead: {err}",
+                    file.config.path.display()
+                );
+                None
+            }
+        }
+    }
+
+    /// Returns the number of bytes in use.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Reports whether the region holds no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of bytes the region can hold.
+    pub fn capacity(&self) -> usize {
+        self.mmap.len()
+    }
+
+    /// Appends `bytes` to the region and returns the appended copy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the region does not have room for `bytes`. The bytes of a
+    /// region never move, so a region never grows.
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) -> &mut [u8] {
+        let start = self.len;
+        let end = start + bytes.len();
+        assert!(end <= self.capacity(), "region capacity exceeded");
+        self.mmap[start..end].copy_from_slice(bytes);
+        self.len = end;
+        &mut self.mmap[start..end]
+    }
+
+    /// Reports the size and capacity in bytes of the resident part of the
+    /// region to `callback`.
+    ///
+    /// The part of the region that is not resident in memory is instead
+    /// accounted for by an enclosing [`spilled_size`] call.
+    pub fn heap_size(&self, mut callback: impl FnMut(usize, usize)) {
+        let (resident_size, resident_capacity) = self.resident_size();
+        if resident_capacity > 0 {
+            callback(resident_size, resident_capacity);
+        }
+        SPILLED_SIZE.with(|s| {
+            if let Some((size, capacity)) = s.get() {
+                s.set(Some((
+                    size + (self.len - resident_size),
+                    capacity + (self.capacity() - resident_capacity),
+                )));
+            }
+        });
+    }
+
+    /// Returns the number of bytes in use and the capacity in bytes of the
+    /// pages of the region that are resident in memory.
+    fn resident_size(&self) -> (usize, usize) {
+        let page_size = page_size();
+        let pages = (self.capacity() + page_size - 1) / page_size;
+        let mut resident = vec![0u8; pages];
+        // SAFETY: The mapping starts at a page boundary, and `resident` holds
+        // one byte for each of its pages.
+        let ret = unsafe {
+            libc::mincore(
+                self.mmap.as_ptr() as *mut libc::c_void,
+                self.capacity(),
+                resident.as_mut_ptr().cast(),
+            )
+        };
+        if ret != 0 {
+            // Assume the worst, which is that all of the region is resident.
+            return (self.len, self.capacity());
+        }
+        let (mut size, mut capacity) = (0, 0);
+        for (page, _) in resident.iter().enumerate().filter(|(_, r)| *r & 1 != 0) {
+            let start = page * page_size;
+            let end = (start + page_size).min(self.capacity());
+            capacity += end - start;
+            size += self.len.min(end).saturating_sub(start);
+        }
+        (size, capacity)
+    }
+}
+
+impl Drop for Region {
+    fn drop(&mut self) {
+        let capacity = self.capacity();
+        // SAFETY: The mapping is not used after this point. It must be removed
+        // before its extent is released, as releasing may truncate the file.
+        unsafe { ManuallyDrop::drop(&mut self.mmap) };
+        self.file.release(self.offset, capacity);
+    }
+}
+
+impl fmt::Debug for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Region")
+            .field("offset", &self.offset)
+            .field("len", &self.len)
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+/// The spill file of a thread, from which its regions are allocated.
+struct SpillFile {
+    config: SpillConfig,
+    state: Mutex<SpillFileState>,
+}
+
+#[derive(Default)]
+struct SpillFileState {
+    /// The unlinked backing file, once created.
+    file: Option<File>,
+    /// The length of the backing file in bytes.
+    len: u64,
+    /// The unused extents below `len`, by offset.
+    free: BTreeMap<u64, u64>,
+}
+
+impl SpillFile {
+    fn new(config: SpillConfig) -> SpillFile {
+        SpillFile {
+            config,
+            state: Mutex::new(SpillFileState::default()),
+        }
+    }
+
+    /// Maps a new region of at least `capacity` bytes, reusing the first free
+    /// extent that is large enough, or else extending the file.
+    fn allocate(self: &Arc<Self>, capacity: usize) -> Result<Region, io::Error> {
+        let page_size = u64::try_from(page_size()).expect("must fit");
+        let capacity = u64::try_from(capacity).expect("must fit");
+        let capacity = (capacity + page_size - 1) / page_size * page_size;
+
+        let mut state = self.state.lock().expect("lock poisoned");
+        if state.file.is_none() {
+            let id = NEXT_FILE_ID.fetch_add(1, Ordering::Relaxed);
+            let path = self
+                .config
+                .path
+                .join(format!("spill-{}-{}", std::process::id(), id));
+            let file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create_new(true)
+                .open(&path)?;
+            // Unlink the file right away, so that its space is reclaimed even
+            // if the process does not exit cleanly.
+            fs::remove_file(&path)?;
+            state.file = Some(file);
+        }
+
+        let reused = state
+            .free
+            .iter()
+            .find(|(_, len)| **len >= capacity)
+            .map(|(offset, len)| (*offset, *len));
+        let offset = match reused {
+            Some((offset, len)) => {
+                state.free.remove(&offset);
+                if len > capacity {
+                    state.free.insert(offset + capacity, len - capacity);
+                }
+                offset
+            }
+            None => {
+                let offset = state.len;
+                let file = state.file.as_ref().expect("created above");
+                file.set_len(offset + capacity)?;
+                state.len = offset + capacity;
+                offset
+            }
+        };
+
+        let file = state.file.as_ref().expect("created above");
+        // SAFETY: The file is unlinked, so no other process can modify it, and
+        // the extent is not handed out to any other region while mapped.
+        let mmap = unsafe {
+            MmapOptions::new()
+                .offset(offset)
+                .len(usize::try_from(capacity).expect("must fit"))
+                .map_mut(file)
+        };
+        let mmap = match mmap {
+            Ok(mmap) => mmap,
+            Err(err) => {
+                drop(state);
+                self.release(offset, usize::try_from(capacity).expect("must fit"));
+                return Err(err);
+            }
+        };
+        SPILLED_BYTES.fetch_add(mmap.len(), Ordering::Relaxed);
+
+        Ok(Region {
+            mmap: ManuallyDrop::new(mmap),
+            len: 0,
+            file: Arc::clone(self),
+            offset,
+        })
+    }
+
+    /// Returns the extent at `offset` to the free list, merging it with
+    /// adjacent free extents and truncating the file if it ends up at the
+    /// file's end.
+    fn release(&self, offset: u64, capacity: usize) {
+        SPILLED_BYTES.fetch_sub(capacity, Ordering::Relaxed);
+
+        let mut state = self.state.lock().expect("lock poisoned");
+        let (mut offset, mut len) = (offset, u64::try_from(capacity).expect("must fit"));
+        if let Some(next_len) = state.free.remove(&(offset + len)) {
+            len += next_len;
+        }
+        let prev = state.free.range(..offset).next_back();
+        if let Some((&prev_offset, &prev_len)) = prev {
+            if prev_offset + prev_len == offset {
+                state.free.remove(&prev_offset);
+                offset = prev_offset;
+                len += prev_len;
+            }
+        }
+
+        if offset + len == state.len {
+            let file = state.file.as_ref().expect("allocated regions imply a file");
+            if file.set_len(offset).is_ok() {
+                state.len = offset;
+                return;
+            }
+        }
+        state.free.insert(offset, len);
+    }
+}
+
+/// Returns the size in bytes of a memory page.
+fn page_size() -> usize {
+    // SAFETY: `sysconf` has no preconditions.
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    usize::try_from(page_size).expect("page size must be positive")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spill_to_temp_dir(threshold_bytes: usize) {
+        set_spill_config(Some(SpillConfig {
+            path: std::env::temp_dir(),
+            threshold_bytes,
+        }));
+    }
+
+    #[mz_test_macro::test]
+    fn test_spilling_disabled() {
+        set_spill_config(None);
+        assert!(Region::new_spilled(1 << 20).is_none());
+    }
+
+    #[mz_test_macro::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `mmap`
+    fn test_spilled_region() {
+        spill_to_temp_dir(16);
+        assert!(Region::new_spilled(8).is_none());
+
+        let mut region = Region::new_spilled(32).unwrap();
+        assert_eq!(region.capacity(), page_size());
+        assert_eq!(region.extend_from_slice(b"abc"), b"abc");
+        assert_eq!(region.extend_from_slice(b"defg"), b"defg");
+        assert_eq!(region.len(), 7);
+
+        let (mut resident_size, mut resident_capacity) = (0, 0);
+        let ((), size, capacity) = spilled_size(|| {
+            region.heap_size(|size, capacity| {
+                resident_size += size;
+                resident_capacity += capacity;
+            })
+        });
+        assert_eq!(resident_size + size, 7);
+        assert_eq!(resident_capacity + capacity, page_size());
+    }
+
+    #[mz_test_macro::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `mmap`
+    fn test_spill_file_reuse() {
+        spill_to_temp_dir(1);
+        let file = SPILL_FILE.with(|file| file.borrow().clone()).unwrap();
+        let page_size = u64::try_from(page_size()).unwrap();
+
+        let a = Region::new_spilled(1).unwrap();
+        let b = Region::new_spilled(1).unwrap();
+        let c = Region::new_spilled(1).unwrap();
+        assert_eq!(
+            (a.offset, b.offset, c.offset),
+            (0, page_size, 2 * page_size)
+        );
+
+        // Freed extents in the middle of the file are reused.
+        drop(b);
+        let d = Region::new_spilled(1).unwrap();
+        assert_eq!(d.offset, page_size);
+
+        // Freed extents at the end of the file shrink it, merging with
+        // adjacent free extents.
+        drop(d);
+        drop(c);
+        assert_eq!(file.state.lock().unwrap().len, page_size);
+        assert!(file.state.lock().unwrap().free.is_empty());
+        drop(a);
+        assert_eq!(file.state.lock().unwrap().len, 0);
+    }
+
+    #[mz_test_macro::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `mmap`
+    fn test_spill_fallback() {
+        set_spill_config(Some(SpillConfig {
+            path: std::env::temp_dir().join("does-not-exist"),
+            threshold_bytes: 1,
+        }));
+        let fallbacks = spill_fallbacks();
+        assert!(Region::new_spilled(1).is_none());
+        assert!(spill_fallbacks() > fallbacks);
+    }
+
+    #[mz_test_macro::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `mmap`
+    #[should_panic(expected = "region capacity exceeded")]
+    fn test_region_capacity_exceeded() {
+        spill_to_temp_dir(1);
+        let mut region = Region::new_spilled(1).unwrap();
+        region.extend_from_slice(&vec![0; page_size() + 1]);
+    }
+}
@@ -510,6 +510,30 @@ where
     type Item = ((Result<K, String>, Result<V, String>), T, D);
 
     fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_key_decoder(K::decode)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // We don't know in advance how restrictive the filter will be.
+        let max_len = self.part.part.updates.iter().map(|x| x.len()).sum();
+        (0, Some(max_len))
+    }
+}
+
+impl<K, V, T, D> FetchedPart<K, V, T, D>
+where
+    K: Debug + Codec,
+    V: Debug + Codec,
+    T: Timestamp + Lattice + Codec64,
+    D: Semigroup + Codec64 + Send + Sync,
+{
+    /// Like [Iterator::next], but decodes the key with `decode_key` instead of [Codec::decode].
+    ///
+    /// This lets callers skip decoding parts of the key that they know they won't read.
+    pub fn next_with_key_decoder(
+        &mut self,
+        decode_key: impl FnOnce(&[u8]) -> Result<K, String>,
+    ) -> Option<((Result<K, String>, Result<V, String>), T, D)> {
         while let Some((k, v, mut t, d)) = self.part_cursor.pop(&self.part) {
             if !self.ts_filter.filter_ts(&mut t) {
                 continue;
@@ -542,18 +566,12 @@ where
                 continue;
             }
 
-            let k = self.metrics.codecs.key.decode(|| K::decode(k));
+            let k = self.metrics.codecs.key.decode(|| decode_key(k));
             let v = self.metrics.codecs.val.decode(|| V::decode(v));
             return Some(((k, v), t, d));
         }
         None
     }
-
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        // We don't know in advance how restrictive the filter will be.
-        let max_len = self.part.part.updates.iter().map(|x| x.len()).sum();
-        (0, Some(max_len))
-    }
 }
 
 impl<T> EncodedPart<T>
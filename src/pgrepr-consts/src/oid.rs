@@ -27,12 +27,16 @@ pub const TYPE_BYTEA_ARRAY_OID: u32 = 1001;
 pub const TYPE_BYTEA_OID: u32 = 17;
 pub const TYPE_CHAR_ARRAY_OID: u32 = 1002;
 pub const TYPE_CHAR_OID: u32 = 18;
+pub const TYPE_CIDR_ARRAY_OID: u32 = 651;
+pub const TYPE_CIDR_OID: u32 = 650;
 pub const TYPE_DATE_ARRAY_OID: u32 = 1182;
 pub const TYPE_DATE_OID: u32 = 1082;
 pub const TYPE_FLOAT4_ARRAY_OID: u32 = 1021;
 pub const TYPE_FLOAT4_OID: u32 = 700;
 pub const TYPE_FLOAT8_ARRAY_OID: u32 = 1022;
 pub const TYPE_FLOAT8_OID: u32 = 701;
+pub const TYPE_INET_ARRAY_OID: u32 = 1041;
+pub const TYPE_INET_OID: u32 = 869;
 pub const TYPE_INT2_ARRAY_OID: u32 = 1005;
 pub const TYPE_INT2_OID: u32 = 21;
 pub const TYPE_INT2_VECTOR_ARRAY_OID: u32 = 1006;
@@ -45,7 +49,11 @@ pub const TYPE_INTERVAL_ARRAY_OID: u32 = 1187;
 pub const TYPE_INTERVAL_OID: u32 = 1186;
 pub const TYPE_JSONB_ARRAY_OID: u32 = 3807;
 pub const TYPE_JSONB_OID: u32 = 3802;
+pub const TYPE_JSONPATH_ARRAY_OID: u32 = 4073;
+pub const TYPE_JSONPATH_OID: u32 = 4072;
 pub const TYPE_LIST_OID_OID: u32 = 16_384;
+pub const TYPE_MACADDR_ARRAY_OID: u32 = 1040;
+pub const TYPE_MACADDR_OID: u32 = 829;
 pub const TYPE_NAME_ARRAY_OID: u32 = 1003;
 pub const TYPE_NAME_OID: u32 = 19;
 pub const TYPE_NUMERIC_ARRAY_OID: u32 = 1231;
@@ -68,6 +76,10 @@ pub const TYPE_TIMESTAMP_ARRAY_OID: u32 = 1115;
 pub const TYPE_TIMESTAMP_OID: u32 = 1114;
 pub const TYPE_TIMESTAMPTZ_ARRAY_OID: u32 = 1185;
 pub const TYPE_TIMESTAMPTZ_OID: u32 = 1184;
+pub const TYPE_TSQUERY_ARRAY_OID: u32 = 3645;
+pub const TYPE_TSQUERY_OID: u32 = 3615;
+pub const TYPE_TSVECTOR_ARRAY_OID: u32 = 3643;
+pub const TYPE_TSVECTOR_OID: u32 = 3614;
 pub const TYPE_UUID_ARRAY_OID: u32 = 2951;
 pub const TYPE_UUID_OID: u32 = 2950;
 pub const TYPE_VARCHAR_ARRAY_OID: u32 = 1015;
@@ -87,6 +99,21 @@ pub const TYPE_TSRANGE_ARRAY_OID: u32 = 3909;
 pub const TYPE_TSTZRANGE_OID: u32 = 3910;
 pub const TYPE_TSTZRANGE_ARRAY_OID: u32 = 3911;
 
+// PostgreSQL builtin function and operator OIDs
+pub const FUNC_JSONB_POPULATE_RECORD_OID: u32 = 3209;
+pub const FUNC_JSONB_SET_OID: u32 = 3304;
+pub const FUNC_JSONB_TO_RECORD_OID: u32 = 3490;
+pub const FUNC_JSONB_TO_RECORDSET_OID: u32 = 3491;
+pub const FUNC_JSONB_INSERT_OID: u32 = 3579;
+pub const FUNC_JSONB_PATH_EXISTS_OID: u32 = 4005;
+pub const FUNC_JSONB_PATH_QUERY_OID: u32 = 4006;
+pub const FUNC_JSONB_PATH_QUERY_ARRAY_OID: u32 = 4007;
+pub const FUNC_JSONB_PATH_QUERY_FIRST_OID: u32 = 4008;
+pub const FUNC_JSONB_PATH_MATCH_OID: u32 = 4009;
+pub const OP_JSONB_PATH_EXISTS_OID: u32 = 4012;
+pub const OP_JSONB_PATH_MATCH_OID: u32 = 4013;
+pub const FUNC_JSONB_SET_LAX_OID: u32 = 6334;
+
 /// The first OID in PostgreSQL's system catalog that is not pinned during
 /// bootstrapping.
 ///
@@ -359,3 +386,20 @@ pub const FUNC_AVG_INTERNAL_V1_UINT16_OID: u32 = 16_640;
 pub const FUNC_AVG_INTERNAL_V1_FLOAT32_OID: u32 = 16_641;
 pub const FUNC_AVG_INTERNAL_V1_FLOAT64_OID: u32 = 16_642;
 pub const FUNC_AVG_INTERNAL_V1_INTERVAL_OID: u32 = 16_643;
+pub const FUNC_JSONB_PATH_EXISTS_JSONB_JSONPATH_OID: u32 = 16_644;
+pub const FUNC_JSONB_PATH_EXISTS_JSONB_JSONPATH_JSONB_OID: u32 = 16_645;
+pub const FUNC_JSONB_PATH_MATCH_JSONB_JSONPATH_OID: u32 = 16_646;
+pub const FUNC_JSONB_PATH_MATCH_JSONB_JSONPATH_JSONB_OID: u32 = 16_647;
+pub const FUNC_JSONB_PATH_QUERY_JSONB_JSONPATH_OID: u32 = 16_648;
+pub const FUNC_JSONB_PATH_QUERY_JSONB_JSONPATH_JSONB_OID: u32 = 16_649;
+pub const FUNC_JSONB_PATH_QUERY_ARRAY_JSONB_JSONPATH_OID: u32 = 16_650;
+pub const FUNC_JSONB_PATH_QUERY_ARRAY_JSONB_JSONPATH_JSONB_OID: u32 = 16_651;
+pub const FUNC_JSONB_PATH_QUERY_FIRST_JSONB_JSONPATH_OID: u32 = 16_652;
+pub const FUNC_JSONB_PATH_QUERY_FIRST_JSONB_JSONPATH_JSONB_OID: u32 = 16_653;
+pub const FUNC_JSONB_SET_JSONB_TEXT_ARRAY_JSONB_OID: u32 = 16_654;
+pub const FUNC_JSONB_INSERT_JSONB_TEXT_ARRAY_JSONB_OID: u32 = 16_655;
+pub const FUNC_JSONB_SET_LAX_JSONB_TEXT_ARRAY_JSONB_OID: u32 = 16_656;
+pub const FUNC_JSONB_SET_LAX_JSONB_TEXT_ARRAY_JSONB_BOOL_OID: u32 = 16_657;
+pub const FUNC_TO_TSVECTOR_TEXT_TEXT_OID: u32 = 16_658;
+pub const FUNC_TO_TSQUERY_TEXT_TEXT_OID: u32 = 16_659;
+pub const FUNC_PLAINTO_TSQUERY_TEXT_TEXT_OID: u32 = 16_660;
@@ -84,6 +84,8 @@ pub enum Type {
     Json,
     /// A binary JSON blob.
     Jsonb,
+    /// A SQL/JSON path expression.
+    JsonPath,
     /// A sequence of homogeneous values.
     List(Box<Type>),
     /// A map with text keys and homogeneous values.
@@ -137,8 +139,18 @@ pub enum Type {
         /// An optional precision for the fractional digits in the second field.
         precision: Option<TimestampPrecision>,
     },
+    /// A document prepared for full text search.
+    TsVector,
+    /// A full text search query.
+    TsQuery,
     /// A universally unique identifier.
     Uuid,
+    /// An IPv4 or IPv6 host address and optionally its subnet.
+    Inet,
+    /// An IPv4 or IPv6 network.
+    Cidr,
+    /// A MAC address.
+    MacAddr,
     /// A function name.
     RegProc,
     /// A type name.
@@ -529,6 +541,9 @@ impl Type {
             postgres_types::Type::INTERVAL => Type::Interval { constraints: None },
             postgres_types::Type::JSON => Type::Json,
             postgres_types::Type::JSONB => Type::Jsonb,
+            postgres_types::Type::JSONPATH => Type::JsonPath,
+            postgres_types::Type::TS_VECTOR => Type::TsVector,
+            postgres_types::Type::TSQUERY => Type::TsQuery,
             postgres_types::Type::NUMERIC => Type::Numeric { constraints: None },
             postgres_types::Type::OID => Type::Oid,
             postgres_types::Type::TEXT => Type::Text,
@@ -541,6 +556,9 @@ impl Type {
             postgres_types::Type::TIMESTAMP => Type::Timestamp { precision: None },
             postgres_types::Type::TIMESTAMPTZ => Type::TimestampTz { precision: None },
             postgres_types::Type::UUID => Type::Uuid,
+            postgres_types::Type::INET => Type::Inet,
+            postgres_types::Type::CIDR => Type::Cidr,
+            postgres_types::Type::MACADDR => Type::MacAddr,
             postgres_types::Type::REGCLASS => Type::RegClass,
             postgres_types::Type::REGPROC => Type::RegProc,
             postgres_types::Type::REGTYPE => Type::RegType,
@@ -560,6 +578,9 @@ impl Type {
             }
             postgres_types::Type::JSON_ARRAY => Type::Array(Box::new(Type::Json)),
             postgres_types::Type::JSONB_ARRAY => Type::Array(Box::new(Type::Jsonb)),
+            postgres_types::Type::JSONPATH_ARRAY => Type::Array(Box::new(Type::JsonPath)),
+            postgres_types::Type::TS_VECTOR_ARRAY => Type::Array(Box::new(Type::TsVector)),
+            postgres_types::Type::TSQUERY_ARRAY => Type::Array(Box::new(Type::TsQuery)),
             postgres_types::Type::NUMERIC_ARRAY => {
                 Type::Array(Box::new(Type::Numeric { constraints: None }))
             }
@@ -578,6 +599,9 @@ impl Type {
                 Type::Array(Box::new(Type::TimestampTz { precision: None }))
             }
             postgres_types::Type::UUID_ARRAY => Type::Array(Box::new(Type::Uuid)),
+            postgres_types::Type::INET_ARRAY => Type::Array(Box::new(Type::Inet)),
+            postgres_types::Type::CIDR_ARRAY => Type::Array(Box::new(Type::Cidr)),
+            postgres_types::Type::MACADDR_ARRAY => Type::Array(Box::new(Type::MacAddr)),
             postgres_types::Type::VARCHAR_ARRAY => {
                 Type::Array(Box::new(Type::VarChar { max_length: None }))
             }
@@ -689,6 +713,7 @@ impl Type {
                 Type::Interval { .. } => &postgres_types::Type::INTERVAL_ARRAY,
                 Type::Json => &postgres_types::Type::JSON_ARRAY,
                 Type::Jsonb => &postgres_types::Type::JSONB_ARRAY,
+                Type::JsonPath => &postgres_types::Type::JSONPATH_ARRAY,
                 Type::List(_) => unreachable!(),
                 Type::Map { .. } => unreachable!(),
                 Type::Name { .. } => &postgres_types::Type::NAME_ARRAY,
@@ -702,7 +727,12 @@ impl Type {
                 Type::TimeTz { .. } => &postgres_types::Type::TIMETZ_ARRAY,
                 Type::Timestamp { .. } => &postgres_types::Type::TIMESTAMP_ARRAY,
                 Type::TimestampTz { .. } => &postgres_types::Type::TIMESTAMPTZ_ARRAY,
+                Type::TsVector => &postgres_types::Type::TS_VECTOR_ARRAY,
+                Type::TsQuery => &postgres_types::Type::TSQUERY_ARRAY,
                 Type::Uuid => &postgres_types::Type::UUID_ARRAY,
+                Type::Inet => &postgres_types::Type::INET_ARRAY,
+                Type::Cidr => &postgres_types::Type::CIDR_ARRAY,
+                Type::MacAddr => &postgres_types::Type::MACADDR_ARRAY,
                 Type::RegClass => &postgres_types::Type::REGCLASS_ARRAY,
                 Type::RegProc => &postgres_types::Type::REGPROC_ARRAY,
                 Type::RegType => &postgres_types::Type::REGTYPE_ARRAY,
@@ -734,6 +764,7 @@ impl Type {
             Type::Interval { .. } => &postgres_types::Type::INTERVAL,
             Type::Json => &postgres_types::Type::JSON,
             Type::Jsonb => &postgres_types::Type::JSONB,
+            Type::JsonPath => &postgres_types::Type::JSONPATH,
             Type::List(_) => &LIST,
             Type::Map { .. } => &MAP,
             Type::Name => &postgres_types::Type::NAME,
@@ -747,7 +778,12 @@ impl Type {
             Type::TimeTz { .. } => &postgres_types::Type::TIMETZ,
             Type::Timestamp { .. } => &postgres_types::Type::TIMESTAMP,
             Type::TimestampTz { .. } => &postgres_types::Type::TIMESTAMPTZ,
+            Type::TsVector => &postgres_types::Type::TS_VECTOR,
+            Type::TsQuery => &postgres_types::Type::TSQUERY,
             Type::Uuid => &postgres_types::Type::UUID,
+            Type::Inet => &postgres_types::Type::INET,
+            Type::Cidr => &postgres_types::Type::CIDR,
+            Type::MacAddr => &postgres_types::Type::MACADDR,
             Type::RegClass => &postgres_types::Type::REGCLASS,
             Type::RegProc => &postgres_types::Type::REGPROC,
             Type::RegType => &postgres_types::Type::REGTYPE,
@@ -874,6 +910,7 @@ impl Type {
             | Type::Interval { constraints: None }
             | Type::Json
             | Type::Jsonb
+            | Type::JsonPath
             | Type::List(_)
             | Type::Map { .. }
             | Type::Name
@@ -889,7 +926,12 @@ impl Type {
             | Type::TimeTz { precision: None }
             | Type::Timestamp { precision: None }
             | Type::TimestampTz { precision: None }
+            | Type::TsVector
+            | Type::TsQuery
             | Type::Uuid
+            | Type::Inet
+            | Type::Cidr
+            | Type::MacAddr
             | Type::MzTimestamp
             | Type::VarChar { max_length: None }
             | Type::Range { .. }
@@ -917,6 +959,7 @@ impl Type {
             Type::Interval { .. } => 16,
             Type::Json => -1,
             Type::Jsonb => -1,
+            Type::JsonPath => -1,
             Type::List(_) => -1,
             Type::Map { .. } => -1,
             Type::Name { .. } => 64,
@@ -930,7 +973,12 @@ impl Type {
             Type::TimeTz { .. } => 4,
             Type::Timestamp { .. } => 8,
             Type::TimestampTz { .. } => 8,
+            Type::TsVector => -1,
+            Type::TsQuery => -1,
             Type::Uuid => 16,
+            Type::Inet => -1,
+            Type::Cidr => -1,
+            Type::MacAddr => 6,
             Type::RegClass => 4,
             Type::RegProc => 4,
             Type::RegType => 4,
@@ -994,6 +1042,7 @@ impl TryFrom<&Type> for ScalarType {
             Type::Interval { .. } => Ok(ScalarType::Interval),
             Type::Json => Err(TypeConversionError::UnsupportedType(Type::Json)),
             Type::Jsonb => Ok(ScalarType::Jsonb),
+            Type::JsonPath => Ok(ScalarType::JsonPath),
             Type::List(t) => Ok(ScalarType::List {
                 element_type: Box::new(TryFrom::try_from(&**t)?),
                 custom_id: None,
@@ -1057,7 +1106,12 @@ impl TryFrom<&Type> for ScalarType {
             Type::TimestampTz { precision: Some(_) } => {
                 Err(TypeConversionError::UnsupportedType(typ.clone()))
             }
+            Type::TsVector => Ok(ScalarType::TsVector),
+            Type::TsQuery => Ok(ScalarType::TsQuery),
             Type::Uuid => Ok(ScalarType::Uuid),
+            Type::Inet => Ok(ScalarType::Inet),
+            Type::Cidr => Ok(ScalarType::Cidr),
+            Type::MacAddr => Ok(ScalarType::MacAddr),
             Type::RegClass => Ok(ScalarType::RegClass),
             Type::RegProc => Ok(ScalarType::RegProc),
             Type::RegType => Ok(ScalarType::RegType),
@@ -1178,6 +1232,9 @@ impl From<&ScalarType> for Type {
             ScalarType::UInt64 => Type::UInt8,
             ScalarType::Interval => Type::Interval { constraints: None },
             ScalarType::Jsonb => Type::Jsonb,
+            ScalarType::JsonPath => Type::JsonPath,
+            ScalarType::TsVector => Type::TsVector,
+            ScalarType::TsQuery => Type::TsQuery,
             ScalarType::List { element_type, .. } => {
                 Type::List(Box::new(From::from(&**element_type)))
             }
@@ -1203,6 +1260,9 @@ impl From<&ScalarType> for Type {
             ScalarType::Timestamp => Type::Timestamp { precision: None },
             ScalarType::TimestampTz => Type::TimestampTz { precision: None },
             ScalarType::Uuid => Type::Uuid,
+            ScalarType::Inet => Type::Inet,
+            ScalarType::Cidr => Type::Cidr,
+            ScalarType::MacAddr => Type::MacAddr,
             ScalarType::Numeric { max_scale } => Type::Numeric {
                 constraints: Some(NumericConstraints {
                     max_precision: i32::from(NUMERIC_DATUM_MAX_PRECISION),
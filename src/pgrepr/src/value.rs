@@ -9,6 +9,7 @@
 
 use std::collections::BTreeMap;
 use std::error::Error;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::{io, str};
 
 use bytes::{BufMut, BytesMut};
@@ -19,8 +20,10 @@ use mz_repr::adt::char;
 use mz_repr::adt::date::Date;
 use mz_repr::adt::jsonb::JsonbRef;
 use mz_repr::adt::mz_acl_item::{AclItem, MzAclItem};
+use mz_repr::adt::network::{Inet, MacAddr};
 use mz_repr::adt::pg_legacy_name::NAME_MAX_BYTES;
 use mz_repr::adt::range::{Range, RangeInner};
+use mz_repr::adt::text_search::{TsQuery, TsVector};
 use mz_repr::adt::timestamp::CheckedTimestamp;
 use mz_repr::strconv::{self, Nestable};
 use mz_repr::{Datum, RelationType, Row, RowArena, ScalarType};
@@ -74,6 +77,12 @@ pub enum Value {
     Interval(Interval),
     /// A binary JSON blob.
     Jsonb(Jsonb),
+    /// A SQL/JSON path expression, in its canonical textual representation.
+    JsonPath(String),
+    /// A full text search document, in its canonical textual representation.
+    TsVector(String),
+    /// A full text search query, in its canonical textual representation.
+    TsQuery(String),
     /// A sequence of homogeneous values.
     List(Vec<Option<Value>>),
     /// A map of string keys and homogeneous values.
@@ -100,6 +109,12 @@ pub enum Value {
     VarChar(String),
     /// A universally unique identifier.
     Uuid(Uuid),
+    /// An IPv4 or IPv6 host address and optionally its subnet.
+    Inet(Inet),
+    /// An IPv4 or IPv6 network.
+    Cidr(Inet),
+    /// A MAC address.
+    MacAddr(MacAddr),
     /// A small int vector.
     Int2Vector {
         /// The elements of the vector.
@@ -156,10 +171,16 @@ impl Value {
                 Some(Value::BpChar(char::format_str_pad(s, *length)))
             }
             (Datum::String(s), ScalarType::PgLegacyName) => Some(Value::Name(s.into())),
+            (Datum::String(s), ScalarType::JsonPath) => Some(Value::JsonPath(s.into())),
+            (Datum::String(s), ScalarType::TsVector) => Some(Value::TsVector(s.into())),
+            (Datum::String(s), ScalarType::TsQuery) => Some(Value::TsQuery(s.into())),
             (_, ScalarType::Jsonb) => {
                 Some(Value::Jsonb(Jsonb(JsonbRef::from_datum(datum).to_owned())))
             }
             (Datum::Uuid(u), ScalarType::Uuid) => Some(Value::Uuid(u)),
+            (Datum::Inet(inet), ScalarType::Inet) => Some(Value::Inet(inet)),
+            (Datum::Inet(cidr), ScalarType::Cidr) => Some(Value::Cidr(cidr)),
+            (Datum::MacAddr(mac_addr), ScalarType::MacAddr) => Some(Value::MacAddr(mac_addr)),
             (Datum::Array(array), ScalarType::Array(elem_type)) => {
                 let dims = array.dims().into_iter().collect();
                 let elements = array
@@ -292,11 +313,16 @@ impl Value {
             Value::Timestamp(ts) => Datum::Timestamp(ts),
             Value::TimestampTz(ts) => Datum::TimestampTz(ts),
             Value::Interval(iv) => Datum::Interval(iv.0),
-            Value::Text(s) | Value::VarChar(s) | Value::Name(s) => {
-                Datum::String(buf.push_string(s))
-            }
+            Value::Text(s)
+            | Value::VarChar(s)
+            | Value::Name(s)
+            | Value::JsonPath(s)
+            | Value::TsVector(s)
+            | Value::TsQuery(s) => Datum::String(buf.push_string(s)),
             Value::BpChar(s) => Datum::String(buf.push_string(s.trim_end().into())),
             Value::Uuid(u) => Datum::Uuid(u),
+            Value::Inet(inet) | Value::Cidr(inet) => Datum::Inet(inet),
+            Value::MacAddr(mac_addr) => Datum::MacAddr(mac_addr),
             Value::Numeric(n) => Datum::Numeric(n.0),
             Value::MzTimestamp(t) => Datum::MzTimestamp(t),
             Value::Range(range) => {
@@ -378,13 +404,20 @@ impl Value {
                 Some(elem) => Ok(elem.encode_text(buf.nonnull_buffer())),
             })
             .expect("provided closure never fails"),
-            Value::Text(s) | Value::VarChar(s) | Value::BpChar(s) | Value::Name(s) => {
-                strconv::format_string(buf, s)
-            }
+            Value::Text(s)
+            | Value::VarChar(s)
+            | Value::BpChar(s)
+            | Value::Name(s)
+            | Value::JsonPath(s)
+            | Value::TsVector(s)
+            | Value::TsQuery(s) => strconv::format_string(buf, s),
             Value::Time(t) => strconv::format_time(buf, *t),
             Value::Timestamp(ts) => strconv::format_timestamp(buf, ts),
             Value::TimestampTz(ts) => strconv::format_timestamptz(buf, ts),
             Value::Uuid(u) => strconv::format_uuid(buf, *u),
+            Value::Inet(inet) => strconv::format_inet(buf, *inet),
+            Value::Cidr(cidr) => strconv::format_cidr(buf, *cidr),
+            Value::MacAddr(mac_addr) => strconv::format_macaddr(buf, *mac_addr),
             Value::Numeric(d) => strconv::format_numeric(buf, &d.0),
             Value::MzTimestamp(t) => strconv::format_mz_timestamp(buf, *t),
             Value::Range(range) => strconv::format_range(buf, range, |buf, elem| match elem {
@@ -443,6 +476,27 @@ impl Value {
             Value::UInt8(u) => u.to_sql(&*UINT8, buf),
             Value::Interval(iv) => iv.to_sql(&PgType::INTERVAL, buf),
             Value::Jsonb(js) => js.to_sql(&PgType::JSONB, buf),
+            Value::JsonPath(s) => {
+                // The binary format is a version number followed by the
+                // textual representation.
+                buf.put_u8(1);
+                buf.put_slice(s.as_bytes());
+                Ok(postgres_types::IsNull::No)
+            }
+            Value::TsVector(s) => {
+                let vector = s
+                    .parse::<TsVector>()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                buf.put_slice(&vector.to_binary());
+                Ok(postgres_types::IsNull::No)
+            }
+            Value::TsQuery(s) => {
+                let query = s
+                    .parse::<TsQuery>()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                buf.put_slice(&query.to_binary());
+                Ok(postgres_types::IsNull::No)
+            }
             Value::List(_) => {
                 // A binary encoding for list is tricky. We only get one OID to
                 // describe the type of this list to the client. And we can't
@@ -504,6 +558,18 @@ impl Value {
             Value::Timestamp(ts) => ts.to_sql(&PgType::TIMESTAMP, buf),
             Value::TimestampTz(ts) => ts.to_sql(&PgType::TIMESTAMPTZ, buf),
             Value::Uuid(u) => u.to_sql(&PgType::UUID, buf),
+            Value::Inet(inet) => {
+                encode_inet_binary(buf, inet, false);
+                Ok(postgres_types::IsNull::No)
+            }
+            Value::Cidr(cidr) => {
+                encode_inet_binary(buf, cidr, true);
+                Ok(postgres_types::IsNull::No)
+            }
+            Value::MacAddr(mac_addr) => {
+                buf.put_slice(&mac_addr.0);
+                Ok(postgres_types::IsNull::No)
+            }
             Value::Numeric(a) => a.to_sql(&PgType::NUMERIC, buf),
             Value::MzTimestamp(t) => t.to_string().to_sql(&PgType::TEXT, buf),
             Value::Range(range) => {
@@ -587,6 +653,9 @@ impl Value {
             Type::Interval { .. } => Value::Interval(Interval(strconv::parse_interval(s)?)),
             Type::Json => return Err("input of json types is not implemented".into()),
             Type::Jsonb => Value::Jsonb(Jsonb(strconv::parse_jsonb(s)?)),
+            Type::JsonPath => Value::JsonPath(strconv::parse_jsonpath(s)?.to_string()),
+            Type::TsVector => Value::TsVector(strconv::parse_tsvector(s)?.to_string()),
+            Type::TsQuery => Value::TsQuery(strconv::parse_tsquery(s)?.to_string()),
             Type::List(elem_type) => Value::List(strconv::parse_list(
                 s,
                 matches!(**elem_type, Type::List(..)),
@@ -614,6 +683,9 @@ impl Value {
             Type::Timestamp { .. } => Value::Timestamp(strconv::parse_timestamp(s)?),
             Type::TimestampTz { .. } => Value::TimestampTz(strconv::parse_timestamptz(s)?),
             Type::Uuid => Value::Uuid(Uuid::parse_str(s)?),
+            Type::Inet => Value::Inet(strconv::parse_inet(s)?),
+            Type::Cidr => Value::Cidr(strconv::parse_cidr(s)?),
+            Type::MacAddr => Value::MacAddr(strconv::parse_macaddr(s)?),
             Type::MzTimestamp => Value::MzTimestamp(strconv::parse_mz_timestamp(s)?),
             Type::Range { element_type } => Value::Range(strconv::parse_range(s, |elem_text| {
                 Value::decode_text(element_type, elem_text.as_bytes()).map(Box::new)
@@ -649,6 +721,15 @@ impl Value {
             Type::Interval { .. } => Interval::from_sql(ty.inner(), raw).map(Value::Interval),
             Type::Json => Err("input of json types is not implemented".into()),
             Type::Jsonb => Jsonb::from_sql(ty.inner(), raw).map(Value::Jsonb),
+            Type::JsonPath => match raw.split_first() {
+                Some((1, path)) => {
+                    let path = strconv::parse_jsonpath(str::from_utf8(path)?)?;
+                    Ok(Value::JsonPath(path.to_string()))
+                }
+                _ => Err("unsupported jsonpath version number".into()),
+            },
+            Type::TsVector => Ok(Value::TsVector(TsVector::from_binary(raw)?.to_string())),
+            Type::TsQuery => Ok(Value::TsQuery(TsQuery::from_binary(raw)?.to_string())),
             Type::List(_) => Err("binary decoding of list types is not implemented".into()),
             Type::Map { .. } => Err("binary decoding of map types is not implemented".into()),
             Type::Name => {
@@ -679,6 +760,20 @@ impl Value {
                 )?))
             }
             Type::Uuid => Uuid::from_sql(ty.inner(), raw).map(Value::Uuid),
+            Type::Inet => decode_inet_binary(raw).map(Value::Inet),
+            Type::Cidr => {
+                let cidr = decode_inet_binary(raw)?;
+                if !cidr.is_network() {
+                    return Err("invalid external \"cidr\" value".into());
+                }
+                Ok(Value::Cidr(cidr))
+            }
+            Type::MacAddr => {
+                let bytes = raw
+                    .try_into()
+                    .map_err(|_| "invalid length in external \"macaddr\" value")?;
+                Ok(Value::MacAddr(MacAddr(bytes)))
+            }
             Type::MzTimestamp => {
                 let s = String::from_sql(ty.inner(), raw)?;
                 let t: mz_repr::Timestamp = s.parse()?;
@@ -708,6 +803,40 @@ fn encode_element(buf: &mut BytesMut, elem: Option<&Value>, ty: &Type) -> Result
     Ok(())
 }
 
+// The address family identifiers in PostgreSQL's binary encoding of `inet` and
+// `cidr` values.
+const PGSQL_AF_INET: u8 = 2;
+const PGSQL_AF_INET6: u8 = 3;
+
+fn encode_inet_binary(buf: &mut BytesMut, inet: &Inet, is_cidr: bool) {
+    match inet.addr() {
+        IpAddr::V4(addr) => {
+            buf.put_slice(&[PGSQL_AF_INET, inet.bits(), u8::from(is_cidr), 4]);
+            buf.put_slice(&addr.octets());
+        }
+        IpAddr::V6(addr) => {
+            buf.put_slice(&[PGSQL_AF_INET6, inet.bits(), u8::from(is_cidr), 16]);
+            buf.put_slice(&addr.octets());
+        }
+    }
+}
+
+fn decode_inet_binary(raw: &[u8]) -> Result<Inet, Box<dyn Error + Sync + Send>> {
+    if raw.len() < 4 || usize::from(raw[3]) != raw.len() - 4 {
+        return Err("invalid length in external \"inet\" value".into());
+    }
+    let (header, addr) = raw.split_at(4);
+    let addr = match (header[0], addr.len()) {
+        (PGSQL_AF_INET, 4) => IpAddr::V4(Ipv4Addr::new(addr[0], addr[1], addr[2], addr[3])),
+        (PGSQL_AF_INET6, 16) => {
+            let octets: [u8; 16] = addr.try_into().expect("length checked above");
+            IpAddr::V6(Ipv6Addr::from(octets))
+        }
+        _ => return Err("invalid address family in external \"inet\" value".into()),
+    };
+    Ok(Inet::new(addr, header[1])?)
+}
+
 fn pg_len(what: &str, len: usize) -> Result<i32, io::Error> {
     len.try_into().map_err(|_| {
         io::Error::new(
@@ -1698,6 +1698,8 @@ where
             | ExecuteResponse::CreatedRole
             | ExecuteResponse::CreatedSchema { .. }
             | ExecuteResponse::CreatedSecret { .. }
+            | ExecuteResponse::CreatedSequence
+            | ExecuteResponse::CreatedFunction
             | ExecuteResponse::CreatedSink { .. }
             | ExecuteResponse::CreatedSource { .. }
             | ExecuteResponse::CreatedTable { .. }
@@ -1709,11 +1711,13 @@ where
             | ExecuteResponse::Deleted(..)
             | ExecuteResponse::DiscardedAll
             | ExecuteResponse::DiscardedTemp
+            | ExecuteResponse::DiscardedSequences
             | ExecuteResponse::DroppedObject(_)
             | ExecuteResponse::DroppedOwned
             | ExecuteResponse::GrantedPrivilege
             | ExecuteResponse::GrantedRole
             | ExecuteResponse::Inserted(..)
+            | ExecuteResponse::Merged(..)
             | ExecuteResponse::Prepare
             | ExecuteResponse::Raised
             | ExecuteResponse::ReassignOwned
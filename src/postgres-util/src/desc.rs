@@ -16,7 +16,7 @@ use mz_proto::{RustType, TryFromProtoError};
 use proptest::prelude::{any, Arbitrary};
 use proptest::strategy::{BoxedStrategy, Strategy};
 use serde::{Deserialize, Serialize};
-use tokio_postgres::types::Oid;
+use tokio_postgres::types::{Oid, Type};
 use tracing::warn;
 
 include!(concat!(env!("OUT_DIR"), "/mz_postgres_util.desc.rs"));
@@ -96,6 +96,81 @@ impl PostgresTableDesc {
             )
         }
     }
+
+    /// Like [`PostgresTableDesc::determine_compatibility`], but additionally
+    /// accepts the following additive changes in `other`:
+    /// - New nullable columns.
+    /// - `varchar` columns whose maximum length was increased or removed.
+    ///
+    /// Errors describe the first breaking change that was found.
+    pub fn determine_additive_compatibility(
+        &self,
+        other: &PostgresTableDesc,
+    ) -> Result<(), anyhow::Error> {
+        if self == other {
+            return Ok(());
+        }
+
+        let name = &self.name;
+        if self.oid != other.oid || self.namespace != other.namespace || name != &other.name {
+            bail!(
+                "source table {} with oid {} has been renamed or replaced",
+                name,
+                self.oid
+            );
+        }
+
+        // Table columns cannot change position, so dropped columns must show
+        // up as a mismatch at or before the end of `self.columns`.
+        for (i, column) in self.columns.iter().enumerate() {
+            let Some(other_column) = other.columns.get(i) else {
+                bail!(
+                    "column {} of source table {} was dropped",
+                    column.name,
+                    name
+                );
+            };
+            if column.name != other_column.name || column.col_num != other_column.col_num {
+                bail!(
+                    "column {} of source table {} was dropped or renamed",
+                    column.name,
+                    name
+                );
+            }
+            if column.type_oid != other_column.type_oid
+                || (column.type_mod != other_column.type_mod && !column.is_widened_by(other_column))
+            {
+                bail!(
+                    "column {} of source table {} changed its type",
+                    column.name,
+                    name
+                );
+            }
+            if !column.nullable && other_column.nullable {
+                bail!(
+                    "column {} of source table {} dropped its NOT NULL constraint",
+                    column.name,
+                    name
+                );
+            }
+        }
+
+        for column in &other.columns[self.columns.len()..] {
+            if !column.nullable {
+                bail!(
+                    "new column {} of source table {} is NOT NULL",
+                    column.name,
+                    name
+                );
+            }
+        }
+
+        if let Some(key) = self.keys.difference(&other.keys).next() {
+            bail!("key {} of source table {} was altered", key.name, name);
+        }
+
+        Ok(())
+    }
 }
 
 impl RustType<ProtoPostgresTableDesc> for PostgresTableDesc {
@@ -187,6 +262,17 @@ impl PostgresColumnDesc {
             // - self and other are both not nullable
             && (self.nullable || self.nullable == other.nullable)
     }
+
+    /// Determines if `other` is the same `varchar` column as `self` with an
+    /// increased or removed maximum length.
+    fn is_widened_by(&self, other: &PostgresColumnDesc) -> bool {
+        // `type_mod` is -1 for an unbounded length and otherwise increases
+        // with the maximum length.
+        self.type_oid == Type::VARCHAR.oid()
+            && self.type_oid == other.type_oid
+            && self.type_mod != -1
+            && (other.type_mod == -1 || other.type_mod > self.type_mod)
+    }
 }
 
 impl RustType<ProtoPostgresColumnDesc> for PostgresColumnDesc {
@@ -82,11 +82,13 @@ fn main() {
 
     prost_build::Config::new()
         .btree_map(["."])
+        .extern_path(".mz_proto", "::mz_proto")
         .compile_protos(
             &[
                 "repr/src/antichain.proto",
                 "repr/src/chrono.proto",
                 "repr/src/global_id.proto",
+                "repr/src/refresh_schedule.proto",
                 "repr/src/row.proto",
                 "repr/src/strconv.proto",
                 "repr/src/relation_and_scalar.proto",
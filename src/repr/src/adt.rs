@@ -23,11 +23,14 @@ pub mod date;
 pub mod datetime;
 pub mod interval;
 pub mod jsonb;
+pub mod jsonpath;
 pub mod mz_acl_item;
+pub mod network;
 pub mod numeric;
 pub mod pg_legacy_name;
 pub mod range;
 pub mod regex;
 pub mod system;
+pub mod text_search;
 pub mod timestamp;
 pub mod varchar;
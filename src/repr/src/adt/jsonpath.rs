@@ -0,0 +1,1775 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! SQL/JSON path language.
+//!
+//! This module implements the PostgreSQL `jsonpath` type: a parser for path
+//! expressions like `strict $.events[*] ? (@.kind == "click")`, a canonical
+//! textual representation, and an evaluator over JSON values represented as
+//! [`Datum`]s.
+//!
+//! Values of type `jsonpath` are stored as their canonical text. Parsing the
+//! text again always succeeds and yields the same path.
+//!
+//! ## Modes
+//!
+//! Paths are evaluated in either lax mode (the default) or strict mode. In lax
+//! mode, arrays are automatically unwrapped when an accessor that expects a
+//! non-array is applied to them, non-arrays are automatically wrapped when an
+//! array accessor is applied to them, and structural errors, like a missing
+//! object key, produce an empty result instead of an error. In strict mode,
+//! all of these situations are errors.
+
+use std::fmt;
+use std::str::FromStr;
+
+use dec::{OrderedDecimal, Rounding};
+use mz_ore::cast::CastFrom;
+use mz_ore::stack::{CheckedRecursion, RecursionGuard, RecursionLimitError};
+use regex::{Regex, RegexBuilder};
+
+use crate::adt::numeric::{self, Numeric};
+use crate::{Datum, RowArena};
+
+/// A parsed SQL/JSON path expression.
+#[derive(Debug, Clone)]
+pub struct JsonPath {
+    strict: bool,
+    expr: Expr,
+}
+
+/// An error encountered while evaluating a [`JsonPath`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPathError(String);
+
+impl JsonPathError {
+    fn new<S: Into<String>>(message: S) -> JsonPathError {
+        JsonPathError(message.into())
+    }
+}
+
+impl fmt::Display for JsonPathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for JsonPathError {}
+
+impl JsonPath {
+    /// Reports whether the path is evaluated in strict mode.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Evaluates the path against `target`, returning the sequence of JSON
+    /// values it selects.
+    ///
+    /// `vars` must be a JSON object, whose keys provide the values of the
+    /// variables (`$name`) referenced by the path.
+    pub fn query<'a>(
+        &self,
+        target: Datum<'a>,
+        vars: Datum<'a>,
+        temp_storage: &'a RowArena,
+    ) -> Result<Vec<Datum<'a>>, JsonPathError> {
+        if !matches!(vars, Datum::Map(_)) {
+            return Err(JsonPathError::new("\"vars\" argument is not an object"));
+        }
+        let evaluator = Evaluator {
+            strict: self.strict,
+            root: target,
+            vars,
+            temp_storage,
+        };
+        let scope = Scope {
+            current: target,
+            last: None,
+        };
+        evaluator.eval(&self.expr, scope)
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<JsonPath, String> {
+        let tokens = tokenize(s)?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            filter_depth: 0,
+            subscript_depth: 0,
+            recursion_guard: RecursionGuard::with_limit(1024), // chosen arbitrarily
+        };
+        parser.parse_path()
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.strict {
+            f.write_str("strict ")?;
+        }
+        fmt_expr(f, &self.expr, false)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    /// `$`
+    Root,
+    /// `@`
+    Current,
+    /// `$name`
+    Variable(String),
+    /// `last`
+    Last,
+    Literal(Literal),
+    Accessor(Box<Expr>, Accessor),
+    Arithmetic(ArithmeticOp, Box<Expr>, Box<Expr>),
+    Unary(UnaryOp, Box<Expr>),
+    Comparison(ComparisonOp, Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    IsUnknown(Box<Expr>),
+    Exists(Box<Expr>),
+    LikeRegex {
+        expr: Box<Expr>,
+        pattern: String,
+        flags: String,
+        regex: Regex,
+    },
+    StartsWith(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Reports whether the expression is a predicate, i.e., whether it
+    /// evaluates to a truth value rather than a sequence of JSON values.
+    fn is_predicate(&self) -> bool {
+        matches!(
+            self,
+            Expr::Comparison(..)
+                | Expr::And(..)
+                | Expr::Or(..)
+                | Expr::Not(..)
+                | Expr::IsUnknown(..)
+                | Expr::Exists(..)
+                | Expr::LikeRegex { .. }
+                | Expr::StartsWith(..)
+        )
+    }
+
+    /// The binding strength of the expression's operator, used to decide where
+    /// parentheses are needed when printing.
+    fn priority(&self) -> u8 {
+        match self {
+            Expr::Or(..) => 0,
+            Expr::And(..) => 1,
+            Expr::Comparison(..) | Expr::StartsWith(..) => 2,
+            Expr::Arithmetic(ArithmeticOp::Add | ArithmeticOp::Sub, ..) => 3,
+            Expr::Arithmetic(..) => 4,
+            Expr::Unary(..) => 5,
+            _ => 6,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Literal {
+    Null,
+    Bool(bool),
+    Numeric(Numeric),
+    String(String),
+}
+
+#[derive(Debug, Clone)]
+enum Accessor {
+    /// `.key`
+    Member(String),
+    /// `.*`
+    WildcardMember,
+    /// `[*]`
+    WildcardElement,
+    /// `[subscript, ...]`
+    Element(Vec<Subscript>),
+    /// `.**{first to last}`, where a `last` of `None` means unbounded.
+    AnyPath { first: u32, last: Option<u32> },
+    /// `? (predicate)`
+    Filter(Box<Expr>),
+    /// `.method()`
+    Method(Method),
+}
+
+#[derive(Debug, Clone)]
+struct Subscript {
+    from: Expr,
+    to: Option<Expr>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Method {
+    Type,
+    Size,
+    Double,
+    Abs,
+    Floor,
+    Ceiling,
+}
+
+impl Method {
+    fn name(&self) -> &'static str {
+        match self {
+            Method::Type => "type",
+            Method::Size => "size",
+            Method::Double => "double",
+            Method::Abs => "abs",
+            Method::Floor => "floor",
+            Method::Ceiling => "ceiling",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ArithmeticOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+impl ArithmeticOp {
+    fn symbol(&self) -> &'static str {
+        match self {
+            ArithmeticOp::Add => "+",
+            ArithmeticOp::Sub => "-",
+            ArithmeticOp::Mul => "*",
+            ArithmeticOp::Div => "/",
+            ArithmeticOp::Mod => "%",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum UnaryOp {
+    Plus,
+    Minus,
+}
+
+impl UnaryOp {
+    fn symbol(&self) -> &'static str {
+        match self {
+            UnaryOp::Plus => "+",
+            UnaryOp::Minus => "-",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ComparisonOp {
+    Eq,
+    NotEq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+}
+
+impl ComparisonOp {
+    fn symbol(&self) -> &'static str {
+        match self {
+            ComparisonOp::Eq => "==",
+            ComparisonOp::NotEq => "!=",
+            ComparisonOp::Lt => "<",
+            ComparisonOp::Lte => "<=",
+            ComparisonOp::Gt => ">",
+            ComparisonOp::Gte => ">=",
+        }
+    }
+}
+
+// Printing.
+
+/// Prints an operand of `parent`, parenthesized if it binds less tightly than,
+/// or as tightly as, `parent`.
+fn fmt_operand(f: &mut fmt::Formatter, operand: &Expr, parent: &Expr) -> fmt::Result {
+    fmt_expr(f, operand, operand.priority() <= parent.priority())
+}
+
+fn fmt_binary(
+    f: &mut fmt::Formatter,
+    parent: &Expr,
+    brackets: bool,
+    left: &Expr,
+    op: &str,
+    right: &Expr,
+) -> fmt::Result {
+    if brackets {
+        f.write_str("(")?;
+    }
+    fmt_operand(f, left, parent)?;
+    write!(f, " {} ", op)?;
+    fmt_operand(f, right, parent)?;
+    if brackets {
+        f.write_str(")")?;
+    }
+    Ok(())
+}
+
+fn fmt_expr(f: &mut fmt::Formatter, expr: &Expr, brackets: bool) -> fmt::Result {
+    match expr {
+        Expr::Root => f.write_str("$"),
+        Expr::Current => f.write_str("@"),
+        Expr::Variable(name) => {
+            f.write_str("$")?;
+            fmt_json_string(f, name)
+        }
+        Expr::Last => f.write_str("last"),
+        Expr::Literal(Literal::Null) => f.write_str("null"),
+        Expr::Literal(Literal::Bool(b)) => write!(f, "{}", b),
+        Expr::Literal(Literal::Numeric(n)) => f.write_str(&n.to_standard_notation_string()),
+        Expr::Literal(Literal::String(s)) => fmt_json_string(f, s),
+        Expr::Accessor(base, accessor) => {
+            if base.priority() < 6 || base.is_predicate() {
+                f.write_str("(")?;
+                fmt_expr(f, base, false)?;
+                f.write_str(")")?;
+            } else {
+                fmt_expr(f, base, false)?;
+            }
+            fmt_accessor(f, accessor)
+        }
+        Expr::Arithmetic(op, left, right) => {
+            fmt_binary(f, expr, brackets, left, op.symbol(), right)
+        }
+        Expr::Comparison(op, left, right) => {
+            fmt_binary(f, expr, brackets, left, op.symbol(), right)
+        }
+        Expr::And(left, right) => fmt_binary(f, expr, brackets, left, "&&", right),
+        Expr::Or(left, right) => fmt_binary(f, expr, brackets, left, "||", right),
+        Expr::StartsWith(left, right) => fmt_binary(f, expr, brackets, left, "starts with", right),
+        Expr::Unary(op, operand) => {
+            if brackets {
+                f.write_str("(")?;
+            }
+            f.write_str(op.symbol())?;
+            fmt_operand(f, operand, expr)?;
+            if brackets {
+                f.write_str(")")?;
+            }
+            Ok(())
+        }
+        Expr::Not(operand) => {
+            f.write_str("!(")?;
+            fmt_expr(f, operand, false)?;
+            f.write_str(")")
+        }
+        Expr::IsUnknown(operand) => {
+            f.write_str("(")?;
+            fmt_expr(f, operand, false)?;
+            f.write_str(") is unknown")
+        }
+        Expr::Exists(operand) => {
+            f.write_str("exists (")?;
+            fmt_expr(f, operand, false)?;
+            f.write_str(")")
+        }
+        Expr::LikeRegex {
+            expr: operand,
+            pattern,
+            flags,
+            regex: _,
+        } => {
+            if brackets {
+                f.write_str("(")?;
+            }
+            fmt_operand(f, operand, expr)?;
+            f.write_str(" like_regex ")?;
+            fmt_json_string(f, pattern)?;
+            if !flags.is_empty() {
+                f.write_str(" flag ")?;
+                fmt_json_string(f, flags)?;
+            }
+            if brackets {
+                f.write_str(")")?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn fmt_accessor(f: &mut fmt::Formatter, accessor: &Accessor) -> fmt::Result {
+    match accessor {
+        Accessor::Member(key) => {
+            f.write_str(".")?;
+            fmt_json_string(f, key)
+        }
+        Accessor::WildcardMember => f.write_str(".*"),
+        Accessor::WildcardElement => f.write_str("[*]"),
+        Accessor::Element(subscripts) => {
+            f.write_str("[")?;
+            for (i, subscript) in subscripts.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(",")?;
+                }
+                fmt_expr(f, &subscript.from, false)?;
+                if let Some(to) = &subscript.to {
+                    f.write_str(" to ")?;
+                    fmt_expr(f, to, false)?;
+                }
+            }
+            f.write_str("]")
+        }
+        Accessor::AnyPath { first, last } => {
+            let fmt_level = |level: &Option<u32>| match level {
+                Some(level) => level.to_string(),
+                None => "last".into(),
+            };
+            f.write_str(".**")?;
+            match (first, last) {
+                (0, None) => Ok(()),
+                (first, last) if Some(*first) == *last => write!(f, "{{{}}}", first),
+                (first, last) => write!(f, "{{{} to {}}}", first, fmt_level(last)),
+            }
+        }
+        Accessor::Filter(predicate) => {
+            f.write_str("?(")?;
+            fmt_expr(f, predicate, false)?;
+            f.write_str(")")
+        }
+        Accessor::Method(method) => write!(f, ".{}()", method.name()),
+    }
+}
+
+fn fmt_json_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\u{8}' => f.write_str("\\b")?,
+            '\u{c}' => f.write_str("\\f")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", u32::from(c))?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    f.write_str("\"")
+}
+
+// Lexing.
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Dollar,
+    At,
+    Variable(String),
+    Ident(String),
+    String(String),
+    Number(String),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    LBrace,
+    RBrace,
+    Dot,
+    Comma,
+    Star,
+    Plus,
+    Minus,
+    Slash,
+    Percent,
+    Question,
+    Eq,
+    NotEq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+    And,
+    Or,
+    Not,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Token::Dollar => "$",
+            Token::At => "@",
+            Token::Variable(name) => return write!(f, "${}", name),
+            Token::Ident(s) | Token::String(s) | Token::Number(s) => s.as_str(),
+            Token::LParen => "(",
+            Token::RParen => ")",
+            Token::LBracket => "[",
+            Token::RBracket => "]",
+            Token::LBrace => "{",
+            Token::RBrace => "}",
+            Token::Dot => ".",
+            Token::Comma => ",",
+            Token::Star => "*",
+            Token::Plus => "+",
+            Token::Minus => "-",
+            Token::Slash => "/",
+            Token::Percent => "%",
+            Token::Question => "?",
+            Token::Eq => "==",
+            Token::NotEq => "!=",
+            Token::Lt => "<",
+            Token::Lte => "<=",
+            Token::Gt => ">",
+            Token::Gte => ">=",
+            Token::And => "&&",
+            Token::Or => "||",
+            Token::Not => "!",
+        };
+        f.write_str(s)
+    }
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '$' => match chars.peek() {
+                Some('"') => {
+                    chars.next();
+                    Token::Variable(lex_string(&mut chars)?)
+                }
+                Some(c) if is_ident_char(*c) => {
+                    let mut name = String::new();
+                    while let Some(c) = chars.next_if(|c| is_ident_char(*c)) {
+                        name.push(c);
+                    }
+                    Token::Variable(name)
+                }
+                _ => Token::Dollar,
+            },
+            '"' => Token::String(lex_string(&mut chars)?),
+            c if c.is_ascii_digit() => {
+                let mut number = String::from(c);
+                while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+                    number.push(c);
+                }
+                // A dot is only part of the number if digits follow it, so
+                // that accessors can follow integers, as in `$[0].a`.
+                let mut lookahead = chars.clone();
+                if lookahead.next() == Some('.')
+                    && lookahead.next().map_or(false, |c| c.is_ascii_digit())
+                {
+                    number.push(chars.next().expect("peeked"));
+                    while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+                        number.push(c);
+                    }
+                }
+                if let Some(e) = chars.next_if(|c| *c == 'e' || *c == 'E') {
+                    number.push(e);
+                    if let Some(sign) = chars.next_if(|c| *c == '+' || *c == '-') {
+                        number.push(sign);
+                    }
+                    let mut exponent = false;
+                    while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+                        number.push(c);
+                        exponent = true;
+                    }
+                    if !exponent {
+                        return Err(format!(
+                            "trailing junk after numeric literal at or near \"{}\"",
+                            number
+                        ));
+                    }
+                }
+                if chars.peek().map_or(false, |c| is_ident_start(*c)) {
+                    return Err(format!(
+                        "trailing junk after numeric literal at or near \"{}\"",
+                        number
+                    ));
+                }
+                Token::Number(number)
+            }
+            c if is_ident_start(c) => {
+                let mut ident = String::from(c);
+                while let Some(c) = chars.next_if(|c| is_ident_char(*c)) {
+                    ident.push(c);
+                }
+                Token::Ident(ident)
+            }
+            '@' => Token::At,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            '{' => Token::LBrace,
+            '}' => Token::RBrace,
+            '.' => Token::Dot,
+            ',' => Token::Comma,
+            '*' => Token::Star,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '/' => Token::Slash,
+            '%' => Token::Percent,
+            '?' => Token::Question,
+            '=' if chars.next_if_eq(&'=').is_some() => Token::Eq,
+            '!' if chars.next_if_eq(&'=').is_some() => Token::NotEq,
+            '!' => Token::Not,
+            '<' if chars.next_if_eq(&'=').is_some() => Token::Lte,
+            '<' if chars.next_if_eq(&'>').is_some() => Token::NotEq,
+            '<' => Token::Lt,
+            '>' if chars.next_if_eq(&'=').is_some() => Token::Gte,
+            '>' => Token::Gt,
+            '&' if chars.next_if_eq(&'&').is_some() => Token::And,
+            '|' if chars.next_if_eq(&'|').is_some() => Token::Or,
+            c => return Err(format!("syntax error at or near \"{}\"", c)),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Lexes the remainder of a double-quoted string whose opening quote has
+/// already been consumed.
+fn lex_string<I>(chars: &mut std::iter::Peekable<I>) -> Result<String, String>
+where
+    I: Iterator<Item = char>,
+{
+    let mut s = String::new();
+    loop {
+        match chars.next() {
+            None => return Err("unexpected end of quoted string".into()),
+            Some('"') => return Ok(s),
+            Some('\\') => match chars.next() {
+                Some('b') => s.push('\u{8}'),
+                Some('f') => s.push('\u{c}'),
+                Some('n') => s.push('\n'),
+                Some('r') => s.push('\r'),
+                Some('t') => s.push('\t'),
+                Some('v') => s.push('\u{b}'),
+                Some('u') => {
+                    let mut code = String::new();
+                    for _ in 0..4 {
+                        match chars.next() {
+                            Some(c) if c.is_ascii_hexdigit() => code.push(c),
+                            _ => return Err("invalid Unicode escape sequence".into()),
+                        }
+                    }
+                    let code = u32::from_str_radix(&code, 16).expect("validated hex digits");
+                    match char::from_u32(code) {
+                        Some(c) if c != '\0' => s.push(c),
+                        _ => return Err("invalid Unicode escape value".into()),
+                    }
+                }
+                Some(c) => s.push(c),
+                None => return Err("unexpected end of quoted string".into()),
+            },
+            Some(c) => s.push(c),
+        }
+    }
+}
+
+// Parsing.
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    filter_depth: usize,
+    subscript_depth: usize,
+    recursion_guard: RecursionGuard,
+}
+
+impl CheckedRecursion for Parser {
+    fn recursion_guard(&self) -> &RecursionGuard {
+        &self.recursion_guard
+    }
+}
+
+impl Parser {
+    /// Calls `f` one level of nesting deeper, failing if the input is nested
+    /// too deeply.
+    fn nested<T, F>(&mut self, f: F) -> Result<T, String>
+    where
+        F: FnOnce(&mut Self) -> Result<T, String>,
+    {
+        self.checked_recur_mut(|parser| Ok(f(parser)))
+            .map_err(|e: RecursionLimitError| e.to_string())?
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + n)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn consume(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn consume_keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Ident(ident)) if ident == keyword => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn syntax_error(&self) -> String {
+        match self.peek() {
+            Some(token) => format!("syntax error at or near \"{}\" of jsonpath input", token),
+            None => "syntax error at end of jsonpath input".into(),
+        }
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<(), String> {
+        if self.consume(token) {
+            Ok(())
+        } else {
+            Err(self.syntax_error())
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), String> {
+        if self.consume_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.syntax_error())
+        }
+    }
+
+    fn expect_predicate(&self, expr: Expr, start: usize) -> Result<Expr, String> {
+        if expr.is_predicate() {
+            Ok(expr)
+        } else {
+            match self.tokens.get(start) {
+                Some(token) => Err(format!(
+                    "syntax error at or near \"{}\" of jsonpath input",
+                    token
+                )),
+                None => Err("syntax error at end of jsonpath input".into()),
+            }
+        }
+    }
+
+    fn parse_path(&mut self) -> Result<JsonPath, String> {
+        let strict = if self.consume_keyword("strict") {
+            true
+        } else {
+            self.consume_keyword("lax");
+            false
+        };
+        let expr = self.parse_or()?;
+        if self.peek().is_some() {
+            return Err(self.syntax_error());
+        }
+        Ok(JsonPath { strict, expr })
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        // Parenthesized expressions, `exists` and filters all nest through
+        // here.
+        self.nested(|parser| {
+            let start = parser.pos;
+            let mut expr = parser.parse_and()?;
+            while parser.consume(&Token::Or) {
+                expr = parser.expect_predicate(expr, start)?;
+                let right_start = parser.pos;
+                let right = parser.parse_and()?;
+                let right = parser.expect_predicate(right, right_start)?;
+                expr = Expr::Or(Box::new(expr), Box::new(right));
+            }
+            Ok(expr)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let start = self.pos;
+        let mut expr = self.parse_not()?;
+        while self.consume(&Token::And) {
+            expr = self.expect_predicate(expr, start)?;
+            let right_start = self.pos;
+            let right = self.parse_not()?;
+            let right = self.expect_predicate(right, right_start)?;
+            expr = Expr::And(Box::new(expr), Box::new(right));
+        }
+        Ok(expr)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, String> {
+        if self.consume(&Token::Not) {
+            let start = self.pos;
+            let operand = self.nested(|parser| parser.parse_not())?;
+            let operand = self.expect_predicate(operand, start)?;
+            Ok(Expr::Not(Box::new(operand)))
+        } else {
+            self.parse_predicate()
+        }
+    }
+
+    fn parse_predicate(&mut self) -> Result<Expr, String> {
+        let start = self.pos;
+        let expr = if matches!(self.peek(), Some(Token::Ident(ident)) if ident == "exists")
+            && self.peek_nth(1) == Some(&Token::LParen)
+        {
+            self.pos += 2;
+            let operand = self.parse_or()?;
+            self.expect(&Token::RParen)?;
+            Expr::Exists(Box::new(operand))
+        } else {
+            let left = self.parse_additive()?;
+            let op = match self.peek() {
+                Some(Token::Eq) => Some(ComparisonOp::Eq),
+                Some(Token::NotEq) => Some(ComparisonOp::NotEq),
+                Some(Token::Lt) => Some(ComparisonOp::Lt),
+                Some(Token::Lte) => Some(ComparisonOp::Lte),
+                Some(Token::Gt) => Some(ComparisonOp::Gt),
+                Some(Token::Gte) => Some(ComparisonOp::Gte),
+                _ => None,
+            };
+            if let Some(op) = op {
+                self.pos += 1;
+                let right = self.parse_additive()?;
+                Expr::Comparison(op, Box::new(left), Box::new(right))
+            } else if self.consume_keyword("like_regex") {
+                let Some(Token::String(pattern)) = self.next() else {
+                    self.pos -= 1;
+                    return Err(self.syntax_error());
+                };
+                let flags = if self.consume_keyword("flag") {
+                    let Some(Token::String(flags)) = self.next() else {
+                        self.pos -= 1;
+                        return Err(self.syntax_error());
+                    };
+                    flags
+                } else {
+                    String::new()
+                };
+                let regex = build_regex(&pattern, &flags)?;
+                Expr::LikeRegex {
+                    expr: Box::new(left),
+                    pattern,
+                    flags,
+                    regex,
+                }
+            } else if self.consume_keyword("starts") {
+                self.expect_keyword("with")?;
+                let right = match self.next() {
+                    Some(Token::String(s)) => Expr::Literal(Literal::String(s)),
+                    Some(Token::Variable(name)) => Expr::Variable(name),
+                    _ => {
+                        self.pos -= 1;
+                        return Err(self.syntax_error());
+                    }
+                };
+                Expr::StartsWith(Box::new(left), Box::new(right))
+            } else {
+                left
+            }
+        };
+        if self.consume_keyword("is") {
+            self.expect_keyword("unknown")?;
+            let expr = self.expect_predicate(expr, start)?;
+            return Ok(Expr::IsUnknown(Box::new(expr)));
+        }
+        Ok(expr)
+    }
+
+    fn parse_additive(&mut self) -> Result<Expr, String> {
+        let mut expr = self.parse_multiplicative()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Plus) => ArithmeticOp::Add,
+                Some(Token::Minus) => ArithmeticOp::Sub,
+                _ => return Ok(expr),
+            };
+            self.pos += 1;
+            let right = self.parse_multiplicative()?;
+            expr = Expr::Arithmetic(op, Box::new(expr), Box::new(right));
+        }
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Expr, String> {
+        let mut expr = self.parse_unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Star) => ArithmeticOp::Mul,
+                Some(Token::Slash) => ArithmeticOp::Div,
+                Some(Token::Percent) => ArithmeticOp::Mod,
+                _ => return Ok(expr),
+            };
+            self.pos += 1;
+            let right = self.parse_unary()?;
+            expr = Expr::Arithmetic(op, Box::new(expr), Box::new(right));
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        let op = match self.peek() {
+            Some(Token::Plus) => UnaryOp::Plus,
+            Some(Token::Minus) => UnaryOp::Minus,
+            _ => return self.parse_accessor_expr(),
+        };
+        self.pos += 1;
+        match (op, self.nested(|parser| parser.parse_unary())?) {
+            // Signs are folded into numeric literals.
+            (UnaryOp::Plus, expr @ Expr::Literal(Literal::Numeric(_))) => Ok(expr),
+            (UnaryOp::Minus, Expr::Literal(Literal::Numeric(mut n))) => {
+                numeric::cx_datum().neg(&mut n);
+                Ok(Expr::Literal(Literal::Numeric(n)))
+            }
+            (op, expr) => Ok(Expr::Unary(op, Box::new(expr))),
+        }
+    }
+
+    fn parse_accessor_expr(&mut self) -> Result<Expr, String> {
+        let mut expr = self.parse_primary()?;
+        loop {
+            let accessor = match self.peek() {
+                Some(Token::Dot) => {
+                    self.pos += 1;
+                    self.parse_dot_accessor()?
+                }
+                Some(Token::LBracket) => {
+                    self.pos += 1;
+                    self.parse_subscripts()?
+                }
+                Some(Token::Question) => {
+                    self.pos += 1;
+                    self.expect(&Token::LParen)?;
+                    self.filter_depth += 1;
+                    let start = self.pos;
+                    let predicate = self.parse_or()?;
+                    let predicate = self.expect_predicate(predicate, start)?;
+                    self.filter_depth -= 1;
+                    self.expect(&Token::RParen)?;
+                    Accessor::Filter(Box::new(predicate))
+                }
+                _ => return Ok(expr),
+            };
+            expr = Expr::Accessor(Box::new(expr), accessor);
+        }
+    }
+
+    fn parse_dot_accessor(&mut self) -> Result<Accessor, String> {
+        match self.next() {
+            Some(Token::Star) if self.consume(&Token::Star) => {
+                if !self.consume(&Token::LBrace) {
+                    return Ok(Accessor::AnyPath {
+                        first: 0,
+                        last: None,
+                    });
+                }
+                let Some(first) = self.parse_any_path_level()? else {
+                    return Err("jsonpath .** accessor must start at a numeric level".into());
+                };
+                let last = if self.consume_keyword("to") {
+                    self.parse_any_path_level()?
+                } else {
+                    Some(first)
+                };
+                self.expect(&Token::RBrace)?;
+                Ok(Accessor::AnyPath { first, last })
+            }
+            Some(Token::Star) => Ok(Accessor::WildcardMember),
+            Some(Token::String(key)) => Ok(Accessor::Member(key)),
+            Some(Token::Ident(name)) if self.peek() == Some(&Token::LParen) => {
+                let method = match name.as_str() {
+                    "type" => Method::Type,
+                    "size" => Method::Size,
+                    "double" => Method::Double,
+                    "abs" => Method::Abs,
+                    "floor" => Method::Floor,
+                    "ceiling" => Method::Ceiling,
+                    _ => return Err(format!("unsupported jsonpath item method .{}()", name)),
+                };
+                self.pos += 1;
+                self.expect(&Token::RParen)?;
+                Ok(Accessor::Method(method))
+            }
+            Some(Token::Ident(key)) => Ok(Accessor::Member(key)),
+            _ => {
+                self.pos -= 1;
+                Err(self.syntax_error())
+            }
+        }
+    }
+
+    /// Parses a level of a `.**{...}` accessor, where `last` means unbounded.
+    fn parse_any_path_level(&mut self) -> Result<Option<u32>, String> {
+        match self.next() {
+            Some(Token::Ident(ident)) if ident == "last" => Ok(None),
+            Some(Token::Number(n)) => match n.parse() {
+                Ok(level) => Ok(Some(level)),
+                Err(_) => Err(format!("invalid jsonpath item level \"{}\"", n)),
+            },
+            _ => {
+                self.pos -= 1;
+                Err(self.syntax_error())
+            }
+        }
+    }
+
+    fn parse_subscripts(&mut self) -> Result<Accessor, String> {
+        if self.consume(&Token::Star) {
+            self.expect(&Token::RBracket)?;
+            return Ok(Accessor::WildcardElement);
+        }
+        self.subscript_depth += 1;
+        let mut subscripts = vec![];
+        loop {
+            let from = self.parse_additive()?;
+            let to = if self.consume_keyword("to") {
+                Some(self.parse_additive()?)
+            } else {
+                None
+            };
+            subscripts.push(Subscript { from, to });
+            if !self.consume(&Token::Comma) {
+                break;
+            }
+        }
+        self.subscript_depth -= 1;
+        self.expect(&Token::RBracket)?;
+        Ok(Accessor::Element(subscripts))
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, String> {
+        let expr = match self.next() {
+            Some(Token::Dollar) => Expr::Root,
+            Some(Token::At) => {
+                if self.filter_depth == 0 {
+                    return Err("@ is not allowed in root expressions".into());
+                }
+                Expr::Current
+            }
+            Some(Token::Variable(name)) => Expr::Variable(name),
+            Some(Token::String(s)) => Expr::Literal(Literal::String(s)),
+            Some(Token::Number(n)) => {
+                let mut cx = numeric::cx_datum();
+                let mut n = cx
+                    .parse(n.as_str())
+                    .map_err(|_| format!("invalid numeric literal \"{}\"", n))?;
+                if cx.status().overflow() || numeric::munge_numeric(&mut n).is_err() {
+                    return Err("numeric literal is out of range".into());
+                }
+                Expr::Literal(Literal::Numeric(n))
+            }
+            Some(Token::Ident(ident)) => match ident.as_str() {
+                "null" => Expr::Literal(Literal::Null),
+                "true" => Expr::Literal(Literal::Bool(true)),
+                "false" => Expr::Literal(Literal::Bool(false)),
+                "last" => {
+                    if self.subscript_depth == 0 {
+                        return Err("LAST is allowed only in array subscripts".into());
+                    }
+                    Expr::Last
+                }
+                _ => {
+                    self.pos -= 1;
+                    return Err(self.syntax_error());
+                }
+            },
+            Some(Token::LParen) => {
+                let expr = self.parse_or()?;
+                self.expect(&Token::RParen)?;
+                expr
+            }
+            _ => {
+                self.pos -= 1;
+                return Err(self.syntax_error());
+            }
+        };
+        Ok(expr)
+    }
+}
+
+/// Builds the regular expression for a `like_regex` predicate, following the
+/// XQuery flags that PostgreSQL supports.
+fn build_regex(pattern: &str, flags: &str) -> Result<Regex, String> {
+    let mut builder_pattern = pattern.to_string();
+    let mut case_insensitive = false;
+    let mut dot_matches_new_line = false;
+    let mut multi_line = false;
+    let mut ignore_whitespace = false;
+    for flag in flags.chars() {
+        match flag {
+            'i' => case_insensitive = true,
+            's' => dot_matches_new_line = true,
+            'm' => multi_line = true,
+            'x' => ignore_whitespace = true,
+            'q' => builder_pattern = regex::escape(pattern),
+            _ => {
+                return Err(format!(
+                    "Unrecognized flag character \"{}\" in LIKE_REGEX predicate.",
+                    flag
+                ))
+            }
+        }
+    }
+    RegexBuilder::new(&builder_pattern)
+        .case_insensitive(case_insensitive)
+        .dot_matches_new_line(dot_matches_new_line)
+        .multi_line(multi_line)
+        .ignore_whitespace(ignore_whitespace)
+        .build()
+        .map_err(|e| format!("invalid regular expression: {}", e))
+}
+
+// Evaluation.
+
+/// The result of evaluating a predicate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Truth {
+    True,
+    False,
+    Unknown,
+}
+
+impl Truth {
+    fn into_datum<'a>(self) -> Datum<'a> {
+        match self {
+            Truth::True => Datum::True,
+            Truth::False => Datum::False,
+            Truth::Unknown => Datum::JsonNull,
+        }
+    }
+}
+
+/// The values of `@` and `last` at some point during evaluation.
+#[derive(Debug, Clone, Copy)]
+struct Scope<'a> {
+    current: Datum<'a>,
+    last: Option<usize>,
+}
+
+struct Evaluator<'a> {
+    strict: bool,
+    root: Datum<'a>,
+    vars: Datum<'a>,
+    temp_storage: &'a RowArena,
+}
+
+impl<'a> Evaluator<'a> {
+    fn structural_error(&self, message: String) -> Result<(), JsonPathError> {
+        if self.strict {
+            Err(JsonPathError(message))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn eval(&self, expr: &Expr, scope: Scope<'a>) -> Result<Vec<Datum<'a>>, JsonPathError> {
+        match expr {
+            Expr::Root => Ok(vec![self.root]),
+            Expr::Current => Ok(vec![scope.current]),
+            Expr::Variable(name) => {
+                let Datum::Map(vars) = self.vars else {
+                    unreachable!("vars validated to be an object")
+                };
+                match vars.iter().find(|(key, _)| *key == name.as_str()) {
+                    Some((_, value)) => Ok(vec![value]),
+                    None => Err(JsonPathError(format!(
+                        "could not find jsonpath variable \"{}\"",
+                        name
+                    ))),
+                }
+            }
+            Expr::Last => match scope.last {
+                Some(last) => Ok(vec![Datum::from(Numeric::from(u64::cast_from(last)))]),
+                None => Err(JsonPathError::new(
+                    "evaluating jsonpath LAST outside of array subscript",
+                )),
+            },
+            Expr::Literal(literal) => Ok(vec![match literal {
+                Literal::Null => Datum::JsonNull,
+                Literal::Bool(b) => Datum::from(*b),
+                Literal::Numeric(n) => Datum::from(*n),
+                Literal::String(s) => Datum::String(self.temp_storage.push_string(s.clone())),
+            }]),
+            Expr::Accessor(base, accessor) => {
+                let mut out = vec![];
+                for item in self.eval(base, scope)? {
+                    self.apply_accessor(accessor, item, scope, true, &mut out)?;
+                }
+                Ok(out)
+            }
+            Expr::Arithmetic(op, left, right) => {
+                let left = self.eval_numeric_operand(left, scope, "left", op)?;
+                let right = self.eval_numeric_operand(right, scope, "right", op)?;
+                Ok(vec![Datum::from(eval_arithmetic(*op, left, right)?)])
+            }
+            Expr::Unary(op, operand) => {
+                let items = self.unwrap_arrays(self.eval(operand, scope)?);
+                items
+                    .into_iter()
+                    .map(|item| match (op, item) {
+                        (UnaryOp::Plus, Datum::Numeric(_)) => Ok(item),
+                        (UnaryOp::Minus, Datum::Numeric(mut n)) => {
+                            numeric::cx_datum().neg(&mut n.0);
+                            Ok(Datum::Numeric(n))
+                        }
+                        _ => Err(JsonPathError(format!(
+                            "operand of unary jsonpath operator {} is not a numeric value",
+                            op.symbol()
+                        ))),
+                    })
+                    .collect()
+            }
+            _ => Ok(vec![self.eval_predicate(expr, scope).into_datum()]),
+        }
+    }
+
+    /// Evaluates an operand of an arithmetic operator, which must be a single
+    /// numeric value.
+    fn eval_numeric_operand(
+        &self,
+        expr: &Expr,
+        scope: Scope<'a>,
+        side: &str,
+        op: &ArithmeticOp,
+    ) -> Result<Numeric, JsonPathError> {
+        match self.unwrap_arrays(self.eval(expr, scope)?).as_slice() {
+            [Datum::Numeric(n)] => Ok(n.0),
+            _ => Err(JsonPathError(format!(
+                "{} operand of jsonpath operator {} is not a single numeric value",
+                side,
+                op.symbol()
+            ))),
+        }
+    }
+
+    /// In lax mode, replaces the arrays in `items` with their elements.
+    fn unwrap_arrays(&self, items: Vec<Datum<'a>>) -> Vec<Datum<'a>> {
+        if self.strict {
+            return items;
+        }
+        let mut out = vec![];
+        for item in items {
+            match item {
+                Datum::List(list) => out.extend(list.iter()),
+                item => out.push(item),
+            }
+        }
+        out
+    }
+
+    /// Applies `accessor` to `item`, pushing the selected values to `out`.
+    ///
+    /// If `unwrap` is set, lax mode applies accessors that expect a non-array
+    /// to each of the elements of an array `item`.
+    fn apply_accessor(
+        &self,
+        accessor: &Accessor,
+        item: Datum<'a>,
+        scope: Scope<'a>,
+        unwrap: bool,
+        out: &mut Vec<Datum<'a>>,
+    ) -> Result<(), JsonPathError> {
+        let unwraps = matches!(
+            accessor,
+            Accessor::Member(_)
+                | Accessor::WildcardMember
+                | Accessor::Filter(_)
+                | Accessor::Method(Method::Double | Method::Abs | Method::Floor | Method::Ceiling)
+        );
+        if let (Datum::List(list), true, true, false) = (item, unwrap, unwraps, self.strict) {
+            for elem in list.iter() {
+                self.apply_accessor(accessor, elem, scope, false, out)?;
+            }
+            return Ok(());
+        }
+        match accessor {
+            Accessor::Member(key) => match item {
+                Datum::Map(map) => match map.iter().find(|(k, _)| *k == key.as_str()) {
+                    Some((_, value)) => out.push(value),
+                    None => self.structural_error(format!(
+                        "JSON object does not contain key \"{}\"",
+                        key
+                    ))?,
+                },
+                _ => self.structural_error(
+                    "jsonpath member accessor can only be applied to an object".into(),
+                )?,
+            },
+            Accessor::WildcardMember => match item {
+                Datum::Map(map) => out.extend(map.iter().map(|(_, value)| value)),
+                _ => self.structural_error(
+                    "jsonpath wildcard member accessor can only be applied to an object".into(),
+                )?,
+            },
+            Accessor::WildcardElement => match item {
+                Datum::List(list) => out.extend(list.iter()),
+                _ if self.strict => self.structural_error(
+                    "jsonpath wildcard array accessor can only be applied to an array".into(),
+                )?,
+                _ => out.push(item),
+            },
+            Accessor::Element(subscripts) => {
+                // Lax mode treats non-arrays as single-element arrays.
+                let elems: Vec<_> = match item {
+                    Datum::List(list) => list.iter().collect(),
+                    _ if self.strict => {
+                        return self.structural_error(
+                            "jsonpath array accessor can only be applied to an array".into(),
+                        )
+                    }
+                    _ => vec![item],
+                };
+                let scope = Scope {
+                    current: scope.current,
+                    last: elems.len().checked_sub(1),
+                };
+                for subscript in subscripts {
+                    let from = self.eval_subscript(&subscript.from, scope)?;
+                    let to = match &subscript.to {
+                        Some(to) => self.eval_subscript(to, scope)?,
+                        None => from,
+                    };
+                    let len = i64::try_from(elems.len()).expect("array length fits in i64");
+                    if from < 0 || from > to || to >= len {
+                        self.structural_error("jsonpath array subscript is out of bounds".into())?;
+                    }
+                    let from = from.max(0);
+                    let to = to.min(len - 1);
+                    for i in from..=to {
+                        out.push(elems[usize::try_from(i).expect("non-negative index")]);
+                    }
+                }
+            }
+            Accessor::AnyPath { first, last } => {
+                self.collect_any_path(item, 0, *first, *last, out);
+            }
+            Accessor::Filter(predicate) => {
+                let scope = Scope {
+                    current: item,
+                    last: scope.last,
+                };
+                if self.eval_predicate(predicate, scope) == Truth::True {
+                    out.push(item);
+                }
+            }
+            Accessor::Method(method) => self.apply_method(*method, item, out)?,
+        }
+        Ok(())
+    }
+
+    fn eval_subscript(&self, expr: &Expr, scope: Scope<'a>) -> Result<i64, JsonPathError> {
+        let n = match self.eval(expr, scope)?.as_slice() {
+            [Datum::Numeric(n)] => n.0,
+            _ => {
+                return Err(JsonPathError::new(
+                    "jsonpath array subscript is not a single numeric value",
+                ))
+            }
+        };
+        let mut cx = numeric::cx_datum();
+        let mut n = n;
+        cx.set_rounding(Rounding::Down);
+        cx.round(&mut n);
+        cx.clear_status();
+        cx.try_into_i32(n)
+            .map(i64::from)
+            .map_err(|_| JsonPathError::new("jsonpath array subscript is out of integer range"))
+    }
+
+    fn collect_any_path(
+        &self,
+        item: Datum<'a>,
+        level: u32,
+        first: u32,
+        last: Option<u32>,
+        out: &mut Vec<Datum<'a>>,
+    ) {
+        if level >= first && last.map_or(true, |last| level <= last) {
+            out.push(item);
+        }
+        if last.map_or(true, |last| level < last) {
+            match item {
+                Datum::List(list) => {
+                    for elem in list.iter() {
+                        self.collect_any_path(elem, level + 1, first, last, out);
+                    }
+                }
+                Datum::Map(map) => {
+                    for (_, value) in map.iter() {
+                        self.collect_any_path(value, level + 1, first, last, out);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn apply_method(
+        &self,
+        method: Method,
+        item: Datum<'a>,
+        out: &mut Vec<Datum<'a>>,
+    ) -> Result<(), JsonPathError> {
+        let value = match (method, item) {
+            (Method::Type, item) => Datum::String(match item {
+                Datum::Map(_) => "object",
+                Datum::List(_) => "array",
+                Datum::String(_) => "string",
+                Datum::Numeric(_) => "number",
+                Datum::True | Datum::False => "boolean",
+                _ => "null",
+            }),
+            (Method::Size, Datum::List(list)) => {
+                Datum::from(Numeric::from(u64::cast_from(list.iter().count())))
+            }
+            (Method::Size, _) if !self.strict => Datum::from(Numeric::from(1)),
+            (Method::Size, _) => {
+                return Err(JsonPathError::new(
+                    "jsonpath item method .size() can only be applied to an array",
+                ))
+            }
+            (Method::Double, Datum::Numeric(_)) => item,
+            (Method::Double, Datum::String(s)) => {
+                let invalid = || {
+                    JsonPathError::new(
+                        "string argument of jsonpath item method .double() is not a valid \
+                         representation of a double precision number",
+                    )
+                };
+                let f: f64 = s.trim().parse().map_err(|_| invalid())?;
+                if !f.is_finite() {
+                    return Err(JsonPathError::new(
+                        "NaN or Infinity is not allowed for jsonpath item method .double()",
+                    ));
+                }
+                let mut cx = numeric::cx_datum();
+                let n = cx.parse(f.to_string().as_str()).map_err(|_| invalid())?;
+                Datum::from(n)
+            }
+            (Method::Double, _) => {
+                return Err(JsonPathError::new(
+                    "jsonpath item method .double() can only be applied to a string or numeric \
+                     value",
+                ))
+            }
+            (Method::Abs | Method::Floor | Method::Ceiling, Datum::Numeric(OrderedDecimal(n))) => {
+                let mut n = n;
+                let mut cx = numeric::cx_datum();
+                match method {
+                    Method::Abs => cx.abs(&mut n),
+                    Method::Floor | Method::Ceiling if n.exponent() < 0 => {
+                        cx.set_rounding(match method {
+                            Method::Floor => Rounding::Floor,
+                            _ => Rounding::Ceiling,
+                        });
+                        cx.round(&mut n);
+                        numeric::munge_numeric(&mut n).expect("rounding cannot overflow");
+                    }
+                    _ => {}
+                }
+                Datum::from(n)
+            }
+            (Method::Abs | Method::Floor | Method::Ceiling, _) => {
+                return Err(JsonPathError(format!(
+                    "jsonpath item method .{}() can only be applied to a numeric value",
+                    method.name()
+                )))
+            }
+        };
+        out.push(value);
+        Ok(())
+    }
+
+    /// Evaluates a predicate. Errors while evaluating the operands of the
+    /// predicate make its result unknown.
+    fn eval_predicate(&self, expr: &Expr, scope: Scope<'a>) -> Truth {
+        match expr {
+            Expr::And(left, right) => match self.eval_predicate(left, scope) {
+                Truth::False => Truth::False,
+                l => match (l, self.eval_predicate(right, scope)) {
+                    (_, Truth::False) => Truth::False,
+                    (Truth::True, Truth::True) => Truth::True,
+                    _ => Truth::Unknown,
+                },
+            },
+            Expr::Or(left, right) => match self.eval_predicate(left, scope) {
+                Truth::True => Truth::True,
+                l => match (l, self.eval_predicate(right, scope)) {
+                    (_, Truth::True) => Truth::True,
+                    (Truth::False, Truth::False) => Truth::False,
+                    _ => Truth::Unknown,
+                },
+            },
+            Expr::Not(operand) => match self.eval_predicate(operand, scope) {
+                Truth::True => Truth::False,
+                Truth::False => Truth::True,
+                Truth::Unknown => Truth::Unknown,
+            },
+            Expr::IsUnknown(operand) => match self.eval_predicate(operand, scope) {
+                Truth::Unknown => Truth::True,
+                _ => Truth::False,
+            },
+            Expr::Exists(operand) => match self.eval(operand, scope) {
+                Ok(items) if items.is_empty() => Truth::False,
+                Ok(_) => Truth::True,
+                Err(_) => Truth::Unknown,
+            },
+            Expr::Comparison(op, left, right) => {
+                let (left, right) = match (self.eval(left, scope), self.eval(right, scope)) {
+                    (Ok(left), Ok(right)) => (self.unwrap_arrays(left), self.unwrap_arrays(right)),
+                    _ => return Truth::Unknown,
+                };
+                self.exists_pair(&left, &right, |l, r| compare(*op, l, r))
+            }
+            Expr::LikeRegex {
+                expr: operand,
+                regex,
+                ..
+            } => {
+                let Ok(items) = self.eval(operand, scope) else {
+                    return Truth::Unknown;
+                };
+                let items = self.unwrap_arrays(items);
+                self.exists_pair(&items, &[Datum::Null], |item, _| match item {
+                    Datum::String(s) if regex.is_match(s) => Truth::True,
+                    Datum::String(_) => Truth::False,
+                    _ => Truth::Unknown,
+                })
+            }
+            Expr::StartsWith(left, right) => {
+                let (left, right) = match (self.eval(left, scope), self.eval(right, scope)) {
+                    (Ok(left), Ok(right)) => (self.unwrap_arrays(left), right),
+                    _ => return Truth::Unknown,
+                };
+                let [Datum::String(prefix)] = right.as_slice() else {
+                    return Truth::Unknown;
+                };
+                self.exists_pair(&left, &[Datum::Null], |item, _| match item {
+                    Datum::String(s) if s.starts_with(prefix) => Truth::True,
+                    Datum::String(_) => Truth::False,
+                    _ => Truth::Unknown,
+                })
+            }
+            _ => unreachable!("parser only allows predicates in predicate position"),
+        }
+    }
+
+    /// Evaluates `f` on pairs of items from `left` and `right`, and reports
+    /// whether it is true for any of them.
+    ///
+    /// Lax mode returns true as soon as some pair satisfies `f`, while strict
+    /// mode returns unknown if `f` is unknown for any pair.
+    fn exists_pair<F>(&self, left: &[Datum<'a>], right: &[Datum<'a>], f: F) -> Truth
+    where
+        F: Fn(&Datum<'a>, &Datum<'a>) -> Truth,
+    {
+        let mut found = false;
+        let mut unknown = false;
+        for l in left {
+            for r in right {
+                match f(l, r) {
+                    Truth::True if !self.strict => return Truth::True,
+                    Truth::True => found = true,
+                    Truth::Unknown if self.strict => return Truth::Unknown,
+                    Truth::Unknown => unknown = true,
+                    Truth::False => {}
+                }
+            }
+        }
+        if found {
+            Truth::True
+        } else if unknown {
+            Truth::Unknown
+        } else {
+            Truth::False
+        }
+    }
+}
+
+fn compare(op: ComparisonOp, left: &Datum, right: &Datum) -> Truth {
+    let ordering = match (left, right) {
+        (Datum::JsonNull, Datum::JsonNull) => std::cmp::Ordering::Equal,
+        // Nulls are not equal to, and not ordered with respect to, anything
+        // else.
+        (Datum::JsonNull, _) | (_, Datum::JsonNull) => {
+            return match op {
+                ComparisonOp::NotEq => Truth::True,
+                _ => Truth::False,
+            };
+        }
+        (Datum::True | Datum::False, Datum::True | Datum::False) => left.cmp(right),
+        (Datum::Numeric(l), Datum::Numeric(r)) => l.cmp(r),
+        (Datum::String(l), Datum::String(r)) => l.cmp(r),
+        // Arrays, objects and values of different types are not comparable.
+        _ => return Truth::Unknown,
+    };
+    let result = match op {
+        ComparisonOp::Eq => ordering.is_eq(),
+        ComparisonOp::NotEq => ordering.is_ne(),
+        ComparisonOp::Lt => ordering.is_lt(),
+        ComparisonOp::Lte => ordering.is_le(),
+        ComparisonOp::Gt => ordering.is_gt(),
+        ComparisonOp::Gte => ordering.is_ge(),
+    };
+    if result {
+        Truth::True
+    } else {
+        Truth::False
+    }
+}
+
+fn eval_arithmetic(
+    op: ArithmeticOp,
+    mut left: Numeric,
+    right: Numeric,
+) -> Result<Numeric, JsonPathError> {
+    let mut cx = numeric::cx_datum();
+    match op {
+        ArithmeticOp::Add => cx.add(&mut left, &right),
+        ArithmeticOp::Sub => cx.sub(&mut left, &right),
+        ArithmeticOp::Mul => cx.mul(&mut left, &right),
+        ArithmeticOp::Div | ArithmeticOp::Mod if right.is_zero() => {
+            return Err(JsonPathError::new("division by zero"))
+        }
+        ArithmeticOp::Div => cx.div(&mut left, &right),
+        ArithmeticOp::Mod => cx.rem(&mut left, &right),
+    }
+    if cx.status().overflow() || numeric::munge_numeric(&mut left).is_err() {
+        return Err(JsonPathError::new("value out of range: overflow"));
+    }
+    Ok(left)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adt::jsonb::Jsonb;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_display() {
+        for (input, expected) in [
+            ("$", "$"),
+            ("lax $.a", "$.\"a\""),
+            ("strict $.a.\"b c\"[*]", "strict $.\"a\".\"b c\"[*]"),
+            ("$[0, 2 to last]", "$[0,2 to last]"),
+            ("$.**{1 to last}.x", "$.**{1 to last}.\"x\""),
+            (
+                "$ ? (@.a > 1 && @.b == \"x\")",
+                "$?(@.\"a\" > 1 && @.\"b\" == \"x\")",
+            ),
+            ("1 + 2 * 3", "1 + 2 * 3"),
+            ("(1 + 2) * 3", "(1 + 2) * 3"),
+            ("-$.a", "-$.\"a\""),
+            ("- 1", "-1"),
+            ("$.a.size()", "$.\"a\".size()"),
+            (
+                "$ ? (@ like_regex \"^a\" flag \"i\")",
+                "$?(@ like_regex \"^a\" flag \"i\")",
+            ),
+            ("$ ? (@ starts with $x)", "$?(@ starts with $\"x\")"),
+            (
+                "$ ? (!(@ > 1) || (@ < 0) is unknown)",
+                "$?(!(@ > 1) || (@ < 0) is unknown)",
+            ),
+            ("exists($.a)", "exists ($.\"a\")"),
+        ] {
+            let path: JsonPath = input.parse().unwrap();
+            assert_eq!(path.to_string(), expected, "display of {input}");
+            let reparsed: JsonPath = expected.parse().unwrap();
+            assert_eq!(reparsed.to_string(), expected, "round trip of {input}");
+        }
+
+        for input in [
+            "",
+            "$.",
+            "$[",
+            "@",
+            "last",
+            "$ ? (@.a)",
+            "$ ? (@ like_regex \"a\" flag \"z\")",
+            "$.a.nope()",
+            "1a",
+        ] {
+            assert!(input.parse::<JsonPath>().is_err(), "parsing {input}");
+        }
+
+        // Deeply nested inputs are rejected rather than overflowing the stack.
+        for input in [
+            format!("{}$", "-".repeat(100_000)),
+            format!("{}$", "(".repeat(100_000)),
+            format!("$ ? ({}(@ > 1))", "!".repeat(100_000)),
+        ] {
+            let err = input.parse::<JsonPath>().unwrap_err();
+            assert!(err.contains("recursion limit"), "{err}");
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_query() {
+        let target: Jsonb = r#"{"a": [1, 2, {"b": 3}], "c": "xyz", "d": null}"#.parse().unwrap();
+        let vars: Jsonb = r#"{"min": 2}"#.parse().unwrap();
+        let temp_storage = RowArena::new();
+        let query = |path: &str| -> Result<Vec<String>, JsonPathError> {
+            let path: JsonPath = path.parse().unwrap();
+            let items = path.query(
+                target.as_ref().into_datum(),
+                vars.as_ref().into_datum(),
+                &temp_storage,
+            )?;
+            Ok(items
+                .into_iter()
+                .map(|d| crate::adt::jsonb::JsonbRef::from_datum(d).to_string())
+                .collect())
+        };
+
+        assert_eq!(query("$.a[*]").unwrap(), ["1", "2", r#"{"b":3}"#]);
+        assert_eq!(query("$.a.b").unwrap(), ["3"]);
+        assert_eq!(query("$.a[last]").unwrap(), [r#"{"b":3}"#]);
+        assert_eq!(query("$.a[0 to 1]").unwrap(), ["1", "2"]);
+        assert_eq!(query("$.a[*] ? (@ >= $min)").unwrap(), ["2"]);
+        assert_eq!(query("$.a.size()").unwrap(), ["3"]);
+        assert_eq!(query("$.c.type()").unwrap(), [r#""string""#]);
+        assert_eq!(query("$.a[0] + $.a[1]").unwrap(), ["3"]);
+        assert_eq!(query("$.c starts with \"xy\"").unwrap(), ["true"]);
+        assert_eq!(query("$.d == null").unwrap(), ["true"]);
+        assert_eq!(query("$.c == 1").unwrap(), ["null"]);
+        assert_eq!(query("$.**.b").unwrap(), ["3", "3"]);
+        assert_eq!(query("$.missing").unwrap(), Vec::<String>::new());
+
+        assert!(query("strict $.missing").is_err());
+        assert!(query("strict $.a.b").is_err());
+        assert!(query("strict $.a[5]").is_err());
+        assert!(query("$.c + 1").is_err());
+        assert!(query("$.a[0] / 0").is_err());
+        assert!(query("$nope").is_err());
+    }
+}
@@ -0,0 +1,605 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Network address types.
+//!
+//! This module implements the PostgreSQL `inet`, `cidr` and `macaddr` types.
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use proptest::prelude::{any, Arbitrary, BoxedStrategy, Strategy};
+
+/// The number of bits in an IPv4 address.
+pub const IPV4_MAX_BITS: u8 = 32;
+
+/// The number of bits in an IPv6 address.
+pub const IPV6_MAX_BITS: u8 = 128;
+
+/// An error that can occur when working with network addresses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkError {
+    /// The netmask length is out of range for the address family.
+    InvalidMaskLength(i64),
+    /// The input could not be parsed.
+    InvalidSyntax,
+    /// A `cidr` value has bits set to the right of its netmask.
+    HostBitsSet,
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NetworkError::InvalidMaskLength(bits) => write!(f, "invalid mask length: {}", bits),
+            NetworkError::InvalidSyntax => f.write_str("invalid network address"),
+            NetworkError::HostBitsSet => f.write_str("Value has bits set to right of mask."),
+        }
+    }
+}
+
+impl Error for NetworkError {}
+
+/// An IPv4 or IPv6 host address with the length of the netmask of its
+/// subnet.
+///
+/// This type backs both the `inet` and the `cidr` types. A `cidr` value is an
+/// `Inet` whose bits to the right of the netmask are all zero; see
+/// [`Inet::is_network`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Inet {
+    addr: IpAddr,
+    bits: u8,
+}
+
+impl Inet {
+    /// Constructs a new address with the netmask length `bits`.
+    pub fn new(addr: IpAddr, bits: u8) -> Result<Inet, NetworkError> {
+        if bits > max_bits(&addr) {
+            return Err(NetworkError::InvalidMaskLength(bits.into()));
+        }
+        Ok(Inet { addr, bits })
+    }
+
+    /// Constructs a new host address, whose netmask covers the whole address.
+    pub fn host(addr: IpAddr) -> Inet {
+        Inet {
+            addr,
+            bits: max_bits(&addr),
+        }
+    }
+
+    /// Returns the address, ignoring the netmask.
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the length of the netmask.
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    /// Returns the number of bits in an address of this address's family.
+    pub fn max_bits(&self) -> u8 {
+        max_bits(&self.addr)
+    }
+
+    /// Reports whether none of the bits to the right of the netmask are set,
+    /// i.e., whether this is a valid `cidr` value.
+    pub fn is_network(&self) -> bool {
+        self.network() == *self
+    }
+
+    /// Returns the network part of the address, with all bits to the right of
+    /// the netmask cleared.
+    pub fn network(&self) -> Inet {
+        let addr = match self.addr {
+            IpAddr::V4(addr) => {
+                let mask = u32::MAX.checked_shl(32 - u32::from(self.bits)).unwrap_or(0);
+                IpAddr::V4(Ipv4Addr::from(u32::from(addr) & mask))
+            }
+            IpAddr::V6(addr) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - u32::from(self.bits))
+                    .unwrap_or(0);
+                IpAddr::V6(Ipv6Addr::from(u128::from(addr) & mask))
+            }
+        };
+        Inet {
+            addr,
+            bits: self.bits,
+        }
+    }
+
+    /// Returns the address with the netmask length `bits`. A `bits` of `-1`
+    /// selects the netmask that covers the whole address.
+    pub fn with_bits(&self, bits: i32) -> Result<Inet, NetworkError> {
+        let bits = if bits == -1 {
+            self.max_bits()
+        } else {
+            u8::try_from(bits)
+                .ok()
+                .filter(|bits| *bits <= self.max_bits())
+                .ok_or_else(|| NetworkError::InvalidMaskLength(bits.into()))?
+        };
+        Ok(Inet {
+            addr: self.addr,
+            bits,
+        })
+    }
+
+    /// Reports whether this address is within the subnet `other`, like
+    /// PostgreSQL's `<<=` operator. If `strict`, the subnets must not be
+    /// equal, like PostgreSQL's `<<` operator.
+    pub fn is_contained_by(&self, other: &Inet, strict: bool) -> bool {
+        let bits_ok = if strict {
+            self.bits > other.bits
+        } else {
+            self.bits >= other.bits
+        };
+        self.same_family(other)
+            && bits_ok
+            && bit_cmp(&self.octets(), &other.octets(), other.bits) == Ordering::Equal
+    }
+
+    /// Reports whether either subnet contains the other, like PostgreSQL's
+    /// `&&` operator.
+    pub fn overlaps(&self, other: &Inet) -> bool {
+        self.same_family(other)
+            && bit_cmp(&self.octets(), &other.octets(), self.bits.min(other.bits))
+                == Ordering::Equal
+    }
+
+    fn same_family(&self, other: &Inet) -> bool {
+        self.addr.is_ipv4() == other.addr.is_ipv4()
+    }
+
+    /// Returns the bytes of the address, zero-padded to the length of an IPv6
+    /// address.
+    fn octets(&self) -> [u8; 16] {
+        match self.addr {
+            IpAddr::V4(addr) => {
+                let mut octets = [0; 16];
+                octets[..4].copy_from_slice(&addr.octets());
+                octets
+            }
+            IpAddr::V6(addr) => addr.octets(),
+        }
+    }
+
+    /// Parses `s` as an `inet` value.
+    ///
+    /// An IPv4 address without a netmask length must spell out all four
+    /// octets.
+    pub fn parse_inet(s: &str) -> Result<Inet, NetworkError> {
+        match parse_parts(s)? {
+            (Parts::V6(addr), bits) => Inet::new(IpAddr::V6(addr), bits.unwrap_or(IPV6_MAX_BITS)),
+            (Parts::V4(octets), bits) => {
+                let bits = match bits {
+                    Some(bits) => bits,
+                    None if octets.len() == 4 => IPV4_MAX_BITS,
+                    None => return Err(NetworkError::InvalidSyntax),
+                };
+                if usize::from(bits / 8) > octets.len() {
+                    return Err(NetworkError::InvalidSyntax);
+                }
+                Inet::new(IpAddr::V4(ipv4_from_octets(&octets)), bits)
+            }
+        }
+    }
+
+    /// Parses `s` as a `cidr` value.
+    ///
+    /// An IPv4 address may omit trailing octets, which are taken to be zero.
+    /// If it also omits the netmask length, the length is derived from the
+    /// address class as in PostgreSQL.
+    pub fn parse_cidr(s: &str) -> Result<Inet, NetworkError> {
+        let inet = match parse_parts(s)? {
+            (Parts::V6(addr), bits) => Inet::new(IpAddr::V6(addr), bits.unwrap_or(IPV6_MAX_BITS))?,
+            (Parts::V4(octets), bits) => {
+                let specified = u8::try_from(octets.len() * 8).expect("at most four octets");
+                let bits = match bits {
+                    Some(bits) => bits,
+                    None => {
+                        let first = octets[0];
+                        let class_bits = match first {
+                            240.. => 32,
+                            224.. => 8,
+                            192.. => 24,
+                            128.. => 16,
+                            _ => 8,
+                        };
+                        // If the implied netmask is narrower than the
+                        // specified octets, widen it.
+                        let bits = class_bits.max(specified);
+                        // A bare class D address has a four bit netmask.
+                        if bits == 8 && first == 224 {
+                            4
+                        } else {
+                            bits
+                        }
+                    }
+                };
+                Inet::new(IpAddr::V4(ipv4_from_octets(&octets)), bits)?
+            }
+        };
+        if !inet.is_network() {
+            return Err(NetworkError::HostBitsSet);
+        }
+        Ok(inet)
+    }
+
+    /// Formats the address in the `cidr` style, which always includes the
+    /// netmask length.
+    pub fn to_cidr_string(&self) -> String {
+        format!("{}/{}", self.addr, self.bits)
+    }
+
+    /// Encodes the address as a family byte (`4` or `6`), the netmask length,
+    /// and the 16 bytes of the zero-padded address.
+    pub fn encode_binary(&self) -> [u8; Inet::binary_size()] {
+        let mut buf = [0; Inet::binary_size()];
+        buf[0] = if self.addr.is_ipv4() { 4 } else { 6 };
+        buf[1] = self.bits;
+        buf[2..].copy_from_slice(&self.octets());
+        buf
+    }
+
+    /// Decodes an address encoded with [`Inet::encode_binary`].
+    pub fn decode_binary(raw: &[u8]) -> Result<Inet, String> {
+        let raw: &[u8; Inet::binary_size()] = raw.try_into().map_err(|_| {
+            format!(
+                "invalid binary size, expecting {}, found {}",
+                Inet::binary_size(),
+                raw.len()
+            )
+        })?;
+        let mut octets = [0; 16];
+        octets.copy_from_slice(&raw[2..]);
+        let addr = match raw[0] {
+            4 => IpAddr::V4(ipv4_from_octets(&octets[..4])),
+            6 => IpAddr::V6(Ipv6Addr::from(octets)),
+            family => return Err(format!("invalid address family {}", family)),
+        };
+        Inet::new(addr, raw[1]).map_err(|e| e.to_string())
+    }
+
+    pub const fn binary_size() -> usize {
+        18
+    }
+}
+
+impl fmt::Display for Inet {
+    /// Formats the address in the `inet` style, which omits the netmask
+    /// length if the netmask covers the whole address.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.bits == self.max_bits() {
+            write!(f, "{}", self.addr)
+        } else {
+            write!(f, "{}/{}", self.addr, self.bits)
+        }
+    }
+}
+
+impl PartialOrd for Inet {
+    fn partial_cmp(&self, other: &Inet) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Inet {
+    /// Orders addresses as PostgreSQL does: IPv4 before IPv6, then by the
+    /// network part of the address, then by the netmask length, and finally
+    /// by the whole address.
+    fn cmp(&self, other: &Inet) -> Ordering {
+        let (a, b) = (self.octets(), other.octets());
+        self.addr
+            .is_ipv6()
+            .cmp(&other.addr.is_ipv6())
+            .then_with(|| bit_cmp(&a, &b, self.bits.min(other.bits)))
+            .then_with(|| self.bits.cmp(&other.bits))
+            .then_with(|| bit_cmp(&a, &b, IPV6_MAX_BITS))
+    }
+}
+
+impl Arbitrary for Inet {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Inet>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<IpAddr>(), any::<u8>())
+            .prop_map(|(addr, bits)| Inet {
+                addr,
+                bits: bits % (max_bits(&addr) + 1),
+            })
+            .boxed()
+    }
+}
+
+fn max_bits(addr: &IpAddr) -> u8 {
+    match addr {
+        IpAddr::V4(_) => IPV4_MAX_BITS,
+        IpAddr::V6(_) => IPV6_MAX_BITS,
+    }
+}
+
+fn ipv4_from_octets(octets: &[u8]) -> Ipv4Addr {
+    let mut full = [0; 4];
+    full[..octets.len()].copy_from_slice(octets);
+    Ipv4Addr::from(full)
+}
+
+/// Compares the first `bits` bits of two addresses.
+fn bit_cmp(a: &[u8; 16], b: &[u8; 16], bits: u8) -> Ordering {
+    let bytes = usize::from(bits / 8);
+    a[..bytes].cmp(&b[..bytes]).then_with(|| {
+        let rest = bits % 8;
+        if rest == 0 {
+            return Ordering::Equal;
+        }
+        let mask = 0xff << (8 - rest);
+        (a[bytes] & mask).cmp(&(b[bytes] & mask))
+    })
+}
+
+/// The address part of a network address, as written.
+enum Parts {
+    /// Between one and four IPv4 octets.
+    V4(Vec<u8>),
+    V6(Ipv6Addr),
+}
+
+/// Splits `s` into its address and its optional netmask length.
+fn parse_parts(s: &str) -> Result<(Parts, Option<u8>), NetworkError> {
+    let s = s.trim();
+    let (addr, bits) = match s.split_once('/') {
+        Some((addr, bits)) => {
+            if bits.is_empty() || !bits.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(NetworkError::InvalidSyntax);
+            }
+            let bits = bits.parse().map_err(|_| NetworkError::InvalidSyntax)?;
+            (addr, Some(bits))
+        }
+        None => (s, None),
+    };
+    if addr.contains(':') {
+        let addr = addr.parse().map_err(|_| NetworkError::InvalidSyntax)?;
+        if bits.map_or(false, |bits| bits > IPV6_MAX_BITS) {
+            return Err(NetworkError::InvalidSyntax);
+        }
+        return Ok((Parts::V6(addr), bits));
+    }
+    let mut octets = vec![];
+    for octet in addr.split('.') {
+        if octet.is_empty() || octet.len() > 3 || !octet.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(NetworkError::InvalidSyntax);
+        }
+        octets.push(octet.parse().map_err(|_| NetworkError::InvalidSyntax)?);
+    }
+    if octets.len() > 4 || bits.map_or(false, |bits| bits > IPV4_MAX_BITS) {
+        return Err(NetworkError::InvalidSyntax);
+    }
+    Ok((Parts::V4(octets), bits))
+}
+
+/// A MAC address, like PostgreSQL's `macaddr` type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MacAddr(pub [u8; 6]);
+
+impl MacAddr {
+    pub const fn binary_size() -> usize {
+        6
+    }
+}
+
+impl FromStr for MacAddr {
+    type Err = NetworkError;
+
+    /// Parses any of the formats that PostgreSQL accepts, e.g.
+    /// `08:00:2b:01:02:03`, `08002b-010203`, `0800.2b01.0203`, or
+    /// `08002b010203`.
+    fn from_str(s: &str) -> Result<MacAddr, NetworkError> {
+        let s = s.trim();
+        // The digits in each group, and the separators between groups.
+        let groups: &[(&[usize], &[char])] = &[
+            (&[2, 2, 2, 2, 2, 2], &[':', '-']),
+            (&[6, 6], &[':', '-']),
+            (&[4, 4, 4], &['.', '-']),
+            (&[12], &[]),
+        ];
+        for (lens, seps) in groups {
+            let parts: Vec<&str> = match seps.iter().find(|sep| s.contains(**sep)) {
+                Some(sep) => s.split(*sep).collect(),
+                None => vec![s],
+            };
+            if parts.len() != lens.len() {
+                continue;
+            }
+            let fixed = lens.len() != 6;
+            let valid = parts.iter().zip(lens.iter()).all(|(part, len)| {
+                let len_ok = if fixed {
+                    part.len() == *len
+                } else {
+                    (1..=2).contains(&part.len())
+                };
+                len_ok && part.bytes().all(|b| b.is_ascii_hexdigit())
+            });
+            if !valid {
+                continue;
+            }
+            let mut bytes = [0; 6];
+            let mut i = 0;
+            for part in parts {
+                let mut start = 0;
+                while start < part.len() {
+                    let end = if fixed { start + 2 } else { part.len() };
+                    bytes[i] = u8::from_str_radix(&part[start..end], 16)
+                        .map_err(|_| NetworkError::InvalidSyntax)?;
+                    i += 1;
+                    start = end;
+                }
+            }
+            return Ok(MacAddr(bytes));
+        }
+        Err(NetworkError::InvalidSyntax)
+    }
+}
+
+impl fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(
+            f,
+            "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+            a, b, c, d, e, g
+        )
+    }
+}
+
+impl Arbitrary for MacAddr {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<MacAddr>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        any::<[u8; 6]>().prop_map(MacAddr).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_parse_inet() {
+        for (input, expected) in [
+            ("192.168.1.5", "192.168.1.5"),
+            ("192.168.1.5/24", "192.168.1.5/24"),
+            ("10.1/16", "10.1.0.0/16"),
+            (" 10.0.0.1/32 ", "10.0.0.1"),
+            ("::1", "::1"),
+            ("2001:db8::1/64", "2001:db8::1/64"),
+            ("::ffff:1.2.3.4", "::ffff:1.2.3.4"),
+        ] {
+            assert_eq!(Inet::parse_inet(input).unwrap().to_string(), expected);
+        }
+        for input in [
+            "10.1",
+            "10.1.2.3.4",
+            "256.0.0.1",
+            "10.0.0.1/33",
+            "::1/129",
+            "a.b.c.d",
+        ] {
+            assert!(Inet::parse_inet(input).is_err(), "{}", input);
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_parse_cidr() {
+        for (input, expected) in [
+            ("192.168.100.128/25", "192.168.100.128/25"),
+            ("192.168.1", "192.168.1.0/24"),
+            ("128.1", "128.1.0.0/16"),
+            ("128", "128.0.0.0/16"),
+            ("10.1.2", "10.1.2.0/24"),
+            ("10", "10.0.0.0/8"),
+            ("224", "224.0.0.0/4"),
+            ("10.1.2.3/32", "10.1.2.3/32"),
+            ("2001:4f8:3:ba::/64", "2001:4f8:3:ba::/64"),
+        ] {
+            let cidr = Inet::parse_cidr(input).unwrap();
+            assert_eq!(cidr.to_cidr_string(), expected);
+        }
+        assert_eq!(
+            Inet::parse_cidr("10.1.2.3/8"),
+            Err(NetworkError::HostBitsSet)
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_containment() {
+        let inet = |s| Inet::parse_inet(s).unwrap();
+        assert!(inet("192.168.1.5").is_contained_by(&inet("192.168.1.0/24"), true));
+        assert!(!inet("192.168.1.0/24").is_contained_by(&inet("192.168.1.0/24"), true));
+        assert!(inet("192.168.1.0/24").is_contained_by(&inet("192.168.1.0/24"), false));
+        assert!(!inet("10.0.0.1").is_contained_by(&inet("192.168.1.0/24"), false));
+        assert!(!inet("::1").is_contained_by(&inet("0.0.0.0/0"), false));
+        assert!(inet("192.168.1.0/24").overlaps(&inet("192.168.1.5")));
+        assert!(inet("192.168.1.5").overlaps(&inet("192.168.0.0/16")));
+        assert!(!inet("192.168.1.0/24").overlaps(&inet("192.168.2.0/24")));
+    }
+
+    #[mz_ore::test]
+    fn test_ordering() {
+        let mut addrs: Vec<_> = [
+            "::1",
+            "10.0.0.10",
+            "10.0.0.9",
+            "10.0.0.0/8",
+            "10.1.0.0/16",
+            "9.0.0.0/8",
+        ]
+        .into_iter()
+        .map(|s| Inet::parse_inet(s).unwrap())
+        .collect();
+        addrs.sort();
+        let addrs: Vec<_> = addrs.iter().map(|a| a.to_string()).collect();
+        assert_eq!(
+            addrs,
+            [
+                "9.0.0.0/8",
+                "10.0.0.0/8",
+                "10.1.0.0/16",
+                "10.0.0.9",
+                "10.0.0.10",
+                "::1"
+            ]
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_parse_macaddr() {
+        for input in [
+            "08:00:2b:01:02:03",
+            "08-00-2b-01-02-03",
+            "08002b:010203",
+            "08002b-010203",
+            "0800.2b01.0203",
+            "0800-2b01-0203",
+            "08002b010203",
+            "8:0:2B:1:2:3",
+        ] {
+            assert_eq!(
+                input.parse::<MacAddr>().unwrap().to_string(),
+                "08:00:2b:01:02:03",
+                "{}",
+                input
+            );
+        }
+        for input in [
+            "08:00:2b:01:02",
+            "08002b01020",
+            "08:00:2b:01:02:0g",
+            "0800:2b01:0203",
+        ] {
+            assert!(input.parse::<MacAddr>().is_err(), "{}", input);
+        }
+    }
+
+    proptest! {
+        #[mz_ore::test]
+        fn inet_binary_roundtrip(inet: Inet) {
+            prop_assert_eq!(Inet::decode_binary(&inet.encode_binary()), Ok(inet));
+        }
+    }
+}
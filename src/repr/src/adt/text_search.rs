@@ -0,0 +1,2024 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Full text search.
+//!
+//! This module implements the PostgreSQL `tsvector` and `tsquery` types, the
+//! `simple` and `english` text search configurations that reduce documents
+//! and queries to lexemes, and the matching and ranking of documents against
+//! queries.
+//!
+//! Values of both types are stored as their canonical text. Parsing the text
+//! again always succeeds and yields the same value.
+//!
+//! ## Configurations
+//!
+//! Documents are split into words at every character that is not
+//! alphanumeric. This is a simplification of PostgreSQL's default parser,
+//! which additionally recognizes tokens like email addresses, URLs, and
+//! hyphenated words. The `simple` configuration lowercases each word. The
+//! `english` configuration additionally drops stop words and reduces the
+//! remaining words to their stems with the Snowball English (Porter2)
+//! stemmer.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+
+use mz_ore::stack::{CheckedRecursion, RecursionGuard, RecursionLimitError};
+
+/// The largest position that can be recorded for a lexeme. Larger positions
+/// are silently clamped to this value.
+pub const MAX_POSITION: u16 = 16383;
+
+/// The default weights used by `ts_rank`, in `{D, C, B, A}` order.
+pub const DEFAULT_RANK_WEIGHTS: [f32; 4] = [0.1, 0.2, 0.4, 1.0];
+
+/// The maximum number of positions recorded for a single lexeme.
+const MAX_POSITIONS_PER_LEXEME: usize = 256;
+
+/// The maximum length of a lexeme, in bytes.
+const MAX_LEXEME_LEN: usize = 2047;
+
+/// The maximum distance of a phrase operator.
+const MAX_PHRASE_DISTANCE: u16 = 16384;
+
+/// The weight of a lexeme occurrence in a [`TsVector`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Weight {
+    D,
+    C,
+    B,
+    A,
+}
+
+impl Weight {
+    /// Parses a weight label, ignoring case.
+    pub fn from_char(c: char) -> Option<Weight> {
+        match c.to_ascii_uppercase() {
+            'A' => Some(Weight::A),
+            'B' => Some(Weight::B),
+            'C' => Some(Weight::C),
+            'D' => Some(Weight::D),
+            _ => None,
+        }
+    }
+
+    fn as_char(&self) -> char {
+        match self {
+            Weight::A => 'A',
+            Weight::B => 'B',
+            Weight::C => 'C',
+            Weight::D => 'D',
+        }
+    }
+
+    /// The index of the weight in the `{D, C, B, A}` ordering used by
+    /// PostgreSQL's on-disk and wire formats.
+    fn code(&self) -> usize {
+        match self {
+            Weight::D => 0,
+            Weight::C => 1,
+            Weight::B => 2,
+            Weight::A => 3,
+        }
+    }
+
+    fn from_code(code: u16) -> Weight {
+        match code & 3 {
+            0 => Weight::D,
+            1 => Weight::C,
+            2 => Weight::B,
+            _ => Weight::A,
+        }
+    }
+
+    /// The bit that represents this weight in a [`TsQuery`] operand's weight
+    /// restriction.
+    fn mask(&self) -> u8 {
+        1 << self.code()
+    }
+}
+
+/// An occurrence of a lexeme in a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Position {
+    pos: u16,
+    weight: Weight,
+}
+
+/// A lexeme and the positions at which it occurs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    lexeme: String,
+    /// Sorted and free of duplicates. Empty if the vector does not record
+    /// positions for the lexeme.
+    positions: Vec<Position>,
+}
+
+/// A text search configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextSearchConfig {
+    /// Lowercases words.
+    Simple,
+    /// Lowercases words, drops English stop words, and stems the remaining
+    /// words.
+    English,
+}
+
+impl FromStr for TextSearchConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<TextSearchConfig, String> {
+        let name = s.to_lowercase();
+        let name = name.strip_prefix("pg_catalog.").unwrap_or(&name);
+        match name {
+            "simple" => Ok(TextSearchConfig::Simple),
+            "english" => Ok(TextSearchConfig::English),
+            _ => Err(format!(
+                "text search configuration \"{}\" does not exist",
+                s
+            )),
+        }
+    }
+}
+
+impl TextSearchConfig {
+    /// Splits `text` into words and reduces each word to a lexeme, returning
+    /// the lexemes with their positions. Words that are dropped, like stop
+    /// words, still occupy a position.
+    fn lexemes(&self, text: &str) -> Vec<(String, u16)> {
+        let mut lexemes = vec![];
+        let mut pos: u16 = 0;
+        for word in text.split(|c: char| !c.is_alphanumeric()) {
+            if word.is_empty() {
+                continue;
+            }
+            pos = pos.saturating_add(1).min(MAX_POSITION);
+            if word.len() > MAX_LEXEME_LEN {
+                continue;
+            }
+            let word = word.to_lowercase();
+            let lexeme = match self {
+                TextSearchConfig::Simple => word,
+                TextSearchConfig::English => {
+                    if ENGLISH_STOP_WORDS.binary_search(&word.as_str()).is_ok() {
+                        continue;
+                    }
+                    if word.bytes().all(|b| b.is_ascii_lowercase()) {
+                        english_stem(&word)
+                    } else {
+                        word
+                    }
+                }
+            };
+            lexemes.push((lexeme, pos));
+        }
+        lexemes
+    }
+}
+
+/// A document, reduced to the lexemes it contains.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TsVector {
+    /// Sorted by lexeme and free of duplicate lexemes.
+    entries: Vec<Entry>,
+}
+
+impl TsVector {
+    /// Builds a vector from entries in any order, merging duplicate lexemes
+    /// and positions. When a position occurs more than once, the highest
+    /// weight wins.
+    fn from_entries(mut entries: Vec<Entry>) -> TsVector {
+        entries.sort_by(|a, b| a.lexeme.cmp(&b.lexeme));
+        let mut merged: Vec<Entry> = Vec::with_capacity(entries.len());
+        for entry in entries {
+            match merged.last_mut() {
+                Some(last) if last.lexeme == entry.lexeme => last.positions.extend(entry.positions),
+                _ => merged.push(entry),
+            }
+        }
+        for entry in &mut merged {
+            entry
+                .positions
+                .sort_by(|a, b| a.pos.cmp(&b.pos).then(b.weight.cmp(&a.weight)));
+            entry.positions.dedup_by_key(|p| p.pos);
+            entry.positions.truncate(MAX_POSITIONS_PER_LEXEME);
+        }
+        TsVector { entries: merged }
+    }
+
+    /// Reduces `text` to a vector using the text search configuration
+    /// `config`.
+    pub fn from_document(config: TextSearchConfig, text: &str) -> TsVector {
+        let entries = config
+            .lexemes(text)
+            .into_iter()
+            .map(|(lexeme, pos)| Entry {
+                lexeme,
+                positions: vec![Position {
+                    pos,
+                    weight: Weight::D,
+                }],
+            })
+            .collect();
+        TsVector::from_entries(entries)
+    }
+
+    /// Assigns `weight` to every position in the vector.
+    pub fn set_weight(&mut self, weight: Weight) {
+        for entry in &mut self.entries {
+            for position in &mut entry.positions {
+                position.weight = weight;
+            }
+        }
+    }
+
+    /// Concatenates two vectors. The positions in `other` are shifted past the
+    /// largest position in `self`, as if the documents were concatenated.
+    pub fn concat(&self, other: &TsVector) -> TsVector {
+        let max_pos = self
+            .entries
+            .iter()
+            .flat_map(|e| e.positions.iter().map(|p| p.pos))
+            .max()
+            .unwrap_or(0);
+        let shifted = other.entries.iter().map(|e| Entry {
+            lexeme: e.lexeme.clone(),
+            positions: e
+                .positions
+                .iter()
+                .map(|p| Position {
+                    pos: p.pos.saturating_add(max_pos).min(MAX_POSITION),
+                    weight: p.weight,
+                })
+                .collect(),
+        });
+        TsVector::from_entries(self.entries.iter().cloned().chain(shifted).collect())
+    }
+
+    /// Reports whether the document matches `query`. An empty query matches
+    /// nothing.
+    pub fn matches(&self, query: &TsQuery) -> bool {
+        match &query.root {
+            None => false,
+            Some(node) => self.eval(node),
+        }
+    }
+
+    fn eval(&self, node: &QueryNode) -> bool {
+        match node {
+            QueryNode::Operand(operand) => self.occurrences(operand).is_match(),
+            QueryNode::Not(inner) => !self.eval(inner),
+            QueryNode::And(left, right) => self.eval(left) && self.eval(right),
+            QueryNode::Or(left, right) => self.eval(left) || self.eval(right),
+            QueryNode::Phrase(..) => self.eval_phrase(node).is_match(),
+            QueryNode::Stop => unreachable!("stop words are removed from queries"),
+        }
+    }
+
+    /// Returns the entries that `operand` matches: either the entry for its
+    /// lexeme, or, for a prefix operand, all entries that start with it.
+    fn lookup(&self, operand: &Operand) -> &[Entry] {
+        let start = self
+            .entries
+            .partition_point(|e| e.lexeme.as_str() < operand.lexeme.as_str());
+        let len = if operand.prefix {
+            self.entries[start..]
+                .iter()
+                .take_while(|e| e.lexeme.starts_with(&operand.lexeme))
+                .count()
+        } else {
+            self.entries[start..]
+                .iter()
+                .take_while(|e| e.lexeme == operand.lexeme)
+                .count()
+        };
+        &self.entries[start..start + len]
+    }
+
+    /// Returns the positions at which `operand` occurs. Entries without
+    /// positions are considered to occur at every position.
+    fn occurrences(&self, operand: &Operand) -> PhraseMatch {
+        let mut positions = BTreeSet::new();
+        for entry in self.lookup(operand) {
+            if entry.positions.is_empty() {
+                return PhraseMatch::all();
+            }
+            positions.extend(
+                entry
+                    .positions
+                    .iter()
+                    .filter(|p| operand.weights == 0 || operand.weights & p.weight.mask() != 0)
+                    .map(|p| u32::from(p.pos)),
+            );
+        }
+        PhraseMatch {
+            positions,
+            negated: false,
+            width: 0,
+        }
+    }
+
+    /// Evaluates `node` within a phrase, returning the positions at which it
+    /// matches. A match of a multi-word subexpression is reported at the
+    /// position of its last word.
+    fn eval_phrase(&self, node: &QueryNode) -> PhraseMatch {
+        match node {
+            QueryNode::Operand(operand) => self.occurrences(operand),
+            QueryNode::Not(inner) => {
+                let mut m = self.eval_phrase(inner);
+                m.negated = !m.negated;
+                m
+            }
+            QueryNode::And(left, right) | QueryNode::Or(left, right) => {
+                let left = self.eval_phrase(left);
+                let right = self.eval_phrase(right);
+                let width = left.width.max(right.width);
+                let (loffset, roffset) = (width - left.width, width - right.width);
+                let (left, right) = (left.shift(loffset), right.shift(roffset));
+                let mut m = if matches!(node, QueryNode::And(..)) {
+                    left.intersect(right)
+                } else {
+                    left.union(right)
+                };
+                m.width = width;
+                m
+            }
+            QueryNode::Phrase(left, right, distance) => {
+                let left = self.eval_phrase(left);
+                let right = self.eval_phrase(right);
+                let width = u32::from(*distance) + left.width + right.width;
+                let left = left.shift(u32::from(*distance) + right.width);
+                let mut m = left.intersect(right);
+                m.width = width;
+                m
+            }
+            QueryNode::Stop => unreachable!("stop words are removed from queries"),
+        }
+    }
+
+    /// Computes the relevance of the document to `query`, like PostgreSQL's
+    /// `ts_rank`.
+    ///
+    /// `weights` gives the weight of each lexeme occurrence label, in `{D, C,
+    /// B, A}` order. `normalization` is a bit mask that selects how the rank
+    /// is scaled by the length of the document.
+    #[allow(clippy::as_conversions)]
+    pub fn rank(&self, query: &TsQuery, weights: &[f32; 4], normalization: i32) -> f32 {
+        let Some(root) = &query.root else {
+            return 0.0;
+        };
+        if self.entries.is_empty() {
+            return 0.0;
+        }
+
+        // Rank each distinct operand once, in lexeme order.
+        let mut operands = BTreeMap::new();
+        root.for_each_operand(&mut |operand| {
+            operands.entry(operand.lexeme.as_str()).or_insert(operand);
+        });
+        let operands: Vec<&Operand> = operands.into_values().collect();
+
+        let mut res = match root {
+            QueryNode::And(..) | QueryNode::Phrase(..) => self.rank_and(&operands, weights),
+            _ => self.rank_or(&operands, weights),
+        };
+        if res < 0.0 {
+            res = 1e-20;
+        }
+
+        let length = self
+            .entries
+            .iter()
+            .map(|e| e.positions.len().max(1))
+            .sum::<usize>();
+        let unique = self.entries.len();
+        if normalization & 1 != 0 {
+            res = (f64::from(res) / ((length as f64 + 1.0).ln() / 2f64.ln())) as f32;
+        }
+        if normalization & 2 != 0 && length > 0 {
+            res /= length as f32;
+        }
+        if normalization & 8 != 0 {
+            res /= unique as f32;
+        }
+        if normalization & 16 != 0 {
+            res = (f64::from(res) / ((unique as f64 + 1.0).ln() / 2f64.ln())) as f32;
+        }
+        if normalization & 32 != 0 {
+            res /= res + 1.0;
+        }
+        res
+    }
+
+    #[allow(clippy::as_conversions)]
+    fn rank_or(&self, operands: &[&Operand], weights: &[f32; 4]) -> f32 {
+        // Entries without positions count as a single occurrence of weight D.
+        let no_positions = [Position {
+            pos: 0,
+            weight: Weight::D,
+        }];
+        let mut res: f32 = 0.0;
+        for operand in operands {
+            for entry in self.lookup(operand) {
+                let positions = if entry.positions.is_empty() {
+                    &no_positions[..]
+                } else {
+                    &entry.positions[..]
+                };
+                let mut resj: f32 = 0.0;
+                let mut wjm: f32 = -1.0;
+                let mut jm = 0;
+                for (j, p) in positions.iter().enumerate() {
+                    let w = weights[p.weight.code()];
+                    resj += w / ((j + 1) * (j + 1)) as f32;
+                    if w > wjm {
+                        wjm = w;
+                        jm = j;
+                    }
+                }
+                // The sum of 1/i^2 converges to pi^2/6.
+                let term = wjm + resj - wjm / ((jm + 1) * (jm + 1)) as f32;
+                res = (f64::from(res) + f64::from(term) / 1.64493406685) as f32;
+            }
+        }
+        if !operands.is_empty() {
+            res /= operands.len() as f32;
+        }
+        res
+    }
+
+    #[allow(clippy::as_conversions)]
+    fn rank_and(&self, operands: &[&Operand], weights: &[f32; 4]) -> f32 {
+        if operands.len() < 2 {
+            return self.rank_or(operands, weights);
+        }
+        // Entries without positions count as a single occurrence of weight D
+        // at the largest position.
+        let no_positions = [Position {
+            pos: MAX_POSITION,
+            weight: Weight::D,
+        }];
+        let mut res: f32 = -1.0;
+        let mut found: Vec<Option<(&[Position], bool)>> = vec![None; operands.len()];
+        for (i, operand) in operands.iter().enumerate() {
+            for entry in self.lookup(operand) {
+                found[i] = Some(if entry.positions.is_empty() {
+                    (&no_positions[..], true)
+                } else {
+                    (&entry.positions[..], false)
+                });
+                let (positions, positions_null) = found[i].expect("just set");
+                for (other, other_null) in found[..i].iter().flatten() {
+                    for l in positions {
+                        for p in *other {
+                            let mut dist = (i32::from(l.pos) - i32::from(p.pos)).abs();
+                            if dist != 0 || positions_null || *other_null {
+                                if dist == 0 {
+                                    dist = i32::from(MAX_POSITION) + 1;
+                                }
+                                let curw = f64::from(
+                                    weights[l.weight.code()]
+                                        * weights[p.weight.code()]
+                                        * word_distance(dist),
+                                )
+                                .sqrt() as f32;
+                                res = if res < 0.0 {
+                                    curw
+                                } else {
+                                    (1.0 - (1.0 - f64::from(res)) * (1.0 - f64::from(curw))) as f32
+                                };
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        res
+    }
+
+    /// Encodes the vector in PostgreSQL's binary format.
+    pub fn to_binary(&self) -> Vec<u8> {
+        let mut buf = vec![];
+        let len = i32::try_from(self.entries.len()).expect("tsvector too large");
+        buf.extend(len.to_be_bytes());
+        for entry in &self.entries {
+            buf.extend(entry.lexeme.as_bytes());
+            buf.push(0);
+            let npos = u16::try_from(entry.positions.len()).expect("bounded by 256");
+            buf.extend(npos.to_be_bytes());
+            for p in &entry.positions {
+                let code = u16::try_from(p.weight.code()).expect("weight codes fit in u16");
+                buf.extend(((code << 14) | p.pos).to_be_bytes());
+            }
+        }
+        buf
+    }
+
+    /// Decodes a vector from PostgreSQL's binary format.
+    pub fn from_binary(raw: &[u8]) -> Result<TsVector, String> {
+        let mut reader = BinaryReader(raw);
+        let len = reader.read_i32()?;
+        let mut entries = vec![];
+        for _ in 0..len {
+            let lexeme = reader.read_cstring()?;
+            let npos = reader.read_u16()?;
+            let mut positions = vec![];
+            for _ in 0..npos {
+                let p = reader.read_u16()?;
+                let pos = p & MAX_POSITION;
+                if pos == 0 {
+                    return Err("invalid position in tsvector".into());
+                }
+                positions.push(Position {
+                    pos,
+                    weight: Weight::from_code(p >> 14),
+                });
+            }
+            entries.push(Entry { lexeme, positions });
+        }
+        reader.finish()?;
+        Ok(TsVector::from_entries(entries))
+    }
+}
+
+fn word_distance(dist: i32) -> f32 {
+    if dist > 100 {
+        return 1e-30;
+    }
+    #[allow(clippy::as_conversions)]
+    let d = (1.0 / (1.005 + 0.05 * (f64::from(dist) / 1.5 - 2.0).exp())) as f32;
+    d
+}
+
+impl FromStr for TsVector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<TsVector, String> {
+        let mut scanner = Scanner::new(s, false);
+        let mut entries = vec![];
+        while let Some(lexeme) = scanner.word()? {
+            let mut positions = vec![];
+            if scanner.peek() == Some(':')
+                && scanner.peek_nth(1).map_or(false, |c| c.is_ascii_digit())
+            {
+                scanner.next();
+                loop {
+                    let pos = scanner.number()?;
+                    if pos == 0 {
+                        return Err("wrong position info".into());
+                    }
+                    let pos = u16::try_from(pos.min(u32::from(MAX_POSITION))).expect("clamped");
+                    let weight = match scanner.peek().and_then(Weight::from_char) {
+                        Some(weight) => {
+                            scanner.next();
+                            weight
+                        }
+                        None => Weight::D,
+                    };
+                    positions.push(Position { pos, weight });
+                    if scanner.peek() == Some(',') {
+                        scanner.next();
+                    } else {
+                        break;
+                    }
+                }
+            }
+            match scanner.peek() {
+                None => {}
+                Some(c) if c.is_whitespace() => {}
+                Some(_) => return Err("syntax error".into()),
+            }
+            entries.push(Entry { lexeme, positions });
+        }
+        Ok(TsVector::from_entries(entries))
+    }
+}
+
+impl fmt::Display for TsVector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, entry) in self.entries.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            fmt_lexeme(f, &entry.lexeme)?;
+            for (j, p) in entry.positions.iter().enumerate() {
+                f.write_str(if j == 0 { ":" } else { "," })?;
+                write!(f, "{}", p.pos)?;
+                if p.weight != Weight::D {
+                    write!(f, "{}", p.weight.as_char())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes a lexeme in single quotes, doubling any embedded quotes and
+/// backslashes.
+fn fmt_lexeme(f: &mut fmt::Formatter, lexeme: &str) -> fmt::Result {
+    f.write_str("'")?;
+    for c in lexeme.chars() {
+        match c {
+            '\'' => f.write_str("''")?,
+            '\\' => f.write_str("\\\\")?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    f.write_str("'")
+}
+
+/// The positions at which a subexpression of a phrase matches.
+#[derive(Debug)]
+struct PhraseMatch {
+    /// If `negated` is false, the positions at which the subexpression
+    /// matches. Otherwise, the positions at which it does not.
+    positions: BTreeSet<u32>,
+    negated: bool,
+    /// The distance between the first and last word of the subexpression.
+    width: u32,
+}
+
+impl PhraseMatch {
+    /// A match at every position.
+    fn all() -> PhraseMatch {
+        PhraseMatch {
+            positions: BTreeSet::new(),
+            negated: true,
+            width: 0,
+        }
+    }
+
+    fn is_match(&self) -> bool {
+        self.negated || !self.positions.is_empty()
+    }
+
+    fn shift(self, offset: u32) -> PhraseMatch {
+        PhraseMatch {
+            positions: self.positions.into_iter().map(|p| p + offset).collect(),
+            ..self
+        }
+    }
+
+    fn intersect(self, other: PhraseMatch) -> PhraseMatch {
+        let (positions, negated) = match (self.negated, other.negated) {
+            (false, false) => (&self.positions & &other.positions, false),
+            (false, true) => (&self.positions - &other.positions, false),
+            (true, false) => (&other.positions - &self.positions, false),
+            (true, true) => (&self.positions | &other.positions, true),
+        };
+        PhraseMatch {
+            positions,
+            negated,
+            width: 0,
+        }
+    }
+
+    fn union(self, other: PhraseMatch) -> PhraseMatch {
+        let (positions, negated) = match (self.negated, other.negated) {
+            (false, false) => (&self.positions | &other.positions, false),
+            (false, true) => (&other.positions - &self.positions, true),
+            (true, false) => (&self.positions - &other.positions, true),
+            (true, true) => (&self.positions & &other.positions, true),
+        };
+        PhraseMatch {
+            positions,
+            negated,
+            width: 0,
+        }
+    }
+}
+
+/// A text search query.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TsQuery {
+    /// `None` if the query contains no lexemes, e.g., because it consisted
+    /// solely of stop words.
+    root: Option<QueryNode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Operand {
+    lexeme: String,
+    /// Whether the operand matches any lexeme that starts with `lexeme`.
+    prefix: bool,
+    /// A bit mask of the [`Weight`]s the operand is restricted to, or zero if
+    /// it is unrestricted.
+    weights: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum QueryNode {
+    Operand(Operand),
+    Not(Box<QueryNode>),
+    And(Box<QueryNode>, Box<QueryNode>),
+    Or(Box<QueryNode>, Box<QueryNode>),
+    /// `left <distance> right`
+    Phrase(Box<QueryNode>, Box<QueryNode>, u16),
+    /// An operand that was reduced to no lexemes by a text search
+    /// configuration. Only present while a query is being constructed.
+    Stop,
+}
+
+impl QueryNode {
+    /// The binding strength of the node's operator, as used by PostgreSQL.
+    fn priority(&self) -> u8 {
+        match self {
+            QueryNode::Or(..) => 1,
+            QueryNode::And(..) => 2,
+            QueryNode::Phrase(..) => 3,
+            QueryNode::Not(..) => 4,
+            QueryNode::Operand(_) | QueryNode::Stop => 5,
+        }
+    }
+
+    fn for_each_operand<'a>(&'a self, f: &mut impl FnMut(&'a Operand)) {
+        match self {
+            QueryNode::Operand(operand) => f(operand),
+            QueryNode::Not(inner) => inner.for_each_operand(f),
+            QueryNode::And(left, right)
+            | QueryNode::Or(left, right)
+            | QueryNode::Phrase(left, right, _) => {
+                left.for_each_operand(f);
+                right.for_each_operand(f);
+            }
+            QueryNode::Stop => {}
+        }
+    }
+
+    /// Replaces each operand with the result of `f`.
+    fn map_operands(self, f: &mut impl FnMut(Operand) -> QueryNode) -> QueryNode {
+        match self {
+            QueryNode::Operand(operand) => f(operand),
+            QueryNode::Not(inner) => QueryNode::Not(Box::new(inner.map_operands(f))),
+            QueryNode::And(left, right) => QueryNode::And(
+                Box::new(left.map_operands(f)),
+                Box::new(right.map_operands(f)),
+            ),
+            QueryNode::Or(left, right) => QueryNode::Or(
+                Box::new(left.map_operands(f)),
+                Box::new(right.map_operands(f)),
+            ),
+            QueryNode::Phrase(left, right, distance) => QueryNode::Phrase(
+                Box::new(left.map_operands(f)),
+                Box::new(right.map_operands(f)),
+                distance,
+            ),
+            QueryNode::Stop => QueryNode::Stop,
+        }
+    }
+
+    /// Removes stop words from the tree, along with the operators that
+    /// reference them. Phrase distances are widened to account for removed
+    /// words, so that `a <-> the <-> b` becomes `a <2> b`.
+    ///
+    /// Returns the cleaned tree and the distance that must be added to the
+    /// left and right of it by the enclosing phrase operator, if any.
+    fn remove_stop_words(self) -> (Option<QueryNode>, u16, u16) {
+        match self {
+            QueryNode::Operand(_) => (Some(self), 0, 0),
+            QueryNode::Stop => (None, 0, 0),
+            QueryNode::Not(inner) => {
+                let (inner, ladd, radd) = inner.remove_stop_words();
+                (inner.map(|n| QueryNode::Not(Box::new(n))), ladd, radd)
+            }
+            QueryNode::And(left, right) => {
+                QueryNode::remove_stop_words_binary(*left, *right, None, |l, r, _| {
+                    QueryNode::And(l, r)
+                })
+            }
+            QueryNode::Or(left, right) => {
+                QueryNode::remove_stop_words_binary(*left, *right, None, |l, r, _| {
+                    QueryNode::Or(l, r)
+                })
+            }
+            QueryNode::Phrase(left, right, distance) => QueryNode::remove_stop_words_binary(
+                *left,
+                *right,
+                Some(distance),
+                QueryNode::Phrase,
+            ),
+        }
+    }
+
+    /// Removes stop words from the operands of a binary operator. `distance`
+    /// is the distance of a phrase operator, or `None` for other operators.
+    fn remove_stop_words_binary(
+        left: QueryNode,
+        right: QueryNode,
+        distance: Option<u16>,
+        make: impl FnOnce(Box<QueryNode>, Box<QueryNode>, u16) -> QueryNode,
+    ) -> (Option<QueryNode>, u16, u16) {
+        let (left, lladd, lradd) = left.remove_stop_words();
+        let (right, rladd, rradd) = right.remove_stop_words();
+        match (left, right, distance) {
+            // When a phrase operator is removed, its own distance must be
+            // propagated to the enclosing phrase operator.
+            (None, None, Some(distance)) => {
+                let add = lladd.saturating_add(distance).saturating_add(rradd);
+                (None, add, add)
+            }
+            (None, None, None) => (None, 0, 0),
+            (None, Some(right), Some(distance)) => (
+                Some(right),
+                lladd.saturating_add(distance).saturating_add(rladd),
+                rradd,
+            ),
+            (None, Some(right), None) => (Some(right), rladd, rradd),
+            (Some(left), None, Some(distance)) => (
+                Some(left),
+                lladd,
+                lradd.saturating_add(distance).saturating_add(rradd),
+            ),
+            (Some(left), None, None) => (Some(left), lladd, lradd),
+            (Some(left), Some(right), Some(distance)) => {
+                let distance = distance
+                    .saturating_add(lradd)
+                    .saturating_add(rladd)
+                    .min(MAX_PHRASE_DISTANCE);
+                let node = make(Box::new(left), Box::new(right), distance);
+                (Some(node), lladd, rradd)
+            }
+            (Some(left), Some(right), None) => {
+                (Some(make(Box::new(left), Box::new(right), 0)), 0, 0)
+            }
+        }
+    }
+
+    fn count(&self) -> usize {
+        match self {
+            QueryNode::Operand(_) | QueryNode::Stop => 1,
+            QueryNode::Not(inner) => 1 + inner.count(),
+            QueryNode::And(left, right)
+            | QueryNode::Or(left, right)
+            | QueryNode::Phrase(left, right, _) => 1 + left.count() + right.count(),
+        }
+    }
+
+    /// Writes the node in PostgreSQL's binary format, which lists operators
+    /// before their operands, and right operands before left operands.
+    fn write_binary(&self, buf: &mut Vec<u8>) {
+        match self {
+            QueryNode::Operand(operand) => {
+                buf.extend([1, operand.weights, u8::from(operand.prefix)]);
+                buf.extend(operand.lexeme.as_bytes());
+                buf.push(0);
+            }
+            QueryNode::Not(inner) => {
+                buf.extend([2, 1]);
+                inner.write_binary(buf);
+            }
+            QueryNode::And(left, right)
+            | QueryNode::Or(left, right)
+            | QueryNode::Phrase(left, right, _) => {
+                match self {
+                    QueryNode::And(..) => buf.extend([2, 2]),
+                    QueryNode::Or(..) => buf.extend([2, 3]),
+                    QueryNode::Phrase(_, _, distance) => {
+                        buf.extend([2, 4]);
+                        buf.extend(distance.to_be_bytes());
+                    }
+                    _ => unreachable!(),
+                }
+                right.write_binary(buf);
+                left.write_binary(buf);
+            }
+            QueryNode::Stop => unreachable!("stop words are removed from queries"),
+        }
+    }
+
+    fn read_binary(reader: &mut BinaryReader, remaining: &mut i32) -> Result<QueryNode, String> {
+        *remaining -= 1;
+        if *remaining < 0 {
+            return Err("malformed tsquery: too few items".into());
+        }
+        match reader.read_u8()? {
+            1 => {
+                let weights = reader.read_u8()?;
+                if weights > 0xF {
+                    return Err("invalid tsquery: invalid weight bitmap".into());
+                }
+                let prefix = reader.read_u8()? != 0;
+                let lexeme = reader.read_cstring()?;
+                Ok(QueryNode::Operand(Operand {
+                    lexeme,
+                    prefix,
+                    weights,
+                }))
+            }
+            2 => {
+                let oper = reader.read_u8()?;
+                let distance = if oper == 4 {
+                    let distance = reader.read_u16()?;
+                    if distance > MAX_PHRASE_DISTANCE {
+                        return Err("invalid tsquery: invalid phrase distance".into());
+                    }
+                    Some(distance)
+                } else {
+                    None
+                };
+                if oper == 1 {
+                    let inner = QueryNode::read_binary(reader, remaining)?;
+                    return Ok(QueryNode::Not(Box::new(inner)));
+                }
+                let right = Box::new(QueryNode::read_binary(reader, remaining)?);
+                let left = Box::new(QueryNode::read_binary(reader, remaining)?);
+                match (oper, distance) {
+                    (2, _) => Ok(QueryNode::And(left, right)),
+                    (3, _) => Ok(QueryNode::Or(left, right)),
+                    (4, Some(distance)) => Ok(QueryNode::Phrase(left, right, distance)),
+                    _ => Err(format!(
+                        "invalid tsquery: unrecognized operator type {}",
+                        oper
+                    )),
+                }
+            }
+            t => Err(format!("invalid tsquery: unrecognized item type {}", t)),
+        }
+    }
+}
+
+impl TsQuery {
+    fn from_node(node: QueryNode) -> TsQuery {
+        TsQuery {
+            root: node.remove_stop_words().0,
+        }
+    }
+
+    /// Parses `text` as a query, like `to_tsquery`. Each operand is reduced
+    /// to lexemes with the text search configuration `config`. Operands that
+    /// reduce to more than one lexeme become a phrase, and operands that
+    /// reduce to no lexemes are removed.
+    pub fn parse_with_config(config: TextSearchConfig, text: &str) -> Result<TsQuery, String> {
+        let Some(root) = parse_query(text)? else {
+            return Ok(TsQuery::default());
+        };
+        let root = root.map_operands(&mut |operand| {
+            let lexemes = config.lexemes(&operand.lexeme);
+            let mut nodes = lexemes.into_iter().map(|(lexeme, _pos)| {
+                QueryNode::Operand(Operand {
+                    lexeme,
+                    prefix: operand.prefix,
+                    weights: operand.weights,
+                })
+            });
+            match nodes.next() {
+                None => QueryNode::Stop,
+                Some(first) => nodes.fold(first, |left, right| {
+                    QueryNode::Phrase(Box::new(left), Box::new(right), 1)
+                }),
+            }
+        });
+        Ok(TsQuery::from_node(root))
+    }
+
+    /// Reduces `text` to a query that matches documents containing all of
+    /// its words, like `plainto_tsquery`.
+    pub fn from_plain_text(config: TextSearchConfig, text: &str) -> TsQuery {
+        let root = config
+            .lexemes(text)
+            .into_iter()
+            .map(|(lexeme, _pos)| {
+                QueryNode::Operand(Operand {
+                    lexeme,
+                    prefix: false,
+                    weights: 0,
+                })
+            })
+            .reduce(|left, right| QueryNode::And(Box::new(left), Box::new(right)));
+        TsQuery { root }
+    }
+
+    /// Encodes the query in PostgreSQL's binary format.
+    pub fn to_binary(&self) -> Vec<u8> {
+        let mut buf = vec![];
+        let count = self.root.as_ref().map_or(0, |root| root.count());
+        let count = i32::try_from(count).expect("tsquery too large");
+        buf.extend(count.to_be_bytes());
+        if let Some(root) = &self.root {
+            root.write_binary(&mut buf);
+        }
+        buf
+    }
+
+    /// Decodes a query from PostgreSQL's binary format.
+    pub fn from_binary(raw: &[u8]) -> Result<TsQuery, String> {
+        let mut reader = BinaryReader(raw);
+        let mut remaining = reader.read_i32()?;
+        if remaining == 0 {
+            reader.finish()?;
+            return Ok(TsQuery::default());
+        }
+        let root = QueryNode::read_binary(&mut reader, &mut remaining)?;
+        if remaining != 0 {
+            return Err("malformed tsquery: extra nodes".into());
+        }
+        reader.finish()?;
+        Ok(TsQuery { root: Some(root) })
+    }
+}
+
+impl FromStr for TsQuery {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<TsQuery, String> {
+        Ok(TsQuery {
+            root: parse_query(s)?,
+        })
+    }
+}
+
+impl fmt::Display for TsQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.root {
+            None => Ok(()),
+            Some(root) => fmt_node(f, root, 0, false),
+        }
+    }
+}
+
+/// Writes `node`, parenthesizing it if it binds more loosely than its parent.
+/// Phrase operators are not associative, so a phrase that is the right
+/// operand of another phrase is always parenthesized.
+fn fmt_node(
+    f: &mut fmt::Formatter,
+    node: &QueryNode,
+    parent_priority: u8,
+    right_of_phrase: bool,
+) -> fmt::Result {
+    let priority = node.priority();
+    match node {
+        QueryNode::Operand(operand) => {
+            fmt_lexeme(f, &operand.lexeme)?;
+            if operand.prefix || operand.weights != 0 {
+                f.write_str(":")?;
+                if operand.prefix {
+                    f.write_str("*")?;
+                }
+                for weight in [Weight::A, Weight::B, Weight::C, Weight::D] {
+                    if operand.weights & weight.mask() != 0 {
+                        write!(f, "{}", weight.as_char())?;
+                    }
+                }
+            }
+            Ok(())
+        }
+        QueryNode::Not(inner) => {
+            let parens = priority < parent_priority;
+            if parens {
+                f.write_str("( ")?;
+            }
+            f.write_str("!")?;
+            fmt_node(f, inner, priority, false)?;
+            if parens {
+                f.write_str(" )")?;
+            }
+            Ok(())
+        }
+        QueryNode::And(left, right)
+        | QueryNode::Or(left, right)
+        | QueryNode::Phrase(left, right, _) => {
+            let is_phrase = matches!(node, QueryNode::Phrase(..));
+            let parens = priority < parent_priority || (is_phrase && right_of_phrase);
+            if parens {
+                f.write_str("( ")?;
+            }
+            fmt_node(f, left, priority, false)?;
+            match node {
+                QueryNode::And(..) => f.write_str(" & ")?,
+                QueryNode::Or(..) => f.write_str(" | ")?,
+                QueryNode::Phrase(_, _, 1) => f.write_str(" <-> ")?,
+                QueryNode::Phrase(_, _, distance) => write!(f, " <{}> ", distance)?,
+                _ => unreachable!(),
+            }
+            fmt_node(f, right, priority, is_phrase)?;
+            if parens {
+                f.write_str(" )")?;
+            }
+            Ok(())
+        }
+        QueryNode::Stop => unreachable!("stop words are removed from queries"),
+    }
+}
+
+/// Parses the `tsquery` syntax. Returns `None` if `s` contains no operands.
+///
+/// `!` binds most tightly, followed by `<->`, `&`, and `|`.
+fn parse_query(s: &str) -> Result<Option<QueryNode>, String> {
+    let mut scanner = Scanner::new(s, true);
+    scanner.skip_whitespace();
+    if scanner.peek().is_none() {
+        return Ok(None);
+    }
+    let node = parse_or(&mut scanner)?;
+    scanner.skip_whitespace();
+    match scanner.peek() {
+        None => Ok(Some(node)),
+        Some(_) => Err("syntax error".into()),
+    }
+}
+
+fn parse_or(scanner: &mut Scanner) -> Result<QueryNode, String> {
+    let mut node = parse_and(scanner)?;
+    while scanner.consume('|') {
+        let right = parse_and(scanner)?;
+        node = QueryNode::Or(Box::new(node), Box::new(right));
+    }
+    Ok(node)
+}
+
+fn parse_and(scanner: &mut Scanner) -> Result<QueryNode, String> {
+    let mut node = parse_phrase(scanner)?;
+    while scanner.consume('&') {
+        let right = parse_phrase(scanner)?;
+        node = QueryNode::And(Box::new(node), Box::new(right));
+    }
+    Ok(node)
+}
+
+fn parse_phrase(scanner: &mut Scanner) -> Result<QueryNode, String> {
+    let mut node = parse_not(scanner)?;
+    while scanner.consume('<') {
+        let distance = if scanner.peek() == Some('-') {
+            scanner.next();
+            1
+        } else {
+            let distance = scanner.number()?;
+            u16::try_from(distance)
+                .ok()
+                .filter(|d| *d <= MAX_PHRASE_DISTANCE)
+                .ok_or_else(|| {
+                    format!(
+                        "distance in phrase operator must be an integer value between zero and {} inclusive",
+                        MAX_PHRASE_DISTANCE
+                    )
+                })?
+        };
+        if scanner.next() != Some('>') {
+            return Err("syntax error".into());
+        }
+        let right = parse_not(scanner)?;
+        node = QueryNode::Phrase(Box::new(node), Box::new(right), distance);
+    }
+    Ok(node)
+}
+
+fn parse_not(scanner: &mut Scanner) -> Result<QueryNode, String> {
+    if scanner.consume('!') {
+        let inner = scanner.nested(parse_not)?;
+        return Ok(QueryNode::Not(Box::new(inner)));
+    }
+    if scanner.consume('(') {
+        let node = scanner.nested(parse_or)?;
+        if !scanner.consume(')') {
+            return Err("syntax error".into());
+        }
+        return Ok(node);
+    }
+    scanner.skip_whitespace();
+    let Some(lexeme) = scanner.word()? else {
+        return Err("syntax error".into());
+    };
+    let mut operand = Operand {
+        lexeme,
+        prefix: false,
+        weights: 0,
+    };
+    if scanner.peek() == Some(':') {
+        scanner.next();
+        loop {
+            match scanner.peek() {
+                Some('*') => operand.prefix = true,
+                Some(c) => match Weight::from_char(c) {
+                    Some(weight) => operand.weights |= weight.mask(),
+                    None => break,
+                },
+                None => break,
+            }
+            scanner.next();
+        }
+    }
+    Ok(QueryNode::Operand(operand))
+}
+
+/// A cursor over the characters of a `tsvector` or `tsquery`.
+struct Scanner {
+    chars: Vec<char>,
+    pos: usize,
+    /// Whether the query operators are special characters.
+    in_query: bool,
+    recursion_guard: RecursionGuard,
+}
+
+impl CheckedRecursion for Scanner {
+    fn recursion_guard(&self) -> &RecursionGuard {
+        &self.recursion_guard
+    }
+}
+
+impl Scanner {
+    fn new(s: &str, in_query: bool) -> Scanner {
+        Scanner {
+            chars: s.chars().collect(),
+            pos: 0,
+            in_query,
+            recursion_guard: RecursionGuard::with_limit(1024), // chosen arbitrarily
+        }
+    }
+
+    /// Calls `f` one level of nesting deeper, failing if the query is nested
+    /// too deeply.
+    fn nested<T, F>(&mut self, f: F) -> Result<T, String>
+    where
+        F: FnOnce(&mut Self) -> Result<T, String>,
+    {
+        self.checked_recur_mut(|scanner| Ok(f(scanner)))
+            .map_err(|e: RecursionLimitError| e.to_string())?
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.chars.get(self.pos + n).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        if c.is_some() {
+            self.pos += 1;
+        }
+        c
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().map_or(false, |c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    /// Skips whitespace and consumes `c` if it is the next character.
+    fn consume(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn number(&mut self) -> Result<u32, String> {
+        let start = self.pos;
+        while self.peek().map_or(false, |c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let digits: String = self.chars[start..self.pos].iter().collect();
+        // Saturate rather than fail on overlong numbers; all callers clamp or
+        // range check the result.
+        match digits.parse::<u32>() {
+            Ok(n) => Ok(n),
+            Err(_) if !digits.is_empty() => Ok(u32::MAX),
+            Err(_) => Err("syntax error".into()),
+        }
+    }
+
+    /// Reads a lexeme, which is either quoted with single quotes or
+    /// terminated by whitespace or a special character. Within a lexeme, a
+    /// backslash escapes the following character, and within quotes, a
+    /// doubled quote stands for a single quote.
+    ///
+    /// Returns `None` at the end of the input.
+    fn word(&mut self) -> Result<Option<String>, String> {
+        self.skip_whitespace();
+        let Some(first) = self.peek() else {
+            return Ok(None);
+        };
+        let mut word = String::new();
+        if first == '\'' {
+            self.next();
+            loop {
+                match self.next() {
+                    None => return Err("unterminated quoted string".into()),
+                    Some('\\') => match self.next() {
+                        Some(c) => word.push(c),
+                        None => return Err("there is no escaped character".into()),
+                    },
+                    Some('\'') if self.peek() == Some('\'') => {
+                        self.next();
+                        word.push('\'');
+                    }
+                    Some('\'') => break,
+                    Some(c) => word.push(c),
+                }
+            }
+        } else {
+            while let Some(c) = self.peek() {
+                if c.is_whitespace()
+                    || c == ':'
+                    || (self.in_query && matches!(c, '(' | ')' | '&' | '|' | '!' | '<'))
+                {
+                    break;
+                }
+                self.next();
+                if c == '\\' {
+                    match self.next() {
+                        Some(c) => word.push(c),
+                        None => return Err("there is no escaped character".into()),
+                    }
+                } else {
+                    word.push(c);
+                }
+            }
+        }
+        if word.is_empty() {
+            return Err("syntax error".into());
+        }
+        if word.len() > MAX_LEXEME_LEN {
+            return Err(format!(
+                "word is too long ({} bytes, max {} bytes)",
+                word.len(),
+                MAX_LEXEME_LEN
+            ));
+        }
+        Ok(Some(word))
+    }
+}
+
+/// A cursor over a value in PostgreSQL's binary format.
+struct BinaryReader<'a>(&'a [u8]);
+
+impl<'a> BinaryReader<'a> {
+    fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        if self.0.len() < N {
+            return Err("insufficient data left in message".into());
+        }
+        let (bytes, rest) = self.0.split_at(N);
+        self.0 = rest;
+        Ok(bytes.try_into().expect("split at N"))
+    }
+
+    fn read_u8(&mut self) -> Result<u8, String> {
+        Ok(self.read_bytes::<1>()?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_be_bytes(self.read_bytes()?))
+    }
+
+    fn read_i32(&mut self) -> Result<i32, String> {
+        let n = i32::from_be_bytes(self.read_bytes()?);
+        if n < 0 {
+            return Err("invalid size".into());
+        }
+        Ok(n)
+    }
+
+    fn read_cstring(&mut self) -> Result<String, String> {
+        let Some(len) = self.0.iter().position(|b| *b == 0) else {
+            return Err("invalid string in message".into());
+        };
+        let s = std::str::from_utf8(&self.0[..len]).map_err(|e| e.to_string())?;
+        if s.is_empty() || s.len() > MAX_LEXEME_LEN {
+            return Err("invalid lexeme length".into());
+        }
+        self.0 = &self.0[len + 1..];
+        Ok(s.into())
+    }
+
+    fn finish(&self) -> Result<(), String> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err("invalid message format".into())
+        }
+    }
+}
+
+/// The stop words of the `english` configuration, which match PostgreSQL's
+/// `english.stop` list. Sorted, so that it can be binary searched.
+const ENGLISH_STOP_WORDS: &[&str] = &[
+    "a",
+    "about",
+    "above",
+    "after",
+    "again",
+    "against",
+    "all",
+    "am",
+    "an",
+    "and",
+    "any",
+    "are",
+    "as",
+    "at",
+    "be",
+    "because",
+    "been",
+    "before",
+    "being",
+    "below",
+    "between",
+    "both",
+    "but",
+    "by",
+    "can",
+    "did",
+    "do",
+    "does",
+    "doing",
+    "don",
+    "down",
+    "during",
+    "each",
+    "few",
+    "for",
+    "from",
+    "further",
+    "had",
+    "has",
+    "have",
+    "having",
+    "he",
+    "her",
+    "here",
+    "hers",
+    "herself",
+    "him",
+    "himself",
+    "his",
+    "how",
+    "i",
+    "if",
+    "in",
+    "into",
+    "is",
+    "it",
+    "its",
+    "itself",
+    "just",
+    "me",
+    "more",
+    "most",
+    "my",
+    "myself",
+    "no",
+    "nor",
+    "not",
+    "now",
+    "of",
+    "off",
+    "on",
+    "once",
+    "only",
+    "or",
+    "other",
+    "our",
+    "ours",
+    "ourselves",
+    "out",
+    "over",
+    "own",
+    "s",
+    "same",
+    "she",
+    "should",
+    "so",
+    "some",
+    "such",
+    "t",
+    "than",
+    "that",
+    "the",
+    "their",
+    "theirs",
+    "them",
+    "themselves",
+    "then",
+    "there",
+    "these",
+    "they",
+    "this",
+    "those",
+    "through",
+    "to",
+    "too",
+    "under",
+    "until",
+    "up",
+    "very",
+    "was",
+    "we",
+    "were",
+    "what",
+    "when",
+    "where",
+    "which",
+    "while",
+    "who",
+    "whom",
+    "why",
+    "will",
+    "with",
+    "you",
+    "your",
+    "yours",
+    "yourself",
+    "yourselves",
+];
+
+/// Reduces a lowercase ASCII word to its stem with the Snowball English
+/// (Porter2) stemming algorithm.
+///
+/// See <https://snowballstem.org/algorithms/english/stemmer.html>.
+fn english_stem(word: &str) -> String {
+    if word.len() <= 2 {
+        return word.into();
+    }
+    match word {
+        "skis" => return "ski".into(),
+        "skies" => return "sky".into(),
+        "dying" => return "die".into(),
+        "lying" => return "lie".into(),
+        "tying" => return "tie".into(),
+        "idly" => return "idl".into(),
+        "gently" => return "gentl".into(),
+        "ugly" => return "ugli".into(),
+        "early" => return "earli".into(),
+        "only" => return "onli".into(),
+        "singly" => return "singl".into(),
+        "sky" | "news" | "howe" | "atlas" | "cosmos" | "bias" | "andes" => return word.into(),
+        _ => {}
+    }
+
+    let mut w = Stemmer::new(word);
+    w.step_1a();
+    if matches!(
+        w.as_str(),
+        "inning" | "outing" | "canning" | "herring" | "earring" | "proceed" | "exceed" | "succeed"
+    ) {
+        return w.finish();
+    }
+    w.step_1b();
+    w.step_1c();
+    w.step_2();
+    w.step_3();
+    w.step_4();
+    w.step_5();
+    w.finish()
+}
+
+/// The state of the Porter2 algorithm.
+///
+/// A `y` that acts as a consonant is stored as `Y` while the algorithm runs.
+struct Stemmer {
+    w: Vec<u8>,
+    /// The start of the region after the first non-vowel following a vowel.
+    r1: usize,
+    /// The start of the region after the first non-vowel following a vowel
+    /// in R1.
+    r2: usize,
+}
+
+impl Stemmer {
+    fn new(word: &str) -> Stemmer {
+        let mut w = word.as_bytes().to_vec();
+        for i in 0..w.len() {
+            if w[i] == b'y' && (i == 0 || is_vowel(w[i - 1])) {
+                w[i] = b'Y';
+            }
+        }
+        let r1 = ["gener", "commun", "arsen"]
+            .iter()
+            .find(|p| w.starts_with(p.as_bytes()))
+            .map(|p| p.len())
+            .unwrap_or_else(|| region_after(&w, 0));
+        let r2 = region_after(&w, r1);
+        Stemmer { w, r1, r2 }
+    }
+
+    fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.w).expect("stemmer operates on ASCII")
+    }
+
+    fn finish(self) -> String {
+        self.as_str().replace('Y', "y")
+    }
+
+    fn ends_with(&self, suffix: &str) -> bool {
+        self.w.ends_with(suffix.as_bytes())
+    }
+
+    /// The index at which `suffix` starts, assuming the word ends with it.
+    fn suffix_start(&self, suffix: &str) -> usize {
+        self.w.len() - suffix.len()
+    }
+
+    fn replace_suffix(&mut self, suffix: &str, replacement: &str) {
+        self.w.truncate(self.suffix_start(suffix));
+        self.w.extend(replacement.as_bytes());
+    }
+
+    /// Returns the longest suffix in `suffixes` that the word ends with.
+    fn longest_suffix<'a>(&self, suffixes: &[(&'a str, &'a str)]) -> Option<(&'a str, &'a str)> {
+        suffixes
+            .iter()
+            .filter(|(suffix, _)| self.ends_with(suffix))
+            .max_by_key(|(suffix, _)| suffix.len())
+            .copied()
+    }
+
+    fn contains_vowel(&self, end: usize) -> bool {
+        self.w[..end].iter().any(|c| is_vowel(*c))
+    }
+
+    /// Reports whether `w[..end]` ends in a short syllable: a vowel followed
+    /// by a non-vowel other than `w`, `x`, or `Y` and preceded by a non-vowel,
+    /// or a vowel at the beginning of the word followed by a non-vowel.
+    fn ends_in_short_syllable(&self, end: usize) -> bool {
+        let w = &self.w[..end];
+        match w.len() {
+            0 | 1 => false,
+            2 => is_vowel(w[0]) && !is_vowel(w[1]),
+            n => {
+                !is_vowel(w[n - 3])
+                    && is_vowel(w[n - 2])
+                    && !is_vowel(w[n - 1])
+                    && !matches!(w[n - 1], b'w' | b'x' | b'Y')
+            }
+        }
+    }
+
+    fn is_short(&self) -> bool {
+        self.r1 >= self.w.len() && self.ends_in_short_syllable(self.w.len())
+    }
+
+    fn step_1a(&mut self) {
+        if self.ends_with("sses") {
+            self.replace_suffix("sses", "ss");
+        } else if self.ends_with("ied") || self.ends_with("ies") {
+            // Both suffixes are three letters long.
+            let replacement = if self.w.len() > 4 { "i" } else { "ie" };
+            self.replace_suffix("ies", replacement);
+        } else if self.ends_with("s")
+            && !self.ends_with("us")
+            && !self.ends_with("ss")
+            && self.contains_vowel(self.w.len() - 2)
+        {
+            self.w.pop();
+        }
+    }
+
+    fn step_1b(&mut self) {
+        let suffixes = [
+            ("eedly", ""),
+            ("ingly", ""),
+            ("edly", ""),
+            ("eed", ""),
+            ("ing", ""),
+            ("ed", ""),
+        ];
+        let Some((suffix, _)) = self.longest_suffix(&suffixes) else {
+            return;
+        };
+        if suffix == "eed" || suffix == "eedly" {
+            if self.suffix_start(suffix) >= self.r1 {
+                self.replace_suffix(suffix, "ee");
+            }
+            return;
+        }
+        if !self.contains_vowel(self.suffix_start(suffix)) {
+            return;
+        }
+        self.replace_suffix(suffix, "");
+        if self.ends_with("at") || self.ends_with("bl") || self.ends_with("iz") {
+            self.w.push(b'e');
+        } else if ["bb", "dd", "ff", "gg", "mm", "nn", "pp", "rr", "tt"]
+            .iter()
+            .any(|d| self.ends_with(d))
+        {
+            self.w.pop();
+        } else if self.is_short() {
+            self.w.push(b'e');
+        }
+    }
+
+    fn step_1c(&mut self) {
+        let n = self.w.len();
+        if n > 2 && matches!(self.w[n - 1], b'y' | b'Y') && !is_vowel(self.w[n - 2]) {
+            self.w[n - 1] = b'i';
+        }
+    }
+
+    fn step_2(&mut self) {
+        let suffixes = [
+            ("ization", "ize"),
+            ("ational", "ate"),
+            ("fulness", "ful"),
+            ("ousness", "ous"),
+            ("iveness", "ive"),
+            ("tional", "tion"),
+            ("biliti", "ble"),
+            ("lessli", "less"),
+            ("entli", "ent"),
+            ("ation", "ate"),
+            ("alism", "al"),
+            ("aliti", "al"),
+            ("ousli", "ous"),
+            ("iviti", "ive"),
+            ("fulli", "ful"),
+            ("enci", "ence"),
+            ("anci", "ance"),
+            ("abli", "able"),
+            ("izer", "ize"),
+            ("ator", "ate"),
+            ("alli", "al"),
+            ("bli", "ble"),
+            ("ogi", "og"),
+            ("li", ""),
+        ];
+        let Some((suffix, replacement)) = self.longest_suffix(&suffixes) else {
+            return;
+        };
+        let start = self.suffix_start(suffix);
+        if start < self.r1 {
+            return;
+        }
+        let preceding = start.checked_sub(1).map(|i| self.w[i]);
+        let valid = match suffix {
+            "ogi" => preceding == Some(b'l'),
+            "li" => matches!(
+                preceding,
+                Some(b'c' | b'd' | b'e' | b'g' | b'h' | b'k' | b'm' | b'n' | b'r' | b't')
+            ),
+            _ => true,
+        };
+        if valid {
+            self.replace_suffix(suffix, replacement);
+        }
+    }
+
+    fn step_3(&mut self) {
+        let suffixes = [
+            ("ational", "ate"),
+            ("tional", "tion"),
+            ("alize", "al"),
+            ("icate", "ic"),
+            ("iciti", "ic"),
+            ("ative", ""),
+            ("ical", "ic"),
+            ("ness", ""),
+            ("ful", ""),
+        ];
+        let Some((suffix, replacement)) = self.longest_suffix(&suffixes) else {
+            return;
+        };
+        let start = self.suffix_start(suffix);
+        let region = if suffix == "ative" { self.r2 } else { self.r1 };
+        if start >= region {
+            self.replace_suffix(suffix, replacement);
+        }
+    }
+
+    fn step_4(&mut self) {
+        let suffixes = [
+            ("ement", ""),
+            ("ance", ""),
+            ("ence", ""),
+            ("able", ""),
+            ("ible", ""),
+            ("ment", ""),
+            ("ant", ""),
+            ("ent", ""),
+            ("ism", ""),
+            ("ate", ""),
+            ("iti", ""),
+            ("ous", ""),
+            ("ive", ""),
+            ("ize", ""),
+            ("ion", ""),
+            ("al", ""),
+            ("er", ""),
+            ("ic", ""),
+        ];
+        let Some((suffix, _)) = self.longest_suffix(&suffixes) else {
+            return;
+        };
+        let start = self.suffix_start(suffix);
+        if start < self.r2 {
+            return;
+        }
+        if suffix == "ion" && !matches!(start.checked_sub(1).map(|i| self.w[i]), Some(b's' | b't'))
+        {
+            return;
+        }
+        self.replace_suffix(suffix, "");
+    }
+
+    fn step_5(&mut self) {
+        let Some(start) = self.w.len().checked_sub(1) else {
+            return;
+        };
+        match self.w[start] {
+            b'e' if start >= self.r2
+                || (start >= self.r1 && !self.ends_in_short_syllable(start)) =>
+            {
+                self.w.pop();
+            }
+            b'l' if start >= self.r2 && self.ends_with("ll") => {
+                self.w.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_vowel(c: u8) -> bool {
+    matches!(c, b'a' | b'e' | b'i' | b'o' | b'u' | b'y')
+}
+
+/// Returns the index after the first non-vowel that follows a vowel, at or
+/// after `start`, or the length of the word if there is none.
+fn region_after(w: &[u8], start: usize) -> usize {
+    for i in start + 1..w.len() {
+        if is_vowel(w[i - 1]) && !is_vowel(w[i]) {
+            return i + 1;
+        }
+    }
+    w.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_tsvector_display() {
+        for (input, expected) in [
+            ("", ""),
+            ("a fat cat", "'a' 'cat' 'fat'"),
+            ("cat:3 fat:2,1A cat:1", "'cat':1,3 'fat':1A,2"),
+            ("'don''t' 'a\\\\b'", "'a\\\\b' 'don''t'"),
+            ("x:2b,2A,99999", "'x':2A,16383"),
+        ] {
+            let vector: TsVector = input.parse().unwrap();
+            assert_eq!(vector.to_string(), expected, "display of {input}");
+            let reparsed: TsVector = expected.parse().unwrap();
+            assert_eq!(reparsed, vector, "round trip of {input}");
+            let decoded = TsVector::from_binary(&vector.to_binary()).unwrap();
+            assert_eq!(decoded, vector, "binary round trip of {input}");
+        }
+
+        for input in ["'a", "a:0", "a:1x", "a:"] {
+            assert!(input.parse::<TsVector>().is_err(), "parsing {input}");
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_tsquery_display() {
+        for (input, expected) in [
+            ("", ""),
+            ("a", "'a'"),
+            ("a & b | c", "'a' & 'b' | 'c'"),
+            ("a & (b | c)", "'a' & ( 'b' | 'c' )"),
+            ("!a & !(b | c)", "!'a' & !( 'b' | 'c' )"),
+            ("a <-> b <2> c", "'a' <-> 'b' <2> 'c'"),
+            ("a <-> (b <-> c)", "'a' <-> ( 'b' <-> 'c' )"),
+            ("a:* & b:Ab & c:*d", "'a':* & 'b':AB & 'c':*D"),
+            ("'it''s'", "'it''s'"),
+        ] {
+            let query: TsQuery = input.parse().unwrap();
+            assert_eq!(query.to_string(), expected, "display of {input}");
+            let reparsed: TsQuery = expected.parse().unwrap();
+            assert_eq!(reparsed.to_string(), expected, "round trip of {input}");
+            let decoded = TsQuery::from_binary(&query.to_binary()).unwrap();
+            assert_eq!(decoded, query, "binary round trip of {input}");
+        }
+
+        for input in ["a b", "a &", "(a", "a <x> b", "a <99999> b", "&"] {
+            assert!(input.parse::<TsQuery>().is_err(), "parsing {input}");
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_tsquery_nesting_limit() {
+        for input in [
+            format!("{}a", "!".repeat(100_000)),
+            format!("{}a{}", "(".repeat(100_000), ")".repeat(100_000)),
+        ] {
+            let err = input.parse::<TsQuery>().unwrap_err();
+            assert!(err.contains("recursion limit"), "{err}");
+        }
+        assert!(format!("{}a", "!".repeat(100)).parse::<TsQuery>().is_ok());
+    }
+
+    #[mz_ore::test]
+    fn test_english_stop_words_sorted() {
+        assert!(ENGLISH_STOP_WORDS.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[mz_ore::test]
+    fn test_english_stem() {
+        for (word, stem) in [
+            ("caresses", "caress"),
+            ("ponies", "poni"),
+            ("ties", "tie"),
+            ("cats", "cat"),
+            ("gas", "gas"),
+            ("running", "run"),
+            ("hoped", "hope"),
+            ("agreed", "agre"),
+            ("cry", "cri"),
+            ("happy", "happi"),
+            ("relational", "relat"),
+            ("conditional", "condit"),
+            ("descriptions", "descript"),
+            ("generously", "generous"),
+            ("foxes", "fox"),
+            ("jumped", "jump"),
+            ("skies", "sky"),
+            ("succeeding", "succeed"),
+        ] {
+            assert_eq!(english_stem(word), stem, "stem of {word}");
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_match() {
+        let vector = TsVector::from_document(
+            TextSearchConfig::English,
+            "The quick brown foxes jumped over the lazy dogs",
+        );
+        assert_eq!(
+            vector.to_string(),
+            "'brown':3 'dog':9 'fox':4 'jump':5 'lazi':8 'quick':2"
+        );
+        let matches = |query: &str| {
+            let query = TsQuery::parse_with_config(TextSearchConfig::English, query).unwrap();
+            vector.matches(&query)
+        };
+        assert!(matches("fox & dog"));
+        assert!(matches("fox | cat"));
+        assert!(!matches("fox & !dog"));
+        assert!(matches("quick <-> brown"));
+        assert!(!matches("brown <-> quick"));
+        assert!(matches("brown <2> jump"));
+        assert!(matches("jumped <-> over <-> the <-> lazy"));
+        assert!(matches("qui:*"));
+        assert!(matches("!cat <-> brown"));
+        assert!(!matches("!quick <-> brown"));
+        assert!(!matches("the"));
+    }
+
+    #[mz_ore::test]
+    fn test_rank() {
+        let rank = |document: &str, query: &str, normalization: i32| {
+            let vector = TsVector::from_document(TextSearchConfig::English, document);
+            let query = TsQuery::parse_with_config(TextSearchConfig::English, query).unwrap();
+            vector.rank(&query, &DEFAULT_RANK_WEIGHTS, normalization)
+        };
+        assert_eq!(rank("The quick brown fox", "fox", 0), 0.06079271);
+        assert_eq!(rank("The quick brown fox", "cat", 0), 0.0);
+        assert!(rank("fox dog", "fox & dog", 0) > rank("fox a b c d e f dog", "fox & dog", 0));
+        assert!(rank("fox fox fox", "fox", 0) > rank("fox", "fox", 0));
+        assert!(rank("fox fox fox", "fox", 2) < rank("fox", "fox", 2));
+
+        let mut vector = TsVector::from_document(TextSearchConfig::Simple, "fox");
+        let query: TsQuery = "fox".parse().unwrap();
+        let unweighted = vector.rank(&query, &DEFAULT_RANK_WEIGHTS, 0);
+        vector.set_weight(Weight::A);
+        assert!(vector.rank(&query, &DEFAULT_RANK_WEIGHTS, 0) > unweighted);
+        assert!(vector.matches(&"fox:A".parse().unwrap()));
+        assert!(!vector.matches(&"fox:B".parse().unwrap()));
+    }
+
+    #[mz_ore::test]
+    fn test_concat() {
+        let a = TsVector::from_document(TextSearchConfig::Simple, "a b");
+        let b = TsVector::from_document(TextSearchConfig::Simple, "b c");
+        assert_eq!(a.concat(&b).to_string(), "'a':1 'b':2,3 'c':4");
+        let stripped: TsVector = "a b".parse().unwrap();
+        assert_eq!(stripped.concat(&b).to_string(), "'a' 'b':1 'c':2");
+    }
+
+    #[mz_ore::test]
+    fn test_remove_stop_words() {
+        for (input, expected) in [
+            ("the & fat", "'fat'"),
+            ("fat <-> the <-> rats", "'fat' <2> 'rat'"),
+            ("the | a", ""),
+            ("!the & cat", "'cat'"),
+        ] {
+            let query = TsQuery::parse_with_config(TextSearchConfig::English, input).unwrap();
+            assert_eq!(query.to_string(), expected, "cleaning {input}");
+        }
+    }
+}
@@ -0,0 +1,177 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Refresh schedules of materialized views created with `REFRESH` options.
+
+use std::time::Duration;
+
+use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use crate::Timestamp;
+
+include!(concat!(env!("OUT_DIR"), "/mz_repr.refresh_schedule.rs"));
+
+/// The times at which a materialized view refreshes its contents.
+///
+/// Between two refreshes, the contents of the materialized view do not change.
+#[derive(Arbitrary, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RefreshSchedule {
+    /// Refreshes that repeat periodically (`REFRESH EVERY`).
+    pub everies: Vec<RefreshEvery>,
+    /// Refreshes that happen once (`REFRESH AT`).
+    pub ats: Vec<Timestamp>,
+}
+
+impl RefreshSchedule {
+    /// Rounds `timestamp` up to the closest refresh time at or after it.
+    ///
+    /// Returns `None` if there is no refresh at or after `timestamp`.
+    pub fn round_up_timestamp(&self, timestamp: Timestamp) -> Option<Timestamp> {
+        let everies = self
+            .everies
+            .iter()
+            .filter_map(|every| every.round_up_timestamp(timestamp));
+        let ats = self.ats.iter().copied().filter(|at| *at >= timestamp);
+        everies.chain(ats).min()
+    }
+
+    /// Returns the time of the last refresh, or `None` if the schedule
+    /// repeats indefinitely or is empty.
+    pub fn last_refresh(&self) -> Option<Timestamp> {
+        if self.everies.is_empty() {
+            self.ats.iter().max().copied()
+        } else {
+            None
+        }
+    }
+}
+
+/// A periodic refresh, happening every `interval`, at times aligned to
+/// `aligned_to`.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RefreshEvery {
+    pub interval: Duration,
+    pub aligned_to: Timestamp,
+}
+
+impl RefreshEvery {
+    /// Rounds `timestamp` up to the closest time of the form
+    /// `aligned_to + n * interval` at or after it, for some (possibly
+    /// negative) integer `n`.
+    ///
+    /// Returns `None` if the resulting time does not fit in a `Timestamp`.
+    pub fn round_up_timestamp(&self, timestamp: Timestamp) -> Option<Timestamp> {
+        let interval = u64::try_from(self.interval.as_millis())
+            .unwrap_or(u64::MAX)
+            .max(1);
+        let timestamp = u64::from(timestamp);
+        let aligned_to = u64::from(self.aligned_to);
+        let rounded = if timestamp <= aligned_to {
+            aligned_to - (aligned_to - timestamp) / interval * interval
+        } else {
+            let steps = (timestamp - aligned_to - 1) / interval + 1;
+            aligned_to.checked_add(steps.checked_mul(interval)?)?
+        };
+        Some(Timestamp::from(rounded))
+    }
+}
+
+impl RustType<ProtoRefreshSchedule> for RefreshSchedule {
+    fn into_proto(&self) -> ProtoRefreshSchedule {
+        ProtoRefreshSchedule {
+            everies: self.everies.into_proto(),
+            ats: self.ats.iter().map(u64::from).collect(),
+        }
+    }
+
+    fn from_proto(proto: ProtoRefreshSchedule) -> Result<Self, TryFromProtoError> {
+        Ok(RefreshSchedule {
+            everies: proto.everies.into_rust()?,
+            ats: proto.ats.into_iter().map(Timestamp::from).collect(),
+        })
+    }
+}
+
+impl RustType<ProtoRefreshEvery> for RefreshEvery {
+    fn into_proto(&self) -> ProtoRefreshEvery {
+        ProtoRefreshEvery {
+            interval: Some(self.interval.into_proto()),
+            aligned_to: self.aligned_to.into(),
+        }
+    }
+
+    fn from_proto(proto: ProtoRefreshEvery) -> Result<Self, TryFromProtoError> {
+        Ok(RefreshEvery {
+            interval: proto
+                .interval
+                .into_rust_if_some("ProtoRefreshEvery::interval")?,
+            aligned_to: proto.aligned_to.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_round_up_timestamp() {
+        let every = RefreshEvery {
+            interval: Duration::from_millis(100),
+            aligned_to: Timestamp::from(1050),
+        };
+        let schedule = RefreshSchedule {
+            everies: vec![every.clone()],
+            ats: vec![Timestamp::from(1070)],
+        };
+
+        for (ts, expected) in [
+            (0, 50),
+            (50, 50),
+            (51, 150),
+            (1050, 1050),
+            (1051, 1150),
+            (1149, 1150),
+        ] {
+            assert_eq!(
+                every.round_up_timestamp(Timestamp::from(ts)),
+                Some(Timestamp::from(expected)),
+                "rounding {ts}"
+            );
+        }
+        assert_eq!(
+            every.round_up_timestamp(Timestamp::from(u64::MAX)),
+            None,
+            "rounding past the maximum timestamp"
+        );
+
+        assert_eq!(
+            schedule.round_up_timestamp(Timestamp::from(1051)),
+            Some(Timestamp::from(1070))
+        );
+        assert_eq!(
+            schedule.round_up_timestamp(Timestamp::from(1071)),
+            Some(Timestamp::from(1150))
+        );
+        assert_eq!(schedule.last_refresh(), None);
+
+        let once = RefreshSchedule {
+            everies: vec![],
+            ats: vec![Timestamp::from(10), Timestamp::from(20)],
+        };
+        assert_eq!(
+            once.round_up_timestamp(Timestamp::from(11)),
+            Some(Timestamp::from(20))
+        );
+        assert_eq!(once.round_up_timestamp(Timestamp::from(21)), None);
+        assert_eq!(once.last_refresh(), Some(Timestamp::from(20)));
+    }
+}
@@ -35,6 +35,7 @@ use crate::adt::array::{
 use crate::adt::date::Date;
 use crate::adt::interval::Interval;
 use crate::adt::mz_acl_item::{AclItem, MzAclItem};
+use crate::adt::network::{Inet, MacAddr};
 use crate::adt::numeric;
 use crate::adt::numeric::Numeric;
 use crate::adt::range::{
@@ -175,6 +176,7 @@ impl Ord for Row {
 mod columnation {
 
     use columnation::{Columnation, Region, StableRegion};
+    use mz_ore::region::Region as SpilledRegion;
 
     use crate::Row;
 
@@ -182,9 +184,23 @@ mod columnation {
     ///
     /// Content bytes are stored in stable contiguous memory locations,
     /// and then a `Row` referencing them is falsified.
+    ///
+    /// When arrangement batches are merged, the bytes of the merged batch
+    /// are reserved in one go through [`Region::reserve_regions`]. If the
+    /// worker has arrangement spilling enabled and the merged batch is large
+    /// enough, they are placed in a [`SpilledRegion`] instead, which the
+    /// kernel can page out to disk.
     #[derive(Default)]
     pub struct RowStack {
         region: StableRegion<u8>,
+        spilled: Option<SpilledRegion>,
+    }
+
+    impl RowStack {
+        /// Returns the number of bytes in use.
+        fn len(&self) -> usize {
+            self.region.len() + self.spilled.as_ref().map_or(0, |s| s.len())
+        }
     }
 
     impl Columnation for Row {
@@ -196,11 +212,17 @@ mod columnation {
         #[inline]
         fn clear(&mut self) {
             self.region.clear();
+            self.spilled = None;
         }
         #[inline(always)]
         unsafe fn copy(&mut self, item: &Row) -> Row {
             if item.data.spilled() {
-                let bytes = self.region.copy_slice(&item.data[..]);
+                let bytes = match &mut self.spilled {
+                    Some(spilled) if spilled.capacity() - spilled.len() >= item.data.len() => {
+                        spilled.extend_from_slice(&item.data[..])
+                    }
+                    _ => self.region.copy_slice(&item.data[..]),
+                };
                 Row {
                     data: smallvec::SmallVec::from_raw_parts(
                         bytes.as_mut_ptr(),
@@ -231,11 +253,21 @@ mod columnation {
             Self: 'a,
             I: Iterator<Item = &'a Self> + Clone,
         {
-            self.region.reserve(regions.map(|r| r.region.len()).sum());
+            let count = regions.map(|r| r.len()).sum();
+            if self.spilled.is_none() {
+                self.spilled = SpilledRegion::new_spilled(count);
+            }
+            match &self.spilled {
+                Some(spilled) if spilled.capacity() - spilled.len() >= count => {}
+                _ => self.region.reserve(count),
+            }
         }
 
-        fn heap_size(&self, callback: impl FnMut(usize, usize)) {
-            self.region.heap_size(callback)
+        fn heap_size(&self, mut callback: impl FnMut(usize, usize)) {
+            self.region.heap_size(&mut callback);
+            if let Some(spilled) = &self.spilled {
+                spilled.heap_size(callback);
+            }
         }
     }
 }
@@ -415,6 +447,8 @@ enum Tag {
     Range,
     MzAclItem,
     AclItem,
+    Inet,
+    MacAddr,
     // Only ever used if the `VARIABLE_LENGTH_ENCODING` global is set.
     // Everything except leap seconds and times beyond the range of
     // i64 nanoseconds. (Note that Materialize does not support leap
@@ -942,6 +976,13 @@ unsafe fn read_datum<'a>(data: &'a [u8], offset: &mut usize) -> Datum<'a> {
                 .expect("invalid aclitem");
             Datum::AclItem(acl_item)
         }
+        Tag::Inet => {
+            const N: usize = Inet::binary_size();
+            let inet =
+                Inet::decode_binary(&read_byte_array::<N>(data, offset)).expect("invalid inet");
+            Datum::Inet(inet)
+        }
+        Tag::MacAddr => Datum::MacAddr(MacAddr(read_byte_array(data, offset))),
     }
 }
 
@@ -1287,6 +1328,14 @@ where
             data.push(Tag::AclItem.into());
             data.extend_from_slice(&acl_item.encode_binary());
         }
+        Datum::Inet(inet) => {
+            data.push(Tag::Inet.into());
+            data.extend_from_slice(&inet.encode_binary());
+        }
+        Datum::MacAddr(mac_addr) => {
+            data.push(Tag::MacAddr.into());
+            data.extend_from_slice(&mac_addr.0);
+        }
     }
 }
 
@@ -1427,6 +1476,8 @@ pub fn datum_size_deterministic<const VAR: bool>(datum: &Datum) -> usize {
         }
         Datum::MzAclItem(_) => 1 + MzAclItem::binary_size(),
         Datum::AclItem(_) => 1 + AclItem::binary_size(),
+        Datum::Inet(_) => 1 + Inet::binary_size(),
+        Datum::MacAddr(_) => 1 + MacAddr::binary_size(),
     }
 }
 
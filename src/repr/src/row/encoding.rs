@@ -11,6 +11,8 @@
 //!
 //! See row.proto for details.
 
+use std::collections::BTreeSet;
+
 use bytes::BufMut;
 use chrono::Timelike;
 use dec::Decimal;
@@ -30,6 +32,7 @@ use uuid::Uuid;
 use crate::adt::array::ArrayDimension;
 use crate::adt::date::{Date, ProtoDate};
 use crate::adt::jsonb::Jsonb;
+use crate::adt::network::{Inet, MacAddr};
 use crate::adt::numeric::Numeric;
 use crate::adt::range::{Range, RangeInner, RangeLowerBound, RangeUpperBound};
 use crate::chrono::ProtoNaiveTime;
@@ -115,17 +118,42 @@ impl ColumnType {
             (true, PgLegacyChar) => Some(f.call::<Option<u8>>()),
             (false, Bytes) => Some(f.call::<Vec<u8>>()),
             (true, Bytes) => Some(f.call::<Option<Vec<u8>>>()),
-            (false, String | Char { .. } | VarChar { .. } | PgLegacyName) => {
-                Some(f.call::<std::string::String>())
-            }
-            (true, String | Char { .. } | VarChar { .. } | PgLegacyName) => {
-                Some(f.call::<Option<std::string::String>>())
-            }
+            (
+                false,
+                String
+                | Char { .. }
+                | VarChar { .. }
+                | PgLegacyName
+                | JsonPath
+                | TsVector
+                | TsQuery,
+            ) => Some(f.call::<std::string::String>()),
+            (
+                true,
+                String
+                | Char { .. }
+                | VarChar { .. }
+                | PgLegacyName
+                | JsonPath
+                | TsVector
+                | TsQuery,
+            ) => Some(f.call::<Option<std::string::String>>()),
             (false, Jsonb) => Some(f.call::<crate::adt::jsonb::Jsonb>()),
             (true, Jsonb) => Some(f.call::<Option<crate::adt::jsonb::Jsonb>>()),
             (false, MzTimestamp) => Some(f.call::<crate::Timestamp>()),
             (true, MzTimestamp) => Some(f.call::<Option<crate::Timestamp>>()),
-            (_, Numeric { .. } | Time | Timestamp | TimestampTz | Interval | Uuid) => {
+            (
+                _,
+                Numeric { .. }
+                | Time
+                | Timestamp
+                | TimestampTz
+                | Interval
+                | Uuid
+                | Inet
+                | Cidr
+                | MacAddr,
+            ) => {
                 if *nullable {
                     Some(f.call::<NullableProtoDatumToPersist>())
                 } else {
@@ -843,6 +871,8 @@ impl<'a> From<Datum<'a>> for ProtoDatum {
             })),
             Datum::MzAclItem(x) => DatumType::MzAclItem(x.into_proto()),
             Datum::AclItem(x) => DatumType::AclItem(x.into_proto()),
+            Datum::Inet(x) => DatumType::Inet(x.encode_binary().to_vec()),
+            Datum::MacAddr(x) => DatumType::MacAddr(x.0.to_vec()),
         };
         ProtoDatum {
             datum_type: Some(datum_type),
@@ -992,6 +1022,13 @@ impl RowPacker<'_> {
             }
             Some(DatumType::MzAclItem(x)) => self.push(Datum::MzAclItem(x.clone().into_rust()?)),
             Some(DatumType::AclItem(x)) => self.push(Datum::AclItem(x.clone().into_rust()?)),
+            Some(DatumType::Inet(x)) => self.push(Datum::Inet(Inet::decode_binary(x)?)),
+            Some(DatumType::MacAddr(x)) => {
+                let bytes = x.as_slice().try_into().map_err(|_| {
+                    format!("invalid macaddr length, expecting 6, found {}", x.len())
+                })?;
+                self.push(Datum::MacAddr(MacAddr(bytes)))
+            }
             None => return Err("unknown datum type".into()),
         };
         Ok(())
@@ -1014,6 +1051,38 @@ impl TryFrom<&ProtoRow> for Row {
     }
 }
 
+impl Row {
+    /// Decodes `proto` into a row of `arity` columns.
+    ///
+    /// Only the columns whose indexes are in `demand`, or all columns if `demand` is `None`, are
+    /// decoded, and all others are [Datum::Null]. The result always has `arity` columns, so column
+    /// references into it remain valid, but the values of columns that are never read are not
+    /// converted.
+    ///
+    /// Rows written before columns were added to their collection lack the added columns, which
+    /// decode as [Datum::Null], and columns that were added after a reader's description of the
+    /// collection was taken are dropped.
+    pub fn decode_projected(
+        proto: &ProtoRow,
+        demand: Option<&BTreeSet<usize>>,
+        arity: usize,
+    ) -> Result<Row, String> {
+        let mut row = Row::default();
+        let mut packer = row.packer();
+        for (idx, d) in proto.datums.iter().take(arity).enumerate() {
+            if demand.map_or(true, |demand| demand.contains(&idx)) {
+                packer.try_push_proto(d)?;
+            } else {
+                packer.push(Datum::Null);
+            }
+        }
+        for _ in proto.datums.len()..arity {
+            packer.push(Datum::Null);
+        }
+        Ok(row)
+    }
+}
+
 impl RustType<ProtoRow> for Row {
     fn into_proto(&self) -> ProtoRow {
         let datums = self.iter().map(|x| x.into()).collect();
@@ -1102,6 +1171,8 @@ mod tests {
             Datum::from(Numeric::nan()),
             Datum::JsonNull,
             Datum::Uuid(Uuid::from_u128(30)),
+            Datum::Inet(Inet::parse_inet("192.168.1.5/24").unwrap()),
+            Datum::MacAddr(MacAddr([8, 0, 43, 1, 2, 3])),
             Datum::Dummy,
             Datum::Null,
         ]);
@@ -1236,4 +1307,44 @@ mod tests {
             scalar_type_columnar_roundtrip(scalar_type)
         });
     }
+
+    #[mz_ore::test]
+    fn decode_projected() {
+        let row = Row::pack_slice(&[
+            Datum::Int64(1),
+            Datum::String("unused"),
+            Datum::Null,
+            Datum::True,
+        ]);
+        let proto = row.into_proto();
+
+        let projected = Row::decode_projected(&proto, Some(&[0, 2, 3].into()), 4).unwrap();
+        assert_eq!(
+            projected,
+            Row::pack_slice(&[Datum::Int64(1), Datum::Null, Datum::Null, Datum::True])
+        );
+
+        let complete = Row::decode_projected(&proto, Some(&(0..4).collect()), 4).unwrap();
+        assert_eq!(complete, row);
+        let complete = Row::decode_projected(&proto, None, 4).unwrap();
+        assert_eq!(complete, row);
+
+        // Columns added after the row was written decode as NULL.
+        let extended = Row::decode_projected(&proto, None, 6).unwrap();
+        assert_eq!(
+            extended,
+            Row::pack_slice(&[
+                Datum::Int64(1),
+                Datum::String("unused"),
+                Datum::Null,
+                Datum::True,
+                Datum::Null,
+                Datum::Null,
+            ])
+        );
+
+        // Columns unknown to the reader are dropped.
+        let truncated = Row::decode_projected(&proto, Some(&[0].into()), 2).unwrap();
+        assert_eq!(truncated, Row::pack_slice(&[Datum::Int64(1), Datum::Null]));
+    }
 }
@@ -34,11 +34,14 @@ use crate::adt::char::{Char, CharLength};
 use crate::adt::date::Date;
 use crate::adt::interval::Interval;
 use crate::adt::jsonb::{Jsonb, JsonbRef};
+use crate::adt::jsonpath::JsonPath;
 use crate::adt::mz_acl_item::{AclItem, AclMode, MzAclItem};
+use crate::adt::network::{Inet, MacAddr};
 use crate::adt::numeric::{Numeric, NumericMaxScale};
 use crate::adt::pg_legacy_name::PgLegacyName;
 use crate::adt::range::{Range, RangeLowerBound, RangeUpperBound};
 use crate::adt::system::{Oid, PgLegacyChar, RegClass, RegProc, RegType};
+use crate::adt::text_search::{TsQuery, TsVector};
 use crate::adt::timestamp::{CheckedTimestamp, TimestampError};
 use crate::adt::varchar::{VarChar, VarCharMaxLength};
 pub use crate::relation_and_scalar::proto_scalar_type::ProtoRecordField;
@@ -133,6 +136,11 @@ pub enum Datum<'a> {
     /// A list of privileges granted to a user that uses [`Oid`]s for role references.
     /// This type is used primarily for compatibility with PostgreSQL.
     AclItem(AclItem),
+    /// An IPv4 or IPv6 address with its netmask, used by both the `inet` and
+    /// the `cidr` types.
+    Inet(Inet),
+    /// A MAC address.
+    MacAddr(MacAddr),
     /// A placeholder value.
     ///
     /// Dummy values are never meant to be observed. Many operations on `Datum`
@@ -881,6 +889,32 @@ impl<'a> Datum<'a> {
         }
     }
 
+    /// Unwraps the inet value within this datum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the datum is not [`Datum::Inet`].
+    #[track_caller]
+    pub fn unwrap_inet(&self) -> Inet {
+        match self {
+            Datum::Inet(inet) => *inet,
+            _ => panic!("Datum::unwrap_inet called on {:?}", self),
+        }
+    }
+
+    /// Unwraps the macaddr value within this datum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the datum is not [`Datum::MacAddr`].
+    #[track_caller]
+    pub fn unwrap_mac_addr(&self) -> MacAddr {
+        match self {
+            Datum::MacAddr(mac_addr) => *mac_addr,
+            _ => panic!("Datum::unwrap_mac_addr called on {:?}", self),
+        }
+    }
+
     /// Unwraps the acl_item value within this datum.
     ///
     /// # Panics
@@ -959,7 +993,10 @@ impl<'a> Datum<'a> {
                     (Datum::String(_), ScalarType::String)
                     | (Datum::String(_), ScalarType::VarChar { .. })
                     | (Datum::String(_), ScalarType::Char { .. })
-                    | (Datum::String(_), ScalarType::PgLegacyName) => true,
+                    | (Datum::String(_), ScalarType::PgLegacyName)
+                    | (Datum::String(_), ScalarType::JsonPath)
+                    | (Datum::String(_), ScalarType::TsVector)
+                    | (Datum::String(_), ScalarType::TsQuery) => true,
                     (Datum::String(_), _) => false,
                     (Datum::Uuid(_), ScalarType::Uuid) => true,
                     (Datum::Uuid(_), _) => false,
@@ -1014,6 +1051,11 @@ impl<'a> Datum<'a> {
                     (Datum::MzAclItem(_), _) => false,
                     (Datum::AclItem(_), ScalarType::AclItem) => true,
                     (Datum::AclItem(_), _) => false,
+                    (Datum::Inet(_), ScalarType::Inet) => true,
+                    (Datum::Inet(inet), ScalarType::Cidr) => inet.is_network(),
+                    (Datum::Inet(_), _) => false,
+                    (Datum::MacAddr(_), ScalarType::MacAddr) => true,
+                    (Datum::MacAddr(_), _) => false,
                 }
             }
         }
@@ -1239,6 +1281,20 @@ impl<'a> From<MzAclItem> for Datum<'a> {
     }
 }
 
+impl<'a> From<Inet> for Datum<'a> {
+    #[inline]
+    fn from(inet: Inet) -> Self {
+        Datum::Inet(inet)
+    }
+}
+
+impl<'a> From<MacAddr> for Datum<'a> {
+    #[inline]
+    fn from(mac_addr: MacAddr) -> Self {
+        Datum::MacAddr(mac_addr)
+    }
+}
+
 impl<'a, T> From<Option<T>> for Datum<'a>
 where
     Datum<'a>: From<T>,
@@ -1339,6 +1395,8 @@ impl fmt::Display for Datum<'_> {
             Datum::Range(i) => write!(f, "{}", i),
             Datum::MzAclItem(mz_acl_item) => write!(f, "{mz_acl_item}"),
             Datum::AclItem(acl_item) => write!(f, "{acl_item}"),
+            Datum::Inet(inet) => write!(f, "{inet}"),
+            Datum::MacAddr(mac_addr) => write!(f, "{mac_addr}"),
         }
     }
 }
@@ -1432,6 +1490,21 @@ pub enum ScalarType {
     ///   * [`Datum::List`]
     ///   * [`Datum::Map`]
     Jsonb,
+    /// A SQL/JSON path expression.
+    ///
+    /// Stored as a [`Datum::String`] holding the canonical representation of
+    /// the path.
+    JsonPath,
+    /// A document prepared for full text search.
+    ///
+    /// Stored as a [`Datum::String`] holding the canonical representation of
+    /// the vector.
+    TsVector,
+    /// A full text search query.
+    ///
+    /// Stored as a [`Datum::String`] holding the canonical representation of
+    /// the query.
+    TsQuery,
     /// The type of [`Datum::Uuid`].
     Uuid,
     /// The type of [`Datum::Array`].
@@ -1484,6 +1557,14 @@ pub enum ScalarType {
     MzAclItem,
     /// The type of [`Datum::AclItem`]
     AclItem,
+    /// An IPv4 or IPv6 host address with an optional netmask. Represented by
+    /// [`Datum::Inet`].
+    Inet,
+    /// An IPv4 or IPv6 network. Represented by [`Datum::Inet`], with no bits
+    /// set to the right of the netmask.
+    Cidr,
+    /// The type of [`Datum::MacAddr`]
+    MacAddr,
 }
 
 impl RustType<ProtoRecordField> for (ColumnName, ColumnType) {
@@ -1532,6 +1613,9 @@ impl RustType<ProtoScalarType> for ScalarType {
                 ScalarType::Bytes => Bytes(()),
                 ScalarType::String => String(()),
                 ScalarType::Jsonb => Jsonb(()),
+                ScalarType::JsonPath => JsonPath(()),
+                ScalarType::TsVector => TsVector(()),
+                ScalarType::TsQuery => TsQuery(()),
                 ScalarType::Uuid => Uuid(()),
                 ScalarType::Oid => Oid(()),
                 ScalarType::RegProc => RegProc(()),
@@ -1572,6 +1656,9 @@ impl RustType<ProtoScalarType> for ScalarType {
                 })),
                 ScalarType::MzAclItem => MzAclItem(()),
                 ScalarType::AclItem => AclItem(()),
+                ScalarType::Inet => Inet(()),
+                ScalarType::Cidr => Cidr(()),
+                ScalarType::MacAddr => MacAddr(()),
             }),
         }
     }
@@ -1603,6 +1690,9 @@ impl RustType<ProtoScalarType> for ScalarType {
             Bytes(()) => Ok(ScalarType::Bytes),
             String(()) => Ok(ScalarType::String),
             Jsonb(()) => Ok(ScalarType::Jsonb),
+            JsonPath(()) => Ok(ScalarType::JsonPath),
+            TsVector(()) => Ok(ScalarType::TsVector),
+            TsQuery(()) => Ok(ScalarType::TsQuery),
             Uuid(()) => Ok(ScalarType::Uuid),
             Oid(()) => Ok(ScalarType::Oid),
             RegProc(()) => Ok(ScalarType::RegProc),
@@ -1654,6 +1744,9 @@ impl RustType<ProtoScalarType> for ScalarType {
             }),
             MzAclItem(()) => Ok(ScalarType::MzAclItem),
             AclItem(()) => Ok(ScalarType::AclItem),
+            Inet(()) => Ok(ScalarType::Inet),
+            Cidr(()) => Ok(ScalarType::Cidr),
+            MacAddr(()) => Ok(ScalarType::MacAddr),
         }
     }
 }
@@ -2291,6 +2384,75 @@ impl<'a> AsColumnType for JsonbRef<'a> {
     }
 }
 
+impl<'a, E> DatumType<'a, E> for JsonPath {
+    fn nullable() -> bool {
+        false
+    }
+
+    fn try_from_result(res: Result<Datum<'a>, E>) -> Result<Self, Result<Datum<'a>, E>> {
+        match res {
+            Ok(Datum::String(s)) => s.parse().map_err(|_| res),
+            _ => Err(res),
+        }
+    }
+
+    fn into_result(self, temp_storage: &'a RowArena) -> Result<Datum<'a>, E> {
+        Ok(Datum::String(temp_storage.push_string(self.to_string())))
+    }
+}
+
+impl AsColumnType for JsonPath {
+    fn as_column_type() -> ColumnType {
+        ScalarType::JsonPath.nullable(false)
+    }
+}
+
+impl<'a, E> DatumType<'a, E> for TsVector {
+    fn nullable() -> bool {
+        false
+    }
+
+    fn try_from_result(res: Result<Datum<'a>, E>) -> Result<Self, Result<Datum<'a>, E>> {
+        match res {
+            Ok(Datum::String(s)) => s.parse().map_err(|_| res),
+            _ => Err(res),
+        }
+    }
+
+    fn into_result(self, temp_storage: &'a RowArena) -> Result<Datum<'a>, E> {
+        Ok(Datum::String(temp_storage.push_string(self.to_string())))
+    }
+}
+
+impl AsColumnType for TsVector {
+    fn as_column_type() -> ColumnType {
+        ScalarType::TsVector.nullable(false)
+    }
+}
+
+impl<'a, E> DatumType<'a, E> for TsQuery {
+    fn nullable() -> bool {
+        false
+    }
+
+    fn try_from_result(res: Result<Datum<'a>, E>) -> Result<Self, Result<Datum<'a>, E>> {
+        match res {
+            Ok(Datum::String(s)) => s.parse().map_err(|_| res),
+            _ => Err(res),
+        }
+    }
+
+    fn into_result(self, temp_storage: &'a RowArena) -> Result<Datum<'a>, E> {
+        Ok(Datum::String(temp_storage.push_string(self.to_string())))
+    }
+}
+
+impl AsColumnType for TsQuery {
+    fn as_column_type() -> ColumnType {
+        ScalarType::TsQuery.nullable(false)
+    }
+}
+
 impl AsColumnType for MzAclItem {
     fn as_column_type() -> ColumnType {
         ScalarType::MzAclItem.nullable(false)
@@ -2337,6 +2499,52 @@ impl<'a, E> DatumType<'a, E> for AclItem {
     }
 }
 
+impl AsColumnType for Inet {
+    fn as_column_type() -> ColumnType {
+        ScalarType::Inet.nullable(false)
+    }
+}
+
+impl<'a, E> DatumType<'a, E> for Inet {
+    fn nullable() -> bool {
+        false
+    }
+
+    fn try_from_result(res: Result<Datum<'a>, E>) -> Result<Self, Result<Datum<'a>, E>> {
+        match res {
+            Ok(Datum::Inet(inet)) => Ok(inet),
+            _ => Err(res),
+        }
+    }
+
+    fn into_result(self, _temp_storage: &'a RowArena) -> Result<Datum<'a>, E> {
+        Ok(Datum::Inet(self))
+    }
+}
+
+impl AsColumnType for MacAddr {
+    fn as_column_type() -> ColumnType {
+        ScalarType::MacAddr.nullable(false)
+    }
+}
+
+impl<'a, E> DatumType<'a, E> for MacAddr {
+    fn nullable() -> bool {
+        false
+    }
+
+    fn try_from_result(res: Result<Datum<'a>, E>) -> Result<Self, Result<Datum<'a>, E>> {
+        match res {
+            Ok(Datum::MacAddr(mac_addr)) => Ok(mac_addr),
+            _ => Err(res),
+        }
+    }
+
+    fn into_result(self, _temp_storage: &'a RowArena) -> Result<Datum<'a>, E> {
+        Ok(Datum::MacAddr(self))
+    }
+}
+
 impl<'a> ScalarType {
     /// Returns the contained numeric maximum scale.
     ///
@@ -2964,6 +3172,27 @@ impl<'a> ScalarType {
             // TODO: Add List, Map.
             Row::pack_slice(&datums)
         });
+        static JSONPATH: Lazy<Row> = Lazy::new(|| {
+            Row::pack_slice(&[
+                Datum::String("$"),
+                Datum::String("strict $.\"a\"[*]"),
+                Datum::String("$?(@ > 1)"),
+            ])
+        });
+        static TSVECTOR: Lazy<Row> = Lazy::new(|| {
+            Row::pack_slice(&[
+                Datum::String(""),
+                Datum::String("'a' 'b'"),
+                Datum::String("'brown':3 'fox':4A,16383 'it''s':1"),
+            ])
+        });
+        static TSQUERY: Lazy<Row> = Lazy::new(|| {
+            Row::pack_slice(&[
+                Datum::String(""),
+                Datum::String("'fox'"),
+                Datum::String("( 'a' | 'b':*A ) & !'c' <2> 'd'"),
+            ])
+        });
         static UUID: Lazy<Row> = Lazy::new(|| {
             Row::pack_slice(&[
                 Datum::Uuid(Uuid::from_u128(u128::MIN)),
@@ -3020,6 +3249,29 @@ impl<'a> ScalarType {
         });
         // aclitem has no binary encoding so we can't test it here.
         static ACLITEM: Lazy<Row> = Lazy::new(|| Row::pack_slice(&[]));
+        static INET: Lazy<Row> = Lazy::new(|| {
+            Row::pack_slice(&[
+                Datum::Inet(Inet::parse_inet("0.0.0.0/0").unwrap()),
+                Datum::Inet(Inet::parse_inet("192.168.1.5/24").unwrap()),
+                Datum::Inet(Inet::parse_inet("255.255.255.255").unwrap()),
+                Datum::Inet(Inet::parse_inet("::/0").unwrap()),
+                Datum::Inet(Inet::parse_inet("2001:db8::1/64").unwrap()),
+            ])
+        });
+        static CIDR: Lazy<Row> = Lazy::new(|| {
+            Row::pack_slice(&[
+                Datum::Inet(Inet::parse_cidr("0.0.0.0/0").unwrap()),
+                Datum::Inet(Inet::parse_cidr("192.168.1.0/24").unwrap()),
+                Datum::Inet(Inet::parse_cidr("2001:db8::/32").unwrap()),
+            ])
+        });
+        static MACADDR: Lazy<Row> = Lazy::new(|| {
+            Row::pack_slice(&[
+                Datum::MacAddr(MacAddr([0; 6])),
+                Datum::MacAddr(MacAddr([0x08, 0x00, 0x2b, 0x01, 0x02, 0x03])),
+                Datum::MacAddr(MacAddr([0xff; 6])),
+            ])
+        });
 
         match self {
             ScalarType::Bool => (*BOOL).iter(),
@@ -3044,6 +3296,9 @@ impl<'a> ScalarType {
             ScalarType::Char { .. } => (*CHAR).iter(),
             ScalarType::VarChar { .. } => (*STRING).iter(),
             ScalarType::Jsonb => (*JSONB).iter(),
+            ScalarType::JsonPath => (*JSONPATH).iter(),
+            ScalarType::TsVector => (*TSVECTOR).iter(),
+            ScalarType::TsQuery => (*TSQUERY).iter(),
             ScalarType::Uuid => (*UUID).iter(),
             ScalarType::Array(_) => (*ARRAY).iter(),
             ScalarType::List { .. } => (*LIST).iter(),
@@ -3058,6 +3313,9 @@ impl<'a> ScalarType {
             ScalarType::Range { .. } => (*RANGE).iter(),
             ScalarType::MzAclItem { .. } => (*MZACLITEM).iter(),
             ScalarType::AclItem { .. } => (*ACLITEM).iter(),
+            ScalarType::Inet => (*INET).iter(),
+            ScalarType::Cidr => (*CIDR).iter(),
+            ScalarType::MacAddr => (*MACADDR).iter(),
         }
     }
 
@@ -3094,6 +3352,9 @@ impl<'a> ScalarType {
             },
             ScalarType::VarChar { max_length: None },
             ScalarType::Jsonb,
+            ScalarType::JsonPath,
+            ScalarType::TsVector,
+            ScalarType::TsQuery,
             ScalarType::Uuid,
             ScalarType::Oid,
             ScalarType::RegProc,
@@ -3102,6 +3363,9 @@ impl<'a> ScalarType {
             ScalarType::Int2Vector,
             ScalarType::MzTimestamp,
             ScalarType::MzAclItem,
+            ScalarType::Inet,
+            ScalarType::Cidr,
+            ScalarType::MacAddr,
             // TODO: Fill in some variants of these.
             /*
             ScalarType::AclItem,
@@ -3153,6 +3417,9 @@ impl<'a> ScalarType {
             | ScalarType::String
             | ScalarType::VarChar { .. }
             | ScalarType::Jsonb
+            | ScalarType::JsonPath
+            | ScalarType::TsVector
+            | ScalarType::TsQuery
             | ScalarType::Uuid
             | ScalarType::Record { .. }
             | ScalarType::Oid
@@ -3162,7 +3429,10 @@ impl<'a> ScalarType {
             | ScalarType::Int2Vector
             | ScalarType::MzTimestamp
             | ScalarType::Range { .. }
-            | ScalarType::MzAclItem { .. }) => Ok(t),
+            | ScalarType::MzAclItem { .. }
+            | ScalarType::Inet
+            | ScalarType::Cidr
+            | ScalarType::MacAddr) => Ok(t),
 
             ScalarType::Array(elem) => Ok(elem.array_of_self_elem_type()?),
 
@@ -3208,7 +3478,13 @@ impl Arbitrary for ScalarType {
                 .prop_map(|max_length| ScalarType::VarChar { max_length })
                 .boxed(),
             Just(ScalarType::Jsonb).boxed(),
+            Just(ScalarType::JsonPath).boxed(),
+            Just(ScalarType::TsVector).boxed(),
+            Just(ScalarType::TsQuery).boxed(),
             Just(ScalarType::Uuid).boxed(),
+            Just(ScalarType::Inet).boxed(),
+            Just(ScalarType::Cidr).boxed(),
+            Just(ScalarType::MacAddr).boxed(),
             Just(ScalarType::Oid).boxed(),
             Just(ScalarType::RegProc).boxed(),
             Just(ScalarType::RegType).boxed(),
@@ -53,10 +53,13 @@ use crate::adt::date::Date;
 use crate::adt::datetime::{self, DateTimeField, ParsedDateTime};
 use crate::adt::interval::Interval;
 use crate::adt::jsonb::{Jsonb, JsonbRef};
+use crate::adt::jsonpath::JsonPath;
 use crate::adt::mz_acl_item::{AclItem, MzAclItem};
+use crate::adt::network::{Inet, MacAddr, NetworkError};
 use crate::adt::numeric::{self, Numeric, NUMERIC_DATUM_MAX_PRECISION};
 use crate::adt::pg_legacy_name::NAME_MAX_BYTES;
 use crate::adt::range::{Range, RangeBound, RangeInner};
+use crate::adt::text_search::{TsQuery, TsVector};
 use crate::adt::timestamp::CheckedTimestamp;
 
 include!(concat!(env!("OUT_DIR"), "/mz_repr.strconv.rs"));
@@ -724,6 +727,29 @@ where
     write!(buf, "{:#}", jsonb)
 }
 
+pub fn parse_jsonpath(s: &str) -> Result<JsonPath, ParseError> {
+    s.parse()
+        .map_err(|e| ParseError::invalid_input_syntax("jsonpath", s).with_details(e))
+}
+
+pub fn format_jsonpath<F>(buf: &mut F, jsonpath: &JsonPath) -> Nestable
+where
+    F: FormatBuffer,
+{
+    write!(buf, "{}", jsonpath);
+    Nestable::MayNeedEscaping
+}
+
+pub fn parse_tsvector(s: &str) -> Result<TsVector, ParseError> {
+    s.parse()
+        .map_err(|e| ParseError::invalid_input_syntax("tsvector", s).with_details(e))
+}
+
+pub fn parse_tsquery(s: &str) -> Result<TsQuery, ParseError> {
+    s.parse()
+        .map_err(|e| ParseError::invalid_input_syntax("tsquery", s).with_details(e))
+}
+
 pub fn parse_uuid(s: &str) -> Result<Uuid, ParseError> {
     s.trim()
         .parse()
@@ -738,6 +764,53 @@ where
     Nestable::Yes
 }
 
+fn network_parse_error(type_name: &str, s: &str, e: NetworkError) -> ParseError {
+    let err = ParseError::invalid_input_syntax(type_name, s);
+    match e {
+        NetworkError::InvalidSyntax => err,
+        e => err.with_details(e),
+    }
+}
+
+pub fn parse_inet(s: &str) -> Result<Inet, ParseError> {
+    Inet::parse_inet(s).map_err(|e| network_parse_error("inet", s, e))
+}
+
+/// Writes an `inet` value to `buf`, omitting the netmask length if the netmask
+/// covers the whole address.
+pub fn format_inet<F>(buf: &mut F, inet: Inet) -> Nestable
+where
+    F: FormatBuffer,
+{
+    write!(buf, "{}", inet);
+    Nestable::Yes
+}
+
+pub fn parse_cidr(s: &str) -> Result<Inet, ParseError> {
+    Inet::parse_cidr(s).map_err(|e| network_parse_error("cidr", s, e))
+}
+
+/// Writes a `cidr` value to `buf`, always including the netmask length.
+pub fn format_cidr<F>(buf: &mut F, cidr: Inet) -> Nestable
+where
+    F: FormatBuffer,
+{
+    write!(buf, "{}", cidr.to_cidr_string());
+    Nestable::Yes
+}
+
+pub fn parse_macaddr(s: &str) -> Result<MacAddr, ParseError> {
+    s.parse().map_err(|e| network_parse_error("macaddr", s, e))
+}
+
+pub fn format_macaddr<F>(buf: &mut F, mac_addr: MacAddr) -> Nestable
+where
+    F: FormatBuffer,
+{
+    write!(buf, "{}", mac_addr);
+    Nestable::Yes
+}
+
 fn format_nanos_to_micros<F>(buf: &mut F, nanos: u32)
 where
     F: FormatBuffer,
@@ -24,7 +24,7 @@
 use std::fmt;
 
 use crate::ast::display::{self, AstDisplay, AstFormatter};
-use crate::ast::{AstInfo, Expr, Ident, OrderByExpr, UnresolvedItemName, WithOptionValue};
+use crate::ast::{AstInfo, Expr, Ident, OrderByExpr, UnresolvedItemName, Value, WithOptionValue};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Schema {
@@ -337,6 +337,7 @@ pub enum Format<T: AstInfo> {
     Csv {
         columns: CsvColumns,
         delimiter: char,
+        options: Vec<CsvOption<T>>,
     },
     Json,
     Text,
@@ -369,6 +370,48 @@ impl AstDisplay for CsvColumns {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CsvOptionName {
+    AllowExtraColumns,
+    AllowMissingColumns,
+    Escape,
+    Null,
+    Quote,
+    Trim,
+}
+
+impl AstDisplay for CsvOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            CsvOptionName::AllowExtraColumns => "ALLOW EXTRA COLUMNS",
+            CsvOptionName::AllowMissingColumns => "ALLOW MISSING COLUMNS",
+            CsvOptionName::Escape => "ESCAPE",
+            CsvOptionName::Null => "NULL",
+            CsvOptionName::Quote => "QUOTE",
+            CsvOptionName::Trim => "TRIM",
+        })
+    }
+}
+impl_display!(CsvOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// An option in a `FORMAT CSV ... OPTIONS (...)` clause.
+pub struct CsvOption<T: AstInfo> {
+    pub name: CsvOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for CsvOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(CsvOption);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SourceIncludeMetadataType {
     Key,
@@ -414,7 +457,10 @@ impl_display!(SourceIncludeMetadata);
 pub enum Envelope {
     None,
     Debezium(DbzMode),
-    Upsert,
+    Upsert {
+        /// How long to retain keys after their latest update, if not forever.
+        retain_keys_for: Option<Value>,
+    },
     CdcV2,
 }
 
@@ -427,7 +473,7 @@ impl Envelope {
             // TODO[btv] - Adjust this if we change Dbz semantics
             // (why is this a parser-level concept, anyway? Should it be moved?)
             Envelope::Debezium(DbzMode::Plain) => false,
-            Envelope::Upsert => false,
+            Envelope::Upsert { .. } => false,
             Envelope::CdcV2 => true,
         }
     }
@@ -444,8 +490,13 @@ impl AstDisplay for Envelope {
                 f.write_str("DEBEZIUM");
                 f.write_node(mode);
             }
-            Self::Upsert => {
+            Self::Upsert { retain_keys_for } => {
                 f.write_str("UPSERT");
+                if let Some(retain_keys_for) = retain_keys_for {
+                    f.write_str(" (RETAIN KEYS FOR ");
+                    f.write_node(retain_keys_for);
+                    f.write_str(")");
+                }
             }
             Self::CdcV2 => {
                 f.write_str("MATERIALIZE");
@@ -503,7 +554,11 @@ impl<T: AstInfo> AstDisplay for Format<T> {
                 f.write_node(&display::escape_single_quote_string(regex));
                 f.write_str("'");
             }
-            Self::Csv { columns, delimiter } => {
+            Self::Csv {
+                columns,
+                delimiter,
+                options,
+            } => {
                 f.write_str("CSV WITH ");
                 f.write_node(columns);
 
@@ -512,6 +567,12 @@ impl<T: AstInfo> AstDisplay for Format<T> {
                     f.write_node(&display::escape_single_quote_string(&delimiter.to_string()));
                     f.write_str("'");
                 }
+
+                if !options.is_empty() {
+                    f.write_str(" OPTIONS (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
             }
             Self::Json => f.write_str("JSON"),
             Self::Text => f.write_str("TEXT"),
@@ -1007,6 +1068,8 @@ pub enum PgConfigOptionName {
     Details,
     /// The name of the publication to sync
     Publication,
+    /// How to handle changes to the schemas of upstream tables
+    SchemaChanges,
     /// Columns whose types you want to unconditionally format as text
     TextColumns,
 }
@@ -1016,6 +1079,7 @@ impl AstDisplay for PgConfigOptionName {
         f.write_str(match self {
             PgConfigOptionName::Details => "DETAILS",
             PgConfigOptionName::Publication => "PUBLICATION",
+            PgConfigOptionName::SchemaChanges => "SCHEMA CHANGES",
             PgConfigOptionName::TextColumns => "TEXT COLUMNS",
         })
     }
@@ -1108,6 +1172,7 @@ pub enum LoadGenerator {
     Auction,
     Datums,
     Tpch,
+    Custom,
 }
 
 impl AstDisplay for LoadGenerator {
@@ -1118,6 +1183,7 @@ impl AstDisplay for LoadGenerator {
             Self::Auction => f.write_str("AUCTION"),
             Self::Datums => f.write_str("DATUMS"),
             Self::Tpch => f.write_str("TPCH"),
+            Self::Custom => f.write_str("CUSTOM"),
         }
     }
 }
@@ -1128,6 +1194,8 @@ pub enum LoadGeneratorOptionName {
     ScaleFactor,
     TickInterval,
     MaxCardinality,
+    Spec,
+    Seed,
 }
 
 impl AstDisplay for LoadGeneratorOptionName {
@@ -1136,6 +1204,8 @@ impl AstDisplay for LoadGeneratorOptionName {
             LoadGeneratorOptionName::ScaleFactor => "SCALE FACTOR",
             LoadGeneratorOptionName::TickInterval => "TICK INTERVAL",
             LoadGeneratorOptionName::MaxCardinality => "MAX CARDINALITY",
+            LoadGeneratorOptionName::Spec => "SPEC",
+            LoadGeneratorOptionName::Seed => "SEED",
         })
     }
 }
@@ -1300,18 +1370,26 @@ impl_display!(KeyConstraint);
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum CreateSourceOptionName {
+    AllowedLateness,
     IgnoreKeys,
+    LateEvents,
+    RetainHistory,
     Size,
     Timeline,
+    TimestampFrom,
     TimestampInterval,
 }
 
 impl AstDisplay for CreateSourceOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str(match self {
+            CreateSourceOptionName::AllowedLateness => "ALLOWED LATENESS",
             CreateSourceOptionName::IgnoreKeys => "IGNORE KEYS",
+            CreateSourceOptionName::LateEvents => "LATE EVENTS",
+            CreateSourceOptionName::RetainHistory => "RETAIN HISTORY",
             CreateSourceOptionName::Size => "SIZE",
             CreateSourceOptionName::Timeline => "TIMELINE",
+            CreateSourceOptionName::TimestampFrom => "TIMESTAMP FROM",
             CreateSourceOptionName::TimestampInterval => "TIMESTAMP INTERVAL",
         })
     }
@@ -1337,7 +1415,7 @@ impl<T: AstInfo> AstDisplay for CreateSourceOption<T> {
 impl_display_t!(CreateSourceOption);
 
 /// SQL column definition
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ColumnDef<T: AstInfo> {
     pub name: Ident,
     pub data_type: T::DataType,
@@ -1378,7 +1456,7 @@ impl_display_t!(ColumnDef);
 /// For maximum flexibility, we don't distinguish between constraint and
 /// non-constraint options, lumping them all together under the umbrella of
 /// "column options," and we allow any column option to be named.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ColumnOptionDef<T: AstInfo> {
     pub name: Option<Ident>,
     pub option: ColumnOption<T>,
@@ -1394,7 +1472,7 @@ impl_display_t!(ColumnOptionDef);
 
 /// `ColumnOption`s are modifiers that follow a column definition in a `CREATE
 /// TABLE` statement.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum ColumnOption<T: AstInfo> {
     /// `NULL`
     Null,
@@ -1414,6 +1492,11 @@ pub enum ColumnOption<T: AstInfo> {
     },
     // `CHECK (<expr>)`
     Check(Expr<T>),
+    /// `GENERATED { ALWAYS | BY DEFAULT } AS IDENTITY [ ( <sequence-option> ... ) ]`
+    Identity {
+        always: bool,
+        options: Vec<SequenceOption<T>>,
+    },
 }
 
 impl<T: AstInfo> AstDisplay for ColumnOption<T> {
@@ -1448,11 +1531,179 @@ impl<T: AstInfo> AstDisplay for ColumnOption<T> {
                 f.write_node(expr);
                 f.write_str(")");
             }
+            Identity { always, options } => {
+                if *always {
+                    f.write_str("GENERATED ALWAYS AS IDENTITY");
+                } else {
+                    f.write_str("GENERATED BY DEFAULT AS IDENTITY");
+                }
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::separated(options, " "));
+                    f.write_str(")");
+                }
+            }
         }
     }
 }
 impl_display_t!(ColumnOption);
 
+/// An option of a sequence, as specified in `CREATE SEQUENCE` or in an
+/// identity column definition.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SequenceOption<T: AstInfo> {
+    /// `INCREMENT [BY] <n>`
+    IncrementBy(i64),
+    /// `MINVALUE <n>` or `NO MINVALUE`
+    MinValue(Option<i64>),
+    /// `MAXVALUE <n>` or `NO MAXVALUE`
+    MaxValue(Option<i64>),
+    /// `START [WITH] <n>`
+    StartWith(i64),
+    /// `CACHE <n>`
+    Cache(i64),
+    /// `CYCLE` or `NO CYCLE`
+    Cycle(bool),
+    /// `OWNED BY <table>.<column>` or `OWNED BY NONE`
+    OwnedBy(Option<SequenceOwner<T>>),
+}
+
+impl<T: AstInfo> AstDisplay for SequenceOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        use SequenceOption::*;
+        match self {
+            IncrementBy(n) => {
+                f.write_str("INCREMENT BY ");
+                f.write_str(n);
+            }
+            MinValue(Some(n)) => {
+                f.write_str("MINVALUE ");
+                f.write_str(n);
+            }
+            MinValue(None) => f.write_str("NO MINVALUE"),
+            MaxValue(Some(n)) => {
+                f.write_str("MAXVALUE ");
+                f.write_str(n);
+            }
+            MaxValue(None) => f.write_str("NO MAXVALUE"),
+            StartWith(n) => {
+                f.write_str("START WITH ");
+                f.write_str(n);
+            }
+            Cache(n) => {
+                f.write_str("CACHE ");
+                f.write_str(n);
+            }
+            Cycle(true) => f.write_str("CYCLE"),
+            Cycle(false) => f.write_str("NO CYCLE"),
+            OwnedBy(Some(owner)) => {
+                f.write_str("OWNED BY ");
+                f.write_node(owner);
+            }
+            OwnedBy(None) => f.write_str("OWNED BY NONE"),
+        }
+    }
+}
+impl_display_t!(SequenceOption);
+
+/// The table column that owns a sequence.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SequenceOwner<T: AstInfo> {
+    pub table: T::ItemName,
+    pub column: Ident,
+}
+
+impl<T: AstInfo> AstDisplay for SequenceOwner<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.table);
+        f.write_str(".");
+        f.write_node(&self.column);
+    }
+}
+impl_display_t!(SequenceOwner);
+
+/// A parameter of a user-defined function: `[ <name> ] <data-type>`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FunctionParameter<T: AstInfo> {
+    pub name: Option<Ident>,
+    pub data_type: T::DataType,
+}
+
+impl<T: AstInfo> AstDisplay for FunctionParameter<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        if let Some(name) = &self.name {
+            f.write_node(name);
+            f.write_str(" ");
+        }
+        f.write_node(&self.data_type);
+    }
+}
+impl_display_t!(FunctionParameter);
+
+/// A column of a function declared to return a table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FunctionColumn<T: AstInfo> {
+    pub name: Ident,
+    pub data_type: T::DataType,
+}
+
+impl<T: AstInfo> AstDisplay for FunctionColumn<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        f.write_str(" ");
+        f.write_node(&self.data_type);
+    }
+}
+impl_display_t!(FunctionColumn);
+
+/// The return type of a user-defined function.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FunctionReturns<T: AstInfo> {
+    /// `RETURNS <data-type>`
+    Scalar(T::DataType),
+    /// `RETURNS SETOF <data-type>`
+    SetOf(T::DataType),
+    /// `RETURNS TABLE ( <column> [, ...] )`
+    Table(Vec<FunctionColumn<T>>),
+}
+
+impl<T: AstInfo> AstDisplay for FunctionReturns<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            FunctionReturns::Scalar(data_type) => f.write_node(data_type),
+            FunctionReturns::SetOf(data_type) => {
+                f.write_str("SETOF ");
+                f.write_node(data_type);
+            }
+            FunctionReturns::Table(columns) => {
+                f.write_str("TABLE (");
+                f.write_node(&display::comma_separated(columns));
+                f.write_str(")");
+            }
+        }
+    }
+}
+impl_display_t!(FunctionReturns);
+
+/// The volatility of a user-defined function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FunctionBehavior {
+    Immutable,
+    Stable,
+    Volatile,
+}
+
+impl AstDisplay for FunctionBehavior {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            FunctionBehavior::Immutable => "IMMUTABLE",
+            FunctionBehavior::Stable => "STABLE",
+            FunctionBehavior::Volatile => "VOLATILE",
+        })
+    }
+}
+impl_display!(FunctionBehavior);
+
 fn display_constraint_name<'a>(name: &'a Option<Ident>) -> impl AstDisplay + 'a {
     struct ConstraintName<'a>(&'a Option<Ident>);
     impl<'a> AstDisplay for ConstraintName<'a> {
@@ -23,7 +23,7 @@ use std::hash::Hash;
 use std::mem;
 
 use crate::ast::display::{self, AstDisplay, AstFormatter};
-use crate::ast::{AstInfo, Expr, Ident, ShowStatement, WithOptionValue};
+use crate::ast::{AstInfo, ColumnDef, Expr, Ident, ShowStatement, Value, WithOptionValue};
 
 use super::Function;
 
@@ -193,6 +193,9 @@ pub enum SelectOptionName {
     AggregateInputGroupSize,
     DistinctOnInputGroupSize,
     LimitInputGroupSize,
+    JoinOrder,
+    DeltaJoin,
+    NoIndex,
 }
 
 impl AstDisplay for SelectOptionName {
@@ -202,6 +205,9 @@ impl AstDisplay for SelectOptionName {
             SelectOptionName::AggregateInputGroupSize => "AGGREGATE INPUT GROUP SIZE",
             SelectOptionName::DistinctOnInputGroupSize => "DISTINCT ON INPUT GROUP SIZE",
             SelectOptionName::LimitInputGroupSize => "LIMIT INPUT GROUP SIZE",
+            SelectOptionName::JoinOrder => "JOIN ORDER",
+            SelectOptionName::DeltaJoin => "DELTA JOIN",
+            SelectOptionName::NoIndex => "NO INDEX",
         })
     }
 }
@@ -319,11 +325,13 @@ impl<T: AstInfo> AstDisplay for Distinct<T> {
 /// A block of common table expressions (CTEs).
 ///
 /// The block can either be entirely "simple" (traditional SQL `WITH` block),
-/// or "mutually recursive", which introduce their bindings before the block
-/// and may result in mutually recursive definitions.
+/// "recursive" (SQL standard `WITH RECURSIVE` block), or "mutually recursive",
+/// which introduce their bindings before the block and may result in mutually
+/// recursive definitions.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum CteBlock<T: AstInfo> {
     Simple(Vec<Cte<T>>),
+    Recursive(Vec<CteRecursive<T>>),
     MutuallyRecursive(MutRecBlock<T>),
 }
 
@@ -342,6 +350,7 @@ impl<T: AstInfo> CteBlock<T> {
     pub fn is_empty(&self) -> bool {
         match self {
             CteBlock::Simple(list) => list.is_empty(),
+            CteBlock::Recursive(list) => list.is_empty(),
             CteBlock::MutuallyRecursive(list) => list.ctes.is_empty(),
         }
     }
@@ -354,6 +363,11 @@ impl<T: AstInfo> CteBlock<T> {
                     names.push(&cte.alias.name);
                 }
             }
+            CteBlock::Recursive(list) => {
+                for cte in list.iter() {
+                    names.push(&cte.alias.name);
+                }
+            }
             CteBlock::MutuallyRecursive(MutRecBlock { options: _, ctes }) => {
                 for cte in ctes.iter() {
                     names.push(&cte.name);
@@ -372,6 +386,10 @@ impl<T: AstInfo> AstDisplay for CteBlock<T> {
                     f.write_str("WITH ");
                     f.write_node(&display::comma_separated(list));
                 }
+                CteBlock::Recursive(list) => {
+                    f.write_str("WITH RECURSIVE ");
+                    f.write_node(&display::comma_separated(list));
+                }
                 CteBlock::MutuallyRecursive(MutRecBlock { options, ctes }) => {
                     f.write_str("WITH MUTUALLY RECURSIVE ");
                     if !options.is_empty() {
@@ -408,6 +426,112 @@ impl<T: AstInfo> AstDisplay for Cte<T> {
 }
 impl_display_t!(Cte);
 
+/// A single CTE in a `WITH RECURSIVE` block: `alias [(col1, col2, ...)] AS (
+/// query ) [SEARCH ...] [CYCLE ...]`.
+///
+/// The binding is visible within its own query, which must then have the form
+/// `non_recursive_term UNION [ALL] recursive_term`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CteRecursive<T: AstInfo> {
+    pub alias: TableAlias,
+    pub id: T::CteId,
+    pub query: Query<T>,
+    pub search: Option<CteSearch>,
+    pub cycle: Option<CteCycle>,
+}
+
+impl<T: AstInfo> AstDisplay for CteRecursive<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.alias);
+        f.write_str(" AS (");
+        f.write_node(&self.query);
+        f.write_str(")");
+        if let Some(search) = &self.search {
+            f.write_str(" ");
+            f.write_node(search);
+        }
+        if let Some(cycle) = &self.cycle {
+            f.write_str(" ");
+            f.write_node(cycle);
+        }
+    }
+}
+impl_display_t!(CteRecursive);
+
+/// The order in which a [`CteSearch`] clause enumerates rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CteSearchOrder {
+    DepthFirst,
+    BreadthFirst,
+}
+
+impl AstDisplay for CteSearchOrder {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            CteSearchOrder::DepthFirst => "DEPTH FIRST",
+            CteSearchOrder::BreadthFirst => "BREADTH FIRST",
+        })
+    }
+}
+impl_display!(CteSearchOrder);
+
+/// `SEARCH {DEPTH | BREADTH} FIRST BY col1, col2, ... SET sequence_column`
+///
+/// Adds `sequence_column` to the rows of a recursive CTE, such that ordering
+/// by it yields the rows in depth-first or breadth-first order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CteSearch {
+    pub order: CteSearchOrder,
+    pub columns: Vec<Ident>,
+    pub sequence_column: Ident,
+}
+
+impl AstDisplay for CteSearch {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SEARCH ");
+        f.write_node(&self.order);
+        f.write_str(" BY ");
+        f.write_node(&display::comma_separated(&self.columns));
+        f.write_str(" SET ");
+        f.write_node(&self.sequence_column);
+    }
+}
+impl_display!(CteSearch);
+
+/// `CYCLE col1, col2, ... SET mark_column [TO mark_value DEFAULT
+/// mark_default] USING path_column`
+///
+/// Stops the recursion of a recursive CTE at rows whose values of the cycle
+/// columns were already seen on the path leading to them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CteCycle {
+    pub columns: Vec<Ident>,
+    pub mark_column: Ident,
+    pub mark_value: Option<Value>,
+    pub mark_default: Option<Value>,
+    pub path_column: Ident,
+}
+
+impl AstDisplay for CteCycle {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("CYCLE ");
+        f.write_node(&display::comma_separated(&self.columns));
+        f.write_str(" SET ");
+        f.write_node(&self.mark_column);
+        if let Some(mark_value) = &self.mark_value {
+            f.write_str(" TO ");
+            f.write_node(mark_value);
+        }
+        if let Some(mark_default) = &self.mark_default {
+            f.write_str(" DEFAULT ");
+            f.write_node(mark_default);
+        }
+        f.write_str(" USING ");
+        f.write_node(&self.path_column);
+    }
+}
+impl_display!(CteCycle);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct CteMutRec<T: AstInfo> {
     pub name: Ident,
@@ -545,6 +669,9 @@ pub enum TableFactor<T: AstInfo> {
     Function {
         function: Function<T>,
         alias: Option<TableAlias>,
+        /// The column definition list of a function returning `record`, as in
+        /// `jsonb_to_record(...) AS x (a int, b text)`.
+        column_defs: Option<Vec<ColumnDef<T>>>,
         with_ordinality: bool,
     },
     RowsFrom {
@@ -580,6 +707,7 @@ impl<T: AstInfo> AstDisplay for TableFactor<T> {
             TableFactor::Function {
                 function,
                 alias,
+                column_defs,
                 with_ordinality,
             } => {
                 f.write_node(function);
@@ -587,6 +715,11 @@ impl<T: AstInfo> AstDisplay for TableFactor<T> {
                     f.write_str(" AS ");
                     f.write_node(alias);
                 }
+                if let Some(column_defs) = column_defs {
+                    f.write_str(if alias.is_some() { " (" } else { " AS (" });
+                    f.write_node(&display::comma_separated(column_defs));
+                    f.write_str(")");
+                }
                 if *with_ordinality {
                     f.write_str(" WITH ORDINALITY");
                 }
@@ -31,8 +31,9 @@ use crate::ast::display::{self, AstDisplay, AstFormatter};
 use crate::ast::{
     AstInfo, ColumnDef, CreateConnection, CreateConnectionOption, CreateSinkConnection,
     CreateSourceConnection, CreateSourceFormat, CreateSourceOption, CreateSourceOptionName,
-    DeferredItemName, Envelope, Expr, Format, Ident, KeyConstraint, Query, SelectItem,
-    SourceIncludeMetadata, SubscribeOutput, TableAlias, TableConstraint, TableWithJoins,
+    DeferredItemName, Envelope, Expr, Format, FunctionBehavior, FunctionParameter, FunctionReturns,
+    Ident, KeyConstraint, Query, SelectItem, SequenceOption, SourceIncludeMetadata,
+    SubscribeOutput, TableAlias, TableConstraint, TableFactor, TableWithJoins,
     UnresolvedDatabaseName, UnresolvedItemName, UnresolvedObjectName, UnresolvedSchemaName, Value,
 };
 
@@ -46,6 +47,7 @@ pub enum Statement<T: AstInfo> {
     Copy(CopyStatement<T>),
     Update(UpdateStatement<T>),
     Delete(DeleteStatement<T>),
+    Merge(MergeStatement<T>),
     CreateConnection(CreateConnectionStatement<T>),
     CreateDatabase(CreateDatabaseStatement),
     CreateSchema(CreateSchemaStatement),
@@ -62,12 +64,15 @@ pub enum Statement<T: AstInfo> {
     CreateCluster(CreateClusterStatement<T>),
     CreateClusterReplica(CreateClusterReplicaStatement<T>),
     CreateSecret(CreateSecretStatement<T>),
+    CreateSequence(CreateSequenceStatement<T>),
+    CreateFunction(CreateFunctionStatement<T>),
     AlterCluster(AlterClusterStatement<T>),
     AlterOwner(AlterOwnerStatement<T>),
     AlterObjectRename(AlterObjectRenameStatement),
     AlterIndex(AlterIndexStatement<T>),
     AlterSecret(AlterSecretStatement<T>),
     AlterSetCluster(AlterSetClusterStatement<T>),
+    AlterRetainHistory(AlterRetainHistoryStatement<T>),
     AlterSink(AlterSinkStatement<T>),
     AlterSource(AlterSourceStatement<T>),
     AlterSystemSet(AlterSystemSetStatement),
@@ -113,6 +118,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::Copy(stmt) => f.write_node(stmt),
             Statement::Update(stmt) => f.write_node(stmt),
             Statement::Delete(stmt) => f.write_node(stmt),
+            Statement::Merge(stmt) => f.write_node(stmt),
             Statement::CreateConnection(stmt) => f.write_node(stmt),
             Statement::CreateDatabase(stmt) => f.write_node(stmt),
             Statement::CreateSchema(stmt) => f.write_node(stmt),
@@ -126,6 +132,8 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::CreateIndex(stmt) => f.write_node(stmt),
             Statement::CreateRole(stmt) => f.write_node(stmt),
             Statement::CreateSecret(stmt) => f.write_node(stmt),
+            Statement::CreateSequence(stmt) => f.write_node(stmt),
+            Statement::CreateFunction(stmt) => f.write_node(stmt),
             Statement::CreateType(stmt) => f.write_node(stmt),
             Statement::CreateCluster(stmt) => f.write_node(stmt),
             Statement::CreateClusterReplica(stmt) => f.write_node(stmt),
@@ -134,6 +142,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::AlterObjectRename(stmt) => f.write_node(stmt),
             Statement::AlterIndex(stmt) => f.write_node(stmt),
             Statement::AlterSetCluster(stmt) => f.write_node(stmt),
+            Statement::AlterRetainHistory(stmt) => f.write_node(stmt),
             Statement::AlterSecret(stmt) => f.write_node(stmt),
             Statement::AlterSink(stmt) => f.write_node(stmt),
             Statement::AlterSource(stmt) => f.write_node(stmt),
@@ -183,6 +192,7 @@ pub fn statement_kind_label_value(kind: StatementKind) -> &'static str {
         StatementKind::Copy => "copy",
         StatementKind::Update => "update",
         StatementKind::Delete => "delete",
+        StatementKind::Merge => "merge",
         StatementKind::CreateConnection => "create_connection",
         StatementKind::CreateDatabase => "create_database",
         StatementKind::CreateSchema => "create_schema",
@@ -199,12 +209,15 @@ pub fn statement_kind_label_value(kind: StatementKind) -> &'static str {
         StatementKind::CreateCluster => "create_cluster",
         StatementKind::CreateClusterReplica => "create_cluster_replica",
         StatementKind::CreateSecret => "create_secret",
+        StatementKind::CreateSequence => "create_sequence",
+        StatementKind::CreateFunction => "create_function",
         StatementKind::AlterCluster => "alter_cluster",
         StatementKind::AlterObjectRename => "alter_object_rename",
         StatementKind::AlterIndex => "alter_index",
         StatementKind::AlterRole => "alter_role",
         StatementKind::AlterSecret => "alter_secret",
         StatementKind::AlterSetCluster => "alter_set_cluster",
+        StatementKind::AlterRetainHistory => "alter_retain_history",
         StatementKind::AlterSink => "alter_sink",
         StatementKind::AlterSource => "alter_source",
         StatementKind::AlterSystemSet => "alter_system_set",
@@ -270,6 +283,8 @@ pub struct InsertStatement<T: AstInfo> {
     pub columns: Vec<Ident>,
     /// A SQL query that specifies what to insert.
     pub source: InsertSource<T>,
+    /// `ON CONFLICT`
+    pub on_conflict: Option<OnConflict<T>>,
     /// RETURNING
     pub returning: Vec<SelectItem<T>>,
 }
@@ -285,6 +300,10 @@ impl<T: AstInfo> AstDisplay for InsertStatement<T> {
         }
         f.write_str(" ");
         f.write_node(&self.source);
+        if let Some(on_conflict) = &self.on_conflict {
+            f.write_str(" ");
+            f.write_node(on_conflict);
+        }
         if !self.returning.is_empty() {
             f.write_str(" RETURNING ");
             f.write_node(&display::comma_separated(&self.returning));
@@ -293,6 +312,59 @@ impl<T: AstInfo> AstDisplay for InsertStatement<T> {
 }
 impl_display_t!(InsertStatement);
 
+/// `ON CONFLICT [(<columns>)] DO ...` in an `INSERT` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OnConflict<T: AstInfo> {
+    /// The columns of the unique constraint that conflicts are detected on.
+    /// Empty if no columns were specified.
+    pub target: Vec<Ident>,
+    pub action: OnConflictAction<T>,
+}
+
+impl<T: AstInfo> AstDisplay for OnConflict<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ON CONFLICT ");
+        if !self.target.is_empty() {
+            f.write_str("(");
+            f.write_node(&display::comma_separated(&self.target));
+            f.write_str(") ");
+        }
+        f.write_node(&self.action);
+    }
+}
+impl_display_t!(OnConflict);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OnConflictAction<T: AstInfo> {
+    /// `DO NOTHING`
+    DoNothing,
+    /// `DO UPDATE SET ... [WHERE ...]`
+    DoUpdate {
+        assignments: Vec<Assignment<T>>,
+        selection: Option<Expr<T>>,
+    },
+}
+
+impl<T: AstInfo> AstDisplay for OnConflictAction<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            OnConflictAction::DoNothing => f.write_str("DO NOTHING"),
+            OnConflictAction::DoUpdate {
+                assignments,
+                selection,
+            } => {
+                f.write_str("DO UPDATE SET ");
+                f.write_node(&display::comma_separated(assignments));
+                if let Some(selection) = selection {
+                    f.write_str(" WHERE ");
+                    f.write_node(selection);
+                }
+            }
+        }
+    }
+}
+impl_display_t!(OnConflictAction);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CopyRelation<T: AstInfo> {
     Table {
@@ -488,6 +560,109 @@ impl<T: AstInfo> AstDisplay for DeleteStatement<T> {
 }
 impl_display_t!(DeleteStatement);
 
+/// `MERGE`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MergeStatement<T: AstInfo> {
+    /// `INTO`
+    pub table_name: T::ItemName,
+    /// `AS`
+    pub alias: Option<TableAlias>,
+    /// `USING`
+    pub source: TableFactor<T>,
+    /// `ON`
+    pub on: Expr<T>,
+    /// The `WHEN` clauses, in the order they are checked.
+    pub clauses: Vec<MergeClause<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for MergeStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("MERGE INTO ");
+        f.write_node(&self.table_name);
+        if let Some(alias) = &self.alias {
+            f.write_str(" AS ");
+            f.write_node(alias);
+        }
+        f.write_str(" USING ");
+        f.write_node(&self.source);
+        f.write_str(" ON ");
+        f.write_node(&self.on);
+        for clause in &self.clauses {
+            f.write_str(" ");
+            f.write_node(clause);
+        }
+    }
+}
+impl_display_t!(MergeStatement);
+
+/// `WHEN [NOT] MATCHED [AND <condition>] THEN <action>` in a `MERGE`
+/// statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MergeClause<T: AstInfo> {
+    /// Whether the clause applies to rows of the target table that matched a
+    /// source row, rather than to source rows that didn't match any.
+    pub matched: bool,
+    pub condition: Option<Expr<T>>,
+    pub action: MergeAction<T>,
+}
+
+impl<T: AstInfo> AstDisplay for MergeClause<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("WHEN ");
+        if !self.matched {
+            f.write_str("NOT ");
+        }
+        f.write_str("MATCHED");
+        if let Some(condition) = &self.condition {
+            f.write_str(" AND ");
+            f.write_node(condition);
+        }
+        f.write_str(" THEN ");
+        f.write_node(&self.action);
+    }
+}
+impl_display_t!(MergeClause);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MergeAction<T: AstInfo> {
+    /// `UPDATE SET ...`
+    Update { assignments: Vec<Assignment<T>> },
+    /// `DELETE`
+    Delete,
+    /// `INSERT [(<columns>)] VALUES (...)`
+    Insert {
+        columns: Vec<Ident>,
+        values: Vec<Expr<T>>,
+    },
+    /// `DO NOTHING`
+    DoNothing,
+}
+
+impl<T: AstInfo> AstDisplay for MergeAction<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            MergeAction::Update { assignments } => {
+                f.write_str("UPDATE SET ");
+                f.write_node(&display::comma_separated(assignments));
+            }
+            MergeAction::Delete => f.write_str("DELETE"),
+            MergeAction::Insert { columns, values } => {
+                f.write_str("INSERT ");
+                if !columns.is_empty() {
+                    f.write_str("(");
+                    f.write_node(&display::comma_separated(columns));
+                    f.write_str(") ");
+                }
+                f.write_str("VALUES (");
+                f.write_node(&display::comma_separated(values));
+                f.write_str(")");
+            }
+            MergeAction::DoNothing => f.write_str("DO NOTHING"),
+        }
+    }
+}
+impl_display_t!(MergeAction);
+
 /// `CREATE DATABASE`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateDatabaseStatement {
@@ -1027,6 +1202,7 @@ impl_display_t!(ReferencedSubsources);
 pub enum CreateSubsourceOptionName {
     Progress,
     References,
+    RetainHistory,
 }
 
 impl AstDisplay for CreateSubsourceOptionName {
@@ -1038,6 +1214,9 @@ impl AstDisplay for CreateSubsourceOptionName {
             CreateSubsourceOptionName::References => {
                 f.write_str("REFERENCES");
             }
+            CreateSubsourceOptionName::RetainHistory => {
+                f.write_str("RETAIN HISTORY");
+            }
         }
     }
 }
@@ -1236,6 +1415,7 @@ pub struct CreateMaterializedViewStatement<T: AstInfo> {
     pub columns: Vec<Ident>,
     pub in_cluster: Option<T::ClusterName>,
     pub query: Query<T>,
+    pub with_options: Vec<MaterializedViewOption<T>>,
 }
 
 impl<T: AstInfo> AstDisplay for CreateMaterializedViewStatement<T> {
@@ -1265,12 +1445,56 @@ impl<T: AstInfo> AstDisplay for CreateMaterializedViewStatement<T> {
             f.write_node(cluster);
         }
 
+        if !self.with_options.is_empty() {
+            f.write_str(" WITH (");
+            f.write_node(&display::comma_separated(&self.with_options));
+            f.write_str(")");
+        }
+
         f.write_str(" AS ");
         f.write_node(&self.query);
     }
 }
 impl_display_t!(CreateMaterializedViewStatement);
 
+/// An option in a `CREATE MATERIALIZED VIEW` statement.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MaterializedViewOptionName {
+    /// The `RETAIN HISTORY` option.
+    RetainHistory,
+    /// The `REFRESH` option.
+    Refresh,
+}
+
+impl AstDisplay for MaterializedViewOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            MaterializedViewOptionName::RetainHistory => {
+                f.write_str("RETAIN HISTORY");
+            }
+            MaterializedViewOptionName::Refresh => {
+                f.write_str("REFRESH");
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MaterializedViewOption<T: AstInfo> {
+    pub name: MaterializedViewOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for MaterializedViewOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+
 /// `ALTER SET CLUSTER`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AlterSetClusterStatement<T: AstInfo> {
@@ -1298,6 +1522,40 @@ impl<T: AstInfo> AstDisplay for AlterSetClusterStatement<T> {
 }
 impl_display_t!(AlterSetClusterStatement);
 
+/// `ALTER ... SET (RETAIN HISTORY ...)` and `ALTER ... RESET (RETAIN HISTORY)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterRetainHistoryStatement<T: AstInfo> {
+    pub object_type: ObjectType,
+    pub if_exists: bool,
+    pub name: UnresolvedItemName,
+    /// The new retention, or `None` to reset to the default.
+    pub history: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for AlterRetainHistoryStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER ");
+        f.write_node(&self.object_type);
+
+        if self.if_exists {
+            f.write_str(" IF EXISTS");
+        }
+
+        f.write_str(" ");
+        f.write_node(&self.name);
+
+        match &self.history {
+            Some(history) => {
+                f.write_str(" SET (RETAIN HISTORY = ");
+                f.write_node(history);
+                f.write_str(")");
+            }
+            None => f.write_str(" RESET (RETAIN HISTORY)"),
+        }
+    }
+}
+impl_display_t!(AlterRetainHistoryStatement);
+
 /// `CREATE TABLE`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateTableStatement<T: AstInfo> {
@@ -1308,6 +1566,7 @@ pub struct CreateTableStatement<T: AstInfo> {
     pub constraints: Vec<TableConstraint<T>>,
     pub if_not_exists: bool,
     pub temporary: bool,
+    pub with_options: Vec<TableOption<T>>,
 }
 
 impl<T: AstInfo> AstDisplay for CreateTableStatement<T> {
@@ -1328,10 +1587,49 @@ impl<T: AstInfo> AstDisplay for CreateTableStatement<T> {
             f.write_node(&display::comma_separated(&self.constraints));
         }
         f.write_str(")");
+
+        if !self.with_options.is_empty() {
+            f.write_str(" WITH (");
+            f.write_node(&display::comma_separated(&self.with_options));
+            f.write_str(")");
+        }
     }
 }
 impl_display_t!(CreateTableStatement);
 
+/// An option in a `CREATE TABLE` statement.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TableOptionName {
+    /// The `RETAIN HISTORY` option.
+    RetainHistory,
+}
+
+impl AstDisplay for TableOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            TableOptionName::RetainHistory => {
+                f.write_str("RETAIN HISTORY");
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TableOption<T: AstInfo> {
+    pub name: TableOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for TableOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+
 /// `CREATE INDEX`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateIndexStatement<T: AstInfo> {
@@ -1498,6 +1796,72 @@ impl<T: AstInfo> AstDisplay for CreateSecretStatement<T> {
 }
 impl_display_t!(CreateSecretStatement);
 
+/// A `CREATE SEQUENCE` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CreateSequenceStatement<T: AstInfo> {
+    pub name: UnresolvedItemName,
+    pub if_not_exists: bool,
+    pub options: Vec<SequenceOption<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for CreateSequenceStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("CREATE SEQUENCE ");
+        if self.if_not_exists {
+            f.write_str("IF NOT EXISTS ");
+        }
+        f.write_node(&self.name);
+        for option in &self.options {
+            f.write_str(" ");
+            f.write_node(option);
+        }
+    }
+}
+impl_display_t!(CreateSequenceStatement);
+
+/// A `CREATE FUNCTION` statement for a function written in SQL.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CreateFunctionStatement<T: AstInfo> {
+    pub name: UnresolvedItemName,
+    pub parameters: Vec<FunctionParameter<T>>,
+    pub returns: FunctionReturns<T>,
+    pub behavior: FunctionBehavior,
+    /// The body of the function, which is written as a string in the SQL
+    /// text but parsed eagerly so that its references can be resolved.
+    pub body: Query<T>,
+}
+
+impl<T: AstInfo> AstDisplay for CreateFunctionStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("CREATE FUNCTION ");
+        f.write_node(&self.name);
+        f.write_str("(");
+        f.write_node(&display::comma_separated(&self.parameters));
+        f.write_str(") RETURNS ");
+        f.write_node(&self.returns);
+        f.write_str(" LANGUAGE SQL ");
+        f.write_node(&self.behavior);
+        f.write_str(" AS ");
+        // Render the body as a dollar-quoted string unless doing so would
+        // terminate the string early.
+        let body = if f.stable() {
+            self.body.to_ast_string_stable()
+        } else {
+            self.body.to_ast_string()
+        };
+        if body.contains("$$") {
+            f.write_str("'");
+            f.write_node(&display::escape_single_quote_string(&body));
+            f.write_str("'");
+        } else {
+            f.write_str("$$");
+            f.write_str(body);
+            f.write_str("$$");
+        }
+    }
+}
+impl_display_t!(CreateFunctionStatement);
+
 /// `CREATE TYPE ..`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateTypeStatement<T: AstInfo> {
@@ -2782,6 +3146,7 @@ pub enum ObjectType {
     Schema,
     Func,
     Subsource,
+    Sequence,
 }
 
 impl ObjectType {
@@ -2797,7 +3162,8 @@ impl ObjectType {
             | ObjectType::Secret
             | ObjectType::Connection
             | ObjectType::Func
-            | ObjectType::Subsource => true,
+            | ObjectType::Subsource
+            | ObjectType::Sequence => true,
             ObjectType::Database
             | ObjectType::Schema
             | ObjectType::Cluster
@@ -2826,6 +3192,7 @@ impl AstDisplay for ObjectType {
             ObjectType::Schema => "SCHEMA",
             ObjectType::Func => "FUNCTION",
             ObjectType::Subsource => "SUBSOURCE",
+            ObjectType::Sequence => "SEQUENCE",
         })
     }
 }
@@ -2867,6 +3234,8 @@ pub enum WithOptionValue<T: AstInfo> {
     // Special cases.
     ClusterReplicas(Vec<ReplicaDefinition<T>>),
     ConnectionKafkaBroker(KafkaBroker<T>),
+    RetainHistoryFor(Value),
+    Refresh(RefreshOptionValue<T>),
 }
 
 impl<T: AstInfo> AstDisplay for WithOptionValue<T> {
@@ -2894,11 +3263,69 @@ impl<T: AstInfo> AstDisplay for WithOptionValue<T> {
             WithOptionValue::ConnectionKafkaBroker(broker) => {
                 f.write_node(broker);
             }
+            WithOptionValue::RetainHistoryFor(value) => {
+                f.write_str("FOR ");
+                f.write_node(value);
+            }
+            WithOptionValue::Refresh(value) => f.write_node(value),
         }
     }
 }
 impl_display_t!(WithOptionValue);
 
+/// The value of a materialized view's `REFRESH` option.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RefreshOptionValue<T: AstInfo> {
+    /// `REFRESH ON COMMIT`, i.e., the default continuous maintenance.
+    OnCommit,
+    /// `REFRESH AT CREATION`
+    AtCreation,
+    /// `REFRESH AT <time>`
+    At(RefreshAtOptionValue<T>),
+    /// `REFRESH EVERY <interval> [ALIGNED TO <time>]`
+    Every(RefreshEveryOptionValue<T>),
+}
+
+impl<T: AstInfo> AstDisplay for RefreshOptionValue<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            RefreshOptionValue::OnCommit => f.write_str("ON COMMIT"),
+            RefreshOptionValue::AtCreation => f.write_str("AT CREATION"),
+            RefreshOptionValue::At(RefreshAtOptionValue { time }) => {
+                f.write_str("AT ");
+                f.write_node(time);
+            }
+            RefreshOptionValue::Every(RefreshEveryOptionValue {
+                interval,
+                aligned_to,
+            }) => {
+                f.write_str("EVERY ");
+                f.write_node(interval);
+                if let Some(aligned_to) = aligned_to {
+                    f.write_str(" ALIGNED TO ");
+                    f.write_node(aligned_to);
+                }
+            }
+        }
+    }
+}
+impl_display_t!(RefreshOptionValue);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RefreshAtOptionValue<T: AstInfo> {
+    /// The time of the refresh, which is cast to `mz_timestamp`.
+    pub time: Expr<T>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RefreshEveryOptionValue<T: AstInfo> {
+    /// The interval between two refreshes.
+    pub interval: Value,
+    /// The time that refreshes are aligned to, which is cast to
+    /// `mz_timestamp`. Defaults to the creation time of the view.
+    pub aligned_to: Option<Expr<T>>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum TransactionMode {
     AccessMode(TransactionAccessMode),
@@ -157,6 +157,61 @@ pub fn parse_data_type(sql: &str) -> Result<RawDataType, ParserError> {
     }
 }
 
+/// Parses a SQL string containing a single, possibly qualified item name.
+pub fn parse_item_name(sql: &str) -> Result<UnresolvedItemName, ParserError> {
+    let tokens = lexer::lex(sql)?;
+    let mut parser = Parser::new(sql, tokens);
+    let name = parser.parse_item_name()?;
+    if parser.next_token().is_some() {
+        parser_err!(
+            parser,
+            parser.peek_prev_pos(),
+            "extra token after item name"
+        )
+    } else {
+        Ok(name)
+    }
+}
+
+/// Parses the body of a SQL function, which must be a single query with an
+/// optional trailing semicolon.
+fn parse_function_body(sql: &str) -> Result<Query<Raw>, ParserError> {
+    let tokens = lexer::lex(sql)?;
+    let mut parser = Parser::new(sql, tokens);
+    let query = parser.parse_query()?;
+    let _ = parser.consume_token(&Token::Semicolon);
+    if parser.next_token().is_some() {
+        parser_err!(
+            parser,
+            parser.peek_prev_pos(),
+            "extra token after query"
+        )
+    } else {
+        Ok(query)
+    }
+}
+
+/// Returns the name of the integer type that a serial pseudo-type, like
+/// `SERIAL`, stands for.
+fn serial_integer_type(data_type: &RawDataType) -> Option<&'static str> {
+    let RawDataType::Other {
+        name: RawItemName::Name(UnresolvedItemName(name)),
+        typ_mod,
+    } = data_type
+    else {
+        return None;
+    };
+    if !typ_mod.is_empty() || name.len() != 1 {
+        return None;
+    }
+    match name[0].as_str() {
+        "smallserial" | "serial2" => Some("int2"),
+        "serial" | "serial4" => Some("int4"),
+        "bigserial" | "serial8" => Some("int8"),
+        _ => None,
+    }
+}
+
 /// Parses a string containing a comma-separated list of identifiers and
 /// returns their underlying string values.
 ///
@@ -386,6 +441,9 @@ impl<'a> Parser<'a> {
                 Token::Keyword(UPDATE) => {
                     Ok(self.parse_update().map_parser_err(StatementKind::Update)?)
                 }
+                Token::Keyword(MERGE) => {
+                    Ok(self.parse_merge().map_parser_err(StatementKind::Merge)?)
+                }
                 Token::Keyword(ALTER) => Ok(self.parse_alter()?),
                 Token::Keyword(COPY) => Ok(self.parse_copy()?),
                 Token::Keyword(SET) => Ok(self.parse_set()?),
@@ -1842,6 +1900,12 @@ impl<'a> Parser<'a> {
         } else if self.peek_keyword(SECRET) {
             self.parse_create_secret()
                 .map_parser_err(StatementKind::CreateSecret)
+        } else if self.peek_keyword(SEQUENCE) {
+            self.parse_create_sequence()
+                .map_parser_err(StatementKind::CreateSequence)
+        } else if self.peek_keyword(FUNCTION) {
+            self.parse_create_function()
+                .map_parser_err(StatementKind::CreateFunction)
         } else if self.peek_keyword(CONNECTION) {
             self.parse_create_connection()
                 .map_parser_err(StatementKind::CreateConnection)
@@ -1926,7 +1990,19 @@ impl<'a> Parser<'a> {
             } else {
                 ','
             };
-            Format::Csv { columns, delimiter }
+            let options = if self.parse_keyword(OPTIONS) {
+                self.expect_token(&Token::LParen)?;
+                let options = self.parse_comma_separated(Parser::parse_csv_option)?;
+                self.expect_token(&Token::RParen)?;
+                options
+            } else {
+                vec![]
+            };
+            Format::Csv {
+                columns,
+                delimiter,
+                options,
+            }
         } else if self.parse_keyword(JSON) {
             Format::Json
         } else if self.parse_keyword(TEXT) {
@@ -1943,6 +2019,35 @@ impl<'a> Parser<'a> {
         Ok(format)
     }
 
+    fn parse_csv_option(&mut self) -> Result<CsvOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[ALLOW, ESCAPE, NULL, QUOTE, TRIM])? {
+            ALLOW => match self.expect_one_of_keywords(&[EXTRA, MISSING])? {
+                EXTRA => {
+                    self.expect_keyword(COLUMNS)?;
+                    CsvOptionName::AllowExtraColumns
+                }
+                MISSING => {
+                    self.expect_keyword(COLUMNS)?;
+                    CsvOptionName::AllowMissingColumns
+                }
+                _ => unreachable!(),
+            },
+            ESCAPE => CsvOptionName::Escape,
+            NULL => {
+                // Accept the `NULL AS '...'` spelling used by `COPY`.
+                let _ = self.parse_keyword(AS);
+                CsvOptionName::Null
+            }
+            QUOTE => CsvOptionName::Quote,
+            TRIM => CsvOptionName::Trim,
+            _ => unreachable!(),
+        };
+        Ok(CsvOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_avro_schema(&mut self) -> Result<AvroSchema<Raw>, ParserError> {
         let avro_schema = if self.parse_keywords(&[CONFLUENT, SCHEMA, REGISTRY]) {
             let csr_connection = self.parse_csr_connection_avro()?;
@@ -2141,7 +2246,19 @@ impl<'a> Parser<'a> {
             let debezium_mode = DbzMode::Plain;
             Envelope::Debezium(debezium_mode)
         } else if self.parse_keyword(UPSERT) {
-            Envelope::Upsert
+            let retain_keys_for = if self.consume_token(&Token::LParen) {
+                self.expect_keywords(&[RETAIN, KEYS, FOR])?;
+                let value = if self.parse_keyword(INTERVAL) {
+                    self.parse_interval_value()?
+                } else {
+                    self.parse_value()?
+                };
+                self.expect_token(&Token::RParen)?;
+                Some(value)
+            } else {
+                None
+            };
+            Envelope::Upsert { retain_keys_for }
         } else if self.parse_keyword(MATERIALIZE) {
             Envelope::CdcV2
         } else {
@@ -2639,9 +2756,13 @@ impl<'a> Parser<'a> {
     fn parse_create_subsource_option_name(
         &mut self,
     ) -> Result<CreateSubsourceOptionName, ParserError> {
-        let name = match self.expect_one_of_keywords(&[PROGRESS, REFERENCES])? {
+        let name = match self.expect_one_of_keywords(&[PROGRESS, REFERENCES, RETAIN])? {
             PROGRESS => CreateSubsourceOptionName::Progress,
             REFERENCES => CreateSubsourceOptionName::References,
+            RETAIN => {
+                self.expect_keyword(HISTORY)?;
+                CreateSubsourceOptionName::RetainHistory
+            }
             _ => unreachable!(),
         };
         Ok(name)
@@ -2649,10 +2770,12 @@ impl<'a> Parser<'a> {
 
     /// Parse a NAME = VALUE parameter for CREATE SINK
     fn parse_create_subsource_option(&mut self) -> Result<CreateSubsourceOption<Raw>, ParserError> {
-        Ok(CreateSubsourceOption {
-            name: self.parse_create_subsource_option_name()?,
-            value: self.parse_optional_option_value()?,
-        })
+        let name = self.parse_create_subsource_option_name()?;
+        let value = match name {
+            CreateSubsourceOptionName::RetainHistory => self.parse_retain_history()?,
+            _ => self.parse_optional_option_value()?,
+        };
+        Ok(CreateSubsourceOption { name, value })
     }
 
     fn parse_create_source(&mut self) -> Result<Statement<Raw>, ParserError> {
@@ -2802,17 +2925,32 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_source_option_name(&mut self) -> Result<CreateSourceOptionName, ParserError> {
-        let name = match self.expect_one_of_keywords(&[IGNORE, SIZE, TIMELINE, TIMESTAMP])? {
+        let name = match self
+            .expect_one_of_keywords(&[ALLOWED, IGNORE, LATE, RETAIN, SIZE, TIMELINE, TIMESTAMP])?
+        {
+            ALLOWED => {
+                self.expect_keyword(LATENESS)?;
+                CreateSourceOptionName::AllowedLateness
+            }
             IGNORE => {
                 self.expect_keyword(KEYS)?;
                 CreateSourceOptionName::IgnoreKeys
             }
+            LATE => {
+                self.expect_keyword(EVENTS)?;
+                CreateSourceOptionName::LateEvents
+            }
+            RETAIN => {
+                self.expect_keyword(HISTORY)?;
+                CreateSourceOptionName::RetainHistory
+            }
             SIZE => CreateSourceOptionName::Size,
             TIMELINE => CreateSourceOptionName::Timeline,
-            TIMESTAMP => {
-                self.expect_keyword(INTERVAL)?;
-                CreateSourceOptionName::TimestampInterval
-            }
+            TIMESTAMP => match self.expect_one_of_keywords(&[FROM, INTERVAL])? {
+                FROM => CreateSourceOptionName::TimestampFrom,
+                INTERVAL => CreateSourceOptionName::TimestampInterval,
+                _ => unreachable!(),
+            },
             _ => unreachable!(),
         };
         Ok(name)
@@ -2821,10 +2959,23 @@ impl<'a> Parser<'a> {
     /// Parses a single valid option in the WITH block of a create source
     fn parse_source_option(&mut self) -> Result<CreateSourceOption<Raw>, ParserError> {
         let name = self.parse_source_option_name()?;
-        Ok(CreateSourceOption {
-            name,
-            value: self.parse_optional_option_value()?,
-        })
+        let value = match name {
+            CreateSourceOptionName::RetainHistory => self.parse_retain_history()?,
+            _ => self.parse_optional_option_value()?,
+        };
+        Ok(CreateSourceOption { name, value })
+    }
+
+    /// Parses the value of a `RETAIN HISTORY` option, i.e. `[=] FOR <interval>`.
+    fn parse_retain_history(&mut self) -> Result<Option<WithOptionValue<Raw>>, ParserError> {
+        let _ = self.consume_token(&Token::Eq);
+        self.expect_keyword(FOR)?;
+        let value = if self.parse_keyword(INTERVAL) {
+            self.parse_interval_value()?
+        } else {
+            self.parse_value()?
+        };
+        Ok(Some(WithOptionValue::RetainHistoryFor(value)))
     }
 
     fn parse_create_webhook_source(
@@ -3071,9 +3222,10 @@ impl<'a> Parser<'a> {
             LOAD => {
                 self.expect_keyword(GENERATOR)?;
                 let generator = match self
-                    .expect_one_of_keywords(&[COUNTER, MARKETING, AUCTION, TPCH, DATUMS])?
+                    .expect_one_of_keywords(&[COUNTER, MARKETING, AUCTION, TPCH, DATUMS, CUSTOM])?
                 {
                     COUNTER => LoadGenerator::Counter,
+                    CUSTOM => LoadGenerator::Custom,
                     AUCTION => LoadGenerator::Auction,
                     TPCH => LoadGenerator::Tpch,
                     DATUMS => LoadGenerator::Datums,
@@ -3101,9 +3253,13 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_pg_connection_option(&mut self) -> Result<PgConfigOption<Raw>, ParserError> {
-        let name = match self.expect_one_of_keywords(&[DETAILS, PUBLICATION, TEXT])? {
+        let name = match self.expect_one_of_keywords(&[DETAILS, PUBLICATION, SCHEMA, TEXT])? {
             DETAILS => PgConfigOptionName::Details,
             PUBLICATION => PgConfigOptionName::Publication,
+            SCHEMA => {
+                self.expect_keyword(CHANGES)?;
+                PgConfigOptionName::SchemaChanges
+            }
             TEXT => {
                 self.expect_keyword(COLUMNS)?;
 
@@ -3134,7 +3290,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_load_generator_option(&mut self) -> Result<LoadGeneratorOption<Raw>, ParserError> {
-        let name = match self.expect_one_of_keywords(&[SCALE, TICK, MAX])? {
+        let name = match self.expect_one_of_keywords(&[SCALE, TICK, MAX, SPEC, SEED])? {
             SCALE => {
                 self.expect_keyword(FACTOR)?;
                 LoadGeneratorOptionName::ScaleFactor
@@ -3147,6 +3303,8 @@ impl<'a> Parser<'a> {
                 self.expect_keyword(CARDINALITY)?;
                 LoadGeneratorOptionName::MaxCardinality
             }
+            SPEC => LoadGeneratorOptionName::Spec,
+            SEED => LoadGeneratorOptionName::Seed,
             _ => unreachable!(),
         };
 
@@ -3239,6 +3397,15 @@ impl<'a> Parser<'a> {
         let columns = self.parse_parenthesized_column_list(Optional)?;
         let in_cluster = self.parse_optional_in_cluster()?;
 
+        let with_options = if self.parse_keyword(WITH) {
+            self.expect_token(&Token::LParen)?;
+            let options = self.parse_comma_separated(Parser::parse_materialized_view_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
+
         self.expect_keyword(AS)?;
         let query = self.parse_query()?;
 
@@ -3249,10 +3416,68 @@ impl<'a> Parser<'a> {
                 columns,
                 in_cluster,
                 query,
+                with_options,
             },
         ))
     }
 
+    fn parse_materialized_view_option(
+        &mut self,
+    ) -> Result<MaterializedViewOption<Raw>, ParserError> {
+        let (name, value) = match self.expect_one_of_keywords(&[RETAIN, REFRESH])? {
+            RETAIN => {
+                self.expect_keyword(HISTORY)?;
+                (
+                    MaterializedViewOptionName::RetainHistory,
+                    self.parse_retain_history()?,
+                )
+            }
+            REFRESH => (
+                MaterializedViewOptionName::Refresh,
+                Some(self.parse_refresh_option_value()?),
+            ),
+            _ => unreachable!(),
+        };
+        Ok(MaterializedViewOption { name, value })
+    }
+
+    fn parse_refresh_option_value(&mut self) -> Result<WithOptionValue<Raw>, ParserError> {
+        let _ = self.consume_token(&Token::Eq);
+        let value = match self.expect_one_of_keywords(&[ON, AT, EVERY])? {
+            ON => {
+                self.expect_keyword(COMMIT)?;
+                RefreshOptionValue::OnCommit
+            }
+            AT => {
+                if self.parse_keyword(CREATION) {
+                    RefreshOptionValue::AtCreation
+                } else {
+                    RefreshOptionValue::At(RefreshAtOptionValue {
+                        time: self.parse_expr()?,
+                    })
+                }
+            }
+            EVERY => {
+                let interval = if self.parse_keyword(INTERVAL) {
+                    self.parse_interval_value()?
+                } else {
+                    self.parse_value()?
+                };
+                let aligned_to = if self.parse_keywords(&[ALIGNED, TO]) {
+                    Some(self.parse_expr()?)
+                } else {
+                    None
+                };
+                RefreshOptionValue::Every(RefreshEveryOptionValue {
+                    interval,
+                    aligned_to,
+                })
+            }
+            _ => unreachable!(),
+        };
+        Ok(WithOptionValue::Refresh(value))
+    }
+
     fn parse_create_index(&mut self) -> Result<Statement<Raw>, ParserError> {
         let default_index = self.parse_keyword(DEFAULT);
         self.expect_keyword(INDEX)?;
@@ -3393,6 +3618,132 @@ impl<'a> Parser<'a> {
         options
     }
 
+    fn parse_create_function(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keyword(FUNCTION)?;
+        let name = self.parse_item_name()?;
+        self.expect_token(&Token::LParen)?;
+        let parameters = if self.consume_token(&Token::RParen) {
+            vec![]
+        } else {
+            let parameters = self.parse_comma_separated(Parser::parse_function_parameter)?;
+            self.expect_token(&Token::RParen)?;
+            parameters
+        };
+        self.expect_keyword(RETURNS)?;
+        let returns = if self.parse_keyword(SETOF) {
+            FunctionReturns::SetOf(self.parse_data_type()?)
+        } else if self.parse_keyword(TABLE) {
+            self.expect_token(&Token::LParen)?;
+            let columns = self.parse_comma_separated(|parser| {
+                Ok(FunctionColumn {
+                    name: parser.parse_identifier()?,
+                    data_type: parser.parse_data_type()?,
+                })
+            })?;
+            self.expect_token(&Token::RParen)?;
+            FunctionReturns::Table(columns)
+        } else {
+            FunctionReturns::Scalar(self.parse_data_type()?)
+        };
+
+        // The remaining clauses may appear in any order, but each at most once.
+        let mut language = None;
+        let mut behavior = None;
+        let mut body = None;
+        loop {
+            if self.parse_keyword(LANGUAGE) {
+                if language.is_some() {
+                    return parser_err!(
+                        self,
+                        self.peek_prev_pos(),
+                        "conflicting or redundant options"
+                    );
+                }
+                let pos = self.peek_pos();
+                let ident = self.parse_identifier()?;
+                if ident.as_str().to_lowercase() != "sql" {
+                    return parser_err!(
+                        self,
+                        pos,
+                        "LANGUAGE {} is not supported; only LANGUAGE SQL functions can be created",
+                        ident
+                    );
+                }
+                language = Some(ident);
+            } else if let Some(kw) = self.parse_one_of_keywords(&[IMMUTABLE, STABLE, VOLATILE]) {
+                if behavior.is_some() {
+                    return parser_err!(
+                        self,
+                        self.peek_prev_pos(),
+                        "conflicting or redundant options"
+                    );
+                }
+                behavior = Some(match kw {
+                    IMMUTABLE => FunctionBehavior::Immutable,
+                    STABLE => FunctionBehavior::Stable,
+                    VOLATILE => FunctionBehavior::Volatile,
+                    _ => unreachable!(),
+                });
+            } else if self.parse_keyword(AS) {
+                if body.is_some() {
+                    return parser_err!(
+                        self,
+                        self.peek_prev_pos(),
+                        "conflicting or redundant options"
+                    );
+                }
+                let pos = self.peek_pos();
+                let sql = self.parse_literal_string()?;
+                body = Some(parse_function_body(&sql).map_err(|e| ParserError {
+                    pos,
+                    message: format!("invalid function body: {}", e.message),
+                })?);
+            } else {
+                break;
+            }
+        }
+        if language.is_none() {
+            return parser_err!(self, self.peek_pos(), "no language specified");
+        }
+        let Some(body) = body else {
+            return parser_err!(self, self.peek_pos(), "no function body specified");
+        };
+        Ok(Statement::CreateFunction(CreateFunctionStatement {
+            name,
+            parameters,
+            returns,
+            // Like PostgreSQL, functions are volatile unless declared otherwise.
+            behavior: behavior.unwrap_or(FunctionBehavior::Volatile),
+            body,
+        }))
+    }
+
+    /// Parses a function parameter, whose name is optional.
+    fn parse_function_parameter(&mut self) -> Result<FunctionParameter<Raw>, ParserError> {
+        // A lone data type is only unambiguous once it is followed by the end
+        // of the parameter; otherwise the first identifier names the parameter.
+        let unnamed = self.maybe_parse(|parser| {
+            let data_type = parser.parse_data_type()?;
+            if !matches!(
+                parser.peek_token(),
+                Some(Token::Comma) | Some(Token::RParen)
+            ) {
+                return parser.expected(parser.peek_pos(), "comma or )", parser.peek_token());
+            }
+            Ok(data_type)
+        });
+        match unnamed {
+            Some(data_type) => Ok(FunctionParameter {
+                name: None,
+                data_type,
+            }),
+            None => Ok(FunctionParameter {
+                name: Some(self.parse_identifier()?),
+                data_type: self.parse_data_type()?,
+            }),
+        }
+    }
+
     fn parse_create_secret(&mut self) -> Result<Statement<Raw>, ParserError> {
         self.expect_keyword(SECRET)?;
         let if_not_exists = self.parse_if_not_exists()?;
@@ -3406,6 +3757,87 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    fn parse_create_sequence(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keyword(SEQUENCE)?;
+        let if_not_exists = self.parse_if_not_exists()?;
+        let name = self.parse_item_name()?;
+        let mut options = vec![];
+        while let Some(option) = self.parse_optional_sequence_option()? {
+            options.push(option);
+        }
+        Ok(Statement::CreateSequence(CreateSequenceStatement {
+            name,
+            if_not_exists,
+            options,
+        }))
+    }
+
+    fn parse_optional_sequence_option(
+        &mut self,
+    ) -> Result<Option<SequenceOption<Raw>>, ParserError> {
+        let option = if self.parse_keyword(INCREMENT) {
+            let _ = self.parse_keyword(BY);
+            SequenceOption::IncrementBy(self.parse_signed_literal_int()?)
+        } else if self.parse_keyword(MINVALUE) {
+            SequenceOption::MinValue(Some(self.parse_signed_literal_int()?))
+        } else if self.parse_keyword(MAXVALUE) {
+            SequenceOption::MaxValue(Some(self.parse_signed_literal_int()?))
+        } else if self.parse_keyword(START) {
+            let _ = self.parse_keyword(WITH);
+            SequenceOption::StartWith(self.parse_signed_literal_int()?)
+        } else if self.parse_keyword(CACHE) {
+            SequenceOption::Cache(self.parse_signed_literal_int()?)
+        } else if self.parse_keyword(CYCLE) {
+            SequenceOption::Cycle(true)
+        } else if self.parse_keyword(NO) {
+            match self.expect_one_of_keywords(&[MINVALUE, MAXVALUE, CYCLE])? {
+                MINVALUE => SequenceOption::MinValue(None),
+                MAXVALUE => SequenceOption::MaxValue(None),
+                CYCLE => SequenceOption::Cycle(false),
+                _ => unreachable!(),
+            }
+        } else if self.parse_keywords(&[OWNED, BY]) {
+            if self.parse_keyword(NONE) {
+                SequenceOption::OwnedBy(None)
+            } else {
+                let mut idents = self.parse_identifiers()?;
+                if idents.len() < 2 {
+                    return parser_err!(
+                        self,
+                        self.peek_prev_pos(),
+                        "OWNED BY requires a table and column name"
+                    );
+                }
+                let column = idents.pop().expect("checked length");
+                SequenceOption::OwnedBy(Some(SequenceOwner {
+                    table: RawItemName::Name(UnresolvedItemName(idents)),
+                    column,
+                }))
+            }
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(option))
+    }
+
+    /// Parse a literal integer that is optionally preceded by a sign.
+    fn parse_signed_literal_int(&mut self) -> Result<i64, ParserError> {
+        if self.consume_token(&Token::Op("-".into())) {
+            match self.next_token() {
+                Some(Token::Number(s)) => format!("-{s}").parse::<i64>().map_err(|e| {
+                    self.error(
+                        self.peek_prev_pos(),
+                        format!("Could not parse '-{}' as i64: {}", s, e),
+                    )
+                }),
+                other => self.expected(self.peek_prev_pos(), "literal integer", other),
+            }
+        } else {
+            let _ = self.consume_token(&Token::Op("+".into()));
+            self.parse_literal_int()
+        }
+    }
+
     fn parse_create_type(&mut self) -> Result<Statement<Raw>, ParserError> {
         self.expect_keyword(TYPE)?;
         let name = self.parse_item_name()?;
@@ -3732,7 +4164,9 @@ impl<'a> Parser<'a> {
             | ObjectType::Index
             | ObjectType::Type
             | ObjectType::Secret
-            | ObjectType::Connection => {
+            | ObjectType::Connection
+            | ObjectType::Sequence
+            | ObjectType::Func => {
                 let names = self.parse_comma_separated(|parser| {
                     Ok(UnresolvedObjectName::Item(parser.parse_item_name()?))
                 })?;
@@ -3747,7 +4181,7 @@ impl<'a> Parser<'a> {
                     cascade,
                 }))
             }
-            ObjectType::Func | ObjectType::Subsource => parser_err!(
+            ObjectType::Subsource => parser_err!(
                 self,
                 self.peek_prev_pos(),
                 format!("Unsupported DROP on {object_type}")
@@ -3816,15 +4250,32 @@ impl<'a> Parser<'a> {
         // parse optional column list (schema)
         let (columns, constraints) = self.parse_columns(Mandatory)?;
 
+        let with_options = if self.parse_keyword(WITH) {
+            self.expect_token(&Token::LParen)?;
+            let options = self.parse_comma_separated(Parser::parse_table_option)?;
+            self.expect_token(&Token::RParen)?;
+            options
+        } else {
+            vec![]
+        };
+
         Ok(Statement::CreateTable(CreateTableStatement {
             name: table_name,
             columns,
             constraints,
             if_not_exists,
             temporary,
+            with_options,
         }))
     }
 
+    fn parse_table_option(&mut self) -> Result<TableOption<Raw>, ParserError> {
+        self.expect_keywords(&[RETAIN, HISTORY])?;
+        let name = TableOptionName::RetainHistory;
+        let value = self.parse_retain_history()?;
+        Ok(TableOption { name, value })
+    }
+
     fn parse_columns(
         &mut self,
         optional: IsOptional,
@@ -3852,13 +4303,33 @@ impl<'a> Parser<'a> {
             if let Some(constraint) = self.parse_optional_table_constraint()? {
                 constraints.push(constraint);
             } else if let Some(column_name) = self.consume_identifier() {
-                let data_type = self.parse_data_type()?;
+                let mut data_type = self.parse_data_type()?;
                 let collation = if self.parse_keyword(COLLATE) {
                     Some(self.parse_item_name()?)
                 } else {
                     None
                 };
                 let mut options = vec![];
+                // The serial types are not true types, but a notational
+                // convenience for an integer identity column, as in
+                // PostgreSQL.
+                if let Some(integer_type) = serial_integer_type(&data_type) {
+                    data_type = RawDataType::Other {
+                        name: RawItemName::Name(UnresolvedItemName::unqualified(integer_type)),
+                        typ_mod: vec![],
+                    };
+                    options.push(ColumnOptionDef {
+                        name: None,
+                        option: ColumnOption::NotNull,
+                    });
+                    options.push(ColumnOptionDef {
+                        name: None,
+                        option: ColumnOption::Identity {
+                            always: false,
+                            options: vec![],
+                        },
+                    });
+                }
                 loop {
                     match self.peek_token() {
                         None | Some(Token::Comma) | Some(Token::RParen) => break,
@@ -3924,6 +4395,24 @@ impl<'a> Parser<'a> {
             let expr = self.parse_expr()?;
             self.expect_token(&Token::RParen)?;
             ColumnOption::Check(expr)
+        } else if self.parse_keyword(GENERATED) {
+            let always = match self.expect_one_of_keywords(&[ALWAYS, BY])? {
+                ALWAYS => true,
+                BY => {
+                    self.expect_keyword(DEFAULT)?;
+                    false
+                }
+                _ => unreachable!(),
+            };
+            self.expect_keywords(&[AS, IDENTITY])?;
+            let mut options = vec![];
+            if self.consume_token(&Token::LParen) {
+                while let Some(option) = self.parse_optional_sequence_option()? {
+                    options.push(option);
+                }
+                self.expect_token(&Token::RParen)?;
+            }
+            ColumnOption::Identity { always, options }
         } else {
             return self.expected(self.peek_pos(), "column option", self.peek_token());
         };
@@ -4098,7 +4587,7 @@ impl<'a> Parser<'a> {
             ObjectType::View | ObjectType::MaterializedView | ObjectType::Table => {
                 self.parse_alter_views(object_type)
             }
-            ObjectType::Type => {
+            ObjectType::Type | ObjectType::Sequence => {
                 let if_exists = self
                     .parse_if_exists()
                     .map_parser_err(StatementKind::AlterOwner)?;
@@ -4344,6 +4833,14 @@ impl<'a> Parser<'a> {
                     })
                 }
                 RESET => {
+                    if let Some(stmt) = self.maybe_parse_alter_retain_history(
+                        if_exists,
+                        &source_name,
+                        ObjectType::Source,
+                        true,
+                    ) {
+                        return stmt;
+                    }
                     self.expect_token(&Token::LParen)
                         .map_parser_err(StatementKind::AlterSource)?;
                     let reset_options = self
@@ -4366,6 +4863,14 @@ impl<'a> Parser<'a> {
                     ) {
                         return stmt;
                     }
+                    if let Some(stmt) = self.maybe_parse_alter_retain_history(
+                        if_exists,
+                        &source_name,
+                        ObjectType::Source,
+                        false,
+                    ) {
+                        return stmt;
+                    }
                     self.expect_token(&Token::LParen)
                         .map_parser_err(StatementKind::AlterSource)?;
                     let set_options = self
@@ -4807,7 +5312,7 @@ impl<'a> Parser<'a> {
         let if_exists = self.parse_if_exists().map_no_statement_parser_err()?;
         let name = self.parse_item_name().map_no_statement_parser_err()?;
         let action = self
-            .expect_one_of_keywords(&[SET, RENAME, OWNER])
+            .expect_one_of_keywords(&[SET, RESET, RENAME, OWNER])
             .map_no_statement_parser_err()?;
         match action {
             RENAME => {
@@ -4822,7 +5327,11 @@ impl<'a> Parser<'a> {
                     to_item_name,
                 }))
             }
+            SET if self.peek_token() == Some(Token::LParen) => {
+                self.parse_alter_retain_history(if_exists, name, object_type, false)
+            }
             SET => self.parse_alter_set_cluster(if_exists, name, object_type),
+            RESET => self.parse_alter_retain_history(if_exists, name, object_type, true),
             OWNER => {
                 self.expect_keyword(TO).map_no_statement_parser_err()?;
                 let new_owner = self
@@ -4853,6 +5362,53 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Parses `(RETAIN HISTORY ...)` fragments into an [`AlterRetainHistoryStatement`] if
+    /// `(RETAIN` is found.
+    fn maybe_parse_alter_retain_history(
+        &mut self,
+        if_exists: bool,
+        name: &UnresolvedItemName,
+        object_type: ObjectType,
+        reset: bool,
+    ) -> Option<Result<Statement<Raw>, ParserStatementError>> {
+        if self.peek_token() == Some(Token::LParen)
+            && self.peek_nth_token(1) == Some(Token::Keyword(RETAIN))
+        {
+            Some(self.parse_alter_retain_history(if_exists, name.clone(), object_type, reset))
+        } else {
+            None
+        }
+    }
+
+    /// Parses the `(RETAIN HISTORY [[=] FOR <interval>])` fragment following `SET` or `RESET`
+    /// into an [`AlterRetainHistoryStatement`].
+    fn parse_alter_retain_history(
+        &mut self,
+        if_exists: bool,
+        name: UnresolvedItemName,
+        object_type: ObjectType,
+        reset: bool,
+    ) -> Result<Statement<Raw>, ParserStatementError> {
+        self.expect_token(&Token::LParen)
+            .map_parser_err(StatementKind::AlterRetainHistory)?;
+        self.expect_keywords(&[RETAIN, HISTORY])
+            .map_parser_err(StatementKind::AlterRetainHistory)?;
+        let history = if reset {
+            None
+        } else {
+            self.parse_retain_history()
+                .map_parser_err(StatementKind::AlterRetainHistory)?
+        };
+        self.expect_token(&Token::RParen)
+            .map_parser_err(StatementKind::AlterRetainHistory)?;
+        Ok(Statement::AlterRetainHistory(AlterRetainHistoryStatement {
+            object_type,
+            if_exists,
+            name,
+            history,
+        }))
+    }
+
     /// Parses `IN CLUSTER name` fragments into a [`AlterSetClusterStatement`].
     fn parse_alter_set_cluster(
         &mut self,
@@ -5344,7 +5900,8 @@ impl<'a> Parser<'a> {
             | ObjectType::Type
             | ObjectType::Secret
             | ObjectType::Connection
-            | ObjectType::Func => UnresolvedObjectName::Item(self.parse_item_name()?),
+            | ObjectType::Func
+            | ObjectType::Sequence => UnresolvedObjectName::Item(self.parse_item_name()?),
             ObjectType::Role => UnresolvedObjectName::Role(self.parse_identifier()?),
             ObjectType::Cluster => UnresolvedObjectName::Cluster(self.parse_identifier()?),
             ObjectType::ClusterReplica => {
@@ -5520,8 +6077,9 @@ impl<'a> Parser<'a> {
                         options,
                         ctes: parser.parse_comma_separated(Parser::parse_cte_mut_rec)?,
                     })
+                } else if parser.parse_keyword(RECURSIVE) {
+                    CteBlock::Recursive(parser.parse_comma_separated(Parser::parse_cte_recursive)?)
                 } else {
-                    // TODO: optional RECURSIVE
                     CteBlock::Simple(parser.parse_comma_separated(Parser::parse_cte)?)
                 }
             } else {
@@ -5689,6 +6247,61 @@ impl<'a> Parser<'a> {
         })
     }
 
+    /// Parse a CTE in a `WITH RECURSIVE` block (`alias [( col1, col2, ... )] AS
+    /// (subquery) [SEARCH ...] [CYCLE ...]`).
+    fn parse_cte_recursive(&mut self) -> Result<CteRecursive<Raw>, ParserError> {
+        let Cte { alias, id, query } = self.parse_cte()?;
+        let search = if self.parse_keyword(SEARCH) {
+            let order = match self.expect_one_of_keywords(&[DEPTH, BREADTH])? {
+                DEPTH => CteSearchOrder::DepthFirst,
+                BREADTH => CteSearchOrder::BreadthFirst,
+                _ => unreachable!(),
+            };
+            self.expect_keywords(&[FIRST, BY])?;
+            let columns = self.parse_comma_separated(Parser::parse_identifier)?;
+            self.expect_keyword(SET)?;
+            let sequence_column = self.parse_identifier()?;
+            Some(CteSearch {
+                order,
+                columns,
+                sequence_column,
+            })
+        } else {
+            None
+        };
+        let cycle = if self.parse_keyword(CYCLE) {
+            let columns = self.parse_comma_separated(Parser::parse_identifier)?;
+            self.expect_keyword(SET)?;
+            let mark_column = self.parse_identifier()?;
+            let (mark_value, mark_default) = if self.parse_keyword(TO) {
+                let mark_value = self.parse_value()?;
+                self.expect_keyword(DEFAULT)?;
+                let mark_default = self.parse_value()?;
+                (Some(mark_value), Some(mark_default))
+            } else {
+                (None, None)
+            };
+            self.expect_keyword(USING)?;
+            let path_column = self.parse_identifier()?;
+            Some(CteCycle {
+                columns,
+                mark_column,
+                mark_value,
+                mark_default,
+                path_column,
+            })
+        } else {
+            None
+        };
+        Ok(CteRecursive {
+            alias,
+            id,
+            query,
+            search,
+            cycle,
+        })
+    }
+
     /// Parse a mutually recursive CTE (`alias ( col1: typ1, col2: typ2, ... ) AS (subquery)`).
     ///
     /// The main distinction from `parse_cte` is that the column names and types are mandatory.
@@ -5883,7 +6496,9 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_select_option(&mut self) -> Result<SelectOption<Raw>, ParserError> {
-        let name = match self.expect_one_of_keywords(&[EXPECTED, AGGREGATE, DISTINCT, LIMIT])? {
+        let name = match self
+            .expect_one_of_keywords(&[EXPECTED, AGGREGATE, DISTINCT, LIMIT, JOIN, DELTA, NO])?
+        {
             EXPECTED => {
                 self.expect_keywords(&[GROUP, SIZE])?;
                 SelectOptionName::ExpectedGroupSize
@@ -5900,6 +6515,32 @@ impl<'a> Parser<'a> {
                 self.expect_keywords(&[INPUT, GROUP, SIZE])?;
                 SelectOptionName::LimitInputGroupSize
             }
+            JOIN => {
+                self.expect_keyword(ORDER)?;
+                SelectOptionName::JoinOrder
+            }
+            DELTA => {
+                self.expect_keyword(JOIN)?;
+                SelectOptionName::DeltaJoin
+            }
+            NO => {
+                self.expect_keyword(INDEX)?;
+                // Index names must be parsed as item names so that they are
+                // resolved against the catalog, which the generic option value
+                // parser does not do. A single name is wrapped in a sequence so
+                // that planning only needs to handle one shape.
+                let _ = self.consume_token(&Token::Eq);
+                let names = match self.parse_option_sequence(Parser::parse_raw_name)? {
+                    Some(names) => names,
+                    None => vec![self.parse_raw_name()?],
+                };
+                return Ok(SelectOption {
+                    name: SelectOptionName::NoIndex,
+                    value: Some(WithOptionValue::Sequence(
+                        names.into_iter().map(WithOptionValue::Item).collect(),
+                    )),
+                });
+            }
             _ => unreachable!(),
         };
         Ok(SelectOption {
@@ -6096,7 +6737,7 @@ impl<'a> Parser<'a> {
                         on_object,
                     }
                 }
-                ObjectType::Func => {
+                ObjectType::Func | ObjectType::Sequence => {
                     return parser_err!(
                         self,
                         self.peek_prev_pos(),
@@ -6271,7 +6912,7 @@ impl<'a> Parser<'a> {
                 let name = self.parse_raw_name()?;
                 self.expect_token(&Token::LParen)?;
                 let args = self.parse_optional_args(false)?;
-                let alias = self.parse_optional_table_alias()?;
+                let (alias, column_defs) = self.parse_table_function_alias()?;
                 let with_ordinality = self.parse_keywords(&[WITH, ORDINALITY]);
                 return Ok(TableFactor::Function {
                     function: Function {
@@ -6282,6 +6923,7 @@ impl<'a> Parser<'a> {
                         distinct: false,
                     },
                     alias,
+                    column_defs,
                     with_ordinality,
                 });
             }
@@ -6343,7 +6985,7 @@ impl<'a> Parser<'a> {
             let name = self.parse_raw_name()?;
             if self.consume_token(&Token::LParen) {
                 let args = self.parse_optional_args(false)?;
-                let alias = self.parse_optional_table_alias()?;
+                let (alias, column_defs) = self.parse_table_function_alias()?;
                 let with_ordinality = self.parse_keywords(&[WITH, ORDINALITY]);
                 Ok(TableFactor::Function {
                     function: Function {
@@ -6354,6 +6996,7 @@ impl<'a> Parser<'a> {
                         distinct: false,
                     },
                     alias,
+                    column_defs,
                     with_ordinality,
                 })
             } else {
@@ -6365,6 +7008,37 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Parses the optional alias of a table function, which may be followed
+    /// by, or replaced with, a column definition list, as in
+    /// `[AS] [alias] (name type, ...)`.
+    fn parse_table_function_alias(
+        &mut self,
+    ) -> Result<(Option<TableAlias>, Option<Vec<ColumnDef<Raw>>>), ParserError> {
+        if self.peek_keyword(AS) && self.peek_nth_token(1) == Some(Token::LParen) {
+            self.expect_keyword(AS)?;
+            let column_defs = self.parse_composite_type_definition()?;
+            return Ok((None, Some(column_defs)));
+        }
+        let Some(name) = self.parse_optional_alias(Keyword::is_reserved_in_table_alias)? else {
+            return Ok((None, None));
+        };
+        let column_defs = if self.peek_token() == Some(Token::LParen) {
+            self.maybe_parse(|parser| parser.parse_composite_type_definition())
+        } else {
+            None
+        };
+        let columns = match column_defs {
+            Some(_) => vec![],
+            None => self.parse_parenthesized_column_list(Optional)?,
+        };
+        let alias = TableAlias {
+            name,
+            columns,
+            strict: false,
+        };
+        Ok((Some(alias), column_defs))
+    }
+
     fn parse_rows_from(&mut self) -> Result<TableFactor<Raw>, ParserError> {
         self.expect_token(&Token::LParen)?;
         let functions = self.parse_comma_separated(Parser::parse_named_function)?;
@@ -6433,15 +7107,53 @@ impl<'a> Parser<'a> {
         } else {
             InsertSource::Query(self.parse_query()?)
         };
+        let on_conflict = if self.parse_keywords(&[ON, CONFLICT]) {
+            Some(self.parse_on_conflict()?)
+        } else {
+            None
+        };
         let returning = self.parse_returning()?;
         Ok(Statement::Insert(InsertStatement {
             table_name,
             columns,
             source,
+            on_conflict,
             returning,
         }))
     }
 
+    /// Parse the remainder of an `ON CONFLICT` clause, assuming that the
+    /// `ON CONFLICT` tokens have already been consumed.
+    fn parse_on_conflict(&mut self) -> Result<OnConflict<Raw>, ParserError> {
+        let target = self.parse_parenthesized_column_list(Optional)?;
+        self.expect_keyword(DO)?;
+        let action = match self.expect_one_of_keywords(&[NOTHING, UPDATE])? {
+            NOTHING => OnConflictAction::DoNothing,
+            UPDATE => {
+                if target.is_empty() {
+                    return parser_err!(
+                        self,
+                        self.peek_prev_pos(),
+                        "ON CONFLICT DO UPDATE requires a conflict target"
+                    );
+                }
+                self.expect_keyword(SET)?;
+                let assignments = self.parse_comma_separated(Parser::parse_assignment)?;
+                let selection = if self.parse_keyword(WHERE) {
+                    Some(self.parse_expr()?)
+                } else {
+                    None
+                };
+                OnConflictAction::DoUpdate {
+                    assignments,
+                    selection,
+                }
+            }
+            _ => unreachable!(),
+        };
+        Ok(OnConflict { target, action })
+    }
+
     fn parse_returning(&mut self) -> Result<Vec<SelectItem<Raw>>, ParserError> {
         Ok(if self.parse_keyword(RETURNING) {
             self.parse_comma_separated(Parser::parse_select_item)?
@@ -6476,6 +7188,83 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    fn parse_merge(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keyword(INTO)?;
+        let table_name = RawItemName::Name(self.parse_item_name()?);
+        // The alias here doesn't support columns, so don't use parse_optional_table_alias.
+        let alias = self.parse_optional_alias(Keyword::is_reserved_in_table_alias)?;
+        let alias = alias.map(|name| TableAlias {
+            name,
+            columns: Vec::new(),
+            strict: false,
+        });
+        self.expect_keyword(USING)?;
+        let source = self.parse_table_factor()?;
+        self.expect_keyword(ON)?;
+        let on = self.parse_expr()?;
+        let mut clauses = vec![self.parse_merge_clause()?];
+        while self.peek_keyword(WHEN) {
+            clauses.push(self.parse_merge_clause()?);
+        }
+        Ok(Statement::Merge(MergeStatement {
+            table_name,
+            alias,
+            source,
+            on,
+            clauses,
+        }))
+    }
+
+    fn parse_merge_clause(&mut self) -> Result<MergeClause<Raw>, ParserError> {
+        self.expect_keyword(WHEN)?;
+        let matched = !self.parse_keyword(NOT);
+        self.expect_keyword(MATCHED)?;
+        let condition = if self.parse_keyword(AND) {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
+        self.expect_keyword(THEN)?;
+        let action_pos = self.peek_pos();
+        let action = match self.expect_one_of_keywords(&[UPDATE, DELETE, INSERT, DO])? {
+            UPDATE => {
+                self.expect_keyword(SET)?;
+                MergeAction::Update {
+                    assignments: self.parse_comma_separated(Parser::parse_assignment)?,
+                }
+            }
+            DELETE => MergeAction::Delete,
+            INSERT => {
+                let columns = self.parse_parenthesized_column_list(Optional)?;
+                self.expect_keyword(VALUES)?;
+                self.expect_token(&Token::LParen)?;
+                let values = self.parse_comma_separated(Parser::parse_expr)?;
+                self.expect_token(&Token::RParen)?;
+                MergeAction::Insert { columns, values }
+            }
+            DO => {
+                self.expect_keyword(NOTHING)?;
+                MergeAction::DoNothing
+            }
+            _ => unreachable!(),
+        };
+        match (&action, matched) {
+            (MergeAction::Insert { .. }, true) => {
+                parser_err!(self, action_pos, "INSERT is not allowed in WHEN MATCHED")
+            }
+            (MergeAction::Update { .. } | MergeAction::Delete, false) => parser_err!(
+                self,
+                action_pos,
+                "UPDATE and DELETE are not allowed in WHEN NOT MATCHED"
+            ),
+            _ => Ok(MergeClause {
+                matched,
+                condition,
+                action,
+            }),
+        }
+    }
+
     /// Parse a `var = expr` assignment, used in an UPDATE statement
     fn parse_assignment(&mut self) -> Result<Assignment<Raw>, ParserError> {
         let id = self.parse_identifier()?;
@@ -6914,7 +7703,8 @@ impl<'a> Parser<'a> {
             Statement::Select(_)
                 | Statement::Insert(_)
                 | Statement::Delete(_)
-                | Statement::Update(_),
+                | Statement::Update(_)
+                | Statement::Merge(_),
         ) {
             return parser_err!(self, pos, "unpreparable statement").map_no_statement_parser_err();
         }
@@ -7204,7 +7994,8 @@ impl<'a> Parser<'a> {
             | ObjectType::Secret
             | ObjectType::Connection
             | ObjectType::Database
-            | ObjectType::Schema => Ok(object_type),
+            | ObjectType::Schema
+            | ObjectType::Sequence => Ok(object_type),
         }
     }
 
@@ -7227,6 +8018,7 @@ impl<'a> Parser<'a> {
                 DATABASE,
                 SCHEMA,
                 FUNCTION,
+                SEQUENCE,
             ])? {
                 TABLE => ObjectType::Table,
                 VIEW => ObjectType::View,
@@ -7254,6 +8046,7 @@ impl<'a> Parser<'a> {
                 DATABASE => ObjectType::Database,
                 SCHEMA => ObjectType::Schema,
                 FUNCTION => ObjectType::Func,
+                SEQUENCE => ObjectType::Sequence,
                 _ => unreachable!(),
             },
         )
@@ -7278,6 +8071,7 @@ impl<'a> Parser<'a> {
                 DATABASE,
                 SCHEMA,
                 FUNCTION,
+                SEQUENCE,
             ])? {
                 TABLE => ObjectType::Table,
                 VIEW => ObjectType::View,
@@ -7306,6 +8100,7 @@ impl<'a> Parser<'a> {
                 DATABASE => ObjectType::Database,
                 SCHEMA => ObjectType::Schema,
                 FUNCTION => ObjectType::Func,
+                SEQUENCE => ObjectType::Sequence,
                 _ => unreachable!(),
             },
         )
@@ -18,9 +18,10 @@ use mz_sql_parser::ast::CreateWebhookSourceStatement;
 use crate::ast::visit::{self, Visit};
 use crate::ast::visit_mut::{self, VisitMut};
 use crate::ast::{
-    AstInfo, CreateConnectionStatement, CreateIndexStatement, CreateMaterializedViewStatement,
-    CreateSecretStatement, CreateSinkStatement, CreateSourceStatement, CreateTableStatement,
-    CreateViewStatement, Expr, Ident, Query, Raw, RawItemName, Statement, UnresolvedItemName,
+    AstInfo, CreateConnectionStatement, CreateFunctionStatement, CreateIndexStatement,
+    CreateMaterializedViewStatement, CreateSecretStatement, CreateSequenceStatement,
+    CreateSinkStatement, CreateSourceStatement, CreateTableStatement, CreateViewStatement, Expr,
+    Ident, Query, Raw, RawItemName, SequenceOption, SequenceOwner, Statement, UnresolvedItemName,
     ViewDefinition,
 };
 use crate::names::FullItemName;
@@ -52,6 +53,14 @@ pub fn create_stmt_rename(create_stmt: &mut Statement<Raw>, to_item_name: String
             let item_name_len = name.0.len() - 1;
             name.0[item_name_len] = Ident::new(to_item_name);
         }
+        Statement::CreateSequence(CreateSequenceStatement { name, .. }) => {
+            let item_name_len = name.0.len() - 1;
+            name.0[item_name_len] = Ident::new(to_item_name);
+        }
+        Statement::CreateFunction(CreateFunctionStatement { name, .. }) => {
+            let item_name_len = name.0.len() - 1;
+            name.0[item_name_len] = Ident::new(to_item_name);
+        }
         Statement::CreateConnection(CreateConnectionStatement { name, .. }) => {
             let item_name_len = name.0.len() - 1;
             name.0[item_name_len] = Ident::new(to_item_name);
@@ -104,9 +113,17 @@ pub fn create_stmt_rename_refs(
             definition: ViewDefinition { query, .. },
             ..
         })
-        | Statement::CreateMaterializedView(CreateMaterializedViewStatement { query, .. }) => {
+        | Statement::CreateMaterializedView(CreateMaterializedViewStatement { query, .. })
+        | Statement::CreateFunction(CreateFunctionStatement { body: query, .. }) => {
             rewrite_query(from_name, to_item_name, query)?;
         }
+        Statement::CreateSequence(CreateSequenceStatement { options, .. }) => {
+            for option in options {
+                if let SequenceOption::OwnedBy(Some(SequenceOwner { table, .. })) = option {
+                    maybe_update_item_name(table.name_mut());
+                }
+            }
+        }
         Statement::CreateSource(_)
         | Statement::CreateTable(_)
         | Statement::CreateSecret(_)
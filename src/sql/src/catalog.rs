@@ -51,7 +51,7 @@ use crate::names::{
 use crate::normalize;
 use crate::plan::statement::ddl::PlannedRoleAttributes;
 use crate::plan::statement::StatementDesc;
-use crate::plan::{PlanError, PlanNotice};
+use crate::plan::{PlanError, PlanNotice, SequenceOptions};
 use crate::session::vars::SystemVars;
 
 /// A catalog keeps track of SQL objects and session state available to the
@@ -405,6 +405,10 @@ pub trait CatalogSchema {
     /// item ID.
     fn item_ids(&self) -> &BTreeMap<String, GlobalId>;
 
+    /// Returns the functions of the schema as a map from function name to
+    /// function ID.
+    fn function_ids(&self) -> &BTreeMap<String, GlobalId>;
+
     /// Returns the ID of the owning role.
     fn owner_id(&self) -> RoleId;
 
@@ -568,7 +572,7 @@ pub trait CatalogItem {
     ///
     /// If the catalog item is not of a type that produces functions (i.e.,
     /// anything other than a function), it returns an error.
-    fn func(&self) -> Result<&'static Func, CatalogError>;
+    fn func(&self) -> Result<&Func, CatalogError>;
 
     /// Returns the resolved source connection.
     ///
@@ -612,6 +616,14 @@ pub trait CatalogItem {
     /// catalog item is a table.
     fn table_details(&self) -> Option<&[Expr<Aug>]>;
 
+    /// Returns the identity columns of the catalog item, if the catalog item
+    /// is a table.
+    fn table_identity_columns(&self) -> Option<&[usize]>;
+
+    /// Returns the options and the owning table and column of the catalog
+    /// item, if the catalog item is a sequence.
+    fn sequence_details(&self) -> Option<(&SequenceOptions, Option<(GlobalId, usize)>)>;
+
     /// Returns the type information associated with the catalog item, if the
     /// catalog item is a type.
     fn type_details(&self) -> Option<&CatalogTypeDetails<IdReference>>;
@@ -649,6 +661,8 @@ pub enum CatalogItemType {
     Secret,
     /// A connection.
     Connection,
+    /// A sequence.
+    Sequence,
 }
 
 impl fmt::Display for CatalogItemType {
@@ -664,6 +678,7 @@ impl fmt::Display for CatalogItemType {
             CatalogItemType::Func => f.write_str("func"),
             CatalogItemType::Secret => f.write_str("secret"),
             CatalogItemType::Connection => f.write_str("connection"),
+            CatalogItemType::Sequence => f.write_str("sequence"),
         }
     }
 }
@@ -681,6 +696,7 @@ impl From<CatalogItemType> for ObjectType {
             CatalogItemType::Func => ObjectType::Func,
             CatalogItemType::Secret => ObjectType::Secret,
             CatalogItemType::Connection => ObjectType::Connection,
+            CatalogItemType::Sequence => ObjectType::Sequence,
         }
     }
 }
@@ -698,6 +714,7 @@ impl RustType<proto::CatalogItemType> for CatalogItemType {
             CatalogItemType::Func => proto::CatalogItemType::Func,
             CatalogItemType::Secret => proto::CatalogItemType::Secret,
             CatalogItemType::Connection => proto::CatalogItemType::Connection,
+            CatalogItemType::Sequence => proto::CatalogItemType::Sequence,
         }
     }
 
@@ -713,6 +730,7 @@ impl RustType<proto::CatalogItemType> for CatalogItemType {
             proto::CatalogItemType::Func => CatalogItemType::Func,
             proto::CatalogItemType::Secret => CatalogItemType::Secret,
             proto::CatalogItemType::Connection => CatalogItemType::Connection,
+            proto::CatalogItemType::Sequence => CatalogItemType::Sequence,
             proto::CatalogItemType::Unknown => {
                 return Err(TryFromProtoError::unknown_enum_variant("CatalogItemType"))
             }
@@ -781,6 +799,7 @@ pub enum CatalogType<T: TypeReference> {
     MzTimestamp,
     Interval,
     Jsonb,
+    JsonPath,
     List {
         element_reference: T::Reference,
     },
@@ -806,7 +825,12 @@ pub enum CatalogType<T: TypeReference> {
     Time,
     Timestamp,
     TimestampTz,
+    TsVector,
+    TsQuery,
     Uuid,
+    Inet,
+    Cidr,
+    MacAddr,
     VarChar,
     Int2Vector,
     MzAclItem,
@@ -1231,6 +1255,7 @@ pub enum ObjectType {
     Database,
     Schema,
     Func,
+    Sequence,
 }
 
 impl ObjectType {
@@ -1251,7 +1276,8 @@ impl ObjectType {
             | ObjectType::Schema
             | ObjectType::Cluster
             | ObjectType::ClusterReplica
-            | ObjectType::Role => false,
+            | ObjectType::Role
+            | ObjectType::Sequence => false,
         }
     }
 }
@@ -1275,6 +1301,7 @@ impl From<mz_sql_parser::ast::ObjectType> for ObjectType {
             mz_sql_parser::ast::ObjectType::Database => ObjectType::Database,
             mz_sql_parser::ast::ObjectType::Schema => ObjectType::Schema,
             mz_sql_parser::ast::ObjectType::Func => ObjectType::Func,
+            mz_sql_parser::ast::ObjectType::Sequence => ObjectType::Sequence,
         }
     }
 }
@@ -1292,6 +1319,7 @@ impl From<CommentObjectId> for ObjectType {
             CommentObjectId::Connection(_) => ObjectType::Connection,
             CommentObjectId::Type(_) => ObjectType::Type,
             CommentObjectId::Secret(_) => ObjectType::Secret,
+            CommentObjectId::Sequence(_) => ObjectType::Sequence,
             CommentObjectId::Role(_) => ObjectType::Role,
             CommentObjectId::Database(_) => ObjectType::Database,
             CommentObjectId::Schema(_) => ObjectType::Schema,
@@ -1319,6 +1347,7 @@ impl Display for ObjectType {
             ObjectType::Database => "DATABASE",
             ObjectType::Schema => "SCHEMA",
             ObjectType::Func => "FUNCTION",
+            ObjectType::Sequence => "SEQUENCE",
         })
     }
 }
@@ -1341,6 +1370,7 @@ impl RustType<proto::ObjectType> for ObjectType {
             ObjectType::Database => proto::ObjectType::Database,
             ObjectType::Schema => proto::ObjectType::Schema,
             ObjectType::Func => proto::ObjectType::Func,
+            ObjectType::Sequence => proto::ObjectType::Sequence,
         }
     }
 
@@ -1361,6 +1391,7 @@ impl RustType<proto::ObjectType> for ObjectType {
             proto::ObjectType::Database => Ok(ObjectType::Database),
             proto::ObjectType::Schema => Ok(ObjectType::Schema),
             proto::ObjectType::Func => Ok(ObjectType::Func),
+            proto::ObjectType::Sequence => Ok(ObjectType::Sequence),
             proto::ObjectType::Unknown => Err(TryFromProtoError::unknown_enum_variant(
                 "ObjectType::Unknown",
             )),
@@ -19,13 +19,19 @@ use mz_expr::func;
 use mz_ore::collections::CollectionExt;
 use mz_ore::str::StrExt;
 use mz_pgrepr::oid;
+use mz_repr::adt::text_search::DEFAULT_RANK_WEIGHTS;
 use mz_repr::role_id::RoleId;
-use mz_repr::{ColumnName, ColumnType, Datum, RelationType, Row, ScalarBaseType, ScalarType};
+use mz_repr::{
+    ColumnName, ColumnType, Datum, DatumMap, RelationType, Row, ScalarBaseType, ScalarType,
+};
 use once_cell::sync::Lazy;
 
-use crate::ast::{SelectStatement, Statement};
+use crate::ast::{
+    CteBlock, Expr, FunctionBehavior, Query, Raw, Select, SelectItem, SelectStatement, SetExpr,
+    Statement,
+};
 use crate::catalog::{CatalogType, TypeCategory, TypeReference};
-use crate::names::{self, ResolvedItemName};
+use crate::names::{self, Aug, ResolvedItemName};
 use crate::plan::error::PlanError;
 use crate::plan::expr::{
     AggregateFunc, BinaryFunc, CoercibleScalarExpr, ColumnOrder, HirRelationExpr, HirScalarExpr,
@@ -34,6 +40,7 @@ use crate::plan::expr::{
 use crate::plan::query::{self, ExprContext, QueryContext};
 use crate::plan::scope::Scope;
 use crate::plan::side_effecting_func::PG_CATALOG_SEF_BUILTINS;
+use crate::plan::statement::StatementContext;
 use crate::plan::transform_ast;
 use crate::plan::typeconv::{self, CastContext};
 use crate::session::vars;
@@ -76,8 +83,13 @@ impl TypeCategory {
             ScalarType::AclItem
             | ScalarType::Bytes
             | ScalarType::Jsonb
+            | ScalarType::JsonPath
+            | ScalarType::TsVector
+            | ScalarType::TsQuery
             | ScalarType::Uuid
+            | ScalarType::MacAddr
             | ScalarType::MzAclItem => Self::UserDefined,
+            ScalarType::Inet | ScalarType::Cidr => Self::NetworkAddress,
             ScalarType::Date
             | ScalarType::Time
             | ScalarType::Timestamp
@@ -149,8 +161,13 @@ impl TypeCategory {
             CatalogType::AclItem
             | CatalogType::Bytes
             | CatalogType::Jsonb
+            | CatalogType::JsonPath
+            | CatalogType::TsVector
+            | CatalogType::TsQuery
             | CatalogType::Uuid
+            | CatalogType::MacAddr
             | CatalogType::MzAclItem => Self::UserDefined,
+            CatalogType::Inet | CatalogType::Cidr => Self::NetworkAddress,
             CatalogType::Date
             | CatalogType::Time
             | CatalogType::Timestamp
@@ -197,13 +214,13 @@ impl TypeCategory {
             | Self::Enum
             | Self::Geometric
             | Self::List
-            | Self::NetworkAddress
             | Self::Pseudo
             | Self::Range
             | Self::Unknown
             | Self::UserDefined => None,
             Self::Boolean => Some(ScalarType::Bool),
             Self::DateTime => Some(ScalarType::TimestampTz),
+            Self::NetworkAddress => Some(ScalarType::Inet),
             Self::Numeric => Some(ScalarType::Float64),
             Self::String => Some(ScalarType::String),
             Self::Timespan => Some(ScalarType::Interval),
@@ -358,6 +375,8 @@ pub fn sql_impl(
                 .map(|(i, ty)| (i + 1, ty))
                 .collect(),
         );
+        // The body cannot see the parameters of a calling user-defined function.
+        scx.param_names = BTreeMap::new();
         let qcx = QueryContext::root(&scx, qcx.lifetime);
 
         let (mut expr, _) = names::resolve(qcx.scx.catalog, expr.clone())?;
@@ -438,6 +457,8 @@ fn sql_impl_table_func_inner(
                 .map(|(i, ty)| (i + 1, ty))
                 .collect(),
         );
+        // The body cannot see the parameters of a calling user-defined function.
+        scx.param_names = BTreeMap::new();
         let mut qcx = QueryContext::root(&scx, qcx.lifetime);
 
         let query = query.clone();
@@ -472,6 +493,281 @@ fn experimental_sql_impl_table_func(
     sql_impl_table_func_inner(sql, Some(feature))
 }
 
+/// The declared result of a user-defined function.
+#[derive(Clone, Debug)]
+pub enum UserFuncReturns {
+    /// The function returns a single value of the specified type.
+    Scalar(ScalarType),
+    /// The function returns a set of rows with the specified columns.
+    Table(Vec<(ColumnName, ScalarType)>),
+}
+
+/// Constructs the definition of a user-defined function from its SQL body.
+///
+/// As with the SQL-defined built-in functions (see [`sql_impl`]), calls to the
+/// function are inlined: the body is planned at each call site with its
+/// parameters bound to the function's parameter types, and the call's
+/// arguments are then spliced in, so that the optimizer sees through the call.
+///
+/// Parameters may be referred to positionally (`$1`) or, if named, by name.
+/// As in PostgreSQL, columns take precedence over parameters of the same name.
+///
+/// The declared `behavior` governs where the function may be inlined. The body
+/// of an `IMMUTABLE` function may not call functions whose result depends on
+/// the time or session in which they are evaluated. A `VOLATILE` function may
+/// return different results each time it is called, so it is only inlined
+/// into one-shot queries, and not into views, indexes, materialized views or
+/// subscriptions, whose results must not depend on when they are computed.
+///
+/// The body is planned once up front to validate it against the declared
+/// parameter and return types.
+pub fn sql_user_func(
+    qcx: &QueryContext,
+    name: &str,
+    params: &[(Option<String>, ScalarType)],
+    returns: UserFuncReturns,
+    behavior: FunctionBehavior,
+    body: Query<Raw>,
+) -> Result<Func, PlanError> {
+    let params = params.to_vec();
+    let param_list = ParamList::Exact(
+        params
+            .iter()
+            .map(|(_, ty)| ParamType::Plain(ty.clone()))
+            .collect(),
+    );
+
+    // Rejects calls of unmaterializable functions in the body of an
+    // `IMMUTABLE` function.
+    let check_immutable = |expr: &HirScalarExpr, depth: usize| {
+        #[allow(deprecated)]
+        expr.visit_recursively(depth, &mut |_, expr| match expr {
+            HirScalarExpr::CallUnmaterializable(f) => {
+                Err(sql_err!("function declared IMMUTABLE cannot call {}", f))
+            }
+            _ => Ok(()),
+        })
+    };
+
+    // Rejects inlining a `VOLATILE` function into anything but a one-shot
+    // query.
+    let name = name.to_string();
+    let check_volatile = move |ecx: &ExprContext| {
+        if behavior == FunctionBehavior::Volatile && !ecx.qcx.lifetime.is_one_shot() {
+            sql_bail!(
+                "cannot call VOLATILE function {} in a view, index, materialized view or \
+                subscription; declare the function STABLE or IMMUTABLE instead",
+                name.quoted()
+            );
+        }
+        Ok(())
+    };
+
+    // User-defined functions are identified by the OID of their catalog item;
+    // their sole implementation does not have one of its own.
+    let oid = 0;
+    Ok(match returns {
+        UserFuncReturns::Scalar(return_type) => {
+            let expr = plan_user_func_scalar(qcx, &params, &return_type, &body)?;
+            if behavior == FunctionBehavior::Immutable {
+                check_immutable(&expr, 0)?;
+            }
+            Func::Scalar(vec![FuncImpl {
+                oid,
+                params: param_list,
+                return_type: ReturnType::scalar(ParamType::Plain(return_type.clone())),
+                op: Operation::variadic(move |ecx, args| {
+                    check_volatile(ecx)?;
+                    let mut expr = plan_user_func_scalar(ecx.qcx, &params, &return_type, &body)?;
+                    expr.splice_parameters(&args, 0);
+                    Ok(expr)
+                }),
+            }])
+        }
+        UserFuncReturns::Table(columns) => {
+            let expr = plan_user_func_table(qcx, &params, &columns, &body)?;
+            if behavior == FunctionBehavior::Immutable {
+                #[allow(deprecated)]
+                expr.visit_scalar_expressions(0, &mut |expr, depth| check_immutable(expr, depth))?;
+            }
+            let return_type = match columns.as_slice() {
+                [(_, ty)] => ParamType::Plain(ty.clone()),
+                _ => ParamType::RecordAny,
+            };
+            Func::Table(vec![FuncImpl {
+                oid,
+                params: param_list,
+                return_type: ReturnType::set_of(return_type),
+                op: Operation::variadic(move |ecx, args| {
+                    check_volatile(ecx)?;
+                    let mut expr = plan_user_func_table(ecx.qcx, &params, &columns, &body)?;
+                    expr.splice_parameters(&args, 0);
+                    Ok(TableFuncPlan {
+                        expr,
+                        column_names: columns.iter().map(|(name, _)| name.clone()).collect(),
+                    })
+                }),
+            }])
+        }
+    })
+}
+
+/// Reconstructs a statement context in which the parameters are bound to the
+/// parameters of a user-defined function.
+fn user_func_context<'a>(
+    qcx: &QueryContext<'a>,
+    params: &[(Option<String>, ScalarType)],
+) -> StatementContext<'a> {
+    let mut scx = qcx.scx.clone();
+    scx.param_types = RefCell::new(
+        params
+            .iter()
+            .enumerate()
+            .map(|(i, (_, ty))| (i + 1, ty.clone()))
+            .collect(),
+    );
+    scx.param_names = params
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (name, _))| Some((name.clone()?, i + 1)))
+        .collect();
+    scx
+}
+
+/// Plans the body of a user-defined function that returns a single value.
+///
+/// A body of the form `SELECT <expr>` is planned as that expression; any other
+/// body is planned as a scalar subquery.
+fn plan_user_func_scalar(
+    qcx: &QueryContext,
+    params: &[(Option<String>, ScalarType)],
+    return_type: &ScalarType,
+    body: &Query<Raw>,
+) -> Result<HirScalarExpr, PlanError> {
+    let scx = user_func_context(qcx, params);
+    let mut qcx = QueryContext::root(&scx, qcx.lifetime);
+    let (mut body, _) = names::resolve(qcx.scx.catalog, body.clone())?;
+    transform_ast::transform(&scx, &mut body)?;
+
+    let expr = match simple_projection(&body) {
+        Some(expr) => {
+            let ecx = user_func_ecx(&qcx, &Scope::empty(), &RelationType::empty());
+            query::plan_expr(&ecx, expr)?.type_as_any(&ecx)?
+        }
+        None => {
+            let (expr, _) = query::plan_nested_query(&mut qcx, &body)?;
+            let arity = expr.arity();
+            if arity != 1 {
+                sql_bail!("function body must return exactly one column, but returns {arity}");
+            }
+            HirScalarExpr::Select(Box::new(expr))
+        }
+    };
+    let ecx = user_func_ecx(&qcx, &Scope::empty(), &RelationType::empty());
+    plan_user_func_cast(&ecx, expr, return_type)
+}
+
+/// Plans the body of a user-defined function that returns a set of rows.
+fn plan_user_func_table(
+    qcx: &QueryContext,
+    params: &[(Option<String>, ScalarType)],
+    columns: &[(ColumnName, ScalarType)],
+    body: &Query<Raw>,
+) -> Result<HirRelationExpr, PlanError> {
+    let scx = user_func_context(qcx, params);
+    let mut qcx = QueryContext::root(&scx, qcx.lifetime);
+    let (mut body, _) = names::resolve(qcx.scx.catalog, body.clone())?;
+    transform_ast::transform(&scx, &mut body)?;
+
+    let (expr, scope) = query::plan_nested_query(&mut qcx, &body)?;
+    let typ = expr.typ(&[], &scx.param_types.borrow());
+    let arity = typ.arity();
+    if arity != columns.len() {
+        sql_bail!(
+            "function body returns {} columns, but function is declared to return {}",
+            arity,
+            columns.len()
+        );
+    }
+    let ecx = user_func_ecx(&qcx, &scope, &typ);
+    let mut casts = vec![];
+    for (i, (_, ty)) in columns.iter().enumerate() {
+        casts.push(plan_user_func_cast(&ecx, HirScalarExpr::column(i), ty)?);
+    }
+    Ok(expr
+        .map(casts)
+        .project((arity..arity + columns.len()).collect()))
+}
+
+/// Constructs the context in which the body of a user-defined function is
+/// planned.
+fn user_func_ecx<'a>(
+    qcx: &'a QueryContext<'a>,
+    scope: &'a Scope,
+    relation_type: &'a RelationType,
+) -> ExprContext<'a> {
+    ExprContext {
+        qcx,
+        name: "function body",
+        scope,
+        relation_type,
+        allow_aggregates: false,
+        allow_subqueries: true,
+        allow_parameters: true,
+        allow_windows: false,
+    }
+}
+
+/// Casts a value produced by the body of a user-defined function to the type
+/// that the function is declared to return.
+fn plan_user_func_cast(
+    ecx: &ExprContext,
+    expr: HirScalarExpr,
+    ty: &ScalarType,
+) -> Result<HirScalarExpr, PlanError> {
+    typeconv::plan_cast(ecx, CastContext::Assignment, expr, ty).map_err(|_| {
+        sql_err!(
+            "return type mismatch in function declared to return {}",
+            ecx.humanize_scalar_type(ty)
+        )
+    })
+}
+
+/// Returns the expression of a query of the form `SELECT <expr>`, if `query`
+/// is of that form.
+fn simple_projection(query: &Query<Aug>) -> Option<&Expr<Aug>> {
+    let Query {
+        ctes: CteBlock::Simple(ctes),
+        body: SetExpr::Select(select),
+        order_by,
+        limit: None,
+        offset: None,
+    } = query
+    else {
+        return None;
+    };
+    if !ctes.is_empty() || !order_by.is_empty() {
+        return None;
+    }
+    match &**select {
+        Select {
+            distinct: None,
+            projection,
+            from,
+            selection: None,
+            group_by,
+            having: None,
+            options,
+        } if from.is_empty() && group_by.is_empty() && options.is_empty() => {
+            match projection.as_slice() {
+                [SelectItem::Expr { expr, .. }] => Some(expr),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
 /// Describes a single function's implementation.
 pub struct FuncImpl<R> {
     pub oid: u32,
@@ -875,7 +1171,13 @@ impl From<ScalarBaseType> for ParamType {
             PgLegacyChar => ScalarType::PgLegacyChar,
             PgLegacyName => ScalarType::PgLegacyName,
             Jsonb => ScalarType::Jsonb,
+            JsonPath => ScalarType::JsonPath,
+            TsVector => ScalarType::TsVector,
+            TsQuery => ScalarType::TsQuery,
             Uuid => ScalarType::Uuid,
+            Inet => ScalarType::Inet,
+            Cidr => ScalarType::Cidr,
+            MacAddr => ScalarType::MacAddr,
             Oid => ScalarType::Oid,
             RegClass => ScalarType::RegClass,
             RegProc => ScalarType::RegProc,
@@ -2066,6 +2368,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
             params!(String, String) => sql_impl_func("has_table_privilege(current_user, $1, $2)") => Bool, 1926;
             params!(Oid, String) => sql_impl_func("has_table_privilege(current_user, $1, $2)") => Bool, 1927;
         },
+        "host" => Scalar {
+            params!(Inet) => UnaryFunc::InetHost(func::InetHost) => String, 699;
+        },
         "hmac" => Scalar {
             params!(String, String, String) => VariadicFunc::HmacString => Bytes, 44156;
             params!(Bytes, Bytes, String) => VariadicFunc::HmacBytes => Bytes, 44157;
@@ -2129,9 +2434,55 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
                 })
             }) => Jsonb, 3273;
         },
+        "jsonb_insert" => Scalar {
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String)), Jsonb) => Operation::variadic(|_ecx, mut exprs| {
+                exprs.push(HirScalarExpr::literal(Datum::False, ScalarType::Bool));
+                Ok(HirScalarExpr::CallVariadic { func: VariadicFunc::JsonbInsert, exprs })
+            }) => Jsonb, oid::FUNC_JSONB_INSERT_JSONB_TEXT_ARRAY_JSONB_OID;
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String)), Jsonb, Bool) => VariadicFunc::JsonbInsert => Jsonb, oid::FUNC_JSONB_INSERT_OID;
+        },
+        "jsonb_path_exists" => Scalar {
+            params!(Jsonb, JsonPath) => jsonb_path_func(VariadicFunc::JsonbPathExists) => Bool, oid::FUNC_JSONB_PATH_EXISTS_JSONB_JSONPATH_OID;
+            params!(Jsonb, JsonPath, Jsonb) => jsonb_path_func(VariadicFunc::JsonbPathExists) => Bool, oid::FUNC_JSONB_PATH_EXISTS_JSONB_JSONPATH_JSONB_OID;
+            params!(Jsonb, JsonPath, Jsonb, Bool) => VariadicFunc::JsonbPathExists => Bool, oid::FUNC_JSONB_PATH_EXISTS_OID;
+        },
+        "jsonb_path_match" => Scalar {
+            params!(Jsonb, JsonPath) => jsonb_path_func(VariadicFunc::JsonbPathMatch) => Bool, oid::FUNC_JSONB_PATH_MATCH_JSONB_JSONPATH_OID;
+            params!(Jsonb, JsonPath, Jsonb) => jsonb_path_func(VariadicFunc::JsonbPathMatch) => Bool, oid::FUNC_JSONB_PATH_MATCH_JSONB_JSONPATH_JSONB_OID;
+            params!(Jsonb, JsonPath, Jsonb, Bool) => VariadicFunc::JsonbPathMatch => Bool, oid::FUNC_JSONB_PATH_MATCH_OID;
+        },
+        "jsonb_path_query_array" => Scalar {
+            params!(Jsonb, JsonPath) => jsonb_path_func(VariadicFunc::JsonbPathQueryArray) => Jsonb, oid::FUNC_JSONB_PATH_QUERY_ARRAY_JSONB_JSONPATH_OID;
+            params!(Jsonb, JsonPath, Jsonb) => jsonb_path_func(VariadicFunc::JsonbPathQueryArray) => Jsonb, oid::FUNC_JSONB_PATH_QUERY_ARRAY_JSONB_JSONPATH_JSONB_OID;
+            params!(Jsonb, JsonPath, Jsonb, Bool) => VariadicFunc::JsonbPathQueryArray => Jsonb, oid::FUNC_JSONB_PATH_QUERY_ARRAY_OID;
+        },
+        "jsonb_path_query_first" => Scalar {
+            params!(Jsonb, JsonPath) => jsonb_path_func(VariadicFunc::JsonbPathQueryFirst) => Jsonb, oid::FUNC_JSONB_PATH_QUERY_FIRST_JSONB_JSONPATH_OID;
+            params!(Jsonb, JsonPath, Jsonb) => jsonb_path_func(VariadicFunc::JsonbPathQueryFirst) => Jsonb, oid::FUNC_JSONB_PATH_QUERY_FIRST_JSONB_JSONPATH_JSONB_OID;
+            params!(Jsonb, JsonPath, Jsonb, Bool) => VariadicFunc::JsonbPathQueryFirst => Jsonb, oid::FUNC_JSONB_PATH_QUERY_FIRST_OID;
+        },
         "jsonb_pretty" => Scalar {
             params!(Jsonb) => UnaryFunc::JsonbPretty(func::JsonbPretty) => String, 3306;
         },
+        "jsonb_set" => Scalar {
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String)), Jsonb) => Operation::variadic(|_ecx, mut exprs| {
+                exprs.push(HirScalarExpr::literal(Datum::True, ScalarType::Bool));
+                Ok(HirScalarExpr::CallVariadic { func: VariadicFunc::JsonbSet, exprs })
+            }) => Jsonb, oid::FUNC_JSONB_SET_JSONB_TEXT_ARRAY_JSONB_OID;
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String)), Jsonb, Bool) => VariadicFunc::JsonbSet => Jsonb, oid::FUNC_JSONB_SET_OID;
+        },
+        "jsonb_set_lax" => Scalar {
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String)), Jsonb) => Operation::variadic(|_ecx, mut exprs| {
+                exprs.push(HirScalarExpr::literal(Datum::True, ScalarType::Bool));
+                exprs.push(HirScalarExpr::literal(Datum::String("use_json_null"), ScalarType::String));
+                Ok(HirScalarExpr::CallVariadic { func: VariadicFunc::JsonbSetLax, exprs })
+            }) => Jsonb, oid::FUNC_JSONB_SET_LAX_JSONB_TEXT_ARRAY_JSONB_OID;
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String)), Jsonb, Bool) => Operation::variadic(|_ecx, mut exprs| {
+                exprs.push(HirScalarExpr::literal(Datum::String("use_json_null"), ScalarType::String));
+                Ok(HirScalarExpr::CallVariadic { func: VariadicFunc::JsonbSetLax, exprs })
+            }) => Jsonb, oid::FUNC_JSONB_SET_LAX_JSONB_TEXT_ARRAY_JSONB_BOOL_OID;
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String)), Jsonb, Bool, String) => VariadicFunc::JsonbSetLax => Jsonb, oid::FUNC_JSONB_SET_LAX_OID;
+        },
         "jsonb_strip_nulls" => Scalar {
             params!(Jsonb) => UnaryFunc::JsonbStripNulls(func::JsonbStripNulls) => Jsonb, 3262;
         },
@@ -2208,6 +2559,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
                 Ok(input.call_binary(algorithm, BinaryFunc::DigestBytes).call_binary(encoding, BinaryFunc::Encode))
             }) => String, 2321;
         },
+        "masklen" => Scalar {
+            params!(Inet) => UnaryFunc::InetMasklen(func::InetMasklen) => Int32, 697;
+        },
         "mod" => Scalar {
             params!(Numeric, Numeric) => Operation::nullary(|_ecx| catalog_name_only!("mod")) => Numeric, 1728;
             params!(Int16, Int16) => Operation::nullary(|_ecx| catalog_name_only!("mod")) => Int16, 940;
@@ -2217,6 +2571,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
             params!(UInt32, UInt32) => Operation::nullary(|_ecx| catalog_name_only!("mod")) => UInt32, oid::FUNC_MOD_UINT32_OID;
             params!(UInt64, UInt64) => Operation::nullary(|_ecx| catalog_name_only!("mod")) => UInt64, oid::FUNC_MOD_UINT64_OID;
         },
+        "network" => Scalar {
+            params!(Inet) => UnaryFunc::CastInetToCidr(func::CastInetToCidr) => Cidr, 683;
+        },
         "now" => Scalar {
             params!() => UnmaterializableFunc::CurrentTimestamp => TimestampTz, 1299;
         },
@@ -2467,6 +2824,10 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
                 Ok(HirScalarExpr::literal(Datum::String(&name), ScalarType::String))
             }) => String, 1619;
         },
+        "plainto_tsquery" => Scalar {
+            params!(String) => text_search_default_config(BinaryFunc::PlainToTsQuery) => TsQuery, 3748;
+            params!(String, String) => BinaryFunc::PlainToTsQuery => TsQuery, oid::FUNC_PLAINTO_TSQUERY_TEXT_TEXT_OID;
+        },
         "position" => Scalar {
             params!(String, String) => BinaryFunc::Position => Int32, 849;
         },
@@ -2506,6 +2867,13 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
             params!(String) => UnaryFunc::TrimTrailingWhitespace(func::TrimTrailingWhitespace) => String, 882;
             params!(String, String) => BinaryFunc::TrimTrailing => String, 876;
         },
+        "set_masklen" => Scalar {
+            params!(Inet, Int32) => BinaryFunc::InetSetMasklen => Inet, 605;
+            params!(Cidr, Int32) => BinaryFunc::CidrSetMasklen => Cidr, 635;
+        },
+        "setweight" => Scalar {
+            params!(TsVector, PgLegacyChar) => BinaryFunc::SetWeight => TsVector, 3624;
+        },
         "sha224" => Scalar {
             params!(Bytes) => digest("sha224") => Bytes, 3419;
         },
@@ -2637,6 +3005,14 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
         "to_timestamp" => Scalar {
             params!(Float64) => UnaryFunc::ToTimestamp(func::ToTimestamp) => TimestampTz, 1158;
         },
+        "to_tsquery" => Scalar {
+            params!(String) => text_search_default_config(BinaryFunc::ToTsQuery) => TsQuery, 3749;
+            params!(String, String) => BinaryFunc::ToTsQuery => TsQuery, oid::FUNC_TO_TSQUERY_TEXT_TEXT_OID;
+        },
+        "to_tsvector" => Scalar {
+            params!(String) => text_search_default_config(BinaryFunc::ToTsVector) => TsVector, 3745;
+            params!(String, String) => BinaryFunc::ToTsVector => TsVector, oid::FUNC_TO_TSVECTOR_TEXT_TEXT_OID;
+        },
         "translate" => Scalar {
             params!(String, String, String) => VariadicFunc::Translate => String, 878;
         },
@@ -2675,6 +3051,12 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
                 })
             }) => ScalarType::Range { element_type: Box::new(ScalarType::TimestampTz)}, 3938;
         },
+        "ts_rank" => Scalar {
+            params!(ScalarType::Array(Box::new(ScalarType::Float32)), TsVector, TsQuery, Int32) => VariadicFunc::TsRank => Float32, 3703;
+            params!(ScalarType::Array(Box::new(ScalarType::Float32)), TsVector, TsQuery) => ts_rank_func(true, false) => Float32, 3704;
+            params!(TsVector, TsQuery, Int32) => ts_rank_func(false, true) => Float32, 3705;
+            params!(TsVector, TsQuery) => ts_rank_func(false, false) => Float32, 3706;
+        },
         "upper" => Scalar {
             params!(String) => UnaryFunc::Upper(func::Upper) => String, 871;
             params!(RangeAny) => UnaryFunc::RangeUpper(func::RangeUpper) => AnyElement, 3849;
@@ -3170,6 +3552,42 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
                 })
             }) => ReturnType::set_of(String.into()), 3931;
         },
+        "jsonb_path_query" => Table {
+            params!(Jsonb, JsonPath) => jsonb_path_query_table_func() => ReturnType::set_of(Jsonb.into()), oid::FUNC_JSONB_PATH_QUERY_JSONB_JSONPATH_OID;
+            params!(Jsonb, JsonPath, Jsonb) => jsonb_path_query_table_func() => ReturnType::set_of(Jsonb.into()), oid::FUNC_JSONB_PATH_QUERY_JSONB_JSONPATH_JSONB_OID;
+            params!(Jsonb, JsonPath, Jsonb, Bool) => jsonb_path_query_table_func() => ReturnType::set_of(Jsonb.into()), oid::FUNC_JSONB_PATH_QUERY_OID;
+        },
+        "jsonb_populate_record" => Table {
+            params!(RecordAny, Jsonb) => Operation::binary(jsonb_populate_record) => ReturnType::set_of(RecordAny), oid::FUNC_JSONB_POPULATE_RECORD_OID;
+        },
+        // The columns of `jsonb_to_record` and `jsonb_to_recordset` are
+        // extracted from the JSON objects they produce by the column
+        // definition list; see `RECORD_TABLE_FUNCTIONS`.
+        "jsonb_to_record" => Table {
+            params!(Jsonb) => Operation::unary(move |_ecx, jsonb| {
+                Ok(TableFuncPlan {
+                    expr: HirRelationExpr::CallTable {
+                        func: TableFunc::TabletizedScalar {
+                            name: "jsonb_to_record".into(),
+                            relation: RelationType::new(vec![ScalarType::Jsonb.nullable(true)]),
+                        },
+                        exprs: vec![jsonb],
+                    },
+                    column_names: vec!["jsonb_to_record".into()],
+                })
+            }) => ReturnType::set_of(RecordAny), oid::FUNC_JSONB_TO_RECORD_OID;
+        },
+        "jsonb_to_recordset" => Table {
+            params!(Jsonb) => Operation::unary(move |_ecx, jsonb| {
+                Ok(TableFuncPlan {
+                    expr: HirRelationExpr::CallTable {
+                        func: TableFunc::JsonbArrayElements { stringify: false },
+                        exprs: vec![jsonb],
+                    },
+                    column_names: vec!["jsonb_to_recordset".into()],
+                })
+            }) => ReturnType::set_of(RecordAny), oid::FUNC_JSONB_TO_RECORDSET_OID;
+        },
         // Note that these implementations' input to `generate_series` is
         // contrived to match Flink's expected values. There are other,
         // equally valid windows we could generate.
@@ -3913,6 +4331,176 @@ fn digest(algorithm: &'static str) -> Operation<HirScalarExpr> {
     })
 }
 
+/// Fills in the optional `vars` and `silent` arguments of the `jsonb_path_*`
+/// functions. `vars` defaults to an empty object.
+fn jsonb_path_args(mut exprs: Vec<HirScalarExpr>, silent: bool) -> Vec<HirScalarExpr> {
+    if exprs.len() < 3 {
+        exprs.push(HirScalarExpr::literal(
+            Datum::Map(DatumMap::empty()),
+            ScalarType::Jsonb,
+        ));
+    }
+    if exprs.len() < 4 {
+        exprs.push(HirScalarExpr::literal(
+            Datum::from(silent),
+            ScalarType::Bool,
+        ));
+    }
+    exprs
+}
+
+/// Plans the form of a text search function that omits the text search
+/// configuration, using `english` in its place.
+fn text_search_default_config(func: BinaryFunc) -> Operation<HirScalarExpr> {
+    Operation::unary(move |_ecx, e| {
+        let config = HirScalarExpr::literal(Datum::String("english"), ScalarType::String);
+        Ok(config.call_binary(e, func.clone()))
+    })
+}
+
+/// Plans a form of `ts_rank` that omits the `weights` or `normalization`
+/// arguments, filling in their defaults.
+fn ts_rank_func(has_weights: bool, has_normalization: bool) -> Operation<HirScalarExpr> {
+    Operation::variadic(move |_ecx, mut exprs| {
+        if !has_weights {
+            let weights = DEFAULT_RANK_WEIGHTS.into_iter().map(Datum::from).collect();
+            exprs.insert(
+                0,
+                HirScalarExpr::literal_1d_array(weights, ScalarType::Float32)?,
+            );
+        }
+        if !has_normalization {
+            exprs.push(HirScalarExpr::literal(Datum::Int32(0), ScalarType::Int32));
+        }
+        Ok(HirScalarExpr::CallVariadic {
+            func: VariadicFunc::TsRank,
+            exprs,
+        })
+    })
+}
+
+fn jsonb_path_func(func: VariadicFunc) -> Operation<HirScalarExpr> {
+    Operation::variadic(move |_ecx, exprs| {
+        Ok(HirScalarExpr::CallVariadic {
+            func: func.clone(),
+            exprs: jsonb_path_args(exprs, false),
+        })
+    })
+}
+
+fn jsonb_path_query_table_func() -> Operation<TableFuncPlan> {
+    Operation::variadic(|_ecx, exprs| {
+        Ok(TableFuncPlan {
+            expr: HirRelationExpr::CallTable {
+                func: TableFunc::JsonbPathQuery,
+                exprs: jsonb_path_args(exprs, false),
+            },
+            column_names: vec!["jsonb_path_query".into()],
+        })
+    })
+}
+
+/// The builtin table functions that return `record` without declaring their
+/// output columns, which must instead be given by a column definition list.
+pub const RECORD_TABLE_FUNCTIONS: &[&str] = &["jsonb_to_record", "jsonb_to_recordset"];
+
+/// Wraps `obj` so that it raises an error, as PostgreSQL does, if it evaluates
+/// to a JSON array or scalar rather than an object. `func_name` names the
+/// function expanding the object in the error message.
+pub fn plan_jsonb_expect_object(obj: HirScalarExpr, func_name: &str) -> HirScalarExpr {
+    let is_type = |ty: &str| {
+        obj.clone()
+            .call_unary(UnaryFunc::JsonbTypeof(func::JsonbTypeof))
+            .call_binary(
+                HirScalarExpr::literal(Datum::String(ty), ScalarType::String),
+                BinaryFunc::Eq,
+            )
+    };
+    let error = |msg: String| HirScalarExpr::CallVariadic {
+        func: VariadicFunc::ErrorIfNull,
+        exprs: vec![
+            HirScalarExpr::literal_null(ScalarType::Jsonb),
+            HirScalarExpr::literal(Datum::String(&msg), ScalarType::String),
+        ],
+    };
+    let is_scalar = HirScalarExpr::variadic_or(vec![
+        is_type("string"),
+        is_type("number"),
+        is_type("boolean"),
+    ]);
+    HirScalarExpr::If {
+        cond: Box::new(is_type("array")),
+        then: Box::new(error(format!("cannot call {} on an array", func_name))),
+        els: Box::new(HirScalarExpr::If {
+            cond: Box::new(is_scalar),
+            then: Box::new(error(format!("cannot call {} on a scalar", func_name))),
+            els: Box::new(obj),
+        }),
+    }
+}
+
+/// Plans the extraction of the value of the key `name` from the JSON object
+/// `obj` as a value of type `ty`. Values of types other than `jsonb` are
+/// converted from their text representation.
+pub fn plan_jsonb_record_field(
+    ecx: &ExprContext,
+    obj: HirScalarExpr,
+    name: &str,
+    ty: &ScalarType,
+) -> Result<HirScalarExpr, PlanError> {
+    let key = HirScalarExpr::literal(Datum::String(name), ScalarType::String);
+    if *ty == ScalarType::Jsonb {
+        return Ok(obj.call_binary(key, BinaryFunc::JsonbGetString { stringify: false }));
+    }
+    let text = obj.call_binary(key, BinaryFunc::JsonbGetString { stringify: true });
+    typeconv::plan_cast(ecx, CastContext::Explicit, text, ty)
+}
+
+/// Expands the JSON object `jsonb` into the fields of the record type of
+/// `base`. Fields that are missing from the object take their value from
+/// `base`.
+fn jsonb_populate_record(
+    ecx: &ExprContext,
+    base: HirScalarExpr,
+    jsonb: HirScalarExpr,
+) -> Result<TableFuncPlan, PlanError> {
+    let fields = match ecx.scalar_type(&base) {
+        ScalarType::Record { fields, .. } => fields,
+        _ => unreachable!("jsonb_populate_record is guaranteed to receive a record"),
+    };
+    let mut exprs = Vec::with_capacity(fields.len());
+    let mut column_types = Vec::with_capacity(fields.len());
+    let mut column_names = Vec::with_capacity(fields.len());
+    let jsonb = plan_jsonb_expect_object(jsonb, "jsonb_populate_record");
+    for (i, (name, ty)) in fields.into_iter().enumerate() {
+        let key = HirScalarExpr::literal(Datum::String(name.as_str()), ScalarType::String);
+        let present = jsonb
+            .clone()
+            .call_binary(key, BinaryFunc::JsonbContainsString);
+        let value = plan_jsonb_record_field(ecx, jsonb.clone(), name.as_str(), &ty.scalar_type)?;
+        let default = base
+            .clone()
+            .call_unary(UnaryFunc::RecordGet(func::RecordGet(i)));
+        exprs.push(HirScalarExpr::If {
+            cond: Box::new(present),
+            then: Box::new(value),
+            els: Box::new(default),
+        });
+        column_types.push(ty.scalar_type.nullable(true));
+        column_names.push(name);
+    }
+    Ok(TableFuncPlan {
+        expr: HirRelationExpr::CallTable {
+            func: TableFunc::TabletizedScalar {
+                name: "jsonb_populate_record".into(),
+                relation: RelationType::new(column_types),
+            },
+            exprs,
+        },
+        column_names,
+    })
+}
+
 fn array_to_string(
     ecx: &ExprContext,
     exprs: Vec<HirScalarExpr>,
@@ -4105,6 +4693,10 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(UInt32, UInt32) => BitShiftLeftUInt32 => UInt32, oid::FUNC_SHIFT_LEFT_UINT32;
             params!(UInt64, UInt32) => BitShiftLeftUInt64 => UInt64, oid::FUNC_SHIFT_LEFT_UINT64;
             params!(RangeAny, RangeAny) => RangeBefore => Bool, 3893;
+            params!(Inet, Inet) => InetContainedBy => Bool, 931;
+        },
+        "<<=" => Scalar {
+            params!(Inet, Inet) => InetContainedByOrEq => Bool, 932;
         },
         ">>" => Scalar {
             params!(Int16, Int32) => BitShiftRightInt16 => Int16, 1879;
@@ -4114,6 +4706,14 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(UInt32, UInt32) => BitShiftRightUInt32 => UInt32, oid::FUNC_SHIFT_RIGHT_UINT32;
             params!(UInt64, UInt32) => BitShiftRightUInt64 => UInt64, oid::FUNC_SHIFT_RIGHT_UINT64;
             params!(RangeAny, RangeAny) => RangeAfter => Bool, 3894;
+            params!(Inet, Inet) => Operation::binary(|_ecx, lhs, rhs| {
+                Ok(rhs.call_binary(lhs, InetContainedBy))
+            }) => Bool, 933;
+        },
+        ">>=" => Scalar {
+            params!(Inet, Inet) => Operation::binary(|_ecx, lhs, rhs| {
+                Ok(rhs.call_binary(lhs, InetContainedByOrEq))
+            }) => Bool, 934;
         },
 
         // ILIKE
@@ -4245,6 +4845,7 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             }) => String, 2780;
             params!(String, String) => TextConcat => String, 654;
             params!(Jsonb, Jsonb) => JsonbConcat => Jsonb, 3284;
+            params!(TsVector, TsVector) => TsVectorConcat => TsVector, 3633;
             params!(ArrayAnyCompatible, ArrayAnyCompatible) => ArrayArrayConcat => ArrayAnyCompatible, 375;
             params!(ListAnyCompatible, ListAnyCompatible) => ListListConcat => ListAnyCompatible, oid::OP_CONCAT_LIST_LIST_OID;
             params!(ListAnyCompatible, ListElementAnyCompatible) => ListElementConcat => ListAnyCompatible, oid::OP_CONCAT_LIST_ELEMENT_OID;
@@ -4316,6 +4917,36 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
                 Ok(rhs.call_binary(lhs, BinaryFunc::RangeContainsRange { rev: true }))
             }) => Bool, 3892;
         },
+        "@?" => Scalar {
+            params!(Jsonb, JsonPath) => Operation::binary(|_ecx, lhs, rhs| {
+                Ok(HirScalarExpr::CallVariadic {
+                    func: VariadicFunc::JsonbPathExists,
+                    exprs: jsonb_path_args(vec![lhs, rhs], true),
+                })
+            }) => Bool, oid::OP_JSONB_PATH_EXISTS_OID;
+        },
+        "@@" => Scalar {
+            params!(Jsonb, JsonPath) => Operation::binary(|_ecx, lhs, rhs| {
+                Ok(HirScalarExpr::CallVariadic {
+                    func: VariadicFunc::JsonbPathMatch,
+                    exprs: jsonb_path_args(vec![lhs, rhs], true),
+                })
+            }) => Bool, oid::OP_JSONB_PATH_MATCH_OID;
+            params!(TsVector, TsQuery) => TsMatch => Bool, 3636;
+            params!(TsQuery, TsVector) => Operation::binary(|_ecx, lhs, rhs| {
+                Ok(rhs.call_binary(lhs, TsMatch))
+            }) => Bool, 3637;
+            params!(String, TsQuery) => Operation::binary(|_ecx, lhs, rhs| {
+                let config = HirScalarExpr::literal(Datum::String("english"), ScalarType::String);
+                Ok(config.call_binary(lhs, BinaryFunc::ToTsVector).call_binary(rhs, TsMatch))
+            }) => Bool, 3660;
+            params!(String, String) => Operation::binary(|_ecx, lhs, rhs| {
+                let config = HirScalarExpr::literal(Datum::String("english"), ScalarType::String);
+                let vector = config.clone().call_binary(lhs, BinaryFunc::ToTsVector);
+                let query = config.call_binary(rhs, BinaryFunc::PlainToTsQuery);
+                Ok(vector.call_binary(query, TsMatch))
+            }) => Bool, 3661;
+        },
         "?" => Scalar {
             params!(Jsonb, String) => JsonbContainsString => Bool, 3247;
             params!(MapAny, String) => MapContainsKey => Bool, oid::OP_CONTAINS_KEY_MAP_OID;
@@ -4328,6 +4959,7 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
         },
         "&&" => Scalar {
             params!(RangeAny, RangeAny) => BinaryFunc::RangeOverlaps => Bool, 3888;
+            params!(Inet, Inet) => BinaryFunc::InetOverlaps => Bool, 3552;
         },
         "&<" => Scalar {
             params!(RangeAny, RangeAny) => BinaryFunc::RangeOverleft => Bool, 3895;
@@ -4357,6 +4989,8 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Lt => Bool, 2062;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Lt => Bool, 1322;
             params!(Uuid, Uuid) => BinaryFunc::Lt => Bool, 2974;
+            params!(Inet, Inet) => BinaryFunc::Lt => Bool, 1203;
+            params!(MacAddr, MacAddr) => BinaryFunc::Lt => Bool, 1222;
             params!(Interval, Interval) => BinaryFunc::Lt => Bool, 1332;
             params!(Bytes, Bytes) => BinaryFunc::Lt => Bool, 1957;
             params!(String, String) => BinaryFunc::Lt => Bool, 664;
@@ -4386,6 +5020,8 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Lte => Bool, 2063;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Lte => Bool, 1323;
             params!(Uuid, Uuid) => BinaryFunc::Lte => Bool, 2976;
+            params!(Inet, Inet) => BinaryFunc::Lte => Bool, 1204;
+            params!(MacAddr, MacAddr) => BinaryFunc::Lte => Bool, 1223;
             params!(Interval, Interval) => BinaryFunc::Lte => Bool, 1333;
             params!(Bytes, Bytes) => BinaryFunc::Lte => Bool, 1958;
             params!(String, String) => BinaryFunc::Lte => Bool, 665;
@@ -4415,6 +5051,8 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Gt => Bool, 2064;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Gt => Bool, 1324;
             params!(Uuid, Uuid) => BinaryFunc::Gt => Bool, 2975;
+            params!(Inet, Inet) => BinaryFunc::Gt => Bool, 1205;
+            params!(MacAddr, MacAddr) => BinaryFunc::Gt => Bool, 1224;
             params!(Interval, Interval) => BinaryFunc::Gt => Bool, 1334;
             params!(Bytes, Bytes) => BinaryFunc::Gt => Bool, 1959;
             params!(String, String) => BinaryFunc::Gt => Bool, 666;
@@ -4444,6 +5082,8 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Gte => Bool, 2065;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Gte => Bool, 1325;
             params!(Uuid, Uuid) => BinaryFunc::Gte => Bool, 2977;
+            params!(Inet, Inet) => BinaryFunc::Gte => Bool, 1206;
+            params!(MacAddr, MacAddr) => BinaryFunc::Gte => Bool, 1225;
             params!(Interval, Interval) => BinaryFunc::Gte => Bool, 1335;
             params!(Bytes, Bytes) => BinaryFunc::Gte => Bool, 1960;
             params!(String, String) => BinaryFunc::Gte => Bool, 667;
@@ -4482,6 +5122,8 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Eq => Bool, 2060;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Eq => Bool, 1320;
             params!(Uuid, Uuid) => BinaryFunc::Eq => Bool, 2972;
+            params!(Inet, Inet) => BinaryFunc::Eq => Bool, 1201;
+            params!(MacAddr, MacAddr) => BinaryFunc::Eq => Bool, 1220;
             params!(Interval, Interval) => BinaryFunc::Eq => Bool, 1330;
             params!(Bytes, Bytes) => BinaryFunc::Eq => Bool, 1955;
             params!(String, String) => BinaryFunc::Eq => Bool, 98;
@@ -4514,6 +5156,8 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::NotEq => Bool, 2061;
             params!(TimestampTz, TimestampTz) => BinaryFunc::NotEq => Bool, 1321;
             params!(Uuid, Uuid) => BinaryFunc::NotEq => Bool, 2973;
+            params!(Inet, Inet) => BinaryFunc::NotEq => Bool, 1202;
+            params!(MacAddr, MacAddr) => BinaryFunc::NotEq => Bool, 1221;
             params!(Interval, Interval) => BinaryFunc::NotEq => Bool, 1331;
             params!(Bytes, Bytes) => BinaryFunc::NotEq => Bool, 1956;
             params!(String, String) => BinaryFunc::NotEq => Bool, 531;
@@ -33,8 +33,8 @@ use crate::ast::fold::{Fold, FoldNode};
 use crate::ast::visit::{Visit, VisitNode};
 use crate::ast::visit_mut::VisitMut;
 use crate::ast::{
-    self, AstInfo, Cte, CteBlock, CteMutRec, Ident, Query, Raw, RawClusterName, RawDataType,
-    RawItemName, Statement, UnresolvedItemName,
+    self, AstInfo, Cte, CteBlock, CteMutRec, CteRecursive, Ident, Query, Raw, RawClusterName,
+    RawDataType, RawItemName, Statement, UnresolvedItemName,
 };
 use crate::catalog::{CatalogError, CatalogItemType, CatalogTypeDetails, SessionCatalog};
 use crate::normalize;
@@ -1172,7 +1172,8 @@ impl From<CommentObjectId> for ObjectId {
             | CommentObjectId::Func(global_id)
             | CommentObjectId::Connection(global_id)
             | CommentObjectId::Type(global_id)
-            | CommentObjectId::Secret(global_id) => ObjectId::Item(global_id),
+            | CommentObjectId::Secret(global_id)
+            | CommentObjectId::Sequence(global_id) => ObjectId::Item(global_id),
             CommentObjectId::Role(id) => ObjectId::Role(id),
             CommentObjectId::Database(id) => ObjectId::Database(id),
             CommentObjectId::Schema(id) => ObjectId::Schema(id),
@@ -1225,6 +1226,7 @@ pub enum CommentObjectId {
     Connection(GlobalId),
     Type(GlobalId),
     Secret(GlobalId),
+    Sequence(GlobalId),
     Role(RoleId),
     Database(DatabaseId),
     Schema((ResolvedDatabaseSpecifier, SchemaSpecifier)),
@@ -1265,6 +1267,9 @@ impl RustType<proto::comment_key::Object> for CommentObjectId {
             CommentObjectId::Secret(global_id) => {
                 proto::comment_key::Object::Secret(global_id.into_proto())
             }
+            CommentObjectId::Sequence(global_id) => {
+                proto::comment_key::Object::Sequence(global_id.into_proto())
+            }
             CommentObjectId::Role(role_id) => {
                 proto::comment_key::Object::Role(role_id.into_proto())
             }
@@ -1322,6 +1327,9 @@ impl RustType<proto::comment_key::Object> for CommentObjectId {
             proto::comment_key::Object::Secret(global_id) => {
                 CommentObjectId::Secret(global_id.into_rust()?)
             }
+            proto::comment_key::Object::Sequence(global_id) => {
+                CommentObjectId::Sequence(global_id.into_rust()?)
+            }
             proto::comment_key::Object::Role(role_id) => {
                 CommentObjectId::Role(role_id.into_rust()?)
             }
@@ -1588,6 +1596,30 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
                 }
                 CteBlock::Simple(result_ctes)
             }
+            CteBlock::Recursive(ctes) => {
+                let mut result_ctes = Vec::<CteRecursive<Aug>>::new();
+
+                let initial_id = self.ctes.len();
+
+                for (offset, cte) in ctes.into_iter().enumerate() {
+                    let cte_name = normalize::ident(cte.alias.name.clone());
+                    let local_id = LocalId::new(u64::cast_from(initial_id + offset));
+
+                    // Unlike in a simple block, the binding is visible within
+                    // its own definition.
+                    let shadowed_id = self.ctes.insert(cte_name.clone(), local_id);
+                    shadowed_cte_ids.push((cte_name, shadowed_id));
+
+                    result_ctes.push(CteRecursive {
+                        alias: cte.alias,
+                        id: local_id,
+                        query: self.fold_query(cte.query),
+                        search: cte.search,
+                        cycle: cte.cycle,
+                    });
+                }
+                CteBlock::Recursive(result_ctes)
+            }
             CteBlock::MutuallyRecursive(MutRecBlock { options, ctes }) => {
                 let mut result_ctes = Vec::<CteMutRec<Aug>>::new();
 
@@ -1869,6 +1901,8 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
                     .collect(),
             ),
             ConnectionKafkaBroker(broker) => ConnectionKafkaBroker(self.fold_kafka_broker(broker)),
+            RetainHistoryFor(value) => RetainHistoryFor(self.fold_value(value)),
+            Refresh(refresh) => Refresh(self.fold_refresh_option_value(refresh)),
         }
     }
 
@@ -1959,6 +1993,7 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
             Function {
                 function,
                 alias,
+                column_defs,
                 with_ordinality,
             } => {
                 match &function.name {
@@ -1974,6 +2009,12 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
                 Function {
                     function: self.fold_function(function),
                     alias: alias.map(|alias| self.fold_table_alias(alias)),
+                    column_defs: column_defs.map(|column_defs| {
+                        column_defs
+                            .into_iter()
+                            .map(|column_def| self.fold_column_def(column_def))
+                            .collect()
+                    }),
                     with_ordinality,
                 }
             }
@@ -21,11 +21,12 @@ use mz_repr::{ColumnName, GlobalId};
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::visit_mut::{self, VisitMut};
 use mz_sql_parser::ast::{
-    CreateConnectionStatement, CreateIndexStatement, CreateMaterializedViewStatement,
-    CreateSecretStatement, CreateSinkStatement, CreateSourceStatement, CreateSubsourceStatement,
-    CreateTableStatement, CreateTypeStatement, CreateViewStatement, CreateWebhookSourceStatement,
-    CteBlock, Function, FunctionArgs, Ident, IfExistsBehavior, MutRecBlock, Op, Query, Statement,
-    TableFactor, UnresolvedItemName, UnresolvedSchemaName, Value, ViewDefinition,
+    CreateConnectionStatement, CreateFunctionStatement, CreateIndexStatement,
+    CreateMaterializedViewStatement, CreateSecretStatement, CreateSequenceStatement,
+    CreateSinkStatement, CreateSourceStatement, CreateSubsourceStatement, CreateTableStatement,
+    CreateTypeStatement, CreateViewStatement, CreateWebhookSourceStatement, CteBlock, Function,
+    FunctionArgs, Ident, IfExistsBehavior, MutRecBlock, Op, Query, Statement, TableFactor,
+    UnresolvedItemName, UnresolvedSchemaName, Value, ViewDefinition,
 };
 
 use crate::names::{Aug, FullItemName, PartialItemName, PartialSchemaName, RawDatabaseSpecifier};
@@ -205,6 +206,11 @@ pub fn create_statement(
                         self.ctes.push(cte.alias.name.clone());
                     }
                 }
+                CteBlock::Recursive(ctes) => {
+                    for cte in ctes.iter() {
+                        self.ctes.push(cte.alias.name.clone());
+                    }
+                }
                 CteBlock::MutuallyRecursive(MutRecBlock { options: _, ctes }) => {
                     for cte in ctes.iter() {
                         self.ctes.push(cte.name.clone());
@@ -300,6 +306,7 @@ pub fn create_statement(
             constraints: _,
             if_not_exists,
             temporary,
+            with_options: _,
         }) => {
             *name = if *temporary {
                 allocate_temporary_name(name)?
@@ -372,6 +379,7 @@ pub fn create_statement(
             columns: _,
             in_cluster: _,
             query,
+            with_options: _,
         }) => {
             *name = allocate_name(name)?;
             {
@@ -420,6 +428,32 @@ pub fn create_statement(
             *name = allocate_name(name)?;
             *if_not_exists = false;
         }
+        Statement::CreateSequence(CreateSequenceStatement {
+            name,
+            if_not_exists,
+            options: _,
+        }) => {
+            *name = allocate_name(name)?;
+            *if_not_exists = false;
+        }
+        Statement::CreateFunction(CreateFunctionStatement {
+            name,
+            parameters,
+            returns,
+            behavior: _,
+            body,
+        }) => {
+            *name = allocate_name(name)?;
+            let mut normalizer = QueryNormalizer::new();
+            for parameter in parameters {
+                normalizer.visit_function_parameter_mut(parameter);
+            }
+            normalizer.visit_function_returns_mut(returns);
+            normalizer.visit_query_mut(body);
+            if let Some(err) = normalizer.err {
+                return Err(err);
+            }
+        }
         Statement::CreateConnection(CreateConnectionStatement {
             name,
             connection: _,
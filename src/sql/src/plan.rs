@@ -32,12 +32,16 @@
 
 use std::collections::{BTreeMap, BTreeSet};
 use std::num::NonZeroUsize;
+use std::sync::Arc;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use enum_kinds::EnumKind;
 use mz_controller::clusters::{ClusterId, ReplicaId};
-use mz_expr::{CollectionPlan, ColumnOrder, MirRelationExpr, MirScalarExpr, RowSetFinishing};
+use mz_expr::{
+    CollectionPlan, ColumnOrder, MirRelationExpr, MirScalarExpr, OptimizerHints, RowSetFinishing,
+};
+use mz_ore::cast::CastFrom;
 use mz_ore::now::{self, NOW_ZERO};
 use mz_pgcopy::CopyFormatParams;
 use mz_repr::adt::mz_acl_item::{AclMode, MzAclItem};
@@ -61,11 +65,13 @@ use crate::catalog::{
     CatalogType, DefaultPrivilegeAclItem, DefaultPrivilegeObject, IdReference, ObjectType,
     RoleAttributes,
 };
+use crate::func::Func;
 use crate::names::{
     Aug, CommentObjectId, FullItemName, ObjectId, QualifiedItemName, ResolvedDatabaseSpecifier,
     ResolvedIds, SystemObjectId,
 };
 
+pub(crate) mod custom_generator;
 pub(crate) mod error;
 pub(crate) mod explain;
 pub(crate) mod expr;
@@ -103,6 +109,7 @@ pub use statement::{describe, plan, plan_copy_from, StatementContext, StatementD
 pub enum Plan {
     CreateConnection(CreateConnectionPlan),
     CreateDatabase(CreateDatabasePlan),
+    CreateFunction(CreateFunctionPlan),
     CreateSchema(CreateSchemaPlan),
     CreateRole(CreateRolePlan),
     CreateCluster(CreateClusterPlan),
@@ -110,6 +117,7 @@ pub enum Plan {
     CreateSource(CreateSourcePlan),
     CreateSources(Vec<CreateSourcePlans>),
     CreateSecret(CreateSecretPlan),
+    CreateSequence(CreateSequencePlan),
     CreateSink(CreateSinkPlan),
     CreateTable(CreateTablePlan),
     CreateView(CreateViewPlan),
@@ -118,6 +126,7 @@ pub enum Plan {
     CreateType(CreateTypePlan),
     Comment(CommentPlan),
     DiscardTemp,
+    DiscardSequences,
     DiscardAll,
     DropObjects(DropObjectsPlan),
     DropOwned(DropOwnedPlan),
@@ -144,6 +153,7 @@ pub enum Plan {
     AlterIndexSetOptions(AlterIndexSetOptionsPlan),
     AlterIndexResetOptions(AlterIndexResetOptionsPlan),
     AlterSetCluster(AlterSetClusterPlan),
+    AlterRetainHistory(AlterRetainHistoryPlan),
     AlterSink(AlterSinkPlan),
     AlterSource(AlterSourcePlan),
     PurifiedAlterSource {
@@ -208,6 +218,9 @@ impl Plan {
             StatementKind::AlterSetCluster => {
                 vec![PlanKind::AlterNoop, PlanKind::AlterSetCluster]
             }
+            StatementKind::AlterRetainHistory => {
+                vec![PlanKind::AlterNoop, PlanKind::AlterRetainHistory]
+            }
             StatementKind::AlterSink => vec![PlanKind::AlterNoop, PlanKind::AlterSink],
             StatementKind::AlterSource => vec![PlanKind::AlterNoop, PlanKind::AlterSource],
             StatementKind::AlterSystemReset => {
@@ -226,11 +239,13 @@ impl Plan {
             StatementKind::CreateClusterReplica => vec![PlanKind::CreateClusterReplica],
             StatementKind::CreateConnection => vec![PlanKind::CreateConnection],
             StatementKind::CreateDatabase => vec![PlanKind::CreateDatabase],
+            StatementKind::CreateFunction => vec![PlanKind::CreateFunction],
             StatementKind::CreateIndex => vec![PlanKind::CreateIndex],
             StatementKind::CreateMaterializedView => vec![PlanKind::CreateMaterializedView],
             StatementKind::CreateRole => vec![PlanKind::CreateRole],
             StatementKind::CreateSchema => vec![PlanKind::CreateSchema],
             StatementKind::CreateSecret => vec![PlanKind::CreateSecret],
+            StatementKind::CreateSequence => vec![PlanKind::CreateSequence],
             StatementKind::CreateSink => vec![PlanKind::CreateSink],
             StatementKind::CreateSource
             | StatementKind::CreateSubsource
@@ -243,7 +258,11 @@ impl Plan {
             StatementKind::Deallocate => vec![PlanKind::Deallocate],
             StatementKind::Declare => vec![PlanKind::Declare],
             StatementKind::Delete => vec![PlanKind::ReadThenWrite],
-            StatementKind::Discard => vec![PlanKind::DiscardAll, PlanKind::DiscardTemp],
+            StatementKind::Discard => vec![
+                PlanKind::DiscardAll,
+                PlanKind::DiscardTemp,
+                PlanKind::DiscardSequences,
+            ],
             StatementKind::DropObjects => vec![PlanKind::DropObjects],
             StatementKind::DropOwned => vec![PlanKind::DropOwned],
             StatementKind::Execute => vec![PlanKind::Execute],
@@ -252,7 +271,8 @@ impl Plan {
             StatementKind::Fetch => vec![PlanKind::Fetch],
             StatementKind::GrantPrivileges => vec![PlanKind::GrantPrivileges],
             StatementKind::GrantRole => vec![PlanKind::GrantRole],
-            StatementKind::Insert => vec![PlanKind::Insert],
+            StatementKind::Insert => vec![PlanKind::Insert, PlanKind::ReadThenWrite],
+            StatementKind::Merge => vec![PlanKind::ReadThenWrite],
             StatementKind::Prepare => vec![PlanKind::Prepare],
             StatementKind::Raise => vec![PlanKind::Raise],
             StatementKind::ReassignOwned => vec![PlanKind::ReassignOwned],
@@ -284,12 +304,14 @@ impl Plan {
             Plan::CreateConnection(_) => "create connection",
             Plan::CreateDatabase(_) => "create database",
             Plan::CreateSchema(_) => "create schema",
+            Plan::CreateFunction(_) => "create function",
             Plan::CreateRole(_) => "create role",
             Plan::CreateCluster(_) => "create cluster",
             Plan::CreateClusterReplica(_) => "create cluster replica",
             Plan::CreateSource(_) => "create source",
             Plan::CreateSources(_) => "create source",
             Plan::CreateSecret(_) => "create secret",
+            Plan::CreateSequence(_) => "create sequence",
             Plan::CreateSink(_) => "create sink",
             Plan::CreateTable(_) => "create table",
             Plan::CreateView(_) => "create view",
@@ -298,6 +320,7 @@ impl Plan {
             Plan::CreateType(_) => "create type",
             Plan::Comment(_) => "comment",
             Plan::DiscardTemp => "discard temp",
+            Plan::DiscardSequences => "discard sequences",
             Plan::DiscardAll => "discard all",
             Plan::DropObjects(plan) => match plan.object_type {
                 ObjectType::Table => "drop table",
@@ -311,6 +334,7 @@ impl Plan {
                 ObjectType::Cluster => "drop clusters",
                 ObjectType::ClusterReplica => "drop cluster replicas",
                 ObjectType::Secret => "drop secret",
+                ObjectType::Sequence => "drop sequence",
                 ObjectType::Connection => "drop connection",
                 ObjectType::Database => "drop database",
                 ObjectType::Schema => "drop schema",
@@ -347,6 +371,7 @@ impl Plan {
                 ObjectType::Cluster => "alter cluster",
                 ObjectType::ClusterReplica => "alter cluster replica",
                 ObjectType::Secret => "alter secret",
+                ObjectType::Sequence => "alter sequence",
                 ObjectType::Connection => "alter connection",
                 ObjectType::Database => "alter database",
                 ObjectType::Schema => "alter schema",
@@ -356,6 +381,7 @@ impl Plan {
             Plan::AlterClusterRename(_) => "alter cluster rename",
             Plan::AlterClusterReplicaRename(_) => "alter cluster replica rename",
             Plan::AlterSetCluster(_) => "alter set cluster",
+            Plan::AlterRetainHistory(_) => "alter retain history",
             Plan::AlterIndexSetOptions(_) => "alter index",
             Plan::AlterIndexResetOptions(_) => "alter index",
             Plan::AlterSink(_) => "alter sink",
@@ -378,6 +404,7 @@ impl Plan {
                 ObjectType::Cluster => "alter cluster owner",
                 ObjectType::ClusterReplica => "alter cluster replica owner",
                 ObjectType::Secret => "alter secret owner",
+                ObjectType::Sequence => "alter sequence owner",
                 ObjectType::Connection => "alter connection owner",
                 ObjectType::Database => "alter database owner",
                 ObjectType::Schema => "alter schema owner",
@@ -390,6 +417,8 @@ impl Plan {
                 MutationKind::Insert => "insert into select",
                 MutationKind::Update => "update",
                 MutationKind::Delete => "delete",
+                MutationKind::Upsert { .. } => "insert on conflict",
+                MutationKind::Merge { .. } => "merge",
             },
             Plan::Prepare(_) => "prepare",
             Plan::Execute(_) => "execute",
@@ -607,6 +636,21 @@ pub struct CreateSecretPlan {
     pub if_not_exists: bool,
 }
 
+#[derive(Debug)]
+pub struct CreateFunctionPlan {
+    pub name: QualifiedItemName,
+    pub function: Function,
+}
+
+#[derive(Debug)]
+pub struct CreateSequencePlan {
+    pub name: QualifiedItemName,
+    pub sequence: Sequence,
+    /// The table and column that own the sequence, if any.
+    pub owned_by: Option<(GlobalId, usize)>,
+    pub if_not_exists: bool,
+}
+
 #[derive(Debug)]
 pub struct CreateSinkPlan {
     pub name: QualifiedItemName,
@@ -621,6 +665,9 @@ pub struct CreateTablePlan {
     pub name: QualifiedItemName,
     pub table: Table,
     pub if_not_exists: bool,
+    /// The name, owning column, and definition of the sequences implicitly
+    /// created for the table's identity columns.
+    pub sequences: Vec<(QualifiedItemName, usize, Sequence)>,
 }
 
 #[derive(Debug)]
@@ -728,6 +775,8 @@ pub struct SelectPlan {
     pub when: QueryWhen,
     pub finishing: RowSetFinishing,
     pub copy_to: Option<CopyFormat>,
+    /// The optimizer hints given in the `OPTIONS` of the query.
+    pub hints: OptimizerHints,
 }
 
 #[derive(Debug)]
@@ -832,6 +881,7 @@ pub enum Explainee {
         raw_plan: HirRelationExpr,
         row_set_finishing: Option<RowSetFinishing>,
         broken: bool,
+        hints: OptimizerHints,
     },
 }
 
@@ -848,6 +898,8 @@ pub struct SendDiffsPlan {
     pub kind: MutationKind,
     pub returning: Vec<(Row, NonZeroUsize)>,
     pub max_result_size: u32,
+    /// The number of rows affected, if it can't be derived from `updates`.
+    pub affected_rows: Option<usize>,
 }
 
 #[derive(Debug)]
@@ -855,6 +907,9 @@ pub struct InsertPlan {
     pub id: GlobalId,
     pub values: mz_expr::MirRelationExpr,
     pub returning: Vec<mz_expr::MirScalarExpr>,
+    /// Columns whose values must be filled in from a sequence at execution
+    /// time, because they were omitted and default to `nextval(...)`.
+    pub sequence_defaults: BTreeMap<usize, GlobalId>,
 }
 
 #[derive(Debug)]
@@ -865,6 +920,9 @@ pub struct ReadThenWritePlan {
     pub assignments: BTreeMap<usize, mz_expr::MirScalarExpr>,
     pub kind: MutationKind,
     pub returning: Vec<mz_expr::MirScalarExpr>,
+    /// The columns of inserted rows that default to the next value of a
+    /// sequence, which are filled in once the rows are read.
+    pub sequence_defaults: BTreeMap<usize, GlobalId>,
 }
 
 /// Generated by `ALTER ... IF EXISTS` if the named object did not exist.
@@ -879,6 +937,17 @@ pub struct AlterSetClusterPlan {
     pub set_cluster: ClusterId,
 }
 
+#[derive(Debug)]
+pub struct AlterRetainHistoryPlan {
+    pub id: GlobalId,
+    pub object_type: ObjectType,
+    /// The new `RETAIN HISTORY` option, or `None` if it is being reset.
+    pub value: Option<WithOptionValue<Aug>>,
+    /// The logical compaction window described by `value`, or `None` to use
+    /// the default.
+    pub window: Option<Duration>,
+}
+
 #[derive(Debug)]
 pub struct AlterIndexSetOptionsPlan {
     pub id: GlobalId,
@@ -1119,7 +1188,39 @@ pub struct Table {
     pub create_sql: String,
     pub desc: RelationDesc,
     pub defaults: Vec<Expr<Aug>>,
+    /// The columns whose values default to the next value of the sequence
+    /// they own.
+    pub identity_columns: Vec<usize>,
     pub temporary: bool,
+    pub constraints: TableConstraints,
+    /// The logical compaction window configured by `RETAIN HISTORY`, if any.
+    pub compaction_window: Option<Duration>,
+}
+
+/// The constraints that are checked when writing to a table, if constraint
+/// enforcement is enabled.
+#[derive(Clone, Debug, Default)]
+pub struct TableConstraints {
+    pub unique: Vec<UniqueConstraint>,
+    pub checks: Vec<CheckConstraint>,
+}
+
+/// A `PRIMARY KEY` or `UNIQUE` constraint.
+#[derive(Clone, Debug)]
+pub struct UniqueConstraint {
+    pub name: String,
+    pub columns: Vec<usize>,
+    /// Whether rows with `NULL`s in `columns` conflict with each other.
+    pub nulls_not_distinct: bool,
+}
+
+/// A `CHECK` constraint.
+#[derive(Clone, Debug)]
+pub struct CheckConstraint {
+    pub name: String,
+    /// An expression over the columns of a row that evaluates to `false` if
+    /// the row violates the constraint.
+    pub expr: MirScalarExpr,
 }
 
 #[derive(Clone, Debug)]
@@ -1127,6 +1228,8 @@ pub struct Source {
     pub create_sql: String,
     pub data_source: DataSourceDesc,
     pub desc: RelationDesc,
+    /// The logical compaction window configured by `RETAIN HISTORY`, if any.
+    pub compaction_window: Option<Duration>,
 }
 
 #[derive(Debug, Clone)]
@@ -1212,6 +1315,65 @@ pub struct Secret {
     pub secret_as: MirScalarExpr,
 }
 
+#[derive(Clone, Debug)]
+pub struct Function {
+    pub create_sql: String,
+    pub func: Arc<Func>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Sequence {
+    pub create_sql: String,
+    pub options: SequenceOptions,
+}
+
+/// The resolved parameters of a sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct SequenceOptions {
+    pub increment: i64,
+    pub min_value: i64,
+    pub max_value: i64,
+    pub start: i64,
+    pub cache: u64,
+    pub cycle: bool,
+}
+
+impl SequenceOptions {
+    /// Produces up to `count` values of the sequence, starting at `next`.
+    ///
+    /// Returns the produced values and the next value to hand out. Fewer than
+    /// `count` values are returned if the sequence is exhausted and does not
+    /// cycle; an exhausted sequence stores a next value outside of
+    /// `[min_value, max_value]`.
+    pub fn advance(&self, mut next: i64, count: u64) -> (Vec<i64>, i64) {
+        let mut values = Vec::new();
+        while u64::cast_from(values.len()) < count {
+            if next < self.min_value || next > self.max_value {
+                if !self.cycle {
+                    break;
+                }
+                next = if self.increment > 0 {
+                    self.min_value
+                } else {
+                    self.max_value
+                };
+            }
+            values.push(next);
+            next = match next.checked_add(self.increment) {
+                Some(next) => next,
+                None if self.increment > 0 => self.max_value.saturating_add(1),
+                None => self.min_value.saturating_sub(1),
+            };
+        }
+        (values, next)
+    }
+
+    /// Reports whether `value` lies within the bounds of the sequence.
+    pub fn contains(&self, value: i64) -> bool {
+        self.min_value <= value && value <= self.max_value
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Sink {
     pub create_sql: String,
@@ -1234,6 +1396,31 @@ pub struct MaterializedView {
     pub expr: mz_expr::MirRelationExpr,
     pub column_names: Vec<ColumnName>,
     pub cluster_id: ClusterId,
+    /// The logical compaction window configured by `RETAIN HISTORY`, if any.
+    pub compaction_window: Option<Duration>,
+    /// The refresh schedule configured by `REFRESH` options, if any.
+    pub refresh_schedule: Option<RefreshSchedulePlan>,
+    /// The optimizer hints given in the `OPTIONS` of the defining query.
+    pub hints: OptimizerHints,
+}
+
+/// The refresh schedule of a materialized view, as planned from its `REFRESH`
+/// options. The times are evaluated when the materialized view is created.
+#[derive(Clone, Debug, Default)]
+pub struct RefreshSchedulePlan {
+    /// The `REFRESH EVERY` options, in the order they were specified.
+    pub everies: Vec<RefreshEveryPlan>,
+    /// The `REFRESH AT` options, in the order they were specified. `None`
+    /// stands for `REFRESH AT CREATION`.
+    pub ats: Vec<Option<MirScalarExpr>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct RefreshEveryPlan {
+    pub interval: Duration,
+    /// The time that refreshes are aligned to. `None` stands for the
+    /// creation time of the materialized view.
+    pub aligned_to: Option<MirScalarExpr>,
 }
 
 #[derive(Clone, Debug)]
@@ -1330,6 +1517,16 @@ pub enum MutationKind {
     Insert,
     Update,
     Delete,
+    /// An `INSERT ... ON CONFLICT`. `update` is true for `DO UPDATE`.
+    Upsert {
+        update: bool,
+    },
+    /// A `MERGE`, recording which actions its clauses may take.
+    Merge {
+        insert: bool,
+        update: bool,
+        delete: bool,
+    },
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -0,0 +1,339 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Parsing of `LOAD GENERATOR CUSTOM` specifications.
+//!
+//! The grammar is:
+//!
+//! ```text
+//! spec      := table [ ';' table ]* [ ';' ]
+//! table     := TABLE name '(' column [ ',' column ]* ')'
+//!              [ WITH '(' option [ ',' option ]* ')' ]
+//! option    := ROWS n | INSERTS PER TICK n | UPDATES PER TICK n | DELETES PER TICK n
+//! column    := name generator
+//! generator := SEQUENCE [ START n ] [ STEP n ]
+//!            | UNIFORM '(' min ',' max ')'
+//!            | NORMAL '(' mean ',' std_dev ')'
+//!            | CHOICE '(' 'value' [ ',' 'value' ]* ')'
+//!            | STRING '(' length ')'
+//!            | REFERENCES table '(' column ')'
+//! ```
+//!
+//! Specifications are usually dollar-quoted, so that the strings of `CHOICE`
+//! need not be escaped.
+
+use std::collections::BTreeSet;
+
+use mz_sql_lexer::lexer::{self, PosToken, Token};
+use mz_storage_client::types::sources::custom_generator::{
+    ColumnGenerator, CustomColumnSpec, CustomGeneratorSpec, CustomTableSpec,
+};
+
+use crate::plan::error::PlanError;
+
+/// Parses and validates a `LOAD GENERATOR CUSTOM` specification.
+pub fn parse_spec(spec: &str) -> Result<CustomGeneratorSpec, PlanError> {
+    let tokens = lexer::lex(spec)
+        .map_err(|e| sql_err!("invalid load generator specification: {}", e.message))?;
+    let mut parser = SpecParser { tokens, index: 0 };
+    let mut tables: Vec<CustomTableSpec> = vec![];
+    while parser.peek().is_some() {
+        let table = parser.parse_table(&tables)?;
+        if tables.iter().any(|t| t.name == table.name) {
+            sql_bail!("table {} specified more than once", table.name);
+        }
+        tables.push(table);
+        if !parser.consume_token(&Token::Semicolon) && parser.peek().is_some() {
+            return Err(parser.expected("semicolon or end of specification"));
+        }
+    }
+    if tables.is_empty() {
+        sql_bail!("load generator specification must contain at least one table");
+    }
+    Ok(CustomGeneratorSpec { tables })
+}
+
+struct SpecParser {
+    tokens: Vec<PosToken>,
+    index: usize,
+}
+
+impl SpecParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|t| &t.kind)
+    }
+
+    fn expected(&self, what: &str) -> PlanError {
+        match self.peek() {
+            Some(token) => sql_err!(
+                "invalid load generator specification: expected {}, found {}",
+                what,
+                token
+            ),
+            None => sql_err!(
+                "invalid load generator specification: expected {}, found end of specification",
+                what
+            ),
+        }
+    }
+
+    /// Consumes the next token if it is the word `word`, which may or may not
+    /// be a SQL keyword.
+    fn consume_word(&mut self, word: &str) -> bool {
+        let matches = match self.peek() {
+            Some(Token::Keyword(kw)) => kw.as_str().eq_ignore_ascii_case(word),
+            Some(Token::Ident(ident)) => ident.eq_ignore_ascii_case(word),
+            _ => false,
+        };
+        if matches {
+            self.index += 1;
+        }
+        matches
+    }
+
+    fn expect_word(&mut self, word: &str) -> Result<(), PlanError> {
+        if self.consume_word(word) {
+            Ok(())
+        } else {
+            Err(self.expected(word))
+        }
+    }
+
+    fn consume_token(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.index += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_token(&mut self, token: Token) -> Result<(), PlanError> {
+        if self.consume_token(&token) {
+            Ok(())
+        } else {
+            Err(self.expected(&token.to_string()))
+        }
+    }
+
+    fn parse_name(&mut self) -> Result<String, PlanError> {
+        match self.peek() {
+            Some(Token::Ident(ident)) => {
+                let name = ident.clone();
+                self.index += 1;
+                Ok(name)
+            }
+            Some(Token::Keyword(kw)) => {
+                let name = kw.as_str().to_lowercase();
+                self.index += 1;
+                Ok(name)
+            }
+            _ => Err(self.expected("name")),
+        }
+    }
+
+    /// Parses a number, with an optional leading minus sign.
+    fn parse_number<T: std::str::FromStr>(&mut self, what: &str) -> Result<T, PlanError> {
+        let negative = matches!(self.peek(), Some(Token::Op(op)) if op == "-");
+        if negative {
+            self.index += 1;
+        }
+        let Some(Token::Number(n)) = self.peek() else {
+            return Err(self.expected(what));
+        };
+        let n = if negative { format!("-{n}") } else { n.clone() };
+        let n = n.parse().map_err(|_| self.expected(what))?;
+        self.index += 1;
+        Ok(n)
+    }
+
+    fn parse_string(&mut self) -> Result<String, PlanError> {
+        match self.peek() {
+            Some(Token::String(s)) => {
+                let s = s.clone();
+                self.index += 1;
+                Ok(s)
+            }
+            _ => Err(self.expected("string literal")),
+        }
+    }
+
+    /// Parses a table, whose references may only refer to the `earlier` tables.
+    fn parse_table(&mut self, earlier: &[CustomTableSpec]) -> Result<CustomTableSpec, PlanError> {
+        self.expect_word("TABLE")?;
+        let mut table = CustomTableSpec {
+            name: self.parse_name()?,
+            columns: vec![],
+            rows: 0,
+            inserts_per_tick: 0,
+            updates_per_tick: 0,
+            deletes_per_tick: 0,
+        };
+        self.expect_token(Token::LParen)?;
+        loop {
+            let name = self.parse_name()?;
+            if table.columns.iter().any(|c| c.name == name) {
+                sql_bail!(
+                    "column {} specified more than once in table {}",
+                    name,
+                    table.name
+                );
+            }
+            let generator = self.parse_generator(&name, earlier)?;
+            table.columns.push(CustomColumnSpec { name, generator });
+            if !self.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+        self.expect_token(Token::RParen)?;
+
+        if self.consume_word("WITH") {
+            self.expect_token(Token::LParen)?;
+            let mut seen = BTreeSet::new();
+            loop {
+                let (option, value) = if self.consume_word("ROWS") {
+                    ("ROWS", &mut table.rows)
+                } else if self.consume_word("INSERTS") {
+                    ("INSERTS PER TICK", &mut table.inserts_per_tick)
+                } else if self.consume_word("UPDATES") {
+                    ("UPDATES PER TICK", &mut table.updates_per_tick)
+                } else if self.consume_word("DELETES") {
+                    ("DELETES PER TICK", &mut table.deletes_per_tick)
+                } else {
+                    return Err(self.expected("ROWS, INSERTS, UPDATES or DELETES"));
+                };
+                if option != "ROWS" {
+                    self.expect_word("PER")?;
+                    self.expect_word("TICK")?;
+                }
+                if !seen.insert(option) {
+                    sql_bail!(
+                        "{} specified more than once in table {}",
+                        option,
+                        table.name
+                    );
+                }
+                self.consume_token(&Token::Eq);
+                *value = self.parse_number("non-negative integer")?;
+                if !self.consume_token(&Token::Comma) {
+                    break;
+                }
+            }
+            self.expect_token(Token::RParen)?;
+        }
+        Ok(table)
+    }
+
+    fn parse_generator(
+        &mut self,
+        column: &str,
+        earlier: &[CustomTableSpec],
+    ) -> Result<ColumnGenerator, PlanError> {
+        let generator = if self.consume_word("SEQUENCE") {
+            let start = if self.consume_word("START") {
+                self.parse_number("integer")?
+            } else {
+                1
+            };
+            let step = if self.consume_word("STEP") {
+                self.parse_number("integer")?
+            } else {
+                1
+            };
+            if step == 0 {
+                sql_bail!("sequence column {} must have a non-zero step", column);
+            }
+            ColumnGenerator::Sequence { start, step }
+        } else if self.consume_word("UNIFORM") {
+            self.expect_token(Token::LParen)?;
+            let min = self.parse_number("integer")?;
+            self.expect_token(Token::Comma)?;
+            let max = self.parse_number("integer")?;
+            self.expect_token(Token::RParen)?;
+            if min > max {
+                sql_bail!("uniform column {} must have min <= max", column);
+            }
+            ColumnGenerator::Uniform { min, max }
+        } else if self.consume_word("NORMAL") {
+            self.expect_token(Token::LParen)?;
+            let mean: f64 = self.parse_number("number")?;
+            self.expect_token(Token::Comma)?;
+            let std_dev: f64 = self.parse_number("number")?;
+            self.expect_token(Token::RParen)?;
+            if !mean.is_finite() || !std_dev.is_finite() || std_dev < 0.0 {
+                sql_bail!(
+                    "normal column {} must have a finite mean and a finite, \
+                    non-negative standard deviation",
+                    column
+                );
+            }
+            ColumnGenerator::Normal { mean, std_dev }
+        } else if self.consume_word("CHOICE") {
+            self.expect_token(Token::LParen)?;
+            let mut values = vec![self.parse_string()?];
+            while self.consume_token(&Token::Comma) {
+                values.push(self.parse_string()?);
+            }
+            self.expect_token(Token::RParen)?;
+            ColumnGenerator::Choice { values }
+        } else if self.consume_word("STRING") {
+            self.expect_token(Token::LParen)?;
+            let length = self.parse_number("non-negative integer")?;
+            self.expect_token(Token::RParen)?;
+            ColumnGenerator::String { length }
+        } else if self.consume_word("REFERENCES") {
+            let table_name = self.parse_name()?;
+            self.expect_token(Token::LParen)?;
+            let column_name = self.parse_name()?;
+            self.expect_token(Token::RParen)?;
+            let table = earlier
+                .iter()
+                .position(|t| t.name == table_name)
+                .ok_or_else(|| {
+                    sql_err!(
+                        "invalid reference in column {}: table {} must be declared before \
+                        it is referenced",
+                        column,
+                        table_name
+                    )
+                })?;
+            let referenced_column = earlier[table]
+                .columns
+                .iter()
+                .position(|c| c.name == column_name)
+                .ok_or_else(|| {
+                    sql_err!(
+                        "invalid reference in column {}: table {} has no column {}",
+                        column,
+                        table_name,
+                        column_name
+                    )
+                })?;
+            // Only sequence columns are keys whose values survive updates.
+            if !matches!(
+                earlier[table].columns[referenced_column].generator,
+                ColumnGenerator::Sequence { .. }
+            ) {
+                sql_bail!(
+                    "invalid reference in column {}: column {} of table {} is not a SEQUENCE",
+                    column,
+                    column_name,
+                    table_name
+                );
+            }
+            ColumnGenerator::Reference {
+                table,
+                column: referenced_column,
+            }
+        } else {
+            return Err(self.expected("SEQUENCE, UNIFORM, NORMAL, CHOICE, STRING or REFERENCES"));
+        };
+        Ok(generator)
+    }
+}
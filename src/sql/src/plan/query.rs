@@ -48,25 +48,28 @@ use mz_ore::str::StrExt;
 use mz_repr::adt::char::CharLength;
 use mz_repr::adt::numeric::{NumericMaxScale, NUMERIC_DATUM_MAX_PRECISION};
 use mz_repr::adt::varchar::VarCharMaxLength;
+use mz_repr::namespaces::PG_CATALOG_SCHEMA;
 use mz_repr::{
     strconv, ColumnName, ColumnType, Datum, GlobalId, RelationDesc, RelationType, Row, RowArena,
     ScalarType,
 };
 use mz_sql_parser::ast::display::AstDisplay;
-use mz_sql_parser::ast::visit::Visit;
+use mz_sql_parser::ast::visit::{Visit, VisitNode};
 use mz_sql_parser::ast::visit_mut::{self, VisitMut};
 use mz_sql_parser::ast::{
-    visit, AsOf, Assignment, AstInfo, CreateWebhookSourceBody, CreateWebhookSourceCheck,
-    CreateWebhookSourceHeader, CreateWebhookSourceSecret, CteBlock, DeleteStatement, Distinct,
-    Expr, Function, FunctionArgs, HomogenizingFunction, Ident, InsertSource, IsExprConstruct, Join,
-    JoinConstraint, JoinOperator, Limit, MutRecBlock, MutRecBlockOption, MutRecBlockOptionName,
-    OrderByExpr, Query, Select, SelectItem, SelectOption, SelectOptionName, SetExpr, SetOperator,
-    ShowStatement, SubscriptPosition, TableAlias, TableFactor, TableWithJoins, UnresolvedItemName,
-    UpdateStatement, Value, Values, WindowFrame, WindowFrameBound, WindowFrameUnits, WindowSpec,
+    visit, AsOf, Assignment, AstInfo, ColumnDef, CreateWebhookSourceBody, CreateWebhookSourceCheck,
+    CreateWebhookSourceHeader, CreateWebhookSourceSecret, CteBlock, CteRecursive, CteSearchOrder,
+    DeleteStatement, Distinct, Expr, Function, FunctionArgs, HomogenizingFunction, Ident,
+    InsertSource, IsExprConstruct, Join, JoinConstraint, JoinOperator, Limit, MergeAction,
+    MergeClause, MergeStatement, MutRecBlock, MutRecBlockOption, MutRecBlockOptionName, OnConflict,
+    OnConflictAction, Op, OrderByExpr, Query, Select, SelectItem, SelectOption, SelectOptionName,
+    SetExpr, SetOperator, ShowStatement, SubscriptPosition, TableAlias, TableFactor,
+    TableWithJoins, UnresolvedItemName, UpdateStatement, Value, Values, WindowFrame,
+    WindowFrameBound, WindowFrameUnits, WindowSpec,
 };
 use uuid::Uuid;
 
-use crate::catalog::{CatalogItemType, CatalogType, SessionCatalog};
+use crate::catalog::{CatalogItem, CatalogItemType, CatalogType, SessionCatalog};
 use crate::func::{self, Func, FuncSpec};
 use crate::names::{Aug, FullItemName, PartialItemName, ResolvedDataType, ResolvedItemName};
 use crate::normalize;
@@ -79,9 +82,10 @@ use crate::plan::expr::{
 };
 use crate::plan::plan_utils::{self, GroupSizeHints, JoinSide};
 use crate::plan::scope::{Scope, ScopeItem};
+use crate::plan::side_effecting_func::resolve_sequence;
 use crate::plan::statement::{show, StatementContext, StatementDesc};
 use crate::plan::typeconv::{self, CastContext};
-use crate::plan::with_options::TryFromValue;
+use crate::plan::with_options::{Object, TryFromValue};
 use crate::plan::PlanError::InvalidWmrRecursionLimit;
 use crate::plan::{
     transform_ast, Params, PlanContext, QueryWhen, ShowCreatePlan, WebhookValidation,
@@ -178,13 +182,26 @@ fn try_push_projection_order_by(
     }
 }
 
+/// Plans the rows written by an `INSERT` into a table, as well as its
+/// `RETURNING` clause.
+///
+/// Omitted columns whose values are drawn from a sequence are returned
+/// separately, keyed by column, and are `NULL` in the planned rows.
 pub fn plan_insert_query(
     scx: &StatementContext,
     table_name: ResolvedItemName,
     columns: Vec<Ident>,
     source: InsertSource<Aug>,
     returning: Vec<SelectItem<Aug>>,
-) -> Result<(GlobalId, HirRelationExpr, PlannedQuery<Vec<HirScalarExpr>>), PlanError> {
+) -> Result<
+    (
+        GlobalId,
+        HirRelationExpr,
+        PlannedQuery<Vec<HirScalarExpr>>,
+        BTreeMap<usize, GlobalId>,
+    ),
+    PlanError,
+> {
     let mut qcx = QueryContext::root(scx, QueryLifetime::OneShot);
     let table = scx.get_item_by_resolved_name(&table_name)?;
 
@@ -213,7 +230,7 @@ pub fn plan_insert_query(
         );
     }
 
-    let columns: Vec<_> = columns.into_iter().map(normalize::column_name).collect();
+    let mut columns: Vec<_> = columns.into_iter().map(normalize::column_name).collect();
 
     // Validate target column order.
     let mut source_types = Vec::with_capacity(columns.len());
@@ -256,14 +273,28 @@ pub fn plan_insert_query(
             match query {
                 // Special-case simple VALUES clauses as PostgreSQL does.
                 Query {
-                    body: SetExpr::Values(Values(values)),
+                    body: SetExpr::Values(Values(mut values)),
                     ctes,
                     order_by,
                     limit: None,
                     offset: None,
                 } if ctes.is_empty() && order_by.is_empty() => {
-                    let names: Vec<_> = ordering.iter().map(|i| desc.get_name(*i)).collect();
-                    plan_values_insert(&qcx, &names, &source_types, &values)?
+                    plan_values_insert_defaults(
+                        scx,
+                        table,
+                        &defaults,
+                        &mut columns,
+                        &mut ordering,
+                        &mut source_types,
+                        &mut values,
+                    )?;
+                    if values[0].is_empty() {
+                        // Every column is `DEFAULT`.
+                        HirRelationExpr::constant(vec![vec![]; values.len()], RelationType::empty())
+                    } else {
+                        let names: Vec<_> = ordering.iter().map(|i| desc.get_name(*i)).collect();
+                        plan_values_insert(&qcx, &names, &source_types, &values)?
+                    }
                 }
                 _ => {
                     let (expr, _scope) = plan_nested_query(&mut qcx, &query)?;
@@ -315,12 +346,19 @@ pub fn plan_insert_query(
     // Maps from table column index to position in the source query
     let col_to_source: BTreeMap<_, _> = ordering.iter().enumerate().map(|(a, b)| (b, a)).collect();
 
+    let mut sequence_defaults = BTreeMap::new();
     let column_details = desc.iter_types().zip_eq(defaults).enumerate();
     for (col_idx, (col_typ, default)) in column_details {
         if let Some(src_idx) = col_to_source.get(&col_idx) {
             project_key.push(*src_idx);
         } else {
-            let hir = plan_default_expr(scx, &default, &col_typ.scalar_type)?;
+            let hir = match plan_sequence_default(scx, table, col_idx, &default)? {
+                Some(sequence) => {
+                    sequence_defaults.insert(col_idx, sequence);
+                    HirScalarExpr::literal_null(col_typ.scalar_type.clone())
+                }
+                None => plan_default_expr(scx, &default, &col_typ.scalar_type)?,
+            };
             project_key.push(expr_arity + map_exprs.len());
             map_exprs.push(hir);
         }
@@ -381,6 +419,7 @@ pub fn plan_insert_query(
         table.id(),
         expr.map(map_exprs).project(project_key),
         returning,
+        sequence_defaults,
     ))
 }
 
@@ -453,13 +492,17 @@ pub fn plan_copy_from(
 
 /// Builds a plan that adds the default values for the missing columns and re-orders
 /// the datums in the given rows to match the order in the target table.
+///
+/// Also returns the missing columns that default to the next value of a
+/// sequence, which are `NULL` in the plan and must be filled in when the rows
+/// are inserted.
 pub fn plan_copy_from_rows(
     pcx: &PlanContext,
     catalog: &dyn SessionCatalog,
     id: GlobalId,
     columns: Vec<usize>,
     rows: Vec<mz_repr::Row>,
-) -> Result<HirRelationExpr, PlanError> {
+) -> Result<(HirRelationExpr, BTreeMap<usize, GlobalId>), PlanError> {
     let scx = StatementContext::new(Some(pcx), catalog);
 
     let table = catalog.get_item(&id);
@@ -497,7 +540,7 @@ pub fn plan_copy_from_rows(
     // projection).
     let default: Vec<_> = (0..desc.arity()).collect();
     if columns == default {
-        return Ok(expr);
+        return Ok((expr, BTreeMap::new()));
     }
 
     // Fill in any omitted columns and rearrange into correct order
@@ -507,18 +550,25 @@ pub fn plan_copy_from_rows(
     // Maps from table column index to position in the source query
     let col_to_source: BTreeMap<_, _> = columns.iter().enumerate().map(|(a, b)| (b, a)).collect();
 
+    let mut sequence_defaults = BTreeMap::new();
     let column_details = desc.iter_types().zip_eq(defaults).enumerate();
     for (col_idx, (col_typ, default)) in column_details {
         if let Some(src_idx) = col_to_source.get(&col_idx) {
             project_key.push(*src_idx);
         } else {
-            let hir = plan_default_expr(&scx, &default, &col_typ.scalar_type)?;
+            let hir = match plan_sequence_default(&scx, table, col_idx, &default)? {
+                Some(sequence) => {
+                    sequence_defaults.insert(col_idx, sequence);
+                    HirScalarExpr::literal_null(col_typ.scalar_type.clone())
+                }
+                None => plan_default_expr(&scx, &default, &col_typ.scalar_type)?,
+            };
             project_key.push(typ.arity() + map_exprs.len());
             map_exprs.push(hir);
         }
     }
 
-    Ok(expr.map(map_exprs).project(project_key))
+    Ok((expr.map(map_exprs).project(project_key), sequence_defaults))
 }
 
 /// Common information used for DELETE, UPDATE, and INSERT INTO ... SELECT plans.
@@ -577,6 +627,62 @@ pub fn plan_mutation_query_inner(
     assignments: Vec<Assignment<Aug>>,
     selection: Option<Expr<Aug>>,
 ) -> Result<ReadThenWritePlan, PlanError> {
+    let (id, desc, mut get, scope) = plan_mutation_target(&qcx, table_name, alias.as_ref())?;
+    let relation_type = qcx.relation_type(&get);
+
+    if using.is_empty() {
+        if let Some(expr) = selection {
+            let ecx = &ExprContext {
+                qcx: &qcx,
+                name: "WHERE clause",
+                scope: &scope,
+                relation_type: &relation_type,
+                allow_aggregates: false,
+                allow_subqueries: true,
+                allow_parameters: true,
+                allow_windows: false,
+            };
+            let expr = plan_expr(ecx, &expr)?.type_as(ecx, &ScalarType::Bool)?;
+            get = get.filter(vec![expr]);
+        }
+    } else {
+        get = handle_mutation_using_clause(&qcx, selection, using, get, scope.clone())?;
+    }
+
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "SET clause",
+        scope: &scope,
+        relation_type: &relation_type,
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_parameters: false,
+        allow_windows: false,
+    };
+    let sets = plan_assignments(ecx, &desc, assignments)?;
+
+    let finishing = RowSetFinishing {
+        order_by: vec![],
+        limit: None,
+        offset: 0,
+        project: (0..desc.arity()).collect(),
+    };
+
+    Ok(ReadThenWritePlan {
+        id,
+        selection: get,
+        finishing,
+        assignments: sets,
+    })
+}
+
+/// Validates that `table_name` names a user table that can be mutated, and
+/// plans a read of its contents, visible in the returned scope as `alias`.
+fn plan_mutation_target(
+    qcx: &QueryContext,
+    table_name: ResolvedItemName,
+    alias: Option<&TableAlias>,
+) -> Result<(GlobalId, RelationDesc, HirRelationExpr, Scope), PlanError> {
     // Get global ID.
     let id = match table_name {
         ResolvedItemName::Item { id, .. } => id,
@@ -600,46 +706,27 @@ pub fn plan_mutation_query_inner(
     }
 
     // Derive structs for operation from validated table
-    let (mut get, scope) = qcx.resolve_table_name(table_name)?;
-    let scope = plan_table_alias(scope, alias.as_ref())?;
-    let desc = item.desc(&qcx.scx.catalog.resolve_full_name(item.name()))?;
-    let relation_type = qcx.relation_type(&get);
-
-    if using.is_empty() {
-        if let Some(expr) = selection {
-            let ecx = &ExprContext {
-                qcx: &qcx,
-                name: "WHERE clause",
-                scope: &scope,
-                relation_type: &relation_type,
-                allow_aggregates: false,
-                allow_subqueries: true,
-                allow_parameters: true,
-                allow_windows: false,
-            };
-            let expr = plan_expr(ecx, &expr)?.type_as(ecx, &ScalarType::Bool)?;
-            get = get.filter(vec![expr]);
-        }
-    } else {
-        get = handle_mutation_using_clause(&qcx, selection, using, get, scope.clone())?;
-    }
+    let (get, scope) = qcx.resolve_table_name(table_name)?;
+    let scope = plan_table_alias(scope, alias)?;
+    let desc = item
+        .desc(&qcx.scx.catalog.resolve_full_name(item.name()))?
+        .into_owned();
+    Ok((id, desc, get, scope))
+}
 
+/// Plans the `SET` assignments of an `UPDATE`-like statement, returning a map
+/// from column index to the expression that column is assigned.
+fn plan_assignments(
+    ecx: &ExprContext,
+    desc: &RelationDesc,
+    assignments: Vec<Assignment<Aug>>,
+) -> Result<BTreeMap<usize, HirScalarExpr>, PlanError> {
     let mut sets = BTreeMap::new();
     for Assignment { id, value } in assignments {
         // Get the index and type of the column.
         let name = normalize::column_name(id);
         match desc.get_by_name(&name) {
             Some((idx, typ)) => {
-                let ecx = &ExprContext {
-                    qcx: &qcx,
-                    name: "SET clause",
-                    scope: &scope,
-                    relation_type: &relation_type,
-                    allow_aggregates: false,
-                    allow_subqueries: false,
-                    allow_parameters: false,
-                    allow_windows: false,
-                };
                 let expr = plan_expr(ecx, &value)?.cast_to(
                     ecx,
                     CastContext::Assignment,
@@ -653,20 +740,403 @@ pub fn plan_mutation_query_inner(
             None => sql_bail!("unknown column {}", name),
         };
     }
+    Ok(sets)
+}
 
-    let finishing = RowSetFinishing {
-        order_by: vec![],
-        limit: None,
-        offset: 0,
-        project: (0..desc.arity()).collect(),
+/// Plans the read portion of an `INSERT ... ON CONFLICT` statement.
+///
+/// `source` is the planned source of the insert, with one column for each of
+/// the table's columns. The rows it proposes are matched against the table's
+/// existing rows on the key named by the conflict target, and the selection
+/// has the shape described in [`plan_upsert_selection`].
+pub fn plan_insert_on_conflict_query(
+    scx: &StatementContext,
+    table_name: ResolvedItemName,
+    source: HirRelationExpr,
+    mut on_conflict: OnConflict<Aug>,
+) -> Result<ReadThenWritePlan, PlanError> {
+    transform_ast::transform(scx, &mut on_conflict)?;
+
+    let qcx = QueryContext::root(scx, QueryLifetime::OneShot);
+    let (id, desc, target, target_scope) = plan_mutation_target(&qcx, table_name, None)?;
+    let arity = desc.arity();
+
+    // Without a conflict target, a conflict on any of the table's keys counts.
+    let keys = if on_conflict.target.is_empty() {
+        desc.typ().keys.clone()
+    } else {
+        let mut columns = BTreeSet::new();
+        for ident in on_conflict.target {
+            let name = normalize::column_name(ident);
+            match desc.get_by_name(&name) {
+                Some((idx, _)) => columns.insert(idx),
+                None => sql_bail!("column {} does not exist", name.as_str().quoted()),
+            };
+        }
+        match desc
+            .typ()
+            .keys
+            .iter()
+            .find(|key| key.iter().copied().collect::<BTreeSet<_>>() == columns)
+        {
+            Some(key) => vec![key.clone()],
+            None => sql_bail!(
+                "there is no unique or exclusion constraint matching the ON CONFLICT specification"
+            ),
+        }
     };
 
-    Ok(ReadThenWritePlan {
+    // Pair each proposed row with the existing row it conflicts with, if any.
+    // The existing row is followed by a marker column, which is only non-null
+    // when there was a conflict.
+    let on = HirScalarExpr::variadic_or(
+        keys.iter()
+            .map(|key| {
+                HirScalarExpr::variadic_and(
+                    key.iter()
+                        .map(|i| {
+                            HirScalarExpr::column(*i)
+                                .call_binary(HirScalarExpr::column(arity + *i), BinaryFunc::Eq)
+                        })
+                        .collect(),
+                )
+            })
+            .collect(),
+    );
+    let joined = source.join(
+        target.map(vec![HirScalarExpr::literal_true()]),
+        on,
+        JoinKind::LeftOuter,
+    );
+    let conflict = HirScalarExpr::column(2 * arity).call_is_null().not();
+
+    let (new, retract, insert) = match on_conflict.action {
+        OnConflictAction::DoNothing => (
+            (0..arity).map(HirScalarExpr::column).collect(),
+            HirScalarExpr::literal_false(),
+            conflict.not(),
+        ),
+        OnConflictAction::DoUpdate {
+            assignments,
+            selection,
+        } => {
+            // The proposed row is only visible by qualified reference to
+            // `excluded`, so that unqualified references find the existing row.
+            let mut excluded_scope = Scope::from_source(
+                Some(PartialItemName {
+                    database: None,
+                    schema: None,
+                    item: "excluded".into(),
+                }),
+                desc.iter_names(),
+            );
+            for item in &mut excluded_scope.items {
+                item.allow_unqualified_references = false;
+            }
+            let scope = excluded_scope.product(with_match_marker(target_scope))?;
+            let relation_type = qcx.relation_type(&joined);
+            let ecx = &ExprContext {
+                qcx: &qcx,
+                name: "ON CONFLICT DO UPDATE",
+                scope: &scope,
+                relation_type: &relation_type,
+                allow_aggregates: false,
+                allow_subqueries: false,
+                allow_parameters: true,
+                allow_windows: false,
+            };
+            let mut update = conflict.clone();
+            if let Some(selection) = selection {
+                let selection = plan_expr(&ecx.with_name("WHERE clause"), &selection)?
+                    .type_as(ecx, &ScalarType::Bool)?;
+                update = update.and(selection);
+            }
+            let update = HirScalarExpr::If {
+                cond: Box::new(update),
+                then: Box::new(HirScalarExpr::literal_true()),
+                els: Box::new(HirScalarExpr::literal_false()),
+            };
+            let mut sets = plan_assignments(&ecx.with_name("SET clause"), &desc, assignments)?;
+            let new = (0..arity)
+                .map(|i| HirScalarExpr::If {
+                    cond: Box::new(conflict.clone()),
+                    then: Box::new(
+                        sets.remove(&i)
+                            .unwrap_or_else(|| HirScalarExpr::column(arity + i)),
+                    ),
+                    els: Box::new(HirScalarExpr::column(i)),
+                })
+                .collect();
+            (new, update.clone(), conflict.not().or(update))
+        }
+    };
+
+    let old = (arity..2 * arity).map(HirScalarExpr::column).collect();
+    Ok(plan_upsert_selection(id, joined, old, new, retract, insert))
+}
+
+/// Plans the read portion of a `MERGE` statement.
+///
+/// Each source row is matched against the target table's rows using the
+/// `ON` condition, and takes the action of the first `WHEN` clause whose
+/// condition it satisfies. The selection has the shape described in
+/// [`plan_upsert_selection`].
+pub fn plan_merge_query(
+    scx: &StatementContext,
+    mut merge_stmt: MergeStatement<Aug>,
+) -> Result<ReadThenWritePlan, PlanError> {
+    transform_ast::transform(scx, &mut merge_stmt)?;
+
+    let MergeStatement {
+        table_name,
+        alias,
+        source,
+        on,
+        clauses,
+    } = merge_stmt;
+    let qcx = QueryContext::root(scx, QueryLifetime::OneShot);
+    let (id, desc, target, target_scope) = plan_mutation_target(&qcx, table_name, alias.as_ref())?;
+    let table = scx.get_item(&id);
+    let arity = desc.arity();
+
+    // Affecting a row twice is detected by looking for rows retracted more
+    // than once, which is only sound when the table's rows are distinct.
+    if desc.typ().keys.is_empty() && clauses.iter().any(|clause| clause.matched) {
+        sql_bail!(
+            "MERGE with WHEN MATCHED clauses requires the target table to have a primary key"
+        );
+    }
+
+    let mut defaults = table
+        .table_details()
+        .expect("mutation target is a table")
+        .to_vec();
+    for default in &mut defaults {
+        transform_ast::transform(scx, default)?;
+    }
+
+    // Pair each source row with the target rows it matches, if any. The
+    // target's columns are followed by a marker column, which is only non-null
+    // when the source row matched.
+    let (source, source_scope) = plan_table_factor(&qcx, &source)?;
+    let source_arity = source.arity();
+    let target = target.map(vec![HirScalarExpr::literal_true()]);
+    let scope = source_scope.product(with_match_marker(target_scope))?;
+    let on = {
+        let relation_type = RelationType::new(
+            qcx.relation_type(&source)
+                .column_types
+                .into_iter()
+                .chain(qcx.relation_type(&target).column_types)
+                .collect(),
+        );
+        let ecx = &ExprContext {
+            qcx: &qcx,
+            name: "ON clause",
+            scope: &scope,
+            relation_type: &relation_type,
+            allow_aggregates: false,
+            allow_subqueries: true,
+            allow_parameters: true,
+            allow_windows: false,
+        };
+        plan_expr(ecx, &on)?.type_as(ecx, &ScalarType::Bool)?
+    };
+    let joined = source.join(target, on, JoinKind::LeftOuter);
+    let matched = HirScalarExpr::column(source_arity + arity)
+        .call_is_null()
+        .not();
+
+    let relation_type = qcx.relation_type(&joined);
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "WHEN clause",
+        scope: &scope,
+        relation_type: &relation_type,
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_parameters: true,
+        allow_windows: false,
+    };
+
+    // Determine which clause, if any, applies to each row. Clauses are
+    // numbered from one, and zero means that no clause applies.
+    let action_column = source_arity + arity + 1;
+    let mut guards = vec![];
+    // For each column, the value it takes under each clause that writes a new
+    // row.
+    let mut new_values = vec![vec![]; arity];
+    let mut retract_actions = vec![];
+    let mut insert_actions = vec![];
+    for (i, clause) in clauses.into_iter().enumerate() {
+        let MergeClause {
+            matched: clause_matched,
+            condition,
+            action,
+        } = clause;
+        let action_id = i32::try_from(i + 1).expect("reasonable number of clauses");
+        let mut guard = if clause_matched {
+            matched.clone()
+        } else {
+            matched.clone().not()
+        };
+        if let Some(condition) = condition {
+            let condition = plan_expr(ecx, &condition)?.type_as(ecx, &ScalarType::Bool)?;
+            guard = guard.and(condition);
+        }
+        guards.push((guard, action_id));
+
+        match action {
+            MergeAction::Update { assignments } => {
+                let mut sets = plan_assignments(&ecx.with_name("SET clause"), &desc, assignments)?;
+                for (col, values) in new_values.iter_mut().enumerate() {
+                    let value = sets
+                        .remove(&col)
+                        .unwrap_or_else(|| HirScalarExpr::column(source_arity + col));
+                    values.push((action_id, value));
+                }
+                retract_actions.push(action_id);
+                insert_actions.push(action_id);
+            }
+            MergeAction::Delete => retract_actions.push(action_id),
+            MergeAction::Insert { columns, values } => {
+                let columns: Vec<_> = if columns.is_empty() {
+                    desc.iter_names().cloned().collect()
+                } else {
+                    columns.into_iter().map(normalize::column_name).collect()
+                };
+                if let Some(dup) = columns.iter().duplicates().next() {
+                    sql_bail!("column {} specified more than once", dup.as_str().quoted());
+                }
+                if values.len() > columns.len() {
+                    sql_bail!("INSERT has more expressions than target columns");
+                }
+                if values.len() < columns.len() {
+                    sql_bail!("INSERT has more target columns than expressions");
+                }
+                let mut inserts = BTreeMap::new();
+                for (name, value) in columns.iter().zip_eq(values) {
+                    let Some((idx, typ)) = desc.get_by_name(name) else {
+                        sql_bail!(
+                            "column {} of relation {} does not exist",
+                            name.as_str().quoted(),
+                            table.name().item.as_str().quoted()
+                        );
+                    };
+                    let expr = plan_expr(&ecx.with_name("VALUES"), &value)?.cast_to(
+                        ecx,
+                        CastContext::Assignment,
+                        &typ.scalar_type,
+                    )?;
+                    inserts.insert(idx, expr);
+                }
+                for (col, (values, typ)) in new_values.iter_mut().zip(desc.iter_types()).enumerate()
+                {
+                    let value = match inserts.remove(&col) {
+                        Some(value) => value,
+                        None => {
+                            if plan_sequence_default(scx, table, col, &defaults[col])?.is_some() {
+                                bail_unsupported!("MERGE omitting columns that default to nextval");
+                            }
+                            plan_default_expr(scx, &defaults[col], &typ.scalar_type)?
+                        }
+                    };
+                    values.push((action_id, value));
+                }
+                insert_actions.push(action_id);
+            }
+            MergeAction::DoNothing => {}
+        }
+    }
+
+    let action = guards.into_iter().rev().fold(
+        HirScalarExpr::literal(Datum::Int32(0), ScalarType::Int32),
+        |els, (guard, action_id)| HirScalarExpr::If {
+            cond: Box::new(guard),
+            then: Box::new(HirScalarExpr::literal(
+                Datum::Int32(action_id),
+                ScalarType::Int32,
+            )),
+            els: Box::new(els),
+        },
+    );
+    let joined = joined.map(vec![action]);
+    let is_action = |action_id: i32| {
+        HirScalarExpr::column(action_column).call_binary(
+            HirScalarExpr::literal(Datum::Int32(action_id), ScalarType::Int32),
+            BinaryFunc::Eq,
+        )
+    };
+
+    let old = (0..arity)
+        .map(|col| HirScalarExpr::column(source_arity + col))
+        .collect();
+    let new = new_values
+        .into_iter()
+        .zip(desc.iter_types())
+        .map(|(values, typ)| {
+            values.into_iter().rev().fold(
+                HirScalarExpr::literal_null(typ.scalar_type.clone()),
+                |els, (action_id, value)| HirScalarExpr::If {
+                    cond: Box::new(is_action(action_id)),
+                    then: Box::new(value),
+                    els: Box::new(els),
+                },
+            )
+        })
+        .collect();
+    let retract = HirScalarExpr::variadic_or(retract_actions.into_iter().map(is_action).collect());
+    let insert = HirScalarExpr::variadic_or(insert_actions.into_iter().map(is_action).collect());
+    Ok(plan_upsert_selection(id, joined, old, new, retract, insert))
+}
+
+/// Appends a column to `scope` that can't be referenced, standing in for the
+/// marker column that records whether a row of the mutated table was matched.
+fn with_match_marker(mut scope: Scope) -> Scope {
+    let mut marker = ScopeItem::empty();
+    marker.allow_unqualified_references = false;
+    scope.items.push(marker);
+    scope
+}
+
+/// Builds the selection of a read-then-write that may both retract existing
+/// rows of the table and insert new ones.
+///
+/// Each row of the selection describes one affected row, and has the shape
+/// `[old..., new..., retract, insert]`: if `retract` is true the existing row
+/// `old` is retracted, and if `insert` is true the row `new` is inserted. Rows
+/// that do neither are filtered out.
+fn plan_upsert_selection(
+    id: GlobalId,
+    input: HirRelationExpr,
+    old: Vec<HirScalarExpr>,
+    new: Vec<HirScalarExpr>,
+    retract: HirScalarExpr,
+    insert: HirScalarExpr,
+) -> ReadThenWritePlan {
+    let input_arity = input.arity();
+    let arity = old.len();
+    let selection = input
+        .map(
+            old.into_iter()
+                .chain(new)
+                .chain([retract, insert])
+                .collect(),
+        )
+        .filter(vec![HirScalarExpr::column(input_arity + 2 * arity)
+            .or(HirScalarExpr::column(input_arity + 2 * arity + 1))])
+        .project((input_arity..input_arity + 2 * arity + 2).collect());
+    ReadThenWritePlan {
         id,
-        selection: get,
-        finishing,
-        assignments: sets,
-    })
+        selection,
+        finishing: RowSetFinishing {
+            order_by: vec![],
+            limit: None,
+            offset: 0,
+            project: (0..2 * arity + 2).collect(),
+        },
+        assignments: BTreeMap::new(),
+    }
 }
 
 // Adjust `get` to perform an existential subquery on `using` accounting for
@@ -854,6 +1324,33 @@ pub fn plan_up_to(
         .lower_uncorrelated()
 }
 
+/// Plans a time in a `REFRESH AT` or `REFRESH EVERY ... ALIGNED TO` option of a
+/// materialized view. The resulting expression has type `mz_timestamp`.
+pub fn plan_refresh_time(
+    scx: &StatementContext,
+    mut time: Expr<Aug>,
+) -> Result<MirScalarExpr, PlanError> {
+    let scope = Scope::empty();
+    let desc = RelationDesc::empty();
+    // The refresh time is evaluated only once, when the materialized view is
+    // created.
+    let qcx = QueryContext::root(scx, QueryLifetime::OneShot);
+    transform_ast::transform(scx, &mut time)?;
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "REFRESH",
+        scope: &scope,
+        relation_type: desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_parameters: false,
+        allow_windows: false,
+    };
+    plan_expr(ecx, &time)?
+        .cast_to(ecx, CastContext::Assignment, &ScalarType::MzTimestamp)?
+        .lower_uncorrelated()
+}
+
 /// Plans an expression in the AS OF position of a `SELECT` or `SUBSCRIBE` statement.
 pub fn plan_as_of(
     scx: &StatementContext,
@@ -1095,6 +1592,101 @@ pub fn plan_default_expr(
     Ok(hir)
 }
 
+/// Returns the sequence that supplies the values of column `col` of `table`
+/// when the column is omitted from a write, if any.
+///
+/// Identity columns draw their values from the sequence they own. Other
+/// columns do so if their `default` is a call to `nextval`.
+pub fn plan_sequence_default(
+    scx: &StatementContext,
+    table: &dyn CatalogItem,
+    col: usize,
+    default: &Expr<Aug>,
+) -> Result<Option<GlobalId>, PlanError> {
+    let is_identity = table
+        .table_identity_columns()
+        .map_or(false, |columns| columns.contains(&col));
+    if !is_identity {
+        return nextval_default(scx, default);
+    }
+    for id in table.used_by() {
+        if let Some((_, Some(owner))) = scx.get_item(id).sequence_details() {
+            if owner == (table.id(), col) {
+                return Ok(Some(*id));
+            }
+        }
+    }
+    sql_bail!(
+        "sequence of identity column {} of table {} does not exist",
+        col + 1,
+        scx.catalog.resolve_full_name(table.name())
+    )
+}
+
+/// Resolves the sequence named in `expr`, if `expr` is a default of the form
+/// `nextval('<sequence>')`.
+///
+/// Such defaults cannot be planned as expressions, as `nextval` has side
+/// effects, and are instead applied when sequencing the write.
+pub fn nextval_default(
+    scx: &StatementContext,
+    expr: &Expr<Aug>,
+) -> Result<Option<GlobalId>, PlanError> {
+    let Expr::Function(Function {
+        name: ResolvedItemName::Item { full_name, .. },
+        args: FunctionArgs::Args { args, order_by },
+        filter: None,
+        over: None,
+        distinct: false,
+    }) = expr
+    else {
+        return Ok(None);
+    };
+    if full_name.schema != PG_CATALOG_SCHEMA || full_name.item != "nextval" || !order_by.is_empty()
+    {
+        return Ok(None);
+    }
+    let arg = match &args[..] {
+        [Expr::Cast { expr, .. }] => &**expr,
+        [arg] => arg,
+        _ => return Ok(None),
+    };
+    match arg {
+        Expr::Value(Value::String(name)) => Ok(Some(resolve_sequence(scx, name)?)),
+        _ => bail_unsupported!("nextval defaults with non-literal arguments"),
+    }
+}
+
+/// Plans the expression of a `CHECK` constraint on a table described by
+/// `desc`. Rows of the table violate the constraint if the expression
+/// evaluates to `false`.
+pub fn plan_check_expr(
+    scx: &StatementContext,
+    expr: &Expr<Aug>,
+    desc: &RelationDesc,
+) -> Result<MirScalarExpr, PlanError> {
+    let mut expr = expr.clone();
+    transform_ast::transform(scx, &mut expr)?;
+    let qcx = QueryContext::root(scx, QueryLifetime::OneShot);
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "CHECK constraint",
+        scope: &Scope::from_source(None, desc.iter_names().cloned()),
+        relation_type: desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_parameters: false,
+        allow_windows: false,
+    };
+    let expr = plan_expr(ecx, &expr)?
+        .type_as(ecx, &ScalarType::Bool)?
+        .lower_uncorrelated()?;
+    if expr.contains_unmaterializable() {
+        sql_bail!("CHECK constraints cannot call unmaterializable functions");
+    }
+    Ok(expr)
+}
+
 pub fn plan_params<'a>(
     scx: &'a StatementContext,
     params: Vec<Expr<Aug>>,
@@ -1299,6 +1891,31 @@ fn plan_query_inner(
                 }
             }
         }
+        CteBlock::Recursive(ctes) => {
+            // Each self-referencing binding becomes its own `LetRec`.
+            for ((id, value, shadowed_val), cte) in cte_bindings.into_iter().zip(ctes).rev() {
+                if let Some(cte_desc) = qcx.ctes.remove(&id) {
+                    result = if count_cte_references(&cte.query, id) > 0 {
+                        let typ = cte_desc.desc.typ().clone();
+                        HirRelationExpr::LetRec {
+                            limit: None,
+                            bindings: vec![(cte_desc.name, id, value, typ)],
+                            body: Box::new(result),
+                        }
+                    } else {
+                        HirRelationExpr::Let {
+                            name: cte_desc.name,
+                            id,
+                            value: Box::new(value),
+                            body: Box::new(result),
+                        }
+                    };
+                }
+                if let Some(shadowed_val) = shadowed_val {
+                    qcx.ctes.insert(id, shadowed_val);
+                }
+            }
+        }
         CteBlock::MutuallyRecursive(MutRecBlock { options, ctes: _ }) => {
             let MutRecBlockOptionExtracted {
                 recursion_limit,
@@ -1335,130 +1952,757 @@ fn plan_query_inner(
                     body: Box::new(result),
                 }
             }
-        }
+        }
+    }
+
+    Ok((result, scope, finishing, group_size_hints))
+}
+
+generate_extracted_config!(
+    MutRecBlockOption,
+    (RecursionLimit, u64),
+    (ReturnAtRecursionLimit, u64),
+    (ErrorAtRecursionLimit, u64)
+);
+
+/// Creates plans for CTEs and introduces them to `qcx.ctes`.
+///
+/// Returns for each identifier a planned `HirRelationExpr` value, and an optional
+/// shadowed value that can be reinstalled once the planning has completed.
+pub fn plan_ctes(
+    qcx: &mut QueryContext,
+    q: &Query<Aug>,
+) -> Result<Vec<(LocalId, HirRelationExpr, Option<CteDesc>)>, PlanError> {
+    // Accumulate planned expressions and shadowed descriptions.
+    let mut result = Vec::new();
+    // Retain the old descriptions of CTE bindings so that we can restore them
+    // after we're done planning this SELECT.
+    let mut shadowed_descs = BTreeMap::new();
+
+    // A reused identifier indicates a reused name.
+    if let Some(ident) = q.ctes.bound_identifiers().duplicates().next() {
+        sql_bail!(
+            "WITH query name {} specified more than once",
+            normalize::ident_ref(ident).quoted()
+        )
+    }
+
+    match &q.ctes {
+        CteBlock::Simple(ctes) => {
+            // Plan all CTEs, introducing the types for non-recursive CTEs as we go.
+            for cte in ctes.iter() {
+                let cte_name = normalize::ident(cte.alias.name.clone());
+                let (val, scope) = plan_nested_query(qcx, &cte.query)?;
+                let typ = qcx.relation_type(&val);
+                let mut desc = RelationDesc::new(typ, scope.column_names());
+                plan_utils::maybe_rename_columns(
+                    format!("CTE {}", cte.alias.name),
+                    &mut desc,
+                    &cte.alias.columns,
+                )?;
+                // Capture the prior value if it exists, so that it can be re-installed.
+                let shadowed = qcx.ctes.insert(
+                    cte.id,
+                    CteDesc {
+                        name: cte_name,
+                        desc,
+                    },
+                );
+
+                result.push((cte.id, val, shadowed));
+            }
+        }
+        CteBlock::Recursive(ctes) => {
+            // Plan all CTEs in order, as for a simple block, except that those
+            // that refer to themselves are planned as recursive bindings.
+            for cte in ctes.iter() {
+                if count_cte_references(&cte.query, cte.id) > 0 {
+                    qcx.scx
+                        .require_feature_flag(&vars::ENABLE_WITH_MUTUALLY_RECURSIVE)?;
+                    let (val, shadowed) = plan_recursive_cte(qcx, cte)?;
+                    result.push((cte.id, val, shadowed));
+                    continue;
+                }
+                if cte.search.is_some() || cte.cycle.is_some() {
+                    sql_bail!(
+                        "WITH query {} has a SEARCH or CYCLE clause but is not recursive",
+                        normalize::ident_ref(&cte.alias.name).quoted()
+                    );
+                }
+                let cte_name = normalize::ident(cte.alias.name.clone());
+                let (val, scope) = plan_nested_query(qcx, &cte.query)?;
+                let typ = qcx.relation_type(&val);
+                let mut desc = RelationDesc::new(typ, scope.column_names());
+                plan_utils::maybe_rename_columns(
+                    format!("CTE {}", cte.alias.name),
+                    &mut desc,
+                    &cte.alias.columns,
+                )?;
+                let shadowed = qcx.ctes.insert(
+                    cte.id,
+                    CteDesc {
+                        name: cte_name,
+                        desc,
+                    },
+                );
+
+                result.push((cte.id, val, shadowed));
+            }
+        }
+        CteBlock::MutuallyRecursive(MutRecBlock { options: _, ctes }) => {
+            qcx.scx
+                .require_feature_flag(&vars::ENABLE_WITH_MUTUALLY_RECURSIVE)?;
+
+            // Insert column types into `qcx.ctes` first for recursive bindings.
+            for cte in ctes.iter() {
+                let cte_name = normalize::ident(cte.name.clone());
+                let mut desc_columns = Vec::with_capacity(cte.columns.capacity());
+                for column in cte.columns.iter() {
+                    desc_columns.push((
+                        normalize::column_name(column.name.clone()),
+                        ColumnType {
+                            scalar_type: scalar_type_from_sql(qcx.scx, &column.data_type)?,
+                            nullable: true,
+                        },
+                    ));
+                }
+                let desc = RelationDesc::from_names_and_types(desc_columns);
+                let shadowed = qcx.ctes.insert(
+                    cte.id,
+                    CteDesc {
+                        name: cte_name,
+                        desc,
+                    },
+                );
+                // Capture the prior value if it exists, so that it can be re-installed.
+                if let Some(shadowed) = shadowed {
+                    shadowed_descs.insert(cte.id, shadowed);
+                }
+            }
+
+            // Plan all CTEs and validate the proposed types.
+            for cte in ctes.iter() {
+                let cte_name = normalize::ident(cte.name.clone());
+                let (val, _scope) = plan_nested_query(qcx, &cte.query)?;
+                // Validate that the derived and proposed types are the same.
+                let typ = qcx.relation_type(&val);
+                // TODO: Use implicit casts to convert among types rather than error.
+                if !typ.subtypes(qcx.ctes[&cte.id].desc.typ()) {
+                    let declared_typ = qcx.ctes[&cte.id]
+                        .desc
+                        .typ()
+                        .column_types
+                        .iter()
+                        .map(|ty| qcx.humanize_scalar_type(&ty.scalar_type))
+                        .collect::<Vec<_>>();
+                    let inferred_typ = typ
+                        .column_types
+                        .iter()
+                        .map(|ty| qcx.humanize_scalar_type(&ty.scalar_type))
+                        .collect::<Vec<_>>();
+                    Err(PlanError::RecursiveTypeMismatch(
+                        cte_name,
+                        declared_typ,
+                        inferred_typ,
+                    ))?;
+                }
+
+                result.push((cte.id, val, shadowed_descs.remove(&cte.id)));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Counts the references to the CTE identified by `id` within `node`.
+fn count_cte_references<'ast, N>(node: &'ast N, id: LocalId) -> usize
+where
+    N: VisitNode<'ast, Aug> + 'ast,
+{
+    struct CteReferenceCounter {
+        id: LocalId,
+        count: usize,
+    }
+
+    impl<'ast> Visit<'ast, Aug> for CteReferenceCounter {
+        fn visit_item_name(&mut self, item_name: &'ast ResolvedItemName) {
+            if let ResolvedItemName::Cte { id, .. } = item_name {
+                if *id == self.id {
+                    self.count += 1;
+                }
+            }
+        }
+    }
+
+    let mut counter = CteReferenceCounter { id, count: 0 };
+    node.visit(&mut counter);
+    counter.count
+}
+
+/// Rejects the recursive term of the recursive CTE `id` unless it is linear,
+/// i.e., unless each row of the CTE contributes to the term's result
+/// independently of the CTE's other rows. Only then is the fixpoint of the
+/// term the result of PostgreSQL's iterative evaluation. The constructs that
+/// break linearity are rejected with PostgreSQL's error messages.
+struct RecursiveTermChecker<'a> {
+    scx: &'a StatementContext<'a>,
+    id: LocalId,
+    name: &'a str,
+}
+
+impl<'a> RecursiveTermChecker<'a> {
+    fn refs<'ast, N>(&self, node: &'ast N) -> usize
+    where
+        N: VisitNode<'ast, Aug> + 'ast,
+    {
+        count_cte_references(node, self.id)
+    }
+
+    fn check_set_expr(&self, expr: &SetExpr<Aug>) -> Result<(), PlanError> {
+        match expr {
+            SetExpr::Select(select) => self.check_select(select),
+            SetExpr::Query(query) => self.check_query(query),
+            SetExpr::SetOperation {
+                op, left, right, ..
+            } => {
+                if *op != SetOperator::Union && self.refs(expr) > 0 {
+                    sql_bail!(
+                        "recursive reference to query {} must not appear within {}",
+                        self.name.quoted(),
+                        op
+                    );
+                }
+                self.check_set_expr(left)?;
+                self.check_set_expr(right)
+            }
+            SetExpr::Values(_) | SetExpr::Show(_) | SetExpr::Table(_) => Ok(()),
+        }
+    }
+
+    fn check_query(&self, query: &Query<Aug>) -> Result<(), PlanError> {
+        if self.refs(query) == 0 {
+            return Ok(());
+        }
+        if !query.order_by.is_empty() {
+            sql_bail!("ORDER BY in a recursive query is not implemented");
+        }
+        if query.limit.is_some() {
+            sql_bail!("LIMIT in a recursive query is not implemented");
+        }
+        if query.offset.is_some() {
+            sql_bail!("OFFSET in a recursive query is not implemented");
+        }
+        if let CteBlock::Simple(ctes) = &query.ctes {
+            for cte in ctes {
+                self.check_query(&cte.query)?;
+            }
+        }
+        self.check_set_expr(&query.body)
+    }
+
+    fn check_select(&self, select: &Select<Aug>) -> Result<(), PlanError> {
+        let from_refs: usize = select.from.iter().map(|twj| self.refs(twj)).sum();
+        if self.refs(select) > from_refs {
+            sql_bail!(
+                "recursive reference to query {} must not appear within a subquery",
+                self.name.quoted()
+            );
+        }
+        if from_refs == 0 {
+            return Ok(());
+        }
+        let mut aggregates = RecursiveTermAggregateFinder {
+            scx: self.scx,
+            aggregate: false,
+            window: false,
+        };
+        for item in &select.projection {
+            aggregates.visit_select_item(item);
+        }
+        if let Some(having) = &select.having {
+            aggregates.visit_expr(having);
+        }
+        if aggregates.aggregate || !select.group_by.is_empty() || select.having.is_some() {
+            sql_bail!("aggregate functions are not allowed in a recursive query's recursive term");
+        }
+        if aggregates.window {
+            sql_bail!("window functions are not allowed in a recursive query's recursive term");
+        }
+        if select.distinct.is_some() {
+            sql_bail!("DISTINCT is not allowed in a recursive query's recursive term");
+        }
+        for twj in &select.from {
+            self.check_table_with_joins(twj)?;
+        }
+        Ok(())
+    }
+
+    fn check_table_with_joins(&self, twj: &TableWithJoins<Aug>) -> Result<(), PlanError> {
+        self.check_table_factor(&twj.relation)?;
+        let mut left_refs = self.refs(&twj.relation);
+        for join in &twj.joins {
+            self.check_table_factor(&join.relation)?;
+            let right_refs = self.refs(&join.relation);
+            let nullable_refs = match &join.join_operator {
+                JoinOperator::Inner(_) | JoinOperator::CrossJoin => 0,
+                JoinOperator::LeftOuter(_) => right_refs,
+                JoinOperator::RightOuter(_) => left_refs,
+                JoinOperator::FullOuter(_) => left_refs + right_refs,
+            };
+            if nullable_refs > 0 {
+                sql_bail!(
+                    "recursive reference to query {} must not appear within an outer join",
+                    self.name.quoted()
+                );
+            }
+            left_refs += right_refs;
+        }
+        Ok(())
+    }
+
+    fn check_table_factor(&self, factor: &TableFactor<Aug>) -> Result<(), PlanError> {
+        match factor {
+            TableFactor::Table { .. } => Ok(()),
+            TableFactor::Derived { subquery, .. } => self.check_query(subquery),
+            TableFactor::NestedJoin { join, .. } => self.check_table_with_joins(join),
+            TableFactor::Function { .. } | TableFactor::RowsFrom { .. } => {
+                if self.refs(factor) > 0 {
+                    sql_bail!(
+                        "recursive reference to query {} must not appear within a subquery",
+                        self.name.quoted()
+                    );
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Finds the aggregate and window function calls of a `SELECT`, outside of
+/// its subqueries.
+struct RecursiveTermAggregateFinder<'a> {
+    scx: &'a StatementContext<'a>,
+    aggregate: bool,
+    window: bool,
+}
+
+impl<'a> Visit<'_, Aug> for RecursiveTermAggregateFinder<'a> {
+    fn visit_function(&mut self, func: &Function<Aug>) {
+        if func.over.is_some() {
+            self.window = true;
+        } else if let Ok(item) = self.scx.get_item_by_resolved_name(&func.name) {
+            if let Ok(Func::Aggregate { .. }) = item.func() {
+                self.aggregate = true;
+            }
+        }
+        visit::visit_function(self, func);
+    }
+
+    fn visit_query(&mut self, _query: &Query<Aug>) {
+        // Don't go into subqueries.
+    }
+}
+
+/// Plans a CTE of a `WITH RECURSIVE` block that refers to itself, and
+/// introduces it to `qcx.ctes`.
+///
+/// The query must have the form `non_recursive_term UNION [ALL]
+/// recursive_term`, where the recursive term refers to the CTE exactly once.
+/// The columns take their types from the non-recursive term. The planned value
+/// is the whole query, to be bound by a `LetRec`: for such linear recursion,
+/// its fixpoint is the result of PostgreSQL's iterative evaluation.
+///
+/// Returns the planned value and the shadowed description, if any.
+fn plan_recursive_cte(
+    qcx: &mut QueryContext,
+    cte: &CteRecursive<Aug>,
+) -> Result<(HirRelationExpr, Option<CteDesc>), PlanError> {
+    let cte_name = normalize::ident(cte.alias.name.clone());
+    let Query {
+        ctes,
+        body,
+        order_by,
+        limit,
+        offset,
+    } = &cte.query;
+    let (all, left, right) = match body {
+        SetExpr::SetOperation {
+            op: SetOperator::Union,
+            all,
+            left,
+            right,
+        } => (*all, left, right),
+        _ => sql_bail!(
+            "recursive query {} does not have the form non-recursive-term UNION [ALL] recursive-term",
+            cte_name.quoted()
+        ),
+    };
+    if !order_by.is_empty() {
+        sql_bail!("ORDER BY in a recursive query is not implemented");
+    }
+    if limit.is_some() {
+        sql_bail!("LIMIT in a recursive query is not implemented");
+    }
+    if offset.is_some() {
+        sql_bail!("OFFSET in a recursive query is not implemented");
+    }
+    let recursive_references = count_cte_references(right.as_ref(), cte.id);
+    if count_cte_references(&cte.query, cte.id) > recursive_references {
+        sql_bail!(
+            "recursive reference to query {} must not appear within its non-recursive term",
+            cte_name.quoted()
+        );
+    }
+    if recursive_references > 1 {
+        sql_bail!(
+            "recursive reference to query {} must not appear more than once",
+            cte_name.quoted()
+        );
+    }
+    RecursiveTermChecker {
+        scx: qcx.scx,
+        id: cte.id,
+        name: cte_name.as_str(),
+    }
+    .check_set_expr(right)?;
+
+    let term_query = |body: SetExpr<Aug>| Query {
+        ctes: ctes.clone(),
+        body,
+        order_by: vec![],
+        limit: None,
+        offset: None,
+    };
+
+    // Plan the non-recursive term on its own to learn the names of the
+    // columns.
+    let (base, scope) = plan_nested_query(qcx, &term_query((**left).clone()))?;
+    let mut desc = RelationDesc::new(qcx.relation_type(&base), scope.column_names());
+    plan_utils::maybe_rename_columns(
+        format!("CTE {}", cte.alias.name),
+        &mut desc,
+        &cte.alias.columns,
+    )?;
+    let mut column_names: Vec<_> = desc.iter_names().cloned().collect();
+
+    // Expand any `SEARCH` and `CYCLE` clauses into additional columns, whose
+    // types we learn by planning the rewritten non-recursive term.
+    let (left, right) = if cte.search.is_some() || cte.cycle.is_some() {
+        let (left, right, added_columns) =
+            rewrite_search_and_cycle(cte, &column_names, left, right)?;
+        column_names.extend(added_columns);
+        let (base, _scope) = plan_nested_query(qcx, &term_query(left.clone()))?;
+        desc = RelationDesc::new(qcx.relation_type(&base), column_names.clone());
+        (left, right)
+    } else {
+        ((**left).clone(), (**right).clone())
+    };
+    let query = term_query(SetExpr::SetOperation {
+        op: SetOperator::Union,
+        all,
+        left: Box::new(left),
+        right: Box::new(right),
+    });
+
+    let mut column_types: Vec<_> = desc
+        .typ()
+        .column_types
+        .iter()
+        .map(|typ| typ.scalar_type.clone().nullable(true))
+        .collect();
+    let shadowed = qcx.ctes.insert(
+        cte.id,
+        CteDesc {
+            name: cte_name.clone(),
+            desc: RelationDesc::new(
+                RelationType::new(column_types.clone()),
+                column_names.clone(),
+            ),
+        },
+    );
+
+    // The whole query may only differ from the non-recursive term in the
+    // nullability of nested fields and in type modifiers. Widen the types of
+    // the CTE until they agree, which terminates as widening only ever goes
+    // one way.
+    loop {
+        let (val, _scope) = plan_nested_query(qcx, &query)?;
+        let typ = qcx.relation_type(&val);
+        let mut widened = false;
+        for (i, (declared, inferred)) in column_types.iter_mut().zip(typ.column_types).enumerate() {
+            if declared.scalar_type == inferred.scalar_type {
+                continue;
+            }
+            if !declared.scalar_type.base_eq(&inferred.scalar_type) {
+                sql_bail!(
+                    "recursive query {} column {} has type {} in non-recursive term but type {} overall",
+                    cte_name.quoted(),
+                    i + 1,
+                    qcx.humanize_scalar_type(&declared.scalar_type),
+                    qcx.humanize_scalar_type(&inferred.scalar_type),
+                );
+            }
+            declared.scalar_type = inferred.scalar_type;
+            widened = true;
+        }
+        if !widened {
+            return Ok((val, shadowed));
+        }
+        qcx.ctes.insert(
+            cte.id,
+            CteDesc {
+                name: cte_name.clone(),
+                desc: RelationDesc::new(
+                    RelationType::new(column_types.clone()),
+                    column_names.clone(),
+                ),
+            },
+        );
     }
-
-    Ok((result, scope, finishing, group_size_hints))
 }
 
-generate_extracted_config!(
-    MutRecBlockOption,
-    (RecursionLimit, u64),
-    (ReturnAtRecursionLimit, u64),
-    (ErrorAtRecursionLimit, u64)
-);
-
-/// Creates plans for CTEs and introduces them to `qcx.ctes`.
+/// Expands the `SEARCH` and `CYCLE` clauses of a recursive CTE into columns
+/// computed by its non-recursive and recursive terms, as PostgreSQL does.
 ///
-/// Returns for each identifier a planned `HirRelationExpr` value, and an optional
-/// shadowed value that can be reinstalled once the planning has completed.
-pub fn plan_ctes(
-    qcx: &mut QueryContext,
-    q: &Query<Aug>,
-) -> Result<Vec<(LocalId, HirRelationExpr, Option<CteDesc>)>, PlanError> {
-    // Accumulate planned expressions and shadowed descriptions.
-    let mut result = Vec::new();
-    // Retain the old descriptions of CTE bindings so that we can restore them
-    // after we're done planning this SELECT.
-    let mut shadowed_descs = BTreeMap::new();
-
-    // A reused identifier indicates a reused name.
-    if let Some(ident) = q.ctes.bound_identifiers().duplicates().next() {
-        sql_bail!(
-            "WITH query name {} specified more than once",
-            normalize::ident_ref(ident).quoted()
-        )
-    }
-
-    match &q.ctes {
-        CteBlock::Simple(ctes) => {
-            // Plan all CTEs, introducing the types for non-recursive CTEs as we go.
-            for cte in ctes.iter() {
-                let cte_name = normalize::ident(cte.alias.name.clone());
-                let (val, scope) = plan_nested_query(qcx, &cte.query)?;
-                let typ = qcx.relation_type(&val);
-                let mut desc = RelationDesc::new(typ, scope.column_names());
-                plan_utils::maybe_rename_columns(
-                    format!("CTE {}", cte.alias.name),
-                    &mut desc,
-                    &cte.alias.columns,
-                )?;
-                // Capture the prior value if it exists, so that it can be re-installed.
-                let shadowed = qcx.ctes.insert(
-                    cte.id,
-                    CteDesc {
-                        name: cte_name,
-                        desc,
-                    },
+/// Both terms are wrapped in a `SELECT` that appends the new columns. For the
+/// recursive term, the values of the new columns in the row of the CTE that
+/// produced each row are threaded through the term, which thus must be a
+/// `SELECT` that refers to the CTE in its `FROM` clause. A `CYCLE` clause also
+/// stops the recursive term at rows that are marked as closing a cycle.
+///
+/// Returns the rewritten terms and the names of the new columns.
+fn rewrite_search_and_cycle(
+    cte: &CteRecursive<Aug>,
+    column_names: &[ColumnName],
+    left: &SetExpr<Aug>,
+    right: &SetExpr<Aug>,
+) -> Result<(SetExpr<Aug>, SetExpr<Aug>, Vec<ColumnName>), PlanError> {
+    let cte_name = normalize::ident_ref(&cte.alias.name);
+    let arity = column_names.len();
+
+    let column_positions = |clause: &str, columns: &[Ident]| {
+        let mut positions = Vec::with_capacity(columns.len());
+        for column in columns {
+            let name = normalize::column_name(column.clone());
+            let Some(position) = column_names.iter().position(|n| *n == name) else {
+                sql_bail!(
+                    "{} column {} not in WITH query column list",
+                    clause,
+                    name.as_str().quoted()
+                );
+            };
+            if positions.contains(&position) {
+                sql_bail!(
+                    "{} column {} specified more than once",
+                    clause,
+                    name.as_str().quoted()
                 );
-
-                result.push((cte.id, val, shadowed));
             }
+            positions.push(position);
         }
-        CteBlock::MutuallyRecursive(MutRecBlock { options: _, ctes }) => {
-            qcx.scx
-                .require_feature_flag(&vars::ENABLE_WITH_MUTUALLY_RECURSIVE)?;
+        Ok::<_, PlanError>(positions)
+    };
 
-            // Insert column types into `qcx.ctes` first for recursive bindings.
-            for cte in ctes.iter() {
-                let cte_name = normalize::ident(cte.name.clone());
-                let mut desc_columns = Vec::with_capacity(cte.columns.capacity());
-                for column in cte.columns.iter() {
-                    desc_columns.push((
-                        normalize::column_name(column.name.clone()),
-                        ColumnType {
-                            scalar_type: scalar_type_from_sql(qcx.scx, &column.data_type)?,
-                            nullable: true,
-                        },
-                    ));
-                }
-                let desc = RelationDesc::from_names_and_types(desc_columns);
-                let shadowed = qcx.ctes.insert(
-                    cte.id,
-                    CteDesc {
-                        name: cte_name,
-                        desc,
-                    },
-                );
-                // Capture the prior value if it exists, so that it can be re-installed.
-                if let Some(shadowed) = shadowed {
-                    shadowed_descs.insert(cte.id, shadowed);
-                }
+    // The names of the new columns, which must be new and distinct.
+    let mut added_columns: Vec<(&str, ColumnName)> = vec![];
+    let mut add_column = |kind: &'static str, name: &Ident| {
+        let name = normalize::column_name(name.clone());
+        if column_names.contains(&name) {
+            sql_bail!(
+                "{} name {} already used in WITH query column list",
+                kind,
+                name.as_str().quoted()
+            );
+        }
+        if let Some((other_kind, _)) = added_columns.iter().find(|(_, other)| *other == name) {
+            sql_bail!("{} name and {} name are the same", other_kind, kind);
+        }
+        added_columns.push((kind, name));
+        Ok::<_, PlanError>(added_columns.len() - 1)
+    };
+    let search = match &cte.search {
+        Some(search) => {
+            let positions = column_positions("search", &search.columns)?;
+            let index = add_column("search sequence column", &search.sequence_column)?;
+            Some((search.order, positions, index))
+        }
+        None => None,
+    };
+    let cycle = match &cte.cycle {
+        Some(cycle) => {
+            let positions = column_positions("cycle", &cycle.columns)?;
+            let mark_index = add_column("cycle mark column", &cycle.mark_column)?;
+            let path_index = add_column("cycle path column", &cycle.path_column)?;
+            let mark_value = cycle.mark_value.clone().unwrap_or(Value::Boolean(true));
+            let mark_default = cycle.mark_default.clone().unwrap_or(Value::Boolean(false));
+            Some((positions, mark_index, path_index, mark_value, mark_default))
+        }
+        None => None,
+    };
+    let added_columns: Vec<_> = added_columns.into_iter().map(|(_, name)| name).collect();
+
+    // Both terms are wrapped as `SELECT ... FROM (term) AS term (column1, ...)`.
+    let term_name = Ident::new("term");
+    let term_columns: Vec<_> = (1..=arity + added_columns.len())
+        .map(|i| Ident::new(format!("column{}", i)))
+        .collect();
+    let term_column = |i: usize| Expr::Identifier(vec![term_name.clone(), term_columns[i].clone()]);
+    let term_row = |positions: &[usize]| Expr::Row {
+        exprs: positions.iter().map(|i| term_column(*i)).collect(),
+    };
+    let concat = |left: Expr<Aug>, right: Expr<Aug>| Expr::Op {
+        op: Op::bare("||"),
+        expr1: Box::new(left),
+        expr2: Some(Box::new(right)),
+    };
+    let wrap = |term: SetExpr<Aug>, columns: usize, projection: Vec<Expr<Aug>>| {
+        SetExpr::Select(Box::new(Select {
+            distinct: None,
+            projection: projection
+                .into_iter()
+                .map(|expr| SelectItem::Expr { expr, alias: None })
+                .collect(),
+            from: vec![TableWithJoins {
+                relation: TableFactor::Derived {
+                    lateral: false,
+                    subquery: Box::new(Query {
+                        ctes: CteBlock::empty(),
+                        body: term,
+                        order_by: vec![],
+                        limit: None,
+                        offset: None,
+                    }),
+                    alias: Some(TableAlias {
+                        name: term_name.clone(),
+                        columns: term_columns[..columns].to_vec(),
+                        strict: true,
+                    }),
+                },
+                joins: vec![],
+            }],
+            selection: None,
+            group_by: vec![],
+            having: None,
+            options: vec![],
+        }))
+    };
+
+    // The non-recursive term initializes the new columns.
+    let mut projection: Vec<_> = (0..arity).map(term_column).collect();
+    if let Some((order, positions, _)) = &search {
+        projection.push(match order {
+            CteSearchOrder::DepthFirst => Expr::Array(vec![term_row(positions)]),
+            CteSearchOrder::BreadthFirst => {
+                let mut exprs = vec![Expr::Value(Value::Number("0".into()))];
+                exprs.extend(positions.iter().map(|i| term_column(*i)));
+                Expr::Row { exprs }
             }
+        });
+    }
+    if let Some((positions, _, _, _, mark_default)) = &cycle {
+        projection.push(Expr::Value(mark_default.clone()));
+        projection.push(Expr::Array(vec![term_row(positions)]));
+    }
+    let left = wrap(left.clone(), arity, projection);
 
-            // Plan all CTEs and validate the proposed types.
-            for cte in ctes.iter() {
-                let cte_name = normalize::ident(cte.name.clone());
-                let (val, _scope) = plan_nested_query(qcx, &cte.query)?;
-                // Validate that the derived and proposed types are the same.
-                let typ = qcx.relation_type(&val);
-                // TODO: Use implicit casts to convert among types rather than error.
-                if !typ.subtypes(qcx.ctes[&cte.id].desc.typ()) {
-                    let declared_typ = qcx.ctes[&cte.id]
-                        .desc
-                        .typ()
-                        .column_types
-                        .iter()
-                        .map(|ty| qcx.humanize_scalar_type(&ty.scalar_type))
-                        .collect::<Vec<_>>();
-                    let inferred_typ = typ
-                        .column_types
-                        .iter()
-                        .map(|ty| qcx.humanize_scalar_type(&ty.scalar_type))
-                        .collect::<Vec<_>>();
-                    Err(PlanError::RecursiveTypeMismatch(
-                        cte_name,
-                        declared_typ,
-                        inferred_typ,
-                    ))?;
-                }
+    // The recursive term passes along the new columns of the row of the CTE
+    // that it refers to.
+    let SetExpr::Select(select) = right else {
+        sql_bail!("with a SEARCH or CYCLE clause, the right side of the UNION must be a SELECT");
+    };
+    let reference = select
+        .from
+        .iter()
+        .flat_map(|from| iter::once(&from.relation).chain(from.joins.iter().map(|j| &j.relation)))
+        .find_map(|relation| match relation {
+            TableFactor::Table {
+                name: ResolvedItemName::Cte { id, name },
+                alias,
+            } if *id == cte.id => Some((name, alias)),
+            _ => None,
+        });
+    let Some((reference_name, reference_alias)) = reference else {
+        sql_bail!(
+            "with a SEARCH or CYCLE clause, the recursive reference to WITH query {} must be at the top level of its right-hand SELECT",
+            cte_name.quoted()
+        );
+    };
+    let reference_column = |i: usize| {
+        let (table, column) = match reference_alias {
+            Some(alias) => (alias.name.clone(), alias.columns.get(arity + i).cloned()),
+            None => (Ident::new(reference_name.as_str()), None),
+        };
+        let column = column.unwrap_or_else(|| Ident::new(added_columns[i].as_str()));
+        Expr::Identifier(vec![table, column])
+    };
+    let mut select = select.clone();
+    select
+        .projection
+        .extend((0..added_columns.len()).map(|i| SelectItem::Expr {
+            expr: reference_column(i),
+            alias: None,
+        }));
+    if let Some((_, mark_index, _, mark_value, _)) = &cycle {
+        let not_cycle = Expr::Op {
+            op: Op::bare("<>"),
+            expr1: Box::new(reference_column(*mark_index)),
+            expr2: Some(Box::new(Expr::Value(mark_value.clone()))),
+        };
+        select.selection = Some(match select.selection.take() {
+            Some(selection) => Expr::And {
+                left: Box::new(selection),
+                right: Box::new(not_cycle),
+            },
+            None => not_cycle,
+        });
+    }
 
-                result.push((cte.id, val, shadowed_descs.remove(&cte.id)));
+    // ...and computes the new columns from them.
+    let mut projection: Vec<_> = (0..arity).map(term_column).collect();
+    if let Some((order, positions, index)) = &search {
+        let previous = term_column(arity + index);
+        projection.push(match order {
+            CteSearchOrder::DepthFirst => concat(previous, Expr::Array(vec![term_row(positions)])),
+            CteSearchOrder::BreadthFirst => {
+                let depth = Expr::Op {
+                    op: Op::bare("+"),
+                    expr1: Box::new(Expr::FieldAccess {
+                        expr: Box::new(previous),
+                        field: Ident::new("f1"),
+                    }),
+                    expr2: Some(Box::new(Expr::Value(Value::Number("1".into())))),
+                };
+                let mut exprs = vec![depth];
+                exprs.extend(positions.iter().map(|i| term_column(*i)));
+                Expr::Row { exprs }
             }
-        }
+        });
+    }
+    if let Some((positions, _, path_index, mark_value, mark_default)) = &cycle {
+        let previous_path = term_column(arity + path_index);
+        projection.push(Expr::Case {
+            operand: None,
+            conditions: vec![Expr::AnyExpr {
+                left: Box::new(term_row(positions)),
+                op: Op::bare("="),
+                right: Box::new(previous_path.clone()),
+            }],
+            results: vec![Expr::Value(mark_value.clone())],
+            else_result: Some(Box::new(Expr::Value(mark_default.clone()))),
+        });
+        projection.push(concat(
+            previous_path,
+            Expr::Array(vec![term_row(positions)]),
+        ));
     }
+    let right = wrap(
+        SetExpr::Select(select),
+        arity + added_columns.len(),
+        projection,
+    );
 
-    Ok(result)
+    Ok((left, right, added_columns))
 }
 
 pub fn plan_nested_query(
@@ -1863,6 +3107,61 @@ fn plan_values_insert(
     })
 }
 
+/// Handles the `DEFAULT` keywords in the rows of the `VALUES` list of an
+/// `INSERT`, which are parsed as references to a column named `default`. As
+/// `VALUES` lists have no columns, such references are otherwise invalid.
+///
+/// A target column that is `DEFAULT` in every row is removed from `columns`,
+/// `ordering`, `source_types` and `values`, so that it is filled in like an
+/// omitted column. In other columns, `DEFAULT` is replaced by the column's
+/// default expression, which isn't supported if the column defaults to the
+/// next value of a sequence.
+fn plan_values_insert_defaults(
+    scx: &StatementContext,
+    table: &dyn CatalogItem,
+    defaults: &[Expr<Aug>],
+    columns: &mut Vec<ColumnName>,
+    ordering: &mut Vec<usize>,
+    source_types: &mut Vec<&ScalarType>,
+    values: &mut [Vec<Expr<Aug>>],
+) -> Result<(), PlanError> {
+    fn is_default(expr: Option<&Expr<Aug>>) -> bool {
+        match expr {
+            Some(Expr::Identifier(names)) => names.len() == 1 && names[0].as_str() == "default",
+            _ => false,
+        }
+    }
+
+    let width = values.iter().map(|row| row.len()).min().unwrap_or(0);
+    // Later columns are handled first, so that removing a column doesn't shift
+    // the positions of the columns that are yet to be handled.
+    for pos in (0..std::cmp::min(width, ordering.len())).rev() {
+        let column = ordering[pos];
+        if values.iter().all(|row| is_default(row.get(pos))) {
+            for row in values.iter_mut() {
+                row.remove(pos);
+            }
+            ordering.remove(pos);
+            source_types.remove(pos);
+            if !columns.is_empty() {
+                columns.remove(pos);
+            }
+        } else {
+            for row in values.iter_mut() {
+                if is_default(row.get(pos)) {
+                    if plan_sequence_default(scx, table, column, &defaults[column])?.is_some() {
+                        bail_unsupported!(
+                            "DEFAULT in only some rows of columns that default to nextval"
+                        );
+                    }
+                    row[pos] = defaults[column].clone();
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 fn plan_join_identity() -> (HirRelationExpr, Scope) {
     let typ = RelationType::new(vec![]);
     let expr = HirRelationExpr::constant(vec![vec![]], typ);
@@ -1888,9 +3187,107 @@ generate_extracted_config!(
     (ExpectedGroupSize, u64),
     (AggregateInputGroupSize, u64),
     (DistinctOnInputGroupSize, u64),
-    (LimitInputGroupSize, u64)
+    (LimitInputGroupSize, u64),
+    (JoinOrder, Vec<String>, Default(vec![])),
+    (DeltaJoin, bool, Default(false)),
+    (NoIndex, Vec<Object>, Default(vec![]))
 );
 
+/// Validates the optimizer hints in the `OPTIONS` of `s` and records them in
+/// the statement context.
+///
+/// The relations named in `JOIN ORDER` must be catalog items that appear
+/// directly in the `FROM` clause of `s`, either by their name or by their
+/// alias. The objects named in `NO INDEX` must be indexes.
+fn plan_optimizer_hints(
+    qcx: &QueryContext,
+    s: &Select<Aug>,
+    options: &SelectOptionExtracted,
+) -> Result<(), PlanError> {
+    if options.join_order.is_empty() && !options.delta_join && options.no_index.is_empty() {
+        return Ok(());
+    }
+    qcx.scx
+        .require_feature_flag(&vars::ENABLE_OPTIMIZER_HINTS)?;
+
+    fn collect_items(factor: &TableFactor<Aug>, items: &mut Vec<(String, GlobalId)>) {
+        match factor {
+            TableFactor::Table {
+                name: ResolvedItemName::Item { id, full_name, .. },
+                alias,
+            } => {
+                let name = match alias {
+                    Some(alias) => alias.name.as_str().to_string(),
+                    None => full_name.item.clone(),
+                };
+                items.push((name, *id));
+            }
+            TableFactor::NestedJoin { join, alias: None } => {
+                collect_items(&join.relation, items);
+                for join in &join.joins {
+                    collect_items(&join.relation, items);
+                }
+            }
+            _ => (),
+        }
+    }
+    let mut items = vec![];
+    for twj in &s.from {
+        collect_items(&twj.relation, &mut items);
+        for join in &twj.joins {
+            collect_items(&join.relation, &mut items);
+        }
+    }
+
+    let mut join_order = vec![];
+    for name in &options.join_order {
+        let mut matches = items.iter().filter(|(n, _)| n == name);
+        let id = match (matches.next(), matches.next()) {
+            (Some((_, id)), None) => *id,
+            (Some(_), Some(_)) => sql_bail!("JOIN ORDER relation {} is ambiguous", name.quoted()),
+            (None, _) => sql_bail!(
+                "JOIN ORDER relation {} does not name a table, view, or source in the FROM clause",
+                name.quoted()
+            ),
+        };
+        if options.join_order.iter().filter(|n| *n == name).count() > 1 {
+            sql_bail!(
+                "JOIN ORDER relation {} specified more than once",
+                name.quoted()
+            );
+        }
+        join_order.push(id);
+    }
+
+    let mut no_index = BTreeSet::new();
+    for object in &options.no_index {
+        let id = GlobalId::from(object);
+        let item = qcx.scx.get_item(&id);
+        if item.item_type() != CatalogItemType::Index {
+            sql_bail!(
+                "NO INDEX {} is not an index",
+                qcx.scx
+                    .catalog
+                    .resolve_full_name(item.name())
+                    .to_string()
+                    .quoted()
+            );
+        }
+        no_index.insert(id);
+    }
+
+    let mut hints = qcx.scx.optimizer_hints.borrow_mut();
+    if !join_order.is_empty() {
+        if !hints.join_order.is_empty() && hints.join_order != join_order {
+            sql_bail!("conflicting JOIN ORDER hints in the same statement");
+        }
+        hints.join_order = join_order;
+    }
+    hints.delta_join |= options.delta_join;
+    hints.no_index.extend(no_index);
+    Ok(())
+}
+
 /// Plans a SELECT query. The SELECT query may contain an intrusive ORDER BY clause.
 ///
 /// Normally, the ORDER BY clause occurs after the columns specified in the
@@ -1920,6 +3317,7 @@ fn plan_view_select(
 
     // Extract query options.
     let select_option_extracted = SelectOptionExtracted::try_from(s.options.clone())?;
+    plan_optimizer_hints(qcx, &s, &select_option_extracted)?;
     let group_size_hints = GroupSizeHints::try_from(select_option_extracted)?;
 
     // Step 1. Handle FROM clause, including joins.
@@ -2374,7 +3772,7 @@ fn plan_scalar_table_funcs(
     if table_funcs.len() == 1 {
         let (table_func, id) = table_funcs.iter().next().unwrap();
         let (expr, mut scope) =
-            plan_solitary_table_function(&rows_from_qcx, table_func, None, false)?;
+            plan_solitary_table_function(&rows_from_qcx, table_func, None, None, false)?;
 
         // A single table-function might return several columns as a record
         let num_cols = scope.len();
@@ -2585,8 +3983,15 @@ fn plan_table_factor(
         TableFactor::Function {
             function,
             alias,
+            column_defs,
             with_ordinality,
-        } => plan_solitary_table_function(qcx, function, alias.as_ref(), *with_ordinality),
+        } => plan_solitary_table_function(
+            qcx,
+            function,
+            alias.as_ref(),
+            column_defs.as_deref(),
+            *with_ordinality,
+        ),
 
         TableFactor::RowsFrom {
             functions,
@@ -2675,7 +4080,7 @@ fn plan_rows_from(
     // If there's only a single table function, planning proceeds as if `ROWS
     // FROM` hadn't been written at all.
     if let [function] = functions {
-        return plan_solitary_table_function(qcx, function, alias, with_ordinality);
+        return plan_solitary_table_function(qcx, function, alias, None, with_ordinality);
     }
 
     // Per PostgreSQL, all scope items take the name of the first function
@@ -2749,8 +4154,13 @@ fn plan_rows_from_internal<'a>(
     // Join together each of the table functions in turn. The last column is
     // always the column to join against and is maintained to be the coalescence
     // of the row number column for all prior functions.
-    let (mut left_expr, mut left_scope) =
-        plan_table_function_internal(qcx, functions.next().unwrap(), true, table_name.clone())?;
+    let (mut left_expr, mut left_scope) = plan_table_function_internal(
+        qcx,
+        functions.next().unwrap(),
+        true,
+        None,
+        table_name.clone(),
+    )?;
     num_cols.push(left_scope.len() - 1);
     // Create the coalesced ordinality column.
     left_expr = left_expr.map(vec![HirScalarExpr::column(left_scope.len() - 1)]);
@@ -2762,7 +4172,7 @@ fn plan_rows_from_internal<'a>(
         // The right hand side of a join must be planned in a new scope.
         let qcx = qcx.empty_derived_context();
         let (right_expr, mut right_scope) =
-            plan_table_function_internal(&qcx, function, true, table_name.clone())?;
+            plan_table_function_internal(&qcx, function, true, None, table_name.clone())?;
         num_cols.push(right_scope.len() - 1);
         let left_col = left_scope.len() - 1;
         let right_col = left_scope.len() + right_scope.len() - 1;
@@ -2804,9 +4214,11 @@ fn plan_solitary_table_function(
     qcx: &QueryContext,
     function: &Function<Aug>,
     alias: Option<&TableAlias>,
+    column_defs: Option<&[ColumnDef<Aug>]>,
     with_ordinality: bool,
 ) -> Result<(HirRelationExpr, Scope), PlanError> {
-    let (expr, mut scope) = plan_table_function_internal(qcx, function, with_ordinality, None)?;
+    let (expr, mut scope) =
+        plan_table_function_internal(qcx, function, with_ordinality, column_defs, None)?;
 
     let single_column_function = scope.len() == 1 + if with_ordinality { 1 } else { 0 };
     if single_column_function {
@@ -2862,12 +4274,26 @@ fn plan_table_function_internal(
         distinct,
     }: &Function<Aug>,
     with_ordinality: bool,
+    column_defs: Option<&[ColumnDef<Aug>]>,
     table_name: Option<FullItemName>,
 ) -> Result<(HirRelationExpr, Scope), PlanError> {
     assert!(filter.is_none(), "cannot parse table function with FILTER");
     assert!(over.is_none(), "cannot parse table function with OVER");
     assert!(!*distinct, "cannot parse table function with DISTINCT");
 
+    match (is_record_table_function(name), column_defs) {
+        (true, None) => {
+            sql_bail!("a column definition list is required for functions returning \"record\"")
+        }
+        (false, Some(_)) => {
+            sql_bail!("a column definition list is only allowed for functions returning \"record\"")
+        }
+        (true, Some(_)) if with_ordinality => {
+            sql_bail!("WITH ORDINALITY cannot be used with a column definition list")
+        }
+        _ => {}
+    }
+
     let ecx = &ExprContext {
         qcx,
         name: "table function arguments",
@@ -2906,8 +4332,17 @@ fn plan_table_function_internal(
     let (mut expr, mut scope) = match resolve_func(ecx, name, args)? {
         Func::Table(impls) => {
             let tf = func::select_impl(ecx, FuncSpec::Func(name), impls, scalar_args, vec![])?;
-            let scope = Scope::from_source(scope_name.clone(), tf.column_names);
-            (tf.expr, scope)
+            match column_defs {
+                Some(column_defs) => {
+                    let (expr, column_names) =
+                        plan_column_definition_list(qcx, name, tf.expr, column_defs)?;
+                    (expr, Scope::from_source(scope_name.clone(), column_names))
+                }
+                None => {
+                    let scope = Scope::from_source(scope_name.clone(), tf.column_names);
+                    (tf.expr, scope)
+                }
+            }
         }
         Func::Scalar(impls) => {
             let expr = func::select_impl(ecx, FuncSpec::Func(name), impls, scalar_args, vec![])?;
@@ -2956,6 +4391,64 @@ fn plan_table_function_internal(
     Ok((expr, scope))
 }
 
+/// Reports whether `name` refers to one of the builtin table functions that
+/// return `record` without declaring their output columns, which must instead
+/// be given by a column definition list.
+fn is_record_table_function(name: &ResolvedItemName) -> bool {
+    match name {
+        ResolvedItemName::Item { full_name, .. } => {
+            full_name.schema == PG_CATALOG_SCHEMA
+                && func::RECORD_TABLE_FUNCTIONS.contains(&full_name.item.as_str())
+        }
+        _ => false,
+    }
+}
+
+/// Plans the column definition list of a function returning `record`.
+///
+/// Such functions produce a single column of JSON objects, from which each of
+/// the defined columns is extracted by name.
+fn plan_column_definition_list(
+    qcx: &QueryContext,
+    name: &ResolvedItemName,
+    expr: HirRelationExpr,
+    column_defs: &[ColumnDef<Aug>],
+) -> Result<(HirRelationExpr, Vec<ColumnName>), PlanError> {
+    let relation_type = RelationType::new(vec![ScalarType::Jsonb.nullable(true)]);
+    let ecx = &ExprContext {
+        qcx,
+        name: "column definition list",
+        scope: &Scope::empty(),
+        relation_type: &relation_type,
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_parameters: false,
+        allow_windows: false,
+    };
+    let obj = func::plan_jsonb_expect_object(
+        HirScalarExpr::column(0),
+        name.full_item_name().item.as_str(),
+    );
+    let mut columns = Vec::with_capacity(column_defs.len());
+    let mut column_names = Vec::with_capacity(column_defs.len());
+    for ColumnDef {
+        name, data_type, ..
+    } in column_defs
+    {
+        let name = normalize::column_name(name.clone());
+        let ty = scalar_type_from_sql(qcx.scx, data_type)?;
+        columns.push(func::plan_jsonb_record_field(
+            ecx,
+            obj.clone(),
+            name.as_str(),
+            &ty,
+        )?);
+        column_names.push(name);
+    }
+    let outputs = (1..=columns.len()).collect();
+    Ok((expr.map(columns).project(outputs), column_names))
+}
+
 fn plan_table_alias(mut scope: Scope, alias: Option<&TableAlias>) -> Result<Scope, PlanError> {
     if let Some(TableAlias {
         name,
@@ -4636,7 +6129,14 @@ fn plan_identifier(ecx: &ExprContext, names: &[Ident]) -> Result<HirScalarExpr,
         Err(e) => return Err(e),
     }
 
-    // The name doesn't refer to a column. Check if it is a whole-row reference
+    // The name doesn't refer to a column. Check if it is a named parameter of
+    // the user-defined function whose body is being planned. As in PostgreSQL,
+    // columns take precedence over parameters of the same name.
+    if let Some(n) = ecx.qcx.scx.param_names.get(col_name.as_str()) {
+        return Ok(HirScalarExpr::Parameter(*n));
+    }
+
+    // The name doesn't refer to a parameter either. Check if it is a whole-row reference
     // to a table.
     let items = ecx.scope.items_from_table(
         &ecx.qcx.outer_scopes,
@@ -4860,11 +6360,11 @@ pub const IGNORE_NULLS_ERROR_MSG: &str =
 /// Resolves the name to a set of function implementations.
 ///
 /// If the name does not specify a known built-in function, returns an error.
-pub fn resolve_func(
-    ecx: &ExprContext,
+pub fn resolve_func<'a>(
+    ecx: &ExprContext<'a>,
     name: &ResolvedItemName,
     args: &mz_sql_parser::ast::FunctionArgs<Aug>,
-) -> Result<&'static Func, PlanError> {
+) -> Result<&'a Func, PlanError> {
     if let Ok(i) = ecx.qcx.scx.get_item_by_resolved_name(name) {
         if let Ok(f) = i.func() {
             return Ok(f);
@@ -5378,6 +6878,9 @@ fn scalar_type_from_catalog(
                 CatalogType::MzTimestamp => Ok(ScalarType::MzTimestamp),
                 CatalogType::Interval => Ok(ScalarType::Interval),
                 CatalogType::Jsonb => Ok(ScalarType::Jsonb),
+                CatalogType::JsonPath => Ok(ScalarType::JsonPath),
+                CatalogType::TsVector => Ok(ScalarType::TsVector),
+                CatalogType::TsQuery => Ok(ScalarType::TsQuery),
                 CatalogType::Oid => Ok(ScalarType::Oid),
                 CatalogType::PgLegacyChar => Ok(ScalarType::PgLegacyChar),
                 CatalogType::PgLegacyName => Ok(ScalarType::PgLegacyName),
@@ -5395,6 +6898,9 @@ fn scalar_type_from_catalog(
                 CatalogType::Timestamp => Ok(ScalarType::Timestamp),
                 CatalogType::TimestampTz => Ok(ScalarType::TimestampTz),
                 CatalogType::Uuid => Ok(ScalarType::Uuid),
+                CatalogType::Inet => Ok(ScalarType::Inet),
+                CatalogType::Cidr => Ok(ScalarType::Cidr),
+                CatalogType::MacAddr => Ok(ScalarType::MacAddr),
                 CatalogType::Int2Vector => Ok(ScalarType::Int2Vector),
                 CatalogType::MzAclItem => Ok(ScalarType::MzAclItem),
                 CatalogType::Numeric => unreachable!("handled above"),
@@ -35,12 +35,16 @@ use enum_kinds::EnumKind;
 use mz_ore::cast::ReinterpretCast;
 use mz_ore::collections::CollectionExt;
 use mz_ore::result::ResultExt;
+use mz_ore::str::StrExt;
 use mz_repr::RelationType;
-use mz_repr::{ColumnType, Datum, RelationDesc, RowArena, ScalarType};
-use mz_sql_parser::ast::{CteBlock, Expr, Function, FunctionArgs, Select, SelectItem, SetExpr};
+use mz_repr::{ColumnType, Datum, GlobalId, RelationDesc, RowArena, ScalarType};
+use mz_sql_parser::ast::{
+    CteBlock, Expr, Function, FunctionArgs, RawItemName, Select, SelectItem, SetExpr,
+};
 use once_cell::sync::Lazy;
 
 use crate::ast::{Query, SelectStatement};
+use crate::catalog::CatalogItemType;
 use crate::func::Func;
 use crate::names::Aug;
 use crate::plan::query::{self, ExprContext, QueryLifetime};
@@ -62,6 +66,23 @@ pub enum SideEffectingFunc {
         // The ID of the connection to cancel.
         connection_id: u32,
     },
+    /// The `nextval` function.
+    Nextval {
+        // The ID of the sequence to advance.
+        sequence: GlobalId,
+    },
+    /// The `currval` function.
+    Currval {
+        // The ID of the sequence whose current value to return.
+        sequence: GlobalId,
+    },
+    /// The `setval` function.
+    Setval {
+        // The ID of the sequence to modify.
+        sequence: GlobalId,
+        // The value the sequence was last advanced to.
+        value: i64,
+    },
 }
 
 /// Describes a `SELECT` if it contains calls to side-effecting functions.
@@ -111,7 +132,7 @@ pub fn plan_select_if_side_effecting(
         datums.push(datum);
     }
 
-    let func = (sef_call.imp.plan_fn)(&datums);
+    let func = (sef_call.imp.plan_fn)(scx, &datums)?;
 
     Ok(Some(func))
 }
@@ -261,13 +282,13 @@ pub struct SideEffectingFuncImpl {
     pub return_type: ColumnType,
     /// A function that will produce a `SideEffectingFunc` given arguments
     /// that have been evaluated to `Datum`s.
-    pub plan_fn: fn(&[Datum]) -> SideEffectingFunc,
+    pub plan_fn: fn(&StatementContext, &[Datum]) -> Result<SideEffectingFunc, PlanError>,
 }
 
 /// A map of the side-effecting functions in the `pg_catalog` schema, keyed by
 /// OID.
 pub static PG_CATALOG_SEF_BUILTINS: Lazy<BTreeMap<u32, SideEffectingFuncImpl>> = Lazy::new(|| {
-    [PG_CANCEL_BACKEND]
+    [PG_CANCEL_BACKEND, NEXTVAL, CURRVAL, SETVAL]
         .into_iter()
         .map(|f| (f.oid, f))
         .collect()
@@ -282,9 +303,81 @@ const PG_CANCEL_BACKEND: SideEffectingFuncImpl = SideEffectingFuncImpl {
     oid: 2171,
     param_types: &[ScalarType::Int32],
     return_type: ScalarType::Bool.nullable(false),
-    plan_fn: |datums| -> SideEffectingFunc {
-        SideEffectingFunc::PgCancelBackend {
+    plan_fn: |_scx, datums| -> Result<SideEffectingFunc, PlanError> {
+        Ok(SideEffectingFunc::PgCancelBackend {
             connection_id: u32::reinterpret_cast(datums[0].unwrap_int32()),
+        })
+    },
+};
+
+const NEXTVAL: SideEffectingFuncImpl = SideEffectingFuncImpl {
+    name: "nextval",
+    oid: 1574,
+    param_types: &[ScalarType::String],
+    return_type: ScalarType::Int64.nullable(false),
+    plan_fn: |scx, datums| -> Result<SideEffectingFunc, PlanError> {
+        Ok(SideEffectingFunc::Nextval {
+            sequence: resolve_sequence_datum(scx, "nextval", datums[0])?,
+        })
+    },
+};
+
+const CURRVAL: SideEffectingFuncImpl = SideEffectingFuncImpl {
+    name: "currval",
+    oid: 1575,
+    param_types: &[ScalarType::String],
+    return_type: ScalarType::Int64.nullable(false),
+    plan_fn: |scx, datums| -> Result<SideEffectingFunc, PlanError> {
+        Ok(SideEffectingFunc::Currval {
+            sequence: resolve_sequence_datum(scx, "currval", datums[0])?,
+        })
+    },
+};
+
+const SETVAL: SideEffectingFuncImpl = SideEffectingFuncImpl {
+    name: "setval",
+    oid: 1576,
+    param_types: &[ScalarType::String, ScalarType::Int64],
+    return_type: ScalarType::Int64.nullable(false),
+    plan_fn: |scx, datums| -> Result<SideEffectingFunc, PlanError> {
+        let sequence = resolve_sequence_datum(scx, "setval", datums[0])?;
+        if datums[1].is_null() {
+            sql_bail!("setval: value must not be null");
         }
+        Ok(SideEffectingFunc::Setval {
+            sequence,
+            value: datums[1].unwrap_int64(),
+        })
     },
 };
+
+/// Resolves the sequence named by the first argument of the sequence
+/// function `func`.
+fn resolve_sequence_datum(
+    scx: &StatementContext,
+    func: &str,
+    datum: Datum,
+) -> Result<GlobalId, PlanError> {
+    if datum.is_null() {
+        sql_bail!("{func}: sequence name must not be null");
+    }
+    resolve_sequence(scx, datum.unwrap_str())
+}
+
+/// Resolves the sequence named by `name`, which is parsed like a possibly
+/// qualified SQL identifier, as in PostgreSQL's `regclass` input.
+pub(crate) fn resolve_sequence(scx: &StatementContext, name: &str) -> Result<GlobalId, PlanError> {
+    let name = mz_sql_parser::parser::parse_item_name(name)
+        .map_err(|e| sql_err!("invalid sequence name {}: {}", name.quoted(), e.message))?;
+    let item = scx.resolve_item(RawItemName::Name(name))?;
+    if item.item_type() != CatalogItemType::Sequence {
+        sql_bail!(
+            "{} is not a sequence",
+            scx.catalog
+                .resolve_full_name(item.name())
+                .to_string()
+                .quoted()
+        );
+    }
+    Ok(item.id())
+}
@@ -14,6 +14,7 @@
 use std::cell::RefCell;
 use std::collections::{BTreeMap, BTreeSet};
 
+use mz_expr::OptimizerHints;
 use mz_repr::{ColumnType, GlobalId, RelationDesc, ScalarType};
 use mz_sql_parser::ast::{
     ColumnDef, RawItemName, ShowStatement, TableConstraint, UnresolvedDatabaseName,
@@ -112,6 +113,8 @@ pub fn describe(
         catalog,
         param_types: RefCell::new(param_types),
         ambiguous_columns: RefCell::new(false),
+        optimizer_hints: RefCell::new(OptimizerHints::default()),
+        param_names: BTreeMap::new(),
     };
 
     let desc = match stmt {
@@ -123,6 +126,7 @@ pub fn describe(
         Statement::AlterRole(stmt) => ddl::describe_alter_role(&scx, stmt)?,
         Statement::AlterSecret(stmt) => ddl::describe_alter_secret_options(&scx, stmt)?,
         Statement::AlterSetCluster(stmt) => ddl::describe_alter_set_cluster(&scx, stmt)?,
+        Statement::AlterRetainHistory(stmt) => ddl::describe_alter_retain_history(&scx, stmt)?,
         Statement::AlterSink(stmt) => ddl::describe_alter_sink(&scx, stmt)?,
         Statement::AlterSource(stmt) => ddl::describe_alter_source(&scx, stmt)?,
         Statement::AlterSystemSet(stmt) => ddl::describe_alter_system_set(&scx, stmt)?,
@@ -133,10 +137,12 @@ pub fn describe(
         Statement::CreateClusterReplica(stmt) => ddl::describe_create_cluster_replica(&scx, stmt)?,
         Statement::CreateConnection(stmt) => ddl::describe_create_connection(&scx, stmt)?,
         Statement::CreateDatabase(stmt) => ddl::describe_create_database(&scx, stmt)?,
+        Statement::CreateFunction(stmt) => ddl::describe_create_function(&scx, stmt)?,
         Statement::CreateIndex(stmt) => ddl::describe_create_index(&scx, stmt)?,
         Statement::CreateRole(stmt) => ddl::describe_create_role(&scx, stmt)?,
         Statement::CreateSchema(stmt) => ddl::describe_create_schema(&scx, stmt)?,
         Statement::CreateSecret(stmt) => ddl::describe_create_secret(&scx, stmt)?,
+        Statement::CreateSequence(stmt) => ddl::describe_create_sequence(&scx, stmt)?,
         Statement::CreateSink(stmt) => ddl::describe_create_sink(&scx, stmt)?,
         Statement::CreateWebhookSource(stmt) => ddl::describe_create_webhook_source(&scx, stmt)?,
         Statement::CreateSource(stmt) => ddl::describe_create_source(&scx, stmt)?,
@@ -210,6 +216,7 @@ pub fn describe(
         Statement::ExplainPlan(stmt) => dml::describe_explain_plan(&scx, stmt)?,
         Statement::ExplainTimestamp(stmt) => dml::describe_explain_timestamp(&scx, stmt)?,
         Statement::Insert(stmt) => dml::describe_insert(&scx, stmt)?,
+        Statement::Merge(stmt) => dml::describe_merge(&scx, stmt)?,
         Statement::Select(stmt) => dml::describe_select(&scx, stmt)?,
         Statement::Subscribe(stmt) => dml::describe_subscribe(&scx, stmt)?,
         Statement::Update(stmt) => dml::describe_update(&scx, stmt)?,
@@ -272,6 +279,8 @@ pub fn plan(
         catalog,
         param_types: RefCell::new(param_types),
         ambiguous_columns: RefCell::new(false),
+        optimizer_hints: RefCell::new(OptimizerHints::default()),
+        param_names: BTreeMap::new(),
     };
 
     if resolved_ids
@@ -297,6 +306,7 @@ pub fn plan(
         Statement::AlterRole(stmt) => ddl::plan_alter_role(scx, stmt),
         Statement::AlterSecret(stmt) => ddl::plan_alter_secret(scx, stmt),
         Statement::AlterSetCluster(stmt) => ddl::plan_alter_item_set_cluster(scx, stmt),
+        Statement::AlterRetainHistory(stmt) => ddl::plan_alter_retain_history(scx, stmt),
         Statement::AlterSink(stmt) => ddl::plan_alter_sink(scx, stmt),
         Statement::AlterSource(stmt) => ddl::plan_alter_source(scx, stmt),
         Statement::AlterSystemSet(stmt) => ddl::plan_alter_system_set(scx, stmt),
@@ -307,10 +317,12 @@ pub fn plan(
         Statement::CreateClusterReplica(stmt) => ddl::plan_create_cluster_replica(scx, stmt),
         Statement::CreateConnection(stmt) => ddl::plan_create_connection(scx, stmt),
         Statement::CreateDatabase(stmt) => ddl::plan_create_database(scx, stmt),
+        Statement::CreateFunction(stmt) => ddl::plan_create_function(scx, stmt),
         Statement::CreateIndex(stmt) => ddl::plan_create_index(scx, stmt),
         Statement::CreateRole(stmt) => ddl::plan_create_role(scx, stmt),
         Statement::CreateSchema(stmt) => ddl::plan_create_schema(scx, stmt),
         Statement::CreateSecret(stmt) => ddl::plan_create_secret(scx, stmt),
+        Statement::CreateSequence(stmt) => ddl::plan_create_sequence(scx, stmt),
         Statement::CreateSink(stmt) => ddl::plan_create_sink(scx, stmt),
         Statement::CreateWebhookSource(stmt) => ddl::plan_create_webhook_source(scx, stmt),
         Statement::CreateSource(stmt) => ddl::plan_create_source(scx, stmt),
@@ -339,6 +351,7 @@ pub fn plan(
         Statement::ExplainPlan(stmt) => dml::plan_explain_plan(scx, stmt, params),
         Statement::ExplainTimestamp(stmt) => dml::plan_explain_timestamp(scx, stmt, params),
         Statement::Insert(stmt) => dml::plan_insert(scx, stmt, params),
+        Statement::Merge(stmt) => dml::plan_merge(scx, stmt, params),
         Statement::Select(stmt) => dml::plan_select(scx, stmt, params, None),
         Statement::Subscribe(stmt) => dml::plan_subscribe(scx, stmt, params, None),
         Statement::Update(stmt) => dml::plan_update(scx, stmt, params),
@@ -399,6 +412,19 @@ pub fn plan(
             plan,
             permitted_plans
         );
+
+        // Only some plans carry optimizer hints to the optimizer. Rather than
+        // silently ignoring the hints of the other statements, we reject them.
+        if !scx.optimizer_hints.borrow().is_empty()
+            && !matches!(
+                plan,
+                Plan::Select(_) | Plan::ExplainPlan(_) | Plan::CreateMaterializedView(_)
+            )
+        {
+            sql_bail!(
+                "optimizer hints are only supported in SELECT, EXPLAIN, and CREATE MATERIALIZED VIEW"
+            );
+        }
     }
 
     plan
@@ -410,7 +436,7 @@ pub fn plan_copy_from(
     id: GlobalId,
     columns: Vec<usize>,
     rows: Vec<mz_repr::Row>,
-) -> Result<super::HirRelationExpr, PlanError> {
+) -> Result<(super::HirRelationExpr, BTreeMap<usize, GlobalId>), PlanError> {
     query::plan_copy_from_rows(pcx, catalog, id, columns, rows)
 }
 
@@ -431,6 +457,7 @@ impl PartialEq<ObjectType> for CatalogItemType {
             | (CatalogItemType::Index, ObjectType::Index)
             | (CatalogItemType::Type, ObjectType::Type)
             | (CatalogItemType::Secret, ObjectType::Secret)
+            | (CatalogItemType::Sequence, ObjectType::Sequence)
             | (CatalogItemType::Connection, ObjectType::Connection) => true,
             (_, _) => false,
         }
@@ -458,6 +485,12 @@ pub struct StatementContext<'a> {
     /// Whether the statement contains an expression that can make the exact column list
     /// ambiguous. For example `NATURAL JOIN` or `SELECT *`. This is filled in as planning occurs.
     pub ambiguous_columns: RefCell<bool>,
+    /// The optimizer hints given in the `OPTIONS` of the `SELECT`s in the statement. This is
+    /// filled in as planning occurs.
+    pub optimizer_hints: RefCell<OptimizerHints>,
+    /// The positions of the named parameters of the user-defined function whose body is being
+    /// planned, if any. Unqualified names that do not refer to a column refer to these.
+    pub param_names: BTreeMap<String, usize>,
 }
 
 impl<'a> StatementContext<'a> {
@@ -470,6 +503,8 @@ impl<'a> StatementContext<'a> {
             catalog,
             param_types: Default::default(),
             ambiguous_columns: RefCell::new(false),
+            optimizer_hints: RefCell::new(OptimizerHints::default()),
+            param_names: BTreeMap::new(),
         }
     }
 
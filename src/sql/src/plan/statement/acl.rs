@@ -636,6 +636,7 @@ pub fn plan_alter_default_privileges(
         | ObjectType::Index
         | ObjectType::Type
         | ObjectType::Secret
+        | ObjectType::Sequence
         | ObjectType::Connection
         | ObjectType::Cluster
         | ObjectType::Database
@@ -15,13 +15,15 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Write;
 use std::iter;
+use std::sync::Arc;
+use std::time::Duration;
 
 use itertools::Itertools;
 use mz_controller::clusters::{ClusterId, ReplicaId, DEFAULT_REPLICA_LOGGING_INTERVAL_MICROS};
 use mz_expr::CollectionPlan;
 use mz_interchange::avro::AvroSchemaGenerator;
 use mz_ore::cast::{self, CastFrom, TryCastFrom};
-use mz_ore::collections::HashSet;
+use mz_ore::collections::{CollectionExt, HashSet};
 use mz_ore::str::StrExt;
 use mz_proto::RustType;
 use mz_repr::adt::interval::Interval;
@@ -36,9 +38,9 @@ use mz_sql_parser::ast::{
     AlterSourceAddSubsourceOptionName, AlterSourceStatement, AlterSystemResetAllStatement,
     AlterSystemResetStatement, AlterSystemSetStatement, CommentObjectType, CommentStatement,
     CreateConnectionOption, CreateConnectionOptionName, CreateTypeListOption,
-    CreateTypeListOptionName, CreateTypeMapOption, CreateTypeMapOptionName, DeferredItemName,
-    DropOwnedStatement, SshConnectionOption, UnresolvedItemName, UnresolvedObjectName,
-    UnresolvedSchemaName, Value,
+    CreateTypeListOptionName, CreateTypeMapOption, CreateTypeMapOptionName, CsvOption,
+    CsvOptionName, DeferredItemName, DropOwnedStatement, SshConnectionOption, UnresolvedItemName,
+    UnresolvedObjectName, UnresolvedSchemaName, Value,
 };
 use mz_storage_client::types::connections::aws::{AwsAssumeRole, AwsConfig, AwsCredentials};
 use mz_storage_client::types::connections::inline::ReferencedConnection;
@@ -55,42 +57,48 @@ use mz_storage_client::types::sources::encoding::{
     ProtobufEncoding, RegexEncoding, SourceDataEncoding, SourceDataEncodingInner,
 };
 use mz_storage_client::types::sources::{
-    GenericSourceConnection, IncludedColumnPos, KafkaSourceConnection, KeyEnvelope, LoadGenerator,
+    EventTimeDesc, GenericSourceConnection, IncludedColumnPos, IncludedColumnSource,
+    KafkaSourceConnection, KeyEnvelope, LateEventPolicy, LoadGenerator,
     LoadGeneratorSourceConnection, PostgresSourceConnection, PostgresSourcePublicationDetails,
     ProtoPostgresSourcePublicationDetails, SourceConnection, SourceDesc, SourceEnvelope,
-    TestScriptSourceConnection, Timeline, UnplannedSourceEnvelope, UpsertStyle,
+    TestScriptSourceConnection, Timeline, UnplannedSourceEnvelope, UpsertRetainKeys, UpsertStyle,
 };
 use prost::Message;
 
 use crate::ast::display::AstDisplay;
 use crate::ast::{
     AlterConnectionStatement, AlterIndexAction, AlterIndexStatement, AlterObjectRenameStatement,
-    AlterSecretStatement, AvroSchema, AvroSchemaOption, AvroSchemaOptionName, AwsConnectionOption,
-    AwsConnectionOptionName, AwsPrivatelinkConnectionOption, AwsPrivatelinkConnectionOptionName,
-    ClusterOption, ClusterOptionName, ColumnOption, CreateClusterReplicaStatement,
-    CreateClusterStatement, CreateConnection, CreateConnectionStatement, CreateDatabaseStatement,
+    AlterRetainHistoryStatement, AlterSecretStatement, AvroSchema, AvroSchemaOption,
+    AvroSchemaOptionName, AwsConnectionOption, AwsConnectionOptionName,
+    AwsPrivatelinkConnectionOption, AwsPrivatelinkConnectionOptionName, ClusterOption,
+    ClusterOptionName, ColumnOption, CreateClusterReplicaStatement, CreateClusterStatement,
+    CreateConnection, CreateConnectionStatement, CreateDatabaseStatement, CreateFunctionStatement,
     CreateIndexStatement, CreateMaterializedViewStatement, CreateRoleStatement,
-    CreateSchemaStatement, CreateSecretStatement, CreateSinkConnection, CreateSinkOption,
-    CreateSinkOptionName, CreateSinkStatement, CreateSourceConnection, CreateSourceFormat,
-    CreateSourceOption, CreateSourceOptionName, CreateSourceStatement, CreateSubsourceOption,
-    CreateSubsourceOptionName, CreateSubsourceStatement, CreateTableStatement, CreateTypeAs,
-    CreateTypeStatement, CreateViewStatement, CreateWebhookSourceStatement, CsrConfigOption,
-    CsrConfigOptionName, CsrConnection, CsrConnectionAvro, CsrConnectionOption,
-    CsrConnectionOptionName, CsrConnectionProtobuf, CsrSeedProtobuf, CsvColumns, DbzMode,
-    DropObjectsStatement, Envelope, Expr, Format, Ident, IfExistsBehavior, IndexOption,
+    CreateSchemaStatement, CreateSecretStatement, CreateSequenceStatement, CreateSinkConnection,
+    CreateSinkOption, CreateSinkOptionName, CreateSinkStatement, CreateSourceConnection,
+    CreateSourceFormat, CreateSourceOption, CreateSourceOptionName, CreateSourceStatement,
+    CreateSubsourceOption, CreateSubsourceOptionName, CreateSubsourceStatement,
+    CreateTableStatement, CreateTypeAs, CreateTypeStatement, CreateViewStatement,
+    CreateWebhookSourceStatement, CsrConfigOption, CsrConfigOptionName, CsrConnection,
+    CsrConnectionAvro, CsrConnectionOption, CsrConnectionOptionName, CsrConnectionProtobuf,
+    CsrSeedProtobuf, CsvColumns, DbzMode, DropObjectsStatement, Envelope, Expr, Format,
+    FunctionColumn, FunctionParameter, FunctionReturns, Ident, IfExistsBehavior, IndexOption,
     IndexOptionName, KafkaBroker, KafkaBrokerAwsPrivatelinkOption,
     KafkaBrokerAwsPrivatelinkOptionName, KafkaBrokerTunnel, KafkaConfigOptionName,
     KafkaConnectionOption, KafkaConnectionOptionName, KeyConstraint, LoadGeneratorOption,
-    LoadGeneratorOptionName, PgConfigOption, PgConfigOptionName, PostgresConnectionOption,
-    PostgresConnectionOptionName, ProtobufSchema, QualifiedReplica, ReferencedSubsources,
-    ReplicaDefinition, ReplicaOption, ReplicaOptionName, RoleAttribute, SourceIncludeMetadata,
-    SourceIncludeMetadataType, SshConnectionOptionName, Statement, TableConstraint,
-    UnresolvedDatabaseName, ViewDefinition,
+    LoadGeneratorOptionName, MaterializedViewOption, MaterializedViewOptionName, PgConfigOption,
+    PgConfigOptionName, PostgresConnectionOption, PostgresConnectionOptionName, ProtobufSchema,
+    QualifiedReplica, Raw, RawItemName, ReferencedSubsources, RefreshAtOptionValue,
+    RefreshEveryOptionValue, RefreshOptionValue, ReplicaDefinition, ReplicaOption,
+    ReplicaOptionName, RoleAttribute, SequenceOption, SequenceOwner, SourceIncludeMetadata,
+    SourceIncludeMetadataType, SshConnectionOptionName, Statement, TableConstraint, TableOption,
+    TableOptionName, UnresolvedDatabaseName, ViewDefinition,
 };
 use crate::catalog::{
     CatalogCluster, CatalogDatabase, CatalogItem, CatalogItemType, CatalogType, CatalogTypeDetails,
     ObjectType, SystemObjectType,
 };
+use crate::func::{self, UserFuncReturns};
 use crate::kafka_util::{self, KafkaConfigOptionExtracted, KafkaStartOffsetType};
 use crate::names::{
     Aug, CommentObjectId, DatabaseId, ObjectId, PartialItemName, QualifiedItemName,
@@ -106,20 +114,21 @@ use crate::plan::statement::{scl, StatementContext, StatementDesc};
 use crate::plan::typeconv::{plan_cast, CastContext};
 use crate::plan::with_options::{self, OptionalInterval, TryFromValue};
 use crate::plan::{
-    plan_utils, query, transform_ast, AlterClusterPlan, AlterClusterRenamePlan,
+    custom_generator, plan_utils, query, transform_ast, AlterClusterPlan, AlterClusterRenamePlan,
     AlterClusterReplicaRenamePlan, AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan,
-    AlterItemRenamePlan, AlterNoopPlan, AlterOptionParameter, AlterRolePlan, AlterSecretPlan,
-    AlterSetClusterPlan, AlterSinkPlan, AlterSourcePlan, AlterSystemResetAllPlan,
-    AlterSystemResetPlan, AlterSystemSetPlan, CommentPlan, ComputeReplicaConfig,
-    ComputeReplicaIntrospectionConfig, CreateClusterManagedPlan, CreateClusterPlan,
-    CreateClusterReplicaPlan, CreateClusterUnmanagedPlan, CreateClusterVariant,
-    CreateConnectionPlan, CreateDatabasePlan, CreateIndexPlan, CreateMaterializedViewPlan,
-    CreateRolePlan, CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan,
-    CreateTablePlan, CreateTypePlan, CreateViewPlan, DataSourceDesc, DropObjectsPlan,
-    DropOwnedPlan, FullItemName, HirScalarExpr, Index, Ingestion, MaterializedView, Params, Plan,
-    PlanClusterOption, PlanNotice, QueryContext, ReplicaConfig, RotateKeysPlan, Secret, Sink,
-    Source, SourceSinkClusterConfig, Table, Type, View, WebhookHeaderFilters, WebhookHeaders,
-    WebhookValidation,
+    AlterItemRenamePlan, AlterNoopPlan, AlterOptionParameter, AlterRetainHistoryPlan,
+    AlterRolePlan, AlterSecretPlan, AlterSetClusterPlan, AlterSinkPlan, AlterSourcePlan,
+    AlterSystemResetAllPlan, AlterSystemResetPlan, AlterSystemSetPlan, CheckConstraint,
+    CommentPlan, ComputeReplicaConfig, ComputeReplicaIntrospectionConfig, CreateClusterManagedPlan,
+    CreateClusterPlan, CreateClusterReplicaPlan, CreateClusterUnmanagedPlan, CreateClusterVariant,
+    CreateConnectionPlan, CreateDatabasePlan, CreateFunctionPlan, CreateIndexPlan,
+    CreateMaterializedViewPlan, CreateRolePlan, CreateSchemaPlan, CreateSecretPlan,
+    CreateSequencePlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan,
+    CreateViewPlan, DataSourceDesc, DropObjectsPlan, DropOwnedPlan, FullItemName, Function,
+    HirScalarExpr, Index, Ingestion, MaterializedView, Params, Plan, PlanClusterOption, PlanNotice,
+    QueryContext, RefreshEveryPlan, RefreshSchedulePlan, ReplicaConfig, RotateKeysPlan, Secret,
+    Sequence, SequenceOptions, Sink, Source, SourceSinkClusterConfig, Table, TableConstraints,
+    Type, UniqueConstraint, View, WebhookHeaderFilters, WebhookHeaders, WebhookValidation,
 };
 use crate::session::vars;
 
@@ -205,8 +214,15 @@ pub fn plan_create_table(
         constraints,
         if_not_exists,
         temporary,
+        with_options,
     } = &stmt;
 
+    let TableOptionExtracted {
+        retain_history,
+        seen: _,
+    } = with_options.clone().try_into()?;
+    let compaction_window = plan_retain_history(scx, retain_history)?;
+
     let names: Vec<_> = columns
         .iter()
         .map(|c| normalize::column_name(c.name.clone()))
@@ -216,39 +232,133 @@ pub fn plan_create_table(
         sql_bail!("column {} specified more than once", dup.as_str().quoted());
     }
 
+    // Constraints are only enforced if `enable_table_constraint_enforcement`
+    // is set when the table is written to, but we always record them so that
+    // turning on enforcement applies to existing tables.
+    let enforce_constraints = scx
+        .catalog
+        .system_vars()
+        .enable_table_constraint_enforcement();
+    let table_name = normalize::unresolved_item_name(name.to_owned())?.item;
+    let mut unique_constraints = Vec::new();
+    // The (optional) name, column and expression of each check constraint,
+    // which can only be planned once all column types are known.
+    let mut check_exprs = Vec::new();
+
     // Build initial relation type that handles declared data types
     // and NOT NULL constraints.
     let mut column_types = Vec::with_capacity(columns.len());
     let mut defaults = Vec::with_capacity(columns.len());
     let mut keys = Vec::new();
+    // The index and sequence options of each identity column.
+    let mut identities = Vec::new();
 
     for (i, c) in columns.into_iter().enumerate() {
+        // Whether each unique constraint of the column is a primary key.
+        let mut unique_columns = vec![];
         let aug_data_type = &c.data_type;
         let ty = query::scalar_type_from_sql(scx, aug_data_type)?;
         let mut nullable = true;
         let mut default = Expr::null();
+        let mut has_default = false;
+        let mut identity = None;
         for option in &c.options {
             match &option.option {
                 ColumnOption::NotNull => nullable = false,
                 ColumnOption::Default(expr) => {
+                    has_default = true;
                     // Ensure expression can be planned and yields the correct
                     // type.
                     let mut expr = expr.clone();
                     transform_ast::transform(scx, &mut expr)?;
-                    let _ = query::plan_default_expr(scx, &expr, &ty)?;
+                    if query::nextval_default(scx, &expr)?.is_some() {
+                        // The next value of the sequence is computed when
+                        // writing to the table, so only its type is checked.
+                        if !matches!(
+                            ty,
+                            ScalarType::Int16 | ScalarType::Int32 | ScalarType::Int64
+                        ) {
+                            sql_bail!(
+                                "column {} with a nextval default must be of type smallint, integer, or bigint",
+                                names[i].as_str().quoted()
+                            );
+                        }
+                    } else {
+                        let _ = query::plan_default_expr(scx, &expr, &ty)?;
+                    }
                     default = expr.clone();
                 }
                 ColumnOption::Unique { is_primary } => {
-                    keys.push(vec![i]);
                     if *is_primary {
                         nullable = false;
                     }
+                    unique_columns.push(*is_primary);
+                    let constraint_name = match &option.name {
+                        Some(name) => normalize::ident(name.clone()),
+                        None if *is_primary => format!("{table_name}_pkey"),
+                        None => format!("{table_name}_{}_key", names[i]),
+                    };
+                    unique_constraints.push(UniqueConstraint {
+                        name: constraint_name,
+                        columns: vec![i],
+                        nulls_not_distinct: false,
+                    });
+                }
+                ColumnOption::Check(expr) => {
+                    if !enforce_constraints {
+                        scx.require_feature_flag(&vars::ENABLE_TABLE_CHECK_CONSTRAINT)?;
+                    }
+                    check_exprs.push((option.name.clone(), Some(i), expr));
+                }
+                ColumnOption::Identity { options, .. } => {
+                    if identity.replace(options).is_some() {
+                        sql_bail!(
+                            "multiple identity specifications for column {} of table {}",
+                            names[i].as_str().quoted(),
+                            table_name
+                        );
+                    }
                 }
                 other => {
                     bail_unsupported!(format!("CREATE TABLE with column constraint: {}", other))
                 }
             }
         }
+        if let Some(options) = identity {
+            if has_default {
+                sql_bail!(
+                    "both default and identity specified for column {} of table {}",
+                    names[i].as_str().quoted(),
+                    table_name
+                );
+            }
+            if *temporary {
+                bail_unsupported!("identity columns in temporary tables");
+            }
+            let (type_min, type_max, type_name) = match ty {
+                ScalarType::Int16 => (i16::MIN.into(), i16::MAX.into(), "smallint"),
+                ScalarType::Int32 => (i32::MIN.into(), i32::MAX.into(), "integer"),
+                ScalarType::Int64 => (i64::MIN, i64::MAX, "bigint"),
+                _ => sql_bail!(
+                    "identity column type must be smallint, integer, or bigint, not {}",
+                    scx.humanize_scalar_type(&ty)
+                ),
+            };
+            let (options, owner) = plan_sequence_options(options, type_min, type_max, type_name)?;
+            if owner.is_some() {
+                sql_bail!("OWNED BY is not allowed in the options of an identity column");
+            }
+            // Identity columns are implicitly NOT NULL.
+            nullable = false;
+            identities.push((i, options));
+        }
+        // Like non-primary key unique constraints on tables, unique constraints on columns are
+        // only keys if the column is `NOT NULL`, which may be declared after the constraint.
+        for is_primary in unique_columns {
+            if is_primary || !nullable {
+                keys.push(vec![i]);
+            }
+        }
         column_types.push(ty.nullable(nullable));
         defaults.push(default);
     }
@@ -257,7 +367,7 @@ pub fn plan_create_table(
     'c: for constraint in constraints {
         match constraint {
             TableConstraint::Unique {
-                name: _,
+                name: constraint_name,
                 columns,
                 is_primary,
                 nulls_not_distinct,
@@ -275,24 +385,41 @@ pub fn plan_create_table(
                     let column = normalize::column_name(column.clone());
                     match names.iter().position(|name| *name == column) {
                         None => sql_bail!("unknown column in constraint: {}", column),
-                        Some(i) => {
-                            let nullable = &mut column_types[i].nullable;
-                            if *is_primary {
-                                if *nulls_not_distinct {
-                                    sql_bail!(
-                                        "[internal error] PRIMARY KEY does not support NULLS NOT DISTINCT"
-                                    );
-                                }
+                        Some(i) => key.push(i),
+                    }
+                }
 
-                                *nullable = false;
-                            } else if !(*nulls_not_distinct || !*nullable) {
-                                // Non-primary key unique constraints are only keys if all of their
-                                // columns are `NOT NULL` or the constraint is `NULLS NOT DISTINCT`.
-                                break 'c;
-                            }
+                let constraint_name = match constraint_name {
+                    Some(name) => normalize::ident(name.clone()),
+                    None if *is_primary => format!("{table_name}_pkey"),
+                    None => format!(
+                        "{table_name}_{}_key",
+                        key.iter().map(|i| names[*i].as_str()).join("_")
+                    ),
+                };
+                unique_constraints.push(UniqueConstraint {
+                    name: constraint_name,
+                    columns: key.clone(),
+                    nulls_not_distinct: *nulls_not_distinct,
+                });
 
-                            key.push(i);
+                for i in &key {
+                    let nullable = &mut column_types[*i].nullable;
+                    if *is_primary {
+                        if *nulls_not_distinct {
+                            sql_bail!(
+                                "[internal error] PRIMARY KEY does not support NULLS NOT DISTINCT"
+                            );
                         }
+
+                        *nullable = false;
+                    } else if !(*nulls_not_distinct || !*nullable) {
+                        // Non-primary key unique constraints are only keys if all of their
+                        // columns are `NOT NULL` or the constraint is `NULLS NOT DISTINCT`. The
+                        // remaining constraints must still be planned, so that e.g. a later
+                        // primary key makes its columns `NOT NULL` and a later check constraint
+                        // is recorded.
+                        continue 'c;
                     }
                 }
 
@@ -307,17 +434,23 @@ pub fn plan_create_table(
                 // them with feature flags for sqllogictest's sake.
                 scx.require_feature_flag(&vars::ENABLE_TABLE_FOREIGN_KEY)?
             }
-            TableConstraint::Check { .. } => {
-                // Check constraints are not presently enforced. We allow them
-                // with feature flags for sqllogictest's sake.
-                scx.require_feature_flag(&vars::ENABLE_TABLE_CHECK_CONSTRAINT)?
+            TableConstraint::Check {
+                name: constraint_name,
+                expr,
+            } => {
+                // Without enforcement, check constraints are only allowed with
+                // feature flags for sqllogictest's sake.
+                if !enforce_constraints {
+                    scx.require_feature_flag(&vars::ENABLE_TABLE_CHECK_CONSTRAINT)?
+                }
+                check_exprs.push((constraint_name.clone(), None, expr));
             }
         }
     }
 
-    if !keys.is_empty() {
-        // Unique constraints are not presently enforced. We allow them with feature flags for
-        // sqllogictest's sake.
+    // Without enforcement, keys are only allowed with feature flags for
+    // sqllogictest's sake.
+    if !keys.is_empty() && !enforce_constraints {
         scx.require_feature_flag(&vars::ENABLE_TABLE_KEYS)?
     }
 
@@ -340,19 +473,107 @@ pub fn plan_create_table(
         });
     }
 
+    // Each identity column draws its values from a sequence that is owned by
+    // the column and named like in PostgreSQL, with a numeric suffix if the
+    // name is already taken.
+    let mut sequences = Vec::with_capacity(identities.len());
+    for (i, options) in &identities {
+        let base = format!("{table_name}_{}_seq", names[*i]);
+        let mut candidate = base.clone();
+        let mut suffix = 0;
+        let sequence_name = loop {
+            let sequence_name = QualifiedItemName {
+                qualifiers: name.qualifiers.clone(),
+                item: candidate.clone(),
+            };
+            let partial_name = PartialItemName::from(scx.catalog.resolve_full_name(&sequence_name));
+            let taken = scx.catalog.resolve_item(&partial_name).is_ok()
+                || sequences.iter().any(|(name, _, _)| *name == sequence_name);
+            if !taken {
+                break sequence_name;
+            }
+            suffix += 1;
+            candidate = format!("{base}{suffix}");
+        };
+        let mut table_parts = UnresolvedItemName::from(full_name.clone()).0;
+        table_parts.push(Ident::new(names[*i].as_str()));
+        let create_sql = CreateSequenceStatement::<Raw> {
+            name: UnresolvedItemName::from(scx.catalog.resolve_full_name(&sequence_name)),
+            if_not_exists: false,
+            options: vec![
+                SequenceOption::IncrementBy(options.increment),
+                SequenceOption::MinValue(Some(options.min_value)),
+                SequenceOption::MaxValue(Some(options.max_value)),
+                SequenceOption::StartWith(options.start),
+                SequenceOption::Cache(i64::try_from(options.cache).expect("from an i64")),
+                SequenceOption::Cycle(options.cycle),
+                SequenceOption::OwnedBy(Some(SequenceOwner {
+                    column: table_parts.pop().expect("just pushed"),
+                    table: RawItemName::Name(UnresolvedItemName(table_parts)),
+                })),
+            ],
+        }
+        .to_ast_string_stable();
+        let sequence = Sequence {
+            create_sql,
+            options: *options,
+        };
+        sequences.push((sequence_name, *i, sequence));
+    }
+
     let desc = RelationDesc::new(typ, names);
 
+    let mut checks: Vec<CheckConstraint> = Vec::with_capacity(check_exprs.len());
+    for (constraint_name, column, expr) in check_exprs {
+        let expr = match query::plan_check_expr(scx, expr, &desc) {
+            Ok(expr) => expr,
+            // Tables created while check constraints were unenforced may have
+            // checks that can't be enforced; we keep ignoring those.
+            Err(_) if scx.catalog.system_vars().enable_table_check_constraint() => continue,
+            Err(e) => return Err(e),
+        };
+        let constraint_name = match constraint_name {
+            Some(name) => normalize::ident(name),
+            None => {
+                // Generate a name like PostgreSQL does, adding a numeric
+                // suffix if the name is already taken.
+                let base = match column {
+                    Some(i) => format!("{table_name}_{}_check", desc.get_name(i)),
+                    None => format!("{table_name}_check"),
+                };
+                let mut candidate = base.clone();
+                let mut suffix = 0;
+                while checks.iter().any(|check| check.name == candidate) {
+                    suffix += 1;
+                    candidate = format!("{base}{suffix}");
+                }
+                candidate
+            }
+        };
+        checks.push(CheckConstraint {
+            name: constraint_name,
+            expr,
+        });
+    }
+
     let create_sql = normalize::create_statement(scx, Statement::CreateTable(stmt.clone()))?;
     let table = Table {
         create_sql,
         desc,
         defaults,
+        identity_columns: identities.into_iter().map(|(i, _)| i).collect(),
         temporary,
+        constraints: TableConstraints {
+            unique: unique_constraints,
+            checks,
+        },
+        compaction_window,
     };
     Ok(Plan::CreateTable(CreateTablePlan {
         name,
         table,
         if_not_exists: *if_not_exists,
+        sequences,
     }))
 }
 
@@ -379,9 +600,13 @@ pub fn describe_create_subsource(
 
 generate_extracted_config!(
     CreateSourceOption,
+    (AllowedLateness, Interval),
     (IgnoreKeys, bool),
+    (LateEvents, String),
+    (RetainHistory, Duration),
     (Size, String),
     (Timeline, String),
+    (TimestampFrom, String),
     (TimestampInterval, Interval)
 );
 
@@ -389,9 +614,39 @@ generate_extracted_config!(
     PgConfigOption,
     (Details, String),
     (Publication, String),
+    (SchemaChanges, String),
     (TextColumns, Vec::<UnresolvedItemName>, Default(vec![]))
 );
 
+/// Returns whether a Postgres source with the given `SCHEMA CHANGES` option
+/// absorbs additive changes to the schemas of its upstream tables.
+pub(crate) fn plan_pg_schema_changes(schema_changes: Option<&str>) -> Result<bool, PlanError> {
+    match schema_changes.map(|s| s.to_lowercase()).as_deref() {
+        None | Some("strict") => Ok(false),
+        Some("additive") => Ok(true),
+        Some(_) => sql_bail!(
+            "invalid SCHEMA CHANGES value {}: expected 'strict' or 'additive'",
+            schema_changes.unwrap_or_default().quoted()
+        ),
+    }
+}
+
+/// Returns the type modifier with which to ingest a column of an upstream
+/// Postgres table. Sources that absorb additive schema changes ingest `varchar`
+/// columns without a maximum length, so that widening a column upstream does
+/// not invalidate the type of the subsource's column.
+pub(crate) fn pg_source_type_mod(
+    type_oid: u32,
+    type_mod: i32,
+    additive_schema_changes: bool,
+) -> i32 {
+    if additive_schema_changes && type_oid == tokio_postgres::types::Type::VARCHAR.oid() {
+        -1
+    } else {
+        type_mod
+    }
+}
+
 pub fn plan_create_webhook_source(
     scx: &StatementContext,
     stmt: CreateWebhookSourceStatement<Aug>,
@@ -539,6 +794,7 @@ pub fn plan_create_webhook_source(
                 headers,
             },
             desc,
+            compaction_window: None,
         },
         if_not_exists,
         timeline,
@@ -568,7 +824,11 @@ pub fn plan_create_source(
     let envelope = envelope.clone().unwrap_or(Envelope::None);
 
     let allowed_with_options = vec![
+        CreateSourceOptionName::AllowedLateness,
+        CreateSourceOptionName::LateEvents,
+        CreateSourceOptionName::RetainHistory,
         CreateSourceOptionName::Size,
+        CreateSourceOptionName::TimestampFrom,
         CreateSourceOptionName::TimestampInterval,
     ];
     if let Some(op) = with_options
@@ -698,7 +958,7 @@ pub fn plan_create_source(
                 })
             };
 
-            if !matches!(envelope, Envelope::Upsert | Envelope::None)
+            if !matches!(envelope, Envelope::Upsert { .. } | Envelope::None)
                 && include_metadata
                     .iter()
                     .any(|sic| sic.ty == SourceIncludeMetadataType::Headers)
@@ -752,10 +1012,16 @@ pub fn plan_create_source(
             let PgConfigOptionExtracted {
                 details,
                 publication,
+                schema_changes,
                 text_columns,
                 seen: _,
             } = options.clone().try_into()?;
 
+            let additive_schema_changes = plan_pg_schema_changes(schema_changes.as_deref())?;
+            if additive_schema_changes {
+                scx.require_feature_flag(&vars::ENABLE_PG_ADDITIVE_SCHEMA_CHANGES)?;
+            }
+
             let details = details
                 .as_ref()
                 .ok_or_else(|| sql_err!("internal error: Postgres source missing details"))?;
@@ -857,7 +1123,11 @@ pub fn plan_create_source(
                         _ => {
                             match mz_pgrepr::Type::from_oid_and_typmod(
                                 column.type_oid,
-                                column.type_mod,
+                                pg_source_type_mod(
+                                    column.type_oid,
+                                    column.type_mod,
+                                    additive_schema_changes,
+                                ),
                             ) {
                                 Ok(t) => t,
                                 // If this reference survived purification, we
@@ -961,6 +1231,7 @@ pub fn plan_create_source(
                     table_casts,
                     publication: publication.expect("validated exists during purification"),
                     publication_details,
+                    additive_schema_changes,
                 });
             // The postgres source only outputs data to its subsources. The catalog object
             // representing the source itself is just an empty relation with no columns
@@ -970,6 +1241,9 @@ pub fn plan_create_source(
             (connection, encoding, Some(available_subsources))
         }
         CreateSourceConnection::LoadGenerator { generator, options } => {
+            if *generator == mz_sql_parser::ast::LoadGenerator::Custom {
+                scx.require_feature_flag(&vars::ENABLE_LOAD_GENERATOR_CUSTOM)?;
+            }
             let (load_generator, available_subsources) =
                 load_generator_ast_to_generator(generator, options)?;
             let available_subsources = available_subsources
@@ -1080,12 +1354,17 @@ pub fn plan_create_source(
     }
 
     let CreateSourceOptionExtracted {
+        allowed_lateness,
         size,
+        late_events,
         timeline,
+        timestamp_from,
         timestamp_interval,
         ignore_keys,
+        retain_history,
         seen: _,
     } = CreateSourceOptionExtracted::try_from(with_options.clone())?;
+    let compaction_window = plan_retain_history(scx, retain_history)?;
 
     let (key_desc, value_desc) = encoding.desc()?;
 
@@ -1099,6 +1378,10 @@ pub fn plan_create_source(
     // compatible in typechecking
     //
     // TODO: remove bails as more support for upsert is added.
+    let retain_keys_for = match &envelope {
+        mz_sql_parser::ast::Envelope::Upsert { retain_keys_for } => retain_keys_for.clone(),
+        _ => None,
+    };
     let envelope = match &envelope {
         // TODO: fixup key envelope
         mz_sql_parser::ast::Envelope::None => UnplannedSourceEnvelope::None(key_envelope),
@@ -1112,7 +1395,7 @@ pub fn plan_create_source(
                 },
             }
         }
-        mz_sql_parser::ast::Envelope::Upsert => {
+        mz_sql_parser::ast::Envelope::Upsert { .. } => {
             let key_encoding = match encoding.key_ref() {
                 None => {
                     bail_unsupported!(format!("upsert requires a key/value format: {:?}", format))
@@ -1142,7 +1425,7 @@ pub fn plan_create_source(
     let metadata_columns = external_connection.metadata_columns();
     let metadata_column_types = external_connection.metadata_column_types();
     let metadata_desc = included_column_desc(metadata_columns.clone());
-    let (envelope, mut desc) = envelope.desc(key_desc, value_desc, metadata_desc)?;
+    let (mut envelope, mut desc) = envelope.desc(key_desc, value_desc, metadata_desc)?;
 
     if ignore_keys.unwrap_or(false) {
         desc = desc.without_keys();
@@ -1194,6 +1477,25 @@ pub fn plan_create_source(
         }
     }
 
+    let event_time = plan_source_event_time(
+        scx,
+        &desc,
+        &envelope,
+        &subsource_exports,
+        timestamp_from,
+        allowed_lateness,
+        late_events,
+    )?;
+
+    if let Some(retain_keys_for) = retain_keys_for {
+        let retain_keys =
+            plan_upsert_retain_keys(scx, &desc, &metadata_column_types, retain_keys_for)?;
+        match &mut envelope {
+            SourceEnvelope::Upsert(upsert) => upsert.retain_keys = Some(retain_keys),
+            _ => sql_bail!("[internal error] RETAIN KEYS requires ENVELOPE UPSERT"),
+        }
+    }
+
     let cluster_config = source_sink_cluster_config(scx, "source", in_cluster.as_ref(), size)?;
 
     let timestamp_interval = match timestamp_interval {
@@ -1219,6 +1521,7 @@ pub fn plan_create_source(
         envelope: envelope.clone(),
         metadata_columns: metadata_column_types,
         timestamp_interval,
+        event_time,
     };
 
     let progress_subsource = match progress_subsource {
@@ -1279,6 +1582,7 @@ pub fn plan_create_source(
             progress_subsource,
         }),
         desc,
+        compaction_window,
     };
 
     Ok(Plan::CreateSource(CreateSourcePlan {
@@ -1290,10 +1594,105 @@ pub fn plan_create_source(
     }))
 }
 
+/// Plans the `TIMESTAMP FROM`, `ALLOWED LATENESS` and `LATE EVENTS` options of
+/// a `CREATE SOURCE` statement.
+fn plan_source_event_time(
+    scx: &StatementContext,
+    desc: &RelationDesc,
+    envelope: &SourceEnvelope,
+    subsource_exports: &BTreeMap<GlobalId, usize>,
+    timestamp_from: Option<String>,
+    allowed_lateness: Option<Interval>,
+    late_events: Option<String>,
+) -> Result<Option<EventTimeDesc>, PlanError> {
+    let column = match timestamp_from {
+        Some(column) => column,
+        None => {
+            if allowed_lateness.is_some() || late_events.is_some() {
+                sql_bail!("ALLOWED LATENESS and LATE EVENTS require TIMESTAMP FROM");
+            }
+            return Ok(None);
+        }
+    };
+    scx.require_feature_flag(&vars::ENABLE_SOURCE_EVENT_TIME)?;
+
+    // Event times may only be assigned to updates that are never retracted:
+    // a retraction would have to be issued at the event time of the update it
+    // retracts, which the envelope does not know about.
+    if !matches!(envelope, SourceEnvelope::None(_)) || !subsource_exports.is_empty() {
+        sql_bail!("TIMESTAMP FROM is only supported for single-output sources with ENVELOPE NONE");
+    }
+
+    let column = ColumnName::from(column);
+    let (idx, typ) = desc
+        .get_by_name(&column)
+        .ok_or_else(|| sql_err!("column {} does not exist", column.as_str().quoted()))?;
+    if desc.get_unambiguous_name(idx).is_none() {
+        sql_bail!("column reference {} is ambiguous", column.as_str().quoted());
+    }
+    if !matches!(
+        typ.scalar_type,
+        ScalarType::Timestamp | ScalarType::TimestampTz
+    ) {
+        sql_bail!(
+            "TIMESTAMP FROM column {} must have type timestamp or timestamp with time zone, not {}",
+            column.as_str().quoted(),
+            scx.humanize_scalar_type(&typ.scalar_type)
+        );
+    }
+
+    let allowed_lateness = match allowed_lateness {
+        Some(interval) => interval.duration()?,
+        None => Duration::ZERO,
+    };
+    let late_event_policy = match late_events.as_deref().map(str::to_lowercase).as_deref() {
+        None | Some("drop") => LateEventPolicy::Drop,
+        Some("error") => LateEventPolicy::Error,
+        Some(other) => sql_bail!(
+            "invalid LATE EVENTS value {}: expected DROP or ERROR",
+            other.quoted()
+        ),
+    };
+
+    Ok(Some(EventTimeDesc {
+        column: idx,
+        allowed_lateness,
+        late_event_policy,
+    }))
+}
+
+/// Plans the `RETAIN KEYS FOR` option of `ENVELOPE UPSERT`.
+///
+/// Keys expire relative to the record timestamp of their latest value, which
+/// must therefore be part of the source's output.
+fn plan_upsert_retain_keys(
+    scx: &StatementContext,
+    desc: &RelationDesc,
+    metadata_column_types: &[IncludedColumnSource],
+    retain_keys_for: Value,
+) -> Result<UpsertRetainKeys, PlanError> {
+    scx.require_feature_flag(&vars::ENABLE_UPSERT_RETAIN_KEYS)?;
+
+    let ttl = Interval::try_from_value(retain_keys_for)?.duration()?;
+    if ttl == Duration::ZERO {
+        sql_bail!("RETAIN KEYS FOR must be a positive interval");
+    }
+
+    // Metadata columns are always the trailing columns of the source.
+    let timestamp_idx = metadata_column_types
+        .iter()
+        .position(|ty| *ty == IncludedColumnSource::Timestamp)
+        .map(|pos| desc.arity() - metadata_column_types.len() + pos)
+        .ok_or_else(|| sql_err!("RETAIN KEYS FOR requires INCLUDE TIMESTAMP"))?;
+
+    Ok(UpsertRetainKeys { timestamp_idx, ttl })
+}
+
 generate_extracted_config!(
     CreateSubsourceOption,
     (Progress, bool, Default(false)),
-    (References, bool, Default(false))
+    (References, bool, Default(false)),
+    (RetainHistory, Duration)
 );
 
 pub fn plan_create_subsource(
@@ -1311,6 +1710,7 @@ pub fn plan_create_subsource(
     let CreateSubsourceOptionExtracted {
         progress,
         references,
+        retain_history,
         ..
     } = with_options.clone().try_into()?;
 
@@ -1438,6 +1838,9 @@ pub fn plan_create_subsource(
             unreachable!("state prohibited above")
         },
         desc,
+        // Purification copies the `RETAIN HISTORY` of the source onto the
+        // subsources that ingest its data.
+        compaction_window: plan_retain_history(scx, retain_history)?,
     };
 
     Ok(Plan::CreateSource(CreateSourcePlan {
@@ -1453,7 +1856,9 @@ generate_extracted_config!(
     LoadGeneratorOption,
     (TickInterval, Interval),
     (ScaleFactor, f64),
-    (MaxCardinality, u64)
+    (MaxCardinality, u64),
+    (Spec, String),
+    (Seed, u64)
 );
 
 pub(crate) fn load_generator_ast_to_generator(
@@ -1511,6 +1916,14 @@ pub(crate) fn load_generator_ast_to_generator(
                 count_clerk,
             }
         }
+        mz_sql_parser::ast::LoadGenerator::Custom => {
+            let LoadGeneratorOptionExtracted { spec, seed, .. } = options.to_vec().try_into()?;
+            let spec = spec.ok_or_else(|| sql_err!("LOAD GENERATOR CUSTOM requires SPEC"))?;
+            LoadGenerator::Custom {
+                spec: custom_generator::parse_spec(&spec)?,
+                seed: seed.unwrap_or(0),
+            }
+        }
     };
 
     let mut available_subsources = BTreeMap::new();
@@ -1523,6 +1936,7 @@ pub(crate) fn load_generator_ast_to_generator(
                 LoadGenerator::Auction => "auction".into(),
                 LoadGenerator::Datums => "datums".into(),
                 LoadGenerator::Tpch { .. } => "tpch".into(),
+                LoadGenerator::Custom { .. } => "custom".into(),
                 // Please use `snake_case` for any multi-word load generators
                 // that you add.
             },
@@ -1589,7 +2003,7 @@ fn get_encoding(
 
     let requires_keyvalue = matches!(
         envelope,
-        Envelope::Debezium(DbzMode::Plain) | Envelope::Upsert
+        Envelope::Debezium(DbzMode::Plain) | Envelope::Upsert { .. }
     );
     let is_keyvalue = matches!(encoding, SourceDataEncoding::KeyValue { .. });
     if requires_keyvalue && !is_keyvalue {
@@ -1629,6 +2043,24 @@ fn source_sink_cluster_config(
 
 generate_extracted_config!(AvroSchemaOption, (ConfluentWireFormat, bool, Default(true)));
 
+generate_extracted_config!(
+    CsvOption,
+    (AllowExtraColumns, bool, Default(false)),
+    (AllowMissingColumns, bool, Default(false)),
+    (Escape, String),
+    (Null, String),
+    (Quote, String),
+    (Trim, bool, Default(false))
+);
+
+/// Converts the value of a single-character CSV option to a byte.
+fn csv_option_char(name: &str, value: &str) -> Result<u8, PlanError> {
+    match value.as_bytes() {
+        [b] if b.is_ascii() => Ok(*b),
+        _ => sql_bail!("CSV {} must be a single ASCII character", name),
+    }
+}
+
 #[derive(Debug)]
 pub struct Schema {
     pub key_schema: Option<String>,
@@ -1795,7 +2227,11 @@ fn get_encoding_inner(
             regex: mz_repr::adt::regex::Regex::new(regex.clone(), false)
                 .map_err(|e| sql_err!("parsing regex: {e}"))?,
         }),
-        Format::Csv { columns, delimiter } => {
+        Format::Csv {
+            columns,
+            delimiter,
+            options,
+        } => {
             let columns = match columns {
                 CsvColumns::Header { names } => {
                     if names.is_empty() {
@@ -1807,10 +2243,36 @@ fn get_encoding_inner(
                 }
                 CsvColumns::Count(n) => ColumnSpec::Count(usize::cast_from(*n)),
             };
+            let CsvOptionExtracted {
+                allow_extra_columns,
+                allow_missing_columns,
+                escape,
+                null,
+                quote,
+                trim,
+                seen: _,
+            } = options.clone().try_into()?;
+            let delimiter = u8::try_from(*delimiter)
+                .map_err(|_| sql_err!("CSV delimiter must be an ASCII character"))?;
+            let quote = match quote {
+                Some(quote) => csv_option_char("QUOTE", &quote)?,
+                None => b'"',
+            };
+            let escape = escape
+                .map(|escape| csv_option_char("ESCAPE", &escape))
+                .transpose()?;
+            if quote == delimiter || escape == Some(delimiter) {
+                sql_bail!("CSV QUOTE and ESCAPE must differ from the delimiter");
+            }
             DataEncodingInner::Csv(CsvEncoding {
                 columns,
-                delimiter: u8::try_from(*delimiter)
-                    .map_err(|_| sql_err!("CSV delimiter must be an ASCII character"))?,
+                delimiter,
+                quote,
+                escape,
+                null_string: null,
+                trim,
+                allow_missing_columns,
+                allow_extra_columns,
             })
         }
         Format::Json => DataEncodingInner::Json,
@@ -2042,6 +2504,20 @@ pub fn plan_create_materialized_view(
     let create_sql =
         normalize::create_statement(scx, Statement::CreateMaterializedView(stmt.clone()))?;
 
+    // `REFRESH` can be specified multiple times, so it is not extracted along
+    // with the other options.
+    let (refresh_options, with_options): (Vec<_>, Vec<_>) = stmt
+        .with_options
+        .into_iter()
+        .partition(|o| o.name == MaterializedViewOptionName::Refresh);
+    let MaterializedViewOptionExtracted {
+        retain_history,
+        refresh: _,
+        seen: _,
+    } = with_options.try_into()?;
+    let compaction_window = plan_retain_history(scx, retain_history)?;
+    let refresh_schedule = plan_refresh_options(scx, refresh_options)?;
+
     let partial_name = normalize::unresolved_item_name(stmt.name)?;
     let name = scx.allocate_qualified_name(partial_name.clone())?;
 
@@ -2131,6 +2607,9 @@ pub fn plan_create_materialized_view(
             expr,
             column_names,
             cluster_id,
+            compaction_window,
+            refresh_schedule,
+            hints: scx.optimizer_hints.borrow().clone(),
         },
         replace,
         drop_ids,
@@ -2139,6 +2618,81 @@ pub fn plan_create_materialized_view(
     }))
 }
 
+generate_extracted_config!(
+    MaterializedViewOption,
+    (RetainHistory, Duration),
+    (Refresh, RefreshOptionValue<Aug>)
+);
+
+/// Plans the refresh schedule configured by the `REFRESH` options of a
+/// materialized view.
+///
+/// Returns `None` if the materialized view is maintained continuously, i.e.,
+/// if no `REFRESH` option or only `REFRESH ON COMMIT` is specified.
+fn plan_refresh_options(
+    scx: &StatementContext,
+    refresh_options: Vec<MaterializedViewOption<Aug>>,
+) -> Result<Option<RefreshSchedulePlan>, PlanError> {
+    if refresh_options.is_empty() {
+        return Ok(None);
+    }
+    scx.require_feature_flag(&vars::ENABLE_REFRESH_EVERY_MVS)?;
+
+    let mut on_commit = false;
+    let mut schedule = RefreshSchedulePlan::default();
+    for option in refresh_options {
+        let value = RefreshOptionValue::<Aug>::try_from_value(option.value)
+            .map_err(|e| sql_err!("invalid REFRESH: {}", e))?;
+        match value {
+            RefreshOptionValue::OnCommit => on_commit = true,
+            RefreshOptionValue::AtCreation => schedule.ats.push(None),
+            RefreshOptionValue::At(RefreshAtOptionValue { time }) => {
+                schedule
+                    .ats
+                    .push(Some(query::plan_refresh_time(scx, time)?));
+            }
+            RefreshOptionValue::Every(RefreshEveryOptionValue {
+                interval,
+                aligned_to,
+            }) => {
+                let interval = Interval::try_from_value(interval)?.duration()?;
+                if interval.is_zero() {
+                    sql_bail!("REFRESH EVERY interval must be positive");
+                }
+                let aligned_to = aligned_to
+                    .map(|time| query::plan_refresh_time(scx, time))
+                    .transpose()?;
+                schedule.everies.push(RefreshEveryPlan {
+                    interval,
+                    aligned_to,
+                });
+            }
+        }
+    }
+
+    if on_commit {
+        if !schedule.everies.is_empty() || !schedule.ats.is_empty() {
+            sql_bail!("REFRESH ON COMMIT cannot be combined with other REFRESH options");
+        }
+        return Ok(None);
+    }
+    Ok(Some(schedule))
+}
+
+generate_extracted_config!(TableOption, (RetainHistory, Duration));
+
+/// Plans the logical compaction window configured by a `RETAIN HISTORY`
+/// option.
+fn plan_retain_history(
+    scx: &StatementContext,
+    retain_history: Option<Duration>,
+) -> Result<Option<Duration>, PlanError> {
+    if retain_history.is_some() {
+        scx.require_feature_flag(&vars::ENABLE_RETAIN_HISTORY)?;
+    }
+    Ok(retain_history)
+}
+
 pub fn describe_create_sink(
     _: &StatementContext,
     _: CreateSinkStatement<Aug>,
@@ -2184,7 +2738,12 @@ pub fn plan_create_sink(
     let envelope = match envelope {
         None => sql_bail!("ENVELOPE clause is required"),
         Some(Envelope::Debezium(mz_sql_parser::ast::DbzMode::Plain)) => SinkEnvelope::Debezium,
-        Some(Envelope::Upsert) => SinkEnvelope::Upsert,
+        Some(Envelope::Upsert {
+            retain_keys_for: None,
+        }) => SinkEnvelope::Upsert,
+        Some(Envelope::Upsert {
+            retain_keys_for: Some(_),
+        }) => bail_unsupported!("RETAIN KEYS for sinks"),
         Some(Envelope::CdcV2) => bail_unsupported!("CDCv2 sinks"),
         Some(Envelope::None) => bail_unsupported!("\"ENVELOPE NONE\" sinks"),
     };
@@ -3221,6 +3780,246 @@ pub fn plan_create_secret(
     }))
 }
 
+pub fn describe_create_function(
+    _: &StatementContext,
+    _: CreateFunctionStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_create_function(
+    scx: &StatementContext,
+    stmt: CreateFunctionStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let CreateFunctionStatement {
+        name,
+        parameters,
+        returns,
+        behavior,
+        body: _,
+    } = &stmt;
+
+    let name = scx.allocate_qualified_name(normalize::unresolved_item_name(name.to_owned())?)?;
+
+    let mut params = Vec::with_capacity(parameters.len());
+    let mut param_names = BTreeSet::new();
+    for FunctionParameter { name, data_type } in parameters {
+        let name = name.clone().map(normalize::ident);
+        if let Some(name) = &name {
+            if !param_names.insert(name.clone()) {
+                sql_bail!("parameter name {} used more than once", name.quoted());
+            }
+        }
+        params.push((name, query::scalar_type_from_sql(scx, data_type)?));
+    }
+
+    let returns = match returns {
+        FunctionReturns::Scalar(ty) => {
+            UserFuncReturns::Scalar(query::scalar_type_from_sql(scx, ty)?)
+        }
+        // As in PostgreSQL, the sole column of a set-returning function is
+        // named after the function.
+        FunctionReturns::SetOf(ty) => UserFuncReturns::Table(vec![(
+            ColumnName::from(name.item.clone()),
+            query::scalar_type_from_sql(scx, ty)?,
+        )]),
+        FunctionReturns::Table(columns) => {
+            let mut cols = Vec::with_capacity(columns.len());
+            for FunctionColumn { name, data_type } in columns {
+                cols.push((
+                    normalize::column_name(name.clone()),
+                    query::scalar_type_from_sql(scx, data_type)?,
+                ));
+            }
+            if let Some(dup) = cols.iter().map(|(name, _)| name).duplicates().next() {
+                sql_bail!("column {} specified more than once", dup.as_str().quoted());
+            }
+            UserFuncReturns::Table(cols)
+        }
+    };
+
+    let create_sql = normalize::create_statement(scx, Statement::CreateFunction(stmt.clone()))?;
+
+    // The function is defined by its normalized body, in which every reference
+    // is fully qualified, so that calls resolve the same objects regardless
+    // of the caller's search path.
+    let body = match mz_sql_parser::parser::parse_statements(&create_sql)
+        .expect("create_sql is valid")
+        .into_element()
+        .ast
+    {
+        Statement::CreateFunction(stmt) => stmt.body,
+        _ => unreachable!("create_sql is a CREATE FUNCTION statement"),
+    };
+    let qcx = QueryContext::root(scx, QueryLifetime::OneShot);
+    let func = func::sql_user_func(&qcx, &name.item, &params, returns, *behavior, body)?;
+
+    Ok(Plan::CreateFunction(CreateFunctionPlan {
+        name,
+        function: Function {
+            create_sql,
+            func: Arc::new(func),
+        },
+    }))
+}
+
+pub fn describe_create_sequence(
+    _: &StatementContext,
+    _: CreateSequenceStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_create_sequence(
+    scx: &StatementContext,
+    stmt: CreateSequenceStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let CreateSequenceStatement {
+        name,
+        if_not_exists,
+        options,
+    } = &stmt;
+
+    let name = scx.allocate_qualified_name(normalize::unresolved_item_name(name.to_owned())?)?;
+    let (options, owner) = plan_sequence_options(options, i64::MIN, i64::MAX, "bigint")?;
+    let owned_by = match owner {
+        Some(owner) => Some(plan_sequence_owner(scx, owner)?),
+        None => None,
+    };
+    let create_sql = normalize::create_statement(scx, Statement::CreateSequence(stmt.clone()))?;
+
+    Ok(Plan::CreateSequence(CreateSequencePlan {
+        name,
+        sequence: Sequence {
+            create_sql,
+            options,
+        },
+        owned_by,
+        if_not_exists: *if_not_exists,
+    }))
+}
+
+/// Resolves the `OWNED BY` target of a sequence to a table and column index.
+fn plan_sequence_owner(
+    scx: &StatementContext,
+    owner: &SequenceOwner<Aug>,
+) -> Result<(GlobalId, usize), PlanError> {
+    let table = scx.get_item_by_resolved_name(&owner.table)?;
+    if table.item_type() != CatalogItemType::Table {
+        sql_bail!(
+            "sequence cannot be owned by {} {}",
+            table.item_type(),
+            scx.catalog.minimal_qualification(table.name())
+        );
+    }
+    if table.id().is_system() {
+        sql_bail!("sequence cannot be owned by a system table");
+    }
+    let desc = table.desc(&scx.catalog.resolve_full_name(table.name()))?;
+    let column = normalize::column_name(owner.column.clone());
+    let Some(i) = desc.iter_names().position(|name| *name == column) else {
+        sql_bail!(
+            "column {} of relation {} does not exist",
+            column.as_str().quoted(),
+            scx.catalog.minimal_qualification(table.name())
+        );
+    };
+    for id in table.used_by() {
+        let dep = scx.catalog.get_item(id);
+        if let Some((_, Some((_, owning_column)))) = dep.sequence_details() {
+            if owning_column == i {
+                sql_bail!(
+                    "column {} of relation {} already owns sequence {}",
+                    column.as_str().quoted(),
+                    scx.catalog.minimal_qualification(table.name()),
+                    scx.catalog.minimal_qualification(dep.name())
+                );
+            }
+        }
+    }
+    Ok((table.id(), i))
+}
+
+/// Plans the options of a sequence whose values must lie in `[type_min,
+/// type_max]`, the range of the data type named `type_name`.
+///
+/// Unspecified options take on the same defaults as in PostgreSQL. The
+/// `OWNED BY` option, if any, is returned separately.
+fn plan_sequence_options<'a>(
+    options: &'a [SequenceOption<Aug>],
+    type_min: i64,
+    type_max: i64,
+    type_name: &str,
+) -> Result<(SequenceOptions, Option<&'a SequenceOwner<Aug>>), PlanError> {
+    let mut increment = None;
+    let mut min_value = None;
+    let mut max_value = None;
+    let mut start = None;
+    let mut cache = None;
+    let mut cycle = None;
+    let mut owner = None;
+
+    fn set<T>(slot: &mut Option<T>, value: T) -> Result<(), PlanError> {
+        if slot.replace(value).is_some() {
+            sql_bail!("conflicting or redundant options");
+        }
+        Ok(())
+    }
+
+    for option in options {
+        match option {
+            SequenceOption::IncrementBy(n) => set(&mut increment, *n)?,
+            SequenceOption::MinValue(n) => set(&mut min_value, *n)?,
+            SequenceOption::MaxValue(n) => set(&mut max_value, *n)?,
+            SequenceOption::StartWith(n) => set(&mut start, *n)?,
+            SequenceOption::Cache(n) => set(&mut cache, *n)?,
+            SequenceOption::Cycle(b) => set(&mut cycle, *b)?,
+            SequenceOption::OwnedBy(o) => set(&mut owner, o.as_ref())?,
+        }
+    }
+
+    let increment = increment.unwrap_or(1);
+    if increment == 0 {
+        sql_bail!("INCREMENT must not be zero");
+    }
+    let min_value = min_value
+        .flatten()
+        .unwrap_or(if increment > 0 { 1 } else { type_min });
+    let max_value = max_value
+        .flatten()
+        .unwrap_or(if increment > 0 { type_max } else { -1 });
+    if min_value < type_min || min_value > type_max {
+        sql_bail!("MINVALUE ({min_value}) is out of range for sequence data type {type_name}");
+    }
+    if max_value < type_min || max_value > type_max {
+        sql_bail!("MAXVALUE ({max_value}) is out of range for sequence data type {type_name}");
+    }
+    if min_value >= max_value {
+        sql_bail!("MINVALUE ({min_value}) must be less than MAXVALUE ({max_value})");
+    }
+    let start = start.unwrap_or(if increment > 0 { min_value } else { max_value });
+    if start < min_value {
+        sql_bail!("START value ({start}) cannot be less than MINVALUE ({min_value})");
+    }
+    if start > max_value {
+        sql_bail!("START value ({start}) cannot be greater than MAXVALUE ({max_value})");
+    }
+    let cache = cache.unwrap_or(1);
+    let Some(cache) = u64::try_from(cache).ok().filter(|cache| *cache > 0) else {
+        sql_bail!("CACHE ({cache}) must be greater than zero");
+    };
+
+    let options = SequenceOptions {
+        increment,
+        min_value,
+        max_value,
+        start,
+        cache,
+        cycle: cycle.unwrap_or(false),
+    };
+    Ok((options, owner.flatten()))
+}
+
 pub fn describe_create_connection(
     _: &StatementContext,
     _: CreateConnectionStatement<Aug>,
@@ -3773,11 +4572,6 @@ pub fn plan_drop_objects(
         cascade,
     }: DropObjectsStatement,
 ) -> Result<Plan, PlanError> {
-    assert_ne!(
-        object_type,
-        mz_sql_parser::ast::ObjectType::Func,
-        "rejected in parser"
-    );
     let object_type = object_type.into();
 
     let mut referenced_ids = Vec::new();
@@ -3948,7 +4742,11 @@ fn plan_drop_item_inner(
     cascade: bool,
     allow_dropping_subsources: bool,
 ) -> Result<Option<GlobalId>, PlanError> {
-    Ok(match resolve_item(scx, name, if_exists)? {
+    let resolved = match object_type {
+        ObjectType::Func => resolve_function(scx, name, if_exists)?,
+        _ => resolve_item(scx, name, if_exists)?,
+    };
+    Ok(match resolved {
         Some(catalog_item) => {
             if catalog_item.id().is_system() {
                 sql_bail!(
@@ -4070,7 +4868,9 @@ fn dependency_prevents_drop(object_type: ObjectType, dep: &dyn CatalogItem) -> b
             | CatalogItemType::Type
             | CatalogItemType::Secret
             | CatalogItemType::Connection => true,
-            CatalogItemType::Index => false,
+            // Indexes and owned sequences are dropped along with the object
+            // they belong to.
+            CatalogItemType::Index | CatalogItemType::Sequence => false,
         },
     }
 }
@@ -4612,6 +5412,74 @@ pub fn plan_alter_item_set_cluster(
     }
 }
 
+pub fn describe_alter_retain_history(
+    _: &StatementContext,
+    _: AlterRetainHistoryStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_retain_history(
+    scx: &StatementContext,
+    AlterRetainHistoryStatement {
+        object_type,
+        if_exists,
+        name,
+        history,
+    }: AlterRetainHistoryStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let object_type = object_type.into();
+
+    // Only collections that are durably recorded support `RETAIN HISTORY`.
+    match object_type {
+        ObjectType::MaterializedView | ObjectType::Source | ObjectType::Table => {}
+        _ => bail_unsupported!(format!("ALTER {object_type} SET (RETAIN HISTORY)")),
+    }
+
+    match resolve_item(scx, name.clone(), if_exists)? {
+        Some(entry) => {
+            let catalog_object_type: ObjectType = entry.item_type().into();
+            if catalog_object_type != object_type {
+                sql_bail!("Cannot modify {} as {object_type}", entry.item_type());
+            }
+            if entry.id().is_system() {
+                sql_bail!(
+                    "cannot modify the retained history of system {} {}",
+                    entry.item_type(),
+                    scx.catalog.resolve_full_name(entry.name())
+                );
+            }
+
+            let window = match &history {
+                Some(value) => {
+                    let window = Duration::try_from_value(value.clone())
+                        .map_err(|e| sql_err!("invalid RETAIN HISTORY: {e}"))?;
+                    plan_retain_history(scx, Some(window))?
+                }
+                None => {
+                    scx.require_feature_flag(&vars::ENABLE_RETAIN_HISTORY)?;
+                    None
+                }
+            };
+
+            Ok(Plan::AlterRetainHistory(AlterRetainHistoryPlan {
+                id: entry.id(),
+                object_type,
+                value: history,
+                window,
+            }))
+        }
+        None => {
+            scx.catalog.add_notice(PlanNotice::ObjectDoesNotExist {
+                name: name.to_ast_string(),
+                object_type,
+            });
+
+            Ok(Plan::AlterNoop(AlterNoopPlan { object_type }))
+        }
+    }
+}
+
 pub fn describe_alter_object_rename(
     _: &StatementContext,
     _: AlterObjectRenameStatement,
@@ -5360,6 +6228,19 @@ pub(crate) fn resolve_item<'a>(
     }
 }
 
+pub(crate) fn resolve_function<'a>(
+    scx: &'a StatementContext,
+    name: UnresolvedItemName,
+    if_exists: bool,
+) -> Result<Option<&'a dyn CatalogItem>, PlanError> {
+    let name = normalize::unresolved_item_name(name)?;
+    match scx.catalog.resolve_function(&name) {
+        Ok(item) => Ok(Some(item)),
+        Err(_) if if_exists => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
 /// Returns an error if the given cluster is a linked cluster
 pub(crate) fn ensure_cluster_is_not_linked(
     scx: &StatementContext,
@@ -16,7 +16,7 @@ use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet};
 
 use itertools::Itertools;
-use mz_expr::MirRelationExpr;
+use mz_expr::{MirRelationExpr, MirScalarExpr};
 use mz_pgcopy::{CopyCsvFormatParams, CopyFormatParams, CopyTextFormatParams};
 use mz_repr::adt::numeric::NumericMaxScale;
 use mz_repr::explain::{ExplainConfig, ExplainFormat};
@@ -26,9 +26,9 @@ use mz_sql_parser::ast::{ExplainTimestampStatement, Expr, OrderByExpr, Subscribe
 use crate::ast::display::AstDisplay;
 use crate::ast::{
     AstInfo, CopyDirection, CopyOption, CopyOptionName, CopyRelation, CopyStatement, CopyTarget,
-    DeleteStatement, ExplainPlanStatement, ExplainStage, Explainee, Ident, InsertStatement, Query,
-    SelectStatement, SubscribeOption, SubscribeOptionName, SubscribeRelation, SubscribeStatement,
-    UpdateStatement,
+    DeleteStatement, ExplainPlanStatement, ExplainStage, Explainee, Ident, InsertStatement,
+    MergeAction, MergeStatement, OnConflictAction, Query, SelectStatement, SubscribeOption,
+    SubscribeOptionName, SubscribeRelation, SubscribeStatement, UpdateStatement,
 };
 use crate::catalog::CatalogItemType;
 use crate::names::{Aug, ResolvedItemName};
@@ -56,10 +56,15 @@ pub fn describe_insert(
         table_name,
         columns,
         source,
+        on_conflict,
         returning,
     }: InsertStatement<Aug>,
 ) -> Result<StatementDesc, PlanError> {
-    let (_, _, returning) = query::plan_insert_query(scx, table_name, columns, source, returning)?;
+    let (_, expr, returning, _) =
+        query::plan_insert_query(scx, table_name.clone(), columns, source, returning)?;
+    if let Some(on_conflict) = on_conflict {
+        query::plan_insert_on_conflict_query(scx, table_name, expr, on_conflict)?;
+    }
     let desc = if returning.expr.is_empty() {
         None
     } else {
@@ -74,24 +79,39 @@ pub fn plan_insert(
         table_name,
         columns,
         source,
+        on_conflict,
         returning,
     }: InsertStatement<Aug>,
     params: &Params,
 ) -> Result<Plan, PlanError> {
-    let (id, mut expr, returning) =
-        query::plan_insert_query(scx, table_name, columns, source, returning)?;
-    expr.bind_parameters(params)?;
-    let expr = expr.optimize_and_lower(&scx.into())?;
+    let (id, mut expr, returning, sequence_defaults) =
+        query::plan_insert_query(scx, table_name.clone(), columns, source, returning)?;
     let returning = returning
         .expr
         .into_iter()
         .map(|expr| expr.lower_uncorrelated())
         .collect::<Result<Vec<_>, _>>()?;
 
+    if let Some(on_conflict) = on_conflict {
+        scx.require_feature_flag(&vars::ENABLE_ON_CONFLICT_AND_MERGE)?;
+        if !sequence_defaults.is_empty() {
+            bail_unsupported!("INSERT ... ON CONFLICT omitting columns that default to nextval");
+        }
+        let kind = MutationKind::Upsert {
+            update: matches!(on_conflict.action, OnConflictAction::DoUpdate { .. }),
+        };
+        let rtw_plan = query::plan_insert_on_conflict_query(scx, table_name, expr, on_conflict)?;
+        return plan_read_then_write(kind, scx, params, rtw_plan, returning);
+    }
+
+    expr.bind_parameters(params)?;
+    let expr = expr.optimize_and_lower(&scx.into())?;
+
     Ok(Plan::Insert(InsertPlan {
         id,
         values: expr,
         returning,
+        sequence_defaults,
     }))
 }
 
@@ -109,7 +129,7 @@ pub fn plan_delete(
     params: &Params,
 ) -> Result<Plan, PlanError> {
     let rtw_plan = query::plan_delete_query(scx, stmt)?;
-    plan_read_then_write(MutationKind::Delete, scx, params, rtw_plan)
+    plan_read_then_write(MutationKind::Delete, scx, params, rtw_plan, Vec::new())
 }
 
 pub fn describe_update(
@@ -126,7 +146,39 @@ pub fn plan_update(
     params: &Params,
 ) -> Result<Plan, PlanError> {
     let rtw_plan = query::plan_update_query(scx, stmt)?;
-    plan_read_then_write(MutationKind::Update, scx, params, rtw_plan)
+    plan_read_then_write(MutationKind::Update, scx, params, rtw_plan, Vec::new())
+}
+
+pub fn describe_merge(
+    scx: &StatementContext,
+    stmt: MergeStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    query::plan_merge_query(scx, stmt)?;
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_merge(
+    scx: &StatementContext,
+    stmt: MergeStatement<Aug>,
+    params: &Params,
+) -> Result<Plan, PlanError> {
+    scx.require_feature_flag(&vars::ENABLE_ON_CONFLICT_AND_MERGE)?;
+    let kind = MutationKind::Merge {
+        insert: stmt
+            .clauses
+            .iter()
+            .any(|clause| matches!(clause.action, MergeAction::Insert { .. })),
+        update: stmt
+            .clauses
+            .iter()
+            .any(|clause| matches!(clause.action, MergeAction::Update { .. })),
+        delete: stmt
+            .clauses
+            .iter()
+            .any(|clause| matches!(clause.action, MergeAction::Delete)),
+    };
+    let rtw_plan = query::plan_merge_query(scx, stmt)?;
+    plan_read_then_write(kind, scx, params, rtw_plan, Vec::new())
 }
 
 pub fn plan_read_then_write(
@@ -139,6 +191,7 @@ pub fn plan_read_then_write(
         finishing,
         assignments,
     }: query::ReadThenWritePlan,
+    returning: Vec<MirScalarExpr>,
 ) -> Result<Plan, PlanError> {
     selection.bind_parameters(params)?;
     let selection = selection.optimize_and_lower(&scx.into())?;
@@ -155,7 +208,8 @@ pub fn plan_read_then_write(
         finishing,
         assignments: assignments_outer,
         kind,
-        returning: Vec::new(),
+        returning,
+        sequence_defaults: BTreeMap::new(),
     }))
 }
 
@@ -191,6 +245,7 @@ pub fn plan_select(
         when,
         finishing,
         copy_to,
+        hints: scx.optimizer_hints.borrow().clone(),
     }))
 }
 
@@ -331,6 +386,7 @@ pub fn plan_explain_plan(
                 raw_plan,
                 row_set_finishing,
                 broken,
+                hints: scx.optimizer_hints.borrow().clone(),
             }
         }
     };
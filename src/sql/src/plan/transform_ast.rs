@@ -452,6 +452,7 @@ impl<'ast> VisitMut<'ast, Aug> for FuncRewriter<'_> {
                 function,
                 alias,
                 with_ordinality,
+                ..
             } => {
                 self.rewriting_table_factor = true;
                 // Functions that get rewritten must be rewritten as exprs
@@ -616,6 +617,7 @@ impl<'a> Desugarer<'a> {
                                 columns: vec![binding.clone()],
                                 strict: true,
                             }),
+                            column_defs: None,
                             with_ordinality: false,
                         },
                         joins: vec![],
@@ -454,7 +454,13 @@ static VALID_CASTS: Lazy<BTreeMap<(ScalarBaseType, ScalarBaseType), CastImpl>> =
         (String, Interval) => Explicit: CastStringToInterval(func::CastStringToInterval),
         (String, Bytes) => Explicit: CastStringToBytes(func::CastStringToBytes),
         (String, Jsonb) => Explicit: CastStringToJsonb(func::CastStringToJsonb),
+        (String, JsonPath) => Explicit: CastStringToJsonPath(func::CastStringToJsonPath),
+        (String, TsVector) => Explicit: CastStringToTsVector(func::CastStringToTsVector),
+        (String, TsQuery) => Explicit: CastStringToTsQuery(func::CastStringToTsQuery),
         (String, Uuid) => Explicit: CastStringToUuid(func::CastStringToUuid),
+        (String, Inet) => Explicit: CastStringToInet(func::CastStringToInet),
+        (String, Cidr) => Explicit: CastStringToCidr(func::CastStringToCidr),
+        (String, MacAddr) => Explicit: CastStringToMacAddr(func::CastStringToMacAddr),
         (String, Array) => Explicit: CastTemplate::new(|ecx, ccx, from_type, to_type| {
             let return_ty = to_type.clone();
             let to_el_type = to_type.unwrap_array_element_type();
@@ -637,9 +643,29 @@ static VALID_CASTS: Lazy<BTreeMap<(ScalarBaseType, ScalarBaseType), CastImpl>> =
         }),
         (Jsonb, String) => Assignment: CastJsonbToString(func::CastJsonbToString),
 
+        // JSONPATH
+        (JsonPath, String) => Assignment: CastVarCharToString(func::CastVarCharToString),
+
+        // TSVECTOR
+        (TsVector, String) => Assignment: CastVarCharToString(func::CastVarCharToString),
+
+        // TSQUERY
+        (TsQuery, String) => Assignment: CastVarCharToString(func::CastVarCharToString),
+
         // UUID
         (Uuid, String) => Assignment: CastUuidToString(func::CastUuidToString),
 
+        // INET
+        (Inet, String) => Assignment: CastInetToString(func::CastInetToString),
+        (Inet, Cidr) => Assignment: CastInetToCidr(func::CastInetToCidr),
+
+        // CIDR
+        (Cidr, String) => Assignment: CastCidrToString(func::CastCidrToString),
+        (Cidr, Inet) => Implicit: CastCidrToInet(func::CastCidrToInet),
+
+        // MACADDR
+        (MacAddr, String) => Assignment: CastMacAddrToString(func::CastMacAddrToString),
+
         // Numeric
         (Numeric, Numeric) => Assignment: CastTemplate::new(|_ecx, _ccx, _from_type, to_type| {
             let scale = to_type.unwrap_numeric_max_scale();
@@ -9,9 +9,11 @@
 
 //! Provides tooling to handle `WITH` options.
 
+use std::time::Duration;
+
 use mz_repr::adt::interval::Interval;
 use mz_repr::{strconv, GlobalId};
-use mz_sql_parser::ast::{KafkaBroker, ReplicaDefinition};
+use mz_sql_parser::ast::{KafkaBroker, RefreshOptionValue, ReplicaDefinition};
 use mz_storage_client::types::connections::StringOrSecret;
 use serde::{Deserialize, Serialize};
 
@@ -197,6 +199,26 @@ impl ImpliedValue for OptionalInterval {
     }
 }
 
+impl TryFromValue<WithOptionValue<Aug>> for Duration {
+    fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
+        match v {
+            WithOptionValue::RetainHistoryFor(value) => {
+                Ok(Interval::try_from_value(value)?.duration()?)
+            }
+            _ => sql_bail!("must provide a retention period, e.g. FOR '1 day'"),
+        }
+    }
+    fn name() -> String {
+        "retention period".to_string()
+    }
+}
+
+impl ImpliedValue for Duration {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide a retention period, e.g. FOR '1 day'")
+    }
+}
+
 impl TryFromValue<Value> for String {
     fn try_from_value(v: Value) -> Result<Self, PlanError> {
         match v {
@@ -410,7 +432,9 @@ impl<V: TryFromValue<Value>, T: AstInfo + std::fmt::Debug> TryFromValue<WithOpti
             | WithOptionValue::Secret(_)
             | WithOptionValue::DataType(_)
             | WithOptionValue::ClusterReplicas(_)
-            | WithOptionValue::ConnectionKafkaBroker(_) => sql_bail!(
+            | WithOptionValue::ConnectionKafkaBroker(_)
+            | WithOptionValue::RetainHistoryFor(_)
+            | WithOptionValue::Refresh(_) => sql_bail!(
                 "incompatible value types: cannot convert {} to {}",
                 match v {
                     WithOptionValue::Sequence(_) => "sequences",
@@ -420,6 +444,8 @@ impl<V: TryFromValue<Value>, T: AstInfo + std::fmt::Debug> TryFromValue<WithOpti
                     WithOptionValue::DataType(_) => "data types",
                     WithOptionValue::ClusterReplicas(_) => "cluster replicas",
                     WithOptionValue::ConnectionKafkaBroker(_) => "connection kafka brokers",
+                    WithOptionValue::RetainHistoryFor(_) => "retention periods",
+                    WithOptionValue::Refresh(_) => "refresh option values",
                     _ => unreachable!(),
                 },
                 V::name()
@@ -461,6 +487,24 @@ impl ImpliedValue for Vec<ReplicaDefinition<Aug>> {
     }
 }
 
+impl TryFromValue<WithOptionValue<Aug>> for RefreshOptionValue<Aug> {
+    fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
+        match v {
+            WithOptionValue::Refresh(refresh) => Ok(refresh),
+            _ => sql_bail!("cannot use value as a refresh option value"),
+        }
+    }
+    fn name() -> String {
+        "refresh option value".to_string()
+    }
+}
+
+impl ImpliedValue for RefreshOptionValue<Aug> {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide a refresh option value")
+    }
+}
+
 impl TryFromValue<WithOptionValue<Aug>> for Vec<KafkaBroker<Aug>> {
     fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
         let mut out = vec![];
@@ -19,8 +19,10 @@ use std::sync::Arc;
 use anyhow::anyhow;
 use mz_ccsr::{Client, GetByIdError, GetBySubjectError, Schema as CcsrSchema};
 use mz_kafka_util::client::MzClientContext;
+use mz_ore::collections::CollectionExt;
 use mz_ore::error::ErrorExt;
 use mz_ore::str::StrExt;
+use mz_postgres_util::desc::PostgresTableDesc;
 use mz_proto::RustType;
 use mz_repr::{strconv, GlobalId};
 use mz_sql_parser::ast::display::AstDisplay;
@@ -43,9 +45,9 @@ use tracing::info;
 use uuid::Uuid;
 
 use crate::ast::{
-    AvroSchema, CreateSourceConnection, CreateSourceFormat, CreateSourceStatement,
-    CreateSourceSubsource, CreateSubsourceStatement, CsrConnectionAvro, CsrConnectionProtobuf,
-    Format, ProtobufSchema, ReferencedSubsources, Value, WithOptionValue,
+    AvroSchema, CreateSourceConnection, CreateSourceFormat, CreateSourceOptionName,
+    CreateSourceStatement, CreateSourceSubsource, CreateSubsourceStatement, CsrConnectionAvro,
+    CsrConnectionProtobuf, Format, ProtobufSchema, ReferencedSubsources, Value, WithOptionValue,
 };
 use crate::catalog::{ErsatzCatalog, SessionCatalog};
 use crate::kafka_util::KafkaConfigOptionExtracted;
@@ -53,6 +55,7 @@ use crate::names::Aug;
 use crate::plan::error::PlanError;
 use crate::plan::statement::ddl::load_generator_ast_to_generator;
 use crate::plan::StatementContext;
+use crate::session::vars;
 use crate::{kafka_util, normalize};
 
 mod postgres;
@@ -331,8 +334,11 @@ async fn purify_create_source(
                 publication,
                 mut text_columns,
                 details,
+                schema_changes,
                 ..
             } = options.clone().try_into()?;
+            let additive_schema_changes =
+                crate::plan::statement::ddl::plan_pg_schema_changes(schema_changes.as_deref())?;
             let publication = publication
                 .ok_or_else(|| sql_err!("POSTGRES CONNECTION must specify PUBLICATION"))?;
 
@@ -457,6 +463,7 @@ async fn purify_create_source(
                 text_cols_dict,
                 get_transient_subsource_id,
                 &publication_tables,
+                additive_schema_changes,
             )?;
 
             *referenced_subsources = Some(ReferencedSubsources::SubsetTables(targeted_subsources));
@@ -481,6 +488,10 @@ async fn purify_create_source(
         CreateSourceConnection::LoadGenerator { generator, options } => {
             let scx = StatementContext::new(None, &catalog);
 
+            if *generator == mz_sql_parser::ast::LoadGenerator::Custom {
+                scx.require_feature_flag(&vars::ENABLE_LOAD_GENERATOR_CUSTOM)?;
+            }
+
             let (_load_generator, available_subsources) =
                 load_generator_ast_to_generator(generator, options)?;
 
@@ -611,9 +622,39 @@ async fn purify_create_source(
 
     purify_source_format(&catalog, format, connection, envelope, &connection_context).await?;
 
+    let retain_history = stmt
+        .with_options
+        .iter()
+        .find(|o| o.name == CreateSourceOptionName::RetainHistory)
+        .and_then(|o| o.value.clone());
+    propagate_retain_history(retain_history, &mut subsources);
+
     Ok((subsources, Statement::CreateSource(stmt)))
 }
 
+/// Copies the `RETAIN HISTORY` of a source onto the subsources that ingest
+/// its data, so that their history is retained for as long as the source's.
+fn propagate_retain_history(
+    retain_history: Option<WithOptionValue<Aug>>,
+    subsources: &mut [(GlobalId, CreateSubsourceStatement<Aug>)],
+) {
+    let Some(retain_history) = retain_history else {
+        return;
+    };
+    for (_, subsource) in subsources {
+        if subsource
+            .with_options
+            .iter()
+            .any(|o| o.name == CreateSubsourceOptionName::References)
+        {
+            subsource.with_options.push(CreateSubsourceOption {
+                name: CreateSubsourceOptionName::RetainHistory,
+                value: Some(retain_history.clone()),
+            });
+        }
+    }
+}
+
 /// Equivalent to `purify_create_source` but for `AlterSourceStatement`.
 ///
 /// On success, returns the `GlobalId` and `CreateSubsourceStatement`s for any
@@ -639,7 +680,7 @@ async fn purify_alter_source(
     } = &mut stmt;
 
     // Get connection
-    let pg_source_connection = {
+    let (pg_source_connection, retain_history) = {
         // Get name.
         let item = match scx.resolve_item(RawItemName::Name(source_name.clone())) {
             Ok(item) => item,
@@ -662,8 +703,28 @@ async fn purify_alter_source(
             return Ok((vec![], Statement::AlterSource(stmt)));
         }
 
+        // New subsources retain as much history as the source, as do the
+        // subsources created along with it.
+        let retain_history = match crate::parse::parse(item.create_sql())
+            .expect("create_sql is valid")
+            .into_element()
+            .ast
+        {
+            Statement::CreateSource(stmt) => stmt
+                .with_options
+                .into_iter()
+                .find(|o| o.name == CreateSourceOptionName::RetainHistory)
+                .and_then(|o| match o.value {
+                    Some(WithOptionValue::RetainHistoryFor(value)) => {
+                        Some(WithOptionValue::RetainHistoryFor(value))
+                    }
+                    _ => None,
+                }),
+            _ => None,
+        };
+
         match desc.connection {
-            GenericSourceConnection::Postgres(pg_connection) => pg_connection,
+            GenericSourceConnection::Postgres(pg_connection) => (pg_connection, retain_history),
             _ => sql_bail!(
                 "{} is a {} source, which does not support ALTER TABLE...ADD SUBSOURCES",
                 scx.catalog.minimal_qualification(item.name()),
@@ -782,12 +843,13 @@ async fn purify_alter_source(
         text_cols_option.value = Some(WithOptionValue::Sequence(seq));
     }
 
-    let (named_subsources, new_subsources) = postgres::generate_targeted_subsources(
+    let (named_subsources, mut new_subsources) = postgres::generate_targeted_subsources(
         &scx,
         validated_requested_subsources,
         text_cols_dict,
         get_transient_subsource_id,
         &publication_tables,
+        pg_source_connection.additive_schema_changes,
     )?;
 
     *targeted_subsources = named_subsources;
@@ -834,9 +896,27 @@ async fn purify_alter_source(
         new_details.into_proto().encode_to_vec(),
     ))));
 
+    propagate_retain_history(retain_history, &mut new_subsources);
+
     Ok((new_subsources, Statement::AlterSource(stmt)))
 }
 
+/// Appends the columns that were added to the upstream table of a subsource of a PostgreSQL
+/// source with `SCHEMA CHANGES 'additive'` to the subsource's definition `stmt`, after the
+/// schema of the upstream table changed from `expected_table` to `table`.
+///
+/// Unlike purification, this does not need to connect to the upstream database, because the
+/// caller already observed `table`.
+pub fn add_postgres_subsource_columns(
+    catalog: &dyn SessionCatalog,
+    stmt: &mut CreateSubsourceStatement<Aug>,
+    expected_table: &PostgresTableDesc,
+    table: &PostgresTableDesc,
+) -> Result<(), PlanError> {
+    let scx = StatementContext::new(None, catalog);
+    postgres::add_subsource_columns(&scx, &mut stmt.columns, expected_table, table)
+}
+
 async fn purify_source_format(
     catalog: &dyn SessionCatalog,
     format: &mut CreateSourceFormat<Aug>,
@@ -12,7 +12,7 @@
 use std::collections::{BTreeMap, BTreeSet};
 
 use itertools::Itertools;
-use mz_postgres_util::desc::PostgresTableDesc;
+use mz_postgres_util::desc::{PostgresColumnDesc, PostgresTableDesc};
 use mz_postgres_util::Config;
 use mz_repr::adt::system::Oid;
 use mz_repr::GlobalId;
@@ -26,6 +26,7 @@ use mz_sql_parser::ast::{CreateSourceSubsource, UnresolvedItemName};
 use crate::catalog::ErsatzCatalog;
 use crate::names::{Aug, PartialItemName};
 use crate::normalize;
+use crate::plan::statement::ddl::pg_source_type_mod;
 use crate::plan::{PlanError, StatementContext};
 
 pub(super) fn derive_catalog_from_publication_tables<'a>(
@@ -179,6 +180,7 @@ pub(crate) fn generate_targeted_subsources<F>(
     mut text_cols_dict: BTreeMap<u32, BTreeSet<String>>,
     mut get_transient_subsource_id: F,
     publication_tables: &[PostgresTableDesc],
+    additive_schema_changes: bool,
 ) -> Result<
     (
         Vec<CreateSourceSubsource<Aug>>,
@@ -204,7 +206,10 @@ where
             let name = Ident::new(c.name.clone());
             let ty = match &text_cols_dict {
                 Some(names) if names.contains(&c.name) => mz_pgrepr::Type::Text,
-                _ => match mz_pgrepr::Type::from_oid_and_typmod(c.type_oid, c.type_mod) {
+                _ => match mz_pgrepr::Type::from_oid_and_typmod(
+                    c.type_oid,
+                    pg_source_type_mod(c.type_oid, c.type_mod, additive_schema_changes),
+                ) {
                     Ok(t) => t,
                     Err(_) => {
                         let mut full_name = upstream_name.0.clone();
@@ -218,22 +223,7 @@ where
                 },
             };
 
-            let data_type = scx.resolve_type(ty)?;
-            let mut options = vec![];
-
-            if !c.nullable {
-                options.push(mz_sql_parser::ast::ColumnOptionDef {
-                    name: None,
-                    option: mz_sql_parser::ast::ColumnOption::NotNull,
-                });
-            }
-
-            columns.push(ColumnDef {
-                name,
-                data_type,
-                collation: None,
-                options,
-            });
+            columns.push(subsource_column(scx, c, ty)?);
         }
 
         let mut constraints = vec![];
@@ -332,3 +322,59 @@ where
 
     Ok((targeted_subsources, subsources))
 }
+
+/// Describes the upstream column `column`, ingested as type `ty`, as a column of a subsource.
+fn subsource_column(
+    scx: &StatementContext,
+    column: &PostgresColumnDesc,
+    ty: mz_pgrepr::Type,
+) -> Result<ColumnDef<Aug>, PlanError> {
+    let data_type = scx.resolve_type(ty)?;
+    let mut options = vec![];
+
+    if !column.nullable {
+        options.push(mz_sql_parser::ast::ColumnOptionDef {
+            name: None,
+            option: mz_sql_parser::ast::ColumnOption::NotNull,
+        });
+    }
+
+    Ok(ColumnDef {
+        name: Ident::new(column.name.clone()),
+        data_type,
+        collation: None,
+        options,
+    })
+}
+
+/// Appends the columns that were added to the upstream table of a subsource of a source that
+/// absorbs additive schema changes, and whose schema changed from `expected_table` to `table`,
+/// to the subsource's `columns`.
+pub(super) fn add_subsource_columns(
+    scx: &StatementContext,
+    columns: &mut Vec<ColumnDef<Aug>>,
+    expected_table: &PostgresTableDesc,
+    table: &PostgresTableDesc,
+) -> Result<(), PlanError> {
+    expected_table
+        .determine_additive_compatibility(table)
+        .map_err(|e| sql_err!("{}", e))?;
+
+    for column in &table.columns[expected_table.columns.len()..] {
+        let ty = mz_pgrepr::Type::from_oid_and_typmod(
+            column.type_oid,
+            pg_source_type_mod(column.type_oid, column.type_mod, true),
+        )
+        .map_err(|_| {
+            sql_err!(
+                "new column {} of source table {} has unsupported type with OID {}",
+                column.name,
+                table.name,
+                column.type_oid
+            )
+        })?;
+        columns.push(subsource_column(scx, column, ty)?);
+    }
+
+    Ok(())
+}
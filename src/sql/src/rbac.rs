@@ -178,6 +178,8 @@ fn requires_item_usage_privileges(plan: &Plan) -> bool {
         | Plan::CreateSource(_)
         | Plan::CreateSources(_)
         | Plan::CreateSecret(_)
+        | Plan::CreateSequence(_)
+        | Plan::CreateFunction(_)
         | Plan::CreateSink(_)
         | Plan::CreateTable(_)
         | Plan::CreateView(_)
@@ -186,6 +188,7 @@ fn requires_item_usage_privileges(plan: &Plan) -> bool {
         | Plan::CreateType(_)
         | Plan::Comment(_)
         | Plan::DiscardTemp
+        | Plan::DiscardSequences
         | Plan::DiscardAll
         | Plan::DropObjects(_)
         | Plan::DropOwned(_)
@@ -210,6 +213,7 @@ fn requires_item_usage_privileges(plan: &Plan) -> bool {
         | Plan::AlterIndexSetOptions(_)
         | Plan::AlterIndexResetOptions(_)
         | Plan::AlterSetCluster(_)
+        | Plan::AlterRetainHistory(_)
         | Plan::AlterSink(_)
         | Plan::AlterSource(_)
         | Plan::PurifiedAlterSource { .. }
@@ -356,6 +360,11 @@ pub fn generate_required_role_membership(
             }
             roles
         }
+        Plan::SideEffectingFunc(
+            plan::SideEffectingFunc::Nextval { .. }
+            | plan::SideEffectingFunc::Currval { .. }
+            | plan::SideEffectingFunc::Setval { .. },
+        ) => Vec::new(),
         Plan::CreateConnection(_)
         | Plan::CreateDatabase(_)
         | Plan::CreateSchema(_)
@@ -365,6 +374,8 @@ pub fn generate_required_role_membership(
         | Plan::CreateSource(_)
         | Plan::CreateSources(_)
         | Plan::CreateSecret(_)
+        | Plan::CreateSequence(_)
+        | Plan::CreateFunction(_)
         | Plan::CreateSink(_)
         | Plan::CreateTable(_)
         | Plan::CreateView(_)
@@ -373,6 +384,7 @@ pub fn generate_required_role_membership(
         | Plan::CreateType(_)
         | Plan::Comment(_)
         | Plan::DiscardTemp
+        | Plan::DiscardSequences
         | Plan::DiscardAll
         | Plan::DropObjects(_)
         | Plan::EmptyQuery
@@ -398,6 +410,7 @@ pub fn generate_required_role_membership(
         | Plan::AlterCluster(_)
         | Plan::AlterNoop(_)
         | Plan::AlterSetCluster(_)
+        | Plan::AlterRetainHistory(_)
         | Plan::AlterIndexSetOptions(_)
         | Plan::AlterIndexResetOptions(_)
         | Plan::AlterSink(_)
@@ -437,10 +450,13 @@ fn generate_required_ownership(plan: &Plan) -> Vec<ObjectId> {
         | Plan::CreateSource(_)
         | Plan::CreateSources(_)
         | Plan::CreateSecret(_)
+        | Plan::CreateSequence(plan::CreateSequencePlan { owned_by: None, .. })
+        | Plan::CreateFunction(_)
         | Plan::CreateSink(_)
         | Plan::CreateTable(_)
         | Plan::CreateType(_)
         | Plan::DiscardTemp
+        | Plan::DiscardSequences
         | Plan::DiscardAll
         | Plan::EmptyQuery
         | Plan::ShowAllVariables
@@ -482,6 +498,10 @@ fn generate_required_ownership(plan: &Plan) -> Vec<ObjectId> {
         | Plan::SideEffectingFunc(_) => Vec::new(),
         Plan::CreateClusterReplica(plan) => vec![ObjectId::Cluster(plan.cluster_id)],
         Plan::CreateIndex(plan) => vec![ObjectId::Item(plan.index.on)],
+        Plan::CreateSequence(plan::CreateSequencePlan {
+            owned_by: Some((table, _)),
+            ..
+        }) => vec![ObjectId::Item(*table)],
         Plan::CreateView(plan::CreateViewPlan { replace, .. })
         | Plan::CreateMaterializedView(plan::CreateMaterializedViewPlan { replace, .. }) => replace
             .map(|id| vec![ObjectId::Item(id)])
@@ -511,6 +531,7 @@ fn generate_required_ownership(plan: &Plan) -> Vec<ObjectId> {
         Plan::AlterSetCluster(plan) => {
             vec![ObjectId::Item(plan.id)]
         }
+        Plan::AlterRetainHistory(plan) => vec![ObjectId::Item(plan.id)],
         Plan::AlterItemRename(plan) => vec![ObjectId::Item(plan.id)],
         Plan::AlterSecret(plan) => vec![ObjectId::Item(plan.id)],
         Plan::RotateKeys(plan) => vec![ObjectId::Item(plan.id)],
@@ -724,7 +745,14 @@ fn generate_required_privileges(
             name,
             secret: _,
             if_not_exists: _,
-        }) => vec![(
+        })
+        | Plan::CreateSequence(plan::CreateSequencePlan {
+            name,
+            sequence: _,
+            owned_by: _,
+            if_not_exists: _,
+        })
+        | Plan::CreateFunction(plan::CreateFunctionPlan { name, function: _ }) => vec![(
             SystemObjectId::Object(name.qualifiers.clone().into()),
             AclMode::CREATE,
             role_id,
@@ -758,6 +786,7 @@ fn generate_required_privileges(
             name,
             table: _,
             if_not_exists: _,
+            sequences: _,
         }) => {
             vec![(
                 SystemObjectId::Object(name.qualifiers.clone().into()),
@@ -899,6 +928,7 @@ fn generate_required_privileges(
             when: _,
             finishing: _,
             copy_to: _,
+            hints: _,
         }) => {
             let mut privileges =
                 generate_read_privileges(catalog, resolved_ids.0.iter().cloned(), role_id);
@@ -967,6 +997,7 @@ fn generate_required_privileges(
             id,
             values,
             returning,
+            sequence_defaults,
         }) => {
             let schema_id: ObjectId = catalog.get_item(id).name().qualifiers.clone().into();
             let mut privileges = vec![
@@ -996,6 +1027,17 @@ fn generate_required_privileges(
                 &mut seen,
             ));
 
+            // Filling in omitted columns advances their sequences.
+            for sequence in sequence_defaults.values() {
+                privileges.extend_from_slice(&generate_sequence_privileges(
+                    catalog,
+                    *sequence,
+                    AclMode::USAGE,
+                    role_id,
+                    &mut seen,
+                ));
+            }
+
             if let Some(privilege) =
                 generate_cluster_usage_privileges(values, target_cluster_id, role_id)
             {
@@ -1020,11 +1062,36 @@ fn generate_required_privileges(
             assignments,
             kind,
             returning,
+            sequence_defaults,
         }) => {
             let acl_mode = match kind {
                 MutationKind::Insert => AclMode::INSERT,
                 MutationKind::Update => AclMode::UPDATE,
                 MutationKind::Delete => AclMode::DELETE,
+                MutationKind::Upsert { update } => {
+                    let mut acl_mode = AclMode::INSERT;
+                    if *update {
+                        acl_mode |= AclMode::UPDATE;
+                    }
+                    acl_mode
+                }
+                MutationKind::Merge {
+                    insert,
+                    update,
+                    delete,
+                } => {
+                    let mut acl_mode = AclMode::empty();
+                    if *insert {
+                        acl_mode |= AclMode::INSERT;
+                    }
+                    if *update {
+                        acl_mode |= AclMode::UPDATE;
+                    }
+                    if *delete {
+                        acl_mode |= AclMode::DELETE;
+                    }
+                    acl_mode
+                }
             };
             let schema_id: ObjectId = catalog.get_item(id).name().qualifiers.clone().into();
             let mut privileges = vec![
@@ -1061,6 +1128,17 @@ fn generate_required_privileges(
                 &mut seen,
             ));
 
+            // Filling in omitted columns advances their sequences.
+            for sequence in sequence_defaults.values() {
+                privileges.extend_from_slice(&generate_sequence_privileges(
+                    catalog,
+                    *sequence,
+                    AclMode::USAGE,
+                    role_id,
+                    &mut seen,
+                ));
+            }
+
             if let Some(privilege) =
                 generate_cluster_usage_privileges(selection, target_cluster_id, role_id)
             {
@@ -1211,6 +1289,7 @@ fn generate_required_privileges(
             config: _,
         })
         | Plan::DiscardTemp
+        | Plan::DiscardSequences
         | Plan::DiscardAll
         | Plan::EmptyQuery
         | Plan::ShowAllVariables
@@ -1236,6 +1315,12 @@ fn generate_required_privileges(
         | Plan::AlterNoop(plan::AlterNoopPlan { object_type: _ })
         | Plan::AlterIndexSetOptions(plan::AlterIndexSetOptionsPlan { id: _, options: _ })
         | Plan::AlterIndexResetOptions(plan::AlterIndexResetOptionsPlan { id: _, options: _ })
+        | Plan::AlterRetainHistory(plan::AlterRetainHistoryPlan {
+            id: _,
+            object_type: _,
+            value: _,
+            window: _,
+        })
         | Plan::AlterSink(plan::AlterSinkPlan { id: _, size: _ })
         | Plan::AlterSource(plan::AlterSourcePlan { id: _, action: _ })
         | Plan::AlterItemRename(plan::AlterItemRenamePlan {
@@ -1284,10 +1369,47 @@ fn generate_required_privileges(
             new_role: _,
             reassign_ids: _,
         })
-        | Plan::SideEffectingFunc(_) => vec![],
+        | Plan::SideEffectingFunc(plan::SideEffectingFunc::PgCancelBackend { .. }) => vec![],
+        Plan::SideEffectingFunc(
+            plan::SideEffectingFunc::Nextval { sequence }
+            | plan::SideEffectingFunc::Currval { sequence },
+        ) => generate_sequence_privileges(
+            catalog,
+            *sequence,
+            AclMode::USAGE,
+            role_id,
+            &mut BTreeSet::new(),
+        ),
+        Plan::SideEffectingFunc(plan::SideEffectingFunc::Setval { sequence, value: _ }) => {
+            generate_sequence_privileges(
+                catalog,
+                *sequence,
+                AclMode::UPDATE,
+                role_id,
+                &mut BTreeSet::new(),
+            )
+        }
     }
 }
 
+/// Generates the privileges required to use `sequence` in the way that
+/// `acl_mode` describes, skipping any that are already in `seen`.
+fn generate_sequence_privileges(
+    catalog: &impl SessionCatalog,
+    sequence: GlobalId,
+    acl_mode: AclMode,
+    role_id: RoleId,
+    seen: &mut BTreeSet<(ObjectId, RoleId)>,
+) -> Vec<(SystemObjectId, AclMode, RoleId)> {
+    let schema_id: ObjectId = catalog.get_item(&sequence).name().qualifiers.clone().into();
+    let mut privileges = Vec::new();
+    if seen.insert((schema_id.clone(), role_id)) {
+        privileges.push((SystemObjectId::Object(schema_id), AclMode::USAGE, role_id));
+    }
+    privileges.push((SystemObjectId::Object(sequence.into()), acl_mode, role_id));
+    privileges
+}
+
 fn generate_required_source_privileges(
     name: &QualifiedItemName,
     data_source: &DataSourceDesc,
@@ -1497,6 +1619,7 @@ pub const fn all_object_privileges(object_type: SystemObjectType) -> AclMode {
         .union(AclMode::UPDATE)
         .union(AclMode::DELETE);
     const USAGE_CREATE_ACL_MODE: AclMode = AclMode::USAGE.union(AclMode::CREATE);
+    const SEQUENCE_ACL_MODE: AclMode = AclMode::USAGE.union(AclMode::SELECT).union(AclMode::UPDATE);
     const ALL_SYSTEM_PRIVILEGES: AclMode = AclMode::CREATE_ROLE
         .union(AclMode::CREATE_DB)
         .union(AclMode::CREATE_CLUSTER);
@@ -1513,6 +1636,7 @@ pub const fn all_object_privileges(object_type: SystemObjectType) -> AclMode {
         SystemObjectType::Object(ObjectType::Cluster) => USAGE_CREATE_ACL_MODE,
         SystemObjectType::Object(ObjectType::ClusterReplica) => EMPTY_ACL_MODE,
         SystemObjectType::Object(ObjectType::Secret) => AclMode::USAGE,
+        SystemObjectType::Object(ObjectType::Sequence) => SEQUENCE_ACL_MODE,
         SystemObjectType::Object(ObjectType::Connection) => AclMode::USAGE,
         SystemObjectType::Object(ObjectType::Database) => USAGE_CREATE_ACL_MODE,
         SystemObjectType::Object(ObjectType::Schema) => USAGE_CREATE_ACL_MODE,
@@ -1542,6 +1666,7 @@ pub const fn default_builtin_object_privilege(object_type: ObjectType) -> MzAclI
         | ObjectType::Cluster
         | ObjectType::ClusterReplica
         | ObjectType::Secret
+        | ObjectType::Sequence
         | ObjectType::Connection
         | ObjectType::Database
         | ObjectType::Func => AclMode::empty(),
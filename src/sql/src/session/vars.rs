@@ -561,6 +561,16 @@ static ALLOWED_CLUSTER_REPLICA_SIZES: Lazy<ServerVar<Vec<Ident>>> = Lazy::new(||
     internal: false,
 });
 
+static DEFAULT_PLAN_REGRESSION_CRITICAL_OBJECTS: Lazy<Vec<Ident>> = Lazy::new(Vec::new);
+static PLAN_REGRESSION_CRITICAL_OBJECTS: Lazy<ServerVar<Vec<Ident>>> = Lazy::new(|| ServerVar {
+    name: UncasedStr::new("plan_regression_critical_objects"),
+    value: &DEFAULT_PLAN_REGRESSION_CRITICAL_OBJECTS,
+    description: "The IDs of the indexes and materialized views whose physical plans must not \
+        regress when upgrading to a new version. The new version refuses to start if the plan \
+        of any of them builds more arrangements than before (Materialize).",
+    internal: true,
+});
+
 /// Controls [`mz_persist_client::cfg::DynamicConfig::blob_target_size`].
 const PERSIST_BLOB_TARGET_SIZE: ServerVar<usize> = ServerVar {
     name: UncasedStr::new("persist_blob_target_size"),
@@ -851,6 +861,16 @@ const PG_REPLICATION_CONNECT_TIMEOUT: ServerVar<Duration> = ServerVar {
     internal: true,
 };
 
+/// Controls how often the coordinator checks PostgreSQL sources for upstream
+/// tables that gained columns.
+const PG_SOURCE_SCHEMA_CHANGE_CHECK_INTERVAL: ServerVar<Duration> = ServerVar {
+    name: UncasedStr::new("pg_source_schema_change_check_interval"),
+    value: &Duration::from_secs(5),
+    description: "Sets how often PostgreSQL sources that allow additive schema changes are \
+    checked for new upstream columns. (Materialize)",
+    internal: true,
+};
+
 static DEFAULT_LOGGING_FILTER: Lazy<CloneableEnvFilter> =
     Lazy::new(|| CloneableEnvFilter::from_str("info").expect("valid EnvFilter"));
 static LOGGING_FILTER: Lazy<ServerVar<CloneableEnvFilter>> = Lazy::new(|| ServerVar {
@@ -1207,6 +1227,14 @@ pub const ENABLE_SESSION_CARDINALITY_ESTIMATES: ServerVar<bool> = ServerVar {
     internal: false,
 };
 
+pub const ENABLE_SESSION_COST_BASED_JOIN_ORDERING: ServerVar<bool> = ServerVar {
+    name: UncasedStr::new("enable_session_cost_based_join_ordering"),
+    value: &false,
+    description: "Feature flag indicating whether to order joins by the estimated size of their \
+    intermediate results; only applies when cardinality estimates are used (Materialize).",
+    internal: false,
+};
+
 static DEFAULT_STATEMENT_LOGGING_SAMPLE_RATE: Lazy<Numeric> = Lazy::new(|| 0.1.into());
 pub static STATEMENT_LOGGING_SAMPLE_RATE: Lazy<ServerVar<Numeric>> = Lazy::new(|| {
     ServerVar {
@@ -1229,6 +1257,27 @@ const ENABLE_MZ_JOIN_CORE: ServerVar<bool> = ServerVar {
     internal: true,
 };
 
+/// Whether compute replicas with a scratch directory should back large merged arrangement
+/// batches by a file on disk.
+const ENABLE_ARRANGEMENT_SPILLING: ServerVar<bool> = ServerVar {
+    name: UncasedStr::new("enable_arrangement_spilling"),
+    value: &false,
+    description: "Feature flag indicating whether compute replicas with disk should spill large \
+         merged arrangement batches to disk (Materialize).",
+    internal: true,
+};
+
+/// The size in bytes from which on merged arrangement batches are spilled to disk.
+/// The default is 256 MiB, so that only the largest and coldest batches of a spine are spilled.
+/// Has no effect unless `enable_arrangement_spilling` is set.
+const ARRANGEMENT_SPILL_THRESHOLD_BYTES: ServerVar<usize> = ServerVar {
+    name: UncasedStr::new("arrangement_spill_threshold_bytes"),
+    value: &(256 << 20),
+    description: "The size in bytes from which on merged arrangement batches are spilled to \
+         disk, if arrangement spilling is enabled (Materialize).",
+    internal: true,
+};
+
 pub const ENABLE_DEFAULT_CONNECTION_VALIDATION: ServerVar<bool> = ServerVar {
     name: UncasedStr::new("enable_default_connection_validation"),
     value: &true,
@@ -1521,6 +1570,27 @@ feature_flags!(
         "CREATE SOURCE ... FROM TEST SCRIPT"
     ),
     (enable_date_bin_hopping, "the date_bin_hopping function"),
+    (enable_load_generator_custom, "`LOAD GENERATOR CUSTOM`"),
+    (
+        enable_pg_additive_schema_changes,
+        "`SCHEMA CHANGES 'additive'` for PostgreSQL sources"
+    ),
+    (
+        enable_table_constraint_enforcement,
+        "enforced PRIMARY KEY, UNIQUE and CHECK constraints on tables"
+    ),
+    (
+        enable_on_conflict_and_merge,
+        "INSERT ... ON CONFLICT and MERGE"
+    ),
+    (
+        enable_upsert_retain_keys,
+        "`RETAIN KEYS FOR` key expiration for `ENVELOPE UPSERT`"
+    ),
+    (
+        enable_source_event_time,
+        "`TIMESTAMP FROM` event-time timestamps for sources"
+    ),
     (
         enable_envelope_debezium_in_subscribe,
         "`ENVELOPE DEBEZIUM (KEY (..))`"
@@ -1538,10 +1608,26 @@ feature_flags!(
         enable_logical_compaction_window,
         "LOGICAL COMPACTION WINDOW"
     ),
+    (
+        enable_retain_history,
+        "`RETAIN HISTORY` for materialized views, sources, and tables"
+    ),
+    (
+        enable_refresh_every_mvs,
+        "`REFRESH` options for materialized views"
+    ),
     (
         enable_monotonic_oneshot_selects,
         "monotonic evaluation of one-shot SELECT queries"
     ),
+    (
+        enable_windowed_reductions,
+        "windowed evaluation of aggregations over tumbling and hopping temporal windows"
+    ),
+    (
+        enable_bounded_topk,
+        "bounded-memory evaluation of TopK with small limits"
+    ),
     (enable_primary_key_not_enforced, "PRIMARY KEY NOT ENFORCED"),
     (enable_mfp_pushdown_explain, "`filter_pushdown` explain"),
     (
@@ -1628,6 +1714,15 @@ feature_flags!(
         "emitting notices for indexes with an empty key (doesn't affect EXPLAIN)",
         true
     ),
+    (
+        enable_optimizer_hints,
+        "`JOIN ORDER`, `DELTA JOIN` and `NO INDEX` optimizer hints"
+    ),
+    (
+        enable_notices_for_unsatisfied_optimizer_hints,
+        "emitting notices for optimizer hints that could not be honored (doesn't affect EXPLAIN)",
+        true
+    ),
 );
 
 /// Represents the input to a variable.
@@ -1723,6 +1818,10 @@ impl SessionVars {
                 &ENABLE_SESSION_CARDINALITY_ESTIMATES,
                 &ENABLE_CARDINALITY_ESTIMATES,
             )
+            .with_feature_gated_var(
+                &ENABLE_SESSION_COST_BASED_JOIN_ORDERING,
+                &ENABLE_CARDINALITY_ESTIMATES,
+            )
             .with_var(&MAX_QUERY_RESULT_SIZE)
             .with_var(&MAX_IDENTIFIER_LENGTH)
             .with_value_constrained_var(
@@ -2098,6 +2197,11 @@ impl SessionVars {
         *self.expect_value(&ENABLE_SESSION_CARDINALITY_ESTIMATES)
     }
 
+    /// Returns the value of `enable_session_cost_based_join_ordering` configuration parameter.
+    pub fn enable_session_cost_based_join_ordering(&self) -> bool {
+        *self.expect_value(&ENABLE_SESSION_COST_BASED_JOIN_ORDERING)
+    }
+
     /// Returns the value of `is_superuser` configuration parameter.
     pub fn is_superuser(&self) -> bool {
         self.user.is_superuser()
@@ -2243,6 +2347,7 @@ impl SystemVars {
             .with_var(&MAX_ROLES)
             .with_var(&MAX_RESULT_SIZE)
             .with_var(&ALLOWED_CLUSTER_REPLICA_SIZES)
+            .with_var(&PLAN_REGRESSION_CRITICAL_OBJECTS)
             .with_var(&DISK_CLUSTER_REPLICAS_DEFAULT)
             .with_var(&upsert_rocksdb::UPSERT_ROCKSDB_AUTO_SPILL_TO_DISK)
             .with_var(&upsert_rocksdb::UPSERT_ROCKSDB_AUTO_SPILL_THRESHOLD_BYTES)
@@ -2297,11 +2402,14 @@ impl SystemVars {
             .with_var(&PG_REPLICATION_KEEPALIVES_INTERVAL)
             .with_var(&PG_REPLICATION_KEEPALIVES_RETRIES)
             .with_var(&PG_REPLICATION_TCP_USER_TIMEOUT)
+            .with_var(&PG_SOURCE_SCHEMA_CHANGE_CHECK_INTERVAL)
             .with_var(&ENABLE_LAUNCHDARKLY)
             .with_var(&MAX_CONNECTIONS)
             .with_var(&KEEP_N_SOURCE_STATUS_HISTORY_ENTRIES)
             .with_var(&KEEP_N_SINK_STATUS_HISTORY_ENTRIES)
             .with_var(&ENABLE_MZ_JOIN_CORE)
+            .with_var(&ENABLE_ARRANGEMENT_SPILLING)
+            .with_var(&ARRANGEMENT_SPILL_THRESHOLD_BYTES)
             .with_var(&ENABLE_STORAGE_SHARD_FINALIZATION)
             .with_var(&ENABLE_CONSOLIDATE_AFTER_UNION_NEGATE)
             .with_var(&ENABLE_DEFAULT_CONNECTION_VALIDATION)
@@ -2650,6 +2758,14 @@ impl SystemVars {
             .collect()
     }
 
+    /// Returns the `plan_regression_critical_objects` configuration parameter.
+    pub fn plan_regression_critical_objects(&self) -> Vec<String> {
+        self.expect_value(&PLAN_REGRESSION_CRITICAL_OBJECTS)
+            .into_iter()
+            .map(|s| s.as_str().into())
+            .collect()
+    }
+
     /// Returns the `disk_cluster_replicas_default` configuration parameter.
     pub fn disk_cluster_replicas_default(&self) -> bool {
         *self.expect_value(&DISK_CLUSTER_REPLICAS_DEFAULT)
@@ -2776,6 +2892,11 @@ impl SystemVars {
         *self.expect_value(&PG_REPLICATION_CONNECT_TIMEOUT)
     }
 
+    /// Returns the `pg_source_schema_change_check_interval` configuration parameter.
+    pub fn pg_source_schema_change_check_interval(&self) -> Duration {
+        *self.expect_value(&PG_SOURCE_SCHEMA_CHANGE_CHECK_INTERVAL)
+    }
+
     /// Returns the `pg_replication_keepalives_retries` configuration parameter.
     pub fn pg_replication_keepalives_retries(&self) -> u32 {
         *self.expect_value(&PG_REPLICATION_KEEPALIVES_RETRIES)
@@ -2932,6 +3053,16 @@ impl SystemVars {
         *self.expect_value(&ENABLE_MZ_JOIN_CORE)
     }
 
+    /// Returns the `enable_arrangement_spilling` configuration parameter.
+    pub fn enable_arrangement_spilling(&self) -> bool {
+        *self.expect_value(&ENABLE_ARRANGEMENT_SPILLING)
+    }
+
+    /// Returns the `arrangement_spill_threshold_bytes` configuration parameter.
+    pub fn arrangement_spill_threshold_bytes(&self) -> usize {
+        *self.expect_value(&ARRANGEMENT_SPILL_THRESHOLD_BYTES)
+    }
+
     /// Returns the `enable_storage_shard_finalization` configuration parameter.
     pub fn enable_storage_shard_finalization(&self) -> bool {
         *self.expect_value(&ENABLE_STORAGE_SHARD_FINALIZATION)
@@ -4499,6 +4630,8 @@ pub fn is_compute_config_var(name: &str) -> bool {
         || name == DATAFLOW_MAX_INFLIGHT_BYTES.name()
         || name == ENABLE_ARRANGEMENT_SIZE_LOGGING.name()
         || name == ENABLE_MZ_JOIN_CORE.name()
+        || name == ENABLE_ARRANGEMENT_SPILLING.name()
+        || name == ARRANGEMENT_SPILL_THRESHOLD_BYTES.name()
         || is_persist_config_var(name)
         || is_tracing_var(name)
 }
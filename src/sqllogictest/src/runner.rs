@@ -59,6 +59,7 @@ use mz_pgrepr::{oid, Interval, Jsonb, Numeric, UInt2, UInt4, UInt8, Value};
 use mz_repr::adt::date::Date;
 use mz_repr::adt::mz_acl_item::{AclItem, MzAclItem};
 use mz_repr::adt::numeric;
+use mz_repr::adt::text_search::{TsQuery, TsVector};
 use mz_repr::ColumnName;
 use mz_secrets::SecretsController;
 use mz_sql::ast::{Expr, Raw, Statement};
@@ -434,6 +435,10 @@ impl<'a> FromSql<'a> for Slt {
             PgType::INT8 => Self(Value::Int8(types::int8_from_sql(raw)?)),
             PgType::INTERVAL => Self(Value::Interval(Interval::from_sql(ty, raw)?)),
             PgType::JSONB => Self(Value::Jsonb(Jsonb::from_sql(ty, raw)?)),
+            PgType::JSONPATH => match raw.split_first() {
+                Some((1, path)) => Self(Value::JsonPath(types::text_from_sql(path)?.to_string())),
+                _ => return Err("unsupported jsonpath version number".into()),
+            },
             PgType::NAME => Self(Value::Name(types::text_from_sql(raw)?.to_string())),
             PgType::NUMERIC => Self(Value::Numeric(Numeric::from_sql(ty, raw)?)),
             PgType::OID => Self(Value::Oid(types::oid_from_sql(raw)?)),
@@ -450,7 +455,12 @@ impl<'a> FromSql<'a> for Slt {
             PgType::TIMESTAMPTZ => Self(Value::TimestampTz(
                 DateTime::<Utc>::from_sql(ty, raw)?.try_into()?,
             )),
+            PgType::TS_VECTOR => Self(Value::TsVector(TsVector::from_binary(raw)?.to_string())),
+            PgType::TSQUERY => Self(Value::TsQuery(TsQuery::from_binary(raw)?.to_string())),
             PgType::UUID => Self(Value::Uuid(Uuid::from_sql(ty, raw)?)),
+            PgType::INET => Self(Value::decode_binary(&mz_pgrepr::Type::Inet, raw)?),
+            PgType::CIDR => Self(Value::decode_binary(&mz_pgrepr::Type::Cidr, raw)?),
+            PgType::MACADDR => Self(Value::decode_binary(&mz_pgrepr::Type::MacAddr, raw)?),
             PgType::RECORD => {
                 let num_fields = read_be_i32(&mut raw)?;
                 let mut tuple = vec![];
@@ -549,6 +559,7 @@ impl<'a> FromSql<'a> for Slt {
                 | PgType::INT8
                 | PgType::INTERVAL
                 | PgType::JSONB
+                | PgType::JSONPATH
                 | PgType::NAME
                 | PgType::NUMERIC
                 | PgType::OID
@@ -562,7 +573,12 @@ impl<'a> FromSql<'a> for Slt {
                 | PgType::TIME
                 | PgType::TIMESTAMP
                 | PgType::TIMESTAMPTZ
+                | PgType::TS_VECTOR
+                | PgType::TSQUERY
                 | PgType::UUID
+                | PgType::INET
+                | PgType::CIDR
+                | PgType::MACADDR
                 | PgType::INT4_RANGE
                 | PgType::INT4_RANGE_ARRAY
                 | PgType::INT8_RANGE
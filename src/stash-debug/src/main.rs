@@ -260,6 +260,7 @@ macro_rules! for_collections {
                 $macro!(catalog::DEFAULT_PRIVILEGES_COLLECTION);
                 $macro!(catalog::ID_ALLOCATOR_COLLECTION);
                 $macro!(catalog::ITEM_COLLECTION);
+                $macro!(catalog::PLAN_FINGERPRINT_COLLECTION);
                 $macro!(catalog::ROLES_COLLECTION);
                 $macro!(catalog::SCHEMAS_COLLECTION);
                 $macro!(catalog::SETTING_COLLECTION);
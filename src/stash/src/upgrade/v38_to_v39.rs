@@ -0,0 +1,24 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::upgrade::objects_v39;
+use crate::{StashError, Transaction, TypedCollection};
+
+/// Migration to initialize the plan fingerprint collection, which is used to detect plan changes
+/// across releases.
+pub async fn upgrade(tx: &'_ mut Transaction<'_>) -> Result<(), StashError> {
+    const PLAN_FINGERPRINT_COLLECTION: TypedCollection<
+        objects_v39::PlanFingerprintKey,
+        objects_v39::PlanFingerprintValue,
+    > = TypedCollection::new("plan_fingerprint");
+
+    PLAN_FINGERPRINT_COLLECTION.initialize(tx, vec![]).await?;
+
+    Ok(())
+}
@@ -30,6 +30,7 @@ use async_trait::async_trait;
 use bytes::BufMut;
 use derivative::Derivative;
 use differential_dataflow::lattice::Lattice;
+use futures::stream::BoxStream;
 use itertools::Itertools;
 use mz_build_info::BuildInfo;
 use mz_cluster_client::client::ClusterReplicaLocation;
@@ -358,6 +359,25 @@ pub trait StorageController: Debug + Send {
         desc: IngestionDescription,
     ) -> Result<(), StorageError>;
 
+    /// Check that the description of the subsource collection associated with `id` can be
+    /// altered to `desc`, which must only append nullable columns to the current description.
+    fn check_alter_collection_desc(
+        &self,
+        id: GlobalId,
+        desc: &RelationDesc,
+    ) -> Result<(), StorageError>;
+
+    /// Alter the description of the subsource collection associated with `id` to `desc`.
+    ///
+    /// Rows written before the alteration lack the appended columns, which read as `NULL`. The
+    /// ingestion that exports the collection writes the appended columns once it is altered
+    /// with `alter_collection`.
+    fn alter_collection_desc(
+        &mut self,
+        id: GlobalId,
+        desc: RelationDesc,
+    ) -> Result<(), StorageError>;
+
     /// Acquire an immutable reference to the export state, should it exist.
     fn export(&self, id: GlobalId) -> Result<&ExportState<Self::Timestamp>, StorageError>;
 
@@ -438,6 +458,18 @@ pub trait StorageController: Debug + Send {
         as_of: Self::Timestamp,
     ) -> Result<Vec<(Row, Diff)>, StorageError>;
 
+    /// Returns the contents of the local input named `id` at `as_of` as a stream,
+    /// which reads them in bounded memory.
+    ///
+    /// Unlike [`StorageController::snapshot`], the stream does not borrow the
+    /// controller, so it can be consumed on another task. The caller must hold a
+    /// read capability at `as_of` until the stream is exhausted.
+    fn snapshot_and_stream(
+        &self,
+        id: GlobalId,
+        as_of: Self::Timestamp,
+    ) -> Result<BoxStream<'static, Result<(Row, Diff), StorageError>>, StorageError>;
+
     /// Returns aggregate statistics about the contents of the local input named
     /// `id` at `as_of`.
     async fn snapshot_stats(
@@ -1688,6 +1720,59 @@ where
         Ok(())
     }
 
+    fn check_alter_collection_desc(
+        &self,
+        id: GlobalId,
+        desc: &RelationDesc,
+    ) -> Result<(), StorageError> {
+        let collection = self.collection(id)?;
+        if !matches!(
+            collection.description.data_source,
+            DataSource::Other(DataSourceOther::Source)
+        ) {
+            tracing::info!(
+                "{id:?} inalterable because its data source is {:?} and not a subsource",
+                collection.description.data_source
+            );
+            return Err(StorageError::InvalidAlterSource { id });
+        }
+
+        let cur_desc = &collection.collection_metadata.relation_desc;
+        let appends_nullable_columns = desc.arity() >= cur_desc.arity()
+            && cur_desc
+                .iter()
+                .zip(desc.iter())
+                .all(|(cur, new)| cur == new)
+            && desc
+                .iter_types()
+                .skip(cur_desc.arity())
+                .all(|typ| typ.nullable);
+        if !appends_nullable_columns {
+            tracing::info!(
+                "{id:?} inalterable because {:?} does not append nullable columns to {:?}",
+                desc,
+                cur_desc
+            );
+            return Err(StorageError::InvalidAlterSource { id });
+        }
+
+        Ok(())
+    }
+
+    fn alter_collection_desc(
+        &mut self,
+        id: GlobalId,
+        desc: RelationDesc,
+    ) -> Result<(), StorageError> {
+        self.check_alter_collection_desc(id, &desc)?;
+
+        let collection = self.collection_mut(id).expect("validated exists");
+        collection.collection_metadata.relation_desc = desc.clone();
+        collection.description.desc = desc;
+
+        Ok(())
+    }
+
     fn export(&self, id: GlobalId) -> Result<&ExportState<Self::Timestamp>, StorageError> {
         self.state
             .exports
@@ -2011,6 +2096,51 @@ where
         }
     }
 
+    fn snapshot_and_stream(
+        &self,
+        id: GlobalId,
+        as_of: Self::Timestamp,
+    ) -> Result<BoxStream<'static, Result<(Row, Diff), StorageError>>, StorageError> {
+        use futures::stream::StreamExt;
+
+        let metadata = self.collection(id)?.collection_metadata.clone();
+        let persist = Arc::clone(&self.persist);
+
+        let stream = async_stream::stream! {
+            let persist_client = persist
+                .open(metadata.persist_location.clone())
+                .await
+                .unwrap();
+            let mut read_handle = persist_client
+                .open_leased_reader::<SourceData, (), _, _>(
+                    metadata.data_shard,
+                    Arc::new(metadata.relation_desc.clone()),
+                    Arc::new(UnitSchema),
+                    Diagnostics {
+                        shard_name: id.to_string(),
+                        handle_purpose: format!("snapshot and stream {}", id),
+                    },
+                )
+                .await
+                .expect("invalid persist usage");
+
+            match read_handle
+                .snapshot_and_stream(Antichain::from_elem(as_of))
+                .await
+            {
+                Ok(contents) => {
+                    futures::pin_mut!(contents);
+                    while let Some(((data, _), _, diff)) = contents.next().await {
+                        let row = data.expect("invalid protobuf data").0;
+                        yield row.map(|row| (row, diff)).map_err(StorageError::from);
+                    }
+                }
+                Err(_) => yield Err(StorageError::ReadBeforeSince(id)),
+            }
+        };
+        Ok(Box::pin(stream))
+    }
+
     async fn snapshot_stats(
         &self,
         id: GlobalId,
@@ -10,6 +10,7 @@
 //! A source that reads from an a persist shard.
 
 use std::any::Any;
+use std::collections::BTreeSet;
 use std::convert::Infallible;
 use std::rc::Rc;
 use std::sync::Arc;
@@ -85,6 +86,10 @@ use crate::types::sources::SourceData;
 /// to the empty antichain can be used. An easy easy of creating such stream is by
 /// using [`timely::dataflow::operators::generic::operator::empty`].
 ///
+/// If supplied, the `part_fetch_logger` is informed of every part of the shard that the
+/// source fetches, and of every part it skips because its statistics rule out any
+/// updates that pass the `map_filter_project`.
+///
 /// [advanced by]: differential_dataflow::lattice::Lattice::advance_by
 pub fn persist_source<G, YFn>(
     scope: &mut G,
@@ -95,6 +100,7 @@ pub fn persist_source<G, YFn>(
     until: Antichain<Timestamp>,
     map_filter_project: Option<&mut MfpPlan>,
     flow_control: Option<FlowControl<G>>,
+    part_fetch_logger: Option<PartFetchLogger>,
     yield_fn: YFn,
 ) -> (
     Stream<G, (Row, Timestamp, Diff)>,
@@ -122,6 +128,7 @@ where
                     summary: Refines::to_inner(fc.summary),
                     metrics: fc.metrics,
                 }),
+                part_fetch_logger,
                 yield_fn,
             );
             (stream.leave(), token)
@@ -136,6 +143,10 @@ where
 
 type RefinedScope<'g, G> = Child<'g, G, (<G as ScopeParent>::Timestamp, u64)>;
 
+/// A callback that a [persist_source] invokes with `true` for every part it fetches, and with
+/// `false` for every part it skips based on the part's statistics.
+pub type PartFetchLogger = Rc<dyn Fn(bool)>;
+
 /// Creates a new source that reads from a persist shard, distributing the work
 /// of reading data to all timely workers.
 ///
@@ -152,6 +163,7 @@ pub fn persist_source_core<'g, G, YFn>(
     until: Antichain<Timestamp>,
     map_filter_project: Option<&mut MfpPlan>,
     flow_control: Option<FlowControl<RefinedScope<'g, G>>>,
+    part_fetch_logger: Option<PartFetchLogger>,
     yield_fn: YFn,
 ) -> (
     Stream<RefinedScope<'g, G>, (Result<Row, DataflowError>, (mz_repr::Timestamp, u64), Diff)>,
@@ -163,6 +175,7 @@ where
 {
     let name = source_id.to_string();
     let desc = metadata.relation_desc.clone();
+    let arity = desc.arity();
     let filter_plan = map_filter_project.as_ref().map(|p| (*p).clone());
     let time_range = if let Some(lower) = as_of.as_ref().and_then(|a| a.as_option().copied()) {
         // If we have a lower bound, we can provide a bound on mz_now to our filter pushdown.
@@ -200,16 +213,34 @@ where
         desc_transformer,
         Arc::new(metadata.relation_desc),
         Arc::new(UnitSchema),
-        move |stats| {
-            if let Some(plan) = &filter_plan {
-                let stats = PersistSourceDataStats { desc: &desc, stats };
-                filter_may_match(desc.typ(), time_range.clone(), stats, plan)
-            } else {
-                true
+        {
+            let part_fetch_logger = part_fetch_logger.clone();
+            move |stats| {
+                let should_fetch = if let Some(plan) = &filter_plan {
+                    let stats = PersistSourceDataStats { desc: &desc, stats };
+                    filter_may_match(desc.typ(), time_range.clone(), stats, plan)
+                } else {
+                    true
+                };
+                // Fetched parts are logged once they have been fetched, in `decode_and_mfp`.
+                if !should_fetch {
+                    if let Some(logger) = &part_fetch_logger {
+                        logger(false);
+                    }
+                }
+                should_fetch
             }
         },
     );
-    let rows = decode_and_mfp(&fetched, &name, until, map_filter_project, yield_fn);
+    let rows = decode_and_mfp(
+        &fetched,
+        &name,
+        arity,
+        until,
+        map_filter_project,
+        part_fetch_logger,
+        yield_fn,
+    );
     (rows, token)
 }
 
@@ -240,8 +271,10 @@ fn filter_may_match(
 pub fn decode_and_mfp<G, YFn>(
     fetched: &Stream<G, FetchedPart<SourceData, (), Timestamp, Diff>>,
     name: &str,
+    arity: usize,
     until: Antichain<Timestamp>,
     mut map_filter_project: Option<&mut MfpPlan>,
+    part_fetch_logger: Option<PartFetchLogger>,
     yield_fn: YFn,
 ) -> Stream<G, (Result<Row, DataflowError>, G::Timestamp, Diff)>
 where
@@ -264,6 +297,8 @@ where
 
     // Extract the MFP if it exists; leave behind an identity MFP in that case.
     let map_filter_project = map_filter_project.as_mut().map(|mfp| mfp.take());
+    // Only the columns the MFP reads need to be decoded.
+    let demand = map_filter_project.as_ref().map(|mfp| mfp.demand());
 
     builder.build(move |_caps| {
         let name = name.to_owned();
@@ -279,6 +314,12 @@ where
                 data.swap(&mut buffer);
                 let capability = time.retain();
                 for fetched_part in buffer.drain(..) {
+                    // Parts fetched only to audit the filter were already logged as skipped.
+                    if fetched_part.is_filter_pushdown_audit().is_none() {
+                        if let Some(logger) = &part_fetch_logger {
+                            logger(true);
+                        }
+                    }
                     pending_work.push_back(PendingWork {
                         capability: capability.clone(),
                         fetched_part,
@@ -296,8 +337,10 @@ where
                     &name,
                     start_time,
                     &yield_fn,
+                    arity,
                     &until,
                     map_filter_project.as_ref(),
+                    demand.as_ref(),
                     &mut datum_vec,
                     &mut row_builder,
                     &mut handle,
@@ -332,8 +375,10 @@ impl PendingWork {
         name: &str,
         start_time: Instant,
         yield_fn: YFn,
+        arity: usize,
         until: &Antichain<Timestamp>,
         map_filter_project: Option<&MfpPlan>,
+        demand: Option<&BTreeSet<usize>>,
         datum_vec: &mut DatumVec,
         row_builder: &mut Row,
         output: &mut ConsolidateBuffer<
@@ -353,7 +398,12 @@ impl PendingWork {
         YFn: Fn(Instant, usize) -> bool,
     {
         let is_filter_pushdown_audit = self.fetched_part.is_filter_pushdown_audit();
-        while let Some(((key, val), time, diff)) = self.fetched_part.next() {
+        // Rows are decoded into the arity of the collection's description, which may have gained
+        // columns since they were written.
+        let decode_key = |buf: &[u8]| SourceData::decode_projected(buf, demand, arity);
+        while let Some(((key, val), time, diff)) =
+            self.fetched_part.next_with_key_decoder(&decode_key)
+        {
             if until.less_equal(&time) {
                 continue;
             }
@@ -724,6 +724,9 @@ mod columnation {
                         EvalError::IfNullError(x) => {
                             EvalError::IfNullError(self.string_region.copy(x))
                         }
+                        EvalError::JsonPathError(x) => {
+                            EvalError::JsonPathError(self.string_region.copy(x))
+                        }
                     };
                     let reference = self.eval_error_region.copy_iter(once(err));
                     let boxed = unsafe { Box::from_raw(reference.as_mut_ptr()) };
@@ -54,10 +54,12 @@ use crate::types::connections::inline::{
 };
 use crate::types::errors::{DataflowError, ProtoDataflowError};
 use crate::types::instances::StorageInstanceId;
+use crate::types::sources::custom_generator::CustomGeneratorSpec;
 use crate::types::sources::encoding::{DataEncoding, DataEncodingInner, SourceDataEncoding};
 use crate::types::sources::proto_ingestion_description::{ProtoSourceExport, ProtoSourceImport};
 use crate::types::sources::proto_load_generator_source_connection::Generator as ProtoGenerator;
 
+pub mod custom_generator;
 pub mod encoding;
 
 include!(concat!(
@@ -918,6 +920,8 @@ pub struct UpsertEnvelope {
     /// The indices of the keys in the full value row, used
     /// to deduplicate data in `upsert_core`
     pub key_indices: Vec<usize>,
+    /// If set, keys are retracted once their latest value becomes too old.
+    pub retain_keys: Option<UpsertRetainKeys>,
 }
 
 impl Arbitrary for UpsertEnvelope {
@@ -929,11 +933,13 @@ impl Arbitrary for UpsertEnvelope {
             any::<usize>(),
             any::<UpsertStyle>(),
             proptest::collection::vec(any::<usize>(), 1..4),
+            any::<Option<UpsertRetainKeys>>(),
         )
-            .prop_map(|(source_arity, style, key_indices)| Self {
+            .prop_map(|(source_arity, style, key_indices, retain_keys)| Self {
                 source_arity,
                 style,
                 key_indices,
+                retain_keys,
             })
             .boxed()
     }
@@ -945,6 +951,7 @@ impl RustType<ProtoUpsertEnvelope> for UpsertEnvelope {
             source_arity: self.source_arity.into_proto(),
             style: Some(self.style.into_proto()),
             key_indices: self.key_indices.into_proto(),
+            retain_keys: self.retain_keys.into_proto(),
         }
     }
 
@@ -955,6 +962,37 @@ impl RustType<ProtoUpsertEnvelope> for UpsertEnvelope {
                 .style
                 .into_rust_if_some("ProtoUpsertEnvelope::style")?,
             key_indices: proto.key_indices.into_rust()?,
+            retain_keys: proto.retain_keys.into_rust()?,
+        })
+    }
+}
+
+/// Describes when the keys of an upsert source expire.
+///
+/// A value expires `ttl` after the record timestamp stored in its
+/// `timestamp_idx` column. Once a key's latest value has expired, the key is
+/// retracted at the expiration time, exactly as if a tombstone had been
+/// received for it.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct UpsertRetainKeys {
+    /// The index of the record timestamp column in the full value row.
+    pub timestamp_idx: usize,
+    /// How long a key is retained after its latest value's record timestamp.
+    pub ttl: Duration,
+}
+
+impl RustType<ProtoUpsertRetainKeys> for UpsertRetainKeys {
+    fn into_proto(&self) -> ProtoUpsertRetainKeys {
+        ProtoUpsertRetainKeys {
+            timestamp_idx: self.timestamp_idx.into_proto(),
+            ttl: Some(self.ttl.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoUpsertRetainKeys) -> Result<Self, TryFromProtoError> {
+        Ok(UpsertRetainKeys {
+            timestamp_idx: usize::from_proto(proto.timestamp_idx)?,
+            ttl: proto.ttl.into_rust_if_some("ProtoUpsertRetainKeys::ttl")?,
         })
     }
 }
@@ -1244,6 +1282,7 @@ impl UnplannedSourceEnvelope {
                     "into_source_envelope to be passed \
                     correct parameters for UnplannedSourceEnvelope::Upsert",
                 ),
+                retain_keys: None,
             }),
             UnplannedSourceEnvelope::Debezium(inner) => SourceEnvelope::Debezium(inner),
             UnplannedSourceEnvelope::None(key_envelope) => SourceEnvelope::None(NoneEnvelope {
@@ -1725,6 +1764,9 @@ pub struct SourceDesc<C: ConnectionAccess = InlinedConnection> {
     pub envelope: SourceEnvelope,
     pub metadata_columns: Vec<IncludedColumnSource>,
     pub timestamp_interval: Duration,
+    /// If set, the source's updates are timestamped by the value of one of
+    /// its columns rather than by the time at which they were ingested.
+    pub event_time: Option<EventTimeDesc>,
 }
 
 impl<R: ConnectionResolver> IntoInlineConnection<SourceDesc, R>
@@ -1737,6 +1779,7 @@ impl<R: ConnectionResolver> IntoInlineConnection<SourceDesc, R>
             envelope,
             metadata_columns,
             timestamp_interval,
+            event_time,
         } = self;
 
         SourceDesc {
@@ -1745,6 +1788,7 @@ impl<R: ConnectionResolver> IntoInlineConnection<SourceDesc, R>
             envelope,
             metadata_columns,
             timestamp_interval,
+            event_time,
         }
     }
 }
@@ -1760,14 +1804,23 @@ impl Arbitrary for SourceDesc {
             any::<SourceEnvelope>(),
             any::<Vec<IncludedColumnSource>>(),
             any::<Duration>(),
+            any::<Option<EventTimeDesc>>(),
         )
             .prop_map(
-                |(connection, encoding, envelope, metadata_columns, timestamp_interval)| Self {
+                |(
                     connection,
                     encoding,
                     envelope,
                     metadata_columns,
                     timestamp_interval,
+                    event_time,
+                )| Self {
+                    connection,
+                    encoding,
+                    envelope,
+                    metadata_columns,
+                    timestamp_interval,
+                    event_time,
                 },
             )
             .boxed()
@@ -1782,6 +1835,7 @@ impl RustType<ProtoSourceDesc> for SourceDesc {
             envelope: Some(self.envelope.into_proto()),
             metadata_columns: self.metadata_columns.into_proto(),
             timestamp_interval: Some(self.timestamp_interval.into_proto()),
+            event_time: self.event_time.into_proto(),
         }
     }
 
@@ -1800,6 +1854,7 @@ impl RustType<ProtoSourceDesc> for SourceDesc {
             timestamp_interval: proto
                 .timestamp_interval
                 .into_rust_if_some("ProtoSourceDesc::timestamp_interval")?,
+            event_time: proto.event_time.into_rust()?,
         })
     }
 }
@@ -1856,6 +1911,7 @@ impl<C: ConnectionAccess> SourceDesc<C> {
             envelope,
             metadata_columns,
             timestamp_interval,
+            event_time,
         } = &self;
         connection.alter_compatible(id, &other.connection)?;
 
@@ -1865,6 +1921,7 @@ impl<C: ConnectionAccess> SourceDesc<C> {
             envelope == &other.envelope,
             metadata_columns == &other.metadata_columns,
             timestamp_interval == &other.timestamp_interval,
+            event_time == &other.event_time,
         ];
 
         for compatible in compatibility_checks {
@@ -1883,6 +1940,77 @@ impl<C: ConnectionAccess> SourceDesc<C> {
     }
 }
 
+/// Describes how to derive the timestamps of a source's updates from its data.
+///
+/// Instead of the time at which an update was ingested, each update is
+/// timestamped by the value of a `timestamp` or `timestamptz` column. Updates
+/// whose event time trails their ingestion time by more than
+/// `allowed_lateness` are handled according to `late_event_policy`.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct EventTimeDesc {
+    /// The index of the column in the source's output that holds the event time.
+    pub column: usize,
+    /// How far behind its ingestion time an update's event time may be before
+    /// the update is considered late.
+    pub allowed_lateness: Duration,
+    /// What to do with late updates.
+    pub late_event_policy: LateEventPolicy,
+}
+
+impl RustType<ProtoEventTimeDesc> for EventTimeDesc {
+    fn into_proto(&self) -> ProtoEventTimeDesc {
+        ProtoEventTimeDesc {
+            column: self.column.into_proto(),
+            allowed_lateness: Some(self.allowed_lateness.into_proto()),
+            late_event_policy: Some(self.late_event_policy.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoEventTimeDesc) -> Result<Self, TryFromProtoError> {
+        Ok(EventTimeDesc {
+            column: usize::from_proto(proto.column)?,
+            allowed_lateness: proto
+                .allowed_lateness
+                .into_rust_if_some("ProtoEventTimeDesc::allowed_lateness")?,
+            late_event_policy: proto
+                .late_event_policy
+                .into_rust_if_some("ProtoEventTimeDesc::late_event_policy")?,
+        })
+    }
+}
+
+/// What to do with an update whose event time is older than the allowed lateness.
+#[derive(Arbitrary, Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum LateEventPolicy {
+    /// Silently discard the update.
+    Drop,
+    /// Report the update in the source's error collection.
+    Error,
+}
+
+impl RustType<ProtoLateEventPolicy> for LateEventPolicy {
+    fn into_proto(&self) -> ProtoLateEventPolicy {
+        use proto_late_event_policy::Kind;
+        ProtoLateEventPolicy {
+            kind: Some(match self {
+                LateEventPolicy::Drop => Kind::Drop(()),
+                LateEventPolicy::Error => Kind::Error(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoLateEventPolicy) -> Result<Self, TryFromProtoError> {
+        use proto_late_event_policy::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoLateEventPolicy::kind"))?;
+        Ok(match kind {
+            Kind::Drop(()) => LateEventPolicy::Drop,
+            Kind::Error(()) => LateEventPolicy::Error,
+        })
+    }
+}
+
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum GenericSourceConnection<C: ConnectionAccess = InlinedConnection> {
     Kafka(KafkaSourceConnection<C>),
@@ -2049,6 +2177,11 @@ pub struct PostgresSourceConnection<C: ConnectionAccess = InlinedConnection> {
     pub table_casts: BTreeMap<usize, Vec<MirScalarExpr>>,
     pub publication: String,
     pub publication_details: PostgresSourcePublicationDetails,
+    /// Whether additive changes to the upstream tables' schemas, such as new
+    /// nullable columns or widened `varchar` columns, are absorbed instead of
+    /// erroring the affected subsources. New columns are appended to the
+    /// subsources by the coordinator, which alters the source to ingest them.
+    pub additive_schema_changes: bool,
 }
 
 impl<R: ConnectionResolver> IntoInlineConnection<PostgresSourceConnection, R>
@@ -2061,6 +2194,7 @@ impl<R: ConnectionResolver> IntoInlineConnection<PostgresSourceConnection, R>
             table_casts,
             publication,
             publication_details,
+            additive_schema_changes,
         } = self;
 
         PostgresSourceConnection {
@@ -2069,6 +2203,7 @@ impl<R: ConnectionResolver> IntoInlineConnection<PostgresSourceConnection, R>
             table_casts,
             publication,
             publication_details,
+            additive_schema_changes,
         }
     }
 }
@@ -2088,14 +2223,23 @@ impl<C: ConnectionAccess> Arbitrary for PostgresSourceConnection<C> {
             ),
             any::<String>(),
             any::<PostgresSourcePublicationDetails>(),
+            any::<bool>(),
         )
             .prop_map(
-                |(connection, connection_id, table_casts, publication, details)| Self {
+                |(
+                    connection,
+                    connection_id,
+                    table_casts,
+                    publication,
+                    details,
+                    additive_schema_changes,
+                )| Self {
                     connection,
                     connection_id,
                     table_casts,
                     publication,
                     publication_details: details,
+                    additive_schema_changes,
                 },
             )
             .boxed()
@@ -2141,6 +2285,7 @@ impl<C: ConnectionAccess> SourceConnection for PostgresSourceConnection<C> {
             table_casts,
             publication,
             publication_details,
+            additive_schema_changes,
         } = self;
 
         let compatibility_checks = [
@@ -2152,11 +2297,21 @@ impl<C: ConnectionAccess> SourceConnection for PostgresSourceConnection<C> {
                     l_key.cmp(r_key)
                 })
                 .all(|r| match r {
-                    Both((_, l_val), (_, r_val)) => l_val == r_val,
+                    // Tables may gain columns, which extends their casts.
+                    Both((_, l_val), (_, r_val)) => r_val.starts_with(l_val),
                     _ => true,
                 }),
             publication == &other.publication,
-            publication_details == &other.publication_details,
+            publication_details.slot == other.publication_details.slot,
+            publication_details.tables.iter().all(|l_table| {
+                other
+                    .publication_details
+                    .tables
+                    .iter()
+                    .filter(|r_table| r_table.oid == l_table.oid)
+                    .all(|r_table| r_table.columns.starts_with(&l_table.columns))
+            }),
+            additive_schema_changes == &other.additive_schema_changes,
         ];
 
         for compatible in compatibility_checks {
@@ -2198,6 +2353,7 @@ impl RustType<ProtoPostgresSourceConnection> for PostgresSourceConnection {
             details: Some(self.publication_details.into_proto()),
             table_casts,
             table_cast_pos,
+            additive_schema_changes: self.additive_schema_changes,
         }
     }
 
@@ -2239,6 +2395,7 @@ impl RustType<ProtoPostgresSourceConnection> for PostgresSourceConnection {
                 .details
                 .into_rust_if_some("ProtoPostgresSourceConnection::details")?,
             table_casts,
+            additive_schema_changes: proto.additive_schema_changes,
         })
     }
 }
@@ -2322,6 +2479,11 @@ pub enum LoadGenerator {
         count_orders: i64,
         count_clerk: i64,
     },
+    Custom {
+        spec: CustomGeneratorSpec,
+        /// The seed of the random number generator.
+        seed: u64,
+    },
 }
 
 impl LoadGenerator {
@@ -2355,6 +2517,7 @@ impl LoadGenerator {
             ),
             LoadGenerator::Marketing => DataEncodingInner::RowCodec(RelationDesc::empty()),
             LoadGenerator::Tpch { .. } => DataEncodingInner::RowCodec(RelationDesc::empty()),
+            LoadGenerator::Custom { .. } => DataEncodingInner::RowCodec(RelationDesc::empty()),
         }
     }
 
@@ -2363,8 +2526,8 @@ impl LoadGenerator {
     }
 
     /// Returns the list of table names and their column types that this generator generates
-    pub fn views(&self) -> Vec<(&str, RelationDesc)> {
-        match self {
+    pub fn views(&self) -> Vec<(String, RelationDesc)> {
+        let views = match self {
             LoadGenerator::Auction => vec![
                 (
                     "organizations",
@@ -2577,7 +2740,12 @@ impl LoadGenerator {
                     ),
                 ]
             }
-        }
+            LoadGenerator::Custom { spec, .. } => return spec.views(),
+        };
+        views
+            .into_iter()
+            .map(|(name, desc)| (name.to_string(), desc))
+            .collect()
     }
 
     pub fn is_monotonic(&self) -> bool {
@@ -2590,6 +2758,7 @@ impl LoadGenerator {
             LoadGenerator::Marketing => false,
             LoadGenerator::Datums => true,
             LoadGenerator::Tpch { .. } => false,
+            LoadGenerator::Custom { spec, .. } => spec.is_monotonic(),
         }
     }
 }
@@ -2629,6 +2798,12 @@ impl RustType<ProtoLoadGeneratorSourceConnection> for LoadGeneratorSourceConnect
                     count_clerk: *count_clerk,
                 }),
                 LoadGenerator::Datums => ProtoGenerator::Datums(()),
+                LoadGenerator::Custom { spec, seed } => {
+                    ProtoGenerator::Custom(ProtoCustomLoadGenerator {
+                        spec: Some(spec.into_proto()),
+                        seed: *seed,
+                    })
+                }
             }),
             tick_micros: self.tick_micros,
         }
@@ -2659,6 +2834,12 @@ impl RustType<ProtoLoadGeneratorSourceConnection> for LoadGeneratorSourceConnect
                     count_clerk,
                 },
                 ProtoGenerator::Datums(()) => LoadGenerator::Datums,
+                ProtoGenerator::Custom(ProtoCustomLoadGenerator { spec, seed }) => {
+                    LoadGenerator::Custom {
+                        spec: spec.into_rust_if_some("ProtoCustomLoadGenerator::spec")?,
+                        seed,
+                    }
+                }
             },
             tick_micros: proto.tick_micros,
         })
@@ -2780,6 +2961,28 @@ impl Codec for SourceData {
     }
 }
 
+impl SourceData {
+    /// Like [Codec::decode], but decodes rows into `arity` columns, and only decodes the values
+    /// of the columns whose indexes are in `demand`, if given. See [Row::decode_projected].
+    ///
+    /// Errors are always decoded in full.
+    pub fn decode_projected(
+        buf: &[u8],
+        demand: Option<&BTreeSet<usize>>,
+        arity: usize,
+    ) -> Result<Self, String> {
+        use proto_source_data::Kind;
+        let proto = ProtoSourceData::decode(buf).map_err(|err| err.to_string())?;
+        match proto.kind {
+            Some(Kind::Ok(row)) => Ok(SourceData(Ok(Row::decode_projected(&row, demand, arity)?))),
+            Some(Kind::Err(err)) => Ok(SourceData(Err(err
+                .into_rust()
+                .map_err(|err| err.to_string())?))),
+            None => Err(TryFromProtoError::missing_field("ProtoSourceData::kind").to_string()),
+        }
+    }
+}
+
 /// An implementation of [PartEncoder] for [SourceData].
 ///
 /// This mostly delegates the encoding logic to [RowEncoder], but flatmaps in
@@ -0,0 +1,251 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The specification of `LOAD GENERATOR CUSTOM` sources.
+//!
+//! Specifications are written in a SQL-like syntax, which is parsed and
+//! validated during planning:
+//!
+//! ```text
+//! TABLE users (
+//!     id SEQUENCE START 1 STEP 1,
+//!     age UNIFORM (18, 90),
+//!     score NORMAL (0.5, 0.1),
+//!     country CHOICE ('DE', 'US'),
+//!     nickname STRING (8)
+//! ) WITH (ROWS 100, INSERTS PER TICK 10, UPDATES PER TICK 5, DELETES PER TICK 1);
+//! TABLE orders (
+//!     id SEQUENCE,
+//!     user_id REFERENCES users (id)
+//! ) WITH (INSERTS PER TICK 100)
+//! ```
+//!
+//! Each table becomes a subsource. The tables' initial `ROWS` are inserted at
+//! the first offset, and every subsequent tick deletes, updates and inserts the
+//! given number of rows in each table.
+
+use mz_proto::{ProtoType, RustType, TryFromProtoError};
+use mz_repr::{RelationDesc, ScalarType};
+use proptest::prelude::any;
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use crate::types::sources::proto_custom_column_spec::{
+    Generator as ProtoGenerator, ProtoChoice, ProtoNormal, ProtoReference, ProtoSequence,
+    ProtoUniform,
+};
+use crate::types::sources::{
+    ProtoCustomColumnSpec, ProtoCustomGeneratorSpec, ProtoCustomTableSpec,
+};
+
+/// A validated `LOAD GENERATOR CUSTOM` specification.
+#[derive(Arbitrary, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CustomGeneratorSpec {
+    #[proptest(strategy = "proptest::collection::vec(any::<CustomTableSpec>(), 1..3)")]
+    pub tables: Vec<CustomTableSpec>,
+}
+
+// Specifications only contain finite floats, so equality is reflexive.
+impl Eq for CustomGeneratorSpec {}
+
+/// A table produced by a custom load generator.
+#[derive(Arbitrary, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CustomTableSpec {
+    pub name: String,
+    #[proptest(strategy = "proptest::collection::vec(any::<CustomColumnSpec>(), 1..4)")]
+    pub columns: Vec<CustomColumnSpec>,
+    /// The number of rows inserted at the first offset.
+    pub rows: u64,
+    /// The number of rows inserted at every tick.
+    pub inserts_per_tick: u64,
+    /// The number of random rows whose values are regenerated at every tick.
+    pub updates_per_tick: u64,
+    /// The number of random rows deleted at every tick.
+    pub deletes_per_tick: u64,
+}
+
+/// A column of a table produced by a custom load generator.
+#[derive(Arbitrary, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CustomColumnSpec {
+    pub name: String,
+    pub generator: ColumnGenerator,
+}
+
+/// How the values of a column are generated.
+#[derive(Arbitrary, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ColumnGenerator {
+    /// Consecutive integers, assigned when a row is inserted and kept when it
+    /// is updated. A sequence column is a key of its table, so a table stops
+    /// receiving inserts once one of its sequences would overflow.
+    Sequence { start: i64, step: i64 },
+    /// Integers drawn uniformly from `min..=max`.
+    Uniform { min: i64, max: i64 },
+    /// Floats drawn from a normal distribution.
+    Normal {
+        #[proptest(strategy = "-1e6f64..1e6")]
+        mean: f64,
+        #[proptest(strategy = "0f64..1e6")]
+        std_dev: f64,
+    },
+    /// Strings drawn uniformly from `values`.
+    Choice { values: Vec<String> },
+    /// Random alphanumeric strings of the given length.
+    String { length: usize },
+    /// The value of a sequence column in a random live row of an earlier
+    /// table, identified by the indexes of the table and the column. `NULL`
+    /// while that table is empty, and set to `NULL` when the referenced row is
+    /// deleted.
+    Reference { table: usize, column: usize },
+}
+
+impl CustomGeneratorSpec {
+    fn scalar_type(&self, table_idx: usize, column_idx: usize) -> ScalarType {
+        match &self.tables[table_idx].columns[column_idx].generator {
+            ColumnGenerator::Sequence { .. } | ColumnGenerator::Uniform { .. } => ScalarType::Int64,
+            ColumnGenerator::Normal { .. } => ScalarType::Float64,
+            ColumnGenerator::Choice { .. } | ColumnGenerator::String { .. } => ScalarType::String,
+            ColumnGenerator::Reference { table, column } => self.scalar_type(*table, *column),
+        }
+    }
+
+    /// Returns the names and descriptions of the generated tables.
+    ///
+    /// Every sequence column is a key of its table.
+    pub fn views(&self) -> Vec<(String, RelationDesc)> {
+        self.tables
+            .iter()
+            .enumerate()
+            .map(|(table_idx, table)| {
+                let mut desc = RelationDesc::empty();
+                for (column_idx, column) in table.columns.iter().enumerate() {
+                    let nullable = matches!(column.generator, ColumnGenerator::Reference { .. });
+                    desc = desc.with_column(
+                        column.name.as_str(),
+                        self.scalar_type(table_idx, column_idx).nullable(nullable),
+                    );
+                }
+                for (column_idx, column) in table.columns.iter().enumerate() {
+                    if matches!(column.generator, ColumnGenerator::Sequence { .. }) {
+                        desc = desc.with_key(vec![column_idx]);
+                    }
+                }
+                (table.name.clone(), desc)
+            })
+            .collect()
+    }
+
+    /// Whether the generated tables only ever receive inserts.
+    pub fn is_monotonic(&self) -> bool {
+        self.tables
+            .iter()
+            .all(|t| t.updates_per_tick == 0 && t.deletes_per_tick == 0)
+    }
+}
+
+impl RustType<ProtoCustomGeneratorSpec> for CustomGeneratorSpec {
+    fn into_proto(&self) -> ProtoCustomGeneratorSpec {
+        ProtoCustomGeneratorSpec {
+            tables: self.tables.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoCustomGeneratorSpec) -> Result<Self, TryFromProtoError> {
+        Ok(CustomGeneratorSpec {
+            tables: proto.tables.into_rust()?,
+        })
+    }
+}
+
+impl RustType<ProtoCustomTableSpec> for CustomTableSpec {
+    fn into_proto(&self) -> ProtoCustomTableSpec {
+        ProtoCustomTableSpec {
+            name: self.name.clone(),
+            columns: self.columns.into_proto(),
+            rows: self.rows,
+            inserts_per_tick: self.inserts_per_tick,
+            updates_per_tick: self.updates_per_tick,
+            deletes_per_tick: self.deletes_per_tick,
+        }
+    }
+
+    fn from_proto(proto: ProtoCustomTableSpec) -> Result<Self, TryFromProtoError> {
+        Ok(CustomTableSpec {
+            name: proto.name,
+            columns: proto.columns.into_rust()?,
+            rows: proto.rows,
+            inserts_per_tick: proto.inserts_per_tick,
+            updates_per_tick: proto.updates_per_tick,
+            deletes_per_tick: proto.deletes_per_tick,
+        })
+    }
+}
+
+impl RustType<ProtoCustomColumnSpec> for CustomColumnSpec {
+    fn into_proto(&self) -> ProtoCustomColumnSpec {
+        let generator = match &self.generator {
+            ColumnGenerator::Sequence { start, step } => ProtoGenerator::Sequence(ProtoSequence {
+                start: *start,
+                step: *step,
+            }),
+            ColumnGenerator::Uniform { min, max } => ProtoGenerator::Uniform(ProtoUniform {
+                min: *min,
+                max: *max,
+            }),
+            ColumnGenerator::Normal { mean, std_dev } => ProtoGenerator::Normal(ProtoNormal {
+                mean: *mean,
+                std_dev: *std_dev,
+            }),
+            ColumnGenerator::Choice { values } => ProtoGenerator::Choice(ProtoChoice {
+                values: values.clone(),
+            }),
+            ColumnGenerator::String { length } => ProtoGenerator::StringLength(length.into_proto()),
+            ColumnGenerator::Reference { table, column } => {
+                ProtoGenerator::Reference(ProtoReference {
+                    table: table.into_proto(),
+                    column: column.into_proto(),
+                })
+            }
+        };
+        ProtoCustomColumnSpec {
+            name: self.name.clone(),
+            generator: Some(generator),
+        }
+    }
+
+    fn from_proto(proto: ProtoCustomColumnSpec) -> Result<Self, TryFromProtoError> {
+        let generator = match proto
+            .generator
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoCustomColumnSpec::generator"))?
+        {
+            ProtoGenerator::Sequence(ProtoSequence { start, step }) => {
+                ColumnGenerator::Sequence { start, step }
+            }
+            ProtoGenerator::Uniform(ProtoUniform { min, max }) => {
+                ColumnGenerator::Uniform { min, max }
+            }
+            ProtoGenerator::Normal(ProtoNormal { mean, std_dev }) => {
+                ColumnGenerator::Normal { mean, std_dev }
+            }
+            ProtoGenerator::Choice(ProtoChoice { values }) => ColumnGenerator::Choice { values },
+            ProtoGenerator::StringLength(length) => ColumnGenerator::String {
+                length: length.into_rust()?,
+            },
+            ProtoGenerator::Reference(ProtoReference { table, column }) => {
+                ColumnGenerator::Reference {
+                    table: table.into_rust()?,
+                    column: column.into_rust()?,
+                }
+            }
+        };
+        Ok(CustomColumnSpec {
+            name: proto.name,
+            generator,
+        })
+    }
+}
@@ -324,17 +324,23 @@ impl<C: ConnectionAccess> DataEncoding<C> {
                     let ty = ScalarType::String.nullable(true);
                     desc.with_column(name, ty)
                 }),
-            DataEncodingInner::Csv(CsvEncoding { columns, .. }) => match columns {
-                ColumnSpec::Count(n) => (1..=*n).fold(RelationDesc::empty(), |desc, i| {
-                    desc.with_column(format!("column{}", i), ScalarType::String.nullable(false))
-                }),
-                ColumnSpec::Header { names } => names
-                    .iter()
-                    .map(|s| &**s)
-                    .fold(RelationDesc::empty(), |desc, name| {
-                        desc.with_column(name, ScalarType::String.nullable(false))
+            DataEncodingInner::Csv(csv) => {
+                let nullable = csv.produces_nulls();
+                match &csv.columns {
+                    ColumnSpec::Count(n) => (1..=*n).fold(RelationDesc::empty(), |desc, i| {
+                        desc.with_column(
+                            format!("column{}", i),
+                            ScalarType::String.nullable(nullable),
+                        )
                     }),
-            },
+                    ColumnSpec::Header { names } => names
+                        .iter()
+                        .map(|s| &**s)
+                        .fold(RelationDesc::empty(), |desc, name| {
+                            desc.with_column(name, ScalarType::String.nullable(nullable))
+                        }),
+                }
+            }
             DataEncodingInner::Text => {
                 RelationDesc::empty().with_column("text", ScalarType::String.nullable(false))
             }
@@ -439,6 +445,30 @@ impl RustType<ProtoProtobufEncoding> for ProtobufEncoding {
 pub struct CsvEncoding {
     pub columns: ColumnSpec,
     pub delimiter: u8,
+    /// The character used to quote fields.
+    pub quote: u8,
+    /// The character used to escape quotes inside quoted fields. If unset, quotes are escaped
+    /// by doubling them. As in PostgreSQL, the escape character has no special meaning outside
+    /// of quoted fields.
+    pub escape: Option<u8>,
+    /// An unquoted field equal to this string, after trimming, is decoded as `NULL`. As in
+    /// PostgreSQL, quoted fields never match, so that the string itself can be quoted. The
+    /// header row is never subject to this.
+    pub null_string: Option<String>,
+    /// Whether to strip leading and trailing whitespace from each field, other than those of
+    /// the header row.
+    pub trim: bool,
+    /// Whether records with fewer fields than columns are padded with `NULL`s.
+    pub allow_missing_columns: bool,
+    /// Whether fields beyond the last column are ignored.
+    pub allow_extra_columns: bool,
+}
+
+impl CsvEncoding {
+    /// Whether decoding can produce `NULL` values.
+    pub fn produces_nulls(&self) -> bool {
+        self.null_string.is_some() || self.allow_missing_columns
+    }
 }
 
 impl RustType<ProtoCsvEncoding> for CsvEncoding {
@@ -446,6 +476,12 @@ impl RustType<ProtoCsvEncoding> for CsvEncoding {
         ProtoCsvEncoding {
             columns: Some(self.columns.into_proto()),
             delimiter: self.delimiter.into_proto(),
+            quote: self.quote.into_proto(),
+            escape: self.escape.into_proto(),
+            null_string: self.null_string.clone(),
+            trim: self.trim,
+            allow_missing_columns: self.allow_missing_columns,
+            allow_extra_columns: self.allow_extra_columns,
         }
     }
 
@@ -455,6 +491,12 @@ impl RustType<ProtoCsvEncoding> for CsvEncoding {
                 .columns
                 .into_rust_if_some("ProtoCsvEncoding::columns")?,
             delimiter: proto.delimiter.into_rust()?,
+            quote: proto.quote.into_rust()?,
+            escape: proto.escape.into_rust()?,
+            null_string: proto.null_string,
+            trim: proto.trim,
+            allow_missing_columns: proto.allow_missing_columns,
+            allow_extra_columns: proto.allow_extra_columns,
         })
     }
 }
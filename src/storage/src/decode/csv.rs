@@ -16,11 +16,23 @@ pub struct CsvDecoderState {
     next_row_is_header: bool,
     header_names: Option<Vec<String>>,
     n_cols: usize,
+    delimiter: u8,
+    quote: u8,
+    escape: Option<u8>,
+    null_string: Option<String>,
+    trim: bool,
+    allow_missing_columns: bool,
+    allow_extra_columns: bool,
     output: Vec<u8>,
     output_cursor: usize,
     ends: Vec<usize>,
     ends_cursor: usize,
     csv_reader: csv_core::Reader,
+    /// The input of the record currently being read, which is kept to tell which of its fields
+    /// are quoted if there is a null string.
+    raw: Vec<u8>,
+    /// The line on which the record currently being read starts, if any.
+    record_line: Option<u64>,
     row_buf: Row,
     events_error: usize,
     events_success: usize,
@@ -32,7 +44,16 @@ impl CsvDecoderState {
     }
 
     pub fn new(format: CsvEncoding) -> Self {
-        let CsvEncoding { columns, delimiter } = format;
+        let CsvEncoding {
+            columns,
+            delimiter,
+            quote,
+            escape,
+            null_string,
+            trim,
+            allow_missing_columns,
+            allow_extra_columns,
+        } = format;
         let n_cols = columns.arity();
 
         let header_names = columns.into_header_names();
@@ -40,11 +61,24 @@ impl CsvDecoderState {
             next_row_is_header: header_names.is_some(),
             header_names,
             n_cols,
+            delimiter,
+            quote,
+            escape,
+            null_string,
+            trim,
+            allow_missing_columns,
+            allow_extra_columns,
             output: vec![0],
             output_cursor: 0,
             ends: vec![0],
             ends_cursor: 1,
-            csv_reader: csv_core::ReaderBuilder::new().delimiter(delimiter).build(),
+            csv_reader: csv_core::ReaderBuilder::new()
+                .delimiter(delimiter)
+                .quote(quote)
+                .escape(escape)
+                .build(),
+            raw: vec![],
+            record_line: None,
             row_buf: Row::default(),
             events_error: 0,
             events_success: 0,
@@ -55,16 +89,107 @@ impl CsvDecoderState {
         if self.header_names.is_some() {
             self.next_row_is_header = true;
         }
+        self.csv_reader.reset();
+        self.raw.clear();
+        self.record_line = None;
+        self.output_cursor = 0;
+        self.ends_cursor = 1;
+    }
+
+    fn column_count_error(&self, n_fields: usize, line: u64) -> DecodeErrorKind {
+        DecodeErrorKind::Text(format!(
+            "CSV error at record number {} (line {}): expected {} columns, got {}.",
+            self.total_events(),
+            line,
+            self.n_cols,
+            n_fields
+        ))
+    }
+
+    /// Checks the header record that was just read against the expected column names.
+    ///
+    /// The header is compared verbatim: `TRIM` and `NULL AS` only apply to data records.
+    fn check_header(&self, n_fields: usize, line: u64) -> Result<(), DecodeErrorKind> {
+        if n_fields != self.n_cols {
+            return Err(self.column_count_error(n_fields, line));
+        }
+        let output = record_str(
+            &self.output[0..self.output_cursor],
+            self.total_events(),
+            line,
+        )?;
+        let fields: Vec<_> = (0..n_fields)
+            .map(|i| &output[self.ends[i]..self.ends[i + 1]])
+            .collect();
+        let mismatched = fields
+            .iter()
+            .zip(self.header_names.iter().flatten())
+            .enumerate()
+            .find(|(_, (actual, expected))| **actual != expected.as_str());
+        match mismatched {
+            Some((i, (actual, expected))) => Err(DecodeErrorKind::Text(format!(
+                "source file contains incorrect columns '{:?}', \
+                 first mismatched column at index {} expected={} actual={}",
+                fields,
+                i + 1,
+                expected,
+                actual
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Packs the fields of the record that was just read into `self.row_buf`.
+    fn pack_record(&mut self, n_fields: usize, line: u64) -> Result<(), DecodeErrorKind> {
+        if (n_fields < self.n_cols && !self.allow_missing_columns)
+            || (n_fields > self.n_cols && !self.allow_extra_columns)
+        {
+            return Err(self.column_count_error(n_fields, line));
+        }
+        // As in PostgreSQL, quoted fields never match the null string.
+        let quoted = match self.null_string {
+            Some(_) => quoted_fields(&self.raw, self.delimiter, self.quote, self.escape),
+            None => vec![],
+        };
+        let output = record_str(
+            &self.output[0..self.output_cursor],
+            self.total_events(),
+            line,
+        )?;
+        let mut row_packer = self.row_buf.packer();
+        for i in 0..self.n_cols {
+            if i >= n_fields {
+                row_packer.push(Datum::Null);
+                continue;
+            }
+            let mut field = &output[self.ends[i]..self.ends[i + 1]];
+            if self.trim {
+                field = field.trim();
+            }
+            let quoted = quoted.get(i).copied().unwrap_or(false);
+            if !quoted && self.null_string.as_deref() == Some(field) {
+                row_packer.push(Datum::Null);
+            } else {
+                row_packer.push(Datum::String(field));
+            }
+        }
+        Ok(())
     }
 
     pub fn decode(&mut self, chunk: &mut &[u8]) -> Result<Option<Row>, DecodeErrorKind> {
         loop {
+            if self.record_line.is_none() {
+                self.record_line = Some(self.csv_reader.line());
+            }
             let (result, n_input, n_output, n_ends) = self.csv_reader.read_record(
                 *chunk,
                 &mut self.output[self.output_cursor..],
                 &mut self.ends[self.ends_cursor..],
             );
             self.output_cursor += n_output;
+            if self.null_string.is_some() {
+                self.raw.extend_from_slice(&chunk[..n_input]);
+            }
             *chunk = &(*chunk)[n_input..];
             self.ends_cursor += n_ends;
             match result {
@@ -80,74 +205,287 @@ impl CsvDecoderState {
                 }
                 // Success cases
                 csv_core::ReadRecordResult::Record | csv_core::ReadRecordResult::End => {
-                    let result = {
-                        let ends_valid = self.ends_cursor - 1;
-                        if ends_valid == 0 {
+                    let n_fields = self.ends_cursor - 1;
+                    let line = self.record_line.take().expect("set above");
+                    if n_fields == 0 {
+                        self.raw.clear();
+                        break Ok(None);
+                    }
+                    // skip header rows, do not send them into dataflow
+                    if self.next_row_is_header {
+                        self.next_row_is_header = false;
+                        let result = self.check_header(n_fields, line);
+                        self.output_cursor = 0;
+                        self.ends_cursor = 1;
+                        self.raw.clear();
+                        if let Err(e) = result {
+                            self.events_error += 1;
+                            break Err(e);
+                        } else if chunk.is_empty() {
                             break Ok(None);
                         }
-                        if ends_valid != self.n_cols {
+                        continue;
+                    }
+
+                    let result = match self.pack_record(n_fields, line) {
+                        Ok(()) => {
+                            self.events_success += 1;
+                            Ok(Some(self.row_buf.clone()))
+                        }
+                        Err(e) => {
                             self.events_error += 1;
-                            Err(DecodeErrorKind::Text(format!(
-                                "CSV error at record number {}: expected {} columns, got {}.",
-                                self.total_events(),
-                                self.n_cols,
-                                ends_valid
-                            )))
-                        } else {
-                            match std::str::from_utf8(&self.output[0..self.output_cursor]) {
-                                Ok(output) => {
-                                    self.events_success += 1;
-                                    let mut row_packer = self.row_buf.packer();
-                                    row_packer.extend((0..self.n_cols).map(|i| {
-                                        Datum::String(&output[self.ends[i]..self.ends[i + 1]])
-                                    }));
-                                    self.output_cursor = 0;
-                                    self.ends_cursor = 1;
-                                    Ok(Some(self.row_buf.clone()))
-                                }
-                                Err(e) => {
-                                    self.events_error += 1;
-                                    Err(DecodeErrorKind::Text(format!(
-                                        "CSV error at record number {}: invalid UTF-8 ({})",
-                                        self.total_events(),
-                                        e
-                                    )))
-                                }
-                            }
+                            Err(e)
                         }
                     };
+                    self.output_cursor = 0;
+                    self.ends_cursor = 1;
+                    self.raw.clear();
+                    break result;
+                }
+            }
+        }
+    }
+}
 
-                    // skip header rows, do not send them into dataflow
-                    if self.next_row_is_header {
-                        self.next_row_is_header = false;
+/// Interprets the bytes of a record as UTF-8.
+fn record_str(output: &[u8], record: usize, line: u64) -> Result<&str, DecodeErrorKind> {
+    std::str::from_utf8(output).map_err(|e| {
+        DecodeErrorKind::Text(format!(
+            "CSV error at record number {} (line {}): invalid UTF-8 ({})",
+            record, line, e
+        ))
+    })
+}
 
-                        if let Ok(Some(row)) = &result {
-                            let mismatched = row
-                                .iter()
-                                .zip(self.header_names.iter().flatten())
-                                .enumerate()
-                                .find(|(_, (actual, expected))| actual.unwrap_str() != &**expected);
-                            if let Some((i, (actual, expected))) = mismatched {
-                                break Err(DecodeErrorKind::Text(format!(
-                                    "source file contains incorrect columns '{:?}', \
-                                     first mismatched column at index {} expected={} actual={}",
-                                    row,
-                                    i + 1,
-                                    expected,
-                                    actual
-                                )));
-                            }
-                        }
-                        if chunk.is_empty() {
-                            break Ok(None);
-                        } else if result.is_err() {
-                            break result;
-                        }
-                    } else {
-                        break result;
-                    }
+/// Determines which fields of the record whose input is `raw` are quoted, by the same rules
+/// that `csv_core` reads the record by: a field is quoted if it starts with `quote`.
+fn quoted_fields(raw: &[u8], delimiter: u8, quote: u8, escape: Option<u8>) -> Vec<bool> {
+    let mut quoted = vec![];
+    // The input can start with the end of the previous record, or with empty lines.
+    let mut bytes = raw
+        .iter()
+        .copied()
+        .skip_while(|b| matches!(b, b'\r' | b'\n'))
+        .peekable();
+    let mut field_start = true;
+    let mut in_quotes = false;
+    while let Some(b) = bytes.next() {
+        if field_start {
+            field_start = false;
+            quoted.push(b == quote);
+            if b == quote {
+                in_quotes = true;
+                continue;
+            }
+        }
+        if in_quotes {
+            if b == quote {
+                // A doubled quote stands for a quote, and any other quote ends the quoted part.
+                if bytes.peek() == Some(&quote) {
+                    bytes.next();
+                } else {
+                    in_quotes = false;
                 }
+            } else if Some(b) == escape {
+                bytes.next();
+            }
+        } else if b == delimiter {
+            field_start = true;
+        } else if matches!(b, b'\r' | b'\n') {
+            break;
+        }
+    }
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_storage_client::types::sources::encoding::ColumnSpec;
+
+    use super::*;
+
+    fn encoding(columns: ColumnSpec) -> CsvEncoding {
+        CsvEncoding {
+            columns,
+            delimiter: b',',
+            quote: b'"',
+            escape: None,
+            null_string: None,
+            trim: false,
+            allow_missing_columns: false,
+            allow_extra_columns: false,
+        }
+    }
+
+    fn row(fields: &[Option<&str>]) -> Row {
+        Row::pack(fields.iter().map(|f| match f {
+            Some(s) => Datum::String(s),
+            None => Datum::Null,
+        }))
+    }
+
+    /// Decodes `input` as one complete object, the way `decode_delimited` and `eof` do.
+    fn decode_object(state: &mut CsvDecoderState, mut input: &[u8]) -> Vec<Result<Row, String>> {
+        let mut out = vec![];
+        let mut eof = false;
+        loop {
+            match state.decode(&mut input) {
+                Ok(Some(row)) => out.push(Ok(row)),
+                Err(e) => out.push(Err(e.to_string())),
+                Ok(None) if eof => break,
+                Ok(None) => eof = input.is_empty(),
+            }
+        }
+        state.reset_for_new_object();
+        out
+    }
+
+    #[mz_ore::test]
+    fn test_quote_and_escape() {
+        let mut state = CsvDecoderState::new(CsvEncoding {
+            quote: b'\'',
+            escape: Some(b'\\'),
+            ..encoding(ColumnSpec::Count(2))
+        });
+        assert_eq!(
+            decode_object(&mut state, b"'a,b','c\\'d'\nx\\,y\n"),
+            vec![
+                Ok(row(&[Some("a,b"), Some("c'd")])),
+                // The escape character only has meaning inside quoted fields.
+                Ok(row(&[Some("x\\"), Some("y")])),
+            ]
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_null_string() {
+        let mut state = CsvDecoderState::new(CsvEncoding {
+            null_string: Some("\\N".into()),
+            ..encoding(ColumnSpec::Count(2))
+        });
+        assert_eq!(
+            decode_object(&mut state, b"\\N,\\Nx\n\"\\N\",\n"),
+            vec![
+                Ok(row(&[None, Some("\\Nx")])),
+                // Quoted fields never match the null string.
+                Ok(row(&[Some("\\N"), Some("")])),
+            ]
+        );
+
+        // Quotes are recognized like the reader does, across chunks and with escapes.
+        let mut state = CsvDecoderState::new(CsvEncoding {
+            escape: Some(b'\\'),
+            null_string: Some("".into()),
+            ..encoding(ColumnSpec::Count(3))
+        });
+        let mut input: &[u8] = b"\"a\\\"\",\"\"\"\",\r\n\"\",,";
+        let mut out = vec![];
+        while !input.is_empty() {
+            let (mut chunk, rest) = input.split_at(1);
+            input = rest;
+            while let Some(row) = state.decode(&mut chunk).unwrap() {
+                out.push(row);
             }
         }
+        out.extend(state.decode(&mut &b""[..]).unwrap());
+        assert_eq!(
+            out,
+            vec![
+                row(&[Some("a\""), Some("\""), None]),
+                row(&[Some(""), None, None]),
+            ]
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_trim() {
+        let mut state = CsvDecoderState::new(CsvEncoding {
+            trim: true,
+            null_string: Some("".into()),
+            ..encoding(ColumnSpec::Count(2))
+        });
+        assert_eq!(
+            decode_object(&mut state, b" a ,\" b\"\n  ,c\n"),
+            vec![
+                Ok(row(&[Some("a"), Some("b")])),
+                Ok(row(&[None, Some("c")])),
+            ]
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_column_counts() {
+        let mut state = CsvDecoderState::new(encoding(ColumnSpec::Count(2)));
+        let out = decode_object(&mut state, b"a\na,b,c\n");
+        assert_eq!(out.len(), 2);
+        assert!(out[0]
+            .as_ref()
+            .unwrap_err()
+            .contains("expected 2 columns, got 1"));
+        assert!(out[1]
+            .as_ref()
+            .unwrap_err()
+            .contains("expected 2 columns, got 3"));
+
+        let mut state = CsvDecoderState::new(CsvEncoding {
+            allow_missing_columns: true,
+            allow_extra_columns: true,
+            ..encoding(ColumnSpec::Count(2))
+        });
+        assert_eq!(
+            decode_object(&mut state, b"a\na,b,c\n"),
+            vec![
+                Ok(row(&[Some("a"), None])),
+                Ok(row(&[Some("a"), Some("b")]))
+            ]
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_header() {
+        let header = ColumnSpec::Header {
+            names: vec!["a".into(), "b".into()],
+        };
+        // The header is not subject to `TRIM` or `NULL AS`.
+        let mut state = CsvDecoderState::new(CsvEncoding {
+            trim: true,
+            null_string: Some("a".into()),
+            ..encoding(header.clone())
+        });
+        assert_eq!(
+            decode_object(&mut state, b"a,b\na, 2\n"),
+            vec![Ok(row(&[None, Some("2")]))]
+        );
+        let out = decode_object(&mut state, b" a,b\n1,2\n");
+        assert_eq!(out.len(), 2);
+        assert!(out[0]
+            .as_ref()
+            .unwrap_err()
+            .contains("first mismatched column at index 1 expected=a actual= a"));
+        assert_eq!(out[1], Ok(row(&[Some("1"), Some("2")])));
+
+        // Every object starts with a header.
+        let mut state = CsvDecoderState::new(encoding(header));
+        assert_eq!(
+            decode_object(&mut state, b"a,b\n1,2"),
+            vec![Ok(row(&[Some("1"), Some("2")]))]
+        );
+        assert_eq!(
+            decode_object(&mut state, b"a,b\n3,4\n"),
+            vec![Ok(row(&[Some("3"), Some("4")]))]
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_line_numbers() {
+        let mut state = CsvDecoderState::new(encoding(ColumnSpec::Count(2)));
+        let out = decode_object(&mut state, b"a,b\n\"multi\nline\",c\nd\n");
+        assert_eq!(out.len(), 3);
+        assert_eq!(out[1], Ok(row(&[Some("multi\nline"), Some("c")])));
+        assert!(out[2].as_ref().unwrap_err().contains("(line 4)"));
+
+        // Line numbers restart with each object.
+        let out = decode_object(&mut state, b"e\n");
+        assert!(out[0].as_ref().unwrap_err().contains("(line 1)"));
     }
 }
@@ -238,6 +238,13 @@ impl DataDecoder {
         }
     }
 
+    /// Forget any state about the object currently being decoded.
+    fn reset_for_new_object(&mut self) {
+        if let DataDecoderInner::Csv(csv) = &mut self.inner {
+            csv.reset_for_new_object();
+        }
+    }
+
     /// Get the next record if it exists, assuming an EOF has occurred.
     ///
     /// This is distinct from `next` because, for example, a CSV record should be returned even if it
@@ -366,7 +373,11 @@ async fn decode_delimited(
             None => Ok(decoder.eof(&mut buf)?),
         }
     }
-    inner(decoder, buf).await.map_err(|inner| DecodeError {
+    let result = inner(decoder, buf).await;
+    // Each buffer is a complete object, so per-object state like CSV line numbers must not
+    // carry over into the next one.
+    decoder.reset_for_new_object();
+    result.map_err(|inner| DecodeError {
         kind: inner,
         raw: buf.to_vec(),
     })
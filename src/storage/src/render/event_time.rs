@@ -0,0 +1,132 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Event-time timestamping of append-only sources.
+//!
+//! Reclocking assigns each update the `mz_timestamp` at which it was ingested. For sources
+//! created with `TIMESTAMP FROM`, the operator in this module instead moves each update to the
+//! time recorded in one of its columns, so that temporal filters and `AS OF` queries reason about
+//! when an event happened rather than when Materialize learned about it.
+//!
+//! An update can only be moved to a time that is not yet complete in the output, so the output
+//! frontier trails the reclocked input frontier by the allowed lateness. An update whose event
+//! time is within the allowed lateness of its ingestion time is emitted at its event time, while
+//! an older update is late and is either dropped or reported in the error collection at its
+//! ingestion time. Event times in the future of the ingestion time are clamped to it.
+//!
+//! Lateness is judged against each update's own ingestion time rather than against the operator's
+//! progress, so the outcome only depends on the (durable) reclocking bindings and is the same
+//! across restarts and replicas. When resuming, updates that land before the resume upper were
+//! made durable before the restart and are skipped.
+
+use differential_dataflow::{AsCollection, Collection};
+use mz_repr::{Datum, Diff, Row, Timestamp};
+use mz_storage_client::types::errors::{DataflowError, EnvelopeError};
+use mz_storage_client::types::sources::{EventTimeDesc, LateEventPolicy};
+use timely::dataflow::channels::pact::Pipeline;
+use timely::dataflow::operators::Operator;
+use timely::dataflow::Scope;
+use timely::progress::Antichain;
+
+/// Re-timestamps `input` according to `event_time`.
+///
+/// `resume_upper` is the upper of the output collection at the time the dataflow was rendered.
+pub(crate) fn render<G>(
+    input: &Collection<G, Result<Row, DataflowError>, Diff>,
+    event_time: &EventTimeDesc,
+    resume_upper: Antichain<Timestamp>,
+) -> Collection<G, Result<Row, DataflowError>, Diff>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let EventTimeDesc {
+        column,
+        allowed_lateness,
+        late_event_policy,
+    } = event_time.clone();
+    let lateness = Timestamp::from(u64::try_from(allowed_lateness.as_millis()).unwrap_or(u64::MAX));
+
+    input
+        .inner
+        .unary_frontier(Pipeline, "EventTime", move |default_cap, _info| {
+            // All output is emitted under this capability, which trails the input frontier by the
+            // allowed lateness but never falls behind the resume upper.
+            let mut held_cap = resume_upper
+                .as_option()
+                .map(|upper| default_cap.delayed(upper));
+            let mut buffer = Vec::new();
+
+            move |input, output| {
+                input.for_each(|_cap, data| {
+                    data.swap(&mut buffer);
+                    let Some(held_cap) = held_cap.as_ref() else {
+                        buffer.clear();
+                        return;
+                    };
+                    let mut session = output.session(held_cap);
+                    for (result, ingest_ts, diff) in buffer.drain(..) {
+                        if ingest_ts < *held_cap.time() {
+                            // Already durable from before a restart.
+                            continue;
+                        }
+                        let row = match result {
+                            Ok(row) => row,
+                            Err(err) => {
+                                session.give((Err(err), ingest_ts, diff));
+                                continue;
+                            }
+                        };
+                        let event_millis = match row.iter().nth(column).expect("column exists") {
+                            Datum::Timestamp(ts) => ts.timestamp_millis(),
+                            Datum::TimestampTz(ts) => ts.timestamp_millis(),
+                            Datum::Null => {
+                                let err = EnvelopeError::Flat("event time is NULL".into());
+                                session.give((Err(err.into()), ingest_ts, diff));
+                                continue;
+                            }
+                            d => panic!("type error: expected timestamp, found {:?}", d),
+                        };
+                        // Events from before the epoch are always late.
+                        let event_ts = u64::try_from(event_millis).ok().map(Timestamp::from);
+                        match event_ts {
+                            Some(event_ts) if ingest_ts.saturating_sub(lateness) <= event_ts => {
+                                let ts = std::cmp::min(event_ts, ingest_ts);
+                                if *held_cap.time() <= ts {
+                                    session.give((Ok(row), ts, diff));
+                                }
+                            }
+                            _ => match late_event_policy {
+                                LateEventPolicy::Drop => {}
+                                LateEventPolicy::Error => {
+                                    let err = EnvelopeError::Flat(format!(
+                                        "late event: event time {event_millis} is more than \
+                                        {allowed_lateness:?} behind ingestion time {ingest_ts}"
+                                    ));
+                                    session.give((Err(err.into()), ingest_ts, diff));
+                                }
+                            },
+                        }
+                    }
+                });
+
+                match input.frontier().frontier().first() {
+                    Some(frontier) => {
+                        if let Some(held_cap) = held_cap.as_mut() {
+                            let target = frontier.saturating_sub(lateness);
+                            if *held_cap.time() < target {
+                                held_cap.downgrade(&target);
+                            }
+                        }
+                    }
+                    None => held_cap = None,
+                }
+            }
+        })
+        .as_collection()
+}
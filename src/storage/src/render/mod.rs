@@ -215,6 +215,7 @@ use crate::source::types::{HealthStatus, HealthStatusUpdate, SourcePersistSinkMe
 use crate::storage_state::StorageState;
 
 mod debezium;
+mod event_time;
 mod persist_sink;
 pub mod sinks;
 pub mod sources;
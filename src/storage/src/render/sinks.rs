@@ -63,6 +63,7 @@ pub(crate) fn render_sink<G: Scope<Timestamp = Timestamp>>(
         timely::progress::Antichain::new(),
         None,
         None,
+        None,
         // Copy the logic in DeltaJoin/Get/Join to start.
         |_timer, count| count > 1_000_000,
     );
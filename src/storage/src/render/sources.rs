@@ -269,6 +269,7 @@ where
         encoding,
         envelope,
         metadata_columns,
+        event_time,
         ..
     } = description.desc;
     let (stream, errors, health) = {
@@ -359,6 +360,7 @@ where
                                     Antichain::new(),
                                     None,
                                     None,
+                                    None,
                                     // Copy the logic in DeltaJoin/Get/Join to start.
                                     |_timer, count| count > 1_000_000,
                                 );
@@ -482,6 +484,7 @@ where
                                         Antichain::new(),
                                         None,
                                         flow_control,
+                                        None,
                                         // Copy the logic in DeltaJoin/Get/Join to start.
                                         |_timer, count| count > 1_000_000,
                                     );
@@ -566,6 +569,15 @@ where
 
                     let flattened_stream = flatten_results_prepend_keys(none_envelope, results);
 
+                    let flattened_stream = match &event_time {
+                        Some(event_time) => super::event_time::render(
+                            &flattened_stream,
+                            event_time,
+                            base_source_config.resume_uppers[&id].clone(),
+                        ),
+                        None => flattened_stream,
+                    };
+
                     let (stream, errors) = flattened_stream.inner.ok_err(split_ok_err);
 
                     let errors = errors.as_collection();
@@ -10,8 +10,10 @@
 use std::any::Any;
 use std::cell::RefCell;
 use std::cmp::Reverse;
+use std::collections::BTreeSet;
 use std::convert::AsRef;
 use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
 use std::time::Instant;
@@ -24,7 +26,7 @@ use mz_ore::error::ErrorExt;
 use mz_repr::{Datum, DatumVec, Diff, Row};
 use mz_storage_client::metrics::BackpressureMetrics;
 use mz_storage_client::types::errors::{DataflowError, EnvelopeError, UpsertError};
-use mz_storage_client::types::sources::UpsertEnvelope;
+use mz_storage_client::types::sources::{UpsertEnvelope, UpsertRetainKeys};
 use mz_timely_util::builder_async::{
     AsyncOutputHandle, Event as AsyncEvent, OperatorBuilder as AsyncOperatorBuilder,
 };
@@ -35,16 +37,19 @@ use timely::dataflow::channels::pushers::TeeCore;
 use timely::dataflow::operators::Capability;
 use timely::dataflow::{Scope, ScopeParent, Stream};
 use timely::order::{PartialOrder, TotalOrder};
+use timely::progress::timestamp::Refines;
 use timely::progress::{Antichain, Timestamp};
 
 use crate::render::sources::OutputIndex;
+use crate::render::upsert::expirations::{Expirations, MAX_RESIDENT_EXPIRATIONS};
 use crate::render::upsert::types::{
-    upsert_bincode_opts, AutoSpillBackend, InMemoryHashMap, RocksDBParams, UpsertState,
+    upsert_bincode_opts, AutoSpillBackend, InMemoryHashMap, RocksDBParams, StateValue, UpsertState,
     UpsertStateBackend,
 };
 use crate::source::types::{HealthStatus, HealthStatusUpdate, UpsertMetrics};
 use crate::storage_state::StorageInstanceContext;
 
+mod expirations;
 mod rocksdb;
 mod types;
 
@@ -207,7 +212,7 @@ pub(crate) fn upsert<G: Scope, O: timely::ExchangeData + Ord>(
     Rc<dyn Any>,
 )
 where
-    G::Timestamp: TotalOrder,
+    G::Timestamp: TotalOrder + Refines<mz_repr::Timestamp>,
 {
     let upsert_metrics = UpsertMetrics::new(
         &source_config.base_metrics,
@@ -219,10 +224,11 @@ where
     // If we are configured to delay raw sources till we rehydrate, we do so. Otherwise, skip
     // this, to prevent unnecessary work.
     let wait_for_input_resumption = dataflow_paramters.delay_sources_past_rehydration;
-    let upsert_config = UpsertConfig {
+    let mut upsert_config = UpsertConfig {
         wait_for_input_resumption,
         shrink_upsert_unused_buffers_by_ratio: dataflow_paramters
             .shrink_upsert_unused_buffers_by_ratio,
+        expiration_spill_directory: None,
     };
 
     if let Some(scratch_directory) = instance_context.scratch_directory.as_ref() {
@@ -250,10 +256,14 @@ where
 
         let rocksdb_in_use_metric = Arc::clone(&upsert_metrics.rocksdb_autospill_in_use);
 
+        // Like the state, the expirations of keys are kept on disk.
+        upsert_config.expiration_spill_directory = Some(scratch_directory.clone());
+
         if allow_auto_spill {
             upsert_inner(
                 input,
                 upsert_envelope.key_indices,
+                upsert_envelope.retain_keys,
                 resume_upper,
                 previous,
                 previous_token,
@@ -278,6 +288,7 @@ where
             upsert_inner(
                 input,
                 upsert_envelope.key_indices,
+                upsert_envelope.retain_keys,
                 resume_upper,
                 previous,
                 previous_token,
@@ -311,6 +322,7 @@ where
         upsert_inner(
             input,
             upsert_envelope.key_indices,
+            upsert_envelope.retain_keys,
             resume_upper,
             previous,
             previous_token,
@@ -358,11 +370,14 @@ struct UpsertConfig {
     // before we finalize `rehydration`.
     wait_for_input_resumption: bool,
     shrink_upsert_unused_buffers_by_ratio: usize,
+    // The directory to spill the expirations of keys to, if they shouldn't all be kept in memory.
+    expiration_spill_directory: Option<PathBuf>,
 }
 
 fn upsert_inner<G: Scope, O: timely::ExchangeData + Ord, F, Fut, US>(
     input: &Collection<G, (UpsertKey, Option<UpsertValue>, O), Diff>,
     mut key_indices: Vec<usize>,
+    retain_keys: Option<UpsertRetainKeys>,
     resume_upper: Antichain<G::Timestamp>,
     previous: Collection<G, Result<Row, DataflowError>, Diff>,
     previous_token: Option<Rc<dyn Any>>,
@@ -376,7 +391,7 @@ fn upsert_inner<G: Scope, O: timely::ExchangeData + Ord, F, Fut, US>(
     Rc<dyn Any>,
 )
 where
-    G::Timestamp: TotalOrder,
+    G::Timestamp: TotalOrder + Refines<mz_repr::Timestamp>,
     F: FnOnce() -> Fut + 'static,
     Fut: std::future::Future<Output = US>,
     US: UpsertStateBackend,
@@ -430,6 +445,12 @@ where
         let mut stash = vec![];
         let mut input_upper = Antichain::from_elem(Timestamp::minimum());
 
+        // The times at which keys expire, if `retain_keys` is set.
+        let mut expirations = Expirations::new(
+            upsert_config.expiration_spill_directory.clone(),
+            MAX_RESIDENT_EXPIRATIONS,
+        );
+
         while !PartialOrder::less_equal(&resume_upper, &snapshot_upper)
             || (upsert_config.wait_for_input_resumption && !PartialOrder::less_equal(&resume_upper, &input_upper))
         {
@@ -487,6 +508,24 @@ where
                 }
             }
 
+            if let Some(retain_keys) = &retain_keys {
+                for (key, value, diff) in events.iter() {
+                    if *diff > 0 {
+                        if let Some(expiration) = expiration_time(value, retain_keys) {
+                            if let Err(e) = expirations.schedule(*key, expiration) {
+                                process_upsert_state_error::<G>(
+                                    "Failed to record expirations".to_string(),
+                                    e,
+                                    &mut health_output,
+                                    &health_cap,
+                                )
+                                .await;
+                            }
+                        }
+                    }
+                }
+            }
+
             match state
                 .merge_snapshot_chunk(
                     events.drain(..),
@@ -544,6 +583,10 @@ where
         let mut commands_state: indexmap::IndexMap<_, types::UpsertValueAndSize> =
             indexmap::IndexMap::new();
         let mut multi_get_scratch = Vec::new();
+        // The keys whose expirations passed in the current batch, and the expirations of the
+        // values of the keys in `commands_state` before the batch, if `retain_keys` is set.
+        let mut expired_keys = BTreeSet::new();
+        let mut current_expirations = Vec::new();
 
         // Now can can resume consuming the collection
         let mut output_updates = vec![];
@@ -579,6 +622,27 @@ where
                     for (_, key, _, _) in stash.iter().take(idx) {
                         commands_state.entry(*key).or_default();
                     }
+                    // Also read the keys that may have expired before `upper`, which have to be
+                    // scheduled again if they haven't.
+                    expired_keys.clear();
+                    loop {
+                        match expirations.pop_expired(&upper) {
+                            Ok(Some(key)) => {
+                                commands_state.entry(key).or_default();
+                                expired_keys.insert(key);
+                            }
+                            Ok(None) => break,
+                            Err(e) => {
+                                process_upsert_state_error::<G>(
+                                    "Failed to read expirations".to_string(),
+                                    e,
+                                    &mut health_output,
+                                    &health_cap,
+                                )
+                                .await;
+                            }
+                        }
+                    }
 
                     // These iterators iterate in the same order because `commands_state`
                     // is an `IndexMap`.
@@ -609,6 +673,24 @@ where
                     });
 
                     let bincode_opts = types::upsert_bincode_opts();
+
+                    // Remember when the current values expire, to only schedule the keys whose
+                    // expiration changes.
+                    current_expirations.clear();
+                    if let Some(retain_keys) = &retain_keys {
+                        for command_state in commands_state.values_mut() {
+                            if let Some(cs) = command_state.value.as_mut() {
+                                cs.ensure_decoded(bincode_opts);
+                            }
+                            current_expirations.push(
+                                command_state
+                                    .value
+                                    .as_ref()
+                                    .and_then(|cs| state_value_expiration(cs, retain_keys)),
+                            );
+                        }
+                    }
+
                     // Upsert the values into `commands_state`, by recording the latest
                     // value (or deletion). These will be synced at the end to the `state`.
                     //
@@ -621,7 +703,7 @@ where
                     // This "mini-upsert" technique is actually useful in `UpsertState`'s
                     // `merge_snapshot_chunk` implementation, minimizing gets and puts on
                     // the `UpsertStateBackend` implementations. In some sense, its "upsert all the way down".
-                    while let Some((ts, key, _, value)) = commands.next() {
+                    while let Some((ts, key, _, mut value)) = commands.next() {
                         let command_state = commands_state
                             .get_mut(&key)
                             .expect("key missing from commands_state");
@@ -630,6 +712,30 @@ where
                             cs.ensure_decoded(bincode_opts);
                         }
 
+                        if let Some(retain_keys) = &retain_keys {
+                            // Retract the current value if it expired before this command.
+                            let current_expiration = command_state
+                                .value
+                                .as_ref()
+                                .and_then(|cs| state_value_expiration(cs, retain_keys));
+                            if let Some(expiration) = current_expiration {
+                                if expiration < ts {
+                                    // Values restored from before a restart cannot expire before
+                                    // the time we resumed at.
+                                    let time = std::cmp::max(expiration, output_cap.time().clone());
+                                    let old_value =
+                                        command_state.value.take().expect("checked above");
+                                    output_updates.push((old_value.to_decoded(), time, -1));
+                                }
+                            }
+                            // A value that has already expired when it arrives is a tombstone.
+                            let new_expiration =
+                                value.as_ref().and_then(|v| expiration_time(v, retain_keys));
+                            if new_expiration.map_or(false, |expiration| expiration <= ts) {
+                                value = None;
+                            }
+                        }
+
                         match value {
                             Some(value) => {
                                 if let Some(old_value) =
@@ -647,6 +753,50 @@ where
                         }
                     }
 
+                    // Retract the values that expire before `upper` and remember when the
+                    // remaining ones expire.
+                    if let Some(retain_keys) = &retain_keys {
+                        let current_expirations = current_expirations.drain(..);
+                        for ((key, command_state), current_expiration) in
+                            commands_state.iter_mut().zip_eq(current_expirations)
+                        {
+                            if let Some(cs) = command_state.value.as_mut() {
+                                cs.ensure_decoded(bincode_opts);
+                            }
+                            let expiration = command_state
+                                .value
+                                .as_ref()
+                                .and_then(|cs| state_value_expiration(cs, retain_keys));
+                            if expiration == current_expiration && !expired_keys.contains(key) {
+                                continue;
+                            }
+                            if let Some(current_expiration) = current_expiration {
+                                expirations.cancel(*key, current_expiration);
+                            }
+                            match expiration {
+                                Some(expiration) if upper.less_equal(&expiration) => {
+                                    if let Err(e) = expirations.schedule(*key, expiration) {
+                                        process_upsert_state_error::<G>(
+                                            "Failed to record expirations".to_string(),
+                                            e,
+                                            &mut health_output,
+                                            &health_cap,
+                                        )
+                                        .await;
+                                    }
+                                }
+                                Some(expiration) => {
+                                    let time =
+                                        std::cmp::max(expiration, output_cap.time().clone());
+                                    let old_value =
+                                        command_state.value.take().expect("checked above");
+                                    output_updates.push((old_value.to_decoded(), time, -1));
+                                }
+                                None => {}
+                            }
+                        }
+                    }
+
                     match state
                         .multi_put(commands_state.drain(..).map(|(k, cv)| {
                             (
@@ -696,6 +846,38 @@ where
     )
 }
 
+/// Returns the time at which `value` expires under `retain_keys`, if ever.
+///
+/// Errors and values without a record timestamp never expire.
+fn expiration_time<T>(value: &UpsertValue, retain_keys: &UpsertRetainKeys) -> Option<T>
+where
+    T: Refines<mz_repr::Timestamp>,
+{
+    let row = value.as_ref().ok()?;
+    let millis = match row.iter().nth(retain_keys.timestamp_idx)? {
+        Datum::Timestamp(ts) => ts.timestamp_millis(),
+        Datum::TimestampTz(ts) => ts.timestamp_millis(),
+        _ => return None,
+    };
+    // Record timestamps from before the epoch have expired since the epoch.
+    let millis = u64::try_from(millis).unwrap_or(0);
+    let ttl = u64::try_from(retain_keys.ttl.as_millis()).unwrap_or(u64::MAX);
+    Some(T::to_inner(mz_repr::Timestamp::from(
+        millis.saturating_add(ttl),
+    )))
+}
+
+/// Like [`expiration_time`], for a value read from the upsert state.
+fn state_value_expiration<T>(value: &StateValue, retain_keys: &UpsertRetainKeys) -> Option<T>
+where
+    T: Refines<mz_repr::Timestamp>,
+{
+    match value {
+        StateValue::Decoded(value) => expiration_time(value, retain_keys),
+        StateValue::Snapshotting(_) => None,
+    }
+}
+
 /// Emit the given error, and stall till the dataflow is restarted.
 async fn process_upsert_state_error<G: Scope>(
     context: String,
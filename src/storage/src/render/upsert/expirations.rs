@@ -0,0 +1,249 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The pending expirations of the keys of an upsert source with `RETAIN KEYS FOR`.
+
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+
+use timely::progress::timestamp::Refines;
+use timely::progress::{Antichain, Timestamp};
+
+use crate::render::upsert::UpsertKey;
+
+/// The maximum number of expirations that are kept in memory if they can be spilled.
+pub const MAX_RESIDENT_EXPIRATIONS: usize = 1 << 16;
+
+/// The size of an expiration in a spill file: a big-endian timestamp followed by the key.
+const SPILLED_EXPIRATION_SIZE: usize = 8 + 32;
+
+/// The pending expirations of the keys of an upsert source, in expiration order.
+///
+/// If a spill directory is given, at most `capacity` of the earliest expirations are kept in
+/// memory, and the later ones are appended to an unlinked file in that directory. They are read
+/// back once the ones in memory have expired, so memory usage doesn't grow with the number of
+/// keys. Without a spill directory, which is the case if the upsert state itself is kept in
+/// memory, all expirations are kept in memory.
+///
+/// Expirations can be stale: a key can have an expiration although its current value expires
+/// later, or never. Users must re-derive the expiration of a key from its current value when the
+/// key expires, and schedule it again if it hasn't expired yet.
+#[derive(Debug)]
+pub struct Expirations<T> {
+    /// The earliest expirations, all of which are no later than the spilled ones.
+    resident: BTreeSet<(T, UpsertKey)>,
+    capacity: usize,
+    spill_directory: Option<PathBuf>,
+    spilled: Option<SpilledExpirations<T>>,
+}
+
+impl<T> Expirations<T>
+where
+    T: Timestamp + Refines<mz_repr::Timestamp>,
+{
+    /// Returns an empty set of expirations that spills to `spill_directory`, if given, once it
+    /// holds more than `capacity` expirations.
+    pub fn new(spill_directory: Option<PathBuf>, capacity: usize) -> Self {
+        Self {
+            resident: BTreeSet::new(),
+            capacity: std::cmp::max(capacity, 2),
+            spill_directory,
+            spilled: None,
+        }
+    }
+
+    /// Records that `key` expires at `time`.
+    pub fn schedule(&mut self, key: UpsertKey, time: T) -> Result<(), anyhow::Error> {
+        let expiration = (time, key);
+        if let Some(spilled) = &mut self.spilled {
+            if expiration >= spilled.min {
+                return spilled.push(&expiration);
+            }
+        }
+        self.resident.insert(expiration);
+        if self.resident.len() > self.capacity {
+            if let Some(spill_directory) = &self.spill_directory {
+                let latest = self.resident.pop_last().expect("not empty");
+                match &mut self.spilled {
+                    Some(spilled) => spilled.push(&latest)?,
+                    None => {
+                        let mut spilled = SpilledExpirations::new(spill_directory, latest.clone())?;
+                        spilled.push(&latest)?;
+                        self.spilled = Some(spilled);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Forgets that `key` expires at `time`, if it is in memory. Spilled expirations are left
+    /// to become stale.
+    pub fn cancel(&mut self, key: UpsertKey, time: T) {
+        self.resident.remove(&(time, key));
+    }
+
+    /// Removes and returns a key that expires before `upper`, if any.
+    pub fn pop_expired(
+        &mut self,
+        upper: &Antichain<T>,
+    ) -> Result<Option<UpsertKey>, anyhow::Error> {
+        if self.resident.is_empty() {
+            match self.spilled.take() {
+                Some(spilled) if !upper.less_equal(&spilled.min.0) => self.unspill(spilled)?,
+                spilled => self.spilled = spilled,
+            }
+        }
+        match self.resident.first() {
+            Some((time, _)) if !upper.less_equal(time) => {
+                let (_, key) = self.resident.pop_first().expect("checked above");
+                Ok(Some(key))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Reads the earliest half of the capacity of expirations back from `spilled`, and spills
+    /// the remaining ones again.
+    fn unspill(&mut self, spilled: SpilledExpirations<T>) -> Result<(), anyhow::Error> {
+        let limit = self.capacity / 2;
+        let mut remaining: Option<SpilledExpirations<T>> = None;
+        let spill_directory = self.spill_directory.as_ref().expect("spilled expirations");
+        for expiration in spilled.into_iter()? {
+            let expiration = expiration?;
+            self.resident.insert(expiration);
+            if self.resident.len() > limit {
+                let latest = self.resident.pop_last().expect("not empty");
+                match &mut remaining {
+                    Some(remaining) => remaining.push(&latest)?,
+                    None => {
+                        let mut spilled = SpilledExpirations::new(spill_directory, latest.clone())?;
+                        spilled.push(&latest)?;
+                        remaining = Some(spilled);
+                    }
+                }
+            }
+        }
+        self.spilled = remaining;
+        Ok(())
+    }
+}
+
+/// Expirations spilled to an unlinked file, in no particular order.
+#[derive(Debug)]
+struct SpilledExpirations<T> {
+    file: BufWriter<File>,
+    /// The earliest spilled expiration.
+    min: (T, UpsertKey),
+}
+
+impl<T> SpilledExpirations<T>
+where
+    T: Timestamp + Refines<mz_repr::Timestamp>,
+{
+    /// Creates an empty spill file in `directory`, whose expirations won't be earlier than `min`.
+    fn new(directory: &PathBuf, min: (T, UpsertKey)) -> Result<Self, anyhow::Error> {
+        std::fs::create_dir_all(directory)?;
+        let file = tempfile::tempfile_in(directory)?;
+        Ok(Self {
+            file: BufWriter::new(file),
+            min,
+        })
+    }
+
+    /// Appends `expiration` to the spill file.
+    fn push(&mut self, expiration: &(T, UpsertKey)) -> Result<(), anyhow::Error> {
+        let (time, UpsertKey(key)) = expiration;
+        let time = u64::from(time.clone().to_outer());
+        self.file.write_all(&time.to_be_bytes())?;
+        self.file.write_all(key)?;
+        if *expiration < self.min {
+            self.min = expiration.clone();
+        }
+        Ok(())
+    }
+
+    /// Returns an iterator over the spilled expirations.
+    fn into_iter(
+        self,
+    ) -> Result<impl Iterator<Item = Result<(T, UpsertKey), anyhow::Error>>, anyhow::Error> {
+        let mut file = self.file.into_inner().map_err(|e| e.into_error())?;
+        file.seek(SeekFrom::Start(0))?;
+        let mut reader = BufReader::new(file);
+        Ok(std::iter::from_fn(move || {
+            let mut buf = [0; SPILLED_EXPIRATION_SIZE];
+            match reader.read_exact(&mut buf) {
+                Ok(()) => {
+                    let (time, key) = buf.split_at(8);
+                    let time = u64::from_be_bytes(time.try_into().expect("8 bytes"));
+                    let time = T::to_inner(mz_repr::Timestamp::from(time));
+                    let key = UpsertKey(key.try_into().expect("32 bytes"));
+                    Some(Ok((time, key)))
+                }
+                Err(e) if e.kind() == ErrorKind::UnexpectedEof => None,
+                Err(e) => Some(Err(e.into())),
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(i: u8) -> UpsertKey {
+        UpsertKey([i; 32])
+    }
+
+    fn pop_all(expirations: &mut Expirations<mz_repr::Timestamp>, upper: u64) -> Vec<UpsertKey> {
+        let upper = Antichain::from_elem(mz_repr::Timestamp::from(upper));
+        let mut keys = vec![];
+        while let Some(key) = expirations.pop_expired(&upper).unwrap() {
+            keys.push(key);
+        }
+        keys
+    }
+
+    #[mz_ore::test]
+    fn test_expirations_in_memory() {
+        let mut expirations = Expirations::new(None, 2);
+        for i in 0..10 {
+            expirations
+                .schedule(key(i), mz_repr::Timestamp::from(u64::from(10 - i)))
+                .unwrap();
+        }
+        expirations.cancel(key(9), mz_repr::Timestamp::from(1));
+        assert_eq!(pop_all(&mut expirations, 4), vec![key(8), key(7)]);
+        assert_eq!(expirations.resident.len(), 7);
+    }
+
+    #[mz_ore::test]
+    fn test_expirations_spilled() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut expirations = Expirations::new(Some(directory.path().to_owned()), 4);
+        for i in (0..20).rev() {
+            expirations
+                .schedule(key(i), mz_repr::Timestamp::from(u64::from(i)))
+                .unwrap();
+            assert!(expirations.resident.len() <= 4);
+        }
+        // Stale duplicates of spilled expirations are returned once.
+        expirations
+            .schedule(key(15), mz_repr::Timestamp::from(15))
+            .unwrap();
+        assert_eq!(pop_all(&mut expirations, 2), vec![key(0), key(1)]);
+        assert_eq!(
+            pop_all(&mut expirations, 20),
+            (2..20).map(key).collect::<Vec<_>>()
+        );
+        assert!(expirations.spilled.is_none());
+    }
+}
@@ -30,12 +30,14 @@ use crate::source::{RawSourceCreationConfig, SourceMessage, SourceReaderError};
 
 mod auction;
 mod counter;
+mod custom;
 mod datums;
 mod marketing;
 mod tpch;
 
 pub use auction::Auction;
 pub use counter::Counter;
+pub use custom::Custom;
 pub use datums::Datums;
 pub use tpch::Tpch;
 
@@ -47,6 +49,10 @@ pub fn as_generator(g: &LoadGenerator, tick_micros: Option<u64>) -> Box<dyn Gene
         LoadGenerator::Counter { max_cardinality } => Box::new(Counter {
             max_cardinality: max_cardinality.clone(),
         }),
+        LoadGenerator::Custom { spec, seed } => Box::new(Custom {
+            spec: spec.clone(),
+            seed: *seed,
+        }),
         LoadGenerator::Datums => Box::new(Datums {}),
         LoadGenerator::Marketing => Box::new(Marketing {}),
         LoadGenerator::Tpch {
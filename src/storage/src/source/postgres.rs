@@ -247,6 +247,8 @@ pub enum TransientError {
     UnexpectedRow,
     #[error("recoverable errors should crash the process during snapshots")]
     SyntheticError,
+    #[error("source table {0} has new columns that are not yet part of its subsource")]
+    PendingSchemaChange(String),
     #[error("sql client error")]
     SQLClient(#[from] tokio_postgres::Error),
     #[error(transparent)]
@@ -325,18 +327,48 @@ async fn fetch_slot_resume_lsn(client: &Client, slot: &str) -> Result<MzOffset,
 }
 
 // Ensures that the table with oid `oid` and expected schema `expected_schema` is still compatible
-// with the current upstream schema `upstream_info`.
+// with the current upstream schema `upstream_info`. If `additive_schema_changes` is set, additive
+// changes to the upstream schema are considered compatible. New upstream columns must however be
+// added to the subsource before any more of its data can be ingested, which happens
+// asynchronously, so they produce a transient error until the source has been altered.
 fn verify_schema(
     oid: u32,
     expected_desc: &PostgresTableDesc,
     upstream_info: &BTreeMap<u32, PostgresTableDesc>,
-) -> Result<(), DefiniteError> {
-    let current_desc = upstream_info.get(&oid).ok_or(DefiniteError::TableDropped)?;
+    additive_schema_changes: bool,
+) -> Result<Result<(), DefiniteError>, TransientError> {
+    let Some(current_desc) = upstream_info.get(&oid) else {
+        return Ok(Err(DefiniteError::TableDropped));
+    };
 
-    match expected_desc.determine_compatibility(current_desc) {
-        Ok(()) => Ok(()),
-        Err(err) => Err(DefiniteError::IncompatibleSchema(err.to_string())),
+    let compatibility = if additive_schema_changes {
+        expected_desc.determine_additive_compatibility(current_desc)
+    } else {
+        expected_desc.determine_compatibility(current_desc)
+    };
+    if let Err(err) = compatibility {
+        return Ok(Err(DefiniteError::IncompatibleSchema(err.to_string())));
     }
+
+    if additive_schema_changes {
+        let new_columns = &current_desc.columns[expected_desc.columns.len()..];
+        let unsupported = new_columns.iter().find(|column| {
+            mz_pgrepr::Type::from_oid_and_typmod(column.type_oid, column.type_mod).is_err()
+        });
+        if let Some(column) = unsupported {
+            return Ok(Err(DefiniteError::IncompatibleSchema(format!(
+                "new column {} of source table {} has unsupported type with OID {}",
+                column.name, current_desc.name, column.type_oid
+            ))));
+        }
+        if !new_columns.is_empty() {
+            return Err(TransientError::PendingSchemaChange(
+                current_desc.name.clone(),
+            ));
+        }
+    }
+
+    Ok(Ok(()))
 }
 
 /// Runs the given query using the client and expects at most a single row to be returned.
@@ -267,6 +267,7 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
                                 &connection_config,
                                 &metrics,
                                 &connection.publication,
+                                connection.additive_schema_changes,
                                 &mut errored
                             ));
 
@@ -329,6 +330,10 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
                 let datum = col.as_deref().map(super::decode_utf8_text).transpose()?;
                 datums.push(datum.unwrap_or(Datum::Null));
             }
+            // Tuples written before columns were added to the upstream table lack them.
+            while datums.len() < casts.len() {
+                datums.push(Datum::Null);
+            }
             super::cast_row(casts, &datums, &mut final_row)?;
             Ok(final_row.clone())
         });
@@ -440,6 +445,7 @@ fn extract_transaction<'a>(
     connection_config: &'a mz_postgres_util::Config,
     metrics: &'a PgSourceMetrics,
     publication: &'a str,
+    additive_schema_changes: bool,
     errored_tables: &'a mut HashSet<u32>,
 ) -> impl AsyncStream<
     Item = Result<(u32, Result<Vec<Option<Bytes>>, DefiniteError>, Diff), TransientError>,
@@ -512,7 +518,12 @@ fn extract_transaction<'a>(
                         .await?;
                         let upstream_info = upstream_info.into_iter().map(|t| (t.oid, t)).collect();
 
-                        if let Err(err) = verify_schema(rel_id, expected_desc, &upstream_info) {
+                        if let Err(err) = verify_schema(
+                            rel_id,
+                            expected_desc,
+                            &upstream_info,
+                            additive_schema_changes,
+                        )? {
                             errored_tables.insert(rel_id);
                             yield (rel_id, Err(err), 1);
                         }
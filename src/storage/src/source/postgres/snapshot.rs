@@ -299,7 +299,12 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
             let upstream_info = upstream_info.into_iter().map(|t| (t.oid, t)).collect();
 
             for (&oid, (_, expected_desc, _)) in reader_snapshot_table_info.iter() {
-                let desc = match verify_schema(oid, expected_desc, &upstream_info) {
+                let desc = match verify_schema(
+                    oid,
+                    expected_desc,
+                    &upstream_info,
+                    connection.additive_schema_changes,
+                )? {
                     Ok(()) => expected_desc,
                     Err(err) => {
                         raw_handle.give(data_cap, ((oid, Err(err)), MzOffset::minimum(), 1)).await;
@@ -124,6 +124,7 @@ pub fn run_script_source(
         envelope,
         metadata_columns: vec![],
         timestamp_interval,
+        event_time: None,
     };
 
     build_and_run_source(desc, timestamp_interval, move |upper, mut read| {
@@ -21,15 +21,16 @@ use mz_compute_client::types::dataflows::{DataflowDesc, IndexImport};
 use mz_expr::visit::Visit;
 use mz_expr::{
     AccessStrategy, CollectionPlan, Id, JoinImplementation, LocalId, MapFilterProject,
-    MirRelationExpr, MirScalarExpr, RECURSION_LIMIT,
+    MirRelationExpr, MirScalarExpr, OptimizerHints, RECURSION_LIMIT,
 };
 use mz_ore::stack::{CheckedRecursion, RecursionGuard, RecursionLimitError};
 use mz_ore::{soft_assert, soft_assert_eq, soft_panic_or_log};
 use mz_repr::explain::IndexUsageType;
 use mz_repr::GlobalId;
 
+use crate::join_implementation::input_collection;
 use crate::monotonic::MonotonicFlag;
-use crate::optimizer_notices::OptimizerNotice;
+use crate::optimizer_notices::{OptimizerNotice, UnsatisfiedHint};
 use crate::{IndexOracle, Optimizer, StatisticsOracle, TransformCtx, TransformError};
 
 /// Optimizes the implementation of each dataflow.
@@ -37,6 +38,9 @@ use crate::{IndexOracle, Optimizer, StatisticsOracle, TransformCtx, TransformErr
 /// Inlines views, performs a full optimization pass including physical
 /// planning using the supplied indexes, propagates filtering and projection
 /// information to dataflow sources and lifts monotonicity information.
+///
+/// The supplied `hints` steer the implementation of joins. Hints that the
+/// optimized dataflow does not honor are reported as optimizer notices.
 #[tracing::instrument(
     target = "optimizer",
     level = "debug",
@@ -47,6 +51,7 @@ pub fn optimize_dataflow(
     dataflow: &mut DataflowDesc,
     indexes: &dyn IndexOracle,
     stats: &dyn StatisticsOracle,
+    hints: &OptimizerHints,
 ) -> Result<DataflowMetainfo, TransformError> {
     let ctx = crate::typecheck::empty_context();
     let mut dataflow_metainfo = DataflowMetainfo::default();
@@ -59,6 +64,7 @@ pub fn optimize_dataflow(
         dataflow,
         indexes,
         stats,
+        hints,
         &Optimizer::logical_optimizer(&ctx),
         &mut dataflow_metainfo,
     )?;
@@ -78,6 +84,7 @@ pub fn optimize_dataflow(
         dataflow,
         indexes,
         stats,
+        hints,
         &Optimizer::logical_cleanup_pass(&ctx, false),
         &mut dataflow_metainfo,
     )?;
@@ -87,6 +94,7 @@ pub fn optimize_dataflow(
         dataflow,
         indexes,
         stats,
+        hints,
         &Optimizer::physical_optimizer(&ctx),
         &mut dataflow_metainfo,
     )?;
@@ -95,6 +103,8 @@ pub fn optimize_dataflow(
 
     prune_and_annotate_dataflow_index_imports(dataflow, indexes, &mut dataflow_metainfo)?;
 
+    report_unsatisfied_hints(dataflow, hints, &mut dataflow_metainfo)?;
+
     mz_repr::explain::trace_plan(dataflow);
 
     Ok(dataflow_metainfo)
@@ -217,6 +227,7 @@ fn optimize_dataflow_relations(
     dataflow: &mut DataflowDesc,
     indexes: &dyn IndexOracle,
     stats: &dyn StatisticsOracle,
+    hints: &OptimizerHints,
     optimizer: &Optimizer,
     dataflow_metainfo: &mut DataflowMetainfo,
 ) -> Result<(), TransformError> {
@@ -231,6 +242,7 @@ fn optimize_dataflow_relations(
             &mut TransformCtx::with_id_and_stats_and_metainfo(
                 indexes,
                 stats,
+                hints,
                 &object.id,
                 dataflow_metainfo,
             ),
@@ -743,6 +755,77 @@ id: {}, key: {:?}",
     Ok(())
 }
 
+/// Reports the optimizer hints that the optimized `dataflow` does not honor as optimizer notices.
+///
+/// A `JOIN ORDER` hint is honored if some differential join starts by joining the listed
+/// collections in the listed order. A `DELTA JOIN` hint is honored if all joins of more than one
+/// input are delta joins. A `NO INDEX` hint is honored if the index is not imported.
+fn report_unsatisfied_hints(
+    dataflow: &DataflowDesc,
+    hints: &OptimizerHints,
+    dataflow_metainfo: &mut DataflowMetainfo,
+) -> Result<(), TransformError> {
+    if hints.is_empty() {
+        return Ok(());
+    }
+
+    let mut join_order_honored = hints.join_order.is_empty();
+    let mut delta_join_honored = true;
+    for build_desc in dataflow.objects_to_build.iter() {
+        build_desc
+            .plan
+            .as_inner()
+            .visit_pre(&mut |expr: &MirRelationExpr| match expr {
+                MirRelationExpr::Join {
+                    inputs,
+                    implementation: JoinImplementation::Differential((start, _, _), order),
+                    ..
+                } => {
+                    if hints.delta_join && inputs.len() > 1 {
+                        delta_join_honored = false;
+                    }
+                    // The collections in the order in which they are joined. An input that is
+                    // joined with itself only counts once.
+                    let collections = std::iter::once(start)
+                        .chain(order.iter().map(|(input, _, _)| input))
+                        .map(|input| input_collection(&inputs[*input]))
+                        .dedup()
+                        .collect::<Vec<_>>();
+                    if collections.len() >= hints.join_order.len()
+                        && hints
+                            .join_order
+                            .iter()
+                            .zip(collections.iter())
+                            .all(|(hinted, collection)| Some(*hinted) == *collection)
+                    {
+                        join_order_honored = true;
+                    }
+                }
+                _ => {}
+            })?;
+    }
+
+    if !join_order_honored {
+        dataflow_metainfo.push_optimizer_notice_dedup(OptimizerNotice::UnsatisfiedHint(
+            UnsatisfiedHint::JoinOrder(hints.join_order.clone()),
+        ));
+    }
+    if !delta_join_honored {
+        dataflow_metainfo.push_optimizer_notice_dedup(OptimizerNotice::UnsatisfiedHint(
+            UnsatisfiedHint::DeltaJoin,
+        ));
+    }
+    for index_id in hints.no_index.iter() {
+        if dataflow.index_imports.contains_key(index_id) {
+            dataflow_metainfo.push_optimizer_notice_dedup(OptimizerNotice::UnsatisfiedHint(
+                UnsatisfiedHint::NoIndex(*index_id),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 /// Pick an index from a given Vec of index keys.
 ///
 /// Currently, we pick as follows:
@@ -38,6 +38,7 @@
 //! Filter.transform(&mut expr, &mut TransformCtx {
 //!   indexes: &mz_transform::EmptyIndexOracle,
 //!   stats: &mz_transform::EmptyStatisticsOracle,
+//!   hints: &Default::default(),
 //!   global_id: None,
 //!   dataflow_metainfo: &mut DataflowMetainfo::default(),
 //! });
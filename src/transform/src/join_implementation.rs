@@ -18,16 +18,18 @@
 
 use std::collections::BTreeMap;
 
+use itertools::Itertools;
 use mz_expr::visit::{Visit, VisitChildren};
 use mz_expr::JoinImplementation::{Differential, IndexedFilter, Unimplemented};
 use mz_expr::{
     FilterCharacteristics, Id, JoinInputCharacteristics, JoinInputMapper, MapFilterProject,
-    MirRelationExpr, MirScalarExpr, RECURSION_LIMIT,
+    MirRelationExpr, MirScalarExpr, OptimizerHints, RECURSION_LIMIT,
 };
 use mz_ore::cast::{CastFrom, CastLossy, TryCastFrom};
 use mz_ore::stack::{CheckedRecursion, RecursionGuard};
+use mz_repr::GlobalId;
 
-use crate::attribute::cardinality::{FactorizerVariable, SymExp};
+use crate::attribute::cardinality::{Factorizer, FactorizerVariable, SymExp, WorstCaseFactorizer};
 use crate::attribute::{Cardinality, RequiredAttributes};
 use crate::join_implementation::index_map::IndexMap;
 use crate::predicate_pushdown::PredicatePushdown;
@@ -67,7 +69,12 @@ impl crate::Transform for JoinImplementation {
         relation: &mut MirRelationExpr,
         ctx: &mut TransformCtx,
     ) -> Result<(), TransformError> {
-        let result = self.action_recursive(relation, &mut IndexMap::new(ctx.indexes), ctx.stats);
+        let result = self.action_recursive(
+            relation,
+            &mut IndexMap::new(ctx.indexes),
+            ctx.stats,
+            ctx.hints,
+        );
         mz_repr::explain::trace_plan(&*relation);
         result
     }
@@ -83,10 +90,11 @@ impl JoinImplementation {
         relation: &mut MirRelationExpr,
         indexes: &mut IndexMap,
         stats: &dyn StatisticsOracle,
+        hints: &OptimizerHints,
     ) -> Result<(), TransformError> {
         self.checked_recur(|_| {
             if let MirRelationExpr::Let { id, value, body } = relation {
-                self.action_recursive(value, indexes, stats)?;
+                self.action_recursive(value, indexes, stats, hints)?;
                 match &**value {
                     MirRelationExpr::ArrangeBy { keys, .. } => {
                         for key in keys {
@@ -101,26 +109,32 @@ impl JoinImplementation {
                     }
                     _ => {}
                 }
-                self.action_recursive(body, indexes, stats)?;
+                self.action_recursive(body, indexes, stats, hints)?;
                 indexes.remove_local(*id);
                 Ok(())
             } else {
                 let (mfp, mfp_input) =
                     MapFilterProject::extract_non_errors_from_expr_ref_mut(relation);
-                mfp_input.try_visit_mut_children(|e| self.action_recursive(e, indexes, stats))?;
-                self.action(mfp_input, mfp, indexes, stats)?;
+                mfp_input
+                    .try_visit_mut_children(|e| self.action_recursive(e, indexes, stats, hints))?;
+                self.action(mfp_input, mfp, indexes, stats, hints)?;
                 Ok(())
             }
         })
     }
 
     /// Determines the join implementation for join operators.
+    ///
+    /// A `JOIN ORDER` hint fixes the start of the order of the joins that read the hinted
+    /// collections, and a `DELTA JOIN` hint plans all joins of more than two inputs as delta
+    /// joins, creating arrangements as needed.
     pub fn action(
         &self,
         relation: &mut MirRelationExpr,
         mfp_above: MapFilterProject,
         indexes: &IndexMap,
         stats: &dyn StatisticsOracle,
+        hints: &OptimizerHints,
     ) -> Result<(), TransformError> {
         if let MirRelationExpr::Join {
             inputs,
@@ -401,6 +415,22 @@ impl JoinImplementation {
                 });
             }
 
+            // The inputs that a `JOIN ORDER` hint asks us to join first, in order.
+            let input_collections = inputs.iter().map(input_collection).collect::<Vec<_>>();
+            let hinted_prefix = hints
+                .join_order
+                .iter()
+                .flat_map(|id| {
+                    input_collections
+                        .iter()
+                        .positions(move |collection| collection == &Some(*id))
+                })
+                .unique()
+                .collect::<Vec<_>>();
+            // Only a differential join has a single order that can honor a `JOIN ORDER` hint, so
+            // we consider a delta join for such a join only if we are also asked to.
+            let consider_delta_query = hinted_prefix.is_empty() || hints.delta_join;
+
             let old_implementation = implementation.clone();
 
             let delta_query_plan = || {
@@ -411,6 +441,7 @@ impl JoinImplementation {
                     &unique_keys,
                     &cardinalities,
                     &filters,
+                    hints.delta_join,
                 )
             };
             let differential_plan = || {
@@ -421,20 +452,27 @@ impl JoinImplementation {
                     &unique_keys,
                     &cardinalities,
                     &filters,
+                    stats.cost_based_join_ordering(),
+                    &hinted_prefix,
                 )
             };
 
             match old_implementation {
                 Unimplemented => {
-                    *relation = delta_query_plan()
-                        .or_else(|_| differential_plan())
-                        .expect("Failed to produce a join plan")
+                    *relation = if consider_delta_query {
+                        delta_query_plan().or_else(|_| differential_plan())
+                    } else {
+                        differential_plan()
+                    }
+                    .expect("Failed to produce a join plan")
                 }
                 Differential(..) => {
                     // As noted above, we don't want to change from Differential to an other
                     // Differential, so we only consider Delta joins here.
-                    if let Ok(delta_query_plan) = delta_query_plan() {
-                        *relation = delta_query_plan;
+                    if consider_delta_query {
+                        if let Ok(delta_query_plan) = delta_query_plan() {
+                            *relation = delta_query_plan;
+                        }
                     }
                 }
                 _ => unreachable!(), // because of the match statement that is one level up
@@ -504,7 +542,8 @@ mod delta_queries {
     /// Creates a delta query plan, and any predicates that need to be lifted.
     ///
     /// The method returns `Err` if it fails to find a sufficiently pleasing plan or
-    /// if any errors occur during planning.
+    /// if any errors occur during planning. If `force` is set, a plan is pleasing even
+    /// if it needs to create new arrangements.
     pub fn plan(
         join: &MirRelationExpr,
         input_mapper: &JoinInputMapper,
@@ -512,6 +551,7 @@ mod delta_queries {
         unique_keys: &[Vec<Vec<usize>>],
         cardinalities: &[Option<usize>],
         filters: &[FilterCharacteristics],
+        force: bool,
     ) -> Result<MirRelationExpr, TransformError> {
         let mut new_join = join.clone();
 
@@ -557,9 +597,10 @@ mod delta_queries {
             // A viable delta query requires that, for every order,
             // there is an arrangement for every input except for
             // the starting one.
-            if !orders
-                .iter()
-                .all(|o| o.iter().skip(1).all(|(c, _, _)| c.arranged))
+            if !force
+                && !orders
+                    .iter()
+                    .all(|o| o.iter().skip(1).all(|(c, _, _)| c.arranged))
             {
                 return Err(TransformError::Internal(String::from(
                     "delta plan not viable",
@@ -604,11 +645,19 @@ mod delta_queries {
 mod differential {
     use mz_expr::{JoinImplementation, JoinInputMapper, MirRelationExpr, MirScalarExpr};
     use mz_ore::soft_assert;
+    use ordered_float::OrderedFloat;
 
     use crate::join_implementation::{FilterCharacteristics, JoinInputCharacteristics};
     use crate::TransformError;
 
     /// Creates a linear differential plan, and any predicates that need to be lifted.
+    ///
+    /// If `cost_based` is set and there are cardinality estimates for all inputs, the order is
+    /// chosen among those with the lowest estimated cost (see
+    /// [`super::estimate_differential_cost`]).
+    ///
+    /// If `prefix` is not empty, the order starts with the inputs in `prefix`, in that order.
+    #[allow(clippy::too_many_arguments)]
     pub fn plan(
         join: &MirRelationExpr,
         input_mapper: &JoinInputMapper,
@@ -616,6 +665,8 @@ mod differential {
         unique_keys: &[Vec<Vec<usize>>],
         cardinalities: &[Option<usize>],
         filters: &[FilterCharacteristics],
+        cost_based: bool,
+        prefix: &[usize],
     ) -> Result<MirRelationExpr, TransformError> {
         let mut new_join = join.clone();
 
@@ -631,14 +682,27 @@ mod differential {
             // Important, we should choose something stable under re-ordering, to converge under fixed
             // point iteration; we choose to start with the first input optimizing our criteria, which
             // should remain stable even when promoted to the first position.
-            let mut orders = super::optimize_orders(
-                equivalences,
-                available,
-                unique_keys,
-                cardinalities,
-                filters,
-                input_mapper,
-            );
+            // If the start of the order is prescribed, there is only one order to choose.
+            let mut orders = if prefix.is_empty() {
+                super::optimize_orders(
+                    equivalences,
+                    available,
+                    unique_keys,
+                    cardinalities,
+                    filters,
+                    input_mapper,
+                )
+            } else {
+                let mut orderer = super::Orderer::new(
+                    equivalences,
+                    available,
+                    unique_keys,
+                    cardinalities,
+                    filters,
+                    input_mapper,
+                );
+                vec![orderer.optimize_order_with_prefix(prefix)]
+            };
 
             // Inside each order, we take the `FilterCharacteristics` from each element, and OR it
             // to every other element to the right. This is because we are gonna be looking for the
@@ -653,6 +717,33 @@ mod differential {
                 }
             });
 
+            // If asked to, we only keep the orders with the lowest estimated cost, which we can
+            // only determine if we have estimates for all inputs. The orders with the same cost
+            // are then ranked by their `Characteristics`, as usual.
+            if cost_based {
+                let costs = orders
+                    .iter()
+                    .map(|order| {
+                        super::estimate_differential_cost(
+                            order,
+                            equivalences,
+                            unique_keys,
+                            cardinalities,
+                            input_mapper,
+                        )
+                        .map(OrderedFloat)
+                    })
+                    .collect::<Option<Vec<_>>>();
+                if let Some(min_cost) = costs.as_ref().and_then(|costs| costs.iter().min()) {
+                    orders = orders
+                        .into_iter()
+                        .zip(costs.iter().flatten())
+                        .filter(|(_, cost)| *cost == min_cost)
+                        .map(|(order, _)| order)
+                        .collect();
+                }
+            }
+
             // `orders` has one order for each starting collection, and now we have to choose one
             // from these. First, we find the worst `Characteristics` inside each order, and then we
             // find the best one among these across all orders, which goes into
@@ -730,6 +821,111 @@ mod differential {
     }
 }
 
+/// Returns the collection that a join input reads, if the input is a read of a global collection,
+/// possibly behind an `ArrangeBy`, an `IndexedFilter`, or an mfp.
+pub(crate) fn input_collection(input: &MirRelationExpr) -> Option<GlobalId> {
+    let (_, mut input) = MapFilterProject::extract_non_errors_from_expr(input);
+    if let MirRelationExpr::ArrangeBy { input: inner, .. } = input {
+        (_, input) = MapFilterProject::extract_non_errors_from_expr(inner);
+    }
+    match input {
+        MirRelationExpr::Get {
+            id: Id::Global(id), ..
+        } => Some(*id),
+        MirRelationExpr::Join {
+            implementation: IndexedFilter(id, ..),
+            ..
+        } => Some(*id),
+        _ => None,
+    }
+}
+
+/// Estimates the cost of a differential join that follows `order`, or returns `None` if we lack
+/// a cardinality estimate for one of its inputs.
+///
+/// The cost is the number of records that the join needs to arrange: the records of each input
+/// that is not already arranged by its key, and the records of each intermediate result. The size
+/// of an intermediate result is the [`Cardinality`] estimate of the join of the inputs that it
+/// covers, given the estimates of these inputs.
+fn estimate_differential_cost(
+    order: &[(JoinInputCharacteristics, Vec<MirScalarExpr>, usize)],
+    equivalences: &[Vec<MirScalarExpr>],
+    unique_keys: &[Vec<Vec<usize>>],
+    cardinalities: &[Option<usize>],
+    input_mapper: &JoinInputMapper,
+) -> Option<f64> {
+    let mut cost = 0.0;
+    let mut prefix = Vec::with_capacity(order.len());
+    for (position, (characteristics, _, input)) in order.iter().enumerate() {
+        let cardinality = f64::cast_lossy(cardinalities[*input]?);
+        if !characteristics.arranged {
+            cost += cardinality;
+        }
+        prefix.push(*input);
+        // The first input is arranged as an input, and the output of the last join is not
+        // arranged at all.
+        if position > 0 && position + 1 < order.len() {
+            cost += estimate_join_cardinality(
+                &prefix,
+                equivalences,
+                unique_keys,
+                cardinalities,
+                input_mapper,
+            )?;
+        }
+    }
+    Some(cost)
+}
+
+/// Estimates the cardinality of the join of `inputs` on the parts of `equivalences` that refer to
+/// these inputs only, using the same model as the [`Cardinality`] attribute, or returns `None` if
+/// we lack a cardinality estimate for one of them.
+fn estimate_join_cardinality(
+    inputs: &[usize],
+    equivalences: &[Vec<MirScalarExpr>],
+    unique_keys: &[Vec<Vec<usize>>],
+    cardinalities: &[Option<usize>],
+    input_mapper: &JoinInputMapper,
+) -> Option<f64> {
+    let mut estimates = Vec::with_capacity(inputs.len());
+    let mut unique_columns = BTreeMap::new();
+    for (index, input) in inputs.iter().enumerate() {
+        estimates.push(SymExp::f64(f64::cast_lossy(cardinalities[*input]?)));
+        for key in &unique_keys[*input] {
+            if let [column] = key.as_slice() {
+                unique_columns.insert(input_mapper.map_column_to_global(*column, *input), index);
+            }
+        }
+    }
+    let equivalences = equivalences
+        .iter()
+        .map(|equivalence| {
+            equivalence
+                .iter()
+                .filter(|expr| {
+                    input_mapper
+                        .lookup_inputs(expr)
+                        .all(|i| inputs.contains(&i))
+                })
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+        .filter(|equivalence| equivalence.len() > 1)
+        .collect::<Vec<_>>();
+    let factorizer = WorstCaseFactorizer {
+        cardinalities: BTreeMap::new(),
+    };
+    let estimate = factorizer.join(
+        &equivalences,
+        &Unimplemented,
+        unique_columns,
+        estimates.iter().collect(),
+    );
+    // The estimates of all inputs are constants, so the estimate should be one too. If it isn't,
+    // we don't know the cost.
+    estimate.try_evaluate(&|_| None)
+}
+
 /// Modify `inputs` to ensure specified arrangements are available.
 ///
 /// Lift filter predicates when all needed arrangements are otherwise available.
@@ -993,6 +1189,17 @@ impl<'a> Orderer<'a> {
         &mut self,
         start: usize,
     ) -> Vec<(JoinInputCharacteristics, Vec<MirScalarExpr>, usize)> {
+        self.optimize_order_with_prefix(&[start])
+    }
+
+    /// Like `optimize_order_for`, but the order starts with the inputs in `prefix`, in the given
+    /// order, each joined by the best key available at its position. The remaining inputs follow
+    /// in decreasing priority.
+    fn optimize_order_with_prefix(
+        &mut self,
+        prefix: &[usize],
+    ) -> Vec<(JoinInputCharacteristics, Vec<MirScalarExpr>, usize)> {
+        let start = prefix[0];
         self.order.clear();
         self.priority_queue.clear();
         for input in 0..self.inputs {
@@ -1045,6 +1252,21 @@ impl<'a> Orderer<'a> {
         // Main loop, ordering all the inputs.
         if self.inputs > 1 {
             self.order_input(start);
+            for &input in &prefix[1..] {
+                // Every input has at least its cross join candidate in the queue. We take the
+                // best candidate for `input`, and put the other candidates back.
+                let (candidates, others): (Vec<_>, Vec<_>) =
+                    std::mem::take(&mut self.priority_queue)
+                        .into_iter()
+                        .partition(|(_, _, candidate)| *candidate == input);
+                self.priority_queue = others.into();
+                let best = candidates
+                    .into_iter()
+                    .max()
+                    .expect("cross join candidate for every input");
+                self.order.push(best);
+                self.order_input(input);
+            }
             while self.order.len() < self.inputs - 1 {
                 let (characteristics, key, input) = self.priority_queue.pop().unwrap();
                 // put the tuple into `self.order` unless the tuple with the same
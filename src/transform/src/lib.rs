@@ -89,12 +89,13 @@
 #![warn(missing_docs)]
 #![warn(missing_debug_implementations)]
 
+use std::collections::BTreeSet;
 use std::error::Error;
 use std::rc::Rc;
 use std::{fmt, iter};
 
 use mz_expr::visit::Visit;
-use mz_expr::{MirRelationExpr, MirScalarExpr};
+use mz_expr::{MirRelationExpr, MirScalarExpr, OptimizerHints};
 use mz_ore::id_gen::IdGen;
 use mz_ore::stack::RecursionLimitError;
 use mz_repr::GlobalId;
@@ -152,6 +153,13 @@ macro_rules! any {
     ($($x:expr,)+) => ( $($x)||+ )
 }
 
+/// The hints of a query that does not give any.
+static NO_HINTS: OptimizerHints = OptimizerHints {
+    join_order: Vec::new(),
+    delta_join: false,
+    no_index: BTreeSet::new(),
+};
+
 /// Arguments that get threaded through all transforms, plus a `DataflowMetainfo` that can be
 /// manipulated by the transforms.
 #[derive(Debug)]
@@ -160,6 +168,8 @@ pub struct TransformCtx<'a> {
     pub indexes: &'a dyn IndexOracle,
     /// Statistical estimates.
     pub stats: &'a dyn StatisticsOracle,
+    /// The optimizer hints given by the user.
+    pub hints: &'a OptimizerHints,
     /// The global ID for this query (if it exists).
     pub global_id: Option<&'a GlobalId>,
     /// Transforms can use this field to communicate information outside the result plans.
@@ -172,22 +182,25 @@ impl<'a> TransformCtx<'a> {
         Self {
             indexes: &EmptyIndexOracle,
             stats: &EmptyStatisticsOracle,
+            hints: &NO_HINTS,
             global_id: None,
             dataflow_metainfo,
         }
     }
 
-    /// Generates a `TransformArgs` instance for the given `IndexOracle` and `StatisticsOracle` with
-    /// a `GlobalId`
+    /// Generates a `TransformArgs` instance for the given `IndexOracle`, `StatisticsOracle` and
+    /// `OptimizerHints` with a `GlobalId`
     pub fn with_id_and_stats_and_metainfo(
         indexes: &'a dyn IndexOracle,
         stats: &'a dyn StatisticsOracle,
+        hints: &'a OptimizerHints,
         global_id: &'a GlobalId,
         dataflow_metainfo: &'a mut DataflowMetainfo,
     ) -> Self {
         Self {
             indexes,
             stats,
+            hints,
             global_id: Some(global_id),
             dataflow_metainfo,
         }
@@ -278,6 +291,15 @@ pub trait StatisticsOracle: fmt::Debug + Send {
     ///
     /// Returning `None` means "no estimate"; returning `Some(0)` means estimating that the shard backing `id` is empty
     fn cardinality_estimate(&self, id: GlobalId) -> Option<usize>;
+
+    /// Returns whether join orders should be chosen by the estimated cost of
+    /// their intermediate results, rather than by the characteristics of their
+    /// inputs alone.
+    ///
+    /// The cost model only applies to joins for whose inputs we have estimates.
+    fn cost_based_join_ordering(&self) -> bool {
+        false
+    }
 }
 
 /// A [`StatisticsOracle`] that knows nothing and can give no estimates.
@@ -35,6 +35,8 @@ pub enum OptimizerNotice {
     ///    input arrangement exists can't happen with a cross join.
     /// Also note that skew is hard to debug, so it's good to avoid this problem in the first place.
     IndexKeyEmpty,
+    /// An optimizer hint given in the `OPTIONS` of a query could not be honored.
+    UnsatisfiedHint(UnsatisfiedHint),
 }
 
 impl OptimizerNotice {
@@ -128,7 +130,35 @@ impl OptimizerNotice {
                 (
                     "Empty index key. The index will be completely skewed to one worker thread, which can lead to performance problems.".to_string(),
                     Some("CREATE DEFAULT INDEX is almost always better than an index with an empty key. (Except for cross joins with big inputs, which are better to avoid anyway.)".to_string()),
+                ),
+            OptimizerNotice::UnsatisfiedHint(UnsatisfiedHint::JoinOrder(ids)) => {
+                let names = separated(
+                    ", ",
+                    ids.iter().map(|id| {
+                        humanizer
+                            .humanize_id_unqualified(*id)
+                            .unwrap_or_else(|| id.to_string())
+                    }),
+                );
+                (
+                    format!("The JOIN ORDER ({names}) hint could not be honored."),
+                    Some("JOIN ORDER only applies to a join that directly reads all of the listed relations.".to_string()),
+                )
+            }
+            OptimizerNotice::UnsatisfiedHint(UnsatisfiedHint::DeltaJoin) =>
+                (
+                    "The DELTA JOIN hint could not be honored for all joins.".to_string(),
+                    Some("Joins of two inputs are always planned as differential joins.".to_string()),
+                ),
+            OptimizerNotice::UnsatisfiedHint(UnsatisfiedHint::NoIndex(index_id)) => {
+                let index_name = humanizer
+                    .humanize_id(*index_id)
+                    .unwrap_or_else(|| index_id.to_string());
+                (
+                    format!("The NO INDEX {index_name} hint could not be honored."),
+                    Some("The index is the only way to read the relation it is on in this cluster.".to_string()),
                 )
+            }
         }
     }
 
@@ -164,6 +194,13 @@ impl OptimizerNotice {
                 },
             ) => humanizer.id_exists(*index_id) && humanizer.id_exists(*index_on_id),
             OptimizerNotice::IndexKeyEmpty => true,
+            OptimizerNotice::UnsatisfiedHint(UnsatisfiedHint::JoinOrder(ids)) => {
+                ids.iter().all(|id| humanizer.id_exists(*id))
+            }
+            OptimizerNotice::UnsatisfiedHint(UnsatisfiedHint::DeltaJoin) => true,
+            OptimizerNotice::UnsatisfiedHint(UnsatisfiedHint::NoIndex(index_id)) => {
+                humanizer.id_exists(*index_id)
+            }
         }
     }
 }
@@ -187,3 +224,14 @@ pub struct IndexTooWideForLiteralConstraints {
     /// columns than `usable_subset`.
     pub recommended_key: Vec<MirScalarExpr>,
 }
+
+/// An optimizer hint that the optimized plan does not honor.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UnsatisfiedHint {
+    /// No join starts by joining the listed collections, in the listed order.
+    JoinOrder(Vec<GlobalId>),
+    /// Some join was not planned as a delta join.
+    DeltaJoin,
+    /// The index is still used by the plan.
+    NoIndex(GlobalId),
+}
@@ -65,6 +65,7 @@
 //! PredicatePushdown::default().transform(&mut expr, &mut TransformCtx {
 //!   indexes: &mz_transform::EmptyIndexOracle,
 //!   stats: &mz_transform::EmptyStatisticsOracle,
+//!   hints: &Default::default(),
 //!   global_id: None,
 //!   dataflow_metainfo: &mut DataflowMetainfo::default(),
 //! });
@@ -52,6 +52,32 @@ impl<V> SymbolicExpression<V> {
         }
     }
 
+    /// Evaluates a symbolic expression, given a way to `concretize` its symbolic parts, or returns
+    /// `None` if some symbolic part can't be concretized
+    pub fn try_evaluate<F>(&self, concretize: &F) -> Option<f64>
+    where
+        F: Fn(&V) -> Option<f64>,
+    {
+        use SymbolicExpression::*;
+        match self {
+            Constant(OrderedFloat(n)) => Some(*n),
+            Symbolic(v, n) => Some(f64::powi(
+                concretize(v)?,
+                i32::try_from(*n).expect("symbolic exponent overflow"),
+            )),
+            Sum(ss) => ss.into_iter().map(|s| s.try_evaluate(concretize)).sum(),
+            Product(ps) => ps.into_iter().map(|p| p.try_evaluate(concretize)).product(),
+            Max(e1, e2) => Some(f64::max(
+                e1.try_evaluate(concretize)?,
+                e2.try_evaluate(concretize)?,
+            )),
+            Min(e1, e2) => Some(f64::min(
+                e1.try_evaluate(concretize)?,
+                e2.try_evaluate(concretize)?,
+            )),
+        }
+    }
+
     /// Computes the order of a symbolic expression
     ///
     /// ```
@@ -221,6 +221,7 @@ fn apply_transform<T: mz_transform::Transform>(
     let mut empty_args: mz_transform::TransformCtx = mz_transform::TransformCtx {
         indexes: &mz_transform::EmptyIndexOracle,
         stats: &mz_transform::EmptyStatisticsOracle,
+        hints: &Default::default(),
         global_id: None,
         dataflow_metainfo: &mut dataflow_metainfo,
     };
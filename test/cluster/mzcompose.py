@@ -72,6 +72,9 @@ def workflow_default(c: Composition, parser: WorkflowArgumentParser) -> None:
         "test-remote-storage",
         "test-drop-default-cluster",
         "test-upsert",
+        "test-upsert-retain-keys",
+        "test-load-generator-custom",
+        "test-event-time",
         "test-resource-limits",
         "test-invalid-compute-reuse",
         "pg-snapshot-resumption",
@@ -85,6 +88,7 @@ def workflow_default(c: Composition, parser: WorkflowArgumentParser) -> None:
         "test-query-without-default-cluster",
         "test-clusterd-death-detection",
         "test-replica-metrics",
+        "test-arrangement-spilling",
         "test-compute-controller-metrics",
         "test-metrics-retention-across-restart",
     ]:
@@ -1264,6 +1268,50 @@ def workflow_test_upsert(c: Composition) -> None:
         c.run("testdrive", "upsert/02-after-clusterd-restart.td")
 
 
+def workflow_test_load_generator_custom(c: Composition) -> None:
+    """Test that custom load generators produce the same output for the same seed
+    across restarts."""
+    with c.override(
+        Testdrive(default_timeout="30s", no_reset=True, consistent_seed=True),
+    ):
+        c.down(destroy_volumes=True)
+        c.up("materialized")
+
+        c.run("testdrive", "load-generator-custom/01-create-sources.td")
+        c.kill("materialized")
+        c.up("materialized")
+        c.run("testdrive", "load-generator-custom/02-after-restart.td")
+
+
+def workflow_test_upsert_retain_keys(c: Composition) -> None:
+    """Test that upsert sources keep expiring keys after a restart."""
+    with c.override(
+        Testdrive(default_timeout="30s", no_reset=True, consistent_seed=True),
+    ):
+        c.down(destroy_volumes=True)
+        c.up("materialized", "zookeeper", "kafka", "schema-registry")
+
+        c.run("testdrive", "upsert-retain-keys/01-create-sources.td")
+        c.kill("materialized")
+        c.up("materialized")
+        c.run("testdrive", "upsert-retain-keys/02-after-restart.td")
+
+
+def workflow_test_event_time(c: Composition) -> None:
+    """Test that event-time sources neither lose nor duplicate records held back by the
+    allowed lateness across a restart."""
+    with c.override(
+        Testdrive(default_timeout="30s", no_reset=True, consistent_seed=True),
+    ):
+        c.down(destroy_volumes=True)
+        c.up("materialized", "zookeeper", "kafka", "schema-registry")
+
+        c.run("testdrive", "event-time/01-create-sources.td")
+        c.kill("materialized")
+        c.up("materialized")
+        c.run("testdrive", "event-time/02-after-restart.td")
+
+
 def workflow_test_remote_storage(c: Composition) -> None:
     """Test creating sources in a remote clusterd process."""
 
@@ -2175,6 +2223,89 @@ def workflow_test_replica_metrics(c: Composition) -> None:
     assert maintenance > 0, f"unexpected arrangement maintanence time: {maintenance}"
 
 
+def workflow_test_arrangement_spilling(c: Composition) -> None:
+    """Test spilling of large merged arrangement batches to the scratch directory."""
+
+    c.down(destroy_volumes=True)
+    with c.override(
+        Clusterd(name="clusterd1", options=["--scratch-directory=/scratch"]),
+    ):
+        c.up("materialized")
+        c.up("clusterd1")
+
+        def fetch_metrics() -> Metrics:
+            resp = c.exec(
+                "clusterd1", "curl", "localhost:6878/metrics", capture=True
+            ).stdout
+            return Metrics(resp)
+
+        c.sql(
+            """
+            ALTER SYSTEM SET enable_unmanaged_cluster_replicas = true;
+            ALTER SYSTEM SET enable_arrangement_spilling = true;
+            ALTER SYSTEM SET arrangement_spill_threshold_bytes = 1048576;
+            """,
+            port=6877,
+            user="mz_system",
+        )
+
+        c.sql(
+            """
+            CREATE CLUSTER cluster1 REPLICAS (replica1 (
+                STORAGECTL ADDRESSES ['clusterd1:2100'],
+                STORAGE ADDRESSES ['clusterd1:2103'],
+                COMPUTECTL ADDRESSES ['clusterd1:2101'],
+                COMPUTE ADDRESSES ['clusterd1:2102'],
+                WORKERS 1
+            ));
+
+            CREATE TABLE t (a int, b text);
+            CREATE INDEX idx IN CLUSTER cluster1 ON t (a);
+            """
+        )
+
+        # Insert in several rounds, so the index's spine has batches to merge.
+        # Each round adds more than 1 MiB of row data.
+        for i in range(10):
+            c.sql(
+                f"""
+                INSERT INTO t
+                SELECT x, repeat('x', 100) || x
+                FROM generate_series({i * 10000 + 1}, {(i + 1) * 10000}) AS x;
+                """
+            )
+
+        # Spine merges happen in the background, so wait for a spilled batch.
+        spilled = 0.0
+        for _ in range(60):
+            spilled = fetch_metrics().get_value("mz_arrangement_spilled_bytes")
+            if spilled > 0:
+                break
+            time.sleep(1)
+        assert spilled > 0, "no arrangement batches were spilled"
+
+        fallbacks = fetch_metrics().get_value("mz_arrangement_spill_fallbacks")
+        assert fallbacks == 0, f"unexpected spill fallbacks: {fallbacks}"
+
+        # Reading the spilled batches must return the data that was inserted.
+        cursor = c.sql_cursor()
+        cursor.execute("SET cluster = cluster1")
+        cursor.execute("SELECT count(*), sum(a), sum(length(b)) FROM t")
+        count, sum_a, sum_length = cursor.fetchone()
+        assert count == 100000, f"unexpected count: {count}"
+        assert sum_a == 5000050000, f"unexpected sum: {sum_a}"
+        assert sum_length == 10488895, f"unexpected length: {sum_length}"
+
+        # Dropping the index releases its spilled batches.
+        c.sql("DROP INDEX idx")
+        for _ in range(60):
+            spilled = fetch_metrics().get_value("mz_arrangement_spilled_bytes")
+            if spilled == 0:
+                break
+            time.sleep(1)
+        assert spilled == 0, f"spilled batches not released: {spilled} bytes"
+
+
 def workflow_test_compute_controller_metrics(c: Composition) -> None:
     """Test metrics exposed by the compute controller."""
 